@@ -1,5 +1,8 @@
 // src/adsr.rs
 use std::sync::Arc;
+use atomic_float::AtomicF32;
+use serde::{Deserialize, Serialize};
+use crate::channels::ChannelMatrix;
 
 /// ADSR Envelope phases
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -12,7 +15,7 @@ pub enum ADSRPhase {
 }
 
 /// ADSR Envelope parameters
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct ADSREnvelope {
     pub attack: f32,    // 0.0 - 2.0 seconds
     pub decay: f32,     // 0.0 - 2.0 seconds
@@ -149,6 +152,30 @@ impl EnvelopeState {
     pub fn is_done(&self) -> bool { self.phase == ADSRPhase::Done }
 }
 
+/// Which side of the crossfader a voice is on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bus {
+    /// Not on the crossfader (previews, recorded takes).
+    Thru,
+    /// Chops: main grid, chop rows, piano notes and pads.
+    Chop,
+    /// Whole-sample drum track hits.
+    Drum,
+}
+
+impl Bus {
+    /// Gain with the crossfader at `x` (0 = chops only, 1 = drums only). Both buses stay at
+    /// full level up to the middle; the far side fades out over the other half.
+    pub fn gain(&self, x: f32) -> f32 {
+        let fade = |d: f32| if d <= 0.5 { 1.0 } else { ((d - 0.5) * std::f32::consts::PI).cos() };
+        match self {
+            Bus::Thru => 1.0,
+            Bus::Chop => fade(x.clamp(0.0, 1.0)),
+            Bus::Drum => fade(1.0 - x.clamp(0.0, 1.0)),
+        }
+    }
+}
+
 /// Voice with PCM data and envelope
 #[derive(Clone)]
 pub struct Voice {
@@ -161,6 +188,18 @@ pub struct Voice {
     pub envelope: EnvelopeState,
     pub adsr_enabled: bool,
     pub end_frame: Option<usize>,
+    /// Seconds of silence before the voice starts (off-grid hits).
+    pub delay: f64,
+    /// Linear track gain.
+    pub level: f32,
+    /// Source → output channel conversion; naive per-channel copy when unset.
+    pub matrix: Option<Arc<ChannelMatrix>>,
+    /// Sample rate of `pcm`; playback steps through it at `source_rate / output rate`
+    /// (on top of `speed`) so pitch and length hold on any device. 0 = output rate.
+    pub source_rate: u32,
+    pub bus: Bus,
+    /// Peak this voice reached, raised by the mixer for the owning track's row meter.
+    pub meter: Option<Arc<AtomicF32>>,
     }
 
 
@@ -173,6 +212,7 @@ impl Voice {
         speed: f32,
         adsr: ADSREnvelope,
         adsr_enabled: bool,
+        source_rate: u32,
     ) -> Self {
         Self {
             pcm,
@@ -184,6 +224,12 @@ impl Voice {
             envelope: EnvelopeState::new(),
             adsr_enabled,
             end_frame: None,  // ✅ ADD THIS
+            delay: 0.0,
+            level: 1.0,
+            matrix: None,
+            source_rate,
+            bus: Bus::Thru,
+            meter: None,
         }
     }
     // ... rest of impl
@@ -194,6 +240,10 @@ impl Voice {
     
     /// Render one sample frame, returns gain-adjusted sample
     pub fn render(&mut self, sample_rate: f32, out_channels: usize) -> Option<Vec<f32>> {
+            if self.delay > 0.0 {
+                self.delay -= 1.0 / sample_rate as f64;
+                return Some(vec![0.0; out_channels]);
+            }
             if self.adsr_enabled && self.envelope.is_done() {
                 return None;
             }
@@ -227,15 +277,27 @@ impl Voice {
             };
             
             let mut samples = Vec::with_capacity(out_channels);
-            for oc in 0..out_channels {
-                let sc = oc.min(self.channels - 1);
-                let s0 = self.pcm.get(i0 * self.channels + sc).copied().unwrap_or(0.0);
-                let s1 = self.pcm.get(i1 * self.channels + sc).copied().unwrap_or(0.0);
-                let smp = (s0 + t * (s1 - s0)) * gain;
-                samples.push(smp);
+            match self.matrix.as_deref().filter(|m| m.from == self.channels && m.to == out_channels) {
+                Some(m) => {
+                    let frame: Vec<f32> = (0..self.channels).map(|sc| {
+                        let s0 = self.pcm.get(i0 * self.channels + sc).copied().unwrap_or(0.0);
+                        let s1 = self.pcm.get(i1 * self.channels + sc).copied().unwrap_or(0.0);
+                        (s0 + t * (s1 - s0)) * gain * self.level
+                    }).collect();
+                    samples.resize(out_channels, 0.0);
+                    m.apply(&frame, &mut samples);
+                }
+                None => for oc in 0..out_channels {
+                    let sc = oc.min(self.channels - 1);
+                    let s0 = self.pcm.get(i0 * self.channels + sc).copied().unwrap_or(0.0);
+                    let s1 = self.pcm.get(i1 * self.channels + sc).copied().unwrap_or(0.0);
+                    let smp = (s0 + t * (s1 - s0)) * gain * self.level;
+                    samples.push(smp);
+                },
             }
             
-            self.frame_pos += self.speed as f64;
+            let rate = if self.source_rate > 0 { self.source_rate as f64 / sample_rate as f64 } else { 1.0 };
+            self.frame_pos += self.speed as f64 * rate;
             Some(samples)  // ✅ Always return samples when not finished
         }
     pub fn is_finished(&self) -> bool {
@@ -248,4 +310,27 @@ impl Voice {
             self.frame_pos >= effective_end as f64
         }
     }
-}
\ No newline at end of file
+}
+
+/// Add every voice into `data` (interleaved, `out_channels` wide), dropping the ones that finish.
+/// `crossfader` is the chop/drum crossfader position (see `Bus::gain`).
+pub fn mix_voices(voices: &mut Vec<Voice>, data: &mut [f32], sample_rate: f32, out_channels: usize, crossfader: f32) {
+    let out_frames = data.len() / out_channels.max(1);
+    voices.retain_mut(|voice| {
+        let mut alive = false;
+        let mut peak  = 0.0f32;
+        let bus_gain  = voice.bus.gain(crossfader);
+        for f in 0..out_frames {
+            if let Some(samples) = voice.render(sample_rate, out_channels) {
+                alive = true;
+                for (oc, smp) in samples.iter().enumerate() {
+                    let oi = f * out_channels + oc;
+                    if oi < data.len() { data[oi] += smp * bus_gain; }
+                    peak = peak.max((smp * bus_gain).abs());
+                }
+            }
+        }
+        if let Some(m) = &voice.meter { m.fetch_max(peak, std::sync::atomic::Ordering::Relaxed); }
+        alive
+    });
+}
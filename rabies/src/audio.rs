@@ -7,7 +7,7 @@ use symphonia::core::{
     codecs::{DecoderOptions, CODEC_TYPE_NULL},
     formats::FormatOptions,
     io::MediaSourceStream,
-    meta::MetadataOptions,
+    meta::{MetadataOptions, StandardTagKey, Tag},
     probe::Hint,
 };
 
@@ -19,6 +19,63 @@ pub struct AudioAsset {
     pub frames: u64,
     pub file_name: String,
     pub sample_uuid: uuid::Uuid,  // ✅ Every loaded asset carries its own UUID
+    pub tags: SampleTags,
+}
+
+/// Tags read from ID3 / Vorbis comments / RIFF INFO at load time.
+#[derive(Debug, Clone, Default)]
+pub struct SampleTags {
+    pub title:  Option<String>,
+    pub artist: Option<String>,
+    pub bpm:    Option<f32>,
+    pub key:    Option<String>,
+}
+
+impl SampleTags {
+    /// Fill any still-empty field from `tags` (first source read wins).
+    fn absorb(&mut self, tags: &[Tag]) {
+        for tag in tags {
+            let value = tag.value.to_string().trim().to_string();
+            if value.is_empty() { continue; }
+            match tag.std_key {
+                Some(StandardTagKey::TrackTitle) => { self.title.get_or_insert(value); }
+                Some(StandardTagKey::Artist)     => { self.artist.get_or_insert(value); }
+                Some(StandardTagKey::Bpm) if self.bpm.is_none() => {
+                    self.bpm = value.parse::<f32>().ok().filter(|b| *b > 0.0);
+                }
+                // No standard key for musical key: ID3 TKEY, Vorbis KEY / INITIALKEY
+                _ if matches!(tag.key.to_ascii_uppercase().as_str(), "TKEY" | "KEY" | "INITIALKEY" | "INITIAL KEY") => {
+                    self.key.get_or_insert(value);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.artist.is_none() && self.bpm.is_none() && self.key.is_none()
+    }
+
+    /// "Title — Artist · 92 BPM · Am"
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        match (&self.title, &self.artist) {
+            (Some(t), Some(a)) => parts.push(format!("{} — {}", t, a)),
+            (Some(t), None)    => parts.push(t.clone()),
+            (None, Some(a))    => parts.push(a.clone()),
+            (None, None)       => {}
+        }
+        if let Some(b) = self.bpm { parts.push(format!("{:.0} BPM", b)); }
+        if let Some(k) = &self.key { parts.push(k.clone()); }
+        parts.join(" · ")
+    }
+
+    /// Case-insensitive match against any tag; `needle` must already be lowercase.
+    pub fn matches(&self, needle: &str) -> bool {
+        [&self.title, &self.artist, &self.key].iter()
+            .any(|f| f.as_ref().is_some_and(|v| v.to_lowercase().contains(needle)))
+            || self.bpm.is_some_and(|b| format!("{:.0}", b).contains(needle))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -65,6 +122,15 @@ impl AudioManager {
     }
 
     pub fn load_audio(&self, path: &str) -> Result<Arc<AudioAsset>, Box<dyn std::error::Error>> {
+        self.decode(path, None)
+    }
+
+    /// Just the first `secs` seconds of `path`, to audition it before a full load.
+    pub fn load_audio_head(&self, path: &str, secs: f32) -> Result<Arc<AudioAsset>, Box<dyn std::error::Error>> {
+        self.decode(path, Some(secs))
+    }
+
+    fn decode(&self, path: &str, max_secs: Option<f32>) -> Result<Arc<AudioAsset>, Box<dyn std::error::Error>> {
         // NOTE: We intentionally do NOT return cached assets here.
         // Returning a cached asset would mean two tracks loaded from the
         // same file share a UUID → they'd share chop markers. Instead we
@@ -82,27 +148,36 @@ impl AudioManager {
             hint.with_extension(ext);
         }
 
-        let probed = symphonia::default::get_probe().format(
+        let mut probed = symphonia::default::get_probe().format(
             &hint,
             mss,
             &FormatOptions::default(),
             &MetadataOptions::default(),
         )?;
 
+        // Container-level tags (ID3v2 ahead of the stream) first, then in-stream ones
+        let mut tags = SampleTags::default();
+        if let Some(rev) = probed.metadata.get().as_ref().and_then(|m| m.current()) {
+            tags.absorb(rev.tags());
+        }
         let mut format = probed.format;
+        if let Some(rev) = format.metadata().skip_to_latest() {
+            tags.absorb(rev.tags());
+        }
 
         let track = format
             .tracks()
             .iter()
             .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
-            .ok_or("no valid audio track found")?;
+            .ok_or(t!("no valid audio track found"))?;
         let track_id = track.id;
-        let sample_rate = track.codec_params.sample_rate.ok_or("unknown sample rate")?;
+        let sample_rate = track.codec_params.sample_rate.ok_or(t!("unknown sample rate"))?;
         let channels = track
             .codec_params
             .channels
-            .ok_or("unknown channels")?
+            .ok_or(t!("unknown channels"))?
             .count() as u16;
+        let limit = max_secs.map(|s| (s.max(0.0) * sample_rate as f32) as u64);
 
         let mut decoder =
             symphonia::default::get_codecs().make(&track.codec_params, &DecoderOptions::default())?;
@@ -111,6 +186,7 @@ impl AudioManager {
         let mut frames: u64 = 0;
 
         loop {
+            if limit.is_some_and(|n| frames >= n) { break; }
             let packet = match format.next_packet() {
                 Ok(p) => p,
                 Err(_) => break,
@@ -216,7 +292,11 @@ impl AudioManager {
         }
 
         if pcm.is_empty() {
-            return Err("no audio samples decoded".into());
+            return Err(t!("no audio samples decoded").into());
+        }
+        if let Some(n) = limit.filter(|&n| frames > n) {
+            pcm.truncate(n as usize * channels as usize);
+            frames = n;
         }
 
         // ✅ Fresh UUID every time — even for the same file path.
@@ -232,6 +312,7 @@ impl AudioManager {
                 .unwrap_or("unknown")
                 .to_string(),
             sample_uuid: uuid::Uuid::new_v4(),
+            tags,
         });
 
         println!("Loaded: {} (uuid={})", path, asset.sample_uuid);
@@ -0,0 +1,242 @@
+// src/autochop.rs
+// Auto-chop: find the hits in a drum track by onset detection and offer them as
+// candidate slices. Nothing becomes a chop until the candidates have been auditioned
+// in the preview dialog and the wanted ones ticked, so the pads don't fill with junk.
+//
+// Two presets: the generic transient detector, and a drum-stem mode that follows the
+// envelope and picks its peaks no closer together than one sequencer step.
+
+use std::sync::Arc;
+use crate::adsr::Voice;
+use crate::audio::AudioAsset;
+use crate::gui::AppState;
+
+/// Analysis hop.
+const HOP_SECS: f32 = 0.005;
+/// Hops averaged for the level a new hit has to jump above.
+const HISTORY_HOPS: usize = 8;
+/// Candidates closer than this to the previous one, or to an existing marker, are dropped.
+const MIN_GAP_SECS: f32 = 0.05;
+/// Most candidates offered at once (the strongest are kept).
+pub const MAX_CANDIDATES: usize = 64;
+/// Peak buckets in each candidate's thumbnail.
+pub const THUMB_BUCKETS: usize = 24;
+/// Drum-stem envelope follower: resolution, attack and release.
+const ENV_HOP_SECS:     f32 = 0.001;
+const ENV_ATTACK_SECS:  f32 = 0.001;
+const ENV_RELEASE_SECS: f32 = 0.060;
+
+/// Which detector finds the candidates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AutoChopMode {
+    /// Level jumps; suits loops and mixed material.
+    Transients,
+    /// Envelope peaks at least a grid step apart; suits isolated drum stems.
+    DrumStem,
+}
+
+impl AutoChopMode {
+    pub const ALL: [AutoChopMode; 2] = [AutoChopMode::Transients, AutoChopMode::DrumStem];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AutoChopMode::Transients => t!("Transients"),
+            AutoChopMode::DrumStem   => t!("Drum stem"),
+        }
+    }
+}
+
+/// Candidates from one auto-chop run, waiting in the preview dialog.
+pub struct AutoChopDraft {
+    pub track_idx:   usize,
+    pub asset:       Arc<AudioAsset>,
+    pub mode:        AutoChopMode,
+    /// 0-1; higher finds quieter hits.
+    pub sensitivity: f32,
+    /// One sequencer step at the current BPM, the drum-stem mode's minimum spacing.
+    pub step_frames: usize,
+    /// Candidate start frames, ascending.
+    pub onsets:      Vec<usize>,
+    pub keep:        Vec<bool>,
+    /// Peak per bucket of each candidate's slice, 0-1.
+    pub thumbs:      Vec<[f32; THUMB_BUCKETS]>,
+    /// Existing marker positions (frames), which new candidates keep clear of.
+    existing:        Vec<usize>,
+}
+
+impl AutoChopDraft {
+    /// Candidate `i` plays up to the next candidate, or to the end of the sample.
+    pub fn slice(&self, i: usize) -> (usize, usize) {
+        let start = self.onsets[i];
+        let end   = self.onsets.get(i + 1).copied().unwrap_or(self.asset.frames as usize);
+        (start, end.max(start))
+    }
+
+    /// Run detection again at the current sensitivity; every candidate starts ticked.
+    pub fn detect(&mut self) {
+        let ch  = (self.asset.channels as usize).max(1);
+        let gap = (MIN_GAP_SECS * self.asset.sample_rate as f32) as usize;
+        let mut onsets = match self.mode {
+            AutoChopMode::Transients => detect_onsets(&self.asset.pcm, ch, self.asset.sample_rate, self.sensitivity),
+            AutoChopMode::DrumStem   => detect_drum_hits(&self.asset.pcm, ch, self.asset.sample_rate, self.sensitivity, self.step_frames),
+        };
+        onsets.retain(|&f| self.existing.iter().all(|&m| m.abs_diff(f) >= gap));
+        self.onsets = onsets;
+        self.keep   = vec![true; self.onsets.len()];
+        self.thumbs = (0..self.onsets.len()).map(|i| {
+            let (s, e) = self.slice(i);
+            thumbnail(&self.asset.pcm[s * ch..e * ch], ch)
+        }).collect();
+    }
+}
+
+/// Onset frames of interleaved `pcm`: a hop whose level jumps well above the last few
+/// hops starts a hit. Higher `sensitivity` (0-1) lowers both the jump and the floor.
+pub fn detect_onsets(pcm: &[f32], channels: usize, sample_rate: u32, sensitivity: f32) -> Vec<usize> {
+    let ch  = channels.max(1);
+    let hop = ((HOP_SECS * sample_rate as f32) as usize).max(1);
+    let levels: Vec<f32> = pcm.chunks(hop * ch).map(|block| {
+        (block.iter().map(|x| x * x).sum::<f32>() / block.len().max(1) as f32).sqrt()
+    }).collect();
+    let peak = levels.iter().fold(0.0f32, |m, &l| m.max(l));
+    if peak <= 1e-6 { return Vec::new(); }
+
+    let s      = sensitivity.clamp(0.0, 1.0);
+    let floor  = peak * (0.02 + 0.25 * (1.0 - s));
+    let jump   = 1.5 + 3.0 * (1.0 - s);
+    let gap    = ((MIN_GAP_SECS * sample_rate as f32) as usize / hop).max(1);
+
+    let mut hits: Vec<(usize, f32)> = Vec::new();
+    for (i, &level) in levels.iter().enumerate() {
+        if level < floor { continue; }
+        let from = i.saturating_sub(HISTORY_HOPS);
+        let avg  = levels[from..i].iter().sum::<f32>() / (i - from).max(1) as f32;
+        if level <= avg * jump { continue; }
+        if hits.last().is_some_and(|&(h, _)| i - h < gap) { continue; }
+        hits.push((i, level));
+    }
+    if hits.len() > MAX_CANDIDATES {
+        hits.sort_by(|a, b| b.1.total_cmp(&a.1));
+        hits.truncate(MAX_CANDIDATES);
+        hits.sort_by_key(|&(i, _)| i);
+    }
+    hits.into_iter().map(|(i, _)| i * hop).collect()
+}
+
+/// Hit frames of a drum stem: peaks of an envelope follower, strongest first, none
+/// within `spacing` frames of a stronger one. Each hit starts where its rise begins.
+pub fn detect_drum_hits(pcm: &[f32], channels: usize, sample_rate: u32, sensitivity: f32, spacing: usize) -> Vec<usize> {
+    let ch  = channels.max(1);
+    let hop = ((ENV_HOP_SECS * sample_rate as f32) as usize).max(1);
+    let hop_secs = hop as f32 / sample_rate.max(1) as f32;
+    let attack   = (-hop_secs / ENV_ATTACK_SECS).exp();
+    let release  = (-hop_secs / ENV_RELEASE_SECS).exp();
+    let mut level = 0.0f32;
+    let env: Vec<f32> = pcm.chunks(hop * ch).map(|block| {
+        let x = block.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        let coeff = if x > level { attack } else { release };
+        level = x + (level - x) * coeff;
+        level
+    }).collect();
+    let peak = env.iter().fold(0.0f32, |m, &l| m.max(l));
+    if peak <= 1e-6 { return Vec::new(); }
+
+    let floor   = peak * (0.05 + 0.45 * (1.0 - sensitivity.clamp(0.0, 1.0)));
+    let spacing = (spacing / hop).max(1);
+    let mut peaks: Vec<usize> = (0..env.len()).filter(|&i| {
+        env[i] >= floor
+            && (i == 0 || env[i] >= env[i - 1])
+            && env.get(i + 1).is_none_or(|&next| env[i] > next)
+    }).collect();
+    peaks.sort_by(|&a, &b| env[b].total_cmp(&env[a]));
+
+    let mut picked: Vec<usize> = Vec::new();
+    for i in peaks {
+        if picked.len() == MAX_CANDIDATES { break; }
+        if picked.iter().all(|&p| p.abs_diff(i) >= spacing) { picked.push(i); }
+    }
+    picked.sort_unstable();
+    // Walk back from each peak to the foot of its rise
+    picked.iter().map(|&i| {
+        let mut j = i;
+        while j > 0 && env[j - 1] < env[j] && i - j < spacing / 2 { j -= 1; }
+        j * hop
+    }).collect()
+}
+
+fn thumbnail(pcm: &[f32], channels: usize) -> [f32; THUMB_BUCKETS] {
+    let mut out = [0.0; THUMB_BUCKETS];
+    let frames  = pcm.len() / channels.max(1);
+    if frames == 0 { return out; }
+    for (f, frame) in pcm.chunks(channels.max(1)).enumerate() {
+        let b = f * THUMB_BUCKETS / frames;
+        out[b] = frame.iter().fold(out[b], |m, x| m.max(x.abs()));
+    }
+    out
+}
+
+impl AppState {
+    /// Analyse drum track `track_idx` and open the preview dialog with its candidates.
+    pub fn start_auto_chop(&self, track_idx: usize) {
+        let Some((asset, uuid)) = self.drum_tracks.read().get(track_idx).map(|t| (t.asset.clone(), t.sample_uuid)) else { return; };
+        let frames   = asset.frames as f32;
+        let step_secs = self.seq_resolution.read().step_secs(self.events.bpm());
+        let step_frames = (step_secs * asset.sample_rate as f64) as usize;
+        let existing = self.samples_manager.get_marks_for_sample(&uuid).iter()
+            .map(|m| (m.position * frames) as usize)
+            .collect();
+        let mut draft = AutoChopDraft {
+            track_idx,
+            asset,
+            mode: AutoChopMode::Transients,
+            sensitivity: 0.5,
+            step_frames,
+            onsets: Vec::new(),
+            keep:   Vec::new(),
+            thumbs: Vec::new(),
+            existing,
+        };
+        draft.detect();
+        if draft.onsets.is_empty() {
+            *self.status.write() = t!("✗ No new hits found in {}", draft.asset.file_name);
+        }
+        *self.pending_autochop.write() = Some(draft);
+    }
+
+    /// Audition candidate `i` on its own.
+    pub fn preview_auto_chop(&self, i: usize) {
+        let voice = {
+            let guard = self.pending_autochop.read();
+            let Some(d) = guard.as_ref().filter(|d| i < d.onsets.len()) else { return; };
+            let (s, e) = d.slice(i);
+            let mut v = Voice::new(Arc::new(d.asset.pcm.clone()), d.asset.channels as usize, s, 1.0, Default::default(), false, d.asset.sample_rate);
+            v.end_frame = Some(e);
+            v
+        };
+        self.play_voices(vec![voice]);
+    }
+
+    /// Turn the ticked candidates into markers on the track; the rest are forgotten.
+    pub fn commit_auto_chop(&self) {
+        let Some(d) = self.pending_autochop.write().take() else { return; };
+        let Some((file_name, uuid)) = self.drum_tracks.read().get(d.track_idx)
+            .filter(|t| Arc::ptr_eq(&t.asset, &d.asset))
+            .map(|t| (t.asset.file_name.clone(), t.sample_uuid))
+        else {
+            *self.status.write() = t!("✗ The track changed before the chops were added").into();
+            return;
+        };
+        self.push_undo(t!("auto-chop"));
+        let frames = d.asset.frames.max(1) as f32;
+        let mut added = 0;
+        for (&onset, _) in d.onsets.iter().zip(&d.keep).filter(|(_, &k)| k) {
+            self.samples_manager.mark_current_position(uuid, &file_name, onset as f32 / frames);
+            added += 1;
+        }
+        *self.status.write() = t!("✓ Added {} chop{} to {}", added, if added == 1 { "" } else { "s" }, file_name);
+    }
+
+    pub fn cancel_auto_chop(&self) {
+        *self.pending_autochop.write() = None;
+    }
+}
@@ -0,0 +1,245 @@
+// src/backend.rs
+// Output backends: cpal's default host everywhere, plus a native JACK client on Linux
+// (PipeWire serves the same client through its JACK server).
+
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use parking_lot::RwLock;
+use atomic_float::AtomicF32;
+use cpal::traits::{DeviceTrait, StreamTrait};
+use cpal::{FromSample, SizedSample};
+use crate::devices::{self, OutputDevice};
+
+/// Fills one interleaved f32 buffer per callback.
+pub type RenderFn = Box<dyn FnMut(&mut [f32]) + Send>;
+pub type ErrorFn  = Box<dyn FnMut(String) + Send>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BackendKind { Default, Jack }
+
+impl BackendKind {
+    pub const ALL: [BackendKind; 2] = [BackendKind::Default, BackendKind::Jack];
+
+    pub fn label(&self) -> &'static str {
+        match self { Self::Default => t!("System"), Self::Jack => t!("JACK / PipeWire") }
+    }
+
+    /// Whether this build can open the backend at all.
+    pub fn compiled_in(&self) -> bool {
+        match self {
+            Self::Default => true,
+            Self::Jack    => cfg!(all(target_os = "linux", feature = "jack")),
+        }
+    }
+}
+
+/// What the caller would like; backends that own the clock (JACK) may ignore it.
+pub struct OutputRequest {
+    /// Short role name, used for JACK client naming ("main" keeps the plain name).
+    pub role:          &'static str,
+    /// cpal device to open; `None` for the host default.
+    pub device:        Option<OutputDevice>,
+    pub sample_rate:   Option<u32>,
+    pub buffer_frames: Option<u32>,
+    pub on_error:      ErrorFn,
+    /// Kept up to date by the backend: seconds from a callback to its first frame being
+    /// heard, as the device reports it.
+    pub latency:       Arc<AtomicF32>,
+}
+
+/// What the backend actually opened.
+#[derive(Clone, Copy, Debug)]
+pub struct OutputSpec {
+    pub channels:    usize,
+    pub sample_rate: u32,
+}
+
+/// Keeps an output running; dropping it closes the stream / JACK client.
+pub struct OutputStream { _inner: Box<dyn std::any::Any> }
+
+/// Host transport as seen by the app.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TransportInfo {
+    pub rolling: bool,
+    pub bpm:     Option<f32>,
+}
+
+pub trait AudioBackend: Send + Sync {
+    fn kind(&self) -> BackendKind;
+
+    /// Open an output. `build` gets the negotiated spec and returns the render callback.
+    fn open_output(
+        &self, req: OutputRequest, build: &mut dyn FnMut(OutputSpec) -> RenderFn,
+    ) -> Result<(OutputStream, OutputSpec), String>;
+
+    /// Shared transport state, for backends that have one.
+    fn transport(&self) -> Option<TransportInfo> { None }
+}
+
+pub fn create(kind: BackendKind, client_name: &str) -> Result<Box<dyn AudioBackend>, String> {
+    match kind {
+        BackendKind::Default => Ok(Box::new(CpalBackend)),
+        #[cfg(all(target_os = "linux", feature = "jack"))]
+        BackendKind::Jack => Ok(Box::new(jack_backend::JackBackend::new(client_name))),
+        #[cfg(not(all(target_os = "linux", feature = "jack")))]
+        BackendKind::Jack => {
+            let _ = client_name;
+            Err(t!("This build has no JACK support (rebuild with --features jack)").to_string())
+        }
+    }
+}
+
+/// Engine boundary: a panic inside `render` is caught and the stream goes silent for good,
+/// with the reason left in `fault` (and `status`) until the engine is restarted.
+pub fn guarded(mut render: RenderFn, fault: Arc<RwLock<Option<String>>>, status: Arc<RwLock<String>>) -> RenderFn {
+    let mut dead = false;
+    Box::new(move |data: &mut [f32]| {
+        if !dead {
+            if let Err(payload) = std::panic::catch_unwind(AssertUnwindSafe(|| render(data))) {
+                let msg = payload.downcast_ref::<&str>().map(|s| s.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| t!("unknown panic").to_string());
+                *status.write() = t!("✗ Audio engine crashed: {} — press ⟳ Restart engine", msg);
+                *fault.write() = Some(msg);
+                dead = true;
+            }
+        }
+        if dead { data.fill(0.0); }
+    })
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+//  cpal (ALSA / CoreAudio / WASAPI)
+// ═══════════════════════════════════════════════════════════════════════════════
+
+pub struct CpalBackend;
+
+impl AudioBackend for CpalBackend {
+    fn kind(&self) -> BackendKind { BackendKind::Default }
+
+    fn open_output(
+        &self, req: OutputRequest, build: &mut dyn FnMut(OutputSpec) -> RenderFn,
+    ) -> Result<(OutputStream, OutputSpec), String> {
+        let device = devices::open_output_device(req.device.as_ref())?;
+        let config = device.default_output_config().map_err(|e| t!("Audio config error: {}", e))?;
+
+        let mut cfg: cpal::StreamConfig = config.clone().into();
+        if let Some(n)  = req.buffer_frames { cfg.buffer_size = cpal::BufferSize::Fixed(n); }
+        if let Some(sr) = req.sample_rate   { cfg.sample_rate = cpal::SampleRate(sr); }
+        let spec   = OutputSpec { channels: cfg.channels as usize, sample_rate: cfg.sample_rate.0 };
+        let render = build(spec);
+
+        let stream = match config.sample_format() {
+            cpal::SampleFormat::F32 => build_cpal::<f32>(&device, &cfg, render, req.on_error, req.latency),
+            cpal::SampleFormat::I16 => build_cpal::<i16>(&device, &cfg, render, req.on_error, req.latency),
+            cpal::SampleFormat::U16 => build_cpal::<u16>(&device, &cfg, render, req.on_error, req.latency),
+            _ => return Err(t!("Unsupported sample format").to_string()),
+        }.map_err(|e| t!("Stream error ({}): {}", req.role, e))?;
+        stream.play().map_err(|e| t!("Playback error: {}", e))?;
+        Ok((OutputStream { _inner: Box::new(stream) }, spec))
+    }
+}
+
+fn build_cpal<T: cpal::Sample + SizedSample + FromSample<f32> + 'static>(
+    device: &cpal::Device, cfg: &cpal::StreamConfig, mut render: RenderFn, mut on_error: ErrorFn, latency: Arc<AtomicF32>,
+) -> Result<cpal::Stream, cpal::BuildStreamError> {
+    let mut scratch: Vec<f32> = Vec::new();
+    device.build_output_stream(cfg, move |data: &mut [T], info: &cpal::OutputCallbackInfo| {
+        let ts = info.timestamp();
+        if let Some(d) = ts.playback.duration_since(&ts.callback) { latency.store(d.as_secs_f32(), Ordering::Relaxed); }
+        scratch.resize(data.len(), 0.0);
+        render(&mut scratch);
+        for (d, s) in data.iter_mut().zip(&scratch) { *d = T::from_sample(*s); }
+    }, move |err| on_error(err.to_string()), None)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+//  JACK (and PipeWire via pipewire-jack)
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(all(target_os = "linux", feature = "jack"))]
+mod jack_backend {
+    use super::*;
+    use parking_lot::Mutex;
+
+    const CHANNELS: usize = 2;
+
+    pub struct JackBackend {
+        client_name: String,
+        /// Portless client that stays connected while outputs come and go, for transport queries.
+        control:     Mutex<Option<jack::Client>>,
+    }
+
+    impl JackBackend {
+        pub fn new(client_name: &str) -> Self {
+            Self { client_name: client_name.to_string(), control: Mutex::new(None) }
+        }
+    }
+
+    impl AudioBackend for JackBackend {
+        fn kind(&self) -> BackendKind { BackendKind::Jack }
+
+        fn open_output(
+            &self, req: OutputRequest, build: &mut dyn FnMut(OutputSpec) -> RenderFn,
+        ) -> Result<(OutputStream, OutputSpec), String> {
+            let name = if req.role == "main" { self.client_name.clone() }
+                else { format!("{} {}", self.client_name, req.role) };
+            let (client, _) = jack::Client::new(&name, jack::ClientOptions::NO_START_SERVER)
+                .map_err(|e| t!("JACK: can't connect as '{}' ({})", name, e))?;
+
+            let mut ports = Vec::with_capacity(CHANNELS);
+            for c in 0..CHANNELS {
+                ports.push(client.register_port(&format!("out_{}", c + 1), jack::AudioOut)
+                    .map_err(|e| t!("JACK: port registration failed ({})", e))?);
+            }
+            let port_names: Vec<String> = ports.iter().filter_map(|p| p.name().ok()).collect();
+            let spec = OutputSpec { channels: CHANNELS, sample_rate: client.sample_rate() as u32 };
+            let mut render   = build(spec);
+            let mut scratch  = vec![0.0f32; client.buffer_size() as usize * CHANNELS];
+            let mut on_error = req.on_error;
+            let latency      = req.latency;
+            let rate         = client.sample_rate() as f32;
+
+            let process = jack::ClosureProcessHandler::new(move |_: &jack::Client, ps: &jack::ProcessScope| {
+                // The ports' playback latency: the cycle's frames reach the speakers that much later
+                let (_, max) = ports[0].get_latency_range(jack::LatencyType::Playback);
+                latency.store(max as f32 / rate, Ordering::Relaxed);
+                let n = ps.n_frames() as usize;
+                scratch.resize(n * CHANNELS, 0.0);
+                render(&mut scratch);
+                for (c, port) in ports.iter_mut().enumerate() {
+                    for (i, s) in port.as_mut_slice(ps).iter_mut().enumerate() {
+                        *s = scratch[i * CHANNELS + c];
+                    }
+                }
+                jack::Control::Continue
+            });
+            let active = client.activate_async((), process)
+                .map_err(|e| t!("JACK: activation failed ({})", e))?;
+
+            // Land on the first physical outputs; routing can be changed in any patchbay
+            let system = active.as_client().ports(None, Some("audio"),
+                jack::PortFlags::IS_INPUT | jack::PortFlags::IS_PHYSICAL);
+            for (src, dst) in port_names.iter().zip(&system) {
+                if let Err(e) = active.as_client().connect_ports_by_name(src, dst) {
+                    on_error(t!("JACK: couldn't connect {} → {} ({})", src, dst, e));
+                }
+            }
+            Ok((OutputStream { _inner: Box::new(active) }, spec))
+        }
+
+        fn transport(&self) -> Option<TransportInfo> {
+            let mut control = self.control.lock();
+            if control.is_none() {
+                let name = format!("{} transport", self.client_name);
+                *control = jack::Client::new(&name, jack::ClientOptions::NO_START_SERVER).ok().map(|(c, _)| c);
+            }
+            let state = control.as_ref()?.transport().query().ok()?;
+            Some(TransportInfo {
+                rolling: state.state != jack::TransportState::Stopped,
+                bpm:     state.pos.bbt().map(|b| b.bpm as f32),
+            })
+        }
+    }
+}
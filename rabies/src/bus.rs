@@ -0,0 +1,199 @@
+// src/bus.rs
+// One door into the app and one window out of it. Front ends — the window, MIDI input,
+// footswitches, grid controllers, the HTTP server, and later scripts and tests — say
+// what they want as an `AppCommand` and hand it to `AppState::dispatch` rather than
+// writing its fields, so each command is carried out the same way whoever sent it.
+// Going the other way, the sequencer and editor publish `EngineEvent`s; anything that
+// wants to follow along subscribes and drains its own queue instead of polling state.
+//
+// The transport — whether the sequencer is playing, and the tempo — lives on the bus
+// itself: it changes only by emitting the `Started`, `Stopped` or `Bpm` event that says
+// so, so no follower can miss a change, and front ends read it back from here. Tracks,
+// patterns and the rest of the edit state stay on `AppState` and are changed through
+// commands.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use atomic_float::AtomicF32;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use crate::grid_controller::Lane;
+use crate::gui::AppState;
+
+/// Something a front end asks the app to do. In JSON: `{"command": "set_bpm", "bpm": 96}`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum AppCommand {
+    /// Start the pattern, after a bar's count-in when that's on.
+    Play,
+    /// Start the pattern this instant, e.g. to follow another device's transport.
+    Start,
+    Stop,
+    TogglePlay,
+    SetBpm { bpm: f32 },
+    /// Play pad `pad` as if it were hit, recording it if Rec is on.
+    TriggerPad { pad: usize, velocity: f32 },
+    /// Turn a step on at `velocity`, or off with `None`.
+    SetStep { lane: Lane, step: usize, velocity: Option<f32> },
+    /// Edit and play another pattern (on the next quantize line while playing).
+    SwitchPattern { index: usize },
+    OpenProject { path: PathBuf },
+    SetRecord { on: bool },
+    /// Take back the last edit, or do the last undone one again.
+    Undo,
+    Redo,
+}
+
+/// Something that happened, for whoever is listening.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum EngineEvent {
+    Started,
+    Stopped,
+    /// The sequencer played `step`.
+    Step { step: usize },
+    Bpm { bpm: f32 },
+    /// Pattern `index` is now the one edited and played.
+    Pattern { index: usize },
+    StepSet { lane: Lane, step: usize, on: bool },
+    Pad { pad: usize, velocity: f32 },
+}
+
+/// Fans events out to every subscriber; one that has gone away is dropped on the next event.
+/// Holds the transport the events describe.
+#[derive(Clone)]
+pub struct EventBus {
+    subscribers: Arc<Mutex<Vec<Sender<EngineEvent>>>>,
+    playing:     Arc<AtomicBool>,
+    /// Shared with the audio engine, which reads it every block.
+    bpm:         Arc<AtomicF32>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self {
+            subscribers: Default::default(),
+            playing:     Arc::new(AtomicBool::new(false)),
+            bpm:         Arc::new(AtomicF32::new(120.0)),
+        }
+    }
+}
+
+impl EventBus {
+    /// The sequencer is playing.
+    pub fn playing(&self) -> bool { self.playing.load(Ordering::Relaxed) }
+
+    pub fn bpm(&self) -> f32 { self.bpm.load(Ordering::Relaxed) }
+
+    /// The tempo, for the engine to follow without going through the bus.
+    pub(crate) fn bpm_source(&self) -> Arc<AtomicF32> { self.bpm.clone() }
+
+    /// A queue of every event from now on.
+    pub fn subscribe(&self) -> Receiver<EngineEvent> {
+        let (tx, rx) = channel();
+        self.subscribers.lock().push(tx);
+        rx
+    }
+
+    /// Apply `event` to the transport and pass it on; a tempo that didn't change isn't news.
+    pub fn emit(&self, event: EngineEvent) {
+        match event {
+            EngineEvent::Started => self.playing.store(true, Ordering::Relaxed),
+            EngineEvent::Stopped => self.playing.store(false, Ordering::Relaxed),
+            // Stores the new tempo either way
+            EngineEvent::Bpm { bpm } if self.bpm.swap(bpm, Ordering::Relaxed) == bpm => return,
+            _ => {}
+        }
+        let mut subs = self.subscribers.lock();
+        if subs.is_empty() { return; }
+        subs.retain(|tx| tx.send(event.clone()).is_ok());
+    }
+}
+
+impl AppState {
+    /// Carry out `cmd`, or say why it couldn't be.
+    pub fn dispatch(&self, cmd: AppCommand) -> Result<(), String> {
+        match cmd {
+            AppCommand::Play => {
+                if !self.seq_running() { self.play_sequencer(); }
+            }
+            AppCommand::Start => {
+                if !self.events.playing() { self.start_sequencer(); }
+            }
+            AppCommand::Stop => {
+                if self.seq_running() { self.stop_sequencer(); }
+            }
+            AppCommand::TogglePlay => {
+                if self.seq_running() { self.stop_sequencer(); } else { self.play_sequencer(); }
+            }
+            AppCommand::SetBpm { bpm } => {
+                if !bpm.is_finite() { return Err(t!("BPM must be a number").to_string()); }
+                let bpm = bpm.clamp(40.0, 300.0);
+                if bpm != self.events.bpm() { self.push_tempo_undo(); }
+                self.events.emit(EngineEvent::Bpm { bpm });
+            }
+            AppCommand::TriggerPad { pad, velocity } => {
+                if !self.trigger_pad_velocity(pad, velocity.clamp(0.0, 1.0)) {
+                    return Err(t!("Pad {} has nothing to play", pad + 1));
+                }
+            }
+            AppCommand::SetStep { lane, step, velocity } => {
+                let (n, _) = self.lane_shape(lane);
+                if step >= n { return Err(t!("Step {} is past the end of a {}-step row", step + 1, n)); }
+                if !self.grid_lanes().contains(&lane) { return Err(t!("No such row").to_string()); }
+                self.push_undo_gesture(t!("step"));
+                self.set_lane_step(lane, step, velocity.map(|v| v.clamp(0.0, 1.0)));
+            }
+            AppCommand::SwitchPattern { index } => {
+                if index >= self.song_editor.pattern_count() { return Err(t!("No pattern {}", index + 1)); }
+                self.switch_pattern(index);
+            }
+            AppCommand::OpenProject { path } => {
+                self.open_project_from(&path);
+                let status = self.status.read().clone();
+                if let Some(e) = status.strip_prefix('✗') { return Err(e.trim().to_string()); }
+            }
+            AppCommand::SetRecord { on } => self.set_seq_record(on),
+            AppCommand::Undo => {
+                if !self.undo() { return Err(t!("Nothing to undo").to_string()); }
+            }
+            AppCommand::Redo => {
+                if !self.redo() { return Err(t!("Nothing to redo").to_string()); }
+            }
+        }
+        Ok(())
+    }
+
+    /// Playing, or counting in to play.
+    fn seq_running(&self) -> bool {
+        self.events.playing() || self.seq_counting_in.load(Ordering::Relaxed)
+    }
+
+    /// `dispatch` for the window and controllers: a refusal goes to the status line.
+    pub fn send(&self, cmd: AppCommand) {
+        if let Err(e) = self.dispatch(cmd) { *self.status.write() = format!("✗ {}", e); }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transport_follows_the_events() {
+        let bus = EventBus::default();
+        let rx  = bus.subscribe();
+        bus.emit(EngineEvent::Started);
+        bus.emit(EngineEvent::Bpm { bpm: 96.0 });
+        bus.emit(EngineEvent::Bpm { bpm: 96.0 });
+        assert!(bus.playing());
+        assert_eq!(bus.bpm(), 96.0);
+        bus.emit(EngineEvent::Stopped);
+        assert!(!bus.playing());
+        // The repeated tempo isn't passed on
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(),
+            vec![EngineEvent::Started, EngineEvent::Bpm { bpm: 96.0 }, EngineEvent::Stopped]);
+    }
+}
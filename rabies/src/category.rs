@@ -0,0 +1,162 @@
+// src/category.rs
+// Drum categories: each track is guessed to be a kick, snare, hat or other percussion,
+// so the step sequencer can colour (and group) rows by what they are rather than by
+// load order. A file name that says what it is wins; otherwise the guess comes from
+// where the attack's energy sits (spectral centroid) and how long the hit rings.
+// A guess can be overridden per track, and the override is saved with the pattern.
+
+use std::sync::atomic::Ordering;
+use serde::{Deserialize, Serialize};
+use crate::audio::AudioAsset;
+use crate::gui::{AppState, DrumTrack};
+
+/// Frames analysed from the peak on.
+const WINDOW: usize = 1024;
+/// Level below the peak at which a hit counts as over.
+const DECAY_DB: f32 = -40.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum DrumCategory {
+    Kick,
+    Snare,
+    Hat,
+    Perc,
+}
+
+impl DrumCategory {
+    pub const ALL: [DrumCategory; 4] = [DrumCategory::Kick, DrumCategory::Snare, DrumCategory::Hat, DrumCategory::Perc];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DrumCategory::Kick  => t!("Kick"),
+            DrumCategory::Snare => t!("Snare"),
+            DrumCategory::Hat   => t!("Hat"),
+            DrumCategory::Perc  => t!("Perc"),
+        }
+    }
+
+    pub fn rgb(self) -> (u8, u8, u8) {
+        match self {
+            DrumCategory::Kick  => (240, 90, 70),
+            DrumCategory::Snare => (240, 200, 60),
+            DrumCategory::Hat   => (80, 210, 230),
+            DrumCategory::Perc  => (130, 220, 120),
+        }
+    }
+}
+
+/// How the step sequencer colours its track rows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RowColors {
+    /// Cycle the palette by track number.
+    Track,
+    #[default]
+    Category,
+}
+
+/// Category the file name gives away, if it does.
+fn from_name(name: &str) -> Option<DrumCategory> {
+    let name = name.to_lowercase();
+    let words: Vec<&str> = name.split(|c: char| !c.is_ascii_alphabetic()).filter(|w| !w.is_empty()).collect();
+    let said = |long: &[&str], short: &[&str]| {
+        long.iter().any(|k| name.contains(k)) || words.iter().any(|w| short.contains(w))
+    };
+    if said(&["kick", "bassdrum"], &["bd", "kik", "kck"])                         { return Some(DrumCategory::Kick); }
+    if said(&["snare", "clap", "rimshot"], &["sd", "sn", "snr", "cp", "rim"])       { return Some(DrumCategory::Snare); }
+    if said(&["hihat", "hat", "cymbal", "ride", "crash", "shaker"], &["hh", "oh", "ch", "cym"]) { return Some(DrumCategory::Hat); }
+    if said(&["tom", "conga", "bongo", "cowbell", "perc", "clave", "tamb"], &["lt", "mt", "ht", "cb"]) { return Some(DrumCategory::Perc); }
+    None
+}
+
+/// Spectral centroid in Hz of the `WINDOW` frames from the peak, and seconds from
+/// the peak until the level stays `DECAY_DB` below it.
+fn centroid_and_decay(asset: &AudioAsset) -> Option<(f32, f32)> {
+    let ch   = (asset.channels as usize).max(1);
+    let rate = asset.sample_rate.max(1) as f32;
+    let mono: Vec<f32> = asset.pcm.chunks_exact(ch).map(|f| f.iter().sum::<f32>() / ch as f32).collect();
+    let (peak_at, peak) = mono.iter().enumerate()
+        .fold((0, 0.0f32), |(i, m), (j, x)| if x.abs() > m { (j, x.abs()) } else { (i, m) });
+    if peak <= 0.0 { return None; }
+
+    let floor = peak * 10f32.powf(DECAY_DB / 20.0);
+    let end   = mono.iter().rposition(|x| x.abs() > floor).unwrap_or(peak_at);
+    let decay = (end - peak_at) as f32 / rate;
+
+    // Hann-windowed DFT from just before the peak, with the twiddles tabulated once
+    let start = peak_at.saturating_sub(WINDOW / 16);
+    let mut frame = vec![0.0f32; WINDOW];
+    for (i, x) in mono.iter().skip(start).take(WINDOW).enumerate() {
+        let w = 0.5 - 0.5 * (std::f32::consts::TAU * i as f32 / WINDOW as f32).cos();
+        frame[i] = x * w;
+    }
+    let (cos, sin): (Vec<f32>, Vec<f32>) = (0..WINDOW)
+        .map(|i| { let a = std::f32::consts::TAU * i as f32 / WINDOW as f32; (a.cos(), a.sin()) })
+        .unzip();
+    let (mut weighted, mut total) = (0.0f32, 0.0f32);
+    for k in 1..WINDOW / 2 {
+        let (mut re, mut im) = (0.0f32, 0.0f32);
+        for (n, x) in frame.iter().enumerate() {
+            let t = (k * n) % WINDOW;
+            re += x * cos[t];
+            im -= x * sin[t];
+        }
+        let mag = (re * re + im * im).sqrt();
+        weighted += mag * k as f32 * rate / WINDOW as f32;
+        total    += mag;
+    }
+    (total > 0.0).then(|| (weighted / total, decay))
+}
+
+/// Best guess at what `asset` is.
+pub fn classify(asset: &AudioAsset) -> DrumCategory {
+    if let Some(c) = from_name(&asset.file_name) { return c; }
+    let Some((centroid, decay)) = centroid_and_decay(asset) else { return DrumCategory::Perc; };
+    // Open hats can ring a while, but sit higher than anything snare-like
+    let hat = centroid >= 6000.0 || (centroid >= 3500.0 && decay < 0.15);
+    if centroid < 350.0                              { DrumCategory::Kick }
+    else if hat                                      { DrumCategory::Hat }
+    else if centroid >= 1200.0 && decay >= 0.06      { DrumCategory::Snare }
+    else                                             { DrumCategory::Perc }
+}
+
+impl DrumTrack {
+    /// Guess the category again, unless it was set by hand.
+    pub fn reclassify(&mut self) {
+        if !self.category_pinned { self.category = classify(&self.asset); }
+    }
+}
+
+impl AppState {
+    /// Set track `track_idx`'s category by hand, or `None` to go back to the guess.
+    pub fn set_track_category(&self, track_idx: usize, category: Option<DrumCategory>) {
+        let mut tracks = self.drum_tracks.write();
+        let Some(t) = tracks.get_mut(track_idx) else { return; };
+        t.category_pinned = category.is_some();
+        match category {
+            Some(c) => t.category = c,
+            None    => t.reclassify(),
+        }
+        *self.status.write() = t!("✓ {} is a {}{}", t.asset.file_name, t.category.label().to_lowercase(),
+            if t.category_pinned { "" } else { t!(" (guessed)") });
+    }
+
+    /// Drop every hand-set category and guess them all again.
+    pub fn reguess_categories(&self) {
+        for t in self.drum_tracks.write().iter_mut() {
+            t.category_pinned = false;
+            t.reclassify();
+        }
+        *self.status.write() = t!("✓ Categories guessed again").into();
+    }
+
+    /// Track indices in the order the sequencer shows them: by category when grouping,
+    /// load order within each group.
+    pub fn track_row_order(&self) -> Vec<usize> {
+        let tracks = self.drum_tracks.read();
+        let mut order: Vec<usize> = (0..tracks.len()).collect();
+        if self.group_rows.load(Ordering::Relaxed) {
+            order.sort_by_key(|&i| tracks[i].category);
+        }
+        order
+    }
+}
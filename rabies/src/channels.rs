@@ -0,0 +1,121 @@
+// src/channels.rs
+// Source → output channel conversion (up/downmix) for the voice mixer and preview.
+
+/// Gains used when folding channels the output doesn't have into left/right.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DownmixCoeffs {
+    pub center:    f32,
+    pub surround:  f32,
+    pub lfe:       f32,
+    /// Scale folded rows back so a full-scale source can't clip.
+    pub normalize: bool,
+}
+
+impl Default for DownmixCoeffs {
+    fn default() -> Self {
+        Self { center: 0.707, surround: 0.707, lfe: 0.0, normalize: true }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Role { L, R, C, Lfe, Sl, Sr, Bl, Br, Other }
+
+/// Speaker roles in WAV/SMPTE channel order.
+fn layout(n: usize) -> Vec<Role> {
+    use Role::*;
+    match n {
+        1 => vec![C],
+        2 => vec![L, R],
+        3 => vec![L, R, C],
+        4 => vec![L, R, Sl, Sr],
+        5 => vec![L, R, C, Sl, Sr],
+        6 => vec![L, R, C, Lfe, Sl, Sr],
+        8 => vec![L, R, C, Lfe, Bl, Br, Sl, Sr],
+        _ => (0..n).map(|i| match i { 0 => L, 1 => R, _ => Other }).collect(),
+    }
+}
+
+pub fn layout_name(n: usize) -> String {
+    match n {
+        1 => "mono".to_string(),
+        2 => "stereo".to_string(),
+        4 => "quad".to_string(),
+        6 => "5.1".to_string(),
+        8 => "7.1".to_string(),
+        _ => format!("{}ch", n),
+    }
+}
+
+/// Output-by-input gain matrix for one source/output channel pairing.
+#[derive(Clone, Debug)]
+pub struct ChannelMatrix {
+    pub from: usize,
+    pub to:   usize,
+    gains:    Vec<f32>,
+}
+
+impl ChannelMatrix {
+    pub fn new(from: usize, to: usize, coeffs: &DownmixCoeffs) -> Self {
+        let (from, to) = (from.max(1), to.max(1));
+        let mut gains  = vec![0.0; from * to];
+        let src = layout(from);
+        let dst = layout(to);
+
+        if from == to {
+            for i in 0..from { gains[i * from + i] = 1.0; }
+        } else if from == 1 {
+            // Mono feeds the front pair (or the single output)
+            for (o, role) in dst.iter().enumerate() {
+                if to == 1 || matches!(role, Role::L | Role::R) { gains[o * from] = 1.0; }
+            }
+        } else {
+            let l = dst.iter().position(|r| *r == Role::L);
+            let r = dst.iter().position(|r| *r == Role::R);
+            for (i, role) in src.iter().enumerate() {
+                if let Some(o) = dst.iter().position(|d| d == role && *d != Role::Other) {
+                    gains[o * from + i] = 1.0;
+                    continue;
+                }
+                // Fold what the output lacks into the front pair
+                let (gl, gr) = match role {
+                    Role::L   => (1.0, 0.0),
+                    Role::R   => (0.0, 1.0),
+                    Role::C   => (coeffs.center, coeffs.center),
+                    Role::Lfe => (coeffs.lfe, coeffs.lfe),
+                    Role::Sl | Role::Bl => (coeffs.surround, 0.0),
+                    Role::Sr | Role::Br => (0.0, coeffs.surround),
+                    Role::Other => (0.0, 0.0),
+                };
+                match (l, r) {
+                    (Some(lo), Some(ro)) => {
+                        gains[lo * from + i] += gl;
+                        gains[ro * from + i] += gr;
+                    }
+                    // Mono output: average of the left and right folds
+                    _ => gains[i] += (gl + gr) * 0.5,
+                }
+            }
+            if coeffs.normalize {
+                for row in gains.chunks_mut(from) {
+                    let sum: f32 = row.iter().sum();
+                    if sum > 1.0 { for g in row.iter_mut() { *g /= sum; } }
+                }
+            }
+        }
+        Self { from, to, gains }
+    }
+
+    pub fn is_identity(&self) -> bool { self.from == self.to }
+
+    /// Convert one interleaved frame. `input` has `from` samples, `out` has `to`.
+    pub fn apply(&self, input: &[f32], out: &mut [f32]) {
+        for (o, row) in self.gains.chunks(self.from).enumerate().take(out.len()) {
+            out[o] = row.iter().zip(input).map(|(g, s)| g * s).sum();
+        }
+    }
+
+    /// "5.1 → stereo" style description, `None` when nothing is converted.
+    pub fn describe(&self) -> Option<String> {
+        (!self.is_identity()).then(|| format!("{} → {}", layout_name(self.from), layout_name(self.to)))
+    }
+}
@@ -0,0 +1,174 @@
+// src/clipboard.rs
+// Pattern and row clipboard. Copying a pattern takes its whole content — main grid and
+// every track's rows, settings included — and pasting it over another pattern replaces
+// that pattern's content while it keeps its name, colour, fill and place in the song.
+// Copying a row takes its hits on its lane with their velocities, pitches, nudges and
+// (for a chop row) piano notes; pasting it onto any track or chop row, in this pattern
+// or another, lays them on that row's lane from step 1. Pastes can be undone (Ctrl+Z).
+// The clipboard lives in the app, not the system clipboard.
+
+use crate::grid_controller::Lane;
+use crate::gui::{lane_len, step_on, AppState, StepResolution};
+use crate::pattern::Pattern;
+use crate::piano_roll::PianoRollNote;
+use crate::step_nudge::StepNudge;
+use crate::step_pitch::StepPitch;
+
+/// One row's hits, off whichever row they came from.
+#[derive(Clone, Debug)]
+pub struct RowClip {
+    pub hits:     Vec<bool>,
+    pub velocity: Vec<f32>,
+    /// Pitches and nudges, their `chop` cleared.
+    pub pitches:  Vec<StepPitch>,
+    pub nudges:   Vec<StepNudge>,
+    pub notes:    Vec<PianoRollNote>,
+}
+
+#[derive(Clone, Debug)]
+pub enum Clip {
+    Pattern(Pattern),
+    Row(RowClip),
+}
+
+impl Pattern {
+    /// The hits of one row of a `len`-step pattern on the `res` grid. Chops of the `main`
+    /// track live on the main grid.
+    pub fn copy_lane(&self, lane: Lane, len: usize, res: StepResolution, main: Option<usize>) -> Option<RowClip> {
+        let (t, chop) = match lane { Lane::Main(t) => (t, None), Lane::Chop(t, c) => (t, Some(c)) };
+        let tr = self.tracks.get(t)?;
+        let (hits, velocity, notes) = match chop {
+            None => {
+                let n = lane_len(len, res, tr.lane(res, None, false));
+                ((0..n).map(|s| step_on(&tr.steps, s)).collect(),
+                 (0..n).map(|s| tr.step_velocity.get(s).copied().unwrap_or(1.0)).collect(),
+                 Vec::new())
+            }
+            Some(c) => {
+                let n = lane_len(len, res, tr.lane(res, Some(c), main == Some(t)));
+                let hits = if main == Some(t) {
+                    (0..n).map(|s| self.main_grid.get(s).is_some_and(|g| g.contains(&c))).collect()
+                } else {
+                    (0..n).map(|s| tr.chop_steps.get(c).is_some_and(|r| step_on(r, s))).collect()
+                };
+                (hits, vec![1.0; n], tr.chop_piano_notes.get(c).cloned().unwrap_or_default())
+            }
+        };
+        Some(RowClip {
+            hits,
+            velocity,
+            pitches: tr.step_pitches.iter().filter(|p| p.chop == chop).map(|p| StepPitch { chop: None, ..*p }).collect(),
+            nudges:  tr.step_nudges.iter().filter(|n| n.chop == chop).map(|n| StepNudge { chop: None, ..*n }).collect(),
+            notes,
+        })
+    }
+
+    /// Lay `clip` onto one row, replacing what was there; steps past the row's lane are dropped.
+    pub fn paste_lane(&mut self, lane: Lane, clip: &RowClip, len: usize, res: StepResolution, main: Option<usize>) {
+        let (t, chop) = match lane { Lane::Main(t) => (t, None), Lane::Chop(t, c) => (t, Some(c)) };
+        let Some(tr) = self.tracks.get_mut(t) else { return; };
+        let n = lane_len(len, res, tr.lane(res, chop, chop.is_some() && main == Some(t)));
+        let hits: Vec<bool> = (0..n).map(|s| clip.hits.get(s).copied().unwrap_or(false)).collect();
+        match chop {
+            None => {
+                tr.steps.resize(tr.steps.len().max(n), false);
+                tr.steps[..n].copy_from_slice(&hits);
+                tr.step_velocity.resize(tr.step_velocity.len().max(n), 1.0);
+                for (s, v) in tr.step_velocity[..n].iter_mut().enumerate() { *v = clip.velocity.get(s).copied().unwrap_or(1.0); }
+            }
+            Some(c) => {
+                if tr.chop_steps.len() <= c { tr.chop_steps.resize(c + 1, vec![false; tr.row_len(len, res)]); }
+                if main == Some(t) {
+                    let grid = &mut self.main_grid;
+                    if grid.len() < n { grid.resize(n, Vec::new()); }
+                    for (s, cell) in grid.iter_mut().enumerate() {
+                        cell.retain(|&x| x != c);
+                        if hits.get(s).copied().unwrap_or(false) { cell.push(c); }
+                    }
+                } else {
+                    let r = &mut tr.chop_steps[c];
+                    r.resize(r.len().max(n), false);
+                    r[..n].copy_from_slice(&hits);
+                }
+                if tr.chop_piano_notes.len() <= c { tr.chop_piano_notes.resize(c + 1, Vec::new()); }
+                tr.chop_piano_notes[c] = clip.notes.iter().filter(|note| note.step < len).cloned().collect();
+            }
+        }
+        tr.step_pitches.retain(|p| p.chop != chop);
+        tr.step_pitches.extend(clip.pitches.iter().filter(|p| p.step < n).map(|p| StepPitch { chop, ..*p }));
+        tr.step_nudges.retain(|x| x.chop != chop);
+        tr.step_nudges.extend(clip.nudges.iter().filter(|x| x.step < n).map(|x| StepNudge { chop, ..*x }));
+    }
+
+    /// Take `from`'s content, keeping this pattern's identity and song settings.
+    pub fn paste_content(&mut self, from: &Pattern) {
+        self.main_grid = from.main_grid.clone();
+        self.tracks    = from.tracks.clone();
+    }
+}
+
+impl AppState {
+    pub fn copy_pattern(&self, idx: usize) {
+        if idx == self.song_editor.active_edit_idx() { self.save_current_pattern_state(); }
+        let Some(p) = self.song_editor.get_pattern_by_idx(idx) else { return; };
+        *self.status.write() = t!("📋 Copied pattern \"{}\"", p.name);
+        *self.clipboard.write() = Some(Clip::Pattern(p));
+    }
+
+    /// A pattern is on the clipboard.
+    pub fn can_paste_pattern(&self) -> bool {
+        matches!(*self.clipboard.read(), Some(Clip::Pattern(_)))
+    }
+
+    /// Replace pattern `idx`'s content with the copied pattern's.
+    pub fn paste_pattern(&self, idx: usize) {
+        let Some(Clip::Pattern(from)) = self.clipboard.read().clone() else { return; };
+        self.push_undo(t!("pattern paste"));
+        self.paste_pattern_into(idx, &from);
+    }
+
+    /// Put the copied pattern's content into a new pattern.
+    pub fn paste_pattern_as_new(&self) {
+        let Some(Clip::Pattern(from)) = self.clipboard.read().clone() else { return; };
+        // Before the pattern is made, so undo takes it away again
+        self.push_undo(t!("pattern paste"));
+        let idx = self.song_editor.create_pattern();
+        self.paste_pattern_into(idx, &from);
+    }
+
+    fn paste_pattern_into(&self, idx: usize, from: &Pattern) {
+        let Some(mut p) = self.song_editor.get_pattern_by_idx(idx) else { return; };
+        p.paste_content(from);
+        *self.status.write() = t!("📋 \"{}\" pasted over \"{}\"", from.name, p.name);
+        self.song_editor.update_pattern_by_idx(idx, p);
+        if idx == self.song_editor.active_edit_idx() { self.reload_active_pattern(); }
+    }
+
+    /// Copy one row of the pattern being edited.
+    pub fn copy_lane(&self, lane: Lane) {
+        self.save_current_pattern_state();
+        let idx  = self.song_editor.active_edit_idx();
+        let main = *self.main_track_index.read();
+        let Some(clip) = self.song_editor.get_pattern_by_idx(idx)
+            .and_then(|p| p.copy_lane(lane, self.pattern_len(), *self.seq_resolution.read(), main)) else { return; };
+        *self.status.write() = t!("📋 Copied {}", self.lane_label(lane));
+        *self.clipboard.write() = Some(Clip::Row(clip));
+    }
+
+    /// A row is on the clipboard.
+    pub fn can_paste_lane(&self) -> bool {
+        matches!(*self.clipboard.read(), Some(Clip::Row(_)))
+    }
+
+    /// Paste the copied row over one row of the pattern being edited.
+    pub fn paste_lane(&self, lane: Lane) {
+        let Some(Clip::Row(clip)) = self.clipboard.read().clone() else { return; };
+        self.push_undo(t!("row paste"));
+        let idx = self.song_editor.active_edit_idx();
+        let Some(mut p) = self.song_editor.get_pattern_by_idx(idx) else { return; };
+        p.paste_lane(lane, &clip, self.pattern_len(), *self.seq_resolution.read(), *self.main_track_index.read());
+        self.song_editor.update_pattern_by_idx(idx, p);
+        self.reload_active_pattern();
+        *self.status.write() = t!("📋 Pasted onto {}", self.lane_label(lane));
+    }
+}
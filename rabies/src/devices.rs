@@ -0,0 +1,114 @@
+// src/devices.rs
+// Output device choice for the cpal backend: which host/device the engine opens, and at
+// what sample rate and buffer size. JACK picks its own clock and ignores all of it.
+
+use parking_lot::RwLock;
+use cpal::traits::{DeviceTrait, HostTrait};
+
+/// Rates offered in the settings panel when the device supports them.
+const COMMON_RATES: [u32; 7] = [22050, 44100, 48000, 88200, 96000, 176400, 192000];
+pub const BUFFER_SIZES: [u32; 7] = [64, 128, 256, 512, 1024, 2048, 4096];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutputDevice {
+    pub host_id:     cpal::HostId,
+    pub host_name:   String,
+    pub device_name: String,
+    pub label:       String,
+    /// Entries of `COMMON_RATES` the device can run at.
+    pub rates:       Vec<u32>,
+}
+
+/// What the engine should open. `None` fields leave the choice to the host.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeviceSelection {
+    pub device:        Option<OutputDevice>,
+    pub sample_rate:   Option<u32>,
+    pub buffer_frames: Option<u32>,
+}
+
+impl Default for DeviceSelection {
+    fn default() -> Self {
+        Self { device: None, sample_rate: Some(48000), buffer_frames: Some(1024) }
+    }
+}
+
+impl DeviceSelection {
+    pub fn device_label(&self) -> String {
+        self.device.as_ref().map_or(t!("System default").to_string(), |d| d.label.clone())
+    }
+}
+
+pub struct AudioDeviceManager {
+    /// `None` until first listed.
+    devices:       RwLock<Option<Vec<OutputDevice>>>,
+    /// Rates of the default host's default device, for "System default".
+    default_rates: RwLock<Vec<u32>>,
+    selection:     RwLock<DeviceSelection>,
+}
+
+impl AudioDeviceManager {
+    pub fn new() -> Self {
+        Self {
+            devices:       RwLock::new(None),
+            default_rates: RwLock::new(Vec::new()),
+            selection:     RwLock::new(DeviceSelection::default()),
+        }
+    }
+
+    /// Re-enumerate every host's output devices.
+    pub fn refresh(&self) {
+        let mut out = Vec::new();
+        for host_id in cpal::available_hosts() {
+            let host_name = format!("{:?}", host_id);
+            let Ok(host) = cpal::host_from_id(host_id) else { continue; };
+            let Ok(devices) = host.output_devices() else { continue; };
+            for device in devices {
+                let Ok(device_name) = device.name() else { continue; };
+                let label = format!("{}: {}", host_name, device_name);
+                out.push(OutputDevice { host_id, host_name: host_name.clone(), device_name, label, rates: supported_rates(&device) });
+            }
+        }
+        *self.default_rates.write() = cpal::default_host().default_output_device()
+            .map(|d| supported_rates(&d)).unwrap_or_default();
+        *self.devices.write() = Some(out);
+    }
+
+    /// Enumerated devices, listing them on first use.
+    pub fn devices(&self) -> Vec<OutputDevice> {
+        if self.devices.read().is_none() { self.refresh(); }
+        self.devices.read().clone().unwrap_or_default()
+    }
+
+    pub fn selection(&self) -> DeviceSelection { self.selection.read().clone() }
+
+    pub fn set_selection(&self, sel: DeviceSelection) { *self.selection.write() = sel; }
+
+    /// Rates the selected device supports.
+    pub fn selected_rates(&self) -> Vec<u32> {
+        match &self.selection.read().device {
+            Some(d) => d.rates.clone(),
+            None    => self.default_rates.read().clone(),
+        }
+    }
+}
+
+fn supported_rates(device: &cpal::Device) -> Vec<u32> {
+    let Ok(ranges) = device.supported_output_configs() else { return Vec::new(); };
+    let ranges: Vec<_> = ranges.collect();
+    COMMON_RATES.iter().copied()
+        .filter(|r| ranges.iter().any(|c| (c.min_sample_rate().0..=c.max_sample_rate().0).contains(r)))
+        .collect()
+}
+
+/// Find `dev` again on its host, or the default output when `dev` is `None`.
+pub fn open_output_device(dev: Option<&OutputDevice>) -> Result<cpal::Device, String> {
+    let Some(dev) = dev else {
+        return cpal::default_host().default_output_device().ok_or_else(|| t!("No audio output device").to_string());
+    };
+    let host = cpal::host_from_id(dev.host_id).map_err(|e| format!("Host error: {:?}", e))?;
+    host.output_devices()
+        .map_err(|e| t!("Device list: {}", e))?
+        .find(|d| d.name().map(|n| n == dev.device_name).unwrap_or(false))
+        .ok_or_else(|| t!("Device '{}' not found (try Refresh)", dev.device_name))
+}
@@ -0,0 +1,229 @@
+// src/engine.rs
+// The one output stream. The main-sample playhead, pad auditions and sequencer voices
+// all go into the same mixer; the GUI talks to it through a command channel. A browser
+// prelisten plays over the top, with the rest ducked under it (prelisten.rs).
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::{Duration, Instant};
+use parking_lot::{Mutex, RwLock};
+use atomic_float::AtomicF32;
+use crate::adsr::{mix_voices, Voice};
+use crate::audio::AudioAsset;
+use crate::backend::{self, AudioBackend, OutputRequest, OutputSpec, OutputStream, RenderFn};
+use crate::channels::ChannelMatrix;
+use crate::devices::DeviceSelection;
+use crate::limiter::{soft_clip, SafetyControls, SafetyStage};
+use crate::looper::{Stutter, StutterControls};
+use crate::netsend::NetTap;
+use crate::prelisten::Ducker;
+
+/// GUI → mixer.
+pub enum Command {
+    /// Start these voices (channel maps already attached).
+    Voices(Vec<Voice>),
+    /// Drop every sounding voice; the playhead keeps going.
+    ClearVoices,
+    /// Play `asset` from the playhead's current sample index.
+    Play { asset: Arc<AudioAsset>, matrix: ChannelMatrix },
+    /// Forget the playhead's asset.
+    StopPlayback,
+    /// Replace the browser prelisten voice, or silence it with `None`.
+    Prelisten(Option<Voice>),
+}
+
+/// Main-sample transport, written by the mixer and read (or seeked) by the GUI.
+#[derive(Clone)]
+pub struct Playhead {
+    pub position:     Arc<AtomicF32>,
+    /// Interleaved sample index into the asset's PCM.
+    pub sample_index: Arc<AtomicU64>,
+    pub is_playing:   Arc<AtomicBool>,
+    /// Normalised position to stop at, < 0 for the end of the file.
+    pub stop_target:  Arc<AtomicF32>,
+    /// Start of the span being played (region or selection), < 0 for none.
+    pub loop_from:    Arc<AtomicF32>,
+    /// Wrap from the stop target back to `loop_from` instead of stopping.
+    pub looping:      Arc<AtomicBool>,
+    /// Varispeed: source frames per output frame, before rate conversion.
+    pub speed:        Arc<AtomicF32>,
+    /// When the first frame of the latest Play was heard (callback time plus output latency).
+    pub heard_at:     Arc<Mutex<Option<Instant>>>,
+    pub status:       Arc<RwLock<String>>,
+}
+
+/// What the mixer shares with the rest of the app while it runs.
+pub struct EngineLinks {
+    pub playhead:      Playhead,
+    pub master_volume: Arc<AtomicF32>,
+    /// Low-pass cutoff on the voice mix, Hz (20 kHz = open).
+    pub master_cutoff: Arc<AtomicF32>,
+    pub crossfader:    Arc<AtomicF32>,
+    /// Tempo the stutter loop lengths follow.
+    pub bpm:           Arc<AtomicF32>,
+    pub stutter:       StutterControls,
+    pub safety:        SafetyControls,
+    pub net_tap:       Arc<NetTap>,
+    /// How far everything else dips under a prelisten, dB.
+    pub duck_db:       Arc<AtomicF32>,
+    /// Output frames rendered since the engine opened; the transport clock reads it.
+    pub frames:        Arc<AtomicU64>,
+    /// Callback-to-speaker delay the backend reports, seconds.
+    pub latency:       Arc<AtomicF32>,
+    pub fault:         Arc<RwLock<Option<String>>>,
+}
+
+/// Keeps the output open; dropping it closes the stream and every voice with it.
+pub struct Engine {
+    tx:      Sender<Command>,
+    spec:    OutputSpec,
+    _stream: OutputStream,
+}
+
+impl Engine {
+    pub fn open(backend: &dyn AudioBackend, sel: DeviceSelection, links: EngineLinks) -> Result<Self, String> {
+        let (tx, rx) = mpsc::channel();
+        let mut rx = Some(rx);
+        let req = OutputRequest {
+            role: "main", device: sel.device, sample_rate: sel.sample_rate, buffer_frames: sel.buffer_frames,
+            on_error: Box::new(|err| eprintln!("Audio stream error: {}", err)),
+            latency:  links.latency.clone(),
+        };
+        let (fault, status) = (links.fault.clone(), links.playhead.status.clone());
+        let mut links = Some(links);
+        let (stream, spec) = backend.open_output(req, &mut |spec| {
+            let (Some(rx), Some(links)) = (rx.take(), links.take()) else { return Box::new(|d: &mut [f32]| d.fill(0.0)); };
+            backend::guarded(mixer(rx, links, spec), fault.clone(), status.clone())
+        })?;
+        Ok(Self { tx, spec, _stream: stream })
+    }
+
+    pub fn spec(&self) -> OutputSpec { self.spec }
+
+    pub fn send(&self, cmd: Command) {
+        // Only fails once the stream is gone, and then there's nothing to play to
+        let _ = self.tx.send(cmd);
+    }
+}
+
+/// The playhead's asset as the mixer holds it.
+struct Track {
+    asset:  Arc<AudioAsset>,
+    matrix: ChannelMatrix,
+    /// One interpolated source frame and its converted output frame.
+    src:    Vec<f32>,
+    dst:    Vec<f32>,
+}
+
+fn mixer(rx: Receiver<Command>, links: EngineLinks, spec: OutputSpec) -> RenderFn {
+    let out_channels  = spec.channels.max(1);
+    let sample_rate   = spec.sample_rate as f32;
+    let mut voices: Vec<Voice> = Vec::new();
+    let mut prelisten: Vec<Voice> = Vec::new();
+    let mut track: Option<Track> = None;
+    let mut lp_state  = vec![0.0f32; out_channels];
+    let mut stutter   = Stutter::new(links.stutter.clone(), links.bpm.clone(), out_channels, sample_rate);
+    let mut safety    = SafetyStage::new(links.safety.clone(), out_channels, sample_rate);
+    let mut ducker    = Ducker::new(links.duck_db.clone(), out_channels, sample_rate);
+    links.net_tap.set_format(spec.sample_rate, out_channels);
+    Box::new(move |data: &mut [f32]| {
+        for cmd in rx.try_iter() {
+            match cmd {
+                Command::Voices(v)            => voices.extend(v),
+                Command::ClearVoices          => voices.clear(),
+                Command::Play { asset, matrix } => {
+                    let (src, dst) = (vec![0.0; matrix.from], vec![0.0; matrix.to]);
+                    track = Some(Track { asset, matrix, src, dst });
+                    // Its first frame opens this block
+                    let latency = Duration::from_secs_f32(links.latency.load(Ordering::Relaxed).max(0.0));
+                    *links.playhead.heard_at.lock() = Some(Instant::now() + latency);
+                }
+                Command::StopPlayback         => track = None,
+                Command::Prelisten(v)         => prelisten = v.into_iter().collect(),
+            }
+        }
+
+        data.fill(0.0);
+        mix_voices(&mut voices, data, sample_rate, out_channels, links.crossfader.load(Ordering::Relaxed));
+        // The playhead joins the mix here, so the master volume and clip cover it too
+        if let Some(t) = track.as_mut() { mix_playhead(t, &links.playhead, data, sample_rate); }
+
+        // ── Master bus: one-pole low-pass + volume + soft clip ──
+        let cutoff = links.master_cutoff.load(Ordering::Relaxed);
+        let vol    = links.master_volume.load(Ordering::Relaxed);
+        let open   = cutoff >= 19999.0;
+        let a      = 1.0 - (-2.0 * std::f32::consts::PI * cutoff / sample_rate).exp();
+        for frame in data.chunks_mut(out_channels) {
+            for (ch, smp) in frame.iter_mut().enumerate() {
+                if !open {
+                    lp_state[ch] += a * (*smp - lp_state[ch]);
+                    *smp = lp_state[ch];
+                }
+                *smp = soft_clip(*smp * vol);
+            }
+        }
+
+        stutter.process(data);
+
+        // A prelisten sits on top of the ducked mix, past the master bus and stutter
+        ducker.process(data, !prelisten.is_empty());
+        mix_voices(&mut prelisten, data, sample_rate, out_channels, 0.0);
+
+        safety.process(data);
+        links.net_tap.push(data);
+        links.frames.fetch_add((data.len() / out_channels) as u64, Ordering::Relaxed);
+    })
+}
+
+/// Add the main-sample playhead to `data`, advancing (and stopping) it.
+fn mix_playhead(track: &mut Track, ph: &Playhead, data: &mut [f32], sample_rate: f32) {
+    if !ph.is_playing.load(Ordering::Relaxed) { return; }
+    let pcm    = &track.asset.pcm;
+    let ch     = (track.asset.channels as usize).max(1);
+    let out_ch = track.matrix.to.max(1);
+    // Everything below counts in source frames, so position, seek and stop targets
+    // keep their meaning at any speed
+    let speed  = ph.speed.load(Ordering::Relaxed).clamp(0.25, 4.0) as f64;
+    let step   = speed * track.asset.sample_rate as f64 / sample_rate.max(1.0) as f64;
+    let total  = pcm.len();
+    let pcm_frames = total / ch;
+    let stop_pos   = ph.stop_target.load(Ordering::Relaxed);
+    let target     = if stop_pos >= 0.0 { Some((stop_pos * pcm_frames as f32) as usize) } else { None };
+    let loop_pos   = ph.loop_from.load(Ordering::Relaxed);
+    // Span to wrap around in, when looping one that ends before the target
+    let wrap = match (target, ph.looping.load(Ordering::Relaxed) && loop_pos >= 0.0) {
+        (Some(t), true) => Some(((loop_pos as f64 * pcm_frames as f64).min(t as f64), t as f64)).filter(|(a, b)| a < b),
+        _ => None,
+    };
+
+    let mut fp = ph.sample_index.load(Ordering::Relaxed) as f64 / ch as f64;
+    for frame in data.chunks_mut(out_ch) {
+        if let Some((from, to)) = wrap {
+            if fp >= to { fp = from + (fp - to) % (to - from); }
+        }
+        let i0 = fp as usize;
+        if target.is_some_and(|t| i0 >= t) {
+            ph.is_playing.store(false, Ordering::Relaxed);
+            *ph.status.write() = t!("Stopped at marker").to_string();
+            break;
+        }
+        if i0 >= pcm_frames.saturating_sub(1) {
+            ph.is_playing.store(false, Ordering::Relaxed);
+            *ph.status.write() = t!("Playback finished").to_string();
+            break;
+        }
+        let i1 = (i0 + 1).min(pcm_frames - 1);
+        let t  = (fp - i0 as f64) as f32;
+        for (c, slot) in track.src.iter_mut().enumerate() {
+            let s0 = pcm.get(i0 * ch + c).copied().unwrap_or(0.0);
+            let s1 = pcm.get(i1 * ch + c).copied().unwrap_or(0.0);
+            *slot = s0 + t * (s1 - s0);
+        }
+        track.matrix.apply(&track.src, &mut track.dst);
+        for (d, s) in frame.iter_mut().zip(&track.dst) { *d += s; }
+        fp += step;
+    }
+    if total > 0 { ph.position.store((fp * ch as f64 / total as f64).min(1.0) as f32, Ordering::Relaxed); }
+    ph.sample_index.store((fp * ch as f64) as u64, Ordering::Relaxed);
+}
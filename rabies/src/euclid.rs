@@ -0,0 +1,53 @@
+// src/euclid.rs
+// Euclidean rhythms: `pulses` hits spread as evenly as they go over `steps` steps —
+// 3 over 8 is the tresillo, 5 over 8 the cinquillo, 4 over 16 four on the floor — then
+// turned `rotation` steps later. A row's menu fills the row with one, repeating it along
+// the row's lane when the rhythm is shorter, replacing the hits that were there (the
+// fill can be undone like the other pattern tools).
+
+use crate::grid_controller::Lane;
+use crate::gui::AppState;
+
+/// The generator's settings, kept between fills.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Euclid {
+    pub pulses:   usize,
+    pub steps:    usize,
+    pub rotation: usize,
+}
+
+impl Default for Euclid {
+    fn default() -> Self { Self { pulses: 3, steps: 8, rotation: 0 } }
+}
+
+impl Euclid {
+    /// One cycle of the rhythm.
+    pub fn cycle(&self) -> Vec<bool> {
+        let n = self.steps.max(1);
+        let k = self.pulses.min(n);
+        let mut row: Vec<bool> = (0..n).map(|i| (i * k) % n < k).collect();
+        row.rotate_right(self.rotation % n);
+        row
+    }
+
+    /// The rhythm repeated over a lane of `len` steps.
+    pub fn row(&self, len: usize) -> Vec<bool> {
+        let cycle = self.cycle();
+        (0..len).map(|s| cycle[s % cycle.len()]).collect()
+    }
+}
+
+impl AppState {
+    /// Replace the hits of `lane` in the pattern being edited with the generator's rhythm.
+    pub fn fill_euclid(&self, lane: Lane) {
+        let e = *self.euclid.read();
+        self.push_undo(t!("euclidean fill"));
+        let idx = self.song_editor.active_edit_idx();
+        let Some(mut p) = self.song_editor.get_pattern_by_idx(idx) else { return; };
+        let main = *self.main_track_index.read();
+        p.fill_lane(lane, self.pattern_len(), *self.seq_resolution.read(), main, |n| e.row(n));
+        self.song_editor.update_pattern_by_idx(idx, p);
+        self.reload_active_pattern();
+        *self.status.write() = t!("✓ {} filled with {} over {} (rotated {})", self.lane_label(lane), e.pulses.min(e.steps), e.steps, e.rotation);
+    }
+}
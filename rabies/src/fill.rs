@@ -0,0 +1,81 @@
+// src/fill.rs
+// Pattern fills: a pattern can name another pattern as its fill. Triggering the fill
+// (the G key or the 🥁 Fill button) waits for the next bar line, plays the fill for one
+// bar and then hands back to the pattern it interrupted.
+//
+// A footswitch mapped to Fill (footswitch.rs) triggers it too.
+
+use std::sync::atomic::Ordering;
+use crate::gui::{AppState, StepResolution};
+
+/// Where a triggered fill is.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FillState {
+    /// Waiting for the next bar line.
+    Queued  { fill: usize, back_to: usize },
+    /// Sounding; `steps_left` counts down to the switch back.
+    Playing { back_to: usize, steps_left: usize },
+}
+
+impl AppState {
+    /// Queue the active pattern's fill. A second press before the bar line cancels it.
+    pub fn trigger_fill(&self) {
+        if !self.events.playing() {
+            *self.status.write() = t!("✗ Start the sequencer to play a fill").into();
+            return;
+        }
+        if self.song_editor.is_playing.load(Ordering::Relaxed) {
+            *self.status.write() = t!("✗ Fills are for live pattern play, not the song").into();
+            return;
+        }
+        let current = *self.fill.read();
+        match current {
+            Some(FillState::Queued { .. }) => {
+                *self.fill.write() = None;
+                *self.status.write() = t!("Fill cancelled").into();
+            }
+            Some(FillState::Playing { .. }) => {}
+            None => {
+                let active = self.song_editor.active_edit_idx();
+                let Some(pattern) = self.song_editor.get_pattern_by_idx(active) else { return; };
+                let Some(fill) = pattern.fill.filter(|&f| f != active && f < self.song_editor.pattern_count()) else {
+                    *self.status.write() = t!("✗ {} has no fill pattern", pattern.name);
+                    return;
+                };
+                *self.pending_pattern.write() = None;
+                *self.fill.write() = Some(FillState::Queued { fill, back_to: active });
+                *self.status.write() = t!("⏳ Fill queued for the next bar").into();
+            }
+        }
+    }
+
+    /// Pick the fill for pattern `idx` (`None` clears it).
+    pub fn set_fill_pattern(&self, idx: usize, fill: Option<usize>) {
+        if let Some(mut p) = self.song_editor.get_pattern_by_idx(idx) {
+            p.fill = fill.filter(|&f| f != idx);
+            self.song_editor.update_pattern_by_idx(idx, p);
+        }
+    }
+
+    /// Called by `tick_sequencer` before `step` sounds: start a queued fill on a bar
+    /// line, and switch back once it has played a full bar.
+    pub(crate) fn tick_fill(&self, step: usize, res: StepResolution) {
+        let state = *self.fill.read();
+        match state {
+            Some(FillState::Queued { fill, back_to }) if step.is_multiple_of(res.steps()) => {
+                self.apply_pattern_switch(fill);
+                *self.fill.write() = Some(FillState::Playing { back_to, steps_left: res.steps() });
+                *self.status.write() = t!("🥁 Fill").into();
+            }
+            Some(FillState::Playing { back_to, steps_left }) => {
+                if steps_left > 1 {
+                    *self.fill.write() = Some(FillState::Playing { back_to, steps_left: steps_left - 1 });
+                } else {
+                    *self.fill.write() = None;
+                    self.apply_pattern_switch(back_to);
+                }
+            }
+            _ => {}
+        }
+    }
+}
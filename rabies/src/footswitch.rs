@@ -0,0 +1,147 @@
+// src/footswitch.rs
+// Footswitches: a CC from the MIDI input (a sustain pedal is CC 64) mapped to a transport
+// action, for when both hands are on an instrument. A value of 64 or more is "down".
+//
+// Play/stop and record can be momentary (running while the switch is held, or while a
+// latching pedal is on) or latching (each press toggles). Tap tempo and fill fire on
+// every press either way. The mappings are saved with the project.
+
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use crate::bus::AppCommand;
+use crate::gui::AppState;
+
+/// CC value from which a switch counts as down.
+const SWITCH_DOWN: u8 = 64;
+/// A tap this long after the last one starts a new count.
+const TAP_RESET: Duration = Duration::from_secs(2);
+/// Taps averaged for the tempo.
+const TAP_HISTORY: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SwitchAction {
+    PlayStop,
+    Record,
+    TapTempo,
+    Fill,
+}
+
+impl SwitchAction {
+    pub const ALL: [SwitchAction; 4] = [SwitchAction::PlayStop, SwitchAction::Record, SwitchAction::TapTempo, SwitchAction::Fill];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SwitchAction::PlayStop => t!("Play / stop"),
+            SwitchAction::Record   => t!("Record"),
+            SwitchAction::TapTempo => t!("Tap tempo"),
+            SwitchAction::Fill     => t!("Fill"),
+        }
+    }
+
+    /// Whether the action has an on and an off, so momentary and latching differ.
+    pub fn holds(&self) -> bool { matches!(self, SwitchAction::PlayStop | SwitchAction::Record) }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SwitchMode {
+    Momentary,
+    Latching,
+}
+
+impl SwitchMode {
+    pub const ALL: [SwitchMode; 2] = [SwitchMode::Momentary, SwitchMode::Latching];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SwitchMode::Momentary => t!("Momentary"),
+            SwitchMode::Latching  => t!("Latching"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Footswitch {
+    pub cc:     u8,
+    pub action: SwitchAction,
+    pub mode:   SwitchMode,
+    /// Last state seen, so repeated values from a half-pedal don't retrigger.
+    #[serde(skip)]
+    down:       bool,
+}
+
+impl Footswitch {
+    /// Sustain pedal starting and stopping the sequencer, one press each.
+    pub fn sustain() -> Self {
+        Self { cc: 64, action: SwitchAction::PlayStop, mode: SwitchMode::Latching, down: false }
+    }
+}
+
+impl AppState {
+    /// Run the action of every footswitch on `cc` whose state `value` changes.
+    pub(crate) fn footswitch(&self, cc: u8, value: u8) {
+        let down = value >= SWITCH_DOWN;
+        let changed: Vec<(SwitchAction, SwitchMode)> = self.footswitches.write().iter_mut()
+            .filter(|s| s.cc == cc && s.down != down)
+            .map(|s| { s.down = down; (s.action, s.mode) })
+            .collect();
+        for (action, mode) in changed {
+            if !action.holds() {
+                if down { self.fire_switch(action, true); }
+            } else if mode == SwitchMode::Momentary {
+                self.fire_switch(action, down);
+            } else if down {
+                self.fire_switch(action, !self.switch_active(action));
+            }
+        }
+    }
+
+    fn switch_active(&self, action: SwitchAction) -> bool {
+        match action {
+            SwitchAction::PlayStop => self.events.playing(),
+            SwitchAction::Record   => self.rec_active_track.read().is_some(),
+            SwitchAction::TapTempo | SwitchAction::Fill => false,
+        }
+    }
+
+    /// Turn `action` on or off; one-shot actions only act on `on`.
+    fn fire_switch(&self, action: SwitchAction, on: bool) {
+        if action.holds() && on == self.switch_active(action) { return; }
+        match action {
+            SwitchAction::PlayStop if on => self.send(AppCommand::Play),
+            SwitchAction::PlayStop       => self.send(AppCommand::Stop),
+            SwitchAction::Record if on   => self.record_first_armed(),
+            SwitchAction::Record         => {
+                let active = *self.rec_active_track.read();
+                if let Some(idx) = active { self.stop_recording(idx); }
+            }
+            SwitchAction::TapTempo => self.tap_tempo(),
+            SwitchAction::Fill     => self.trigger_fill(),
+        }
+    }
+
+    /// Start recording on the first recording track that has an input picked.
+    fn record_first_armed(&self) {
+        let target = self.rec_tracks.read().iter().position(|t| t.device_label.is_some());
+        match target {
+            Some(idx) => self.start_recording(idx),
+            None      => *self.status.write() = t!("✗ Add a recording track and pick its input to record").into(),
+        }
+    }
+
+    /// One tap: after two or more, the BPM becomes the average of the last few gaps.
+    pub fn tap_tempo(&self) {
+        let now = Instant::now();
+        let mut taps = self.tap_times.lock();
+        if taps.last().is_some_and(|&t| now.duration_since(t) > TAP_RESET) { taps.clear(); }
+        taps.push(now);
+        if taps.len() > TAP_HISTORY + 1 { taps.remove(0); }
+        if taps.len() < 2 {
+            *self.status.write() = t!("Tap…").into();
+            return;
+        }
+        let span = now.duration_since(taps[0]).as_secs_f32() / (taps.len() - 1) as f32;
+        let bpm  = (60.0 / span).clamp(40.0, 300.0).round();
+        self.send(AppCommand::SetBpm { bpm });
+        *self.status.write() = t!("Tap tempo: {:.0} BPM", bpm);
+    }
+}
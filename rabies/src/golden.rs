@@ -0,0 +1,391 @@
+// src/golden.rs
+// Golden-render tests: small patterns built from synthetic samples are rendered offline
+// (`AppState::render_steps`), then compared against the WAVs in
+// `tests/golden/`. After an intended change in how things sound, re-bless them with
+//
+//     RABIES_BLESS=1 cargo test golden
+//
+// and listen to the new files before committing them.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use crate::adsr::ADSREnvelope;
+use crate::audio::AudioAsset;
+use crate::bus::EngineEvent;
+use crate::gui::{AppState, ChopPlayMode, DrumTrack, StepResolution, DEFAULT_PATTERN_LEN};
+use crate::humanize::RowHumanize;
+use crate::piano_roll::PianoRollNote;
+use crate::render::write_wav;
+use crate::step_pitch::StepPitch;
+use crate::tempo_track::{bpm_at, TempoEvent};
+use crate::variation::ChopAlternate;
+
+const SAMPLE_RATE: u32  = 24000;
+const OUT_CHANNELS: usize = 2;
+/// Largest per-sample difference still counted as a match (16-bit goldens round to ±1.5e-5).
+const TOLERANCE: f32 = 1e-4;
+
+/// The pattern loaded in `app`, rendered the same way for every case.
+fn render_offline(app: &AppState, steps: usize) -> Vec<f32> {
+    app.render_steps(steps, SAMPLE_RATE, OUT_CHANNELS)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+//  Fixtures
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Exponentially decaying sine, one frequency per channel, so channel routing shows up in the render.
+fn tone(name: &str, freqs: &[f32], secs: f32, decay: f32) -> Arc<AudioAsset> {
+    tone_at(SAMPLE_RATE, name, freqs, secs, decay)
+}
+
+fn tone_at(rate: u32, name: &str, freqs: &[f32], secs: f32, decay: f32) -> Arc<AudioAsset> {
+    let frames = (secs * rate as f32) as usize;
+    let mut pcm = Vec::with_capacity(frames * freqs.len());
+    for i in 0..frames {
+        let t = i as f32 / rate as f32;
+        for f in freqs {
+            pcm.push(0.5 * (std::f32::consts::TAU * f * t).sin() * (-t * decay).exp());
+        }
+    }
+    Arc::new(AudioAsset {
+        pcm,
+        sample_rate: rate,
+        channels:    freqs.len() as u16,
+        frames:      frames as u64,
+        file_name:   name.to_string(),
+        sample_uuid: uuid::Uuid::new_v4(),
+        tags:        Default::default(),
+    })
+}
+
+fn steps_at(hits: &[usize]) -> Vec<bool> {
+    let mut row = vec![false; DEFAULT_PATTERN_LEN];
+    for &h in hits { row[h] = true; }
+    row
+}
+
+fn session(bpm: f32, tracks: Vec<DrumTrack>) -> AppState {
+    let app = AppState::default();
+    app.events.emit(EngineEvent::Bpm { bpm });
+    *app.seq_resolution.write() = StepResolution::Sixteenth;
+    *app.drum_tracks.write() = tracks;
+    app
+}
+
+fn add_marks(app: &AppState, track_idx: usize, positions: &[f32]) {
+    let tracks = app.drum_tracks.read();
+    let t = &tracks[track_idx];
+    for &p in positions {
+        app.samples_manager.mark_current_position(t.sample_uuid, &t.asset.file_name, p);
+    }
+}
+
+fn chop_track(asset: Arc<AudioAsset>, chops: usize) -> DrumTrack {
+    let mut t = DrumTrack::new(asset, None);
+    t.chop_steps        = vec![vec![false; DEFAULT_PATTERN_LEN]; chops];
+    t.chop_triplet      = vec![false; chops];
+    t.chop_adsr         = vec![ADSREnvelope::default(); chops];
+    t.chop_adsr_enabled = vec![false; chops];
+    t.chop_play_modes   = vec![ChopPlayMode::ToNextChop; chops];
+    t.chop_piano_notes  = vec![Vec::new(); chops];
+    t
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+//  WAV files (16-bit PCM) and comparison
+// ═══════════════════════════════════════════════════════════════════════════════
+
+fn golden_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden").join(format!("{}.wav", name))
+}
+
+/// Samples of a WAV written by `render::write_wav`.
+fn read_wav(path: &Path) -> Result<Vec<f32>, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    if bytes.len() < 44 || &bytes[..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(format!("{}: not a WAV file", path.display()));
+    }
+    // Walk the chunks to "data"
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let len = u32::from_le_bytes(bytes[pos + 4..pos + 8].try_into().unwrap()) as usize;
+        if &bytes[pos..pos + 4] == b"data" {
+            let end = (pos + 8 + len).min(bytes.len());
+            return Ok(bytes[pos + 8..end].chunks_exact(2)
+                .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32767.0)
+                .collect());
+        }
+        pos += 8 + len + len % 2;
+    }
+    Err(format!("{}: no data chunk", path.display()))
+}
+
+/// Compare `rendered` with the stored golden, or write it when blessing.
+fn assert_golden(name: &str, rendered: &[f32]) {
+    assert!(rendered.iter().any(|s| s.abs() > 1e-3), "{}: render is silent", name);
+    let path = golden_path(name);
+    if std::env::var_os("RABIES_BLESS").is_some() {
+        write_wav(&path, rendered, SAMPLE_RATE, OUT_CHANNELS as u16).expect("write golden");
+        return;
+    }
+    let golden = read_wav(&path)
+        .unwrap_or_else(|e| panic!("{} — run with RABIES_BLESS=1 to create it", e));
+    assert_eq!(rendered.len() / OUT_CHANNELS, golden.len() / OUT_CHANNELS,
+        "{}: length differs (frames rendered vs golden)", name);
+    let worst = rendered.iter().zip(&golden).enumerate()
+        .map(|(i, (a, b))| (i, (a - b).abs()))
+        .fold((0, 0.0f32), |w, d| if d.1 > w.1 { d } else { w });
+    assert!(worst.1 <= TOLERANCE,
+        "{}: differs by {:.6} at frame {} (ch {}), tolerance {}",
+        name, worst.1, worst.0 / OUT_CHANNELS, worst.0 % OUT_CHANNELS, TOLERANCE);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+//  Cases
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn golden_one_shot_steps() {
+    // Mono one-shot on the quarter notes, upmixed to stereo
+    let mut kick = DrumTrack::new(tone("kick", &[110.0], 0.12, 30.0), None);
+    kick.steps = steps_at(&[0, 4, 8, 12]);
+    let app = session(120.0, vec![kick]);
+    assert_golden("one_shot_steps", &render_offline(&app, 16));
+}
+
+#[test]
+fn golden_adsr_envelope() {
+    let mut pad = DrumTrack::new(tone("pad", &[330.0, 440.0], 0.4, 2.0), None);
+    pad.steps        = steps_at(&[0, 8]);
+    pad.adsr         = ADSREnvelope::new(0.02, 0.05, 0.5, 0.04);
+    pad.adsr_enabled = true;
+    let app = session(120.0, vec![pad]);
+    assert_golden("adsr_envelope", &render_offline(&app, 16));
+}
+
+#[test]
+fn golden_chops_and_triplets() {
+    let mut breaks = chop_track(tone("breaks", &[220.0, 277.0], 1.0, 1.5), 4);
+    breaks.chop_steps[0] = steps_at(&[0, 8]);
+    breaks.chop_steps[1] = steps_at(&[4]);
+    breaks.chop_steps[2] = steps_at(&[1, 4, 7]);
+    breaks.chop_triplet[2]    = true;
+    breaks.chop_play_modes[3] = ChopPlayMode::ToNextStep;
+    breaks.chop_steps[3] = steps_at(&[14, 15]);
+    let app = session(96.0, vec![breaks]);
+    add_marks(&app, 0, &[0.0, 0.25, 0.5, 0.75]);
+    assert_golden("chops_and_triplets", &render_offline(&app, 16));
+}
+
+#[test]
+fn golden_chop_alternates() {
+    // Seeded rolls: every bounce picks the same alternates
+    let mut flip = chop_track(tone("flip", &[196.0, 247.0], 1.0, 3.0), 3);
+    flip.chop_steps[0] = steps_at(&[0, 2, 4, 6, 8, 10, 12, 14]);
+    flip.chop_alternates = (0..8).map(|i| ChopAlternate { step: i * 2, chop: 0, alt: 2, chance: 0.5 }).collect();
+    let app = session(120.0, vec![flip]);
+    add_marks(&app, 0, &[0.0, 0.3, 0.6]);
+    let first = render_offline(&app, 16);
+    assert_eq!(first, render_offline(&app, 16));
+    assert_golden("chop_alternates", &first);
+}
+
+#[test]
+fn golden_pitched_piano_notes() {
+    // Piano notes resample the chop by their speed
+    let mut lead = chop_track(tone("lead", &[440.0], 0.5, 4.0), 2);
+    lead.chop_piano_notes[0] = [(0, 0), (4, 7), (8, -5), (12, 12)].iter()
+        .map(|&(step, semitone)| PianoRollNote { step, semitone, velocity: 1.0 })
+        .collect();
+    let app = session(120.0, vec![lead]);
+    add_marks(&app, 0, &[0.0, 0.5]);
+    assert_golden("pitched_piano_notes", &render_offline(&app, 16));
+}
+
+#[test]
+fn golden_step_pitch() {
+    // Pitched steps on a chop row sound just like the same notes in the piano roll
+    let mut lead = chop_track(tone("lead", &[440.0], 0.5, 4.0), 2);
+    lead.chop_steps[0] = steps_at(&[0, 4, 8, 12]);
+    lead.step_pitches = [(4, 7), (8, -5), (12, 12)].iter()
+        .map(|&(step, semitones)| StepPitch { chop: Some(0), step, semitones })
+        .collect();
+    let app = session(120.0, vec![lead]);
+    add_marks(&app, 0, &[0.0, 0.5]);
+    assert_golden("pitched_piano_notes", &render_offline(&app, 16));
+}
+
+#[test]
+fn golden_region_lane() {
+    // A region plays marker to marker from its own lane, alongside the chop rows
+    let mut phrase = chop_track(tone("phrase", &[262.0, 330.0, 392.0], 1.0, 1.0), 3);
+    phrase.chop_steps[0] = steps_at(&[0]);
+    let app = session(120.0, vec![phrase]);
+    add_marks(&app, 0, &[0.0, 0.25, 0.6]);
+    let marks = app.samples_manager.get_marks_for_sample(&app.drum_tracks.read()[0].sample_uuid);
+    let region = app.samples_manager.create_region(marks[1].id, marks[2].id, marks[1].sample_uuid);
+    app.samples_manager.set_region_steps(region, steps_at(&[4, 12]));
+    assert_golden("region_lane", &render_offline(&app, 16));
+}
+
+#[test]
+fn golden_step_velocity() {
+    // Accents: each hit scaled by its step velocity, and carried over to the triplet lane
+    let mut snare = DrumTrack::new(tone("snare", &[200.0, 330.0], 0.15, 25.0), None);
+    snare.steps = steps_at(&[0, 2, 4, 6]);
+    snare.step_velocity[2] = 0.5;
+    snare.step_velocity[4] = 0.25;
+    let mut shaker = DrumTrack::new(tone("shaker", &[2400.0], 0.05, 60.0), None);
+    shaker.steps         = steps_at(&[0, 1, 2]);
+    shaker.step_velocity = vec![0.3; DEFAULT_PATTERN_LEN];
+    shaker.triplet       = true;
+    let app = session(120.0, vec![snare, shaker]);
+    assert_golden("step_velocity", &render_offline(&app, 16));
+}
+
+#[test]
+fn golden_layered_mix() {
+    // Two stereo layers with gain, overlapping hard enough to hit the soft clipper
+    let mut a = DrumTrack::new(tone("layer_a", &[150.0, 151.0], 0.3, 6.0), None);
+    a.steps = steps_at(&[0, 2, 4, 6, 8, 10, 12, 14]);
+    a.gain  = 1.6;
+    let mut b = DrumTrack::new(tone("layer_b", &[600.0, 900.0], 0.2, 10.0), None);
+    b.steps = steps_at(&[0, 3, 6, 9, 12]);
+    b.gain  = 0.5;
+    let mut muted = DrumTrack::new(tone("muted", &[1000.0], 0.2, 1.0), None);
+    muted.steps = steps_at(&[1, 5]);
+    muted.muted = true;
+    let app = session(140.0, vec![a, b, muted]);
+    assert_golden("layered_mix", &render_offline(&app, 16));
+}
+
+#[test]
+fn golden_track_lateness() {
+    // A fixed drag on one layer and seeded random drift on the other
+    let mut clap = DrumTrack::new(tone("clap", &[900.0, 1300.0], 0.1, 40.0), None);
+    clap.steps   = steps_at(&[4, 12]);
+    clap.late_ms = 12.0;
+    let mut shaker = DrumTrack::new(tone("drift", &[2400.0], 0.05, 60.0), None);
+    shaker.steps          = steps_at(&[0, 2, 4, 6, 8, 10, 12, 14]);
+    shaker.late_jitter_ms = 20.0;
+    let app = session(120.0, vec![clap, shaker]);
+    let first = render_offline(&app, 16);
+    assert_eq!(first, render_offline(&app, 16));
+    assert_golden("track_lateness", &first);
+}
+
+#[test]
+fn golden_track_resolution() {
+    // A 1/32 hat over a 1/16 kick: the odd 32nds fall between grid steps
+    let mut kick = DrumTrack::new(tone("kick", &[110.0], 0.12, 30.0), None);
+    kick.steps = steps_at(&[0, 8]);
+    let mut hat = DrumTrack::new(tone("hat32", &[5200.0], 0.03, 80.0), None);
+    hat.resolution = Some(StepResolution::ThirtySecond);
+    hat.steps = (0..DEFAULT_PATTERN_LEN * 2).map(|s| s % 4 == 3 || s == 30).collect();
+    hat.step_velocity = vec![0.7; DEFAULT_PATTERN_LEN * 2];
+    let app = session(120.0, vec![kick, hat]);
+    assert_golden("track_resolution", &render_offline(&app, 16));
+}
+
+#[test]
+fn golden_resampled_source() {
+    // A 48 kHz file on the 24 kHz render keeps its pitch and length
+    let mut hat = DrumTrack::new(tone_at(48000, "hat_48k", &[880.0, 1320.0], 0.1, 20.0), None);
+    hat.steps = steps_at(&[0, 6, 12]);
+    let app = session(120.0, vec![hat]);
+    assert_golden("resampled_source", &render_offline(&app, 16));
+}
+
+#[test]
+fn solo_plays_only_soloed_tracks() {
+    // A solo silences every other track, chop rows included; mute still wins over solo
+    let kit = || {
+        let mut kick = DrumTrack::new(tone("kick", &[60.0], 0.3, 8.0), None);
+        kick.steps = steps_at(&[0, 8]);
+        let mut hat = DrumTrack::new(tone("hat", &[5000.0], 0.05, 60.0), None);
+        hat.steps = steps_at(&[2, 6, 10, 14]);
+        let mut perc = DrumTrack::new(tone("perc", &[700.0], 0.2, 20.0), None);
+        perc.steps = steps_at(&[4]);
+        vec![kick, hat, perc]
+    };
+    let soloed = session(120.0, kit());
+    add_marks(&soloed, 2, &[0.0]);
+    soloed.drum_tracks.write()[2].chop_steps = vec![steps_at(&[12])];
+    {
+        let mut tracks = soloed.drum_tracks.write();
+        tracks[0].soloed = true;
+        tracks[1].soloed = true;
+        tracks[1].muted  = true;
+    }
+    let mut only = kit();
+    only.truncate(1);
+    assert_eq!(render_offline(&soloed, 16), render_offline(&session(120.0, only), 16));
+}
+
+#[test]
+fn stems_sum_to_the_bounce() {
+    // Every track rolls its own dice, so humanize, drift and alternates land the same alone
+    let mut kick = DrumTrack::new(tone("kick", &[60.0], 0.3, 8.0), None);
+    kick.steps        = steps_at(&[0, 4, 8, 12]);
+    kick.row_humanize = vec![RowHumanize { chop: None, amount: 0.6 }];
+    let mut shaker = DrumTrack::new(tone("drift", &[2400.0], 0.05, 60.0), None);
+    shaker.steps          = steps_at(&[0, 2, 4, 6, 8, 10, 12, 14]);
+    shaker.late_jitter_ms = 20.0;
+    let mut flip = chop_track(tone("flip", &[196.0, 247.0], 1.0, 3.0), 3);
+    flip.chop_steps[0]   = steps_at(&[2, 6, 10, 14]);
+    flip.chop_alternates = (0..4).map(|i| ChopAlternate { step: i * 4 + 2, chop: 0, alt: 2, chance: 0.5 }).collect();
+    let mut tracks = vec![kick, shaker, flip];
+    // Quiet enough that the bounce stays under the soft clip's knee
+    for t in tracks.iter_mut() { t.gain = 0.2; }
+    let app = session(120.0, tracks);
+    add_marks(&app, 2, &[0.0, 0.3, 0.6]);
+
+    let bounce = render_offline(&app, 16);
+    let stems  = app.render_stems(1, SAMPLE_RATE, OUT_CHANNELS);
+    assert_eq!(stems.len(), 3);
+    let mut sum = vec![0.0f32; stems[0].2.len()];
+    for (_, _, pcm) in &stems {
+        for (s, x) in sum.iter_mut().zip(pcm) { *s += x; }
+    }
+    assert_eq!(sum.len(), bounce.len());
+    assert!(sum.iter().zip(&bounce).all(|(a, b)| (a - b).abs() <= TOLERANCE));
+}
+
+#[test]
+fn song_render_follows_tempo_track() {
+    // A tempo change on bar 1 overrides the sequencer BPM for the whole song
+    let kit = || {
+        let mut kick = DrumTrack::new(tone("kick", &[60.0], 0.3, 8.0), None);
+        kick.steps = steps_at(&[0, 6, 8, 13]);
+        vec![kick]
+    };
+    let song = session(120.0, kit());
+    song.song_editor.set_block(0, 0, Some(0));
+    song.song_editor.set_block(0, 1, Some(0));
+    song.set_tempo_event(TempoEvent { bar: 0, bpm: 240.0, ramp_bars: 0 });
+    let looped = session(240.0, kit());
+    assert_eq!(song.render_song(SAMPLE_RATE, OUT_CHANNELS).unwrap(), render_offline(&looped, 32));
+
+    // A ramp reaches its tempo at the end of its bars
+    let ramp = [TempoEvent { bar: 2, bpm: 160.0, ramp_bars: 4 }];
+    assert_eq!(bpm_at(&ramp, 120.0, 1.0), 120.0);
+    assert_eq!(bpm_at(&ramp, 120.0, 4.0), 140.0);
+    assert_eq!(bpm_at(&ramp, 120.0, 7.0), 160.0);
+}
+
+#[test]
+fn render_scene_sounds_like_the_live_state() {
+    // The copy a share job renders from leaves nothing out
+    let mut breaks = chop_track(tone("breaks", &[220.0, 277.0], 1.0, 1.5), 3);
+    breaks.chop_steps[0] = steps_at(&[0, 8]);
+    breaks.chop_steps[2] = steps_at(&[1, 4, 7]);
+    breaks.chop_triplet[2] = true;
+    let mut hat = DrumTrack::new(tone("hat", &[5200.0], 0.03, 80.0), None);
+    hat.steps = steps_at(&[2, 6, 10, 14]);
+    hat.gain  = 0.6;
+    let app = session(104.0, vec![breaks, hat]);
+    add_marks(&app, 0, &[0.0, 0.3, 0.6]);
+    app.master_volume.store(0.8, std::sync::atomic::Ordering::Relaxed);
+    assert_eq!(app.render_scene().into_app().render_steps(16, SAMPLE_RATE, OUT_CHANNELS), render_offline(&app, 16));
+}
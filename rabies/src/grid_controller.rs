@@ -0,0 +1,291 @@
+// src/grid_controller.rs
+// Grid controllers (Launchpad, Push, or any 4×4 pad box) as a second view of the
+// sequencer. In Steps mode every lane the step sequencer shows (each track's main row,
+// then its chops) takes as many grid rows as it needs; pressing a cell toggles that
+// step. In Pads mode the grid plays the pad layout. The LEDs mirror the screen: hits,
+// beat starts and the playhead, with only the cells that changed sent each frame.
+//
+// A Launchpad has to be in Programmer mode for its notes to match.
+
+use serde::{Deserialize, Serialize};
+use crate::bus::EngineEvent;
+use crate::gui::{lane_len, set_step, step_on, AppState, WaveformFocus};
+use crate::midi::{MidiClient, MidiMessage, MidiPort};
+
+/// LED colours, as velocities from the Launchpad/Push palette.
+const LED_OFF:      u8 = 0;
+const LED_BEAT:     u8 = 1;
+const LED_HIT:      u8 = 21;
+const LED_PLAYHEAD: u8 = 3;
+const LED_PLAY_HIT: u8 = 13;
+const LED_PAD:      u8 = 45;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GridKind {
+    /// Programmer mode: note = row·10 + column, row 1 at the bottom.
+    Launchpad,
+    /// User mode: notes 36-99 from the bottom-left, eight per row.
+    Push,
+    /// Generic 4×4 pads on notes 36-51 from the bottom-left.
+    Pads4x4,
+}
+
+impl GridKind {
+    pub const ALL: [GridKind; 3] = [GridKind::Launchpad, GridKind::Push, GridKind::Pads4x4];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GridKind::Launchpad => "Launchpad",
+            GridKind::Push      => "Push",
+            GridKind::Pads4x4   => t!("4×4 pads"),
+        }
+    }
+
+    /// Guess from the port name; anything unrecognised is treated as 4×4 pads.
+    pub fn detect(port_name: &str) -> GridKind {
+        let name = port_name.to_lowercase();
+        if name.contains("launchpad") { GridKind::Launchpad }
+        else if name.contains("push") { GridKind::Push }
+        else { GridKind::Pads4x4 }
+    }
+
+    pub fn size(&self) -> usize {
+        match self {
+            GridKind::Launchpad | GridKind::Push => 8,
+            GridKind::Pads4x4 => 4,
+        }
+    }
+
+    /// Note of the cell at `row` (0 at the top) and `col`.
+    fn note(&self, row: usize, col: usize) -> u8 {
+        let up = self.size() - 1 - row;
+        match self {
+            GridKind::Launchpad => ((up + 1) * 10 + col + 1) as u8,
+            GridKind::Push      => (36 + up * 8 + col) as u8,
+            GridKind::Pads4x4   => (36 + up * 4 + col) as u8,
+        }
+    }
+
+    fn cell(&self, note: u8) -> Option<(usize, usize)> {
+        let n = self.size();
+        (0..n * n).map(|i| (i / n, i % n)).find(|&(r, c)| self.note(r, c) == note)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GridMode {
+    Steps,
+    Pads,
+}
+
+/// One row of the step sequencer.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Lane {
+    Main(usize),
+    Chop(usize, usize),
+}
+
+pub struct GridController {
+    pub kind:       GridKind,
+    pub mode:       GridMode,
+    pub port:       MidiPort,
+    /// Lane shown on the top row in Steps mode, for projects with more than fit.
+    pub first_lane: usize,
+    midi:           MidiClient,
+    /// Colour last sent to each cell; `None` redraws them all.
+    leds:           Option<Vec<u8>>,
+}
+
+impl GridController {
+    /// Turn every LED off and forget what was lit, e.g. before the layout changes.
+    pub fn clear(&mut self) {
+        let n = self.kind.size();
+        for i in 0..n * n {
+            self.midi.send(MidiMessage::NoteOn { channel: 0, note: self.kind.note(i / n, i % n), velocity: LED_OFF });
+        }
+        self.leds = None;
+    }
+
+    fn show(&mut self, frame: &[u8]) {
+        let n = self.kind.size();
+        for (i, &colour) in frame.iter().enumerate() {
+            if self.leds.as_ref().is_some_and(|leds| leds[i] == colour) { continue; }
+            self.midi.send(MidiMessage::NoteOn { channel: 0, note: self.kind.note(i / n, i % n), velocity: colour });
+        }
+        self.leds = Some(frame.to_vec());
+    }
+}
+
+impl AppState {
+    pub fn connect_grid(&self, port: MidiPort) {
+        self.disconnect_grid();
+        let midi = match MidiClient::open("Rabies grid").and_then(|mut m| m.connect(&port).map(|_| m)) {
+            Ok(m)  => m,
+            Err(e) => { *self.status.write() = format!("✗ {}", e); return; }
+        };
+        let kind = GridKind::detect(&port.name);
+        *self.status.write() = match kind {
+            GridKind::Launchpad => t!("✓ {} connected — switch it to Programmer mode", port.name),
+            _                   => t!("✓ {} connected as {}", port.name, kind.label()),
+        };
+        *self.grid.lock() = Some(GridController { kind, mode: GridMode::Steps, port, first_lane: 0, midi, leds: None });
+    }
+
+    pub fn disconnect_grid(&self) {
+        if let Some(mut g) = self.grid.lock().take() { g.clear(); }
+    }
+
+    /// Called every frame: act on presses, then bring the LEDs up to date.
+    pub(crate) fn tick_grid(&self) {
+        let (kind, mode, first_lane, presses) = {
+            let mut guard = self.grid.lock();
+            let Some(g) = guard.as_mut() else { return; };
+            g.first_lane = g.first_lane.min(self.grid_lanes().len().saturating_sub(1));
+            let presses: Vec<(u8, u8)> = g.midi.poll().into_iter().filter_map(|m| match m {
+                MidiMessage::NoteOn { note, velocity, .. } => Some((note, velocity)),
+                _ => None,
+            }).collect();
+            (g.kind, g.mode, g.first_lane, presses)
+        };
+        for (note, velocity) in presses {
+            let Some((row, col)) = kind.cell(note) else { continue; };
+            match mode {
+                GridMode::Steps => {
+                    let cells = self.grid_step_cells(kind, first_lane);
+                    if let Some((lane, step)) = cells[row * kind.size() + col] { self.toggle_lane_step(lane, step); }
+                }
+                GridMode::Pads => {
+                    let layout = self.pad_layout.read().clone();
+                    if col >= layout.size.cols() || row >= layout.size.rows() { continue; }
+                    if let Some(pad) = layout.pad_at(row * layout.size.cols() + col) {
+                        self.trigger_pad_velocity(pad, velocity as f32 / 127.0);
+                    }
+                }
+            }
+        }
+
+        let frame = match mode {
+            GridMode::Steps => self.grid_steps_frame(kind, first_lane),
+            GridMode::Pads  => self.grid_pads_frame(kind),
+        };
+        if let Some(g) = self.grid.lock().as_mut() { g.show(&frame); }
+    }
+
+    /// Lanes in the order the step sequencer draws them.
+    pub(crate) fn grid_lanes(&self) -> Vec<Lane> {
+        let tracks = self.drum_tracks.read();
+        let mut lanes = Vec::new();
+        for (i, t) in tracks.iter().enumerate() {
+            lanes.push(Lane::Main(i));
+            let chops = self.samples_manager.get_marks_for_sample(&t.sample_uuid).len();
+            lanes.extend((0..chops).map(|c| Lane::Chop(i, c)));
+        }
+        lanes
+    }
+
+    /// Steps on `lane`, and how many of them make a beat.
+    pub(crate) fn lane_shape(&self, lane: Lane) -> (usize, usize) {
+        let len = self.pattern_len();
+        let res = *self.seq_resolution.read();
+        let main = *self.main_track_index.read();
+        let tracks = self.drum_tracks.read();
+        let steps = match lane {
+            Lane::Main(t)    => tracks.get(t).map_or(res.steps(), |tr| tr.lane(res, None, false)),
+            Lane::Chop(t, c) => tracks.get(t).map_or(res.steps(), |tr| tr.lane(res, Some(c), main == Some(t))),
+        };
+        (lane_len(len, res, steps), steps / 4)
+    }
+
+    fn lane_step_on(&self, lane: Lane, step: usize) -> bool {
+        let tracks = self.drum_tracks.read();
+        match lane {
+            Lane::Main(t) => tracks.get(t).is_some_and(|t| step_on(&t.steps, step)),
+            Lane::Chop(t, c) if Some(t) == *self.main_track_index.read() =>
+                self.seq_grid.read().get(step).is_some_and(|g| g.contains(&c)),
+            Lane::Chop(t, c) => tracks.get(t).and_then(|t| t.chop_steps.get(c)).is_some_and(|r| step_on(r, step)),
+        }
+    }
+
+    /// Same toggle as clicking the step on screen.
+    pub(crate) fn toggle_lane_step(&self, lane: Lane, step: usize) {
+        let on = !self.lane_step_on(lane, step);
+        self.set_lane_step(lane, step, on.then_some(1.0));
+    }
+
+    /// Turn a step on at `velocity` (kept on main rows only), or off with `None`.
+    pub(crate) fn set_lane_step(&self, lane: Lane, step: usize, velocity: Option<f32>) {
+        let main_idx = *self.main_track_index.read();
+        let mut tracks = self.drum_tracks.write();
+        match lane {
+            Lane::Main(t) => if let Some(t) = tracks.get_mut(t) {
+                set_step(&mut t.steps, step, velocity.is_some());
+                if let Some(v) = velocity { t.set_velocity(step, v); }
+            },
+            Lane::Chop(t, c) if Some(t) == main_idx => if let Some(sp) = self.seq_grid.write().get_mut(step) {
+                sp.retain(|&p| p != c);
+                if velocity.is_some() { sp.push(c); }
+            },
+            Lane::Chop(t, c) => if let Some(row) = tracks.get_mut(t).and_then(|t| t.chop_steps.get_mut(c)) {
+                set_step(row, step, velocity.is_some());
+            },
+        }
+        self.events.emit(EngineEvent::StepSet { lane, step, on: velocity.is_some() });
+    }
+
+    /// Lane and step under each cell, row-major. A lane wraps onto as many rows as it
+    /// needs; lanes past the bottom are left off.
+    fn grid_step_cells(&self, kind: GridKind, first_lane: usize) -> Vec<Option<(Lane, usize)>> {
+        let n = kind.size();
+        let mut cells = vec![None; n * n];
+        let mut row = 0;
+        for lane in self.grid_lanes().into_iter().skip(first_lane) {
+            let (len, _) = self.lane_shape(lane);
+            for step in 0..len {
+                let r = row + step / n;
+                if r >= n { break; }
+                cells[r * n + step % n] = Some((lane, step));
+            }
+            row += len.div_ceil(n);
+            if row >= n { break; }
+        }
+        cells
+    }
+
+    fn grid_steps_frame(&self, kind: GridKind, first_lane: usize) -> Vec<u8> {
+        let playing = self.events.playing();
+        let current = *self.seq_current_step.read();
+        let len     = self.pattern_len();
+        self.grid_step_cells(kind, first_lane).into_iter().map(|cell| {
+            let Some((lane, step)) = cell else { return LED_OFF; };
+            let (lane_len, per_beat) = self.lane_shape(lane);
+            let hit  = self.lane_step_on(lane, step);
+            let head = playing && step == current * lane_len / len;
+            match (hit, head) {
+                (true, true)   => LED_PLAY_HIT,
+                (true, false)  => LED_HIT,
+                (false, true)  => LED_PLAYHEAD,
+                (false, false) if step.is_multiple_of(per_beat.max(1)) => LED_BEAT,
+                _ => LED_OFF,
+            }
+        }).collect()
+    }
+
+    /// Slots whose pad has a chop on the focused track light up.
+    fn grid_pads_frame(&self, kind: GridKind) -> Vec<u8> {
+        let n = kind.size();
+        let layout = self.pad_layout.read().clone();
+        let chops = match *self.waveform_focus.read() {
+            WaveformFocus::DrumTrack(i) => self.drum_tracks.read().get(i)
+                .map_or(0, |t| self.samples_manager.get_marks_for_sample(&t.sample_uuid).len()),
+            _ => 0,
+        };
+        (0..n * n).map(|i| {
+            let (row, col) = (i / n, i % n);
+            if col >= layout.size.cols() || row >= layout.size.rows() { return LED_OFF; }
+            match layout.pad_at(row * layout.size.cols() + col) {
+                Some(pad) if pad < chops => LED_PAD,
+                _ => LED_OFF,
+            }
+        }).collect()
+    }
+}
@@ -0,0 +1,216 @@
+// src/grid_export.rs
+// Printable overview of a pattern: every row that has hits, by step, with velocities.
+// Built from the pattern model, written as a text grid or an SVG image.
+
+use std::fmt::Write as _;
+use std::path::Path;
+use crate::gui::{lane_len, step_on, AppState, StepResolution};
+use crate::pattern::Pattern;
+use crate::piano_roll::PianoRollNote;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GridFormat { Text, Svg }
+
+impl GridFormat {
+    pub fn label(&self) -> &'static str {
+        match self { Self::Text => t!("Text (.txt)"), Self::Svg => t!("Image (.svg)") }
+    }
+    pub fn extension(&self) -> &'static str {
+        match self { Self::Text => "txt", Self::Svg => "svg" }
+    }
+}
+
+pub struct SheetRow {
+    pub label:   String,
+    /// Runs on a triplet lane.
+    pub triplet: bool,
+    /// Steps across the pattern on this row's lane.
+    pub cells:   usize,
+    /// (step, velocity 0-1)
+    pub hits:    Vec<(usize, f32)>,
+}
+
+pub struct PatternSheet {
+    pub title:    String,
+    pub color:    (u8, u8, u8),
+    pub steps:    usize,
+    pub per_beat: usize,
+    pub rows:     Vec<SheetRow>,
+}
+
+fn row_hits(row: &[bool], n: usize) -> Vec<(usize, f32)> {
+    (0..n).filter(|&s| step_on(row, s)).map(|s| (s, 1.0)).collect()
+}
+
+fn velocity_hits(row: &[bool], vel: &[f32], n: usize) -> Vec<(usize, f32)> {
+    (0..n).filter(|&s| step_on(row, s)).map(|s| (s, vel.get(s).copied().unwrap_or(1.0).clamp(0.0, 1.0))).collect()
+}
+
+/// Loudest note on each step.
+fn note_hits(notes: &[PianoRollNote], n: usize) -> Vec<(usize, f32)> {
+    let mut hits: Vec<(usize, f32)> = Vec::new();
+    for note in notes.iter().filter(|note| note.step < n) {
+        match hits.iter_mut().find(|(s, _)| *s == note.step) {
+            Some(h) => h.1 = h.1.max(note.velocity),
+            None    => hits.push((note.step, note.velocity.clamp(0.0, 1.0))),
+        }
+    }
+    hits.sort_by_key(|h| h.0);
+    hits
+}
+
+impl PatternSheet {
+    /// Rows with at least one hit in the first `len` steps. The main grid's chops are
+    /// listed under `main_track`.
+    pub fn from_pattern(p: &Pattern, res: StepResolution, len: usize, bpm: f32, main_track: Option<usize>) -> Self {
+        let steps = len;
+        let mut rows = Vec::new();
+        let mut push = |label: String, triplet: bool, cells: usize, hits: Vec<(usize, f32)>| {
+            if !hits.is_empty() { rows.push(SheetRow { label, triplet, cells, hits }); }
+        };
+
+        for (ti, t) in p.tracks.iter().enumerate() {
+            // Tracks on their own resolution say which
+            let name = match t.resolution {
+                Some(own) => format!("{} ({})", t.file_name, own.label()),
+                None      => t.file_name.clone(),
+            };
+            let n = lane_len(len, res, t.lane(res, None, false));
+            push(name.clone(), t.triplet, n, velocity_hits(&t.steps, &t.step_velocity, n));
+            let on_main = main_track == Some(ti);
+            let chops = t.chop_steps.len().max(t.chop_piano_notes.len()).max(t.marks.len());
+            for c in 0..chops {
+                let triplet = t.row_triplet(Some(c));
+                let n = lane_len(len, res, t.lane(res, Some(c), on_main));
+                let hits = if on_main {
+                    (0..n.min(p.main_grid.len()))
+                        .filter(|&s| p.main_grid[s].contains(&c)).map(|s| (s, 1.0)).collect()
+                } else {
+                    t.chop_steps.get(c).map(|r| row_hits(r, n)).unwrap_or_default()
+                };
+                let label = if on_main { format!("{} · chop {}", t.file_name, c + 1) } else { format!("{} · chop {}", name, c + 1) };
+                push(label, triplet, n, hits);
+                if let Some(notes) = t.chop_piano_notes.get(c) {
+                    push(format!("{} · chop {} ♪", t.file_name, c + 1), false, steps, note_hits(notes, steps));
+                }
+            }
+        }
+        // Main-grid chops with no main track in the pattern
+        if main_track.is_none_or(|m| m >= p.tracks.len()) {
+            let top = p.main_grid.iter().flatten().copied().max();
+            for c in 0..top.map_or(0, |m| m + 1) {
+                let hits = (0..steps.min(p.main_grid.len()))
+                    .filter(|&s| p.main_grid[s].contains(&c)).map(|s| (s, 1.0)).collect();
+                push(format!("Main · chop {}", c + 1), false, steps, hits);
+            }
+        }
+
+        Self {
+            title: format!("{} — {} · {:.0} BPM", p.name, res.label(), bpm),
+            color: p.color,
+            steps,
+            per_beat: res.per_beat(),
+            rows,
+        }
+    }
+
+    /// Fixed-width grid: `X` full velocity, `1`–`9` tenths, `.` rest, `|` every beat.
+    /// Rows on another lane (triplets, a track's own resolution) are placed on the grid
+    /// column each hit falls in.
+    pub fn to_text(&self) -> String {
+        let width = self.rows.iter().map(|r| r.label.chars().count()).max().unwrap_or(0).max(4);
+        let pb    = self.per_beat.max(1);
+        let mut out = String::new();
+        let _ = writeln!(out, "{}", self.title);
+        let mut header = format!("{:<w$} ", "", w = width);
+        for s in 0..self.steps {
+            if s > 0 && s % pb == 0 { header.push('|'); }
+            // Beat numbers above the first step of each beat
+            header.push(if s % pb == 0 { char::from_digit(((s / pb + 1) % 10) as u32, 10).unwrap_or(' ') } else { ' ' });
+        }
+        let _ = writeln!(out, "{}", header.trim_end());
+        for row in &self.rows {
+            let mut cells = vec!['.'; self.steps];
+            for &(s, v) in &row.hits {
+                let col = s * self.steps / row.cells.max(1);
+                let ch  = if v >= 0.95 { 'X' } else { char::from_digit(((v * 10.0).round() as u32).clamp(1, 9), 10).unwrap_or('X') };
+                if let Some(c) = cells.get_mut(col) { *c = ch; }
+            }
+            let mut line = format!("{:<w$} ", row.label, w = width);
+            for (s, c) in cells.iter().enumerate() {
+                if s > 0 && s % pb == 0 { line.push('|'); }
+                line.push(*c);
+            }
+            if row.triplet { line.push_str("  ³"); }
+            let _ = writeln!(out, "{}", line);
+        }
+        if self.rows.is_empty() { let _ = writeln!(out, "(empty pattern)"); }
+        out
+    }
+
+    /// Grid image: one cell per step, filled in the pattern colour with opacity by velocity.
+    pub fn to_svg(&self) -> String {
+        const CELL: f32 = 22.0;
+        const LABEL_W: f32 = 220.0;
+        const TOP: f32 = 40.0;
+        let grid_w = CELL * self.steps as f32;
+        let (w, h)  = (LABEL_W + grid_w + 10.0, TOP + CELL * self.rows.len().max(1) as f32 + 10.0);
+        let (r, g, b) = self.color;
+        let mut s = String::new();
+        let _ = writeln!(s, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{:.0}" height="{:.0}" font-family="sans-serif" font-size="12">"#, w, h);
+        let _ = writeln!(s, r#"<rect width="100%" height="100%" fill="rgb(24,24,28)"/>"#);
+        let _ = writeln!(s, r#"<text x="8" y="20" fill="white" font-size="14">{}</text>"#, xml_escape(&self.title));
+        for (i, row) in self.rows.iter().enumerate() {
+            let y = TOP + i as f32 * CELL;
+            let label = if row.triplet { format!("{} ³", row.label) } else { row.label.clone() };
+            let _ = writeln!(s, r#"<text x="8" y="{:.1}" fill="rgb(200,200,200)">{}</text>"#, y + CELL * 0.7, xml_escape(&label));
+            let n  = row.cells;
+            let cw = grid_w / n.max(1) as f32;
+            for k in 0..n {
+                // Beats shaded alternately, as in the step grid
+                let beat  = k * self.steps / n.max(1) / self.per_beat.max(1);
+                let shade = if beat.is_multiple_of(2) { 48 } else { 38 };
+                let _ = writeln!(s, r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" rx="2" fill="rgb({},{},{})"/>"#,
+                    LABEL_W + k as f32 * cw + 1.0, y + 1.0, cw - 2.0, CELL - 2.0, shade, shade, shade + 4);
+            }
+            for &(k, v) in &row.hits {
+                let _ = writeln!(s, r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" rx="2" fill="rgb({},{},{})" fill-opacity="{:.2}"/>"#,
+                    LABEL_W + k as f32 * cw + 1.0, y + 1.0, cw - 2.0, CELL - 2.0, r, g, b, 0.25 + 0.75 * v.clamp(0.0, 1.0));
+            }
+        }
+        s.push_str("</svg>\n");
+        s
+    }
+}
+
+fn xml_escape(t: &str) -> String {
+    t.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+impl AppState {
+    /// Overview of the pattern being edited.
+    pub fn pattern_sheet(&self) -> Option<PatternSheet> {
+        self.save_current_pattern_state();
+        let p = self.song_editor.get_pattern_by_idx(self.song_editor.active_edit_idx())?;
+        Some(PatternSheet::from_pattern(&p, *self.seq_resolution.read(), self.pattern_len(),
+            self.events.bpm(), *self.main_track_index.read()))
+    }
+
+    pub fn export_pattern_grid(&self, format: GridFormat) {
+        let Some(sheet) = self.pattern_sheet() else { return; };
+        let name = sheet.title.split(" — ").next().unwrap_or("pattern").to_string();
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(format.label(), &[format.extension()])
+            .set_file_name(format!("{}.{}", name, format.extension()))
+            .save_file() else { return; };
+        *self.status.write() = match write_sheet(&path, &sheet, format) {
+            Ok(())  => t!("✓ Pattern grid saved: {}", path.display()),
+            Err(e)  => format!("✗ {}", e),
+        };
+    }
+}
+
+fn write_sheet(path: &Path, sheet: &PatternSheet, format: GridFormat) -> Result<(), String> {
+    let body = match format { GridFormat::Text => sheet.to_text(), GridFormat::Svg => sheet.to_svg() };
+    std::fs::write(path, body).map_err(|e| t!("Write {}: {}", path.display(), e))
+}
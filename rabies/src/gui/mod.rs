@@ -2,24 +2,87 @@
 use crate::playlist::PlaylistAudioTrack;
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::time::Instant;
-use parking_lot::RwLock;
-use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use cpal::{SizedSample, FromSample};
+use parking_lot::{Mutex, RwLock};
 use atomic_float::AtomicF32;
 use uuid::Uuid;
+use serde::{Deserialize, Serialize};
 use crate::audio::{AudioAsset, AudioManager, WaveformAnalysis};
-use crate::samples::{SamplesManager, PlaybackMode};
-use crate::adsr::{ADSREnvelope, Voice};
+use crate::samples::{SamplesManager, PlaybackMode, MarkerTimeUnit};
+use crate::adsr::{ADSREnvelope, Bus, Voice};
 use crate::piano_roll::PianoRollNote;
-use crate::recording::{RecordingManager, RecordingTrack, RecordState};
-use crate::pattern::{Pattern, TrackSnapshot, MarkSnapshot};
+use crate::recording::{RecordingManager, RecordingTrack, RecordState, LoopRecording, LoopTarget, RetroCapture};
+use crate::pattern::{Pattern, TrackSnapshot, MarkSnapshot, RegionSnapshot};
 use crate::playlist::SongEditor;
+use crate::macros::{MacroDest, MacroKnob, default_macros};
+use crate::pads::PadLayout;
+use crate::mapping::MappingProfiles;
+use crate::samples::{CustomRegion, SampleMark};
+use crate::step_pitch::{self, StepPitch};
+use crate::step_nudge::{self, StepNudge};
+use crate::row_timing::{self, RowOffset};
+use crate::humanize::{self, RowFeel, RowHumanize};
+use crate::variation::{self, ChopAlternate, Dice, TrackDice};
+use crate::channels::{ChannelMatrix, DownmixCoeffs};
+use crate::limiter::SafetyControls;
+use crate::looper::StutterControls;
+use crate::punch::PunchIn;
+use crate::fill::FillState;
+use crate::footswitch::Footswitch;
+use crate::midi_learn::{LearnTarget, MidiBinding};
+use crate::metronome::Metronome;
+use crate::grid_controller::GridController;
+use crate::category::{DrumCategory, RowColors};
+use crate::sync_start::ArmedStart;
+use crate::midi::{MidiInput, MidiInputSettings, MidiPort};
+use crate::netsend::NetSender;
+use crate::backend::{self, AudioBackend, BackendKind};
+use crate::devices::{AudioDeviceManager, DeviceSelection};
+use crate::engine::{Command, Engine, EngineLinks, Playhead};
+use crate::safe_mode::SelfTest;
+use crate::live_record::RecordTiming;
+use crate::bus::{EngineEvent, EventBus};
+use crate::jobs::{JobKind, Jobs};
+use crate::notes::{ProjectNotes, SampleNotes};
+use crate::grid_controller::Lane;
+
+/// Longest pattern, in steps.
+pub const MAX_STEPS: usize = 64;
+/// Pattern lengths offered, in steps.
+pub const PATTERN_LENGTHS: [usize; 4] = [8, 16, 32, 64];
+/// Pattern lengths offered on a triplet grid, which has 12 or 24 steps to the bar.
+pub const TRIPLET_PATTERN_LENGTHS: [usize; 4] = [6, 12, 24, 48];
+/// One bar of 1/16 steps.
+pub const DEFAULT_PATTERN_LEN: usize = 16;
+/// Files decoded at once when several drum tracks load together.
+const LOAD_DECODERS: usize = 4;
+
+/// Steps per bar on a row's lane: its track's own resolution (the grid's when it has
+/// none), and three for every four of those on a triplet row.
+pub fn lane_steps(grid: StepResolution, own: Option<StepResolution>, triplet: bool) -> usize {
+    let straight = own.unwrap_or(grid).steps();
+    if triplet { straight * 3 / 4 } else { straight }
+}
+
+/// Steps on a lane of `lane` steps per bar, in a pattern of `len` grid steps.
+pub fn lane_len(len: usize, grid: StepResolution, lane: usize) -> usize { len * lane / grid.steps() }
+
+/// `step` of a `from`-step lane moved to the nearest step of a `to`-step one.
+pub fn lane_step(step: usize, from: usize, to: usize) -> usize {
+    ((step * to * 2 + from) / (from.max(1) * 2)).min(to.saturating_sub(1))
+}
 
-pub const NUM_STEPS: usize = 16;
+/// Whether `step` of `row` is on; steps past the end of a short row are rests.
+pub fn step_on(row: &[bool], step: usize) -> bool { row.get(step).copied().unwrap_or(false) }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+/// Turn `step` of `row` on or off, growing a short row to reach it.
+pub fn set_step(row: &mut Vec<bool>, step: usize, on: bool) {
+    if row.len() <= step { row.resize(step + 1, false); }
+    row[step] = on;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ChopPlayMode {
     ToEnd,
     ToNextChop,
@@ -27,43 +90,259 @@ pub enum ChopPlayMode {
     ToMarker(usize),
 }
 
+#[derive(Clone)]
 pub struct DrumTrack {
     pub file_path: Option<String>,
     pub asset: Arc<AudioAsset>,
     pub waveform: Option<WaveformAnalysis>,
     pub sample_uuid: Uuid,
-    pub steps: [bool; NUM_STEPS],
-    pub chop_steps: Vec<[bool; NUM_STEPS]>,
+    /// Step rows hold one entry per pattern step (see `AppState::pattern_len`).
+    pub steps: Vec<bool>,
+    /// Level of each main-row step, 0-1 (read only where the step is on).
+    pub step_velocity: Vec<f32>,
+    pub chop_steps: Vec<Vec<bool>>,
+    /// Main row runs on the triplet lane (12 steps per bar at 1/16).
+    pub triplet: bool,
+    pub chop_triplet: Vec<bool>,
+    /// The track's rows run at this resolution rather than the grid's, their steps
+    /// counted on it. Chops on the main grid keep the grid's.
+    pub resolution: Option<StepResolution>,
     pub chop_adsr: Vec<ADSREnvelope>,
     pub chop_adsr_enabled: Vec<bool>,
     pub chop_play_modes: Vec<ChopPlayMode>,
     pub chop_piano_notes: Vec<Vec<PianoRollNote>>,
+    /// Steps where another chop sometimes plays instead.
+    pub chop_alternates: Vec<ChopAlternate>,
+    /// Semitone offsets of single steps, on the main row or a chop row.
+    pub step_pitches: Vec<StepPitch>,
+    /// Steps that sound a little late, as recorded live with micro-timing.
+    pub step_nudges: Vec<StepNudge>,
+    /// Rows that sit ahead of or behind the grid.
+    pub row_offsets: Vec<RowOffset>,
+    /// Rows whose hits are humanized as they play.
+    pub row_humanize: Vec<RowHumanize>,
     pub muted: bool,
+    /// Soloed: while any track is, only soloed ones play (mute still wins).
+    pub soloed: bool,
     pub adsr: ADSREnvelope,
     pub adsr_enabled: bool,
+    /// Input trim in dB, applied to the sample before the gain.
+    pub trim_db: f32,
+    /// Polarity flipped, for layered kicks or snares that cancel.
+    pub invert: bool,
+    /// Folded to one channel; the file itself may have more.
+    pub mono: bool,
+    /// Kick, snare, hat or other percussion, for colouring and grouping rows.
+    pub category: DrumCategory,
+    /// `category` was set by hand rather than guessed.
+    pub category_pinned: bool,
+    /// Linear playback gain.
+    pub gain: f32,
+    /// Gain from before the last loudness match, so it can be restored.
+    pub gain_before_match: Option<f32>,
+    /// Peak of the track's sounding voices, decayed by the row meter as it's drawn.
+    pub meter: Arc<AtomicF32>,
+    /// Every hit starts this many ms late…
+    pub late_ms: f32,
+    /// …plus a random 0 to this many ms, for loosely layered percussion.
+    pub late_jitter_ms: f32,
 }
 
 impl DrumTrack {
     pub fn new(asset: Arc<AudioAsset>, waveform: Option<WaveformAnalysis>) -> Self {
+        let category = crate::category::classify(&asset);
         Self {
             file_path: None,
             asset,
             waveform,
             sample_uuid: Uuid::new_v4(),
-            steps: [false; NUM_STEPS],
+            steps: vec![false; DEFAULT_PATTERN_LEN],
+            step_velocity: vec![1.0; DEFAULT_PATTERN_LEN],
             chop_steps: Vec::new(),
+            triplet: false,
+            chop_triplet: Vec::new(),
+            resolution: None,
             chop_adsr: Vec::new(),
             chop_adsr_enabled: Vec::new(),
             chop_play_modes: Vec::new(),
             chop_piano_notes: Vec::new(),
+            chop_alternates: Vec::new(),
+            step_pitches: Vec::new(),
+            step_nudges: Vec::new(),
+            row_offsets: Vec::new(),
+            row_humanize: Vec::new(),
             muted: false,
+            soloed: false,
             adsr: ADSREnvelope::default(),
             adsr_enabled: false,
+            trim_db: 0.0,
+            invert: false,
+            mono: false,
+            category,
+            category_pinned: false,
+            gain: 1.0,
+            gain_before_match: None,
+            meter: Arc::new(AtomicF32::new(0.0)),
+            late_ms: 0.0,
+            late_jitter_ms: 0.0,
+        }
+    }
+
+    /// Whether the track sounds, given whether any track is soloed.
+    pub fn audible(&self, any_solo: bool) -> bool {
+        !self.muted && (self.soloed || !any_solo)
+    }
+
+    /// Loudness-match this track to `target` LUFS, remembering the old gain.
+    /// The trim stays as it is; the gain makes up the rest.
+    pub fn match_loudness(&mut self, target: f32) -> Option<f32> {
+        let gain = crate::loudness::match_gain(&self.asset.pcm, self.asset.channels as usize, self.asset.sample_rate, target)?;
+        if self.gain_before_match.is_none() { self.gain_before_match = Some(self.gain); }
+        self.gain = gain / 10f32.powf(self.trim_db / 20.0);
+        Some(self.gain)
+    }
+
+    /// Level every voice of this track plays at: trim and polarity, then the gain.
+    pub fn level(&self) -> f32 {
+        let polarity = if self.invert { -1.0 } else { 1.0 };
+        polarity * 10f32.powf(self.trim_db / 20.0) * self.gain
+    }
+
+    /// Seconds this track's next hit starts late. Only rolls when there is jitter.
+    pub fn lateness(&self, dice: &mut Dice) -> f64 {
+        let jitter = if self.late_jitter_ms > 0.0 { dice.roll() * self.late_jitter_ms } else { 0.0 };
+        (self.late_ms + jitter) as f64 / 1000.0
+    }
+
+    pub fn restore_gain(&mut self) -> bool {
+        match self.gain_before_match.take() {
+            Some(g) => { self.gain = g; true }
+            None    => false,
         }
     }
 
+    /// Build the voice for chop `chop_idx`, honouring its ADSR and play mode.
+    pub fn chop_voice(
+        &self,
+        pcm: &Arc<Vec<f32>>,
+        chop_marks: &[SampleMark],
+        chop_idx: usize,
+        speed: f32,
+        step_secs: f64,
+    ) -> Option<Voice> {
+        let mark         = chop_marks.get(chop_idx)?;
+        let channels     = self.asset.channels as usize;
+        let total_frames = self.asset.pcm.len() / channels.max(1);
+        let start_frame  = (mark.position as f64 * total_frames as f64) as usize;
+        let adsr         = self.chop_adsr.get(chop_idx).copied().unwrap_or(self.adsr);
+        let chop_adsr_on = self.chop_adsr_enabled.get(chop_idx).copied().unwrap_or(self.adsr_enabled);
+        let play_mode    = self.chop_play_modes.get(chop_idx).copied().unwrap_or(ChopPlayMode::ToNextChop);
+
+        let end_frame = match play_mode {
+            ChopPlayMode::ToEnd => None,
+            ChopPlayMode::ToNextChop => {
+                chop_marks.get(chop_idx + 1)
+                    .map(|n| (n.position as f64 * total_frames as f64) as usize)
+            }
+            ChopPlayMode::ToNextStep => {
+                let step_frames = (step_secs * self.asset.sample_rate as f64) as usize;
+                Some(start_frame + step_frames)
+            }
+            ChopPlayMode::ToMarker(tid) => {
+                chop_marks.iter().find(|m| m.id == tid)
+                    .map(|m| (m.position as f64 * total_frames as f64) as usize)
+            }
+        };
+
+        let mut voice = Voice::new(pcm.clone(), channels, start_frame, speed, adsr, chop_adsr_on, self.asset.sample_rate);
+        voice.end_frame = end_frame;
+        voice.level     = self.level();
+        voice.bus       = Bus::Chop;
+        voice.meter     = Some(self.meter.clone());
+        Some(voice)
+    }
+
+    /// Build the voice for `region`: its first marker to its second, through the track's envelope.
+    pub fn region_voice(&self, pcm: &Arc<Vec<f32>>, marks: &[SampleMark], region: &CustomRegion) -> Option<Voice> {
+        let pos          = |id: usize| marks.iter().find(|m| m.id == id).map(|m| m.position);
+        let (a, b)       = (pos(region.from)?, pos(region.to)?);
+        let channels     = self.asset.channels as usize;
+        let total_frames = self.asset.pcm.len() / channels.max(1);
+        let frame        = |p: f32| (p as f64 * total_frames as f64) as usize;
+        let mut voice = Voice::new(pcm.clone(), channels, frame(a.min(b)), 1.0, self.adsr, self.adsr_enabled, self.asset.sample_rate);
+        voice.end_frame = Some(frame(a.max(b)));
+        voice.level     = self.level();
+        voice.bus       = Bus::Chop;
+        voice.meter     = Some(self.meter.clone());
+        Some(voice)
+    }
+
+    /// The grid moved from `from` to `to`: piano notes follow it, and so do the step
+    /// rows unless the track runs at its own resolution. Returns how many hits were merged.
+    pub fn remap_steps(&mut self, from: StepResolution, to: StepResolution) -> usize {
+        let mut merged = 0;
+        for notes in self.chop_piano_notes.iter_mut() { merged += from.remap_notes(notes, to); }
+        if self.resolution.is_none() { merged += self.remap_rows(from, to); }
+        merged
+    }
+
+    /// Move every step row, and what hangs off its steps, from lanes on `from` to `to`.
+    /// Returns how many hits were merged.
+    pub fn remap_rows(&mut self, from: StepResolution, to: StepResolution) -> usize {
+        self.step_velocity = from.remap_velocity(&self.steps, &self.step_velocity, to);
+        let (steps, mut merged) = from.remap_row(&self.steps, to);
+        self.steps = steps;
+        for row in self.chop_steps.iter_mut() {
+            let (r, m) = from.remap_row(row, to);
+            *row = r;
+            merged += m;
+        }
+        variation::move_steps(&mut self.chop_alternates, |_| true, |s| Some(from.map_step(s, to)));
+        step_pitch::move_steps(&mut self.step_pitches, |_| true, |s| Some(from.map_step(s, to)));
+        step_nudge::move_steps(&mut self.step_nudges, |_| true, |s| Some(from.map_step(s, to)));
+        merged
+    }
+
+    /// Whether the main row (`None`) or chop `c`'s row runs on the triplet lane.
+    pub fn row_triplet(&self, chop: Option<usize>) -> bool {
+        chop.map_or(self.triplet, |c| self.chop_triplet.get(c).copied().unwrap_or(false))
+    }
+
+    /// Steps per bar on the main row (`None`) or a chop row; `on_grid` for chops that
+    /// live on the main grid.
+    pub fn lane(&self, grid: StepResolution, chop: Option<usize>, on_grid: bool) -> usize {
+        lane_steps(grid, if on_grid { None } else { self.resolution }, self.row_triplet(chop))
+    }
+
+    /// Steps in each row for a pattern of `len` grid steps: the straight lane's, which
+    /// a triplet row uses the front of.
+    pub fn row_len(&self, len: usize, grid: StepResolution) -> usize {
+        lane_len(len, grid, lane_steps(grid, self.resolution, false))
+    }
+
+    /// Size every step row to a pattern of `len` grid steps, dropping steps past the end.
+    pub fn fit_steps(&mut self, len: usize, grid: StepResolution) {
+        let len = self.row_len(len, grid);
+        self.steps.resize(len, false);
+        self.step_velocity.resize(len, 1.0);
+        for row in self.chop_steps.iter_mut() { row.resize(len, false); }
+        variation::move_steps(&mut self.chop_alternates, |_| true, |s| (s < len).then_some(s));
+        step_pitch::move_steps(&mut self.step_pitches, |_| true, |s| (s < len).then_some(s));
+        step_nudge::move_steps(&mut self.step_nudges, |_| true, |s| (s < len).then_some(s));
+    }
+
+    /// The velocity of main-row step `step` (full for steps it doesn't hold).
+    pub fn velocity(&self, step: usize) -> f32 { self.step_velocity.get(step).copied().unwrap_or(1.0) }
+
+    pub fn set_velocity(&mut self, step: usize, v: f32) {
+        if self.step_velocity.len() <= step { self.step_velocity.resize(step + 1, 1.0); }
+        self.step_velocity[step] = v;
+    }
+
     pub fn ensure_chop_steps(&mut self, needed: usize) {
-        while self.chop_steps.len() < needed        { self.chop_steps.push([false; NUM_STEPS]); }
+        let len = self.steps.len();
+        while self.chop_steps.len() < needed        { self.chop_steps.push(vec![false; len]); }
+        while self.chop_triplet.len() < needed      { self.chop_triplet.push(false); }
         while self.chop_adsr.len() < needed          { self.chop_adsr.push(self.adsr); }
         while self.chop_adsr_enabled.len() < needed  { self.chop_adsr_enabled.push(false); }
         while self.chop_play_modes.len() < needed    { self.chop_play_modes.push(ChopPlayMode::ToNextChop); }
@@ -71,12 +350,280 @@ impl DrumTrack {
     }
 }
 
+/// Where "lay chops across the bar" puts chop 1..N.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChopLayout {
+    /// Chop N on the Nth free step.
+    Sequential,
+    /// Chops spaced evenly over the bar (four chops land on the beats).
+    Spread,
+}
+
+/// Project-wide quantize grid, shared by live mark placement, recording and pattern launch.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Quantize {
+    Off,
+    Quarter,
+    Eighth,
+    Sixteenth,
+    ThirtySecond,
+    QuarterTriplet,
+    EighthTriplet,
+    SixteenthTriplet,
+}
+
+impl Quantize {
+    pub const ALL: [Quantize; 8] = [
+        Quantize::Off,
+        Quantize::Quarter,
+        Quantize::Eighth,
+        Quantize::Sixteenth,
+        Quantize::ThirtySecond,
+        Quantize::QuarterTriplet,
+        Quantize::EighthTriplet,
+        Quantize::SixteenthTriplet,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Quantize::Off              => t!("Off"),
+            Quantize::Quarter          => "1/4",
+            Quantize::Eighth           => "1/8",
+            Quantize::Sixteenth        => "1/16",
+            Quantize::ThirtySecond     => "1/32",
+            Quantize::QuarterTriplet   => "1/4T",
+            Quantize::EighthTriplet    => "1/8T",
+            Quantize::SixteenthTriplet => "1/16T",
+        }
+    }
+
+    /// Grid length in beats (quarter notes), `None` when off.
+    pub fn beats(&self) -> Option<f64> {
+        match self {
+            Quantize::Off              => None,
+            Quantize::Quarter          => Some(1.0),
+            Quantize::Eighth           => Some(0.5),
+            Quantize::Sixteenth        => Some(0.25),
+            Quantize::ThirtySecond     => Some(0.125),
+            Quantize::QuarterTriplet   => Some(2.0 / 3.0),
+            Quantize::EighthTriplet    => Some(1.0 / 3.0),
+            Quantize::SixteenthTriplet => Some(1.0 / 6.0),
+        }
+    }
+
+    fn grid_secs(&self, bpm: f32) -> Option<f64> {
+        self.beats().map(|b| b * 60.0 / bpm.max(1.0) as f64)
+    }
+
+    /// Snap `secs` to the nearest grid line at `bpm`.
+    pub fn snap_secs(&self, secs: f64, bpm: f32) -> f64 {
+        match self.grid_secs(bpm) {
+            Some(g) => (secs / g).round() * g,
+            None    => secs,
+        }
+    }
+
+    /// Seconds from `secs` until the next grid line (0 when off).
+    pub fn secs_to_next(&self, secs: f64, bpm: f32) -> f64 {
+        match self.grid_secs(bpm) {
+            Some(g) => ((secs / g - 1e-9).ceil() * g - secs).max(0.0),
+            None    => 0.0,
+        }
+    }
+
+    /// True when sequencer `step` (on a grid of `per_beat` steps per beat) is
+    /// the first step at or after a grid line.
+    pub fn step_on_grid(&self, step: usize, per_beat: usize) -> bool {
+        let Some(b) = self.beats() else { return true; };
+        if step == 0 { return true; }
+        let g = b * per_beat as f64;
+        (step as f64 / g + 1e-9).floor() > ((step - 1) as f64 / g + 1e-9).floor()
+    }
+}
+
+/// How many steps one bar of the sequencer is split into: straight, or a triplet feel
+/// with three steps where the straight grid has two. How many steps a pattern runs
+/// for is separate (`AppState::pattern_len`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum StepResolution {
+    Eighth,
+    EighthTriplet,
+    #[default]
+    Sixteenth,
+    SixteenthTriplet,
+    ThirtySecond,
+}
+
+impl StepResolution {
+    pub const ALL: [StepResolution; 5] = [
+        StepResolution::Eighth, StepResolution::EighthTriplet, StepResolution::Sixteenth,
+        StepResolution::SixteenthTriplet, StepResolution::ThirtySecond,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StepResolution::Eighth           => "1/8",
+            StepResolution::EighthTriplet    => "1/8T",
+            StepResolution::Sixteenth        => "1/16",
+            StepResolution::SixteenthTriplet => "1/16T",
+            StepResolution::ThirtySecond     => "1/32",
+        }
+    }
+
+    pub fn steps(&self) -> usize {
+        match self {
+            StepResolution::Eighth           => 8,
+            StepResolution::EighthTriplet    => 12,
+            StepResolution::Sixteenth        => 16,
+            StepResolution::SixteenthTriplet => 24,
+            StepResolution::ThirtySecond     => 32,
+        }
+    }
+
+    /// The whole grid has a triplet feel; rows then have no triplet lane of their own.
+    pub fn is_triplet(&self) -> bool {
+        matches!(self, StepResolution::EighthTriplet | StepResolution::SixteenthTriplet)
+    }
+
+    /// Lengths the pattern-length picker offers on this grid.
+    pub fn pattern_lengths(&self) -> [usize; 4] {
+        if self.is_triplet() { TRIPLET_PATTERN_LENGTHS } else { PATTERN_LENGTHS }
+    }
+
+    pub fn per_beat(&self) -> usize { self.steps() / 4 }
+
+    /// Steps of a `lane`-steps-per-bar lane that fall inside grid `step` of a `len`-step
+    /// pattern, each with its offset into that step as a fraction (0-1).
+    pub fn lane_in_step(&self, step: usize, len: usize, lane: usize) -> Vec<(usize, f64)> {
+        let (n, t) = (self.steps(), lane);
+        (step * t / n..((step + 1) * t).div_ceil(n))
+            .filter_map(|k| {
+                let num = (k * n) as i64 - (step * t) as i64;
+                (k < lane_len(len, *self, t) && num >= 0 && num < t as i64).then(|| (k, num as f64 / t as f64))
+            })
+            .collect()
+    }
+
+    pub fn step_secs(&self, bpm: f32) -> f64 {
+        60.0 / bpm.max(1.0) as f64 / self.per_beat() as f64
+    }
+
+    /// Where `step` on this grid lands on `to`. Going coarser, in-between
+    /// steps fold onto the step they fall inside.
+    pub fn map_step(&self, step: usize, to: StepResolution) -> usize {
+        step * to.steps() / self.steps()
+    }
+
+    /// How long a `len`-step pattern is on `to`, so it covers the same bars.
+    pub fn map_len(&self, len: usize, to: StepResolution) -> usize {
+        (len * to.steps()).div_ceil(self.steps())
+    }
+
+    /// Move a step row onto `to`. Returns the row and how many hits were merged.
+    /// Triplet lanes scale by the same ratio, so they re-map the same way.
+    pub fn remap_row(&self, row: &[bool], to: StepResolution) -> (Vec<bool>, usize) {
+        let mut out    = vec![false; self.map_len(row.len(), to)];
+        let mut merged = 0;
+        for step in (0..row.len()).filter(|&s| row[s]) {
+            let dst = self.map_step(step, to);
+            if out[dst] { merged += 1; }
+            out[dst] = true;
+        }
+        (out, merged)
+    }
+
+    /// Velocities of `row`'s hits moved onto `to`; merged hits keep the loudest.
+    pub fn remap_velocity(&self, row: &[bool], vel: &[f32], to: StepResolution) -> Vec<f32> {
+        let mut out = vec![1.0f32; self.map_len(row.len(), to)];
+        let mut set = vec![false; out.len()];
+        for step in (0..row.len()).filter(|&s| row[s]) {
+            let (dst, v) = (self.map_step(step, to), vel.get(step).copied().unwrap_or(1.0));
+            out[dst] = if set[dst] { out[dst].max(v) } else { v };
+            set[dst] = true;
+        }
+        out
+    }
+
+    /// Move the main-sample chop grid (`[step] → chops`) onto `to`.
+    pub fn remap_grid(&self, grid: &[Vec<usize>], to: StepResolution) -> (Vec<Vec<usize>>, usize) {
+        let mut out    = vec![Vec::new(); self.map_len(grid.len(), to)];
+        let mut merged = 0;
+        for (step, chops) in grid.iter().enumerate() {
+            let dst: &mut Vec<usize> = &mut out[self.map_step(step, to)];
+            for &c in chops {
+                if dst.contains(&c) { merged += 1; } else { dst.push(c); }
+            }
+        }
+        (out, merged)
+    }
+
+    /// Move piano-roll notes onto `to`, dropping notes that collapse onto an
+    /// identical one. Returns how many were merged.
+    pub fn remap_notes(&self, notes: &mut Vec<PianoRollNote>, to: StepResolution) -> usize {
+        let before = notes.len();
+        let mut out: Vec<PianoRollNote> = Vec::with_capacity(before);
+        for mut n in notes.drain(..) {
+            n.step = self.map_step(n.step, to);
+            if !out.iter().any(|o| o.step == n.step && o.semitone == n.semitone) { out.push(n); }
+        }
+        *notes = out;
+        before - notes.len()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum WaveformFocus {
     MainSample,
     DrumTrack(usize),
 }
 
+/// A/B compare of the active pattern: the version not currently on the grid.
+pub struct AbCompare {
+    pub pattern_idx: usize,
+    /// The stashed version (B while A is showing, A while B is showing).
+    pub other:       Pattern,
+    pub showing_a:   bool,
+}
+
+/// Two chops of one track alternated back to back at the sequencer tempo.
+pub struct ChopAudition {
+    pub track_idx: usize,
+    /// A is the chop the pattern uses; B is the candidate.
+    pub a:         usize,
+    pub b:         usize,
+    /// Beats each side plays before the other takes over.
+    pub beats:     f32,
+    /// Side of the most recently scheduled hit.
+    pub hearing_b: bool,
+    next_hit:      Instant,
+    next_is_b:     bool,
+}
+
+/// State of the numeric marker editor window.
+pub struct MarkerEditor {
+    pub open:       bool,
+    pub unit:       MarkerTimeUnit,
+    /// Text being typed per mark id; refreshed from the mark while not focused.
+    pub drafts:     HashMap<usize, String>,
+    pub new_pos:    String,
+    pub focus_mark: Option<usize>,
+}
+
+impl Default for MarkerEditor {
+    fn default() -> Self {
+        Self {
+            open:       false,
+            unit:       MarkerTimeUnit::Seconds,
+            drafts:     HashMap::new(),
+            new_pos:    String::new(),
+            focus_mark: None,
+        }
+    }
+}
+
+/// An asset, a bucket count and the analysis made from them.
+type DisplayWaveform = (Arc<AudioAsset>, usize, WaveformAnalysis);
+
 pub struct AppState {
     // ── Song editor ────────────────────────────────────────────────────────
     pub song_editor:           Arc<SongEditor>,
@@ -84,47 +631,249 @@ pub struct AppState {
     pub playlist_view_open:    Arc<AtomicBool>,
     /// Drag-move source: (pattern_row, bar) held while dragging a pattern block.
     pub pl_drag_src:           Arc<RwLock<Option<(usize, usize)>>>,
+    /// Song tempo changes, sorted by bar.
+    pub tempo_events:          Arc<RwLock<Vec<crate::tempo_track::TempoEvent>>>,
     /// Audio tracks in the FL playlist arrangement view.
     pub playlist_audio_tracks: Arc<RwLock<Vec<PlaylistAudioTrack>>>,
     /// Asset pool: file_path → loaded AudioAsset (PCM only, for fast pattern switching)
     pub asset_pool:            Arc<RwLock<HashMap<String, Arc<AudioAsset>>>>,
+    /// Sample browser search (file name or tags).
+    pub browser_query:         Arc<RwLock<String>>,
 
     // ── Audio ─────────────────────────────────────────────────────────────
     pub audio_manager:    Arc<AudioManager>,
     pub samples_manager:  Arc<SamplesManager>,
     pub current_asset:    Arc<RwLock<Option<Arc<AudioAsset>>>>,
     pub waveform_analysis: Arc<RwLock<Option<WaveformAnalysis>>>,
+    /// The assets on screen (focused and pinned) analysed at the bucket count each
+    /// was last drawn with.
+    pub waveform_display: Arc<Mutex<Vec<DisplayWaveform>>>,
     pub status:           Arc<RwLock<String>>,
 
+    // ── Audio backend ─────────────────────────────────────────────────────
+    pub(crate) audio_backend:        Arc<RwLock<Box<dyn AudioBackend>>>,
+    /// JACK client name of the engine output.
+    pub jack_client_name:            Arc<RwLock<String>>,
+    /// Follow the JACK transport's play/stop and tempo.
+    pub transport_sync:              Arc<AtomicBool>,
+    /// The one output stream; opened on first use, dropped to switch backend or restart.
+    pub(crate) engine:               Arc<RwLock<Option<Engine>>>,
+    /// Output device, sample rate and buffer size for the cpal backend.
+    pub audio_devices:               Arc<AudioDeviceManager>,
+    pub audio_settings_open:         Arc<AtomicBool>,
+    /// MIDI ports found by the last scan.
+    pub midi_ports:                  Arc<RwLock<Vec<MidiPort>>>,
+    pub midi_input:                  Arc<Mutex<Option<MidiInput>>>,
+    pub midi_settings:               Arc<RwLock<MidiInputSettings>>,
+    /// Where our MIDI clock goes, when it's sent anywhere.
+    pub midi_clock_out:              Arc<Mutex<Option<crate::midi_clock_out::ClockOut>>>,
+    /// The Ableton Link session, while Link is on.
+    pub link:                        Arc<Mutex<Option<crate::link::LinkSync>>>,
+    /// CCs from the MIDI input that run transport actions.
+    pub footswitches:                Arc<RwLock<Vec<Footswitch>>>,
+    /// Recent taps, for tap tempo.
+    pub tap_times:                   Arc<Mutex<Vec<Instant>>>,
+    /// Notes and CCs bound to controls by MIDI learn.
+    pub midi_bindings:               Arc<RwLock<Vec<MidiBinding>>>,
+    /// Control waiting for the next note or CC.
+    pub midi_learn:                  Arc<RwLock<Option<LearnTarget>>>,
+
     // ── Playback ──────────────────────────────────────────────────────────
     pub(crate) playback_position:    Arc<AtomicF32>,
     pub(crate) is_playing:           Arc<AtomicBool>,
-    pub(crate) stream_handle:        Arc<RwLock<Option<cpal::Stream>>>,
     pub(crate) playback_asset:       Arc<RwLock<Option<Arc<AudioAsset>>>>,
     pub(crate) playback_sample_index: Arc<AtomicU64>,
     pub(crate) playback_stop_target:  Arc<AtomicF32>,
-    pub(crate) loading:              Arc<AtomicBool>,
+    /// Start of the region or selection playing, where a loop wraps to (< 0 for none).
+    pub(crate) playback_loop_from:    Arc<AtomicF32>,
+    /// Loop region and selection playback instead of stopping at the end marker.
+    pub playback_loop:                Arc<AtomicBool>,
+    /// Varispeed of the main playhead (1 = as recorded). Positions stay in source time.
+    pub(crate) playback_speed:       Arc<AtomicF32>,
+    /// When the playhead's latest start was heard, for lining up punch-in takes.
+    pub(crate) playback_heard_at:    Arc<Mutex<Option<Instant>>>,
+    /// The output's callback-to-speaker delay, seconds.
+    pub(crate) output_latency:       Arc<AtomicF32>,
+    /// Grains played while the playhead is dragged (see `scrub`).
+    pub(crate) scrub:                Arc<Mutex<crate::scrub::Scrub>>,
     pub(crate) dragged_mark_index:   Arc<RwLock<Option<usize>>>,
     pub(crate) selected_from_marker: Arc<RwLock<Option<usize>>>,
     pub(crate) selected_to_marker:   Arc<RwLock<Option<usize>>>,
+    /// Last normalised pointer position over the waveform ("insert at cursor").
+    pub(crate) waveform_cursor:      Arc<RwLock<Option<f32>>>,
+    /// Pointer over one waveform panel (`true` for the pinned one), taken by the other
+    /// panel on its next draw to show a matching guide line.
+    pub(crate) waveform_guide:       Arc<RwLock<Option<(bool, f32)>>>,
+    /// Markers and bar count of the tempo-from-span tool.
+    pub span_pick:                   Arc<RwLock<crate::tempo_span::SpanPick>>,
+    /// Span dragged out on a track's waveform.
+    pub(crate) waveform_selection:   Arc<RwLock<Option<crate::selection::WaveSelection>>>,
+    /// Bar lines anchored on a marker by the tempo-from-span tool.
+    pub bar_grid:                    Arc<RwLock<Option<crate::tempo_span::BarGrid>>>,
+    pub marker_editor:               Arc<RwLock<MarkerEditor>>,
 
     // ── Step sequencer ────────────────────────────────────────────────────
     pub seq_grid:         Arc<RwLock<Vec<Vec<usize>>>>,
     pub chop_adsr:        Arc<RwLock<Vec<ADSREnvelope>>>,
     pub drum_tracks:      Arc<RwLock<Vec<DrumTrack>>>,
-    pub(crate) active_voices: Arc<std::sync::Mutex<Vec<Voice>>>,
-    pub drum_loading:     Arc<AtomicBool>,
-    pub seq_bpm:          Arc<AtomicF32>,
-    pub seq_playing:      Arc<AtomicBool>,
+    /// New drum track waiting in the trim dialog.
+    pub pending_trim:     Arc<RwLock<Option<crate::trim::TrimDraft>>>,
+    /// Kit report rows while its window is open.
+    pub kit_report:       Arc<RwLock<Option<Vec<crate::kit_report::TrackReport>>>>,
+    /// Stand-ins being auditioned per track, while the swap-kit tool is open.
+    pub kit_swap:         Arc<RwLock<Option<crate::kit_swap::KitSwap>>>,
+    /// Auto-chop candidates waiting in the preview dialog.
+    pub pending_autochop: Arc<RwLock<Option<crate::autochop::AutoChopDraft>>>,
+    /// MIDI clip waiting in the import dialog.
+    pub pending_midi_import: Arc<RwLock<Option<crate::midi_file::MidiImportDraft>>>,
     pub seq_current_step: Arc<RwLock<usize>>,
     pub seq_last_step_time: Arc<RwLock<Option<Instant>>>,
-    pub(crate) seq_stream_handle: Arc<RwLock<Option<cpal::Stream>>>,
-    pub(crate) seq_voice_queue:   Arc<std::sync::Mutex<Vec<Voice>>>,
+    /// When an armed synced start is due to begin the pattern (see `sync_start`).
+    pub seq_start_at:     Arc<RwLock<Option<Instant>>>,
+    /// The armed start is the end of a count-in, not a synced start.
+    pub seq_counting_in:  Arc<AtomicBool>,
+    pub quantize:         Arc<RwLock<Quantize>>,
+    pub seq_resolution:   Arc<RwLock<StepResolution>>,
+    /// Steps in every pattern of the project (one of the grid's `pattern_lengths`).
+    pub pattern_len:      Arc<AtomicUsize>,
+    /// Colour track rows by number or by drum category.
+    pub row_colors:       Arc<RwLock<RowColors>>,
+    /// Show track rows grouped by category instead of in load order.
+    pub group_rows:       Arc<AtomicBool>,
+    /// Rolls for chop alternates, humanize and drift, per track.
+    pub dice:             Arc<Mutex<TrackDice>>,
+    pub metronome:        Arc<Mutex<Metronome>>,
+    /// Pattern waiting for the next quantize line before it takes over.
+    pub pending_pattern:  Arc<RwLock<Option<usize>>>,
+    /// Triggered pattern fill, queued or sounding.
+    pub fill:             Arc<RwLock<Option<FillState>>>,
+    pub ab_compare:       Arc<RwLock<Option<AbCompare>>>,
+    /// Undo and redo stacks for edits.
+    pub history:          Arc<Mutex<crate::history::History>>,
+    /// Tracks a multi-file load has decoded, for the next frame to add.
+    pub loaded_tracks:    Arc<Mutex<Vec<DrumTrack>>>,
+    /// Chops last seen per track, for their sidecar files.
+    pub sidecars:         Arc<Mutex<crate::sidecar::SidecarWatch>>,
+    /// Steps the shift tools move by.
+    pub pattern_shift:    Arc<AtomicUsize>,
+    pub chop_audition:    Arc<RwLock<Option<ChopAudition>>>,
+    /// Slices the "Slice into N" action makes.
+    pub slice_count:      Arc<AtomicUsize>,
+    /// Pads picked for the next audition (A, then B); `None` when not picking.
+    pub audition_picks:   Arc<RwLock<Option<Vec<usize>>>>,
+
+    // ── Sample pads ───────────────────────────────────────────────────────
+    pub pad_layout:       Arc<RwLock<PadLayout>>,
+    pub pad_edit_mode:    Arc<AtomicBool>,
+    /// Slot being dragged in the pad layout editor.
+    pub pad_drag_src:     Arc<RwLock<Option<usize>>>,
+    /// Keyboard and MIDI maps for the pads.
+    pub mapping:          Arc<RwLock<MappingProfiles>>,
+    /// Slot waiting for a key press to rebind it (layout edit mode).
+    pub key_learn_slot:   Arc<RwLock<Option<usize>>>,
+    /// Pads jump the main playhead to their marker instead of playing the chop.
+    pub pad_hot_cues:     Arc<AtomicBool>,
+    /// Folders loaded onto the pads, one file per pad.
+    pub pad_banks:        Arc<RwLock<Vec<crate::pad_bank::PadBank>>>,
+    /// Bank the pads play; `None` plays the focused track's chops.
+    pub active_pad_bank:  Arc<RwLock<Option<usize>>>,
+    /// Grid controller mirroring the sequencer, when one is connected.
+    pub grid:             Arc<Mutex<Option<GridController>>>,
+
+    // ── Project ───────────────────────────────────────────────────────────
+    pub project_path:     Arc<RwLock<Option<std::path::PathBuf>>>,
+    /// Copy imported samples into the project's `samples/` folder.
+    pub copy_on_import:   Arc<AtomicBool>,
+    /// Fold new drum tracks to one channel as they're added.
+    pub mono_oneshots:    Arc<AtomicBool>,
+    /// Where and how share previews are written.
+    pub share:            Arc<RwLock<crate::share::ShareSettings>>,
+    /// Another project waiting in the "Import from project…" window.
+    pub merge_import:     Arc<RwLock<Option<crate::project::MergeImport>>>,
+    /// An import whose samples are decoded, with how many couldn't be, for the next frame.
+    pub merge_ready:      Arc<Mutex<Option<(crate::project::MergeImport, usize)>>>,
+    pub versions_open:    Arc<AtomicBool>,
+    pub versions:         Arc<RwLock<Vec<crate::versions::VersionInfo>>>,
+    /// Note typed for the next "Save Version".
+    pub version_note:     Arc<RwLock<String>>,
+
+    // ── Master bus + performance macros ───────────────────────────────────
+    pub master_volume:    Arc<AtomicF32>,
+    /// Dip under a browser prelisten, dB (0 = none).
+    pub prelisten_duck_db: Arc<AtomicF32>,
+    /// A file's first seconds, decoded to audition before loading it.
+    pub audition:         Arc<RwLock<Option<crate::prelisten::Audition>>>,
+    /// Low-pass cutoff on the sequencer mix, Hz (20 kHz = open).
+    pub master_cutoff:    Arc<AtomicF32>,
+    /// Chop bus ↔ drum bus, 0-1 (0.5 = both full).
+    pub crossfader:       Arc<AtomicF32>,
+    pub macros:           Arc<RwLock<Vec<MacroKnob>>>,
+    pub macros_open:      Arc<AtomicBool>,
+    /// Loudness-match one-shots as they're added.
+    pub loudness_match:   Arc<AtomicBool>,
+    pub loudness_target:  Arc<AtomicF32>,
+    pub downmix:          Arc<RwLock<DownmixCoeffs>>,
+    /// Output channels of the engine stream (0 until it opens).
+    pub(crate) seq_out_channels: Arc<AtomicUsize>,
+    /// Last channel conversion applied ("mono → stereo"), shown in the transport.
+    pub channel_notice:   Arc<RwLock<Option<String>>>,
+    /// Watching the drum tracks' files for edits made outside the app.
+    pub file_watch:       Arc<Mutex<crate::reload::FileWatch>>,
+    /// Panic mute + safety limiter, applied at the end of every output.
+    pub safety:           SafetyControls,
+    /// Stutter looper hold and length, on the master output.
+    pub stutter:          StutterControls,
+    pub(crate) last_escape: Arc<RwLock<Option<Instant>>>,
+    /// Set when voice rendering panicked; outputs stay silent until `restart_engine`.
+    pub engine_fault:     Arc<RwLock<Option<String>>>,
+    /// Why audio is off for this session (`--safe-mode`, or the last one crashed).
+    pub safe_mode:        Arc<RwLock<Option<String>>>,
+    /// Startup audio self-test and its last report.
+    pub self_test:        Arc<RwLock<SelfTest>>,
+    /// Pad hits while the sequencer runs are written into the pattern.
+    pub seq_record:       Arc<AtomicBool>,
+    /// How recorded hits land on the grid.
+    pub record_timing:    Arc<RwLock<RecordTiming>>,
+    /// The take under way already has its undo point.
+    pub(crate) record_take: Arc<AtomicBool>,
+    /// Where the sequencer and editor publish what they did (see `bus`).
+    pub events:           EventBus,
+    /// Decoding, analysis and export running in the background (see `jobs`).
+    pub jobs:             Jobs,
+    /// Tasks window open.
+    pub tasks_open:       Arc<RwLock<bool>>,
+    /// Source, clearance and notes of sample files, one entry per file that has any.
+    pub sample_notes:     Arc<RwLock<Vec<SampleNotes>>>,
+    pub project_notes:    Arc<RwLock<ProjectNotes>>,
+    /// Named mixes to flip or fade between.
+    pub mix_snapshots:    Arc<RwLock<crate::mix_snapshot::MixSnapshots>>,
+    /// A snapshot recall fading in.
+    pub(crate) mix_fade:  Arc<RwLock<Option<crate::mix_snapshot::MixFade>>>,
+    /// Position of the sequencer run, for the header readout.
+    pub transport:        Arc<crate::transport_clock::TransportClock>,
+    /// Row timing offsets shown in ticks rather than ms.
+    pub row_timing_ticks: Arc<AtomicBool>,
+    /// Humanize on every row, on top of each row's own (0-1).
+    pub humanize:         Arc<AtomicF32>,
+    /// Euclidean generator settings, kept between fills.
+    pub euclid:           Arc<RwLock<crate::euclid::Euclid>>,
+    /// Copied pattern or row.
+    pub clipboard:        Arc<RwLock<Option<crate::clipboard::Clip>>>,
+    /// Where playback was, to offer back after a restart or PANIC.
+    pub resume:           Arc<Mutex<crate::resume::ResumeState>>,
+    /// Notes & credits window open.
+    pub notes_open:       Arc<AtomicBool>,
+    /// Streams the master output to LAN listeners over TCP.
+    pub net_sender:       NetSender,
 
     // ── UI focus ──────────────────────────────────────────────────────────
     pub waveform_focus:   Arc<RwLock<WaveformFocus>>,
+    /// Second waveform panel, kept above the focused one for comparing.
+    pub pinned_waveform:  Arc<RwLock<Option<WaveformFocus>>>,
     pub piano_roll_open:  Arc<RwLock<bool>>,
     pub piano_roll_chop:  Arc<RwLock<Option<(usize, usize)>>>,
+    /// (track, chop) heard alone while the sequencer plays; bounces ignore it.
+    pub chop_solo:        Arc<RwLock<Option<(usize, usize)>>>,
     pub main_track_index: Arc<RwLock<Option<usize>>>,
 
     // ── Recording ─────────────────────────────────────────────────────────
@@ -132,6 +881,11 @@ pub struct AppState {
     pub rec_tracks:       Arc<RwLock<Vec<RecordingTrack>>>,
     pub rec_active_track: Arc<RwLock<Option<usize>>>,
     pub input_devices:    Arc<RwLock<Vec<crate::recording::InputDevice>>>,
+    pub loop_rec:         Arc<RwLock<Option<LoopRecording>>>,
+    pub retro_capture:    RetroCapture,
+    /// Punch-in re-recording on a drum track, and the input it records from.
+    pub punch:            Arc<RwLock<Option<PunchIn>>>,
+    pub punch_device:     Arc<RwLock<Option<String>>>,
 }
 
 impl Default for AppState {
@@ -141,43 +895,150 @@ impl Default for AppState {
             song_editor_open:      Arc::new(AtomicBool::new(false)),
             playlist_view_open:    Arc::new(AtomicBool::new(false)),
             pl_drag_src:           Arc::new(RwLock::new(None)),
+            tempo_events:          Arc::new(RwLock::new(Vec::new())),
             playlist_audio_tracks: Arc::new(RwLock::new(Vec::new())),
             asset_pool:            Arc::new(RwLock::new(HashMap::new())),
+            browser_query:         Arc::new(RwLock::new(String::new())),
 
             audio_manager:         Arc::new(AudioManager::new()),
-            active_voices:         Arc::new(std::sync::Mutex::new(Vec::new())),
             samples_manager:       Arc::new(SamplesManager::new()),
             current_asset:         Arc::new(RwLock::new(None)),
             waveform_analysis:     Arc::new(RwLock::new(None)),
-            status:                Arc::new(RwLock::new("Click Load Sample to begin".to_string())),
+            waveform_display:      Arc::new(Mutex::new(Vec::new())),
+            status:                Arc::new(RwLock::new(t!("Click Load Sample to begin").to_string())),
             playback_stop_target:  Arc::new(AtomicF32::new(-1.0)),
+            playback_loop_from:    Arc::new(AtomicF32::new(-1.0)),
+            playback_loop:         Arc::new(AtomicBool::new(false)),
             playback_position:     Arc::new(AtomicF32::new(0.0)),
+            playback_speed:        Arc::new(AtomicF32::new(1.0)),
+            playback_heard_at:     Arc::new(Mutex::new(None)),
+            output_latency:        Arc::new(AtomicF32::new(0.0)),
+            scrub:                 Arc::new(Mutex::new(crate::scrub::Scrub::default())),
             is_playing:            Arc::new(AtomicBool::new(false)),
-            stream_handle:         Arc::new(RwLock::new(None)),
+            audio_backend:         Arc::new(RwLock::new(Box::new(backend::CpalBackend))),
+            jack_client_name:      Arc::new(RwLock::new("Rabies".to_string())),
+            transport_sync:        Arc::new(AtomicBool::new(false)),
+            engine:                Arc::new(RwLock::new(None)),
+            audio_devices:         Arc::new(AudioDeviceManager::new()),
+            audio_settings_open:   Arc::new(AtomicBool::new(false)),
+            midi_ports:            Arc::new(RwLock::new(Vec::new())),
+            midi_input:            Arc::new(Mutex::new(None)),
+            midi_settings:         Arc::new(RwLock::new(MidiInputSettings::default())),
+            midi_clock_out:        Arc::new(Mutex::new(None)),
+            link:                  Arc::new(Mutex::new(None)),
+            footswitches:          Arc::new(RwLock::new(Vec::new())),
+            tap_times:             Arc::new(Mutex::new(Vec::new())),
+            midi_bindings:         Arc::new(RwLock::new(Vec::new())),
+            midi_learn:            Arc::new(RwLock::new(None)),
             playback_asset:        Arc::new(RwLock::new(None)),
             playback_sample_index: Arc::new(AtomicU64::new(0)),
-            loading:               Arc::new(AtomicBool::new(false)),
             dragged_mark_index:    Arc::new(RwLock::new(None)),
             selected_from_marker:  Arc::new(RwLock::new(None)),
             selected_to_marker:    Arc::new(RwLock::new(None)),
-            seq_grid:              Arc::new(RwLock::new(vec![Vec::new(); NUM_STEPS])),
+            waveform_cursor:       Arc::new(RwLock::new(None)),
+            waveform_guide:        Arc::new(RwLock::new(None)),
+            span_pick:             Arc::new(RwLock::new(Default::default())),
+            waveform_selection:    Arc::new(RwLock::new(None)),
+            bar_grid:              Arc::new(RwLock::new(None)),
+            marker_editor:         Arc::new(RwLock::new(MarkerEditor::default())),
+            seq_grid:              Arc::new(RwLock::new(vec![Vec::new(); DEFAULT_PATTERN_LEN])),
             chop_adsr:             Arc::new(RwLock::new(Vec::new())),
             drum_tracks:           Arc::new(RwLock::new(Vec::new())),
-            drum_loading:          Arc::new(AtomicBool::new(false)),
-            seq_bpm:               Arc::new(AtomicF32::new(120.0)),
-            seq_playing:           Arc::new(AtomicBool::new(false)),
+            pending_trim:          Arc::new(RwLock::new(None)),
+            kit_report:            Arc::new(RwLock::new(None)),
+            kit_swap:              Arc::new(RwLock::new(None)),
+            pending_autochop:      Arc::new(RwLock::new(None)),
+            pending_midi_import:   Arc::new(RwLock::new(None)),
             seq_current_step:      Arc::new(RwLock::new(0)),
             seq_last_step_time:    Arc::new(RwLock::new(None)),
-            seq_stream_handle:     Arc::new(RwLock::new(None)),
-            seq_voice_queue:       Arc::new(std::sync::Mutex::new(Vec::new())),
+            seq_start_at:          Arc::new(RwLock::new(None)),
+            seq_counting_in:       Arc::new(AtomicBool::new(false)),
+            quantize:              Arc::new(RwLock::new(Quantize::Off)),
+            seq_resolution:        Arc::new(RwLock::new(StepResolution::default())),
+            pattern_len:           Arc::new(AtomicUsize::new(DEFAULT_PATTERN_LEN)),
+            row_colors:            Arc::new(RwLock::new(RowColors::default())),
+            group_rows:            Arc::new(AtomicBool::new(false)),
+            dice:                  Arc::new(Mutex::new(TrackDice::default())),
+            metronome:             Arc::new(Mutex::new(Metronome::default())),
+            pending_pattern:       Arc::new(RwLock::new(None)),
+            fill:                  Arc::new(RwLock::new(None)),
+            ab_compare:            Arc::new(RwLock::new(None)),
+            history:               Arc::new(Mutex::new(Default::default())),
+            loaded_tracks:         Arc::new(Mutex::new(Vec::new())),
+            sidecars:              Arc::new(Mutex::new(Default::default())),
+            pattern_shift:         Arc::new(AtomicUsize::new(1)),
+            chop_audition:         Arc::new(RwLock::new(None)),
+            slice_count:           Arc::new(AtomicUsize::new(8)),
+            audition_picks:        Arc::new(RwLock::new(None)),
+            pad_layout:            Arc::new(RwLock::new(PadLayout::default())),
+            pad_edit_mode:         Arc::new(AtomicBool::new(false)),
+            pad_drag_src:          Arc::new(RwLock::new(None)),
+            mapping:               Arc::new(RwLock::new(MappingProfiles::default())),
+            key_learn_slot:        Arc::new(RwLock::new(None)),
+            pad_hot_cues:          Arc::new(AtomicBool::new(false)),
+            pad_banks:             Arc::new(RwLock::new(Vec::new())),
+            active_pad_bank:       Arc::new(RwLock::new(None)),
+            grid:                  Arc::new(Mutex::new(None)),
+            project_path:          Arc::new(RwLock::new(None)),
+            copy_on_import:        Arc::new(AtomicBool::new(true)),
+            mono_oneshots:         Arc::new(AtomicBool::new(false)),
+            share:                 Arc::new(RwLock::new(crate::share::ShareSettings::default())),
+            merge_import:          Arc::new(RwLock::new(None)),
+            merge_ready:           Arc::new(Mutex::new(None)),
+            versions_open:         Arc::new(AtomicBool::new(false)),
+            versions:              Arc::new(RwLock::new(Vec::new())),
+            version_note:          Arc::new(RwLock::new(String::new())),
+            master_volume:         Arc::new(AtomicF32::new(1.0)),
+            prelisten_duck_db:     Arc::new(AtomicF32::new(crate::prelisten::DEFAULT_DUCK_DB)),
+            audition:              Arc::new(RwLock::new(None)),
+            master_cutoff:         Arc::new(AtomicF32::new(20000.0)),
+            crossfader:            Arc::new(AtomicF32::new(0.5)),
+            macros:                Arc::new(RwLock::new(default_macros())),
+            macros_open:           Arc::new(AtomicBool::new(false)),
+            loudness_match:        Arc::new(AtomicBool::new(false)),
+            loudness_target:       Arc::new(AtomicF32::new(crate::loudness::DEFAULT_TARGET_LUFS)),
+            downmix:               Arc::new(RwLock::new(DownmixCoeffs::default())),
+            seq_out_channels:      Arc::new(AtomicUsize::new(0)),
+            channel_notice:        Arc::new(RwLock::new(None)),
+            file_watch:            Arc::new(Mutex::new(Default::default())),
+            safety:                SafetyControls::default(),
+            stutter:               StutterControls::default(),
+            last_escape:           Arc::new(RwLock::new(None)),
+            engine_fault:          Arc::new(RwLock::new(None)),
+            safe_mode:             Arc::new(RwLock::new(None)),
+            self_test:             Arc::new(RwLock::new(SelfTest::NotRun)),
+            seq_record:            Arc::new(AtomicBool::new(false)),
+            record_timing:         Arc::new(RwLock::new(RecordTiming::Nearest)),
+            record_take:           Arc::new(AtomicBool::new(false)),
+            events:                EventBus::default(),
+            jobs:                  Jobs::default(),
+            tasks_open:            Arc::new(RwLock::new(false)),
+            sample_notes:          Arc::new(RwLock::new(Vec::new())),
+            project_notes:         Arc::new(RwLock::new(ProjectNotes::default())),
+            mix_snapshots:         Arc::new(RwLock::new(Default::default())),
+            mix_fade:              Arc::new(RwLock::new(None)),
+            transport:             Arc::new(Default::default()),
+            row_timing_ticks:      Arc::new(AtomicBool::new(false)),
+            humanize:              Arc::new(AtomicF32::new(0.0)),
+            euclid:                Arc::new(RwLock::new(Default::default())),
+            clipboard:             Arc::new(RwLock::new(None)),
+            resume:                Arc::new(Mutex::new(Default::default())),
+            notes_open:            Arc::new(AtomicBool::new(false)),
+            net_sender:            NetSender::default(),
             waveform_focus:        Arc::new(RwLock::new(WaveformFocus::MainSample)),
+            pinned_waveform:       Arc::new(RwLock::new(None)),
             piano_roll_open:       Arc::new(RwLock::new(false)),
             piano_roll_chop:       Arc::new(RwLock::new(None)),
+            chop_solo:             Arc::new(RwLock::new(None)),
             main_track_index:      Arc::new(RwLock::new(None)),
             rec_manager:           Arc::new(RecordingManager::new()),
             rec_tracks:            Arc::new(RwLock::new(Vec::new())),
             rec_active_track:      Arc::new(RwLock::new(None)),
             input_devices:         Arc::new(RwLock::new(Vec::new())),
+            loop_rec:              Arc::new(RwLock::new(None)),
+            retro_capture:         RetroCapture::new(),
+            punch:                 Arc::new(RwLock::new(None)),
+            punch_device:          Arc::new(RwLock::new(None)),
         }
     }
 }
@@ -196,21 +1057,50 @@ impl AppState {
         pattern.main_grid = self.seq_grid.read().clone();
 
         let tracks = self.drum_tracks.read();
+        // Recorded/promoted tracks have no file on disk — pool them under their
+        // name so reloading the pattern can find them.
+        for t in tracks.iter().filter(|t| t.file_path.is_none()) {
+            if !self.asset_pool.read().contains_key(&t.asset.file_name) {
+                self.pool_asset(&t.asset.file_name, t.asset.clone());
+            }
+        }
         pattern.tracks = tracks.iter().map(|t| {
             let marks = self.samples_manager.get_marks_for_sample(&t.sample_uuid);
             TrackSnapshot {
                 file_path: t.file_path.clone().unwrap_or_else(|| t.asset.file_name.clone()),
                 file_name: t.asset.file_name.clone(),
-                steps:     t.steps,
+                steps:     t.steps.clone(),
+                step_velocity: t.step_velocity.clone(),
                 chop_steps: t.chop_steps.clone(),
+                triplet:    t.triplet,
+                chop_triplet: t.chop_triplet.clone(),
+                resolution: t.resolution,
                 adsr:       t.adsr,
                 adsr_enabled: t.adsr_enabled,
                 chop_adsr:    t.chop_adsr.clone(),
                 chop_adsr_enabled: t.chop_adsr_enabled.clone(),
                 chop_play_modes:   t.chop_play_modes.clone(),
                 chop_piano_notes:  t.chop_piano_notes.clone(),
+                chop_alternates:   t.chop_alternates.clone(),
+                step_pitches:      t.step_pitches.clone(),
+                step_nudges:       t.step_nudges.clone(),
+                row_offsets:       t.row_offsets.clone(),
+                row_humanize:      t.row_humanize.clone(),
                 marks: marks.iter().map(|m| MarkSnapshot { position: m.position }).collect(),
+                regions: self.samples_manager.get_regions_for_sample(&t.sample_uuid).into_iter().filter_map(|r| {
+                    let idx = |id: usize| marks.iter().position(|m| m.id == id);
+                    Some(RegionSnapshot { from: idx(r.from)?, to: idx(r.to)?, name: r.name, steps: r.steps })
+                }).collect(),
                 muted: t.muted,
+                soloed: t.soloed,
+                trim_db: t.trim_db,
+                invert:  t.invert,
+                mono:    t.mono,
+                category: t.category_pinned.then_some(t.category),
+                gain:  t.gain,
+                gain_before_match: t.gain_before_match,
+                late_ms:        t.late_ms,
+                late_jitter_ms: t.late_jitter_ms,
             }
         }).collect();
 
@@ -223,7 +1113,10 @@ impl AppState {
             None    => return,
         };
 
-        *self.seq_grid.write() = pattern.main_grid.clone();
+        let len = self.pattern_len();
+        let mut grid = pattern.main_grid.clone();
+        grid.resize(len, Vec::new());
+        *self.seq_grid.write() = grid;
 
         {
             let existing = self.drum_tracks.read();
@@ -239,36 +1132,68 @@ impl AppState {
             if let Some(cached_asset) = pool.get(&snap.file_path) {
                 let new_uuid = Uuid::new_v4();
 
-                let asset = Arc::new(AudioAsset {
+                let asset = AudioAsset {
                     pcm: cached_asset.pcm.clone(),
                     sample_rate: cached_asset.sample_rate,
                     channels:    cached_asset.channels,
                     frames:      cached_asset.frames,
                     file_name:   cached_asset.file_name.clone(),
                     sample_uuid: new_uuid,
-                });
+                    tags:        cached_asset.tags.clone(),
+                };
+                let asset = Arc::new(if snap.mono { crate::mono::fold_to_mono(&asset) } else { asset });
 
                 let waveform = Some(self.audio_manager.analyze_waveform(&asset, 400));
 
                 let mut track = DrumTrack::new(asset.clone(), waveform);
                 track.file_path           = Some(snap.file_path.clone());
                 track.sample_uuid         = new_uuid;
-                track.steps               = snap.steps;
+                track.steps               = snap.steps.clone();
+                track.step_velocity       = snap.step_velocity.clone();
                 track.chop_steps          = snap.chop_steps.clone();
+                track.triplet             = snap.triplet;
+                track.chop_triplet        = snap.chop_triplet.clone();
+                track.resolution          = snap.resolution;
                 track.adsr                = snap.adsr;
                 track.adsr_enabled        = snap.adsr_enabled;
                 track.chop_adsr           = snap.chop_adsr.clone();
                 track.chop_adsr_enabled   = snap.chop_adsr_enabled.clone();
                 track.chop_play_modes     = snap.chop_play_modes.clone();
                 track.chop_piano_notes    = snap.chop_piano_notes.clone();
+                track.chop_alternates     = snap.chop_alternates.clone();
+                track.step_pitches        = snap.step_pitches.clone();
+                track.step_nudges         = snap.step_nudges.clone();
+                track.row_offsets         = snap.row_offsets.clone();
+                track.row_humanize        = snap.row_humanize.clone();
                 track.muted               = snap.muted;
+                track.soloed              = snap.soloed;
+                track.trim_db             = snap.trim_db;
+                track.invert              = snap.invert;
+                track.mono                = snap.mono;
+                if let Some(c) = snap.category {
+                    track.category        = c;
+                    track.category_pinned = true;
+                }
+                track.gain                = snap.gain;
+                track.gain_before_match   = snap.gain_before_match;
+                track.late_ms             = snap.late_ms;
+                track.late_jitter_ms      = snap.late_jitter_ms;
+                track.fit_steps(len, *self.seq_resolution.read());
 
-                for mark in &snap.marks {
+                let ids: Vec<usize> = snap.marks.iter().map(|mark| {
                     self.samples_manager.mark_current_position(
                         new_uuid,
                         &snap.file_name,
                         mark.position,
-                    );
+                    )
+                }).collect();
+                for r in &snap.regions {
+                    let (Some(&from), Some(&to)) = (ids.get(r.from), ids.get(r.to)) else { continue; };
+                    let id = self.samples_manager.create_region(from, to, new_uuid);
+                    self.samples_manager.rename_region(id, r.name.clone());
+                    let mut steps = r.steps.clone();
+                    steps.resize(len, false);
+                    self.samples_manager.set_region_steps(id, steps);
                 }
 
                 new_tracks.push(track);
@@ -294,17 +1219,43 @@ impl AppState {
         }
     }
 
+    /// Switch the edited pattern. While the sequencer runs with quantize on,
+    /// the switch is queued and applied by `tick_sequencer` on the next grid line.
     pub fn switch_pattern(&self, idx: usize) {
-        if idx == self.song_editor.active_edit_idx() { return; }
+        *self.fill.write() = None;
+        if idx == self.song_editor.active_edit_idx() {
+            *self.pending_pattern.write() = None;
+            return;
+        }
+        let q = *self.quantize.read();
+        if q != Quantize::Off
+            && self.events.playing()
+            && !self.song_editor.is_playing.load(Ordering::Relaxed)
+        {
+            *self.pending_pattern.write() = Some(idx);
+            let name = self.song_editor.get_pattern_by_idx(idx)
+                .map(|p| p.name.clone())
+                .unwrap_or_default();
+            *self.status.write() = t!("⏳ {} queued for next {}", name, q.label());
+            return;
+        }
+        self.apply_pattern_switch(idx);
+    }
+
+    pub(crate) fn apply_pattern_switch(&self, idx: usize) {
+        *self.pending_pattern.write() = None;
+        self.ab_commit();
         self.save_current_pattern_state();
         self.load_pattern_state(idx);
         let name = self.song_editor.get_pattern_by_idx(idx)
             .map(|p| p.name.clone())
             .unwrap_or_default();
-        *self.status.write() = format!("✓ Switched to {}", name);
+        *self.status.write() = t!("✓ Switched to {}", name);
+        self.events.emit(EngineEvent::Pattern { index: idx });
     }
 
     pub fn create_new_pattern(&self) -> usize {
+        self.ab_commit();
         self.save_current_pattern_state();
         let new_idx = self.song_editor.create_pattern();
         {
@@ -314,20 +1265,168 @@ impl AppState {
             }
         }
         *self.drum_tracks.write() = Vec::new();
-        *self.seq_grid.write()    = vec![Vec::new(); NUM_STEPS];
+        *self.seq_grid.write()    = vec![Vec::new(); self.pattern_len()];
         *self.waveform_focus.write() = WaveformFocus::MainSample;
         *self.main_track_index.write() = None;
         *self.waveform_analysis.write() = None;
         self.song_editor.set_active_edit_idx(new_idx);
         let name = self.song_editor.get_pattern_by_idx(new_idx)
             .map(|p| p.name.clone()).unwrap_or_default();
-        *self.status.write() = format!("✓ New pattern: {}", name);
+        *self.status.write() = t!("✓ New pattern: {}", name);
         new_idx
     }
 
     pub fn pool_asset(&self, file_path: &str, asset: Arc<AudioAsset>) {
         self.asset_pool.write().insert(file_path.to_string(), asset);
     }
+
+    /// New drum track from a pooled sample (fresh UUID so chop marks aren't shared).
+    pub fn add_track_from_pool(&self, file_path: &str) {
+        let Some(pooled) = self.asset_pool.read().get(file_path).cloned() else { return; };
+        let mut asset = (*pooled).clone();
+        asset.sample_uuid = Uuid::new_v4();
+        let asset    = Arc::new(asset);
+        let waveform = self.audio_manager.analyze_waveform(&asset, 400);
+        let mut track = DrumTrack::new(asset.clone(), Some(waveform));
+        track.file_path = Some(file_path.to_string());
+        self.apply_mono_import(&mut track);
+        self.push_undo(t!("track add"));
+        let restored = crate::sidecar::restore_chops(&self.samples_manager, track.sample_uuid, &asset.file_name, &[std::path::Path::new(file_path)]);
+        self.drum_tracks.write().push(track);
+        *self.status.write() = format!("{}{}", t!("✓ Track added: {}", asset.file_name), crate::sidecar::restored_note(restored));
+    }
+
+    // ── A/B compare ───────────────────────────────────────────────────────
+
+    /// Stash the active pattern as "A"; further edits become "B".
+    pub fn ab_snapshot(&self) {
+        self.save_current_pattern_state();
+        let idx = self.song_editor.active_edit_idx();
+        let Some(a) = self.song_editor.get_pattern_by_idx(idx) else { return; };
+        *self.status.write() = t!("A/B: snapshot of {} taken — edit freely, then compare", a.name);
+        *self.ab_compare.write() = Some(AbCompare { pattern_idx: idx, other: a, showing_a: false });
+    }
+
+    /// Swap the grid between A and B, keeping the focused track.
+    pub fn ab_toggle(&self) {
+        let Some(idx) = self.ab_compare.read().as_ref().map(|ab| ab.pattern_idx) else { return; };
+        self.save_current_pattern_state();
+        let Some(current) = self.song_editor.get_pattern_by_idx(idx) else { return; };
+        let showing_a = {
+            let mut guard = self.ab_compare.write();
+            let Some(ab) = guard.as_mut() else { return; };
+            let other = std::mem::replace(&mut ab.other, current);
+            self.song_editor.update_pattern_by_idx(idx, other);
+            ab.showing_a = !ab.showing_a;
+            ab.showing_a
+        };
+        let focus = self.waveform_focus.read().clone();
+        self.load_pattern_state(idx);
+        if let WaveformFocus::DrumTrack(t) = focus {
+            let wf = self.drum_tracks.read().get(t).map(|tr| tr.waveform.clone());
+            if let Some(wf) = wf {
+                *self.waveform_focus.write()    = WaveformFocus::DrumTrack(t);
+                *self.waveform_analysis.write() = wf;
+            }
+        }
+        *self.status.write() = t!("A/B: hearing {}", if showing_a { t!("A (original)") } else { t!("B (edited)") });
+    }
+
+    /// Keep the edited version (B) and end the comparison.
+    pub fn ab_commit(&self) {
+        let showing_a = match self.ab_compare.read().as_ref() { Some(ab) => ab.showing_a, None => return };
+        if showing_a { self.ab_toggle(); }
+        *self.ab_compare.write() = None;
+        *self.status.write() = t!("A/B: kept edited version").to_string();
+    }
+
+    /// Restore the original (A) and end the comparison.
+    pub fn ab_revert(&self) {
+        let showing_a = match self.ab_compare.read().as_ref() { Some(ab) => ab.showing_a, None => return };
+        if !showing_a { self.ab_toggle(); }
+        *self.ab_compare.write() = None;
+        *self.status.write() = t!("A/B: reverted to original").to_string();
+    }
+
+    // ── Chop audition ─────────────────────────────────────────────────────
+
+    pub fn start_chop_audition(&self, track_idx: usize, a: usize, b: usize) {
+        self.stop_playback();
+        *self.chop_audition.write() = Some(ChopAudition {
+            track_idx, a, b, beats: 1.0, hearing_b: false,
+            next_hit: Instant::now(), next_is_b: false,
+        });
+        *self.status.write() = t!("A/B audition: chop {} vs chop {} — Enter swaps B into the track", a + 1, b + 1);
+    }
+
+    pub fn stop_chop_audition(&self) {
+        if self.chop_audition.write().take().is_some() {
+            *self.status.write() = t!("A/B audition stopped").to_string();
+        }
+    }
+
+    /// Schedule the next audition hits a little ahead so sides join without a gap.
+    pub fn tick_chop_audition(&self) {
+        const LOOKAHEAD_SECS: f64 = 0.05;
+        let bpm = self.events.bpm().max(1.0);
+        let mut voices = Vec::new();
+        {
+            let mut guard = self.chop_audition.write();
+            let Some(au) = guard.as_mut() else { return; };
+            let tracks = self.drum_tracks.read();
+            let Some(track) = tracks.get(au.track_idx) else { *guard = None; return; };
+            let marks = self.samples_manager.get_marks_for_sample(&track.sample_uuid);
+            if au.a >= marks.len() || au.b >= marks.len() { *guard = None; return; }
+
+            let slot_secs = au.beats as f64 * 60.0 / bpm as f64;
+            let slot      = std::time::Duration::from_secs_f64(slot_secs);
+            let now       = Instant::now();
+            // After a stall, pick up from now instead of firing a burst of late hits
+            if now.duration_since(au.next_hit).as_secs_f64() > slot_secs { au.next_hit = now; }
+            // Copied once a hit is due, not every frame
+            let mut pcm = None;
+            while au.next_hit.saturating_duration_since(now).as_secs_f64() <= LOOKAHEAD_SECS {
+                let chop = if au.next_is_b { au.b } else { au.a };
+                let pcm  = pcm.get_or_insert_with(|| Arc::new(track.asset.pcm.clone()));
+                if let Some(mut v) = track.chop_voice(pcm, &marks, chop, 1.0, slot_secs) {
+                    // Cut at the slot so the other side starts clean
+                    let slot_end = v.start_frame + (slot_secs * track.asset.sample_rate as f64) as usize;
+                    v.end_frame  = Some(v.end_frame.map_or(slot_end, |e| e.min(slot_end)));
+                    v.delay      = au.next_hit.saturating_duration_since(now).as_secs_f64();
+                    voices.push(v);
+                }
+                au.hearing_b = au.next_is_b;
+                au.next_is_b = !au.next_is_b;
+                au.next_hit += slot;
+            }
+        }
+        if !voices.is_empty() { self.play_voices(voices); }
+    }
+
+    /// Put B where A was in the track: swap their step rows, lanes and notes.
+    pub fn swap_audition_into_track(&self) {
+        let (track_idx, a, b) = match self.chop_audition.read().as_ref() {
+            Some(au) => (au.track_idx, au.a, au.b),
+            None     => return,
+        };
+        {
+            let mut tracks = self.drum_tracks.write();
+            let Some(track) = tracks.get_mut(track_idx) else { return; };
+            track.ensure_chop_steps(a.max(b) + 1);
+            track.chop_steps.swap(a, b);
+            track.chop_triplet.swap(a, b);
+            track.chop_piano_notes.swap(a, b);
+            let swap = |c: usize| if c == a { b } else if c == b { a } else { c };
+            for p in track.step_pitches.iter_mut() { p.chop = p.chop.map(swap); }
+            for n in track.step_nudges.iter_mut() { n.chop = n.chop.map(swap); }
+            for o in track.row_offsets.iter_mut() { o.chop = o.chop.map(swap); }
+            for h in track.row_humanize.iter_mut() { h.chop = h.chop.map(swap); }
+        }
+        if let Some(au) = self.chop_audition.write().as_mut() {
+            std::mem::swap(&mut au.a, &mut au.b);
+        }
+        *self.status.write() = t!("✓ Chop {} now plays chop {}'s part — chop {} is the new B", b + 1, a + 1, a + 1);
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -338,6 +1437,7 @@ impl AppState {
         self.stop_playback();
         *self.playback_asset.write() = Some(asset.clone());
         let start_pos   = self.playback_position.load(Ordering::Relaxed);
+        self.playback_loop_from.store(-1.0, Ordering::Relaxed);
         let stop_target = match self.samples_manager.get_playback_mode() {
             PlaybackMode::PlayToEnd => -1.0,
             PlaybackMode::PlayToNextMarker =>
@@ -349,6 +1449,7 @@ impl AppState {
                             self.playback_position.store(from_mark.position, Ordering::Relaxed);
                             let sp = (from_mark.position as f64 * asset.pcm.len() as f64) as u64;
                             self.playback_sample_index.store(sp, Ordering::Relaxed);
+                            self.playback_loop_from.store(from_mark.position, Ordering::Relaxed);
                         }
                     }
                     self.samples_manager.get_mark_by_id(region.to).map(|m| m.position).unwrap_or(-1.0)
@@ -359,61 +1460,35 @@ impl AppState {
         self.playback_stop_target.store(stop_target, Ordering::Relaxed);
         self.is_playing.store(true, Ordering::Relaxed);
 
-        let host   = cpal::default_host();
-        let device = match host.default_output_device() {
-            Some(d) => d,
-            None => { *self.status.write() = "No audio output device".to_string(); self.is_playing.store(false, Ordering::Relaxed); return; }
-        };
-        let config = match device.default_output_config() {
-            Ok(c) => c,
-            Err(e) => { *self.status.write() = format!("Audio config error: {}", e); self.is_playing.store(false, Ordering::Relaxed); return; }
-        };
-
-        let args = StreamArgs {
-            channels: asset.channels, pcm: asset.pcm.clone(),
-            position: self.playback_position.clone(), sample_index: self.playback_sample_index.clone(),
-            is_playing: self.is_playing.clone(), total_samples: asset.pcm.len() as u64,
-            status: self.status.clone(), stop_target: self.playback_stop_target.clone(),
-        };
-
-        let stream = match config.sample_format() {
-            cpal::SampleFormat::F32 => build_stream::<f32>(&device, &config.into(), args),
-            cpal::SampleFormat::I16 => build_stream::<i16>(&device, &config.into(), args),
-            cpal::SampleFormat::U16 => build_stream::<u16>(&device, &config.into(), args),
-            _ => { *self.status.write() = "Unsupported sample format".to_string(); self.is_playing.store(false, Ordering::Relaxed); return; }
-        };
+        if !self.play_on_engine(asset.clone()) { self.is_playing.store(false, Ordering::Relaxed); return; }
+        *self.status.write() = t!("Playing: {}", asset.file_name);
+    }
 
-        match stream {
-            Ok(s) => {
-                if let Err(e) = s.play() { *self.status.write() = format!("Playback error: {}", e); self.is_playing.store(false, Ordering::Relaxed); }
-                else { *self.stream_handle.write() = Some(s); *self.status.write() = format!("Playing: {}", asset.file_name); }
-            }
-            Err(e) => { *self.status.write() = format!("Stream error: {}", e); self.is_playing.store(false, Ordering::Relaxed); }
-        }
+    /// Hand `asset` to the engine's playhead, converted to its output channels.
+    fn play_on_engine(&self, asset: Arc<AudioAsset>) -> bool {
+        let Some(out) = self.ensure_engine() else { return false; };
+        let matrix = ChannelMatrix::new(asset.channels as usize, out, &self.downmix.read());
+        if let Some(desc) = matrix.describe() { *self.channel_notice.write() = Some(desc); }
+        self.send_engine(Command::Play { asset, matrix });
+        true
     }
 
     pub fn stop_playback(&self) {
         self.is_playing.store(false, Ordering::Relaxed);
-        *self.stream_handle.write() = None;
+        self.send_engine(Command::StopPlayback);
         *self.playback_asset.write() = None;
     }
 
-    pub fn toggle_playback(&self) {
-        let asset_to_play = {
-            let focus = self.waveform_focus.read();
-            match &*focus {
-                WaveformFocus::MainSample => self.current_asset.read().clone(),
-                WaveformFocus::DrumTrack(idx) => {
-                    let tracks = self.drum_tracks.read();
-                    tracks.get(*idx).map(|t| t.asset.clone())
-                }
-            }
-        };
+    /// The sample shown in the waveform: the main sample or the focused drum track's.
+    pub fn focused_asset(&self) -> Option<Arc<AudioAsset>> {
+        self.waveform_asset(&self.waveform_focus.read())
+    }
 
-        if let Some(asset) = asset_to_play {
+    pub fn toggle_playback(&self) {
+        if let Some(asset) = self.focused_asset() {
             if self.is_playing.load(Ordering::Relaxed) {
                 self.is_playing.store(false, Ordering::Relaxed);
-                *self.status.write() = format!("Paused: {}", asset.file_name);
+                *self.status.write() = t!("Paused: {}", asset.file_name);
             } else {
                 if let PlaybackMode::CustomRegion { region_id } = self.samples_manager.get_playback_mode() {
                     if let Some(region) = self.samples_manager.get_region_by_id(region_id) {
@@ -434,96 +1509,179 @@ impl AppState {
         }
     }
 
+    /// Move the playhead to `normalized_pos` of the playing or focused sample; playback
+    /// carries on from there.
     pub fn seek_to(&self, normalized_pos: f32) {
-        if let Some(asset) = self.current_asset.read().as_ref() {
-            let was_playing = self.is_playing.load(Ordering::Relaxed);
-            self.is_playing.store(false, Ordering::Relaxed);
-            let sp = (normalized_pos as f64 * asset.pcm.len() as f64) as usize;
-            self.playback_position.store(normalized_pos, Ordering::Relaxed);
-            self.playback_sample_index.store(sp.min(asset.pcm.len()) as u64, Ordering::Relaxed);
-            let dur = asset.frames as f32 / asset.sample_rate as f32;
-            *self.status.write() = format!("Seeked to {:.2}s / {:.2}s", normalized_pos * dur, dur);
-            if was_playing { self.start_playback(asset.clone()); }
-        }
+        let Some(asset) = self.playback_asset.read().clone().or_else(|| self.focused_asset()) else { return; };
+        let pos      = normalized_pos.clamp(0.0, 1.0);
+        let channels = (asset.channels as u64).max(1);
+        let frame    = (pos as f64 * (asset.pcm.len() as u64 / channels) as f64) as u64;
+        self.playback_position.store(pos, Ordering::Relaxed);
+        self.playback_sample_index.store(frame * channels, Ordering::Relaxed);
+        let dur = asset.frames as f32 / asset.sample_rate as f32;
+        *self.status.write() = t!("Seeked to {:.2}s / {:.2}s", pos * dur, dur);
     }
 
     pub fn load_sample_as_track(&self) {
         if let Some(path) = rfd::FileDialog::new()
-            .add_filter("Audio", &["mp3","wav","flac","ogg","m4a","aac"])
+            .add_filter(t!("Audio"), &["mp3","wav","flac","ogg","m4a","aac"])
             .pick_file()
         {
             let audio_manager     = self.audio_manager.clone();
             let drum_tracks       = self.drum_tracks.clone();
-            let drum_loading      = self.drum_loading.clone();
-            let status            = self.status.clone();
             let waveform_focus    = self.waveform_focus.clone();
             let main_track_index  = self.main_track_index.clone();
             let waveform_analysis = self.waveform_analysis.clone();
             let asset_pool        = self.asset_pool.clone();
-            let path_str          = path.to_str().unwrap_or("").to_string();
-
-            drum_loading.store(true, Ordering::Relaxed);
-            std::thread::spawn(move || {
-                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                    audio_manager.load_audio(&path_str)
-                }));
-                match result {
-                    Ok(Ok(asset)) => {
-                        asset_pool.write().insert(path_str.clone(), asset.clone());
-                        let waveform  = audio_manager.analyze_waveform(&asset, 400);
-                        let mut track = DrumTrack::new(asset.clone(), Some(waveform.clone()));
-                        track.file_path = Some(path_str);
-
-                        let track_idx = {
-                            let mut tracks = drum_tracks.write();
-                            tracks.push(track);
-                            tracks.len() - 1
-                        };
-                        *waveform_focus.write()    = WaveformFocus::DrumTrack(track_idx);
-                        *waveform_analysis.write() = Some(waveform);
-                        *main_track_index.write()  = Some(track_idx);
-                        *status.write() = format!("✓ Track loaded: {}", asset.file_name);
-                    }
-                    Ok(Err(e)) => { *status.write() = format!("✗ Track load error: {}", e); }
-                    Err(_)     => { *status.write() = "✗ Track load crashed".to_string(); }
-                }
-                drum_loading.store(false, Ordering::Relaxed);
+            let samples_manager   = self.samples_manager.clone();
+            let import_dir        = self.import_dir();
+            let fold_mono         = self.mono_oneshots.load(Ordering::Relaxed);
+            let name              = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+
+            self.push_undo(t!("track add"));
+            self.spawn_job(JobKind::Load, t!("Load {}", name), move |_| {
+                let path_str = crate::project::import_path(&path, import_dir.as_deref());
+                let asset = audio_manager.load_audio(&path_str).map_err(|e| t!("Track load error: {}", e))?;
+                asset_pool.write().insert(path_str.clone(), asset.clone());
+                let fold      = fold_mono && asset.channels > 1;
+                let asset     = if fold { Arc::new(crate::mono::fold_to_mono(&asset)) } else { asset };
+                let waveform  = audio_manager.analyze_waveform(&asset, 400);
+                let mut track = DrumTrack::new(asset.clone(), Some(waveform.clone()));
+                let restored  = crate::sidecar::restore_chops(&samples_manager, track.sample_uuid, &asset.file_name,
+                    &[std::path::Path::new(&path_str), &path]);
+                track.file_path = Some(path_str);
+                track.mono      = fold;
+
+                let track_idx = {
+                    let mut tracks = drum_tracks.write();
+                    tracks.push(track);
+                    tracks.len() - 1
+                };
+                *waveform_focus.write()    = WaveformFocus::DrumTrack(track_idx);
+                *waveform_analysis.write() = Some(waveform);
+                *main_track_index.write()  = Some(track_idx);
+                Ok(format!("{}{}", t!("✓ Track loaded: {}", asset.file_name), crate::sidecar::restored_note(restored)))
             });
         }
     }
 
+    /// Pick one or more files for new drum tracks. One file opens the trim dialog;
+    /// several load straight in, see `load_drum_tracks`.
     pub fn load_drum_track(&self) {
-        if let Some(path) = rfd::FileDialog::new()
-            .add_filter("Audio", &["mp3","wav","flac","ogg","m4a","aac"])
-            .pick_file()
-        {
-            let audio_manager = self.audio_manager.clone();
-            let drum_tracks   = self.drum_tracks.clone();
-            let drum_loading  = self.drum_loading.clone();
-            let status        = self.status.clone();
-            let asset_pool    = self.asset_pool.clone();
-            let path_str      = path.to_str().unwrap_or("").to_string();
-
-            drum_loading.store(true, Ordering::Relaxed);
-            std::thread::spawn(move || {
-                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                    audio_manager.load_audio(&path_str)
-                }));
-                match result {
-                    Ok(Ok(asset)) => {
-                        asset_pool.write().insert(path_str.clone(), asset.clone());
-                        let waveform  = audio_manager.analyze_waveform(&asset, 400);
-                        let mut track = DrumTrack::new(asset.clone(), Some(waveform));
-                        track.file_path = Some(path_str);
-                        drum_tracks.write().push(track);
-                        *status.write() = format!("✓ Track added: {}", asset.file_name);
-                    }
-                    Ok(Err(e)) => { *status.write() = format!("✗ Track load error: {}", e); }
-                    Err(_)     => { *status.write() = "✗ Track load crashed".to_string(); }
+        let Some(mut paths) = rfd::FileDialog::new()
+            .add_filter(t!("Audio"), &["mp3","wav","flac","ogg","m4a","aac"])
+            .pick_files() else { return; };
+        if paths.len() > 1 {
+            self.load_drum_tracks(paths);
+        } else if let Some(path) = paths.pop() {
+            self.load_drum_track_from(path);
+        }
+    }
+
+    /// Decode `path` for a new drum track, then open the trim dialog on it.
+    pub fn load_drum_track_from(&self, path: std::path::PathBuf) {
+        let audio_manager = self.audio_manager.clone();
+        let pending_trim  = self.pending_trim.clone();
+        let import_dir    = self.import_dir();
+        let path_str      = path.to_str().unwrap_or("").to_string();
+        let name          = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let match_target  = self.loudness_match.load(Ordering::Relaxed)
+            .then(|| self.loudness_target.load(Ordering::Relaxed));
+
+        self.spawn_job(JobKind::Load, t!("Load {}", name), move |_| {
+            let asset = audio_manager.load_audio(&path_str).map_err(|e| t!("Track load error: {}", e))?;
+            // The track is created once the trim dialog is confirmed
+            let waveform = audio_manager.analyze_waveform(&asset, 400);
+            let loaded   = t!("✓ Decoded {} — set its trim", asset.file_name);
+            *pending_trim.write() = Some(crate::trim::TrimDraft {
+                path: path_str, asset, waveform,
+                start: 0.0, end: 1.0, normalize: false,
+                import_dir, match_target,
+            });
+            Ok(loaded)
+        });
+    }
+
+    /// A drum track per file, untrimmed. The files decode side by side in one load job,
+    /// and the tracks are added together, in the order the files were picked, by
+    /// `tick_loaded_tracks` on the next frame.
+    pub fn load_drum_tracks(&self, paths: Vec<std::path::PathBuf>) {
+        let audio_manager     = self.audio_manager.clone();
+        let loaded_tracks     = self.loaded_tracks.clone();
+        let asset_pool        = self.asset_pool.clone();
+        let samples_manager   = self.samples_manager.clone();
+        let import_dir        = self.import_dir();
+        let fold_mono         = self.mono_oneshots.load(Ordering::Relaxed);
+        let match_target      = self.loudness_match.load(Ordering::Relaxed)
+            .then(|| self.loudness_target.load(Ordering::Relaxed));
+        let n = paths.len();
+
+        self.spawn_job(JobKind::Load, t!("Load {} tracks", n), move |job| {
+            let load = |path: &std::path::Path| -> Result<DrumTrack, String> {
+                let path_str = crate::project::import_path(path, import_dir.as_deref());
+                let asset = audio_manager.load_audio(&path_str).map_err(|e| t!("Track load error: {}", e))?;
+                asset_pool.write().insert(path_str.clone(), asset.clone());
+                let fold      = fold_mono && asset.channels > 1;
+                let asset     = if fold { Arc::new(crate::mono::fold_to_mono(&asset)) } else { asset };
+                let waveform  = audio_manager.analyze_waveform(&asset, 400);
+                let mut track = DrumTrack::new(asset.clone(), Some(waveform));
+                crate::sidecar::restore_chops(&samples_manager, track.sample_uuid, &asset.file_name,
+                    &[std::path::Path::new(&path_str), path]);
+                track.file_path = Some(path_str);
+                track.mono      = fold;
+                if let Some(t) = match_target { track.match_loudness(t); }
+                Ok(track)
+            };
+
+            let results: Vec<Mutex<Option<Result<DrumTrack, String>>>> = paths.iter().map(|_| Mutex::new(None)).collect();
+            let done     = AtomicUsize::new(0);
+            let decoders = n.min(LOAD_DECODERS);
+            std::thread::scope(|scope| {
+                for k in 0..decoders {
+                    let (paths, results, done, load) = (&paths, &results, &done, &load);
+                    scope.spawn(move || {
+                        for i in (k..n).step_by(decoders) {
+                            if job.cancelled() { return; }
+                            *results[i].lock() = Some(load(&paths[i]));
+                            job.progress(done.fetch_add(1, Ordering::Relaxed) + 1, n);
+                        }
+                    });
                 }
-                drum_loading.store(false, Ordering::Relaxed);
             });
-        }
+            if job.cancelled() { return Err(t!("Cancelled").to_string()); }
+
+            let mut loaded = Vec::new();
+            let mut failed = 0;
+            for r in results {
+                match r.into_inner() {
+                    Some(Ok(track)) => loaded.push(track),
+                    Some(Err(e))    => { eprintln!("[import] {}", e); failed += 1; }
+                    None            => failed += 1,
+                }
+            }
+            if loaded.is_empty() { return Err(t!("None of the {} files could be loaded", n)); }
+            let added = loaded.len();
+            loaded_tracks.lock().extend(loaded);
+            Ok(t!("✓ {} tracks loaded{}", added,
+                if failed > 0 { t!(" — {} file(s) couldn't be decoded", failed) } else { String::new() }))
+        });
+    }
+
+    /// Called every frame: add the tracks a load job finished, as one undoable edit,
+    /// and show the first of them.
+    pub(crate) fn tick_loaded_tracks(&self) {
+        let loaded = std::mem::take(&mut *self.loaded_tracks.lock());
+        let Some(waveform) = loaded.first().map(|t| t.waveform.clone()) else { return; };
+        self.push_undo(t!("track add"));
+        let first = {
+            let mut tracks = self.drum_tracks.write();
+            let first = tracks.len();
+            tracks.extend(loaded);
+            first
+        };
+        *self.waveform_focus.write()    = WaveformFocus::DrumTrack(first);
+        *self.waveform_analysis.write() = waveform;
+        if self.main_track_index.read().is_none() { *self.main_track_index.write() = Some(first); }
     }
 
     pub fn switch_to_track(&self, track_idx: usize) {
@@ -531,7 +1689,7 @@ impl AppState {
         if let Some(track) = tracks.get(track_idx) {
             *self.waveform_focus.write()    = WaveformFocus::DrumTrack(track_idx);
             *self.waveform_analysis.write() = track.waveform.clone();
-            *self.status.write()            = format!("Viewing: {}", track.asset.file_name);
+            *self.status.write()            = t!("Viewing: {}", track.asset.file_name);
         }
     }
 
@@ -541,12 +1699,13 @@ impl AppState {
 
     pub fn add_rec_track(&self) {
         if self.input_devices.read().is_empty() { self.refresh_input_devices(); }
-        self.rec_tracks.write().push(RecordingTrack::new());
+        self.rec_tracks.write().push(RecordingTrack::new(self.pattern_len()));
     }
 
     pub fn start_recording(&self, track_idx: usize) {
+        if self.audio_blocked() { return; }
         if self.rec_manager.is_recording() {
-            *self.status.write() = "Already recording — stop current recording first".to_string();
+            *self.status.write() = t!("Already recording — stop current recording first").to_string();
             return;
         }
         let dev_label = {
@@ -555,7 +1714,7 @@ impl AppState {
         };
         let dev_label = match dev_label {
             Some(l) => l,
-            None => { *self.status.write() = "Select an input device first".to_string(); return; }
+            None => { *self.status.write() = t!("Select an input device first").to_string(); return; }
         };
         let dev = {
             let devices = self.input_devices.read();
@@ -563,19 +1722,35 @@ impl AppState {
         };
         let dev = match dev {
             Some(d) => d,
-            None => { *self.status.write() = format!("Device '{}' not found", dev_label); return; }
+            None => { *self.status.write() = t!("Device '{}' not found", dev_label); return; }
         };
         match self.rec_manager.start(&dev) {
             Ok(()) => {
                 *self.rec_active_track.write() = Some(track_idx);
                 if let Some(t) = self.rec_tracks.write().get_mut(track_idx) { t.state = RecordState::Recording; }
-                *self.status.write() = format!("🔴 Recording from {}", dev.device_name);
+                // While the sequencer runs, the take starts on the next quantize line
+                let q = *self.quantize.read();
+                let count_in = self.metronome.lock().settings.count_in
+                    && !self.events.playing() && self.seq_start_at.read().is_none();
+                if count_in {
+                    let wait = self.play_sequencer();
+                    self.rec_manager.set_skip_secs(wait as f32);
+                    *self.status.write() = t!("🔴 Recording from {} after a bar's count-in", dev.device_name);
+                } else if let (Some(beat), true) = (self.seq_beat_position(), q != Quantize::Off) {
+                    let bpm = self.events.bpm();
+                    let wait = q.secs_to_next(beat * 60.0 / bpm.max(1.0) as f64, bpm);
+                    self.rec_manager.set_skip_secs(wait as f32);
+                    *self.status.write() = t!("🔴 Recording from {} (from next {})", dev.device_name, q.label());
+                } else {
+                    *self.status.write() = t!("🔴 Recording from {}", dev.device_name);
+                }
             }
-            Err(e) => { *self.status.write() = format!("Record error: {}", e); }
+            Err(e) => { *self.status.write() = t!("Record error: {}", e); }
         }
     }
 
     pub fn stop_recording(&self, track_idx: usize) {
+        *self.loop_rec.write() = None;
         self.rec_manager.stop();
         *self.rec_active_track.write() = None;
         let (_dev_label, take_num) = {
@@ -594,30 +1769,232 @@ impl AppState {
                     t.state = RecordState::Recorded;
                     t.take_number += 1;
                 }
-                *self.status.write() = format!("✓ Recorded {:.2}s → {}", dur, file_name);
+                *self.status.write() = t!("✓ Recorded {:.2}s → {}", dur, file_name);
             }
             None => {
                 if let Some(t) = self.rec_tracks.write().get_mut(track_idx) { t.state = RecordState::Idle; }
-                *self.status.write() = "Recording was empty".to_string();
+                *self.status.write() = t!("Recording was empty").to_string();
             }
         }
     }
 
+    /// Arm a loop take of the track's `loop_bars` bars, starting on the next bar line.
+    /// Starts the sequencer if it is stopped.
+    pub fn start_loop_recording(&self, track_idx: usize) {
+        let bars = match self.rec_tracks.read().get(track_idx) {
+            Some(t) => t.loop_bars.max(1),
+            None    => return,
+        };
+        let was_playing = self.events.playing();
+        let beat = self.seq_beat_position();
+        self.start_recording(track_idx);
+        if *self.rec_active_track.read() != Some(track_idx) { return; }
+        // A count-in the recording started already holds the sequencer back a bar
+        let counting_in = self.seq_counting_in.load(Ordering::Relaxed);
+        if !was_playing && !counting_in { self.start_sequencer(); }
+
+        let bpm      = self.events.bpm().max(1.0);
+        let bar_secs = 4.0 * 60.0 / bpm;
+        let wait     = match beat {
+            Some(b) => (4.0 - b.rem_euclid(4.0)) as f32 * 60.0 / bpm,
+            None if counting_in => self.seq_start_at.read()
+                .map_or(0.0, |at| at.saturating_duration_since(Instant::now()).as_secs_f32()),
+            None => 0.0,
+        };
+        self.rec_manager.set_skip_secs(wait);
+        self.rec_manager.set_length_secs(Some(bars as f32 * bar_secs));
+        *self.loop_rec.write() = Some(LoopRecording { track_idx, bars_left: bars, started: false });
+        *self.status.write() = t!("🔁 Loop recording {} bar{} from next bar", bars, if bars == 1 { "" } else { "s" });
+    }
+
+    /// Called on every sequencer step: starts the bar count and finishes the take.
+    /// Bars are counted from Start rather than by pattern passes, so they hold for
+    /// patterns shorter or longer than a bar.
+    fn tick_loop_recording(&self) {
+        let per_bar = self.seq_resolution.read().steps() as u64;
+        if !self.transport.steps().saturating_sub(1).is_multiple_of(per_bar) { return; }
+        let finished = {
+            let mut lr = self.loop_rec.write();
+            let Some(l) = lr.as_mut() else { return; };
+            if !l.started {
+                l.started = true;
+                None
+            } else {
+                l.bars_left = l.bars_left.saturating_sub(1);
+                if l.bars_left == 0 { lr.take().map(|l| l.track_idx) } else { None }
+            }
+        };
+        if let Some(idx) = finished { self.finish_loop_recording(idx); }
+    }
+
+    /// Stop the loop take and turn it into a drum track (or beat chops). The
+    /// recording track is kept, empty, so the next layer can be recorded right away.
+    fn finish_loop_recording(&self, rec_idx: usize) {
+        self.stop_recording(rec_idx);
+        let (asset, bars, target) = {
+            let mut tracks = self.rec_tracks.write();
+            let Some(t) = tracks.get_mut(rec_idx) else { return; };
+            let Some(asset) = t.asset.take() else { return; };
+            t.state = RecordState::Idle;
+            (asset, t.loop_bars.max(1), t.loop_target)
+        };
+        let waveform = self.audio_manager.analyze_waveform(&asset, 400);
+        let mut drum = DrumTrack::new(asset.clone(), Some(waveform));
+        drum.fit_steps(self.pattern_len(), *self.seq_resolution.read());
+        let beats = bars as usize * 4;
+        if target == LoopTarget::ChopSet {
+            for b in 0..beats {
+                self.samples_manager.mark_current_position(
+                    drum.sample_uuid, &asset.file_name, b as f32 / beats as f32,
+                );
+            }
+            drum.ensure_chop_steps(beats);
+            // A one-bar take plays back as-is: each beat chop on its own beat
+            if bars == 1 {
+                let per_beat = self.seq_resolution.read().per_beat();
+                for (b, steps) in drum.chop_steps.iter_mut().enumerate() { set_step(steps, b * per_beat, true); }
+            }
+        } else if bars == 1 {
+            set_step(&mut drum.steps, 0, true);
+        }
+        self.push_undo(t!("track add"));
+        let new_idx = {
+            let mut tracks = self.drum_tracks.write();
+            tracks.push(drum);
+            tracks.len() - 1
+        };
+        self.switch_to_track(new_idx);
+        *self.status.write() = match target {
+            LoopTarget::DrumTrack => t!("✓ Loop take '{}' → drum track {}", asset.file_name, new_idx + 1),
+            LoopTarget::ChopSet   => t!("✓ Loop take '{}' → {} chops on track {}", asset.file_name, beats, new_idx + 1),
+        };
+    }
+
     pub fn promote_rec_to_drum(&self, rec_idx: usize) {
         let (asset_opt, steps) = {
             let tracks = self.rec_tracks.read();
-            if let Some(t) = tracks.get(rec_idx) { (t.asset.clone(), t.steps) } else { return; }
+            if let Some(t) = tracks.get(rec_idx) { (t.asset.clone(), t.steps.clone()) } else { return; }
         };
         if let Some(asset) = asset_opt {
             let waveform = self.audio_manager.analyze_waveform(&asset, 400);
             let mut drum = DrumTrack::new(asset.clone(), Some(waveform));
             drum.steps = steps;
+            drum.fit_steps(self.pattern_len(), *self.seq_resolution.read());
+            self.push_undo(t!("track add"));
             self.drum_tracks.write().push(drum);
             self.rec_tracks.write().remove(rec_idx);
-            *self.status.write() = format!("✓ Promoted '{}' to drum track", asset.file_name);
+            *self.status.write() = t!("✓ Promoted '{}' to drum track", asset.file_name);
+        }
+    }
+
+    /// Start listening on `label` into the rolling capture buffer.
+    pub fn start_retro_capture(&self, label: &str) {
+        if self.audio_blocked() { return; }
+        let dev = self.input_devices.read().iter().find(|d| d.label == label).cloned();
+        let Some(dev) = dev else {
+            *self.status.write() = t!("Device '{}' not found", label);
+            return;
+        };
+        match self.retro_capture.start(&dev) {
+            Ok(()) => *self.status.write() = t!("⏺ Capturing last {}s from {}", *self.retro_capture.seconds.read(), dev.device_name),
+            Err(e) => *self.status.write() = t!("✗ Capture error: {}", e),
         }
     }
 
+    pub fn stop_retro_capture(&self) {
+        self.retro_capture.stop();
+        *self.status.write() = t!("Capture off").to_string();
+    }
+
+    /// "Keep that!" — turn the capture buffer into a new drum track.
+    pub fn keep_retro_capture(&self) {
+        let Some(asset) = self.retro_capture.keep() else {
+            *self.status.write() = t!("✗ Capture buffer is empty").to_string();
+            return;
+        };
+        self.pool_asset(&asset.file_name, asset.clone());
+        let waveform = self.audio_manager.analyze_waveform(&asset, 400);
+        let new_idx = {
+            let mut tracks = self.drum_tracks.write();
+            tracks.push(DrumTrack::new(asset.clone(), Some(waveform)));
+            tracks.len() - 1
+        };
+        self.switch_to_track(new_idx);
+        *self.status.write() = t!("✓ Kept {:.1}s as '{}' on track {}",
+            asset.frames as f32 / asset.sample_rate as f32, asset.file_name, new_idx + 1);
+    }
+
+    /// Snap a live mark position on the focused track to the quantize grid,
+    /// measured from the start of the sample at the sequencer tempo.
+    pub fn quantize_mark_position(&self, position: f32) -> f32 {
+        let WaveformFocus::DrumTrack(idx) = self.waveform_focus.read().clone() else { return position; };
+        let dur = match self.drum_tracks.read().get(idx) {
+            Some(t) => t.asset.frames as f64 / t.asset.sample_rate as f64,
+            None    => return position,
+        };
+        if dur <= 0.0 { return position; }
+        let bpm = self.events.bpm();
+        let snapped = self.quantize.read().snap_secs(position as f64 * dur, bpm);
+        (snapped / dur).clamp(0.0, 1.0) as f32
+    }
+
+    /// Drop a chop marker at `position` (0-1) on the focused drum track.
+    pub fn insert_marker_at(&self, position: f32) -> Option<usize> {
+        let WaveformFocus::DrumTrack(idx) = self.waveform_focus.read().clone() else { return None; };
+        let (file_name, dur, sample_uuid) = {
+            let tracks = self.drum_tracks.read();
+            tracks.get(idx).map(|t| (
+                t.asset.file_name.clone(),
+                t.asset.frames as f32 / t.asset.sample_rate as f32,
+                t.sample_uuid,
+            ))?
+        };
+        let position = position.clamp(0.0, 1.0);
+        self.push_undo(t!("marker"));
+        let id = self.samples_manager.mark_current_position(sample_uuid, &file_name, position);
+        *self.status.write() = t!("✓ Chopped {} at {:.2}s", file_name, position * dur);
+        Some(id)
+    }
+
+    /// Delete chop `c_idx` of track `t_idx`: its marker and every per-chop array entry.
+    pub fn remove_chop(&self, t_idx: usize, c_idx: usize) {
+        let uuid = self.drum_tracks.read().get(t_idx).map(|t| t.sample_uuid);
+        if let Some(uuid) = uuid {
+            // Find and delete the mark from the global marks list
+            let marks = self.samples_manager.get_marks_for_sample(&uuid);
+            if let Some(mark) = marks.get(c_idx) {
+                let mark_id = mark.id;
+                let global_idx = self.samples_manager.get_marks()
+                    .iter()
+                    .position(|m| m.id == mark_id);
+                if let Some(gi) = global_idx {
+                    self.samples_manager.delete_mark(gi);
+                }
+            }
+        }
+        // Remove corresponding per-chop arrays at that index
+        let mut tracks = self.drum_tracks.write();
+        if let Some(t) = tracks.get_mut(t_idx) {
+            if c_idx < t.chop_steps.len()       { t.chop_steps.remove(c_idx); }
+            if c_idx < t.chop_triplet.len()     { t.chop_triplet.remove(c_idx); }
+            if c_idx < t.chop_adsr.len()        { t.chop_adsr.remove(c_idx); }
+            if c_idx < t.chop_adsr_enabled.len(){ t.chop_adsr_enabled.remove(c_idx); }
+            if c_idx < t.chop_play_modes.len()  { t.chop_play_modes.remove(c_idx); }
+            if c_idx < t.chop_piano_notes.len() { t.chop_piano_notes.remove(c_idx); }
+            variation::remove_chop(&mut t.chop_alternates, c_idx);
+            step_pitch::remove_chop(&mut t.step_pitches, c_idx);
+            step_nudge::remove_chop(&mut t.step_nudges, c_idx);
+            row_timing::remove_chop(&mut t.row_offsets, c_idx);
+            humanize::remove_chop(&mut t.row_humanize, c_idx);
+        }
+        let mut solo = self.chop_solo.write();
+        *solo = match *solo {
+            Some((t, c)) if t == t_idx && c == c_idx => None,
+            Some((t, c)) if t == t_idx && c > c_idx  => Some((t, c - 1)),
+            other => other,
+        };
+    }
+
     pub fn focused_display(&self) -> (Option<Arc<AudioAsset>>, Option<WaveformAnalysis>) {
         match self.waveform_focus.read().clone() {
             WaveformFocus::MainSample => (
@@ -634,6 +2011,29 @@ impl AppState {
             }
         }
     }
+
+    /// `asset` analysed into `buckets` columns, re-analysed only when the asset or the
+    /// count changes (a resize, or moving to a screen with another scale). The two most
+    /// recent analyses are kept, one per waveform panel.
+    pub fn display_waveform(&self, asset: &Arc<AudioAsset>, buckets: usize) -> WaveformAnalysis {
+        let mut cache = self.waveform_display.lock();
+        if let Some((_, _, wf)) = cache.iter().find(|(a, n, _)| Arc::ptr_eq(a, asset) && *n == buckets) {
+            return wf.clone();
+        }
+        let wf = self.audio_manager.analyze_waveform(asset, buckets);
+        cache.retain(|(a, _, _)| !Arc::ptr_eq(a, asset));
+        if cache.len() >= 2 { cache.remove(0); }
+        cache.push((asset.clone(), buckets, wf.clone()));
+        wf
+    }
+
+    /// Asset a waveform panel showing `focus` draws; a track that's gone shows nothing.
+    pub fn waveform_asset(&self, focus: &WaveformFocus) -> Option<Arc<AudioAsset>> {
+        match focus {
+            WaveformFocus::MainSample     => self.current_asset.read().clone(),
+            WaveformFocus::DrumTrack(idx) => self.drum_tracks.read().get(*idx).map(|t| t.asset.clone()),
+        }
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -644,7 +2044,7 @@ impl AppState {
     pub fn add_playlist_audio_track(&self) {
         let idx = self.playlist_audio_tracks.read().len();
         self.playlist_audio_tracks.write().push(PlaylistAudioTrack::new(idx));
-        *self.status.write() = format!(
+        *self.status.write() = t!(
             "Audio track {} added — double-click its label to load a file", idx + 1
         );
     }
@@ -653,8 +2053,8 @@ impl AppState {
     /// thumbnail in the playlist audio track at `track_idx`.
     pub fn load_audio_into_playlist_track(&self, track_idx: usize) {
         let path = match rfd::FileDialog::new()
-            .add_filter("Audio", &["wav","mp3","flac","ogg","aiff","aif","m4a"])
-            .set_title("Load Audio into Playlist Track")
+            .add_filter(t!("Audio"), &["wav","mp3","flac","ogg","aiff","aif","m4a"])
+            .set_title(t!("Load Audio into Playlist Track"))
             .pick_file()
         {
             Some(p) => p,
@@ -664,7 +2064,7 @@ impl AppState {
         let path_str = path.to_string_lossy().to_string();
         let asset = match self.audio_manager.load_audio(&path_str) {
             Ok(a)  => a,
-            Err(e) => { *self.status.write() = format!("Failed to load audio: {}", e); return; }
+            Err(e) => { *self.status.write() = t!("Failed to load audio: {}", e); return; }
         };
 
         let waveform = self.audio_manager.analyze_waveform(&asset, 512);
@@ -680,7 +2080,7 @@ impl AppState {
             t.source_waveform = Some(waveform);
         }
 
-        *self.status.write() = format!(
+        *self.status.write() = t!(
             "Loaded \"{}\" into audio track {}",
             path.file_name().and_then(|n| n.to_str()).unwrap_or("?"),
             track_idx + 1,
@@ -688,7 +2088,7 @@ impl AppState {
     }
 
     pub fn tick_sequencer(&self) {
-        if self.song_editor.is_playing.load(Ordering::Relaxed) && self.seq_playing.load(Ordering::Relaxed) {
+        if self.song_editor.is_playing.load(Ordering::Relaxed) && self.events.playing() {
             let bar  = self.song_editor.current_bar.load(Ordering::Relaxed);
             let arr  = self.song_editor.get_arrangement_snapshot();
             let first = arr.iter().enumerate()
@@ -697,39 +2097,89 @@ impl AppState {
             if let Some(new_idx) = first {
                 let active = self.song_editor.active_edit_idx();
                 if new_idx != active {
+                    self.ab_commit();
                     self.save_current_pattern_state();
                     self.load_pattern_state(new_idx);
                 }
             }
         }
 
-        if !self.seq_playing.load(Ordering::Relaxed) { return; }
+        // A synced start takes its first step early; `lead` is how long its voices wait
+        let lead = match self.tick_armed_start() {
+            ArmedStart::Waiting  => return,
+            ArmedStart::Idle     => 0.0,
+            ArmedStart::Due(s)   => s,
+        };
+        if !self.events.playing() { return; }
+        // Locked to an external clock, steps come from its ticks (`midi_clock_step`) or
+        // from Link's beat (`tick_link`)
+        if self.clock_slaved() || self.link_steps() { return; }
 
-        let bpm      = self.seq_bpm.load(Ordering::Relaxed);
-        let step_dur = std::time::Duration::from_secs_f64(60.0 / bpm as f64 / 4.0);
+        let step_secs = self.seq_resolution.read().step_secs(self.song_bpm());
+        let step_dur  = std::time::Duration::from_secs_f64(step_secs);
         let now      = Instant::now();
         let should_advance = {
             let last = self.seq_last_step_time.read();
             last.map_or(true, |t| now.duration_since(t) >= step_dur)
         };
         if !should_advance { return; }
-        *self.seq_last_step_time.write() = Some(now);
+        // Stamped with the step's true time, so the next steps keep a synced start's phase
+        *self.seq_last_step_time.write() = Some(now + std::time::Duration::from_secs_f64(lead));
+        self.advance_step(lead);
+    }
 
+    /// Play the next step of the running sequencer, its voices starting `lead` seconds late.
+    pub(crate) fn advance_step(&self, lead: f64) {
+        let res       = *self.seq_resolution.read();
+        let step_secs = res.step_secs(self.song_bpm());
         let step = {
             let mut s = self.seq_current_step.write();
             let cur = *s;
-            *s = (cur + 1) % NUM_STEPS;
+            *s = (cur + 1) % self.pattern_len();
             cur
         };
+        self.transport.step();
 
         if self.song_editor.is_playing.load(Ordering::Relaxed) {
-            let _ = self.song_editor.advance_song();
+            let _ = self.song_editor.advance_song(self.pattern_len());
+        }
+
+        self.events.emit(EngineEvent::Step { step });
+        self.tick_loop_recording();
+        self.tick_fill(step, res);
+
+        // Quantized pattern launch
+        let pending = *self.pending_pattern.read();
+        if let Some(idx) = pending {
+            if self.quantize.read().step_on_grid(step, res.per_beat()) {
+                self.apply_pattern_switch(idx);
+            }
         }
 
+        let solo = *self.chop_solo.read();
+        let mut voices = self.step_voices(step, res, step_secs, solo, None, self.transport.steps() == 1);
+        voices.extend(self.metronome.lock().tick(res, step_secs));
+        for v in voices.iter_mut() { v.delay += lead; }
+        if !voices.is_empty() { self.play_voices(voices); }
+    }
+
+    /// Every voice that starts on `step` of the live pattern: chops on the main grid,
+    /// drum track rows (hits off the grid's lane delayed into place), piano notes and recorded takes.
+    /// With `solo`, only hits that play that (track, chop) slice, mute or not. With `stem`,
+    /// only that drum track, as if it alone were soloed, whatever the solo buttons say.
+    /// `first` on the first step of a run, which also plays the hits of rows set early off it.
+    pub(crate) fn step_voices(&self, step: usize, res: StepResolution, step_secs: f64, solo: Option<(usize, usize)>, stem: Option<usize>, first: bool) -> Vec<Voice> {
         let mut voices: Vec<Voice> = Vec::new();
+        let any_solo = stem.is_some() || self.any_track_soloed();
 
-        if let Some(asset) = self.current_asset.read().clone() {
-            let active_pads  = self.seq_grid.read()[step].clone();
+        // The main grid belongs to the main track, so a solo elsewhere silences it too
+        let main_idx     = *self.main_track_index.read();
+        let main_grid_on = match stem {
+            Some(s) => main_idx == Some(s),
+            None    => !any_solo || main_idx.and_then(|i| self.drum_tracks.read().get(i).map(|t| t.soloed)).unwrap_or(false),
+        };
+        if let (Some(asset), None, true) = (self.current_asset.read().clone(), solo, main_grid_on) {
+            let active_pads  = self.seq_grid.read().get(step).cloned().unwrap_or_default();
             if !active_pads.is_empty() {
                 let marks        = self.samples_manager.get_marks();
                 let channels     = asset.channels as usize;
@@ -741,76 +2191,130 @@ impl AppState {
                         if mark.sample_name != asset.file_name { continue; }
                         let start_frame = (mark.position as f64 * total_frames as f64) as usize;
                         let adsr        = chop_adsr.get(pad_idx).copied().unwrap_or_default();
-                        voices.push(Voice::new(pcm.clone(), channels, start_frame, 1.0, adsr, false));
+                        let mut voice = Voice::new(pcm.clone(), channels, start_frame, 1.0, adsr, false, asset.sample_rate);
+                        voice.bus = Bus::Chop;
+                        voices.push(voice);
                     }
                 }
             }
         }
 
+        // Rows on the grid's lane fire on the step; rows on any other (triplets, or a
+        // track at its own resolution) fire each of their steps that lands inside it,
+        // delayed to its exact spot against the same clock.
+        let len       = self.pattern_len();
+        let next      = (step + 1) % len;
+        let grid_lane = res.steps();
+        // A nudged step starts that fraction of one of its lane's steps late
+        let lane_secs = |lane: usize| step_secs * grid_lane as f64 / lane.max(1) as f64;
+        // Hits of grid step `at` (0 = this one, 1 = the next), as (lane step, delay from this step).
+        let hits_in = |at: usize, lane: usize, on: &dyn Fn(usize) -> bool| -> Vec<(usize, f64)> {
+            let (base, s) = (at as f64 * step_secs, [step, next][at]);
+            if lane != grid_lane {
+                res.lane_in_step(s, len, lane).into_iter().filter(|(k, _)| on(*k)).map(|(k, off)| (k, base + off * step_secs)).collect()
+            } else if on(s) {
+                vec![(s, base)]
+            } else {
+                Vec::new()
+            }
+        };
+        // A row `offset` steps off the grid: late, its own hits wait; early, it plays the
+        // next step's hits now, ahead of that step. The first step of a run has no step
+        // before it, so its early hits play on it.
+        let lane_hits = |lane: usize, offset: f32, on: &dyn Fn(usize) -> bool| -> Vec<(usize, f64)> {
+            let shift = offset as f64 * lane_secs(lane);
+            let mut hits = if offset >= 0.0 || first { hits_in(0, lane, on) } else { Vec::new() };
+            for h in hits.iter_mut() { h.1 += shift.max(0.0); }
+            if offset < 0.0 {
+                hits.extend(hits_in(1, lane, on).into_iter().map(|(k, d)| (k, d + shift)));
+            }
+            hits
+        };
+        let global = self.humanize.load(Ordering::Relaxed);
+
         {
-            let tracks   = self.drum_tracks.read();
-            let main_idx = *self.main_track_index.read();
+            let tracks = self.drum_tracks.read();
 
             for (track_idx, track) in tracks.iter().enumerate() {
-                if track.muted { continue; }
+                let audible = match stem {
+                    Some(s) => s == track_idx && !track.muted,
+                    None    => track.audible(any_solo),
+                };
+                match solo {
+                    Some((t, _)) if t != track_idx => continue,
+                    None if !audible => continue,
+                    _ => {}
+                }
+                // Some(c): only voices of chop c
+                let only = solo.map(|(_, c)| c);
                 let chop_marks = self.samples_manager.get_marks_for_sample(&track.sample_uuid);
 
                 if !chop_marks.is_empty() {
-                    let channels     = track.asset.channels as usize;
-                    let total_frames = track.asset.pcm.len() / channels.max(1);
-                    let pcm          = Arc::new(track.asset.pcm.clone());
-
-                    for (chop_idx, mark) in chop_marks.iter().enumerate() {
-                        let start_frame  = (mark.position as f64 * total_frames as f64) as usize;
-                        let adsr         = track.chop_adsr.get(chop_idx).copied().unwrap_or(track.adsr);
-                        let chop_adsr_on = track.chop_adsr_enabled.get(chop_idx).copied().unwrap_or(track.adsr_enabled);
-                        let play_mode    = track.chop_play_modes.get(chop_idx).copied().unwrap_or(ChopPlayMode::ToNextChop);
-
-                        let end_frame = match play_mode {
-                            ChopPlayMode::ToEnd => None,
-                            ChopPlayMode::ToNextChop => {
-                                chop_marks.get(chop_idx + 1)
-                                    .map(|n| (n.position as f64 * total_frames as f64) as usize)
-                            }
-                            ChopPlayMode::ToNextStep => {
-                                let step_frames = (60.0 / bpm as f64 / 4.0 * track.asset.sample_rate as f64) as usize;
-                                Some(start_frame + step_frames)
-                            }
-                            ChopPlayMode::ToMarker(tid) => {
-                                chop_marks.iter().find(|m| m.id == tid)
-                                    .map(|m| (m.position as f64 * total_frames as f64) as usize)
-                            }
-                        };
+                    let pcm = Arc::new(track.asset.pcm.clone());
 
+                    for chop_idx in 0..chop_marks.len() {
                         let has_piano_notes = track.chop_piano_notes
                             .get(chop_idx).map(|n| !n.is_empty()).unwrap_or(false);
 
                         if has_piano_notes {
-                            let piano_notes_now: Vec<PianoRollNote> = track.chop_piano_notes
-                                .get(chop_idx)
-                                .map(|notes| notes.iter().filter(|n| n.step == step).cloned().collect())
-                                .unwrap_or_default();
-                            for note in &piano_notes_now {
-                                let mut voice = Voice::new(pcm.clone(), channels, start_frame, note.speed(), adsr, chop_adsr_on);
-                                voice.end_frame = end_frame;
-                                voices.push(voice);
+                            if only.is_some_and(|c| c != chop_idx) { continue; }
+                            let notes  = track.chop_piano_notes.get(chop_idx).map_or(&[][..], |n| &n[..]);
+                            let feel   = RowFeel::new(track, Some(chop_idx), global, lane_secs(grid_lane));
+                            for (k, delay) in lane_hits(grid_lane, feel.offset, &|s| notes.iter().any(|n| n.step == s)) {
+                                for note in notes.iter().filter(|n| n.step == k) {
+                                    if let Some(mut voice) = track.chop_voice(&pcm, &chop_marks, chop_idx, note.speed(), step_secs) {
+                                        voice.level *= note.velocity;
+                                        voice.delay  = delay + track.lateness(self.dice.lock().track(track_idx));
+                                        feel.apply(&mut voice, self.dice.lock().track(track_idx));
+                                        voices.push(voice);
+                                    }
+                                }
                             }
                         } else {
-                            let fires = if Some(track_idx) == main_idx {
-                                self.seq_grid.read()[step].contains(&chop_idx)
+                            let on_grid = Some(track_idx) == main_idx;
+                            let lane    = track.lane(res, Some(chop_idx), on_grid);
+                            let feel    = RowFeel::new(track, Some(chop_idx), global, lane_secs(lane));
+                            let hits = if on_grid {
+                                let grid = self.seq_grid.read();
+                                lane_hits(lane, feel.offset, &|s| grid.get(s).is_some_and(|c| c.contains(&chop_idx)))
                             } else {
-                                track.chop_steps.get(chop_idx).map(|s| s[step]).unwrap_or(false)
+                                lane_hits(lane, feel.offset, &|s| track.chop_steps.get(chop_idx).is_some_and(|r| step_on(r, s)))
                             };
-                            if fires {
-                                let mut voice = Voice::new(pcm.clone(), channels, start_frame, 1.0, adsr, chop_adsr_on);
-                                voice.end_frame = end_frame;
-                                voices.push(voice);
+                            for (k, delay) in hits {
+                                let chop = variation::pick_chop(&track.chop_alternates, chop_idx, k, self.dice.lock().track(track_idx));
+                                if only.is_some_and(|c| c != chop) { continue; }
+                                let speed = step_pitch::speed(&track.step_pitches, Some(chop_idx), k);
+                                let nudge = step_nudge::fraction(&track.step_nudges, Some(chop_idx), k) as f64 * lane_secs(lane);
+                                if let Some(mut voice) = track.chop_voice(&pcm, &chop_marks, chop, speed, step_secs) {
+                                    voice.delay = delay + nudge + track.lateness(self.dice.lock().track(track_idx));
+                                    feel.apply(&mut voice, self.dice.lock().track(track_idx));
+                                    voices.push(voice);
+                                }
                             }
                         }
                     }
-                } else if track.steps[step] {
-                    let channels = track.asset.channels as usize;
-                    voices.push(Voice::new(Arc::new(track.asset.pcm.clone()), channels, 0, 1.0, track.adsr, track.adsr_enabled));
+                    for region in self.samples_manager.get_regions_for_sample(&track.sample_uuid) {
+                        if only.is_some() || !step_on(&region.steps, step) { continue; }
+                        if let Some(mut voice) = track.region_voice(&pcm, &chop_marks, &region) {
+                            voice.delay = track.lateness(self.dice.lock().track(track_idx));
+                            voices.push(voice);
+                        }
+                    }
+                } else if only.is_none() {
+                    let lane = track.lane(res, None, false);
+                    let feel = RowFeel::new(track, None, global, lane_secs(lane));
+                    for (k, delay) in lane_hits(lane, feel.offset, &|s| step_on(&track.steps, s)) {
+                        let channels = track.asset.channels as usize;
+                        let speed    = step_pitch::speed(&track.step_pitches, None, k);
+                        let nudge    = step_nudge::fraction(&track.step_nudges, None, k) as f64 * lane_secs(lane);
+                        let mut voice = Voice::new(Arc::new(track.asset.pcm.clone()), channels, 0, speed, track.adsr, track.adsr_enabled, track.asset.sample_rate);
+                        voice.delay = delay + nudge + track.lateness(self.dice.lock().track(track_idx));
+                        voice.level = track.level() * track.velocity(k);
+                        voice.bus   = Bus::Drum;
+                        voice.meter = Some(track.meter.clone());
+                        feel.apply(&mut voice, self.dice.lock().track(track_idx));
+                        voices.push(voice);
+                    }
                 }
             }
         }
@@ -818,150 +2322,662 @@ impl AppState {
         {
             let rec_tracks = self.rec_tracks.read();
             for track in rec_tracks.iter() {
-                if track.muted || track.state != RecordState::Recorded { continue; }
-                if !track.steps[step] { continue; }
+                if solo.is_some() || any_solo || track.muted || track.state != RecordState::Recorded { continue; }
+                if !step_on(&track.steps, step) { continue; }
                 if let Some(asset) = &track.asset {
                     let channels = asset.channels as usize;
                     voices.push(crate::adsr::Voice::new(
-                        Arc::new(asset.pcm.clone()), channels, 0, 1.0, track.adsr, track.adsr_enabled,
+                        Arc::new(asset.pcm.clone()), channels, 0, 1.0, track.adsr, track.adsr_enabled, asset.sample_rate,
                     ));
                 }
             }
         }
 
-        if !voices.is_empty() {
-            self.ensure_seq_stream();
-            if let Ok(mut active) = self.active_voices.lock() { active.extend(voices); }
-        }
-    }
-
-    fn ensure_seq_stream(&self) {
-        if self.seq_stream_handle.read().is_some() { return; }
-        let host   = cpal::default_host();
-        let device = match host.default_output_device() { Some(d) => d, None => return };
-        let config = match device.default_output_config() { Ok(c) => c, Err(_) => return };
-
-        let mut cfg: cpal::StreamConfig = config.clone().into();
-        cfg.buffer_size = cpal::BufferSize::Fixed(1024);
-        cfg.sample_rate = cpal::SampleRate(48000);
-
-        let out_channels = cfg.channels as usize;
-        let sample_rate  = cfg.sample_rate.0 as f32;
-
-        let stream = device.build_output_stream(
-            &cfg,
-            {
-                let active_voices = self.active_voices.clone();
-                let seq_playing   = self.seq_playing.clone();
-                move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
-                    for s in data.iter_mut() { *s = 0.0; }
-                    if !seq_playing.load(Ordering::Relaxed) { return; }
-                    let mut voices = match active_voices.lock() { Ok(v) => v, Err(_) => return };
-                    let out_frames = data.len() / out_channels.max(1);
-                    voices.retain_mut(|voice| {
-                        let mut alive = false;
-                        for f in 0..out_frames {
-                            if let Some(samples) = voice.render(sample_rate, out_channels) {
-                                alive = true;
-                                for (oc, smp) in samples.iter().enumerate() {
-                                    let oi = f * out_channels + oc;
-                                    if oi < data.len() { data[oi] = (data[oi] + smp).clamp(-1.0, 1.0); }
-                                }
-                            }
-                        }
-                        alive
-                    });
+        voices
+    }
+
+    /// Put every chop of `track_idx` that has no hits yet onto an empty straight step:
+    /// one after another from the top of the bar, or spread evenly across it.
+    /// Returns how many chops were placed.
+    pub fn lay_chops_across_bar(&self, track_idx: usize, layout: ChopLayout) -> usize {
+        let n_steps  = self.pattern_len();
+        let main_idx = *self.main_track_index.read();
+        let mut tracks = self.drum_tracks.write();
+        let Some(t) = tracks.get_mut(track_idx) else { return 0; };
+        let n_chops = self.samples_manager.get_marks_for_sample(&t.sample_uuid).len();
+        if n_chops == 0 {
+            *self.status.write() = t!("No chops to lay out — drop markers first (M)").to_string();
+            return 0;
+        }
+        t.ensure_chop_steps(n_chops);
+
+        // Main track chops live on the shared grid, the rest in their own rows, on the
+        // track's lane
+        let on_main = main_idx == Some(track_idx);
+        let res     = *self.seq_resolution.read();
+        let n_steps = lane_len(n_steps, res, lane_steps(res, if on_main { None } else { t.resolution }, false));
+        let mut grid = self.seq_grid.write();
+        let hit = |grid: &[Vec<usize>], t: &DrumTrack, c: usize, s: usize| {
+            if on_main { grid.get(s).is_some_and(|g| g.contains(&c)) } else { step_on(&t.chop_steps[c], s) }
+        };
+        let mut placed = 0;
+        let mut cursor = 0;
+        for c in 0..n_chops {
+            if (0..n_steps).any(|s| hit(&grid, t, c, s)) || t.chop_triplet[c] { continue; }
+            let from = match layout {
+                ChopLayout::Sequential => cursor,
+                ChopLayout::Spread     => c * n_steps / n_chops,
+            };
+            let free = (0..n_steps).map(|k| (from + k) % n_steps)
+                .find(|&s| (0..n_chops).all(|o| !hit(&grid, t, o, s)));
+            let Some(s) = free else { break; };
+            if on_main { grid[s].push(c); } else { set_step(&mut t.chop_steps[c], s, true); }
+            cursor = s + 1;
+            placed += 1;
+        }
+        *self.status.write() = match placed {
+            0 => t!("Every chop already has a step (or the bar is full)").to_string(),
+            n => t!("✓ Laid {} chop(s) across the bar", n),
+        };
+        placed
+    }
+
+    /// Sequence the chops of `track_idx` where they sit in the source loop, so the pattern
+    /// plays the loop back as it was. Uses the file's BPM tag when it has one (and sets the
+    /// tempo to it), otherwise the current tempo. A loop longer than a pattern continues in
+    /// new patterns, laid out one after another in the song. Returns the number of patterns used.
+    pub fn pattern_from_loop(&self, track_idx: usize) -> usize {
+        let res     = *self.seq_resolution.read();
+        let on_main = *self.main_track_index.read() == Some(track_idx);
+        let (marks, asset, own) = {
+            let tracks = self.drum_tracks.read();
+            let Some(t) = tracks.get(track_idx) else { return 0; };
+            (self.samples_manager.get_marks_for_sample(&t.sample_uuid), t.asset.clone(), t.resolution)
+        };
+        // Chop rows are laid on the track's lane; the main grid is on the grid's
+        let grid_len = self.pattern_len();
+        let lane     = lane_steps(res, if on_main { None } else { own }, false);
+        let n_steps  = lane_len(grid_len, res, lane);
+        if marks.is_empty() {
+            *self.status.write() = t!("No chops to sequence — drop markers first (M)").to_string();
+            return 0;
+        }
+        let bpm = match asset.tags.bpm {
+            Some(b) => { let b = b.clamp(40.0, 300.0); self.events.emit(EngineEvent::Bpm { bpm: b }); b }
+            None    => self.events.bpm(),
+        };
+        let step_secs = res.step_secs(bpm) * res.steps() as f64 / lane as f64;
+        let dur       = asset.frames as f64 / asset.sample_rate.max(1) as f64;
+        let bars      = ((dur / (step_secs * n_steps as f64) - 1e-6).ceil() as usize).max(1);
+
+        // (chop, step) hits per pattern, plus the worst snap to the grid
+        let mut per_bar: Vec<Vec<(usize, usize)>> = vec![Vec::new(); bars];
+        let mut worst = 0.0f64;
+        for (c, m) in marks.iter().enumerate() {
+            let t = m.position as f64 * dur;
+            let g = (t / step_secs).round() as usize;
+            worst = worst.max((t - g as f64 * step_secs).abs());
+            if let Some(bar) = per_bar.get_mut(g / n_steps) { bar.push((c, g % n_steps)); }
+        }
+        let n_chops = marks.len();
+        let write = |grid: &mut Vec<Vec<usize>>, rows: &mut Vec<Vec<bool>>, hits: &[(usize, usize)]| {
+            rows.resize(rows.len().max(n_chops), Vec::new());
+            for row in rows.iter_mut().take(n_chops) { *row = vec![false; n_steps]; }
+            grid.resize(grid_len, Vec::new());
+            for cell in grid.iter_mut() { cell.retain(|&c| c >= n_chops); }
+            for &(c, s) in hits {
+                if on_main { grid[s].push(c); } else { rows[c][s] = true; }
+            }
+        };
+
+        {
+            let mut tracks = self.drum_tracks.write();
+            let t = &mut tracks[track_idx];
+            t.ensure_chop_steps(n_chops);
+            // Each chop runs up to the next, as in the loop
+            for c in 0..n_chops {
+                t.chop_triplet[c]    = false;
+                t.chop_play_modes[c] = ChopPlayMode::ToNextChop;
+            }
+            write(&mut self.seq_grid.write(), &mut t.chop_steps, &per_bar[0]);
+        }
+
+        if bars > 1 {
+            self.save_current_pattern_state();
+            let active = self.song_editor.active_edit_idx();
+            let Some(first) = self.song_editor.get_pattern_by_idx(active) else { return 1; };
+            self.song_editor.set_block(active, 0, Some(active));
+            for (b, hits) in per_bar.iter().enumerate().skip(1) {
+                let mut p = first.clone();
+                p.name = format!("{} · part {}", first.name, b + 1);
+                if let Some(snap) = p.tracks.get_mut(track_idx) {
+                    write(&mut p.main_grid, &mut snap.chop_steps, hits);
                 }
+                let row = self.song_editor.import_pattern(p);
+                self.song_editor.set_block(row, b, Some(row));
+            }
+        }
+
+        let off = if worst * 1000.0 >= 1.0 { t!(", chops up to {:.0} ms off the grid", worst * 1000.0) } else { String::new() };
+        *self.status.write() = t!("✓ Sequenced {} chop(s) at {:.0} BPM over {} pattern(s){}", n_chops, bpm, bars, off);
+        bars
+    }
+
+    /// Switch one row between the straight and triplet lanes, moving its hits
+    /// to the nearest step. `chop` is `None` for the track's main row.
+    pub fn set_row_triplet(&self, track_idx: usize, chop: Option<usize>, on: bool) {
+        let res      = *self.seq_resolution.read();
+        let len      = self.pattern_len();
+        let main_idx = *self.main_track_index.read();
+        let mut tracks = self.drum_tracks.write();
+        let Some(t) = tracks.get_mut(track_idx) else { return; };
+        let own      = if chop.is_some() && main_idx == Some(track_idx) { None } else { t.resolution };
+        // Rows are as long as the straight lane, the triplet lane using the front of them
+        let row_n    = lane_len(len, res, lane_steps(res, own, false));
+        let from_n   = lane_len(len, res, lane_steps(res, own, !on));
+        let to_n     = lane_len(len, res, lane_steps(res, own, on));
+        let moved    = |s: usize| lane_step(s, from_n, to_n);
+        let convert = |row: &[bool]| {
+            let mut out = vec![false; row_n];
+            for s in (0..from_n).filter(|&s| step_on(row, s)) { out[moved(s)] = true; }
+            out
+        };
+        match chop {
+            None => {
+                if t.triplet == on { return; }
+                let mut vel = vec![1.0; row_n];
+                for s in (0..from_n).filter(|&s| step_on(&t.steps, s)) { vel[moved(s)] = t.velocity(s); }
+                t.triplet = on;
+                t.steps   = convert(&t.steps);
+                t.step_velocity = vel;
+                step_pitch::move_steps(&mut t.step_pitches, |x| x.is_none(), |s| Some(moved(s)));
+                step_nudge::move_steps(&mut t.step_nudges, |x| x.is_none(), |s| Some(moved(s)));
+            }
+            Some(c) => {
+                t.ensure_chop_steps(c + 1);
+                if t.chop_triplet[c] == on { return; }
+                t.chop_triplet[c] = on;
+                if main_idx == Some(track_idx) {
+                    let mut grid = self.seq_grid.write();
+                    let row: Vec<bool> = grid.iter().map(|g| g.contains(&c)).collect();
+                    let moved = convert(&row);
+                    for (s, chops) in grid.iter_mut().enumerate() {
+                        chops.retain(|&x| x != c);
+                        if step_on(&moved, s) { chops.push(c); }
+                    }
+                } else {
+                    t.chop_steps[c] = convert(&t.chop_steps[c]);
+                }
+                variation::move_steps(&mut t.chop_alternates, |x| x == c, |s| Some(moved(s)));
+                step_pitch::move_steps(&mut t.step_pitches, |x| x == Some(c), |s| Some(moved(s)));
+                step_nudge::move_steps(&mut t.step_nudges, |x| x == Some(c), |s| Some(moved(s)));
+            }
+        }
+        *self.status.write() = t!("{} lane: {} steps per bar",
+            if on { t!("Triplet") } else { t!("Straight") }, lane_steps(res, own, on));
+    }
+
+    /// Run `track_idx`'s rows at `to` instead of the grid's resolution (`None` for the
+    /// grid's), moving its hits so they stay at the same time in the bar. The main
+    /// track's chops live on the main grid, so it keeps the grid's.
+    pub fn set_track_resolution(&self, track_idx: usize, to: Option<StepResolution>) {
+        let grid = *self.seq_resolution.read();
+        if *self.main_track_index.read() == Some(track_idx) {
+            *self.status.write() = t!("✗ The main track's chops are on the main grid, which sets its steps").to_string();
+            return;
+        }
+        let len = self.pattern_len();
+        let mut tracks = self.drum_tracks.write();
+        let Some(t) = tracks.get_mut(track_idx) else { return; };
+        let from = t.resolution.unwrap_or(grid);
+        if t.resolution == to { return; }
+        let target = to.unwrap_or(grid);
+        // A triplet resolution has no triplet lanes of its own
+        if target.is_triplet() && (t.triplet || t.chop_triplet.iter().any(|&x| x)) {
+            *self.status.write() = t!("✗ Switch the triplet rows back to straight first").to_string();
+            return;
+        }
+        let merged = t.remap_rows(from, target);
+        t.resolution = to;
+        t.fit_steps(len, grid);
+        *self.status.write() = if merged > 0 {
+            t!("✓ Track {} at {} — {} hit(s) merged", track_idx + 1, target.label(), merged)
+        } else {
+            t!("✓ Track {} at {}", track_idx + 1, target.label())
+        };
+    }
+
+    /// Audition pad `pad` (chop `pad` of the focused drum track) through the sequencer mix,
+    /// or jump to its cue in hot-cue mode.
+    pub fn trigger_pad(&self, pad: usize) -> bool { self.trigger_pad_velocity(pad, 1.0) }
+
+    /// `trigger_pad` at `velocity` (0-1). When the focused track has no chops, pad `n`
+    /// plays drum track `n` whole, so a kit of one-shots is playable from MIDI pads.
+    pub fn trigger_pad_velocity(&self, pad: usize, velocity: f32) -> bool {
+        if self.pad_hot_cues.load(Ordering::Relaxed) { return self.jump_to_cue(pad); }
+        if let Some(bank) = self.active_bank() {
+            let Some(mut voice) = self.bank_voice(&bank, pad) else { return false; };
+            voice.level *= velocity;
+            self.play_voices(vec![voice]);
+            return true;
+        }
+        let focused = match *self.waveform_focus.read() { WaveformFocus::DrumTrack(i) => Some(i), _ => None };
+        let step_secs = self.seq_resolution.read().step_secs(self.events.bpm());
+        let (voice, lane) = {
+            let tracks = self.drum_tracks.read();
+            let chopped = focused.and_then(|i| tracks.get(i).map(|t| (i, t)))
+                .map(|(i, t)| (i, t, self.samples_manager.get_marks_for_sample(&t.sample_uuid)))
+                .filter(|(_, _, marks)| !marks.is_empty());
+            match chopped {
+                Some((track_idx, track, chop_marks)) => {
+                    if pad >= chop_marks.len() { return false; }
+                    let pcm = Arc::new(track.asset.pcm.clone());
+                    (track.chop_voice(&pcm, &chop_marks, pad, 1.0, step_secs), Lane::Chop(track_idx, pad))
+                }
+                None => (tracks.get(pad).map(|track| {
+                    let mut v = Voice::new(Arc::new(track.asset.pcm.clone()), track.asset.channels as usize, 0, 1.0,
+                        track.adsr, track.adsr_enabled, track.asset.sample_rate);
+                    v.level = track.level();
+                    v.bus   = Bus::Drum;
+                    v.meter = Some(track.meter.clone());
+                    v
+                }), Lane::Main(pad)),
+            }
+        };
+        let Some(mut voice) = voice else { return false; };
+        voice.level *= velocity;
+        self.play_voices(vec![voice]);
+        self.events.emit(EngineEvent::Pad { pad, velocity });
+        self.record_pad_hit(lane, velocity);
+        true
+    }
+
+    /// Hot cue: move the main playhead to marker `pad` of the sample it's playing and keep
+    /// going from there. When stopped, the focused sample starts from that marker.
+    pub fn jump_to_cue(&self, pad: usize) -> bool {
+        let playing = self.is_playing.load(Ordering::Relaxed);
+        let current = if playing { self.playback_asset.read().clone() } else { None };
+        let Some(asset) = current.clone().or_else(|| self.focused_asset()) else { return false; };
+        let marks = self.samples_manager.get_marks_for_sample(&asset.sample_uuid);
+        let Some(mark) = marks.get(pad) else { return false; };
+
+        let pos = mark.position.clamp(0.0, 1.0);
+        let ch  = (asset.channels as u64).max(1);
+        self.playback_sample_index.store((pos as f64 * asset.frames as f64) as u64 * ch, Ordering::Relaxed);
+        self.playback_position.store(pos, Ordering::Relaxed);
+        let target = match self.samples_manager.get_playback_mode() {
+            PlaybackMode::PlayToNextMarker => self.samples_manager.get_playback_target(pos, &asset.sample_uuid).unwrap_or(-1.0),
+            _ => -1.0,
+        };
+        self.playback_stop_target.store(target, Ordering::Relaxed);
+        self.playback_loop_from.store(-1.0, Ordering::Relaxed);
+        if current.is_none() {
+            *self.playback_asset.write() = Some(asset.clone());
+            self.is_playing.store(true, Ordering::Relaxed);
+            if !self.play_on_engine(asset.clone()) { self.is_playing.store(false, Ordering::Relaxed); return false; }
+        }
+        let dur = asset.frames as f32 / asset.sample_rate.max(1) as f32;
+        *self.status.write() = t!("Cue {} — {:.2}s", pad + 1, pos * dur);
+        true
+    }
+
+    /// Give each voice an explicit channel conversion for an `out`-channel output.
+    pub(crate) fn attach_channel_maps(&self, voices: &mut [Voice], out: usize) {
+        if out == 0 { return; }
+        let coeffs = *self.downmix.read();
+        let mut cache: HashMap<usize, Arc<ChannelMatrix>> = HashMap::new();
+        for v in voices.iter_mut() {
+            let m = cache.entry(v.channels)
+                .or_insert_with(|| Arc::new(ChannelMatrix::new(v.channels, out, &coeffs)))
+                .clone();
+            if let Some(desc) = m.describe() { *self.channel_notice.write() = Some(desc); }
+            v.matrix = Some(m);
+        }
+    }
+
+    /// Current sequencer position in beats from the top of the pattern, `None` when stopped.
+    pub fn seq_beat_position(&self) -> Option<f64> {
+        Some(self.seq_step_position()? / self.seq_resolution.read().per_beat() as f64)
+    }
+
+    /// Where the sequencer is in the pattern, in straight steps; `None` when stopped.
+    pub fn seq_step_position(&self) -> Option<f64> {
+        if !self.events.playing() { return None; }
+        let last      = (*self.seq_last_step_time.read())?;
+        let res       = *self.seq_resolution.read();
+        let next      = *self.seq_current_step.read();
+        let len       = self.pattern_len();
+        let cur       = (next + len - 1) % len;
+        let step_secs = res.step_secs(self.events.bpm());
+        let elapsed   = last.elapsed().as_secs_f64().min(step_secs);
+        Some(cur as f64 + elapsed / step_secs)
+    }
+
+    /// Some drum track is soloed, so only soloed tracks play.
+    pub fn any_track_soloed(&self) -> bool { self.drum_tracks.read().iter().any(|t| t.soloed) }
+
+    /// Steps in every pattern of the project.
+    pub fn pattern_len(&self) -> usize { self.pattern_len.load(Ordering::Relaxed).clamp(1, MAX_STEPS) }
+
+    /// Change how many steps every pattern runs for. Rows grow with rests, or
+    /// lose their hits past the new end; the status line says how many.
+    pub fn set_pattern_len(&self, len: usize) {
+        let len = len.clamp(1, MAX_STEPS);
+        if len == self.pattern_len() { return; }
+        let dropped = self.fit_pattern_len(len);
+        *self.status.write() = if dropped > 0 {
+            t!("✓ Pattern length {} steps — {} hit(s) past the end dropped", len, dropped)
+        } else {
+            t!("✓ Pattern length {} steps", len)
+        };
+    }
+
+    /// Size the live pattern, recording tracks and stored patterns to `len`
+    /// steps. Returns how many hits fell off the end.
+    fn fit_pattern_len(&self, len: usize) -> usize {
+        self.pattern_len.store(len, Ordering::Relaxed);
+        let past = |row: &[bool]| row.iter().skip(len).filter(|&&on| on).count();
+        let mut dropped = 0;
+        {
+            let mut grid = self.seq_grid.write();
+            dropped += grid.iter().skip(len).filter(|c| !c.is_empty()).count();
+            grid.resize(len, Vec::new());
+        }
+        let res = *self.seq_resolution.read();
+        for t in self.drum_tracks.write().iter_mut() {
+            let n    = t.row_len(len, res);
+            let past = |row: &[bool]| row.iter().skip(n).filter(|&&on| on).count();
+            dropped += past(&t.steps) + t.chop_steps.iter().map(|r| past(r)).sum::<usize>();
+            t.fit_steps(len, res);
+        }
+        for t in self.rec_tracks.write().iter_mut() {
+            dropped += past(&t.steps);
+            t.steps.resize(len, false);
+        }
+        self.samples_manager.for_each_region_lane(|row| {
+            dropped += past(row);
+            row.resize(len, false);
+        });
+
+        let active = self.song_editor.active_edit_idx();
+        for (idx, mut p) in self.song_editor.get_all_patterns().into_iter().enumerate() {
+            if idx == active { continue; }
+            p.fit_steps(len, res);
+            self.song_editor.update_pattern_by_idx(idx, p);
+        }
+        if let Some(ab) = self.ab_compare.write().as_mut() { ab.other.fit_steps(len, res); }
+
+        let mut cur = self.seq_current_step.write();
+        if *cur >= len { *cur = 0; }
+        dropped
+    }
+
+    /// Change the step grid for the whole project. Every pattern, recording
+    /// track and piano roll is re-mapped so hits stay at the same time in the
+    /// bar, and the pattern length scales to cover the same bars.
+    pub fn set_step_resolution(&self, to: StepResolution) {
+        let from = *self.seq_resolution.read();
+        if from == to { return; }
+
+        let mut merged = 0;
+        {
+            let (grid, m) = from.remap_grid(&self.seq_grid.read(), to);
+            *self.seq_grid.write() = grid;
+            merged += m;
+        }
+        for t in self.drum_tracks.write().iter_mut() { merged += t.remap_steps(from, to); }
+        for t in self.rec_tracks.write().iter_mut() {
+            let (steps, m) = from.remap_row(&t.steps, to);
+            t.steps = steps;
+            merged += m;
+        }
+        self.samples_manager.for_each_region_lane(|row| {
+            let (steps, m) = from.remap_row(row, to);
+            *row = steps;
+            merged += m;
+        });
+
+        // Stored patterns; the active one is rewritten from live state on next save
+        let active = self.song_editor.active_edit_idx();
+        for (idx, mut p) in self.song_editor.get_all_patterns().into_iter().enumerate() {
+            if idx == active { continue; }
+            merged += p.remap_steps(from, to);
+            self.song_editor.update_pattern_by_idx(idx, p);
+        }
+        if let Some(ab) = self.ab_compare.write().as_mut() { ab.other.remap_steps(from, to); }
+
+        {
+            let mut cur = self.seq_current_step.write();
+            *cur = from.map_step(*cur, to);
+        }
+        *self.seq_resolution.write() = to;
+        let dropped = self.fit_pattern_len(from.map_len(self.pattern_len(), to).clamp(to.pattern_lengths()[0], MAX_STEPS));
+        *self.status.write() = if merged > 0 && to.steps() < from.steps() {
+            t!("✓ Grid {} — {} hit(s) merged", to.label(), merged)
+        } else if dropped > 0 {
+            t!("✓ Grid {} — {} hit(s) past step {} dropped", to.label(), dropped, MAX_STEPS)
+        } else {
+            t!("✓ Grid {}", to.label())
+        };
+    }
+
+    /// Open the engine if it isn't running; the output's channel count, or `None` if it can't open.
+    pub(crate) fn ensure_engine(&self) -> Option<usize> {
+        if let Some(e) = self.engine.read().as_ref() { return Some(e.spec().channels); }
+        if self.audio_blocked() { return None; }
+        let links = EngineLinks {
+            playhead: Playhead {
+                position:     self.playback_position.clone(),
+                sample_index: self.playback_sample_index.clone(),
+                is_playing:   self.is_playing.clone(),
+                stop_target:  self.playback_stop_target.clone(),
+                loop_from:    self.playback_loop_from.clone(),
+                looping:      self.playback_loop.clone(),
+                speed:        self.playback_speed.clone(),
+                heard_at:     self.playback_heard_at.clone(),
+                status:       self.status.clone(),
             },
-            |err| eprintln!("Seq stream error: {}", err),
-            None,
-        );
-        if let Ok(s) = stream { let _ = s.play(); *self.seq_stream_handle.write() = Some(s); }
+            master_volume: self.master_volume.clone(),
+            master_cutoff: self.master_cutoff.clone(),
+            crossfader:    self.crossfader.clone(),
+            bpm:           self.events.bpm_source(),
+            stutter:       self.stutter.clone(),
+            safety:        self.safety.clone(),
+            net_tap:       self.net_sender.tap.clone(),
+            duck_db:       self.prelisten_duck_db.clone(),
+            frames:        self.transport.frames.clone(),
+            latency:       self.output_latency.clone(),
+            fault:         self.engine_fault.clone(),
+        };
+        let opened = Engine::open(self.audio_backend.read().as_ref(), self.audio_devices.selection(), links);
+        match opened {
+            Ok(e) => {
+                let out = e.spec().channels;
+                self.seq_out_channels.store(out, Ordering::Relaxed);
+                *self.engine.write() = Some(e);
+                Some(out)
+            }
+            Err(e) => { *self.status.write() = format!("✗ {}", e); None }
+        }
+    }
+
+    /// Pass `cmd` to the engine if it's open (nothing is sounding otherwise).
+    pub(crate) fn send_engine(&self, cmd: Command) {
+        if let Some(e) = self.engine.read().as_ref() { e.send(cmd); }
+    }
+
+    /// Start `voices` on the engine, mapped to its output channels.
+    pub(crate) fn play_voices(&self, mut voices: Vec<Voice>) {
+        let Some(out) = self.ensure_engine() else { return; };
+        self.attach_channel_maps(&mut voices, out);
+        self.send_engine(Command::Voices(voices));
     }
 
     pub fn start_sequencer(&self) {
-        self.seq_voice_queue.lock().unwrap().clear();
-        *self.seq_stream_handle.write() = None;
         *self.seq_current_step.write()  = 0;
         *self.seq_last_step_time.write() = None;
-        self.seq_playing.store(true, Ordering::Relaxed);
-        *self.status.write() = format!("Sequencer ▶ {:.0} BPM", self.seq_bpm.load(Ordering::Relaxed));
+        self.metronome.lock().reset();
+        self.transport.start();
+        self.events.emit(EngineEvent::Started);
+        *self.status.write() = t!("Sequencer ▶ {:.0} BPM", self.events.bpm());
     }
 
     pub fn stop_sequencer(&self) {
-        self.seq_playing.store(false, Ordering::Relaxed);
-        *self.seq_stream_handle.write() = None;
-        self.seq_voice_queue.lock().unwrap().clear();
-        if let Ok(mut v) = self.active_voices.lock() { v.clear(); }
+        // Stopping mid-loop keeps whatever was captured as a normal take
+        let loop_take = self.loop_rec.write().take();
+        if let Some(l) = loop_take { self.stop_recording(l.track_idx); }
+        // A fill cut short hands back to the pattern it interrupted
+        let fill = self.fill.write().take();
+        if let Some(FillState::Playing { back_to, .. }) = fill { self.apply_pattern_switch(back_to); }
+        self.events.emit(EngineEvent::Stopped);
+        self.record_take.store(false, Ordering::Relaxed);
+        self.seq_counting_in.store(false, Ordering::Relaxed);
+        *self.seq_start_at.write() = None;
+        self.send_engine(Command::ClearVoices);
         *self.seq_current_step.write() = 0;
-        *self.status.write() = "Sequencer stopped".to_string();
+        self.transport.stop();
+        *self.status.write() = t!("Sequencer stopped").to_string();
     }
 
     pub fn start_song(&self) {
         self.song_editor.start();
         self.start_sequencer();
-        *self.status.write() = "▶ Song playing".to_string();
+        *self.status.write() = t!("▶ Song playing").to_string();
     }
 
     pub fn stop_song(&self) {
         self.song_editor.stop();
         self.stop_sequencer();
-        *self.status.write() = "Song stopped".to_string();
+        *self.status.write() = t!("Song stopped").to_string();
+    }
+
+    /// Emergency mute: gate every output and drop all sounding voices. Latches until `unmute`.
+    pub fn panic(&self) {
+        self.hold_resume_point();
+        self.safety.muted.store(true, Ordering::Relaxed);
+        self.stop_playback();
+        self.song_editor.stop();
+        self.stop_sequencer();
+        *self.status.write() = t!("⚠ PANIC — output muted (click 🔇 Muted to restore)").to_string();
+    }
+
+    pub fn unmute(&self) {
+        self.safety.muted.store(false, Ordering::Relaxed);
+        *self.status.write() = t!("Output unmuted").to_string();
+    }
+
+    /// Esc twice within 400 ms triggers `panic`.
+    pub fn handle_escape(&self) {
+        let now  = Instant::now();
+        let prev = self.last_escape.write().replace(now);
+        if prev.is_some_and(|t| now.duration_since(t).as_millis() < 400) {
+            *self.last_escape.write() = None;
+            self.panic();
+        }
+    }
+
+    /// Reopen the output after an engine fault. Voices in flight are dropped; the project isn't touched.
+    pub fn restart_engine(&self) {
+        // The crashed mixer goes with its stream; a fresh one opens on next use
+        self.hold_resume_point();
+        self.stop_playback();
+        *self.engine.write() = None;
+        self.song_editor.stop();
+        self.stop_sequencer();
+        *self.engine_fault.write() = None;
+        *self.status.write() = t!("✓ Audio engine restarted").to_string();
+    }
+
+    pub fn toggle_net_send(&self) {
+        if self.net_sender.is_running() {
+            self.net_sender.stop();
+            *self.status.write() = t!("Network send stopped").to_string();
+            return;
+        }
+        *self.status.write() = match self.net_sender.start() {
+            Ok(port) => t!("📡 Sending master output on TCP port {}", port),
+            Err(e)   => t!("✗ Network send: {}", e),
+        };
+    }
+
+    /// Switch output backend; the engine closes now and reopens on next use.
+    pub fn set_audio_backend(&self, kind: BackendKind) {
+        let name = self.jack_client_name.read().clone();
+        match backend::create(kind, &name) {
+            Ok(b) => {
+                self.stop_playback();
+                *self.engine.write() = None;
+                *self.audio_backend.write() = b;
+                *self.status.write() = t!("✓ Audio: {}", kind.label());
+            }
+            Err(e) => *self.status.write() = format!("✗ {}", e),
+        }
+    }
+
+    /// Use a different output device / rate / buffer. A running engine is reopened on the
+    /// new settings; the sequencer carries on and main-sample playback resumes where it was.
+    pub fn set_output_selection(&self, sel: DeviceSelection) {
+        if sel == self.audio_devices.selection() { return; }
+        let label = sel.device_label();
+        self.audio_devices.set_selection(sel);
+        if self.engine.write().take().is_none() {
+            *self.status.write() = t!("✓ Output: {}", label);
+            return;
+        }
+        let Some(out) = self.ensure_engine() else { return; };
+        if self.is_playing.load(Ordering::Relaxed) {
+            if let Some(asset) = self.playback_asset.read().clone() { self.play_on_engine(asset); }
+        }
+        let rate = self.engine.read().as_ref().map_or(0, |e| e.spec().sample_rate);
+        *self.status.write() = t!("✓ Output: {} — {} Hz, {} ch", label, rate, out);
+    }
+
+    /// Follow the backend transport (JACK) when sync is on: play/stop and tempo.
+    pub fn sync_transport(&self) {
+        if !self.transport_sync.load(Ordering::Relaxed) { return; }
+        let Some(t) = self.audio_backend.read().transport() else { return; };
+        if let Some(bpm) = t.bpm { self.events.emit(EngineEvent::Bpm { bpm: bpm.clamp(40.0, 300.0) }); }
+        if t.rolling != self.events.playing() {
+            if t.rolling { self.start_sequencer(); } else { self.stop_sequencer(); }
+        }
     }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
-//  Stream infrastructure
+//  Performance macros
 // ═══════════════════════════════════════════════════════════════════════════════
+impl AppState {
+    /// Move macro `idx` to `value` (0-1) and push it to every mapped destination.
+    pub fn set_macro(&self, idx: usize, value: f32) {
+        let mappings = {
+            let mut macros = self.macros.write();
+            let Some(m) = macros.get_mut(idx) else { return; };
+            m.value = value.clamp(0.0, 1.0);
+            m.mappings.iter().map(|mp| (mp.dest, mp.value_at(m.value))).collect::<Vec<_>>()
+        };
+        for (dest, v) in mappings { self.set_macro_dest(dest, v); }
+    }
 
-struct StreamArgs {
-    channels: u16, pcm: Vec<f32>,
-    position: Arc<AtomicF32>, sample_index: Arc<AtomicU64>,
-    is_playing: Arc<AtomicBool>, total_samples: u64,
-    status: Arc<RwLock<String>>, stop_target: Arc<AtomicF32>,
-}
-
-fn build_stream<T: cpal::Sample + SizedSample + FromSample<f32> + 'static>(
-    device: &cpal::Device, config: &cpal::StreamConfig, args: StreamArgs,
-) -> Result<cpal::Stream, cpal::BuildStreamError> {
-    let ch = args.channels as usize; let total = args.total_samples; let pcm = args.pcm;
-    let err_status = args.status.clone(); let err_playing = args.is_playing.clone();
-    let err_fn = move |err| {
-        eprintln!("Audio error: {}", err);
-        *err_status.write() = format!("Playback error: {}", err);
-        err_playing.store(false, Ordering::Relaxed);
-    };
-    let d_status = args.status; let d_playing = args.is_playing; let d_pos = args.position;
-    let d_idx = args.sample_index; let d_stop = args.stop_target;
-    let stream = device.build_output_stream(config, move |data: &mut [T], _| {
-        let mut fp = d_idx.load(Ordering::Relaxed) as f64 / ch.max(1) as f64;
-        if !d_playing.load(Ordering::Relaxed) {
-            for d in data.iter_mut() { *d = T::from_sample(0.0f32); }
-            return;
+    fn set_macro_dest(&self, dest: MacroDest, v: f32) {
+        match dest {
+            MacroDest::MasterVolume => self.master_volume.store(v, Ordering::Relaxed),
+            MacroDest::MasterFilter => self.master_cutoff.store(v, Ordering::Relaxed),
+            MacroDest::Bpm          => self.events.emit(EngineEvent::Bpm { bpm: v }),
+            MacroDest::Crossfader   => self.crossfader.store(v, Ordering::Relaxed),
+            MacroDest::TrackAttack | MacroDest::TrackDecay
+            | MacroDest::TrackSustain | MacroDest::TrackRelease => {
+                let WaveformFocus::DrumTrack(idx) = self.waveform_focus.read().clone() else { return; };
+                let mut tracks = self.drum_tracks.write();
+                let Some(t) = tracks.get_mut(idx) else { return; };
+                match dest {
+                    MacroDest::TrackAttack  => t.adsr.attack  = v,
+                    MacroDest::TrackDecay   => t.adsr.decay   = v,
+                    MacroDest::TrackSustain => t.adsr.sustain = v,
+                    _                       => t.adsr.release = v,
+                }
+            }
         }
-        let frames     = data.len() / ch.max(1);
-        let pcm_frames = pcm.len() / ch.max(1);
-        let stop_pos   = d_stop.load(Ordering::Relaxed);
-        let target     = if stop_pos >= 0.0 { Some((stop_pos * pcm_frames as f32) as usize) } else { None };
-        let mut out    = 0usize;
-        'outer: for _ in 0..frames {
-            let i0 = fp as usize;
-            if let Some(t) = target { if i0 >= t { d_playing.store(false, Ordering::Relaxed); *d_status.write() = "Stopped at marker".to_string(); break 'outer; } }
-            if i0 >= pcm_frames.saturating_sub(1) { d_playing.store(false, Ordering::Relaxed); *d_status.write() = "Playback finished".to_string(); break 'outer; }
-            let i1 = (i0 + 1).min(pcm_frames - 1); let t = (fp - i0 as f64) as f32;
-            for c in 0..ch {
-                let s0 = pcm.get(i0 * ch + c).copied().unwrap_or(0.0);
-                let s1 = pcm.get(i1 * ch + c).copied().unwrap_or(0.0);
-                if out < data.len() { data[out] = T::from_sample(s0 + t * (s1 - s0)); }
-                out += 1;
-            }
-            fp += 1.0;
-        }
-        for d in data.iter_mut().skip(out) { *d = T::from_sample(0.0f32); }
-        if total > 0 { d_pos.store((fp * ch as f64 / total as f64).min(1.0) as f32, Ordering::Relaxed); }
-        d_idx.store((fp * ch as f64) as u64, Ordering::Relaxed);
-    }, err_fn, None)?;
-    Ok(stream)
+    }
 }
 
-pub mod ui;
\ No newline at end of file
+pub mod ui;
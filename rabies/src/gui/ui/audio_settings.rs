@@ -0,0 +1,260 @@
+// src/gui/ui/audio_settings.rs
+use eframe::egui;
+use std::sync::atomic::Ordering;
+use crate::backend::BackendKind;
+use crate::devices::BUFFER_SIZES;
+use crate::gui::AppState;
+use crate::i18n::{self, Language};
+use crate::safe_mode::SelfTest;
+use crate::midi::{MidiPort, DEFAULT_BPM_CC};
+use crate::footswitch::{Footswitch, SwitchAction, SwitchMode};
+
+impl AppState {
+    /// Output device, sample rate, buffer size and MIDI input; changes apply straight away.
+    pub fn draw_audio_settings(&mut self, ctx: &egui::Context) {
+        if !self.audio_settings_open.load(Ordering::Relaxed) { return; }
+        let jack    = self.audio_backend.read().kind() == BackendKind::Jack;
+        let devices = self.audio_devices.devices();
+        let rates   = self.audio_devices.selected_rates();
+        let running = self.engine.read().as_ref().map(|e| e.spec());
+        let mut sel = self.audio_devices.selection();
+        let mut refresh = false;
+        let midi_ports = self.midi_ports.read().clone();
+        let midi_in    = self.midi_input.lock().as_ref().map(|m| m.port.name.clone());
+        let mut ms     = self.midi_settings.read().clone();
+        let clock_out  = self.midi_clock_out.lock().as_ref().map(|c| c.port.name.clone());
+        let mut midi_pick: Option<Option<MidiPort>> = None;
+        let mut clock_pick: Option<Option<MidiPort>> = None;
+        let mut midi_scan = false;
+        let mut switches  = self.footswitches.read().clone();
+        let bindings      = self.midi_bindings.read().clone();
+        let armed         = *self.midi_learn.read();
+        let mut unbind    = None;
+
+        let mut window_open = true;
+        egui::Window::new(t!("🔊 Audio / MIDI settings"))
+            .id(egui::Id::new("audio_settings"))
+            .default_size([380.0, 300.0])
+            .collapsible(false)
+            .open(&mut window_open)
+            .show(ctx, |ui| {
+                if jack {
+                    ui.label(egui::RichText::new(t!("JACK sets the device, rate and buffer size — these apply to the System backend"))
+                        .small().color(egui::Color32::from_gray(140)));
+                }
+                ui.add_enabled_ui(!jack, |ui| {
+                    egui::Grid::new("audio_settings_grid").num_columns(2).spacing([10.0, 6.0]).show(ui, |ui| {
+                        ui.label(t!("Output"));
+                        ui.horizontal(|ui| {
+                            egui::ComboBox::from_id_source("output_device")
+                                .selected_text(sel.device_label())
+                                .width(260.0)
+                                .show_ui(ui, |ui| {
+                                    if ui.selectable_label(sel.device.is_none(), t!("System default")).clicked() {
+                                        sel.device = None;
+                                    }
+                                    for d in &devices {
+                                        if ui.selectable_label(sel.device.as_ref() == Some(d), &d.label).clicked() {
+                                            sel.device = Some(d.clone());
+                                        }
+                                    }
+                                });
+                            if ui.button("↻").on_hover_text(t!("Refresh devices")).clicked() { refresh = true; }
+                        });
+                        ui.end_row();
+
+                        ui.label(t!("Sample rate"));
+                        let rate_label = |r: Option<u32>| r.map_or(t!("Device default").to_string(), |r| t!("{} Hz", r));
+                        egui::ComboBox::from_id_source("output_rate")
+                            .selected_text(rate_label(sel.sample_rate))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut sel.sample_rate, None, rate_label(None));
+                                for &r in &rates { ui.selectable_value(&mut sel.sample_rate, Some(r), rate_label(Some(r))); }
+                            });
+                        ui.end_row();
+
+                        ui.label(t!("Buffer"));
+                        let buf_label = |b: Option<u32>| b.map_or(t!("Device default").to_string(), |b| t!("{} frames", b));
+                        egui::ComboBox::from_id_source("output_buffer")
+                            .selected_text(buf_label(sel.buffer_frames))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut sel.buffer_frames, None, buf_label(None));
+                                for b in BUFFER_SIZES { ui.selectable_value(&mut sel.buffer_frames, Some(b), buf_label(Some(b))); }
+                            });
+                        ui.end_row();
+                    });
+                });
+                ui.separator();
+                let text = match running {
+                    Some(spec) => t!("Running: {} Hz · {} ch", spec.sample_rate, spec.channels),
+                    None       => t!("Not running — opens on first sound").to_string(),
+                };
+                ui.label(egui::RichText::new(text).small().color(egui::Color32::from_gray(140)));
+                ui.horizontal(|ui| {
+                    let (text, color) = match &*self.self_test.read() {
+                        SelfTest::NotRun             => (t!("Self-test not run").to_string(), egui::Color32::from_gray(140)),
+                        SelfTest::Running            => (t!("Self-test running…").to_string(), egui::Color32::from_gray(140)),
+                        SelfTest::Done(r) if r.passed() => (format!("✓ {}", r.summary()), egui::Color32::from_rgb(120, 200, 120)),
+                        SelfTest::Done(r)            => (format!("✗ {}", r.summary()), egui::Color32::from_rgb(240, 90, 60)),
+                    };
+                    if ui.button(t!("🩺 Self-test")).on_hover_text(t!("List devices and run a short silent stream on this output")).clicked() {
+                        self.start_self_test();
+                    }
+                    ui.label(egui::RichText::new(text).small().color(color));
+                });
+                let mut safe = self.safe_mode.read().is_some();
+                if ui.checkbox(&mut safe, t!("🛟 Safe mode")).on_hover_text(t!("No audio this session; projects still open and save")).changed() {
+                    if safe { self.enter_safe_mode(t!("turned on in settings")); } else { self.leave_safe_mode(); }
+                }
+
+                ui.separator();
+                egui::Grid::new("midi_settings_grid").num_columns(2).spacing([10.0, 6.0]).show(ui, |ui| {
+                    ui.label(t!("MIDI input"));
+                    ui.horizontal(|ui| {
+                        egui::ComboBox::from_id_source("midi_input")
+                            .selected_text(midi_in.as_deref().unwrap_or(t!("None")))
+                            .width(260.0)
+                            .show_ui(ui, |ui| {
+                                if ui.selectable_label(midi_in.is_none(), t!("None")).clicked() { midi_pick = Some(None); }
+                                for p in midi_ports.iter().filter(|p| p.input) {
+                                    if ui.selectable_label(midi_in.as_ref() == Some(&p.name), &p.name).clicked() {
+                                        midi_pick = Some(Some(p.clone()));
+                                    }
+                                }
+                            });
+                        if ui.button("↻").on_hover_text(t!("Scan for MIDI devices")).clicked() { midi_scan = true; }
+                    });
+                    ui.end_row();
+
+                    ui.label(t!("Channel"));
+                    let ch_label = |c: Option<u8>| c.map_or(t!("All").to_string(), |c| format!("{}", c + 1));
+                    egui::ComboBox::from_id_source("midi_channel")
+                        .selected_text(ch_label(ms.channel))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut ms.channel, None, ch_label(None));
+                            for c in 0..16 { ui.selectable_value(&mut ms.channel, Some(c), ch_label(Some(c))); }
+                        });
+                    ui.end_row();
+
+                    ui.label(t!("Transport"));
+                    ui.checkbox(&mut ms.transport, t!("Start / Stop run the sequencer"));
+                    ui.end_row();
+
+                    ui.label(t!("Tempo"));
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut ms.follow_clock, t!("Follow MIDI clock"));
+                        let mut by_cc = ms.bpm_cc.is_some();
+                        if ui.checkbox(&mut by_cc, t!("BPM from CC")).on_hover_text(t!("The CC sweeps 40-300 BPM")).changed() {
+                            ms.bpm_cc = by_cc.then_some(ms.bpm_cc.unwrap_or(DEFAULT_BPM_CC));
+                        }
+                        if let Some(cc) = ms.bpm_cc.as_mut() { ui.add(egui::DragValue::new(cc).clamp_range(0..=127)); }
+                    });
+                    ui.end_row();
+
+                    ui.label(t!("Clock"));
+                    ui.checkbox(&mut ms.clock_sync, t!("Sync to MIDI clock"))
+                        .on_hover_text(t!("The sequencer steps on the device's clock instead of its own, following its Start and Stop"));
+                    ui.end_row();
+
+                    ui.label(t!("Clock out"));
+                    egui::ComboBox::from_id_source("midi_clock_out")
+                        .selected_text(clock_out.as_deref().unwrap_or(t!("None")))
+                        .width(260.0)
+                        .show_ui(ui, |ui| {
+                            if ui.selectable_label(clock_out.is_none(), t!("None")).clicked() { clock_pick = Some(None); }
+                            for p in midi_ports.iter().filter(|p| p.output) {
+                                if ui.selectable_label(clock_out.as_ref() == Some(&p.name), &p.name).clicked() {
+                                    clock_pick = Some(Some(p.clone()));
+                                }
+                            }
+                        })
+                        .response
+                        .on_hover_text(t!("Send MIDI clock, Start and Stop so other gear follows our BPM"));
+                    ui.end_row();
+                });
+                ui.label(egui::RichText::new(t!("Notes play the pads through the ⌨ mapping profile, with velocity"))
+                    .small().color(egui::Color32::from_gray(140)));
+
+                ui.separator();
+                ui.label(t!("Footswitches"));
+                let mut remove = None;
+                egui::Grid::new("footswitch_grid").num_columns(4).spacing([8.0, 4.0]).show(ui, |ui| {
+                    for (i, s) in switches.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.label(t!("CC"));
+                            ui.add(egui::DragValue::new(&mut s.cc).clamp_range(0..=127));
+                        });
+                        egui::ComboBox::from_id_source(("footswitch_action", i))
+                            .selected_text(s.action.label())
+                            .show_ui(ui, |ui| {
+                                for a in SwitchAction::ALL { ui.selectable_value(&mut s.action, a, a.label()); }
+                            });
+                        ui.add_enabled_ui(s.action.holds(), |ui| {
+                            ui.horizontal(|ui| {
+                                for m in SwitchMode::ALL {
+                                    let hint = match m {
+                                        SwitchMode::Momentary => t!("On while the switch is down (or a latching pedal is on)"),
+                                        SwitchMode::Latching  => t!("Each press toggles"),
+                                    };
+                                    ui.selectable_value(&mut s.mode, m, m.label()).on_hover_text(hint);
+                                }
+                            });
+                        });
+                        if ui.small_button("🗑").clicked() { remove = Some(i); }
+                        ui.end_row();
+                    }
+                });
+                if let Some(i) = remove { switches.remove(i); }
+                if ui.button(t!("＋ Footswitch")).on_hover_text(t!("Map a CC (64 is the sustain pedal) to a transport action")).clicked() {
+                    switches.push(Footswitch::sustain());
+                }
+
+                ui.separator();
+                ui.label(t!("MIDI learn"));
+                if let Some(target) = armed {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(t!("Waiting for {}…", target.label())).color(egui::Color32::from_rgb(237, 164, 80)));
+                        if ui.small_button(t!("Cancel")).clicked() { self.toggle_midi_learn(target); }
+                    });
+                }
+                egui::Grid::new("midi_learn_grid").num_columns(3).spacing([8.0, 4.0]).show(ui, |ui| {
+                    for (i, b) in bindings.iter().enumerate() {
+                        ui.label(b.source.label());
+                        ui.label(format!("→ {}", b.target.label()));
+                        if ui.small_button("🗑").clicked() { unbind = Some(i); }
+                        ui.end_row();
+                    }
+                });
+                ui.label(egui::RichText::new(t!("Right-click a pad, a track's M, the BPM field or a step, then play a note or move a control"))
+                    .small().color(egui::Color32::from_gray(140)));
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label(t!("🌐 Language"));
+                    let current = i18n::language();
+                    egui::ComboBox::from_id_source("ui_language")
+                        .selected_text(current.label())
+                        .show_ui(ui, |ui| {
+                            for l in Language::ALL {
+                                if ui.selectable_label(l == current, l.label()).clicked() { i18n::set_language(l); }
+                            }
+                        });
+                });
+            });
+
+        if refresh { self.audio_devices.refresh(); }
+        if midi_scan { self.refresh_midi_ports(); }
+        if let Some(port) = midi_pick { self.connect_midi_input(port); }
+        if let Some(port) = clock_pick { self.connect_clock_output(port); }
+        *self.midi_settings.write() = ms;
+        if switches != *self.footswitches.read() { *self.footswitches.write() = switches; }
+        if let Some(i) = unbind { self.midi_bindings.write().remove(i); }
+        // A device that can't run the chosen rate falls back to its own default
+        if sel.device != self.audio_devices.selection().device {
+            let rates = sel.device.as_ref().map_or_else(|| self.audio_devices.selected_rates(), |d| d.rates.clone());
+            if sel.sample_rate.is_some_and(|r| !rates.contains(&r)) { sel.sample_rate = None; }
+        }
+        self.set_output_selection(sel);
+        if !window_open { self.audio_settings_open.store(false, Ordering::Relaxed); }
+    }
+}
@@ -0,0 +1,103 @@
+// src/gui/ui/autochop_dialog.rs
+use eframe::egui;
+use crate::autochop::{AutoChopMode, THUMB_BUCKETS};
+use crate::gui::AppState;
+
+/// Candidates per row of the matrix.
+const COLUMNS: usize = 4;
+
+impl AppState {
+    /// Grid of auto-chop candidates: audition each one and tick the ones to keep as chops.
+    pub fn draw_autochop_dialog(&mut self, ctx: &egui::Context) {
+        if self.pending_autochop.read().is_none() { return; }
+
+        let mut preview = None;
+        let mut add     = false;
+        let mut cancel  = false;
+        let mut window_open = true;
+        egui::Window::new(t!("✂ Auto-chop"))
+            .id(egui::Id::new("autochop_dialog"))
+            .default_size([520.0, 360.0])
+            .collapsible(false)
+            .open(&mut window_open)
+            .show(ctx, |ui| {
+                let mut guard = self.pending_autochop.write();
+                let Some(d) = guard.as_mut() else { return; };
+                let sr = d.asset.sample_rate.max(1) as f32;
+                ui.label(egui::RichText::new(&d.asset.file_name).strong());
+                ui.horizontal(|ui| {
+                    for mode in AutoChopMode::ALL {
+                        let hint = match mode {
+                            AutoChopMode::Transients => t!("Level jumps — loops and mixed material"),
+                            AutoChopMode::DrumStem   => t!("Envelope peaks at least one sequencer step apart — isolated drum stems"),
+                        };
+                        if ui.selectable_label(d.mode == mode, mode.label()).on_hover_text(hint).clicked() && d.mode != mode {
+                            d.mode = mode;
+                            d.detect();
+                        }
+                    }
+                    ui.separator();
+                    ui.label(t!("Sensitivity"));
+                    let resp = ui.add(egui::Slider::new(&mut d.sensitivity, 0.0..=1.0).show_value(false))
+                        .on_hover_text(t!("Higher finds quieter hits"));
+                    if resp.drag_stopped() || (resp.changed() && !resp.dragged()) { d.detect(); }
+                    ui.separator();
+                    if ui.small_button(t!("All")).clicked()  { d.keep.fill(true); }
+                    if ui.small_button(t!("None")).clicked() { d.keep.fill(false); }
+                });
+                ui.separator();
+
+                if d.onsets.is_empty() {
+                    ui.label(egui::RichText::new(t!("No new hits — try a higher sensitivity")).color(egui::Color32::from_gray(120)));
+                }
+                egui::ScrollArea::vertical().max_height(280.0).show(ui, |ui| {
+                    egui::Grid::new("autochop_matrix").spacing([8.0, 6.0]).show(ui, |ui| {
+                        for i in 0..d.onsets.len() {
+                            let (s, e) = d.slice(i);
+                            let kept = d.keep[i];
+                            ui.vertical(|ui| {
+                                let (rect, _) = ui.allocate_exact_size(egui::vec2(96.0, 28.0), egui::Sense::hover());
+                                let painter = ui.painter_at(rect);
+                                painter.rect_filled(rect, 2.0, egui::Color32::from_gray(22));
+                                let col = if kept { egui::Color32::from_rgb(80, 220, 140) } else { egui::Color32::from_gray(70) };
+                                let bw  = rect.width() / THUMB_BUCKETS as f32;
+                                for (b, peak) in d.thumbs[i].iter().enumerate() {
+                                    let h = (peak * rect.height()).clamp(1.0, rect.height());
+                                    let x = rect.left() + b as f32 * bw;
+                                    painter.rect_filled(
+                                        egui::Rect::from_center_size(egui::pos2(x + bw / 2.0, rect.center().y), egui::vec2(bw - 1.0, h)),
+                                        0.0, col,
+                                    );
+                                }
+                                ui.horizontal(|ui| {
+                                    if ui.small_button("▶").on_hover_text(t!("{:.3} s long", (e - s) as f32 / sr)).clicked() {
+                                        preview = Some(i);
+                                    }
+                                    ui.checkbox(&mut d.keep[i], t!("{} · {:.2}s", i + 1, s as f32 / sr));
+                                });
+                            });
+                            if (i + 1) % COLUMNS == 0 { ui.end_row(); }
+                        }
+                    });
+                });
+
+                ui.separator();
+                let n = d.keep.iter().filter(|&&k| k).count();
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(n > 0, egui::Button::new(
+                        egui::RichText::new(t!("＋ Add {} chop{}", n, if n == 1 { "" } else { "s" })).color(egui::Color32::from_rgb(80, 220, 140))
+                    )).clicked() {
+                        add = true;
+                    }
+                    if ui.button(t!("Cancel")).clicked() { cancel = true; }
+                });
+            });
+
+        if let Some(i) = preview { self.preview_auto_chop(i); }
+        if add {
+            self.commit_auto_chop();
+        } else if cancel || !window_open {
+            self.cancel_auto_chop();
+        }
+    }
+}
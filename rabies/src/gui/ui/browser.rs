@@ -0,0 +1,92 @@
+// src/gui/ui/browser.rs
+use eframe::egui;
+use std::sync::atomic::Ordering;
+use crate::audio::AudioAsset;
+use crate::channels::layout_name;
+use crate::gui::AppState;
+use crate::prelisten::AUDITION_SECS;
+
+/// "44100 Hz · stereo · 1.24 s · Title — Artist · 92 BPM · Am"
+pub fn sample_info_line(asset: &AudioAsset) -> String {
+    let secs = asset.frames as f32 / asset.sample_rate.max(1) as f32;
+    let mut line = format!("{} Hz · {} · {:.2} s", asset.sample_rate, layout_name(asset.channels as usize), secs);
+    if !asset.tags.is_empty() {
+        line.push_str(" · ");
+        line.push_str(&asset.tags.summary());
+    }
+    line
+}
+
+impl AppState {
+    /// Every sample in the pool, searchable by file name and tags.
+    pub fn draw_sample_browser(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(egui::RichText::new(t!("🗂 Sample Browser")).strong())
+            .id_source("sample_browser")
+            .show(ui, |ui| {
+                let mut query = self.browser_query.read().clone();
+                ui.horizontal(|ui| {
+                    ui.label("🔍");
+                    if ui.add(egui::TextEdit::singleline(&mut query).hint_text(t!("name, title, artist, BPM, key"))
+                        .desired_width(260.0)).changed() {
+                        *self.browser_query.write() = query.clone();
+                    }
+                    ui.separator();
+                    let mut duck = self.prelisten_duck_db.load(Ordering::Relaxed);
+                    ui.label(egui::RichText::new(t!("Duck")).small().color(egui::Color32::from_gray(100)));
+                    if ui.add(egui::DragValue::new(&mut duck).speed(0.5).clamp_range(-40.0..=0.0).suffix(" dB"))
+                        .on_hover_text(t!("How far everything else dips while a sample prelistens (0 = not at all)"))
+                        .changed() {
+                        self.prelisten_duck_db.store(duck, Ordering::Relaxed);
+                    }
+                    if ui.small_button("⏹").on_hover_text(t!("Stop the prelisten")).clicked() { self.stop_prelisten(); }
+                    if ui.small_button("🎧").on_hover_text(t!("Audition a file before loading it: its first {:.0} s prelisten", AUDITION_SECS)).clicked() {
+                        self.audition_file();
+                    }
+                });
+                let audition = self.audition.read().as_ref().map(|a| a.asset.clone());
+                if let Some(asset) = audition {
+                    ui.horizontal(|ui| {
+                        if ui.small_button("＋").on_hover_text(t!("Load the whole file as a new track")).clicked() { self.load_audition(); }
+                        if ui.small_button("▶").on_hover_text(t!("Prelisten again")).clicked() { self.prelisten(&asset); }
+                        if ui.small_button("✕").on_hover_text(t!("Dismiss")).clicked() {
+                            self.stop_prelisten();
+                            *self.audition.write() = None;
+                        }
+                        ui.label(egui::RichText::new(t!("🎧 {} (first {:.0} s)", asset.file_name, AUDITION_SECS)).color(egui::Color32::from_rgb(120, 200, 240)));
+                    });
+                }
+                let needle = query.trim().to_lowercase();
+                let mut entries: Vec<(String, std::sync::Arc<AudioAsset>)> = self.asset_pool.read().iter()
+                    .filter(|(_, a)| needle.is_empty()
+                        || a.file_name.to_lowercase().contains(&needle)
+                        || a.tags.matches(&needle))
+                    .map(|(p, a)| (p.clone(), a.clone()))
+                    .collect();
+                entries.sort_by(|a, b| a.1.file_name.cmp(&b.1.file_name));
+
+                if entries.is_empty() {
+                    ui.label(egui::RichText::new(if needle.is_empty() { t!("No samples loaded yet") } else { t!("No matches") })
+                        .italics().color(egui::Color32::GRAY));
+                    return;
+                }
+                let mut add: Option<String> = None;
+                let mut listen: Option<std::sync::Arc<AudioAsset>> = None;
+                egui::ScrollArea::vertical().id_source("browser_scroll").max_height(180.0).show(ui, |ui| {
+                    for (path, asset) in &entries {
+                        ui.horizontal(|ui| {
+                            if ui.small_button("＋").on_hover_text(t!("Add as a new track")).clicked() {
+                                add = Some(path.clone());
+                            }
+                            if ui.small_button("▶").on_hover_text(t!("Prelisten — whatever is playing dips under it")).clicked() {
+                                listen = Some(asset.clone());
+                            }
+                            ui.label(&asset.file_name).on_hover_text(path);
+                            ui.label(egui::RichText::new(sample_info_line(asset)).small().color(egui::Color32::from_gray(130)));
+                        });
+                    }
+                });
+                if let Some(path) = add { self.add_track_from_pool(&path); }
+                if let Some(asset) = listen { self.prelisten(&asset); }
+            });
+    }
+}
@@ -0,0 +1,74 @@
+// src/gui/ui/kit_report_window.rs
+use eframe::egui;
+use crate::gui::AppState;
+use crate::jobs::JobKind;
+use crate::kit_report::KitFix;
+use crate::limiter::CEILING;
+
+impl AppState {
+    /// Per-track levels of the kit, with the suggested fixes as buttons.
+    pub fn draw_kit_report(&mut self, ctx: &egui::Context) {
+        let Some(rows) = self.kit_report.read().clone() else { return; };
+
+        let mut apply: Vec<(usize, KitFix)> = Vec::new();
+        let mut refresh = false;
+        let mut window_open = true;
+        egui::Window::new(t!("📊 Kit report"))
+            .id(egui::Id::new("kit_report"))
+            .default_size([640.0, 280.0])
+            .open(&mut window_open)
+            .show(ctx, |ui| {
+                if rows.is_empty() {
+                    ui.label(egui::RichText::new(t!("No drum tracks yet")).italics().color(egui::Color32::GRAY));
+                }
+                let dim = egui::Color32::from_gray(140);
+                let hot = egui::Color32::from_rgb(240, 110, 80);
+                egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                    egui::Grid::new("kit_report_grid").num_columns(7).striped(true).spacing([12.0, 4.0]).show(ui, |ui| {
+                        for h in [t!("Track"), t!("Peak"), t!("RMS"), t!("LUFS"), t!("Length"), t!("Effective"), t!("Suggestions")] {
+                            ui.label(egui::RichText::new(h).small().color(dim));
+                        }
+                        ui.end_row();
+                        for r in &rows {
+                            ui.label(format!("{} {}", r.track_idx + 1, r.name));
+                            let over = r.peak_db > 20.0 * CEILING.log10() + 0.1;
+                            ui.label(egui::RichText::new(t!("{:.1} dB", r.peak_db)).monospace()
+                                .color(if over { hot } else { ui.visuals().text_color() }));
+                            ui.label(egui::RichText::new(t!("{:.1} dB", r.rms_db)).monospace());
+                            ui.label(egui::RichText::new(r.lufs.map_or("—".to_string(), |l| format!("{:.1}", l))).monospace());
+                            ui.label(egui::RichText::new(t!("{:.2} s", r.length_secs)).monospace());
+                            ui.label(egui::RichText::new(t!("{:.2} s", r.effective_secs)).monospace());
+                            ui.horizontal(|ui| {
+                                if r.fixes.is_empty() { ui.label(egui::RichText::new(t!("✓ fine")).small().color(dim)); }
+                                for &fix in &r.fixes {
+                                    if ui.small_button(fix.label(r.sample_rate)).clicked() { apply.push((r.track_idx, fix)); }
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let all: Vec<(usize, KitFix)> = rows.iter()
+                        .flat_map(|r| r.fixes.iter().map(move |&f| (r.track_idx, f)))
+                        .collect();
+                    if ui.add_enabled(!all.is_empty(), egui::Button::new(t!("Apply all"))).clicked() { apply = all; }
+                    if ui.button(t!("↻ Re-measure")).clicked() { refresh = true; }
+                    if self.jobs.busy(JobKind::Analysis) {
+                        ui.spinner();
+                        ui.label(egui::RichText::new(t!("Measuring…")).small().color(egui::Color32::from_gray(140)));
+                    }
+                });
+            });
+
+        // Fixes change the levels, so measure again once they're all in
+        if !apply.is_empty() { refresh = true; }
+        for (track_idx, fix) in apply { self.apply_kit_fix(track_idx, fix); }
+        if !window_open {
+            *self.kit_report.write() = None;
+        } else if refresh {
+            self.open_kit_report();
+        }
+    }
+}
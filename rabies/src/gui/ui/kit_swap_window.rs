@@ -0,0 +1,87 @@
+// src/gui/ui/kit_swap_window.rs
+use eframe::egui;
+use crate::bus::AppCommand;
+use crate::gui::AppState;
+
+enum SwapAction { Step(usize, isize), Keep(usize), Revert(usize) }
+
+impl AppState {
+    /// Per-track stand-ins from the browser, stepped through while the pattern plays.
+    pub fn draw_kit_swap(&mut self, ctx: &egui::Context) {
+        // (name, candidate being heard, position, count, track still there)
+        let rows: Vec<(String, Option<String>, usize, usize, bool)> = {
+            let guard = self.kit_swap.read();
+            let Some(swap) = guard.as_ref() else { return; };
+            let tracks = self.drum_tracks.read();
+            swap.rows.iter().map(|r| {
+                let hearing = r.current.map(|c| {
+                    let path = &r.candidates[c];
+                    std::path::Path::new(path).file_name().map_or(path.clone(), |n| n.to_string_lossy().to_string())
+                });
+                let present = tracks.iter().any(|t| t.sample_uuid == r.sample_uuid);
+                (r.name.clone(), hearing, r.current.map_or(0, |c| c + 1), r.candidates.len(), present)
+            }).collect()
+        };
+
+        let mut action: Option<SwapAction> = None;
+        let mut window_open = true;
+        egui::Window::new(t!("🔀 Swap kit"))
+            .id(egui::Id::new("kit_swap"))
+            .default_size([520.0, 260.0])
+            .open(&mut window_open)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let playing = self.events.playing();
+                    if ui.button(if playing { t!("⏹ Stop pattern") } else { t!("▶ Loop pattern") }).clicked() {
+                        self.send(AppCommand::TogglePlay);
+                    }
+                    ui.label(egui::RichText::new(t!("Unkept tracks go back to their own sample on close"))
+                        .small().color(egui::Color32::GRAY));
+                });
+                ui.separator();
+                if rows.is_empty() {
+                    ui.label(egui::RichText::new(t!("Nothing left to swap")).italics().color(egui::Color32::GRAY));
+                }
+                let dim = egui::Color32::from_gray(140);
+                egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                    egui::Grid::new("kit_swap_grid").num_columns(3).striped(true).spacing([12.0, 4.0]).show(ui, |ui| {
+                        for (i, (name, hearing, pos, count, present)) in rows.iter().enumerate() {
+                            ui.label(name);
+                            if !present {
+                                ui.label(egui::RichText::new(t!("track gone")).small().color(dim));
+                                ui.label("");
+                            } else if *count == 0 {
+                                ui.label(egui::RichText::new(t!("no similar samples in the browser")).small().color(dim));
+                                ui.label("");
+                            } else {
+                                ui.horizontal(|ui| {
+                                    if ui.small_button("◀").clicked() { action = Some(SwapAction::Step(i, -1)); }
+                                    let shown = hearing.clone().unwrap_or_else(|| "original".to_string());
+                                    ui.label(egui::RichText::new(format!("{}  ({}/{})", shown, pos, count))
+                                        .color(if hearing.is_some() { ui.visuals().strong_text_color() } else { dim }));
+                                    if ui.small_button("▶").clicked() { action = Some(SwapAction::Step(i, 1)); }
+                                });
+                                ui.horizontal(|ui| {
+                                    if ui.add_enabled(hearing.is_some(), egui::Button::new(t!("✓ Keep"))).clicked() {
+                                        action = Some(SwapAction::Keep(i));
+                                    }
+                                    if ui.add_enabled(hearing.is_some(), egui::Button::new(t!("↺ Original"))).clicked() {
+                                        action = Some(SwapAction::Revert(i));
+                                    }
+                                });
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+
+        match action {
+            Some(SwapAction::Step(i, dir)) => self.kit_swap_step(i, dir),
+            Some(SwapAction::Keep(i))      => self.kit_swap_keep(i),
+            Some(SwapAction::Revert(i))    => self.kit_swap_revert(i),
+            None => {}
+        }
+        if !window_open { self.close_kit_swap(); }
+    }
+}
@@ -0,0 +1,78 @@
+// src/gui/ui/merge_window.rs
+use eframe::egui;
+use crate::gui::AppState;
+use crate::project::MergeMode;
+
+impl AppState {
+    /// "Import from project…": choose what each pattern of the other file becomes here.
+    pub fn draw_merge_window(&mut self, ctx: &egui::Context) {
+        if self.merge_import.read().is_none() { return; }
+        let taken: Vec<String> = self.song_editor.get_all_patterns().into_iter().map(|p| p.name).collect();
+        let here  = *self.seq_resolution.read();
+
+        let mut window_open = true;
+        let mut do_import   = false;
+        let mut cancel      = false;
+        egui::Window::new(t!("⤵ Import from project"))
+            .id(egui::Id::new("merge_import_window"))
+            .default_size([420.0, 320.0])
+            .collapsible(false)
+            .open(&mut window_open)
+            .show(ctx, |ui| {
+                let mut guard = self.merge_import.write();
+                let Some(merge) = guard.as_mut() else { return; };
+                ui.label(egui::RichText::new(merge.path.display().to_string())
+                    .small().color(egui::Color32::from_gray(140)));
+                if merge.project.resolution != here {
+                    ui.label(egui::RichText::new(t!("Steps will be remapped from {} to {}",
+                        merge.project.resolution.label(), here.label()))
+                        .small().color(egui::Color32::from_rgb(220, 180, 80)));
+                }
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(t!("All")).small().color(egui::Color32::from_gray(120)));
+                    for mode in MergeMode::ALL {
+                        if ui.small_button(mode.label()).on_hover_text(mode.hint()).clicked() {
+                            merge.modes.iter_mut().for_each(|m| *m = mode);
+                        }
+                    }
+                });
+                ui.separator();
+
+                egui::ScrollArea::vertical().max_height(220.0).show(ui, |ui| {
+                    for (pattern, mode) in merge.project.patterns.iter().zip(merge.modes.iter_mut()) {
+                        ui.horizontal(|ui| {
+                            let (r, g, b) = pattern.color;
+                            ui.label(egui::RichText::new("■").color(egui::Color32::from_rgb(r, g, b)));
+                            ui.label(&pattern.name);
+                            ui.label(egui::RichText::new(t!("{} track(s)", pattern.tracks.len()))
+                                .small().color(egui::Color32::from_gray(120)));
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                for m in MergeMode::ALL.iter().rev() {
+                                    if ui.selectable_label(*mode == *m, m.label()).on_hover_text(m.hint()).clicked() {
+                                        *mode = *m;
+                                    }
+                                }
+                                if *mode == MergeMode::Pattern && taken.contains(&pattern.name) {
+                                    ui.label(egui::RichText::new(t!("name taken → renamed"))
+                                        .small().color(egui::Color32::from_rgb(220, 180, 80)));
+                                }
+                            });
+                        });
+                    }
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let any = merge.modes.iter().any(|m| *m != MergeMode::Skip);
+                    if ui.add_enabled(any, egui::Button::new(t!("⤵ Import"))).clicked() { do_import = true; }
+                    if ui.button(t!("Cancel")).clicked() { cancel = true; }
+                });
+            });
+
+        if do_import {
+            self.apply_merge_import();
+        } else if cancel || !window_open {
+            *self.merge_import.write() = None;
+        }
+    }
+}
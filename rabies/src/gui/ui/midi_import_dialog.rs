@@ -0,0 +1,69 @@
+// src/gui/ui/midi_import_dialog.rs
+use eframe::egui;
+use crate::gui::AppState;
+use crate::midi_file::note_name;
+
+impl AppState {
+    /// Note → row mapping for a MIDI clip about to be imported.
+    pub fn draw_midi_import_dialog(&mut self, ctx: &egui::Context) {
+        if self.pending_midi_import.read().is_none() { return; }
+
+        let lanes = self.grid_lanes();
+        let labels: Vec<String> = lanes.iter().map(|&l| self.lane_label(l)).collect();
+        let mut import = false;
+        let mut cancel = false;
+        let mut window_open = true;
+        egui::Window::new(t!("🎼 Import MIDI clip"))
+            .id(egui::Id::new("midi_import_dialog"))
+            .default_size([360.0, 300.0])
+            .collapsible(false)
+            .open(&mut window_open)
+            .show(ctx, |ui| {
+                let mut guard = self.pending_midi_import.write();
+                let Some(d) = guard.as_mut() else { return; };
+                ui.label(egui::RichText::new(&d.file_name).strong());
+                ui.label(egui::RichText::new(t!("Rows that get notes are cleared first"))
+                    .small().color(egui::Color32::from_gray(140)));
+
+                egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    egui::Grid::new("midi_import_grid").num_columns(3).spacing([10.0, 4.0]).show(ui, |ui| {
+                        for m in d.map.iter_mut() {
+                            ui.label(format!("{} ({})", note_name(m.note), m.note));
+                            ui.label(egui::RichText::new(format!("{}×", m.hits)).color(egui::Color32::from_gray(140)));
+                            let shown = m.lane.and_then(|l| lanes.iter().position(|&x| x == l))
+                                .map_or("—", |i| labels[i].as_str());
+                            egui::ComboBox::from_id_source(("midi_import_lane", m.note))
+                                .selected_text(shown)
+                                .width(200.0)
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(&mut m.lane, None, "—");
+                                    for (&lane, label) in lanes.iter().zip(&labels) {
+                                        ui.selectable_value(&mut m.lane, Some(lane), label);
+                                    }
+                                });
+                            ui.end_row();
+                        }
+                    });
+                });
+
+                if let Some(bpm) = d.clip.bpm {
+                    ui.checkbox(&mut d.use_tempo, t!("Set BPM to the file's {:.0}", bpm));
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let any = d.map.iter().any(|m| m.lane.is_some());
+                    if ui.add_enabled(any, egui::Button::new(egui::RichText::new(t!("⤓ Import")).color(egui::Color32::from_rgb(80, 220, 140))))
+                        .clicked() {
+                        import = true;
+                    }
+                    if ui.button(t!("Cancel")).clicked() { cancel = true; }
+                });
+            });
+
+        if import {
+            self.commit_midi_import();
+        } else if cancel || !window_open {
+            *self.pending_midi_import.write() = None;
+        }
+    }
+}
@@ -5,6 +5,19 @@ pub mod widgets;
 pub mod panels;
 pub mod view;
 pub mod pattern_playlist;
+pub mod pad_panel;
+pub mod browser;
+pub mod merge_window;
+pub mod versions_window;
+pub mod trim_dialog;
+pub mod autochop_dialog;
+pub mod midi_import_dialog;
+pub mod pinned_waveform;
+pub mod kit_report_window;
+pub mod kit_swap_window;
+pub mod audio_settings;
+pub mod tasks_window;
+pub mod notes_window;
 // ✅ DON'T re-export AppState - it's in crate::gui
 // Remove: pub use view::AppState;
 
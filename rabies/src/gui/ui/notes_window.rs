@@ -0,0 +1,85 @@
+// src/gui/ui/notes_window.rs
+use eframe::egui;
+use std::sync::atomic::Ordering;
+use crate::gui::AppState;
+use crate::notes::{Clearance, SampleNotes};
+
+/// Clearance picker, source and notes fields for one sample. True when something changed.
+pub fn sample_notes_editor(ui: &mut egui::Ui, id: &str, notes: &mut SampleNotes) -> bool {
+    let mut changed = false;
+    egui::ComboBox::from_id_source(("clearance", id))
+        .selected_text(notes.clearance.label())
+        .show_ui(ui, |ui| {
+            for c in Clearance::ALL {
+                changed |= ui.selectable_value(&mut notes.clearance, c, c.label()).changed();
+            }
+        });
+    changed |= ui.add(egui::TextEdit::singleline(&mut notes.source)
+        .hint_text(t!("Source / credit"))
+        .desired_width(200.0)).changed();
+    changed |= ui.add(egui::TextEdit::singleline(&mut notes.notes)
+        .hint_text(t!("Notes"))
+        .desired_width(200.0)).changed();
+    changed
+}
+
+impl AppState {
+    /// Project notes and credits, and the source and clearance of every sample in use.
+    pub fn draw_notes_window(&mut self, ctx: &egui::Context) {
+        if !self.notes_open.load(Ordering::Relaxed) { return; }
+        let files = self.project_sample_files();
+        let only_id = egui::Id::new("notes_only_outstanding");
+        let mut only_outstanding = ctx.data(|d| d.get_temp::<bool>(only_id).unwrap_or(false));
+
+        let mut window_open = true;
+        egui::Window::new(t!("📝 Notes & credits"))
+            .id(egui::Id::new("notes_window"))
+            .default_size([620.0, 420.0])
+            .collapsible(false)
+            .open(&mut window_open)
+            .show(ctx, |ui| {
+                {
+                    let mut project = self.project_notes.write();
+                    ui.label(t!("Project notes"));
+                    ui.add(egui::TextEdit::multiline(&mut project.notes).desired_rows(3).desired_width(f32::INFINITY));
+                    ui.label(t!("Credits"));
+                    ui.add(egui::TextEdit::multiline(&mut project.credits)
+                        .hint_text(t!("Writers, players, samples to credit on release"))
+                        .desired_rows(2).desired_width(f32::INFINITY));
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label(t!("Samples"));
+                    let outstanding = self.outstanding_clearances();
+                    if outstanding > 0 {
+                        ui.label(egui::RichText::new(t!("{} still to clear", outstanding)).small()
+                            .color(egui::Color32::from_rgb(240, 170, 60)));
+                    }
+                    ui.checkbox(&mut only_outstanding, t!("Only those to clear"));
+                    if ui.button(t!("📋 Copy credits")).on_hover_text(t!("Project credits and every sample's source, as text")).clicked() {
+                        ui.output_mut(|o| o.copied_text = self.credits_text());
+                        *self.status.write() = t!("✓ Credits copied").to_string();
+                    }
+                });
+                if files.is_empty() {
+                    ui.label(egui::RichText::new(t!("No samples yet")).small().color(egui::Color32::from_gray(100)));
+                }
+                egui::ScrollArea::vertical().max_height(260.0).show(ui, |ui| {
+                    egui::Grid::new("sample_notes_grid").num_columns(4).striped(true).spacing([8.0, 4.0]).show(ui, |ui| {
+                        for file in &files {
+                            let mut notes = self.sample_notes(file);
+                            if only_outstanding && !notes.clearance.outstanding() { continue; }
+                            let name = std::path::Path::new(file).file_name()
+                                .map_or_else(|| file.clone(), |n| n.to_string_lossy().to_string());
+                            ui.label(name).on_hover_text(file);
+                            if sample_notes_editor(ui, file, &mut notes) { self.set_sample_notes(notes); }
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+
+        ctx.data_mut(|d| d.insert_temp(only_id, only_outstanding));
+        if !window_open { self.notes_open.store(false, Ordering::Relaxed); }
+    }
+}
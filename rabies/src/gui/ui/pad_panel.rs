@@ -0,0 +1,381 @@
+// src/gui/ui/pad_panel.rs
+use eframe::egui;
+use std::sync::atomic::Ordering;
+use crate::gui::{AppState, WaveformFocus};
+use crate::mapping::KEY_SLOTS;
+use crate::midi_learn::LearnTarget;
+use crate::pads::{PadGridSize, PadLayout};
+use super::widgets::*;
+
+impl AppState {
+    /// Sample pads for the focused drum track: pad N plays chop N, or file N of the
+    /// picked pad bank. As hot cues, pad N jumps the main playhead to marker N of the
+    /// sample being played (or shown).
+    pub fn draw_sample_pads(&mut self, ui: &mut egui::Ui) {
+        let focus    = self.waveform_focus.read().clone();
+        let hot_cues = self.pad_hot_cues.load(Ordering::Relaxed);
+        let track_info = if hot_cues {
+            let playing = if self.is_playing.load(Ordering::Relaxed) { self.playback_asset.read().clone() } else { None };
+            playing.or_else(|| self.focused_asset())
+                .map(|a| (a.sample_uuid, a.frames as f32 / a.sample_rate as f32))
+        } else if let WaveformFocus::DrumTrack(idx) = focus {
+            let tracks = self.drum_tracks.read();
+            tracks.get(idx).map(|t| (t.sample_uuid, t.asset.frames as f32 / t.asset.sample_rate as f32))
+        } else { None };
+        let marks = track_info
+            .map(|(uuid, _)| self.samples_manager.get_marks_for_sample(&uuid))
+            .unwrap_or_default();
+        // Hot cue the playhead is in: the last marker it has passed
+        let live_cue = if hot_cues && self.is_playing.load(Ordering::Relaxed) {
+            let pos = self.playback_position.load(Ordering::Relaxed);
+            marks.iter().enumerate()
+                .filter(|(_, m)| m.position <= pos)
+                .max_by(|a, b| a.1.position.total_cmp(&b.1.position))
+                .map(|(i, _)| i)
+        } else { None };
+        let dur = track_info.map(|(_, d)| d).unwrap_or(0.0);
+        // Hot cues win over a bank
+        let bank = if hot_cues { None } else { self.active_bank() };
+        let layout  = self.pad_layout.read().clone();
+        let editing = self.pad_edit_mode.load(Ordering::Relaxed);
+        let picking = self.audition_picks.read().is_some();
+        let profile = self.mapping.read().active().clone();
+        let learning = *self.key_learn_slot.read();
+        // Chop pads come from the focused track's file, bank pads from their own
+        let track_file = match focus {
+            WaveformFocus::DrumTrack(idx) if !hot_cues => self.drum_tracks.read().get(idx).and_then(|t| t.file_path.clone()),
+            _ => None,
+        };
+
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(t!("Sample Pads")).strong());
+                ui.separator();
+                for size in PadGridSize::ALL {
+                    if ui.selectable_label(layout.size == size, size.label()).clicked() && layout.size != size {
+                        self.pad_layout.write().resize(size);
+                        *self.status.write() = t!("Pad grid: {}", size.label());
+                    }
+                }
+                ui.separator();
+                if ui.selectable_label(hot_cues, t!("⏭ Hot cues"))
+                    .on_hover_text(t!("Pads jump the main playhead to their marker instead of playing the chop"))
+                    .clicked() {
+                    self.pad_hot_cues.store(!hot_cues, Ordering::Relaxed);
+                    self.audition_picks.write().take();
+                }
+                if !hot_cues { self.draw_bank_menu(ui, bank.as_ref().map(|b| b.name.as_str())); }
+                if !hot_cues && bank.is_none() { self.draw_audition_controls(ui, marks.len()); }
+                if let Some((uuid, _)) = track_info { self.draw_slice_menu(ui, uuid); }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.selectable_label(editing, t!("✏ Edit Layout"))
+                        .on_hover_text(t!("Drag pads onto other slots to match your controller; click one, then press a key to rebind it")).clicked() {
+                        self.pad_edit_mode.store(!editing, Ordering::Relaxed);
+                        *self.pad_drag_src.write() = None;
+                        *self.key_learn_slot.write() = None;
+                    }
+                    self.draw_mapping_menu(ui, &profile.name);
+                    if editing && ui.small_button(t!("Reset")).clicked() {
+                        *self.pad_layout.write() = PadLayout::new(layout.size);
+                    }
+                });
+            });
+            ui.add_space(4.0);
+            if marks.is_empty() && bank.is_none() && !editing {
+                let hint = if hot_cues { t!("No cues yet — press M while playing to drop markers") }
+                    else { t!("No pads yet — press M while previewing a track to create chop points") };
+                ui.label(egui::RichText::new(hint).italics().color(egui::Color32::GRAY));
+                return;
+            }
+
+            let cols = layout.size.cols();
+            let gap  = 6.0;
+            let pw   = ((ui.available_width() - (cols as f32 - 1.0) * gap) / cols as f32).max(30.0);
+            let ph   = if layout.size.rows() > 4 { 40.0 } else { 52.0 };
+            let pointer   = ui.input(|i| i.pointer.interact_pos());
+            let released  = ui.input(|i| i.pointer.any_released());
+            let drag_src  = *self.pad_drag_src.read();
+            let mut drop_on: Option<usize> = None;
+            let mut ghost: Option<(usize, egui::Pos2)> = None;
+
+            for row in 0..layout.size.rows() {
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = gap;
+                    for col in 0..cols {
+                        let slot = row * cols + col;
+                        let Some(pad) = layout.pad_at(slot) else { continue; };
+                        let sense = if editing { egui::Sense::click_and_drag() } else { egui::Sense::click() };
+                        let (rect, resp) = ui.allocate_exact_size(egui::vec2(pw, ph), sense);
+                        let mark = marks.get(pad);
+                        let file = bank.as_ref().and_then(|b| b.pad_label(pad));
+                        let filled = if bank.is_some() { file.is_some() } else { mark.is_some() };
+                        let is_src = editing && drag_src == Some(slot);
+
+                        let base = if filled { pad_color(pad) } else { egui::Color32::from_rgb(35, 35, 40) };
+                        let col_fill = if is_src { pad_color_dim(pad) }
+                            else if resp.hovered() { base.gamma_multiply(1.25) } else { base };
+                        ui.painter().rect_filled(rect, 4.0, col_fill);
+                        let drop_target = editing && drag_src.is_some() && !is_src
+                            && pointer.map(|p| rect.contains(p)).unwrap_or(false);
+                        let highlight = drop_target || live_cue == Some(pad) || (editing && learning == Some(slot));
+                        ui.painter().rect_stroke(rect, 4.0, egui::Stroke::new(
+                            if highlight { 2.5 } else { 1.0 },
+                            if highlight { egui::Color32::WHITE } else { egui::Color32::from_gray(55) },
+                        ));
+                        draw_focus_ring(ui, rect, &resp);
+                        let key = if editing && learning == Some(slot) { Some("…") } else { profile.key_label(slot) };
+                        if let Some(k) = key {
+                            ui.painter().text(rect.min + egui::vec2(5.0, 3.0), egui::Align2::LEFT_TOP, k,
+                                egui::FontId::proportional(10.0), egui::Color32::from_gray(140));
+                        }
+                        ui.painter().text(rect.center() - egui::vec2(0.0, 7.0), egui::Align2::CENTER_CENTER,
+                            format!("{}", pad + 1), egui::FontId::proportional(18.0), egui::Color32::WHITE);
+                        if let Some(f) = &file {
+                            let shown: String = if f.chars().count() > 14 { format!("{}…", f.chars().take(13).collect::<String>()) } else { f.clone() };
+                            ui.painter().text(rect.center() + egui::vec2(0.0, 10.0), egui::Align2::CENTER_CENTER,
+                                shown, egui::FontId::proportional(10.0), egui::Color32::from_gray(200));
+                        } else if let (Some(m), None) = (mark, &bank) {
+                            ui.painter().text(rect.center() + egui::vec2(0.0, 10.0), egui::Align2::CENTER_CENTER,
+                                format!("{:.2}s", m.position * dur), egui::FontId::proportional(10.0), egui::Color32::from_gray(200));
+                        }
+                        resp.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, match (&file, mark, key) {
+                            (Some(f), _, Some(k))    => t!("Pad {}, key {}, {}", pad + 1, k, f),
+                            (Some(f), _, None)       => t!("Pad {}, {}", pad + 1, f),
+                            (None, _, _) if bank.is_some() => t!("Pad {}, empty", pad + 1),
+                            (None, Some(m), Some(k)) => t!("Pad {}, key {}, chop at {:.2} seconds", pad + 1, k, m.position * dur),
+                            (None, Some(m), None)    => t!("Pad {}, chop at {:.2} seconds", pad + 1, m.position * dur),
+                            (None, None, _)          => t!("Pad {}, empty", pad + 1),
+                        }));
+                        let source = match &bank {
+                            Some(b) => b.files.get(pad).cloned(),
+                            None    => mark.and(track_file.clone()),
+                        };
+                        let resp = match source.and_then(|f| self.sample_notes(&f).summary()) {
+                            Some(s) => resp.on_hover_text(s),
+                            None    => resp,
+                        };
+
+                        if editing {
+                            if resp.drag_started() { *self.pad_drag_src.write() = Some(slot); }
+                            if is_src { if let Some(p) = pointer { ghost = Some((pad, p)); } }
+                            if released && drag_src.is_some() && pointer.map(|p| rect.contains(p)).unwrap_or(false) {
+                                drop_on = Some(slot);
+                            }
+                            if resp.clicked() && slot < KEY_SLOTS {
+                                *self.key_learn_slot.write() = if learning == Some(slot) { None } else { Some(slot) };
+                            }
+                        } else if resp.clicked() {
+                            if picking { self.pick_audition_pad(pad); } else { self.trigger_pad(pad); }
+                        } else if resp.secondary_clicked() {
+                            self.toggle_midi_learn(LearnTarget::Pad(pad));
+                        }
+                    }
+                });
+            }
+
+            if let Some((pad, pos)) = ghost {
+                let r = egui::Rect::from_center_size(pos, egui::vec2(pw * 0.8, ph * 0.8));
+                let painter = ui.ctx().layer_painter(egui::LayerId::new(egui::Order::Tooltip, egui::Id::new("pad_ghost")));
+                painter.rect_filled(r, 4.0, pad_color(pad).gamma_multiply(0.8));
+                painter.text(r.center(), egui::Align2::CENTER_CENTER, format!("{}", pad + 1),
+                    egui::FontId::proportional(18.0), egui::Color32::WHITE);
+            }
+            if released {
+                if let (Some(src), Some(dst)) = (drag_src, drop_on) {
+                    self.pad_layout.write().swap(src, dst);
+                }
+                *self.pad_drag_src.write() = None;
+            }
+        });
+    }
+
+    /// What the pads play: the focused track's chops or one of the imported banks.
+    fn draw_bank_menu(&mut self, ui: &mut egui::Ui, bank: Option<&str>) {
+        ui.menu_button(format!("🗃 {}", bank.unwrap_or(t!("Chops"))), |ui| {
+            let active = *self.active_pad_bank.read();
+            if ui.radio(active.is_none(), t!("Chops of the focused track")).clicked() {
+                *self.active_pad_bank.write() = None;
+                ui.close_menu();
+            }
+            let banks: Vec<(String, usize)> = self.pad_banks.read().iter().map(|b| (b.name.clone(), b.files.len())).collect();
+            for (i, (name, n)) in banks.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui.radio(active == Some(i), t!("{} ({} pads)", name, n)).clicked() {
+                        *self.active_pad_bank.write() = Some(i);
+                        ui.close_menu();
+                    }
+                    if ui.small_button("✕").on_hover_text(t!("Remove this bank")).clicked() {
+                        self.remove_pad_bank(i);
+                        ui.close_menu();
+                    }
+                });
+            }
+            ui.separator();
+            if ui.button(t!("📁 Import folder as pad bank…"))
+                .on_hover_text(t!("Each audio file in the folder becomes a pad, in name order"))
+                .clicked() {
+                ui.close_menu();
+                self.import_folder_as_pad_bank();
+            }
+        });
+    }
+
+    /// "Slice into N": evenly spaced markers across the whole sample or one of its regions.
+    fn draw_slice_menu(&mut self, ui: &mut egui::Ui, uuid: uuid::Uuid) {
+        ui.menu_button(t!("▥ Slice"), |ui| {
+            let mut n = self.slice_count.load(Ordering::Relaxed);
+            ui.horizontal(|ui| {
+                ui.label(t!("Slice into"));
+                ui.add(egui::DragValue::new(&mut n).clamp_range(2..=64));
+            });
+            self.slice_count.store(n, Ordering::Relaxed);
+            ui.separator();
+            let mut ranges = vec![(None, t!("Whole sample").to_string())];
+            ranges.extend(self.samples_manager.get_regions_for_sample(&uuid).into_iter().map(|r| (Some(r.id), r.name)));
+            egui::Grid::new("slice_ranges").num_columns(3).spacing([8.0, 4.0]).show(ui, |ui| {
+                for (region, name) in ranges {
+                    ui.label(name);
+                    if ui.small_button(t!("Replace")).on_hover_text(t!("Clear the markers in this range first")).clicked() {
+                        self.slice_evenly(uuid, n, region, true);
+                        ui.close_menu();
+                    }
+                    if ui.small_button(t!("Append")).on_hover_text(t!("Keep the markers already there")).clicked() {
+                        self.slice_evenly(uuid, n, region, false);
+                        ui.close_menu();
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    /// A/B chop audition: pick two pads, then they alternate at the sequencer tempo.
+    fn draw_audition_controls(&mut self, ui: &mut egui::Ui, n_chops: usize) {
+        let running = self.chop_audition.read().as_ref().map(|au| (au.a, au.b, au.beats, au.hearing_b));
+        if let Some((a, b, beats, hearing_b)) = running {
+            let on  = egui::Color32::from_rgb(240, 200, 80);
+            let off = egui::Color32::from_gray(150);
+            ui.label(egui::RichText::new(t!("A: {}", a + 1)).strong().color(if hearing_b { off } else { on }));
+            ui.label(egui::RichText::new(t!("B: {}", b + 1)).strong().color(if hearing_b { on } else { off }));
+            let mut beats = beats;
+            egui::ComboBox::from_id_source("audition_beats")
+                .selected_text(t!("{} beat", beats))
+                .width(70.0)
+                .show_ui(ui, |ui| {
+                    for v in [0.5, 1.0, 2.0, 4.0] { ui.selectable_value(&mut beats, v, t!("{} beat", v)); }
+                });
+            if let Some(au) = self.chop_audition.write().as_mut() { au.beats = beats; }
+            if ui.small_button(t!("⇄ Swap into track")).on_hover_text(t!("Give B the pattern A had (Enter)")).clicked() {
+                self.swap_audition_into_track();
+            }
+            if ui.small_button("■").on_hover_text(t!("Stop audition (Esc)")).clicked() { self.stop_chop_audition(); }
+            return;
+        }
+        let picks = self.audition_picks.read().clone();
+        let label = match picks.as_deref() {
+            Some([a]) => t!("A/B: pick B (A = {})", a + 1),
+            Some(_)   => t!("A/B: pick A").to_string(),
+            None      => t!("A/B").to_string(),
+        };
+        if ui.add_enabled(n_chops >= 2, egui::SelectableLabel::new(picks.is_some(), label))
+            .on_hover_text(t!("Compare two chops back to back at the sequencer tempo"))
+            .clicked() {
+            let mut p = self.audition_picks.write();
+            *p = if p.is_some() { None } else { Some(Vec::new()) };
+        }
+    }
+
+    fn pick_audition_pad(&self, pad: usize) {
+        let picked = {
+            let mut guard = self.audition_picks.write();
+            let Some(picks) = guard.as_mut() else { return; };
+            if !picks.contains(&pad) { picks.push(pad); }
+            if picks.len() < 2 { return; }
+            let pair = (picks[0], picks[1]);
+            *guard = None;
+            pair
+        };
+        if let WaveformFocus::DrumTrack(idx) = *self.waveform_focus.read() {
+            self.start_chop_audition(idx, picked.0, picked.1);
+        }
+    }
+
+    /// Computer-keyboard pad triggers (1234 / QWER / ASDF / ZXCV → first 16 slots), crossfader keys and the fill key.
+    pub fn handle_pad_keys(&self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() { return; }
+        if self.pad_edit_mode.load(Ordering::Relaxed) {
+            self.learn_pad_key(ctx);
+            return;
+        }
+        let layout  = self.pad_layout.read().clone();
+        let profile = self.mapping.read().active().clone();
+        for slot in 0..KEY_SLOTS {
+            let Some(key) = profile.key(slot) else { continue; };
+            if ctx.input(|i| i.key_pressed(key) && i.modifiers.is_none()) {
+                if let Some(pad) = layout.pad_at(slot) { self.trigger_pad(pad); }
+            }
+        }
+        // Crossfader: [ toward chops, ] toward drums, \ back to the middle
+        let x = self.crossfader.load(Ordering::Relaxed);
+        let moved = ctx.input(|i| {
+            if !i.modifiers.is_none() { None }
+            else if i.key_pressed(egui::Key::OpenBracket)  { Some((x - 0.1).max(0.0)) }
+            else if i.key_pressed(egui::Key::CloseBracket) { Some((x + 0.1).min(1.0)) }
+            else if i.key_pressed(egui::Key::Backslash)    { Some(0.5) }
+            else { None }
+        });
+        if let Some(x) = moved { self.crossfader.store(x, Ordering::Relaxed); }
+        if ctx.input(|i| i.key_pressed(egui::Key::G) && i.modifiers.is_none()) { self.trigger_fill(); }
+    }
+
+    /// Layout edit mode: the next key pressed goes to the slot waiting for one (Esc clears it).
+    fn learn_pad_key(&self, ctx: &egui::Context) {
+        let Some(slot) = *self.key_learn_slot.read() else { return; };
+        let pressed = ctx.input(|i| i.events.iter().find_map(|e| match e {
+            egui::Event::Key { key, pressed: true, repeat: false, modifiers, .. } if modifiers.is_none() => Some(*key),
+            _ => None,
+        }));
+        let Some(key) = pressed else { return; };
+        *self.key_learn_slot.write() = None;
+        let mut mapping = self.mapping.write();
+        let profile = mapping.active_mut();
+        if key == egui::Key::Escape {
+            if let Some(k) = profile.keys.get_mut(slot) { *k = None; }
+            *self.status.write() = t!("Slot {} has no key in {}", slot + 1, profile.name);
+        } else {
+            profile.set_key(slot, key);
+            *self.status.write() = t!("✓ {} plays slot {} in {}", key.symbol_or_name(), slot + 1, profile.name);
+        }
+    }
+
+    /// Profile picker: switch, copy, rename or delete the project's keyboard/MIDI maps.
+    fn draw_mapping_menu(&self, ui: &mut egui::Ui, active_name: &str) {
+        ui.menu_button(format!("⌨ {}", active_name), |ui| {
+            let mut mapping = self.mapping.write();
+            let mut pick = None;
+            for (i, p) in mapping.profiles.iter().enumerate() {
+                if ui.selectable_label(i == mapping.active, &p.name).clicked() { pick = Some(i); }
+            }
+            if let Some(i) = pick {
+                mapping.active = i;
+                *self.status.write() = t!("Mapping: {}", mapping.profiles[i].name);
+                ui.close_menu();
+            }
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label(t!("Name"));
+                ui.text_edit_singleline(&mut mapping.active_mut().name);
+            });
+            ui.horizontal(|ui| {
+                if ui.button(t!("⎘ Duplicate")).on_hover_text(t!("Copy this profile to change it without losing the original")).clicked() {
+                    mapping.duplicate_active();
+                }
+                if ui.add_enabled(mapping.profiles.len() > 1, egui::Button::new(t!("🗑 Delete"))).clicked() {
+                    mapping.remove_active();
+                }
+            });
+            ui.label(egui::RichText::new(t!("Keys: ✏ Edit Layout, click a pad, press a key (Esc unbinds)"))
+                .small().color(egui::Color32::GRAY));
+        }).response.on_hover_text(t!("Keyboard/MIDI mapping profile, saved with the project"));
+    }
+}
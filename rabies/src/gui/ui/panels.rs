@@ -1,10 +1,29 @@
 // src/gui/ui/panels.rs
 use eframe::egui;
 use std::sync::atomic::Ordering;
-use crate::gui::{AppState, WaveformFocus, NUM_STEPS};
+use crate::gui::{lane_len, lane_steps, set_step, step_on, AppState, ChopLayout, Quantize, StepResolution, WaveformFocus};
 use super::widgets::*;
 use crate::adsr::ADSREnvelope;
-use crate::recording::RecordState;
+use crate::recording::{RecordState, LoopTarget, CAPTURE_LENGTHS};
+use crate::samples::MarkerTimeUnit;
+use crate::macros::{MacroDest, MacroMapping};
+use crate::backend::BackendKind;
+use crate::grid_export::GridFormat;
+use crate::looper::STUTTER_LENGTHS;
+use crate::render::BOUNCE_BARS;
+use crate::share::{ShareFormat, SHARE_DIR};
+use crate::variation::ChopAlternate;
+use crate::metronome::{ClickSound, Meter};
+use crate::grid_controller::{GridKind, GridMode, Lane};
+use crate::midi_learn::LearnTarget;
+use crate::category::{DrumCategory, RowColors};
+use crate::step_pitch;
+use crate::step_nudge;
+use crate::row_timing;
+use crate::humanize;
+use crate::live_record::RecordTiming;
+use crate::bus::AppCommand;
+use super::notes_window::sample_notes_editor;
 
 impl AppState {
     pub fn seq_header_ui(&mut self, ui: &mut egui::Ui) {
@@ -14,9 +33,9 @@ impl AppState {
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 let pl_open = self.playlist_view_open.load(std::sync::atomic::Ordering::Relaxed);
                 let (pl_lbl, pl_col) = if pl_open {
-                    ("🎛 Playlist ▲", egui::Color32::from_rgb(237, 164, 80))
+                    (t!("🎛 Playlist ▲"), egui::Color32::from_rgb(237, 164, 80))
                 } else {
-                    ("🎛 Playlist ▼", egui::Color32::from_gray(130))
+                    (t!("🎛 Playlist ▼"), egui::Color32::from_gray(130))
                 };
                 if ui.add(egui::Button::new(egui::RichText::new(pl_lbl).size(20.0).color(pl_col))
                     .fill(if pl_open {
@@ -24,15 +43,15 @@ impl AppState {
                     } else {
                         egui::Color32::TRANSPARENT
                     })
-                ).on_hover_text("Toggle FL Playlist – drag blocks to freely arrange patterns").clicked() {
+                ).on_hover_text(t!("Toggle FL Playlist – drag blocks to freely arrange patterns")).clicked() {
                     self.playlist_view_open.store(!pl_open, std::sync::atomic::Ordering::Relaxed);
                 }
 
                 let open      = self.song_editor_open.load(std::sync::atomic::Ordering::Relaxed);
                 let (lbl, col) = if open {
-                    ("📋 Song ▲", egui::Color32::from_rgb(100, 149, 237))
+                    (t!("📋 Song ▲"), egui::Color32::from_rgb(100, 149, 237))
                 } else {
-                    ("📋 Song ▼", egui::Color32::from_gray(130))
+                    (t!("📋 Song ▼"), egui::Color32::from_gray(130))
                 };
                 if ui.add(egui::Button::new(egui::RichText::new(lbl).size(20.0).color(col))
                     .fill(if open {
@@ -40,7 +59,7 @@ impl AppState {
                     } else {
                         egui::Color32::TRANSPARENT
                     })
-                ).on_hover_text("Toggle Song Editor – arrange patterns on a timeline").clicked() {
+                ).on_hover_text(t!("Toggle Song Editor – arrange patterns on a timeline")).clicked() {
                     self.song_editor_open.store(!open, std::sync::atomic::Ordering::Relaxed);
                 }
             });
@@ -49,63 +68,516 @@ impl AppState {
         ui.add(egui::Separator::default().horizontal().spacing(3.0));
 
         ui.horizontal(|ui| {
-            ui.label(egui::RichText::new("STEP SEQUENCER").size(20.0).strong().color(egui::Color32::from_gray(100)));
+            ui.label(egui::RichText::new(t!("STEP SEQUENCER")).size(20.0).strong().color(egui::Color32::from_gray(100)));
             ui.separator();
 
-            let mut bpm = self.seq_bpm.load(std::sync::atomic::Ordering::Relaxed);
-            ui.label(egui::RichText::new("BPM").size(20.0).color(egui::Color32::from_gray(120)));
-            if ui.add(egui::DragValue::new(&mut bpm).speed(0.5).clamp_range(40.0..=300.0).fixed_decimals(0)).changed() {
-                self.seq_bpm.store(bpm, std::sync::atomic::Ordering::Relaxed);
+            let mut bpm = self.events.bpm();
+            ui.label(egui::RichText::new(t!("BPM")).size(20.0).color(egui::Color32::from_gray(120)));
+            let bpm_resp = ui.add(egui::DragValue::new(&mut bpm).speed(0.5).clamp_range(40.0..=300.0).fixed_decimals(0))
+                .on_hover_text(t!("Right-click to MIDI learn"));
+            if bpm_resp.changed() {
+                self.send(AppCommand::SetBpm { bpm });
             }
+            if bpm_resp.secondary_clicked() { self.toggle_midi_learn(LearnTarget::Bpm); }
+            if ui.small_button(t!("Tap")).on_hover_text(t!("Tap a few times in time to set the BPM")).clicked() { self.tap_tempo(); }
+            let peers = self.link_peers();
+            let link_label = match peers {
+                Some(n) => t!("🔗 Link ({})", n),
+                None    => t!("🔗 Link").to_string(),
+            };
+            if ui.selectable_label(peers.is_some(), link_label)
+                .on_hover_text(t!("Ableton Link: share tempo and bar phase with apps on the network"))
+                .clicked() {
+                self.set_link_enabled(peers.is_none());
+            }
+            ui.separator();
+
+            ui.label(egui::RichText::new(t!("Quantize")).size(20.0).color(egui::Color32::from_gray(120)));
+            let mut q = *self.quantize.read();
+            egui::ComboBox::from_id_source("seq_quantize")
+                .selected_text(egui::RichText::new(q.label()).size(20.0))
+                .show_ui(ui, |ui| {
+                    for opt in Quantize::ALL {
+                        ui.selectable_value(&mut q, opt, opt.label());
+                    }
+                })
+                .response
+                .on_hover_text(t!("Grid for live marks, recording start and pattern launch"));
+            if q != *self.quantize.read() {
+                *self.quantize.write() = q;
+                *self.status.write() = t!("Quantize: {}", q.label());
+            }
+            ui.separator();
+
+            ui.label(egui::RichText::new(t!("Grid")).size(20.0).color(egui::Color32::from_gray(120)));
+            let mut res = *self.seq_resolution.read();
+            egui::ComboBox::from_id_source("seq_resolution")
+                .selected_text(egui::RichText::new(res.label()).size(20.0))
+                .show_ui(ui, |ui| {
+                    for opt in StepResolution::ALL {
+                        ui.selectable_value(&mut res, opt, opt.label());
+                    }
+                })
+                .response
+                .on_hover_text(t!("Steps per bar, straight or triplet (T) — existing steps are moved to the new grid"));
+            self.set_step_resolution(res);
+
+            let mut len = self.pattern_len();
+            egui::ComboBox::from_id_source("pattern_len")
+                .selected_text(egui::RichText::new(t!("{} steps", len)).size(20.0))
+                .show_ui(ui, |ui| {
+                    for opt in res.pattern_lengths() {
+                        ui.selectable_value(&mut len, opt, t!("{} steps", opt));
+                    }
+                })
+                .response
+                .on_hover_text(t!("Pattern length — hits past a shorter end are dropped"));
+            self.set_pattern_len(len);
             ui.separator();
 
-            let playing = self.seq_playing.load(std::sync::atomic::Ordering::Relaxed);
-            let (lbl, col) = if playing {
-                ("⏹ Stop", egui::Color32::from_rgb(220, 80, 60))
+            let playing = self.events.playing();
+            let (lbl, col) = if playing || self.seq_counting_in.load(Ordering::Relaxed) {
+                (t!("⏹ Stop"), egui::Color32::from_rgb(220, 80, 60))
             } else {
-                ("▶ Play", egui::Color32::from_rgb(60, 200, 100))
+                (t!("▶ Play"), egui::Color32::from_rgb(60, 200, 100))
             };
             if ui.add(egui::Button::new(egui::RichText::new(lbl).color(col).size(20.0))).clicked() {
-                if playing { self.stop_sequencer(); } else { self.start_sequencer(); }
+                self.send(AppCommand::TogglePlay);
+            }
+            self.draw_transport_clock(ui);
+            let recording = self.seq_record.load(Ordering::Relaxed);
+            let rec_col = if recording { egui::Color32::from_rgb(230, 60, 60) } else { egui::Color32::from_gray(140) };
+            if ui.add(egui::Button::new(egui::RichText::new(t!("⏺ Rec")).size(20.0).color(rec_col))
+                .fill(if recording { egui::Color32::from_rgba_unmultiplied(200, 40, 40, 40) } else { egui::Color32::TRANSPARENT }))
+                .on_hover_text(t!("Record pad and drum-key hits into the pattern while it plays"))
+                .clicked() {
+                self.send(AppCommand::SetRecord { on: !recording });
+            }
+            let mut timing = *self.record_timing.read();
+            egui::ComboBox::from_id_source("seq_record_timing")
+                .selected_text(timing.label())
+                .show_ui(ui, |ui| {
+                    for opt in RecordTiming::ALL {
+                        ui.selectable_value(&mut timing, opt, opt.label());
+                    }
+                })
+                .response
+                .on_hover_text(t!("Recorded hits snap to the nearest step, or keep their timing as a nudge off the step"));
+            *self.record_timing.write() = timing;
+            let armed = self.seq_start_at.read().is_some() && !self.seq_counting_in.load(Ordering::Relaxed);
+            let sync_col = if armed { egui::Color32::from_rgb(240, 190, 70) } else { egui::Color32::from_gray(140) };
+            if ui.add(egui::Button::new(egui::RichText::new(t!("⏱ On bar")).size(20.0).color(sync_col)))
+                .on_hover_text(t!("Start the pattern on the next bar line of the sample playing, in phase with it (click again to cancel)"))
+                .clicked() {
+                self.start_pattern_at_playhead();
             }
 
+            let has_fill = self.song_editor.get_pattern_by_idx(self.song_editor.active_edit_idx())
+                .is_some_and(|p| p.fill.is_some());
+            let fill_col = if self.fill.read().is_some() { egui::Color32::from_rgb(240, 190, 70) } else { egui::Color32::from_gray(140) };
+            if ui.add_enabled(has_fill && playing, egui::Button::new(egui::RichText::new(t!("🥁 Fill")).size(20.0).color(fill_col)))
+                .on_hover_text(t!("Play this pattern's fill for one bar from the next bar line (G)"))
+                .on_disabled_hover_text(t!("Pick a fill for this pattern in the song editor (right-click its name), then play"))
+                .clicked()
+            {
+                self.trigger_fill();
+            }
+
+            let click_on = self.metronome.lock().settings.enabled;
+            let click_col = if click_on { egui::Color32::from_rgb(120, 200, 240) } else { egui::Color32::from_gray(120) };
+            ui.menu_button(egui::RichText::new(t!("🕭 Click")).size(20.0).color(click_col), |ui| {
+                let mut pick_sample = false;
+                {
+                    let mut m = self.metronome.lock();
+                    let has_sample = m.has_sample();
+                    let s = &mut m.settings;
+                    ui.checkbox(&mut s.enabled, t!("Metronome"));
+                    ui.checkbox(&mut s.count_in, t!("Count-in"))
+                        .on_hover_text(t!("One bar of clicks before Play, or a recording, starts the pattern"));
+                    ui.add(egui::Slider::new(&mut s.level, 0.0..=1.0).text("level").show_value(false));
+                    ui.separator();
+                    ui.label(egui::RichText::new(t!("Sound")).small().color(egui::Color32::from_gray(140)));
+                    ui.horizontal(|ui| {
+                        for sound in ClickSound::ALL {
+                            let resp = ui.add_enabled(sound != ClickSound::Sample || has_sample,
+                                egui::SelectableLabel::new(s.sound == sound, sound.label()));
+                            if resp.clicked() { s.sound = sound; }
+                        }
+                        let file = s.sample_path.as_deref()
+                            .and_then(|p| std::path::Path::new(p).file_name())
+                            .map(|f| f.to_string_lossy().to_string());
+                        if ui.small_button("📂").on_hover_text(file.unwrap_or_else(|| t!("Load a click sample").into())).clicked() {
+                            pick_sample = true;
+                        }
+                    });
+                    ui.label(egui::RichText::new(t!("Time signature")).small().color(egui::Color32::from_gray(140)));
+                    ui.horizontal_wrapped(|ui| {
+                        for meter in Meter::ALL {
+                            ui.selectable_value(&mut s.meter, meter, meter.label())
+                                .on_hover_text(t!("Accents the downbeat (and every third eighth in 6/8, 9/8, 12/8)"));
+                        }
+                    });
+                }
+                if pick_sample {
+                    ui.close_menu();
+                    self.pick_metronome_sample();
+                }
+            }).response.on_hover_text(t!("Metronome: sound, level and time signature (never in bounces)"));
+
+            let mut human = self.humanize.load(Ordering::Relaxed);
+            let human_col = if human > 0.0 { egui::Color32::from_rgb(240, 170, 110) } else { egui::Color32::from_gray(120) };
+            ui.menu_button(egui::RichText::new(t!("🎲 Humanize")).size(20.0).color(human_col), |ui| {
+                ui.add(egui::Slider::new(&mut human, 0.0..=1.0).text(t!("every row"))
+                    .custom_formatter(|v, _| format!("{:.0}%", v * 100.0)))
+                    .on_hover_text(t!("Each hit lands a little off the grid and a little louder or softer, rolled as it plays; the steps stay where they are"));
+                ui.label(egui::RichText::new(t!("Rows can add their own from their right-click menu"))
+                    .small().color(egui::Color32::from_gray(140)));
+            }).response.on_hover_text(t!("Humanize timing and velocity as the pattern plays"));
+            self.humanize.store(human, Ordering::Relaxed);
+
+            let solo = *self.chop_solo.read();
+            if let Some((t, c)) = solo {
+                if ui.add(egui::Button::new(egui::RichText::new(t!("🎧 Track {} chop {} ✕", t + 1, c + 1)).size(20.0)
+                    .color(egui::Color32::from_rgb(240, 200, 60))))
+                    .on_hover_text(t!("Only this chop is heard; click to hear everything again")).clicked() {
+                    *self.chop_solo.write() = None;
+                }
+            }
+
+            let grid_col = if self.grid.lock().is_some() { egui::Color32::from_rgb(180, 140, 240) } else { egui::Color32::from_gray(120) };
+            ui.menu_button(egui::RichText::new(t!("▦ Grid")).size(20.0).color(grid_col), |ui| {
+                let mut connect    = None;
+                let mut disconnect = false;
+                let mut scan       = false;
+                {
+                    let mut guard = self.grid.lock();
+                    if let Some(g) = guard.as_mut() {
+                        ui.label(egui::RichText::new(&g.port.name).small().color(egui::Color32::from_gray(140)));
+                        ui.horizontal(|ui| {
+                            for kind in GridKind::ALL {
+                                if ui.selectable_label(g.kind == kind, kind.label()).clicked() && g.kind != kind {
+                                    g.clear();
+                                    g.kind = kind;
+                                }
+                            }
+                        });
+                        ui.horizontal(|ui| {
+                            ui.selectable_value(&mut g.mode, GridMode::Steps, t!("Steps"))
+                                .on_hover_text(t!("Sequencer rows, each wrapped over as many grid rows as it needs"));
+                            ui.selectable_value(&mut g.mode, GridMode::Pads, t!("Pads"))
+                                .on_hover_text(t!("Play the pad layout"));
+                        });
+                        if g.mode == GridMode::Steps {
+                            ui.horizontal(|ui| {
+                                if ui.small_button("▲").clicked() { g.first_lane = g.first_lane.saturating_sub(1); }
+                                if ui.small_button("▼").clicked() { g.first_lane += 1; }
+                                ui.label(t!("From row {}", g.first_lane + 1));
+                            });
+                        }
+                        if ui.button(t!("Disconnect")).clicked() { disconnect = true; }
+                    } else {
+                        let ports: Vec<_> = self.midi_ports.read().iter().filter(|p| p.input && p.output).cloned().collect();
+                        if ports.is_empty() {
+                            ui.label(egui::RichText::new(t!("No controllers — plug one in and scan")).small().color(egui::Color32::from_gray(140)));
+                        }
+                        for p in ports {
+                            if ui.button(&p.name).on_hover_text(t!("Connect as {}", GridKind::detect(&p.name).label())).clicked() {
+                                connect = Some(p);
+                            }
+                        }
+                        if ui.button(t!("↻ Scan")).clicked() { scan = true; }
+                    }
+                }
+                if scan { self.refresh_midi_ports(); }
+                if disconnect { self.disconnect_grid(); ui.close_menu(); }
+                if let Some(p) = connect { self.connect_grid(p); ui.close_menu(); }
+            }).response.on_hover_text(t!("Launchpad / Push: steps or pads on the grid, with LED feedback"));
+
             if ui.add(egui::Button::new(
-                egui::RichText::new("🗑 Clear").size(20.0).color(egui::Color32::from_gray(120))
+                egui::RichText::new(t!("🗑 Clear")).size(20.0).color(egui::Color32::from_gray(120))
             )).clicked() {
                 let mut g = self.seq_grid.write();
                 for s in g.iter_mut() { s.clear(); }
                 let mut tracks = self.drum_tracks.write();
                 for t in tracks.iter_mut() {
-                    t.steps = [false; crate::gui::NUM_STEPS];
-                    for row in t.chop_steps.iter_mut() { *row = [false; crate::gui::NUM_STEPS]; }
+                    t.steps.fill(false);
+                    for row in t.chop_steps.iter_mut() { row.fill(false); }
+                    t.chop_alternates.clear();
+                    t.step_pitches.clear();
+                    t.step_nudges.clear();
+                }
+                self.samples_manager.for_each_region_lane(|row| row.fill(false));
+            }
+
+            let focused = match *self.waveform_focus.read() { WaveformFocus::DrumTrack(i) => Some(i), _ => None };
+            ui.add_enabled_ui(focused.is_some(), |ui| {
+                ui.menu_button(egui::RichText::new(t!("⇶ Lay chops")).size(20.0).color(egui::Color32::from_rgb(140, 200, 240)), |ui| {
+                    let Some(idx) = focused else { return; };
+                    if ui.button(t!("One per step")).on_hover_text(t!("Chop 1 on step 1, chop 2 on step 2, …")).clicked() {
+                        self.lay_chops_across_bar(idx, ChopLayout::Sequential);
+                        ui.close_menu();
+                    }
+                    if ui.button(t!("Spread over the bar")).on_hover_text(t!("Space the chops evenly, e.g. four chops on the four beats")).clicked() {
+                        self.lay_chops_across_bar(idx, ChopLayout::Spread);
+                        ui.close_menu();
+                    }
+                    ui.label(egui::RichText::new(t!("Only chops with no steps yet, onto empty steps"))
+                        .small().color(egui::Color32::GRAY));
+                    ui.separator();
+                    if ui.button(t!("As in the original loop"))
+                        .on_hover_text(t!("Replace this track's chop steps with each chop at its spot in the loop (BPM tag if present); longer loops continue in new patterns"))
+                        .clicked() {
+                        self.pattern_from_loop(idx);
+                        ui.close_menu();
+                    }
+                });
+            }).response.on_hover_text(t!("Place the focused track's chops on the grid to rebuild the loop"));
+
+            ui.menu_button(egui::RichText::new(t!("⎙ Export grid")).size(20.0).color(egui::Color32::from_gray(160)), |ui| {
+                for format in [GridFormat::Text, GridFormat::Svg] {
+                    if ui.button(format.label()).clicked() {
+                        self.export_pattern_grid(format);
+                        ui.close_menu();
+                    }
+                }
+                ui.label(egui::RichText::new(t!("Rows with hits × steps, with velocities"))
+                    .small().color(egui::Color32::GRAY));
+            }).response.on_hover_text(t!("Save this pattern as a printable grid"));
+
+            if ui.add(egui::Button::new(egui::RichText::new(t!("🎼 Import MIDI")).size(20.0).color(egui::Color32::from_gray(160))))
+                .on_hover_text(t!("Fill rows of this pattern from the notes of a .mid clip")).clicked() {
+                self.open_midi_import();
+            }
+
+            ui.menu_button(egui::RichText::new(t!("⇆ Steps")).size(20.0).color(egui::Color32::from_gray(160)), |ui| {
+                let len = self.pattern_len();
+                if ui.button(t!("×2  Double")).on_hover_text(t!("Play every pattern twice, in patterns twice as long")).clicked() {
+                    self.double_pattern();
+                    ui.close_menu();
+                }
+                if ui.button(t!("½  Halve")).on_hover_text(t!("Keep the first half of every pattern")).clicked() {
+                    self.halve_pattern();
+                    ui.close_menu();
+                }
+                ui.separator();
+                let mut by = self.pattern_shift.load(Ordering::Relaxed);
+                ui.horizontal(|ui| {
+                    if ui.button("◀").on_hover_text(t!("Shift this pattern earlier, wrapping round")).clicked() { self.shift_pattern(-(by as isize)); }
+                    if ui.add(egui::DragValue::new(&mut by).clamp_range(1..=len.saturating_sub(1).max(1)).suffix(t!(" step(s)"))).changed() {
+                        self.pattern_shift.store(by, Ordering::Relaxed);
+                    }
+                    if ui.button("▶").on_hover_text(t!("Shift this pattern later, wrapping round")).clicked() { self.shift_pattern(by as isize); }
+                });
+                ui.label(egui::RichText::new(t!("Right-click a row to shift just that row"))
+                    .small().color(egui::Color32::GRAY));
+                ui.separator();
+                let undo = self.undo_label();
+                if ui.add_enabled(undo.is_some(), egui::Button::new(t!("↶ Undo {}", undo.as_deref().unwrap_or(""))))
+                    .on_hover_text(t!("Ctrl+Z")).clicked() {
+                    self.send(AppCommand::Undo);
+                    ui.close_menu();
+                }
+                let redo = self.redo_label();
+                if ui.add_enabled(redo.is_some(), egui::Button::new(t!("↷ Redo {}", redo.as_deref().unwrap_or(""))))
+                    .on_hover_text(t!("Ctrl+Shift+Z")).clicked() {
+                    self.send(AppCommand::Redo);
+                    ui.close_menu();
+                }
+            }).response.on_hover_text(t!("Double, halve or shift the pattern's steps"));
+
+            ui.menu_button(egui::RichText::new(t!("🎨 Rows")).size(20.0).color(egui::Color32::from_gray(160)), |ui| {
+                ui.label(egui::RichText::new(t!("Colour rows by")).small().color(egui::Color32::from_gray(140)));
+                let mut colors = *self.row_colors.read();
+                let before = colors;
+                ui.radio_value(&mut colors, RowColors::Track, t!("Track number"));
+                ui.radio_value(&mut colors, RowColors::Category, t!("Category"));
+                if colors != before { *self.row_colors.write() = colors; }
+                let mut group = self.group_rows.load(Ordering::Relaxed);
+                if ui.checkbox(&mut group, t!("Group by category")).changed() {
+                    self.group_rows.store(group, Ordering::Relaxed);
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    for c in DrumCategory::ALL {
+                        ui.label(egui::RichText::new(format!("■ {}", c.label())).color(category_color(c)));
+                    }
+                });
+                if ui.button(t!("↻ Guess categories again")).on_hover_text(t!("Forget categories set by hand")).clicked() {
+                    self.reguess_categories();
+                    ui.close_menu();
+                }
+                ui.label(egui::RichText::new(t!("Right-click a track to set its category"))
+                    .small().color(egui::Color32::GRAY));
+            }).response.on_hover_text(t!("Colour and group track rows by kick, snare, hat and percussion"));
+
+            ui.menu_button(egui::RichText::new(t!("⤓ Bounce")).size(20.0).color(egui::Color32::from_gray(160)), |ui| {
+                for bars in BOUNCE_BARS {
+                    if ui.button(t!("{} bar{}", bars, if bars == 1 { "" } else { "s" })).clicked() {
+                        ui.close_menu();
+                        self.bounce_pattern(bars);
+                    }
+                }
+                ui.label(egui::RichText::new(t!("Stereo WAV at the current BPM, tails included"))
+                    .small().color(egui::Color32::GRAY));
+                ui.separator();
+                ui.menu_button(t!("Stems…"), |ui| {
+                    for bars in BOUNCE_BARS {
+                        if ui.button(t!("{} bar{}", bars, if bars == 1 { "" } else { "s" })).clicked() {
+                            ui.close_menu();
+                            self.export_stems(bars);
+                        }
+                    }
+                }).response.on_hover_text(t!("One WAV per unmuted track, all sample-aligned and the same length"));
+            }).response.on_hover_text(t!("Render this pattern offline to a WAV file"));
+
+            ui.menu_button(egui::RichText::new(t!("📤 Share")).size(20.0).color(egui::Color32::from_gray(160)), |ui| {
+                if ui.button(t!("📤 Share preview")).on_hover_text(t!("Two loops of this pattern into the share folder")).clicked() {
+                    ui.close_menu();
+                    self.share_preview();
+                }
+                ui.separator();
+                let mut share = self.share.read().clone();
+                ui.horizontal(|ui| {
+                    for f in ShareFormat::ALL { ui.radio_value(&mut share.format, f, f.label()); }
+                });
+                let folder = self.share_dir().map_or(t!("asks the first time").to_string(), |d| d.display().to_string());
+                ui.label(egui::RichText::new(t!("Folder: {}", folder)).small().color(egui::Color32::from_gray(140)));
+                ui.horizontal(|ui| {
+                    if ui.button(t!("Choose…")).clicked() {
+                        if let Some(d) = rfd::FileDialog::new().set_title(t!("Folder for share previews")).pick_folder() {
+                            share.folder = Some(d);
+                        }
+                    }
+                    if ui.add_enabled(share.folder.is_some(), egui::Button::new(t!("Use project's {}/", SHARE_DIR))).clicked() {
+                        share.folder = None;
+                    }
+                });
+                ui.checkbox(&mut share.on_save, t!("Also write one on every save"));
+                ui.label(egui::RichText::new(t!("MP3 and OGG go through ffmpeg, lame or oggenc if installed"))
+                    .small().color(egui::Color32::GRAY));
+                *self.share.write() = share;
+            }).response.on_hover_text(t!("Bounce a quick MP3/OGG of this pattern to send to collaborators"));
+
+            // ── A/B compare ──
+            ui.separator();
+            let ab_state = self.ab_compare.read().as_ref().map(|ab| ab.showing_a);
+            match ab_state {
+                None => {
+                    if ui.add(egui::Button::new(egui::RichText::new(t!("A/B")).size(20.0).color(egui::Color32::from_gray(140))))
+                        .on_hover_text(t!("Snapshot this pattern, then flip between original and edits")).clicked() {
+                        self.ab_snapshot();
+                    }
+                }
+                Some(showing_a) => {
+                    let ab_col = egui::Color32::from_rgb(240, 200, 60);
+                    if ui.selectable_label(showing_a, egui::RichText::new(t!("A")).size(20.0).color(ab_col)).clicked() && !showing_a {
+                        self.ab_toggle();
+                    }
+                    if ui.selectable_label(!showing_a, egui::RichText::new(t!("B")).size(20.0).color(ab_col)).clicked() && showing_a {
+                        self.ab_toggle();
+                    }
+                    if ui.add(egui::Button::new(egui::RichText::new(t!("✓ Commit")).size(20.0).color(egui::Color32::from_rgb(80, 200, 130))))
+                        .on_hover_text(t!("Keep the edited version (B)")).clicked() {
+                        self.ab_commit();
+                    }
+                    if ui.add(egui::Button::new(egui::RichText::new(t!("↺ Revert")).size(20.0).color(egui::Color32::from_rgb(200, 80, 80))))
+                        .on_hover_text(t!("Go back to the snapshot (A)")).clicked() {
+                        self.ab_revert();
+                    }
                 }
             }
 
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 if ui.add(egui::Button::new(
-                    egui::RichText::new("＋ Add Track").size(20.0).color(egui::Color32::from_rgb(80,220,140))
+                    egui::RichText::new(t!("＋ Add Track")).size(20.0).color(egui::Color32::from_rgb(80,220,140))
                 )).clicked() {
                     self.load_drum_track();
                 }
                 if ui.add(egui::Button::new(
-                    egui::RichText::new("🎙 Rec Track").size(20.0).color(egui::Color32::from_rgb(220, 80, 80))
-                )).on_hover_text("Add a recording track").clicked() {
+                    egui::RichText::new(t!("🎙 Rec Track")).size(20.0).color(egui::Color32::from_rgb(220, 80, 80))
+                )).on_hover_text(t!("Add a recording track")).clicked() {
                     self.add_rec_track();
                 }
                 if ui.add(egui::Button::new(
-                    egui::RichText::new("🎹 Piano Roll").size(20.0).color(egui::Color32::from_rgb(140,180,255))
+                    egui::RichText::new(t!("🎹 Piano Roll")).size(20.0).color(egui::Color32::from_rgb(140,180,255))
                 )).clicked() {
                     *self.piano_roll_open.write() = true;
                 }
+                ui.separator();
+                if ui.add(egui::Button::new(egui::RichText::new(t!("📊 Kit")).size(20.0)))
+                    .on_hover_text(t!("Peak, RMS, loudness and length of every track, with fixes")).clicked() {
+                    self.open_kit_report();
+                }
+                if ui.add(egui::Button::new(egui::RichText::new(t!("🔀 Swap kit")).size(20.0)))
+                    .on_hover_text(t!("Step through similar samples from the browser for each track while the pattern loops")).clicked() {
+                    self.open_kit_swap();
+                }
+                let mut matching = self.loudness_match.load(Ordering::Relaxed);
+                if matching {
+                    let mut target = self.loudness_target.load(Ordering::Relaxed);
+                    if ui.add(egui::DragValue::new(&mut target).speed(0.2).clamp_range(-36.0..=-6.0)
+                        .fixed_decimals(0).suffix(t!(" LUFS"))).changed() {
+                        self.loudness_target.store(target, Ordering::Relaxed);
+                    }
+                }
+                if ui.checkbox(&mut matching, egui::RichText::new(t!("≈ Match")).size(20.0))
+                    .on_hover_text(t!("Loudness-match one-shots as they're added (per-track ↺ restores the original gain)"))
+                    .changed() {
+                    self.loudness_match.store(matching, Ordering::Relaxed);
+                }
+                ui.separator();
+                let (mut watching, changed) = {
+                    let w = self.file_watch.lock();
+                    (w.enabled, w.changed.clone())
+                };
+                if ui.checkbox(&mut watching, egui::RichText::new(t!("👁 Watch")).size(20.0))
+                    .on_hover_text(t!("Notice when a track's sample file is edited outside the app"))
+                    .changed() {
+                    self.file_watch.lock().enabled = watching;
+                }
+                if !changed.is_empty() {
+                    ui.menu_button(egui::RichText::new(t!("⟳ {} changed", changed.len())).size(20.0)
+                        .color(egui::Color32::from_rgb(230, 190, 90)), |ui| {
+                        for path in &changed {
+                            ui.horizontal(|ui| {
+                                let name = std::path::Path::new(path).file_name()
+                                    .map_or(path.clone(), |f| f.to_string_lossy().to_string());
+                                ui.label(name).on_hover_text(path);
+                                if ui.small_button(t!("⟳ Reload")).clicked() { self.reload_asset(path); }
+                                if ui.small_button(t!("Ignore")).clicked() { self.ignore_file_change(path); }
+                            });
+                        }
+                    }).response.on_hover_text(t!("Sample files edited on disk since they were loaded"));
+                }
+                let notice = self.channel_notice.read().clone();
+                if let Some(text) = notice {
+                    ui.separator();
+                    ui.menu_button(egui::RichText::new(format!("⇄ {}", text)).size(20.0)
+                        .color(egui::Color32::from_rgb(230, 190, 90)), |ui| {
+                        ui.label(t!("Channel conversion coefficients"));
+                        let mut c = *self.downmix.read();
+                        let mut changed = false;
+                        changed |= ui.add(egui::Slider::new(&mut c.center, 0.0..=1.0).text(t!("Center"))).changed();
+                        changed |= ui.add(egui::Slider::new(&mut c.surround, 0.0..=1.0).text(t!("Surround"))).changed();
+                        changed |= ui.add(egui::Slider::new(&mut c.lfe, 0.0..=1.0).text(t!("LFE"))).changed();
+                        changed |= ui.checkbox(&mut c.normalize, t!("Normalize to avoid clipping")).changed();
+                        if changed { *self.downmix.write() = c; }
+                        ui.label(egui::RichText::new(t!("Applies to voices triggered after the change"))
+                            .small().color(egui::Color32::GRAY));
+                    }).response.on_hover_text(t!("A source's channel layout differs from the output device"));
+                }
             });
         });
     }
 
 
     pub fn draw_step_sequencer(&mut self, ui: &mut egui::Ui) {
+        let res         = *self.seq_resolution.read();
+        let n_steps     = self.pattern_len();
         let label_w     = 130.0;
-        let step_w      = 38.0;
-        let steps_total = step_w * NUM_STEPS as f32;
+        // A bar keeps the same width at every resolution; longer patterns scroll
+        let step_w      = 38.0 * 16.0 / res.steps() as f32;
+        let steps_total = step_w * n_steps as f32;
         let row_h       = 36.0;
         let knob_h      = 52.0;
 
@@ -117,17 +589,32 @@ impl AppState {
 
         frame.show(ui, |ui| {
             self.seq_header_ui(ui);
+            self.draw_macro_strip(ui);
             ui.add(egui::Separator::default().horizontal().spacing(4.0));
 
             let current_step = *self.seq_current_step.read();
-            let seq_playing  = self.seq_playing.load(Ordering::Relaxed);
+            let seq_playing  = self.events.playing();
+            // Every lane (triplets, a track's own resolution) spans the same bar width as
+            // the grid: (steps, button width, current step, steps per beat)
+            let sp      = ui.spacing().item_spacing.x;
+            let row_w   = n_steps as f32 * (step_w - 2.0) + (n_steps - 1) as f32 * sp;
+            let lane = |steps: usize| {
+                let n = lane_len(n_steps, res, steps).max(1);
+                let w = ((row_w - (n - 1) as f32 * sp) / n as f32 + 2.0).max(3.0);
+                (n, w, current_step * n / n_steps, (steps / 4).max(1))
+            };
+            let mut triplet_toggle: Option<(usize, Option<usize>, bool)> = None;
+            let mut track_resolution: Option<(usize, Option<StepResolution>)> = None;
 
             // ── Deferred mutation targets – set inside the scroll area,
             //    applied after it closes to avoid mid-loop structural changes.
             let mut track_to_remove: Option<usize> = None;
             let mut chop_to_remove:  Option<(usize, usize)> = None;
+            let mut row_shift:       Option<(Lane, isize)> = None;
+            let mut row_euclid:      Option<Lane> = None;
+            let mut row_clip:        Option<(Lane, bool)> = None;
 
-            egui::ScrollArea::vertical()
+            egui::ScrollArea::both()
                 .id_source("seq_body_scroll")
                 .auto_shrink([false, true])
                 .max_height(500.0)
@@ -136,15 +623,15 @@ impl AppState {
                 ui.add_space(2.0);
                 ui.horizontal(|ui| {
                     ui.add_space(label_w + 8.0);
-                    for step in 0..NUM_STEPS {
+                    for step in 0..n_steps {
                         let sz = egui::vec2(step_w - 2.0, 13.0);
                         let (r, _) = ui.allocate_exact_size(sz, egui::Sense::hover());
-                        if step % 4 == 0 {
+                        if step.is_multiple_of(res.per_beat()) {
                             ui.painter().text(r.center(), egui::Align2::CENTER_CENTER,
-                                format!("{}", step / 4 + 1), egui::FontId::proportional(9.0),
+                                format!("{}", step / res.per_beat() + 1), egui::FontId::proportional(9.0),
                                 egui::Color32::from_gray(75));
                         }
-                        let tc = if step % 4 == 0 { egui::Color32::from_gray(65) } else { egui::Color32::from_gray(38) };
+                        let tc = if step.is_multiple_of(res.per_beat()) { egui::Color32::from_gray(65) } else { egui::Color32::from_gray(38) };
                         ui.painter().vline(r.left(), r.y_range(), egui::Stroke::new(0.5, tc));
                     }
                 });
@@ -153,20 +640,31 @@ impl AppState {
                 let main_idx = *self.main_track_index.read();
                 if n_drums > 0 {
                     ui.add_space(4.0);
-                    ui.label(egui::RichText::new("  Tracks").size(20.0).color(egui::Color32::from_gray(70)));
+                    ui.label(egui::RichText::new(t!("  Tracks")).size(20.0).color(egui::Color32::from_gray(70)));
                 }
 
-                for drum_idx in 0..n_drums {
-                    let color     = drum_color(drum_idx);
-                    let color_dim = drum_color_dim(drum_idx);
+                let grouped  = self.group_rows.load(Ordering::Relaxed);
+                let any_solo = self.any_track_soloed();
+                let mut last_category = None;
+                for drum_idx in self.track_row_order() {
+                    let (color, color_dim) = self.track_row_colors(drum_idx);
+                    let category = self.drum_tracks.read().get(drum_idx).map(|t| t.category);
+                    if grouped && category != last_category {
+                        if let Some(c) = category {
+                            ui.label(egui::RichText::new(format!("  {}", c.label())).small().color(category_color(c)));
+                        }
+                        last_category = category;
+                    }
 
-                    let (file_name, time_str, muted, sample_uuid) = {
+                    let (file_name, time_str, muted, soloed, silent, sample_uuid) = {
                         let tracks = self.drum_tracks.read();
                         let t = &tracks[drum_idx];
                         (
                             t.asset.file_name.clone(),
                             format!("{:.2}s", t.asset.frames as f32 / t.asset.sample_rate as f32),
                             t.muted,
+                            t.soloed,
+                            !t.audible(any_solo),
                             t.sample_uuid,
                         )
                     };
@@ -185,52 +683,192 @@ impl AppState {
 
                     // ── Main track step row ──────────────────────────────────
                     {
-                        let steps = {
-                            let tracks = self.drum_tracks.read();
-                            tracks.get(drum_idx).map(|t| t.steps).unwrap_or([false; NUM_STEPS])
+                        let (steps, velocity, pitches, triplet, own) = {
+                            let tracks  = self.drum_tracks.read();
+                            let t       = tracks.get(drum_idx);
+                            let triplet = t.is_some_and(|t| t.triplet);
+                            let n       = lane(t.map_or(res.steps(), |t| t.lane(res, None, false))).0;
+                            let steps: Vec<bool>   = (0..n).map(|s| t.is_some_and(|t| step_on(&t.steps, s))).collect();
+                            let velocity: Vec<f32> = (0..n).map(|s| t.map_or(1.0, |t| t.velocity(s))).collect();
+                            let pitches = t.map_or_else(|| vec![0; n], |t| step_pitch::row(&t.step_pitches, None, n));
+                            (steps, velocity, pitches, triplet, t.and_then(|t| t.resolution))
                         };
+                        let (_, lane_w, lane_cur, lane_beat) = lane(lane_steps(res, own, triplet));
 
                         ui.horizontal(|ui| {
                             let (lr, lresp) = ui.allocate_exact_size(egui::vec2(label_w, row_h), egui::Sense::click());
                             let label_bg = if is_focused { egui::Color32::from_rgb(20,30,25) } else { egui::Color32::from_rgb(20,20,28) };
-                            ui.painter().rect_filled(lr, 3.0, if muted { egui::Color32::from_rgb(18,18,22) } else { label_bg });
+                            ui.painter().rect_filled(lr, 3.0, if silent { egui::Color32::from_rgb(18,18,22) } else { label_bg });
                             ui.painter().rect_stroke(lr, 3.0, egui::Stroke::new(
                                 if is_focused { 1.5 } else { 1.0 },
                                 if is_focused { color } else { egui::Color32::from_gray(38) },
                             ));
                             ui.painter().rect_filled(egui::Rect::from_min_size(lr.min+egui::vec2(5.0, 6.0), egui::vec2(4.0, row_h-12.0)), 2.0,
-                                if muted { egui::Color32::from_gray(50) } else { color });
+                                if silent { egui::Color32::from_gray(50) } else { color });
                             let dn = if file_name.len() > 14 { format!("{}…", &file_name[..12]) } else { file_name.clone() };
                             ui.painter().text(egui::pos2(lr.min.x+14.0, lr.center().y-5.0), egui::Align2::LEFT_CENTER,
-                                dn, egui::FontId::proportional(11.0), if muted { egui::Color32::from_gray(80) } else { color });
+                                dn, egui::FontId::proportional(11.0), if silent { egui::Color32::from_gray(80) } else { color });
                             ui.painter().text(egui::pos2(lr.min.x+14.0, lr.center().y+6.0), egui::Align2::LEFT_CENTER,
                                 &time_str, egui::FontId::proportional(8.5), egui::Color32::from_gray(90));
+                            if let Some(meter) = self.drum_tracks.read().get(drum_idx).map(|t| t.meter.clone()) {
+                                // Falls about 20 dB a second between hits
+                                let peak = meter.load(Ordering::Relaxed);
+                                meter.store(peak * (-ui.input(|i| i.stable_dt) * 2.3).exp(), Ordering::Relaxed);
+                                let mr = egui::Rect::from_min_max(egui::pos2(lr.right() - 8.0, lr.top() + 6.0), egui::pos2(lr.right() - 4.0, lr.bottom() - 6.0));
+                                draw_row_meter(ui.painter(), mr, peak);
+                            }
+                            draw_focus_ring(ui, lr, &lresp);
+                            lresp.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button,
+                                t!("Track {}: {}{}{}, preview", drum_idx + 1, file_name,
+                                    if soloed { t!(", soloed") } else { "" }, if muted { t!(", muted") } else { "" })));
                             if lresp.clicked() {
                                 *self.waveform_focus.write() = WaveformFocus::DrumTrack(drum_idx);
-                                *self.status.write() = format!("Previewing: {}", file_name);
+                                *self.status.write() = t!("Previewing: {}", file_name);
                                 if let Some(track) = self.drum_tracks.read().get(drum_idx) {
                                     self.playback_position.store(0.0, Ordering::Relaxed);
                                     self.playback_sample_index.store(0, Ordering::Relaxed);
                                     self.start_playback(track.asset.clone());
                                 }
                             }
+                            lresp.context_menu(|ui| {
+                                ui.set_min_width(175.0);
+                                ui.label(egui::RichText::new(t!("Track {}", drum_idx + 1)).size(20.0).color(color));
+                                ui.separator();
+                                if let Some(t) = self.drum_tracks.write().get_mut(drum_idx) {
+                                    if ui.button(if t.muted { t!("🔊 Unmute") } else { t!("🔇 Mute") }).clicked() {
+                                        t.muted = !t.muted;
+                                        ui.close_menu();
+                                    }
+                                    if ui.button(if t.soloed { t!("Unsolo") } else { t!("🎧 Solo") }).clicked() {
+                                        t.soloed = !t.soloed;
+                                        ui.close_menu();
+                                    }
+                                    ui.separator();
+                                    adsr_menu(ui, &mut t.adsr, &mut t.adsr_enabled);
+                                }
+                                ui.separator();
+                                if let Some(by) = self.shift_row_menu(ui) { row_shift = Some((Lane::Main(drum_idx), by)); }
+                                self.row_timing_menu(ui, drum_idx, None);
+                                if let Some(to) = self.track_resolution_menu(ui, own, main_idx == Some(drum_idx)) {
+                                    track_resolution = Some((drum_idx, to));
+                                }
+                                if self.euclid_menu(ui) { row_euclid = Some(Lane::Main(drum_idx)); }
+                                if let Some(paste) = self.row_clip_menu(ui) { row_clip = Some((Lane::Main(drum_idx), paste)); }
+                                ui.separator();
+                                let (path, mono, channels) = self.drum_tracks.read().get(drum_idx)
+                                    .map_or((None, false, 1), |t| (t.file_path.clone(), t.mono, t.asset.channels));
+                                if mono {
+                                    if ui.button(t!("◑ Restore channels")).on_hover_text(t!("Go back to the file's own channel layout")).clicked() {
+                                        self.set_track_mono(drum_idx, false);
+                                        ui.close_menu();
+                                    }
+                                } else if channels > 1 && ui.button(t!("◐ Fold to mono")).clicked() {
+                                    self.set_track_mono(drum_idx, true);
+                                    ui.close_menu();
+                                }
+                                if ui.add_enabled(path.is_some(), egui::Button::new(t!("⟳ Reload from disk")))
+                                    .on_hover_text(t!("Re-read the sample file; markers keep their place")).clicked() {
+                                    if let Some(p) = &path { self.reload_asset(p); }
+                                    ui.close_menu();
+                                }
+                                let (category, pinned) = self.drum_tracks.read().get(drum_idx)
+                                    .map_or((DrumCategory::Perc, false), |t| (t.category, t.category_pinned));
+                                ui.menu_button(t!("🏷 Category: {}", category.label()), |ui| {
+                                    for c in DrumCategory::ALL {
+                                        if ui.radio(pinned && c == category, egui::RichText::new(c.label()).color(category_color(c))).clicked() {
+                                            self.set_track_category(drum_idx, Some(c));
+                                            ui.close_menu();
+                                        }
+                                    }
+                                    ui.separator();
+                                    if ui.radio(!pinned, t!("Guess from the sound")).clicked() {
+                                        self.set_track_category(drum_idx, None);
+                                        ui.close_menu();
+                                    }
+                                });
+                                if let Some(p) = &path {
+                                    ui.menu_button(t!("📝 Notes & credit"), |ui| {
+                                        let mut notes = self.sample_notes(p);
+                                        ui.vertical(|ui| {
+                                            if sample_notes_editor(ui, p, &mut notes) { self.set_sample_notes(notes); }
+                                        });
+                                    });
+                                }
+                            });
                             ui.add_space(8.0);
-                            draw_step_buttons(ui, step_w, row_h, color, color_dim, &steps, current_step, seq_playing,
+                            let mut set_velocity = |step: usize, v: f32| {
+                                if let Some(t) = self.drum_tracks.write().get_mut(drum_idx) { t.set_velocity(step, v); }
+                            };
+                            let mut set_pitch = |step: usize, semi: i8| {
+                                if let Some(t) = self.drum_tracks.write().get_mut(drum_idx) { step_pitch::set(&mut t.step_pitches, None, step, semi); }
+                            };
+                            let learn_step = draw_step_buttons(ui, &file_name, lane_w, row_h, color, color_dim, &steps, lane_beat, lane_cur, seq_playing,
                                 &mut |step| {
-                                    if let Some(t) = self.drum_tracks.write().get_mut(drum_idx) { t.steps[step] = !t.steps[step]; }
-                                }
+                                    self.push_undo_gesture(t!("step"));
+                                    if let Some(t) = self.drum_tracks.write().get_mut(drum_idx) {
+                                        let on = !step_on(&t.steps, step);
+                                        set_step(&mut t.steps, step, on);
+                                        t.set_velocity(step, 1.0);
+                                        step_pitch::set(&mut t.step_pitches, None, step, 0);
+                                        step_nudge::set(&mut t.step_nudges, None, step, 0.0);
+                                    }
+                                },
+                                Some(StepVelocity { values: &velocity, on_change: &mut set_velocity }),
+                                Some(StepPitches { values: &pitches, on_change: &mut set_pitch }),
                             );
+                            if let Some(step) = learn_step {
+                                self.toggle_midi_learn(LearnTarget::Step(Lane::Main(drum_idx), step));
+                            }
+                            if triplet_button(ui, triplet, lane_steps(res, own, true), row_h, triplet || !own.unwrap_or(res).is_triplet()).clicked() {
+                                triplet_toggle = Some((drum_idx, None, !triplet));
+                            }
+
+                            // ── Solo ────────────────────────────────────────
+                            let sresp = ui.add(
+                                egui::Button::new(
+                                    egui::RichText::new(t!("S")).size(12.0)
+                                        .color(if soloed { egui::Color32::BLACK } else { egui::Color32::from_gray(150) })
+                                )
+                                .min_size(egui::vec2(24.0, row_h))
+                                .fill(if soloed { egui::Color32::from_rgb(90, 200, 230) } else { egui::Color32::from_gray(28) })
+                            )
+                            .on_hover_text(if soloed { t!("Unsolo (ctrl-click: clear every solo)") } else { t!("Solo: only soloed tracks play (ctrl-click: solo just this one)") });
+                            if sresp.clicked() {
+                                let exclusive = ui.input(|i| i.modifiers.command);
+                                let mut tracks = self.drum_tracks.write();
+                                if exclusive {
+                                    let only = !soloed;
+                                    for (i, t) in tracks.iter_mut().enumerate() { t.soloed = only && i == drum_idx; }
+                                } else if let Some(t) = tracks.get_mut(drum_idx) {
+                                    t.soloed = !t.soloed;
+                                }
+                            }
+
+                            // ── Mute (right-click to MIDI learn) ────────────
+                            let mresp = ui.add(
+                                egui::Button::new(
+                                    egui::RichText::new(t!("M")).size(12.0)
+                                        .color(if muted { egui::Color32::BLACK } else { egui::Color32::from_gray(150) })
+                                )
+                                .min_size(egui::vec2(24.0, row_h))
+                                .fill(if muted { egui::Color32::from_rgb(230, 180, 60) } else { egui::Color32::from_gray(28) })
+                            )
+                            .on_hover_text(if muted { t!("Unmute (right-click to MIDI learn)") } else { t!("Mute (right-click to MIDI learn)") });
+                            if mresp.clicked() {
+                                if let Some(t) = self.drum_tracks.write().get_mut(drum_idx) { t.muted = !t.muted; }
+                            }
+                            if mresp.secondary_clicked() { self.toggle_midi_learn(LearnTarget::Mute(drum_idx)); }
 
                             // ── ✕ Remove track (+ all its chops) ────────────
                             if ui.add(
                                 egui::Button::new(
-                                    egui::RichText::new("X").size(13.0)
+                                    egui::RichText::new(t!("X")).size(13.0)
                                         .color(egui::Color32::from_rgb(200, 70, 70))
                                 )
                                 .min_size(egui::vec2(26.0, row_h))
                                 .fill(egui::Color32::from_rgb(38, 12, 12))
                             )
-                            .on_hover_text("Remove track and all its chops")
+                            .on_hover_text(t!("Remove track and all its chops"))
                             .clicked()
                             {
                                 track_to_remove = Some(drum_idx);
@@ -243,13 +881,50 @@ impl AppState {
                             ui.add_space(8.0);
                             let mut tracks = self.drum_tracks.write();
                             if let Some(t) = tracks.get_mut(drum_idx) {
-                                if ui.checkbox(&mut t.adsr_enabled, "ADSR").changed() {
-                                    *self.status.write() = if t.adsr_enabled {
-                                        format!("ADSR ON for {}", file_name)
-                                    } else {
-                                        format!("ADSR OFF for {} (full volume)", file_name)
-                                    };
-                                }
+                                ui.vertical(|ui| {
+                                    if ui.checkbox(&mut t.adsr_enabled, t!("ADSR")).changed() {
+                                        *self.status.write() = if t.adsr_enabled {
+                                            t!("ADSR ON for {}", file_name)
+                                        } else {
+                                            t!("ADSR OFF for {} (full volume)", file_name)
+                                        };
+                                    }
+                                    ui.horizontal(|ui| {
+                                        ui.add(egui::DragValue::new(&mut t.trim_db).speed(0.1).clamp_range(-24.0..=24.0)
+                                            .fixed_decimals(1).prefix(t!("trim ")).suffix(t!(" dB"))).on_hover_text(t!("Input trim, before the track gain"));
+                                        let inv_col = if t.invert { color } else { egui::Color32::from_gray(120) };
+                                        if ui.selectable_label(t.invert, egui::RichText::new("Ø").color(inv_col))
+                                            .on_hover_text(t!("Invert polarity (for layered hits that cancel)")).clicked() {
+                                            t.invert = !t.invert;
+                                        }
+                                        let mut db = 20.0 * t.gain.max(1e-4).log10();
+                                        if ui.add(egui::DragValue::new(&mut db).speed(0.1).clamp_range(-40.0..=24.0)
+                                            .fixed_decimals(1).suffix(t!(" dB"))).on_hover_text(t!("Track gain")).changed() {
+                                            t.gain = 10f32.powf(db / 20.0);
+                                        }
+                                        let target = self.loudness_target.load(Ordering::Relaxed);
+                                        if ui.small_button("≈").on_hover_text(t!("Match loudness to {:.0} LUFS", target)).clicked() {
+                                            *self.status.write() = match t.match_loudness(target) {
+                                                Some(g) => t!("✓ {} matched ({:+.1} dB)", file_name, 20.0 * g.log10()),
+                                                None    => t!("✗ {} is silent", file_name),
+                                            };
+                                        }
+                                        if t.gain_before_match.is_some()
+                                            && ui.small_button("↺").on_hover_text(t!("Restore gain from before loudness match")).clicked() {
+                                            t.restore_gain();
+                                        }
+                                        let late = t.late_ms > 0.0 || t.late_jitter_ms > 0.0;
+                                        ui.menu_button(egui::RichText::new("⏱").color(if late { color } else { egui::Color32::from_gray(120) }), |ui| {
+                                            ui.label(egui::RichText::new(t!("Lateness")).small().color(egui::Color32::from_gray(140)));
+                                            ui.add(egui::DragValue::new(&mut t.late_ms).speed(0.2).clamp_range(0.0..=50.0)
+                                                .fixed_decimals(1).prefix(t!("late ")).suffix(t!(" ms")))
+                                                .on_hover_text(t!("Every hit on this track starts this much late"));
+                                            ui.add(egui::DragValue::new(&mut t.late_jitter_ms).speed(0.2).clamp_range(0.0..=50.0)
+                                                .fixed_decimals(1).prefix(t!("+ up to ")).suffix(t!(" ms")))
+                                                .on_hover_text(t!("Plus a random extra delay per hit, for loose layering"));
+                                        }).response.on_hover_text(t!("Start drift: play this track's hits a little late"));
+                                    });
+                                });
                             }
                             drop(tracks);
                             let (knob_rect, _) = ui.allocate_exact_size(egui::vec2(steps_total, knob_h), egui::Sense::hover());
@@ -291,74 +966,123 @@ impl AppState {
                                         .map(|n| !n.is_empty())
                                         .unwrap_or(false)
                                 };
+                                let has_alternates = self.drum_tracks.read().get(drum_idx)
+                                    .is_some_and(|t| t.chop_alternates.iter().any(|a| a.chop == chop_idx));
                                 ui.painter().text(egui::pos2(lr.min.x+22.0, lr.center().y-4.0), egui::Align2::LEFT_CENTER,
-                                    format!("Chop {}{}", chop_idx + 1, if has_piano_notes { " 🎹" } else { "" }),
+                                    t!("Chop {}{}{}{}", chop_idx + 1, if has_piano_notes { " 🎹" } else { "" }, if has_alternates { " 🎲" } else { "" },
+                                        if *self.chop_solo.read() == Some((drum_idx, chop_idx)) { " 🎧" } else { "" }),
                                     egui::FontId::proportional(10.0), chop_color);
                                 ui.painter().text(egui::pos2(lr.min.x+22.0, lr.center().y+5.0), egui::Align2::LEFT_CENTER,
                                     format!("{:.2}s", time_at), egui::FontId::proportional(8.0), egui::Color32::from_gray(85));
+                                draw_focus_ring(ui, lr, &lresp);
+                                lresp.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button,
+                                    t!("Chop {} at {:.2} seconds", chop_idx + 1, time_at)));
                                 if lresp.clicked() {
                                     *self.waveform_focus.write() = WaveformFocus::DrumTrack(drum_idx);
                                 }
                                 let pr_ref = self.piano_roll_chop.clone();
                                 lresp.context_menu(|ui| {
                                     ui.set_min_width(175.0);
-                                    ui.label(egui::RichText::new(format!("Chop {}  @{:.2}s", chop_idx + 1, time_at)).size(20.0).color(chop_color));
+                                    ui.label(egui::RichText::new(t!("Chop {}  @{:.2}s", chop_idx + 1, time_at)).size(20.0).color(chop_color));
                                     ui.separator();
-                                    if ui.button("🎹  Piano Roll").clicked() {
+                                    if ui.button(t!("🎹  Piano Roll")).clicked() {
                                         *pr_ref.write() = Some((drum_idx, chop_idx));
                                         ui.close_menu();
                                     }
+                                    let soloed = *self.chop_solo.read() == Some((drum_idx, chop_idx));
+                                    if ui.button(if soloed { t!("🎧  Unsolo") } else { t!("🎧  Solo listen") })
+                                        .on_hover_text(t!("Hear only this chop's hits while the pattern plays")).clicked() {
+                                        *self.chop_solo.write() = if soloed { None } else { Some((drum_idx, chop_idx)) };
+                                        ui.close_menu();
+                                    }
+                                    ui.separator();
+                                    if let Some(t) = self.drum_tracks.write().get_mut(drum_idx) {
+                                        if let (Some(adsr), Some(enabled)) = (t.chop_adsr.get_mut(chop_idx), t.chop_adsr_enabled.get_mut(chop_idx)) {
+                                            adsr_menu(ui, adsr, enabled);
+                                        }
+                                    }
                                     ui.separator();
-                                    if ui.button(egui::RichText::new("🗑  Clear Steps").color(egui::Color32::from_rgb(200,80,80))).clicked() {
+                                    if let Some(by) = self.shift_row_menu(ui) { row_shift = Some((Lane::Chop(drum_idx, chop_idx), by)); }
+                                    self.row_timing_menu(ui, drum_idx, Some(chop_idx));
+                                    if self.euclid_menu(ui) { row_euclid = Some(Lane::Chop(drum_idx, chop_idx)); }
+                                    if let Some(paste) = self.row_clip_menu(ui) { row_clip = Some((Lane::Chop(drum_idx, chop_idx), paste)); }
+                                    ui.separator();
+                                    self.chop_alternates_menu(ui, drum_idx, chop_idx, chop_marks.len());
+                                    ui.separator();
+                                    if ui.button(egui::RichText::new(t!("🗑  Clear Steps")).color(egui::Color32::from_rgb(200,80,80))).clicked() {
                                         let mut tracks = self.drum_tracks.write();
                                         if let Some(t) = tracks.get_mut(drum_idx) {
-                                            if let Some(row) = t.chop_steps.get_mut(chop_idx) { *row = [false; NUM_STEPS]; }
+                                            if let Some(row) = t.chop_steps.get_mut(chop_idx) { row.fill(false); }
+                                            t.chop_alternates.retain(|a| a.chop != chop_idx);
                                             if let Some(notes) = t.chop_piano_notes.get_mut(chop_idx) { notes.clear(); }
                                         }
                                         ui.close_menu();
                                     }
                                 });
                                 ui.add_space(8.0);
-                                let is_ons: [bool; NUM_STEPS] = {
+                                // The main track's chops are on the main grid
+                                let own = if Some(drum_idx) == main_idx { None } else { self.drum_tracks.read().get(drum_idx).and_then(|t| t.resolution) };
+                                let triplet = self.drum_tracks.read().get(drum_idx)
+                                    .is_some_and(|t| t.row_triplet(Some(chop_idx)));
+                                let (lane_n, lane_w, lane_cur, lane_beat) = lane(lane_steps(res, own, triplet));
+                                let is_ons: Vec<bool> = {
                                     let tracks = self.drum_tracks.read();
                                     if Some(drum_idx) == main_idx {
                                         let grid = self.seq_grid.read();
-                                        std::array::from_fn(|s| grid[s].contains(&chop_idx))
+                                        (0..lane_n).map(|s| grid.get(s).is_some_and(|g| g.contains(&chop_idx))).collect()
                                     } else {
-                                        tracks.get(drum_idx)
-                                            .and_then(|t| t.chop_steps.get(chop_idx))
-                                            .copied()
-                                            .unwrap_or([false; NUM_STEPS])
+                                        let row = tracks.get(drum_idx).and_then(|t| t.chop_steps.get(chop_idx));
+                                        (0..lane_n).map(|s| row.is_some_and(|r| step_on(r, s))).collect()
+                                    }
+                                };
+                                let pitches = self.drum_tracks.read().get(drum_idx)
+                                    .map_or_else(|| vec![0; lane_n], |t| step_pitch::row(&t.step_pitches, Some(chop_idx), lane_n));
+                                let mut set_pitch = |step: usize, semi: i8| {
+                                    if let Some(t) = self.drum_tracks.write().get_mut(drum_idx) {
+                                        step_pitch::set(&mut t.step_pitches, Some(chop_idx), step, semi);
                                     }
                                 };
-                                draw_step_buttons(
-                                    ui, step_w, row_h, chop_color, chop_color_dim,
-                                    &is_ons, current_step, seq_playing,
+                                let learn_step = draw_step_buttons(
+                                    ui, &format!("{} chop {}", file_name, chop_idx + 1), lane_w, row_h, chop_color, chop_color_dim,
+                                    &is_ons, lane_beat, lane_cur, seq_playing,
                                     &mut |step| {
+                                        self.push_undo_gesture(t!("step"));
                                         let mut tracks = self.drum_tracks.write();
                                         if let Some(t) = tracks.get_mut(drum_idx) {
+                                            step_pitch::set(&mut t.step_pitches, Some(chop_idx), step, 0);
+                                            step_nudge::set(&mut t.step_nudges, Some(chop_idx), step, 0.0);
                                             if Some(drum_idx) == main_idx {
                                                 let mut grid = self.seq_grid.write();
                                                 let sp = &mut grid[step];
                                                 if let Some(i) = sp.iter().position(|&p| p == chop_idx) { sp.remove(i); }
                                                 else { sp.push(chop_idx); }
                                             } else if let Some(row) = t.chop_steps.get_mut(chop_idx) {
-                                                row[step] = !row[step];
+                                                let on = !step_on(row, step);
+                                                set_step(row, step, on);
                                             }
                                         }
                                     },
+                                    None,
+                                    Some(StepPitches { values: &pitches, on_change: &mut set_pitch }),
                                 );
+                                if let Some(step) = learn_step {
+                                    self.toggle_midi_learn(LearnTarget::Step(Lane::Chop(drum_idx, chop_idx), step));
+                                }
+
+                                if triplet_button(ui, triplet, lane_steps(res, own, true), row_h, triplet || !own.unwrap_or(res).is_triplet()).clicked() {
+                                    triplet_toggle = Some((drum_idx, Some(chop_idx), !triplet));
+                                }
 
                                 // ── ✕ Remove this chop ───────────────────────
                                 if ui.add(
                                     egui::Button::new(
-                                        egui::RichText::new("X").size(11.0)
+                                        egui::RichText::new(t!("X")).size(11.0)
                                             .color(egui::Color32::from_rgb(180, 60, 60))
                                     )
                                     .min_size(egui::vec2(22.0, row_h))
                                     .fill(egui::Color32::from_rgb(32, 10, 10))
                                 )
-                                .on_hover_text("Remove this chop marker")
+                                .on_hover_text(t!("Remove this chop marker"))
                                 .clicked()
                                 {
                                     chop_to_remove = Some((drum_idx, chop_idx));
@@ -373,11 +1097,11 @@ impl AppState {
                                 let mut tracks = self.drum_tracks.write();
                                 if let Some(t) = tracks.get_mut(drum_idx) {
                                     if let Some(enabled) = t.chop_adsr_enabled.get_mut(chop_idx) {
-                                        if ui.checkbox(enabled, "ADSR").changed() {
+                                        if ui.checkbox(enabled, t!("ADSR")).changed() {
                                             *self.status.write() = if *enabled {
-                                                format!("ADSR ON for Chop {}", chop_idx + 1)
+                                                t!("ADSR ON for Chop {}", chop_idx + 1)
                                             } else {
-                                                format!("ADSR OFF for Chop {} (full volume)", chop_idx + 1)
+                                                t!("ADSR OFF for Chop {} (full volume)", chop_idx + 1)
                                             };
                                         }
                                     }
@@ -391,9 +1115,9 @@ impl AppState {
                                         .unwrap_or(crate::gui::ChopPlayMode::ToNextChop)
                                 };
                                 let fixed_modes = [
-                                    (crate::gui::ChopPlayMode::ToEnd,      "▶∞",  "Play to end of sample"),
-                                    (crate::gui::ChopPlayMode::ToNextChop, "▶|",  "Play to next chop marker"),
-                                    (crate::gui::ChopPlayMode::ToNextStep, "▶□",  "Play for one step then stop"),
+                                    (crate::gui::ChopPlayMode::ToEnd,      "▶∞",  t!("Play to end of sample")),
+                                    (crate::gui::ChopPlayMode::ToNextChop, "▶|",  t!("Play to next chop marker")),
+                                    (crate::gui::ChopPlayMode::ToNextStep, "▶□",  t!("Play for one step then stop")),
                                 ];
                                 for (mode, label, tip) in fixed_modes {
                                     let active = play_mode == mode;
@@ -415,11 +1139,11 @@ impl AppState {
                                     let is_to_marker = matches!(play_mode, crate::gui::ChopPlayMode::ToMarker(_));
                                     let current_target_id: Option<usize> = if let crate::gui::ChopPlayMode::ToMarker(id) = play_mode { Some(id) } else { None };
                                     let col = if is_to_marker { chop_color } else { egui::Color32::from_gray(80) };
-                                    let btn = egui::Button::new(egui::RichText::new("▶M").size(20.0).color(col))
+                                    let btn = egui::Button::new(egui::RichText::new(t!("▶M")).size(20.0).color(col))
                                         .fill(if is_to_marker {
                                             egui::Color32::from_rgba_unmultiplied(chop_color.r(), chop_color.g(), chop_color.b(), 35)
                                         } else { egui::Color32::TRANSPARENT });
-                                    if ui.add(btn).on_hover_text("Play to a specific marker you choose").clicked() && !is_to_marker {
+                                    if ui.add(btn).on_hover_text(t!("Play to a specific marker you choose")).clicked() && !is_to_marker {
                                         let own_pos = all_marks.get(chop_idx).map(|m| m.position).unwrap_or(0.0);
                                         let first_other = all_marks.iter().find(|m| m.position > own_pos).map(|m| m.id)
                                             .or_else(|| all_marks.first().map(|m| m.id));
@@ -440,7 +1164,7 @@ impl AppState {
                                         let selected_label = current_target_id
                                             .and_then(|id| all_marks.iter().find(|m| m.id == id))
                                             .map(|m| format!("M{} {:.2}s", m.id, m.position * dur_secs))
-                                            .unwrap_or_else(|| "Pick marker".to_string());
+                                            .unwrap_or_else(|| t!("Pick marker").to_string());
                                         let combo_id = egui::Id::new("to_marker_combo").with(drum_idx).with(chop_idx);
                                         egui::ComboBox::from_id_source(combo_id)
                                             .selected_text(egui::RichText::new(&selected_label).size(20.0).color(chop_color))
@@ -482,31 +1206,46 @@ impl AppState {
                             });
                         }
                     }
+                    self.draw_region_rows(ui, drum_idx, label_w, step_w, row_h);
 
                     ui.add_space(2.0);
                 } // for drum_idx
 
                 self.draw_recording_tracks(ui, current_step, seq_playing, step_w, row_h, label_w);
+                self.draw_retro_capture(ui);
 
                 if n_drums == 0 && self.rec_tracks.read().is_empty() {
                     ui.label(egui::RichText::new(
-                        "No tracks yet — click ＋ Add Track to load a sample")
+                        t!("No tracks yet — click ＋ Add Track to load a sample"))
                         .size(20.0).color(egui::Color32::from_gray(80)).italics());
                 }
                 ui.add_space(3.0);
                 ui.label(egui::RichText::new(
-                    "Click steps to toggle  ·  Click label to focus/preview  ·  Right-click for options  ·  X to remove")
+                    t!("Click steps to toggle  ·  Click label to focus/preview  ·  Right-click for options  ·  X to remove"))
                     .size(20.0).color(egui::Color32::from_gray(58)));
 
                 });
 
             // ── Apply deferred track removal ──────────────────────────────────
             if let Some(rm_idx) = track_to_remove {
+                self.push_undo(t!("track remove"));
                 let uuid = self.drum_tracks.read().get(rm_idx).map(|t| t.sample_uuid);
                 if let Some(uuid) = uuid {
                     self.samples_manager.clear_marks_for_uuid(&uuid);
                 }
                 self.drum_tracks.write().remove(rm_idx);
+                let solo = *self.chop_solo.read();
+                *self.chop_solo.write() = match solo {
+                    Some((t, _)) if t == rm_idx => None,
+                    Some((t, c)) if t > rm_idx  => Some((t - 1, c)),
+                    other => other,
+                };
+                let pinned = self.pinned_waveform.read().clone();
+                *self.pinned_waveform.write() = match pinned {
+                    Some(WaveformFocus::DrumTrack(t)) if t == rm_idx => None,
+                    Some(WaveformFocus::DrumTrack(t)) if t > rm_idx  => Some(WaveformFocus::DrumTrack(t - 1)),
+                    other => other,
+                };
                 let n = self.drum_tracks.read().len();
                 if n == 0 {
                     *self.waveform_focus.write()    = WaveformFocus::MainSample;
@@ -523,37 +1262,246 @@ impl AppState {
                         *self.waveform_analysis.write() = Some(wf);
                     }
                 }
-                *self.status.write() = format!("Track {} removed", rm_idx + 1);
+                *self.status.write() = t!("Track {} removed", rm_idx + 1);
+            }
+
+            // ── Apply deferred lane switch ────────────────────────────────────
+            if let Some((t_idx, chop, on)) = triplet_toggle {
+                self.set_row_triplet(t_idx, chop, on);
+            }
+            if let Some((t_idx, to)) = track_resolution {
+                self.push_undo(t!("track resolution"));
+                self.set_track_resolution(t_idx, to);
             }
 
             // ── Apply deferred chop removal ───────────────────────────────────
             if let Some((t_idx, c_idx)) = chop_to_remove {
-                let uuid = self.drum_tracks.read().get(t_idx).map(|t| t.sample_uuid);
-                if let Some(uuid) = uuid {
-                    // Find and delete the mark from the global marks list
-                    let marks = self.samples_manager.get_marks_for_sample(&uuid);
-                    if let Some(mark) = marks.get(c_idx) {
-                        let mark_id = mark.id;
-                        let global_idx = self.samples_manager.get_marks()
-                            .iter()
-                            .position(|m| m.id == mark_id);
-                        if let Some(gi) = global_idx {
-                            self.samples_manager.delete_mark(gi);
-                        }
-                    }
+                self.push_undo(t!("chop delete"));
+                self.remove_chop(t_idx, c_idx);
+                *self.status.write() = t!("Chop {} removed", c_idx + 1);
+            }
+
+            // ── Apply deferred row shift ──────────────────────────────────────
+            if let Some((lane, by)) = row_shift {
+                self.shift_lane(lane, by);
+            }
+            if let Some(lane) = row_euclid { self.fill_euclid(lane); }
+            match row_clip {
+                Some((lane, false)) => self.copy_lane(lane),
+                Some((lane, true))  => self.paste_lane(lane),
+                None => {}
+            }
+        });
+    }
+
+    /// Row menu section: shift this row's hits by the shift tool's step count.
+    /// Returns the shift picked, for the caller to apply after the rows are drawn.
+    /// Bright and dim colour of track `drum_idx`'s rows, by the chosen scheme.
+    fn track_row_colors(&self, drum_idx: usize) -> (egui::Color32, egui::Color32) {
+        let category = self.drum_tracks.read().get(drum_idx).map(|t| t.category);
+        match (*self.row_colors.read(), category) {
+            (RowColors::Category, Some(c)) => (category_color(c), category_color_dim(c)),
+            _ => (drum_color(drum_idx), drum_color_dim(drum_idx)),
+        }
+    }
+
+    fn shift_row_menu(&self, ui: &mut egui::Ui) -> Option<isize> {
+        let by = self.pattern_shift.load(Ordering::Relaxed) as isize;
+        let mut picked = None;
+        ui.horizontal(|ui| {
+            if ui.button(t!("◀ Shift")).on_hover_text(t!("{} step(s) earlier, wrapping round", by)).clicked() { picked = Some(-by); }
+            if ui.button(t!("Shift ▶")).on_hover_text(t!("{} step(s) later, wrapping round", by)).clicked() { picked = Some(by); }
+        });
+        if picked.is_some() { ui.close_menu(); }
+        picked
+    }
+
+    /// Row menu section: copy the row, or paste a copied one over it. Some(true) to paste.
+    fn row_clip_menu(&self, ui: &mut egui::Ui) -> Option<bool> {
+        let mut picked = None;
+        ui.horizontal(|ui| {
+            if ui.button(t!("📋 Copy row")).clicked() { picked = Some(false); }
+            if ui.add_enabled(self.can_paste_lane(), egui::Button::new(t!("Paste row")))
+                .on_hover_text(t!("Replace this row's hits with the copied row's"))
+                .clicked() { picked = Some(true); }
+        });
+        if picked.is_some() { ui.close_menu(); }
+        picked
+    }
+
+    /// Row menu section: a Euclidean rhythm to fill the row with. True when Fill is clicked.
+    fn euclid_menu(&self, ui: &mut egui::Ui) -> bool {
+        let mut e = *self.euclid.read();
+        let mut fill = false;
+        ui.menu_button(t!("⬡ Euclidean…"), |ui| {
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut e.pulses).clamp_range(0..=e.steps).prefix(t!("pulses ")));
+                ui.add(egui::DragValue::new(&mut e.steps).clamp_range(1..=64).prefix(t!("steps ")));
+                ui.add(egui::DragValue::new(&mut e.rotation).clamp_range(0..=e.steps.saturating_sub(1)).prefix(t!("rotate ")));
+            });
+            e.pulses   = e.pulses.min(e.steps);
+            e.rotation = e.rotation.min(e.steps.saturating_sub(1));
+            // One cycle, as dots
+            let cycle = e.cycle();
+            let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width().max(120.0), 14.0), egui::Sense::hover());
+            let w = rect.width() / cycle.len() as f32;
+            for (i, &on) in cycle.iter().enumerate() {
+                let c = egui::pos2(rect.left() + (i as f32 + 0.5) * w, rect.center().y);
+                if on { ui.painter().circle_filled(c, (w * 0.35).min(5.0), egui::Color32::from_rgb(240, 190, 70)); }
+                else  { ui.painter().circle_stroke(c, (w * 0.25).min(3.0), egui::Stroke::new(1.0, egui::Color32::from_gray(90))); }
+            }
+            if ui.button(t!("Fill row")).on_hover_text(t!("Replace this row's hits with the rhythm, repeated along the row")).clicked() {
+                fill = true;
+                ui.close_menu();
+            }
+        });
+        *self.euclid.write() = e;
+        fill
+    }
+
+    /// Track menu entry: run the track's rows at their own steps per bar (`Some(None)` for
+    /// the grid's). The main track's chops are on the main grid, so it has no choice.
+    fn track_resolution_menu(&self, ui: &mut egui::Ui, own: Option<StepResolution>, is_main: bool) -> Option<Option<StepResolution>> {
+        let res = *self.seq_resolution.read();
+        let grid = t!("Grid ({})", res.label());
+        let mut pick = None;
+        ui.add_enabled_ui(!is_main, |ui| {
+            ui.menu_button(t!("Steps: {}", own.map_or(grid.clone(), |r| r.label().to_string())), |ui| {
+                if ui.radio(own.is_none(), grid.as_str()).clicked() {
+                    pick = Some(None);
+                    ui.close_menu();
                 }
-                // Remove corresponding per-chop arrays at that index
-                let mut tracks = self.drum_tracks.write();
-                if let Some(t) = tracks.get_mut(t_idx) {
-                    if c_idx < t.chop_steps.len()       { t.chop_steps.remove(c_idx); }
-                    if c_idx < t.chop_adsr.len()        { t.chop_adsr.remove(c_idx); }
-                    if c_idx < t.chop_adsr_enabled.len(){ t.chop_adsr_enabled.remove(c_idx); }
-                    if c_idx < t.chop_play_modes.len()  { t.chop_play_modes.remove(c_idx); }
-                    if c_idx < t.chop_piano_notes.len() { t.chop_piano_notes.remove(c_idx); }
+                for r in StepResolution::ALL {
+                    if ui.radio(own == Some(r), r.label()).clicked() {
+                        pick = Some(Some(r));
+                        ui.close_menu();
+                    }
                 }
-                *self.status.write() = format!("Chop {} removed", c_idx + 1);
+            }).response.on_hover_text(t!("Steps per bar for this track's rows; hits keep their place in the bar"));
+        }).response.on_disabled_hover_text(t!("The main track's chops are on the main grid, which sets its steps"));
+        pick.filter(|&to| to != own)
+    }
+
+    /// Row menu section: sit the whole row ahead of or behind the grid, in ms or ticks,
+    /// and humanize it.
+    fn row_timing_menu(&self, ui: &mut egui::Ui, drum_idx: usize, chop: Option<usize>) {
+        let res = *self.seq_resolution.read();
+        let mut tracks = self.drum_tracks.write();
+        let Some(t) = tracks.get_mut(drum_idx) else { return; };
+        let on_grid = chop.is_some() && *self.main_track_index.read() == Some(drum_idx);
+        // One step of the row's own lane, in ms and in ticks
+        let lane  = res.steps() as f64 / t.lane(res, chop, on_grid) as f64;
+        let ms    = (res.step_secs(self.song_bpm()) * lane * 1000.0) as f32;
+        let ticks = row_timing::ticks_per_step(res.per_beat()) * lane as f32;
+        let in_ticks = self.row_timing_ticks.load(Ordering::Relaxed);
+        let (unit, suffix) = if in_ticks { (ticks, t!(" ticks")) } else { (ms, t!(" ms")) };
+        let mut frac = row_timing::fraction(&t.row_offsets, chop);
+        ui.horizontal(|ui| {
+            let mut v = frac * unit;
+            let half  = row_timing::MAX_OFFSET * unit;
+            if ui.add(egui::DragValue::new(&mut v).speed(unit / 100.0).clamp_range(-half..=half)
+                .fixed_decimals(1).prefix(t!("⇆ ")).suffix(suffix))
+                .on_hover_text(t!("Whole row ahead of the grid (−) or behind it (+), up to half a step"))
+                .changed() {
+                frac = v / unit.max(1e-6);
+            }
+            if ui.selectable_label(in_ticks, t!("ticks")).on_hover_text(t!("Show offsets in ticks, 96 to the beat")).clicked() {
+                self.row_timing_ticks.store(!in_ticks, Ordering::Relaxed);
             }
+            if frac != 0.0 && ui.small_button("0").on_hover_text(t!("Back on the grid")).clicked() { frac = 0.0; }
         });
+        row_timing::set(&mut t.row_offsets, chop, frac);
+        let mut human = humanize::amount(&t.row_humanize, chop);
+        ui.add(egui::Slider::new(&mut human, 0.0..=1.0).text(t!("🎲 humanize"))
+            .custom_formatter(|v, _| format!("{:.0}%", v * 100.0)))
+            .on_hover_text(t!("Random timing and level on this row's hits, on top of the project's"));
+        humanize::set(&mut t.row_humanize, chop, human);
+    }
+
+    /// Chop-row menu section: steps where another chop sometimes plays instead.
+    fn chop_alternates_menu(&self, ui: &mut egui::Ui, drum_idx: usize, chop_idx: usize, n_chops: usize) {
+        ui.label(egui::RichText::new(t!("🎲 Alternates")).color(egui::Color32::from_gray(140)));
+        let len     = self.pattern_len();
+        let on_main = *self.main_track_index.read() == Some(drum_idx);
+        let mut tracks = self.drum_tracks.write();
+        let Some(t) = tracks.get_mut(drum_idx) else { return; };
+        let mut remove = None;
+        for (i, a) in t.chop_alternates.iter_mut().enumerate().filter(|(_, a)| a.chop == chop_idx) {
+            ui.horizontal(|ui| {
+                let (mut step, mut alt) = (a.step + 1, a.alt + 1);
+                ui.add(egui::DragValue::new(&mut step).clamp_range(1..=len).prefix(t!("step ")));
+                ui.add(egui::DragValue::new(&mut alt).clamp_range(1..=n_chops.max(1)).prefix(t!("→ chop ")));
+                (a.step, a.alt) = (step - 1, alt - 1);
+                ui.add(egui::Slider::new(&mut a.chance, 0.0..=1.0)
+                    .custom_formatter(|v, _| format!("{:.0}%", v * 100.0)))
+                    .on_hover_text(t!("How often the alternate plays instead"));
+                if ui.small_button("✕").clicked() { remove = Some(i); }
+            });
+        }
+        if let Some(i) = remove { t.chop_alternates.remove(i); }
+        if n_chops > 1 && ui.button(t!("＋ Add alternate")).clicked() {
+            // First hit of this row that has no alternate yet
+            let hit = |s: usize| if on_main {
+                self.seq_grid.read().get(s).is_some_and(|g| g.contains(&chop_idx))
+            } else {
+                t.chop_steps.get(chop_idx).is_some_and(|r| step_on(r, s))
+            };
+            let taken = |s: usize| t.chop_alternates.iter().any(|a| a.chop == chop_idx && a.step == s);
+            let step  = (0..len).find(|&s| hit(s) && !taken(s)).unwrap_or(0);
+            t.chop_alternates.push(ChopAlternate { step, chop: chop_idx, alt: (chop_idx + 1) % n_chops, chance: 0.3 });
+        }
+    }
+
+    /// One lane per region of drum track `drum_idx`; a step plays the whole region.
+    fn draw_region_rows(&mut self, ui: &mut egui::Ui, drum_idx: usize, label_w: f32, step_w: f32, row_h: f32) {
+        let Some(uuid) = self.drum_tracks.read().get(drum_idx).map(|t| t.sample_uuid) else { return; };
+        let regions = self.samples_manager.get_regions_for_sample(&uuid);
+        if regions.is_empty() { return; }
+        let n_steps      = self.pattern_len();
+        let per_beat     = self.seq_resolution.read().per_beat();
+        let current_step = *self.seq_current_step.read();
+        let seq_playing  = self.events.playing();
+        let color        = egui::Color32::from_rgb(90, 190, 200);
+        let color_dim    = egui::Color32::from_rgb(18, 38, 42);
+
+        for region in &regions {
+            ui.horizontal(|ui| {
+                let (lr, lresp) = ui.allocate_exact_size(egui::vec2(label_w, row_h), egui::Sense::click());
+                ui.painter().rect_filled(lr, 3.0, egui::Color32::from_rgb(15, 22, 25));
+                ui.painter().rect_stroke(lr, 3.0, egui::Stroke::new(0.5, egui::Color32::from_gray(30)));
+                ui.painter().text(egui::pos2(lr.min.x + 14.0, lr.center().y), egui::Align2::LEFT_CENTER,
+                    format!("▭ {}", region.name), egui::FontId::proportional(10.0), color);
+                draw_focus_ring(ui, lr, &lresp);
+                lresp.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button,
+                    t!("Region lane {}, preview", region.name)));
+                if lresp.clicked() {
+                    let voice = {
+                        let tracks = self.drum_tracks.read();
+                        tracks.get(drum_idx).and_then(|t| {
+                            t.region_voice(&std::sync::Arc::new(t.asset.pcm.clone()), &self.samples_manager.get_marks_for_sample(&uuid), region)
+                        })
+                    };
+                    if let Some(v) = voice { self.play_voices(vec![v]); }
+                }
+                lresp.context_menu(|ui| {
+                    if ui.button(t!("🗑 Clear Steps")).clicked() {
+                        self.push_undo(t!("clear steps"));
+                        self.samples_manager.set_region_steps(region.id, vec![false; n_steps]);
+                        ui.close_menu();
+                    }
+                });
+                ui.add_space(8.0);
+                let is_ons: Vec<bool> = (0..n_steps).map(|s| step_on(&region.steps, s)).collect();
+                draw_step_buttons(ui, &region.name, step_w, row_h, color, color_dim, &is_ons, per_beat, current_step, seq_playing,
+                    &mut |step| {
+                        self.push_undo_gesture(t!("step"));
+                        self.samples_manager.set_region_step(region.id, step, !is_ons[step]);
+                    },
+                    None,
+                    None,
+                );
+            });
+        }
     }
 
     pub fn draw_recording_tracks(
@@ -567,6 +1515,8 @@ impl AppState {
     ) {
         let n_rec = self.rec_tracks.read().len();
         if n_rec == 0 { return; }
+        let n_steps  = self.pattern_len();
+        let per_beat = self.seq_resolution.read().per_beat();
 
         let ctrl_w   = 120.0_f32;
         let knob_h   = 30.0_f32;
@@ -574,7 +1524,7 @@ impl AppState {
         let rec_dim  = egui::Color32::from_rgb(44, 14, 12);
 
         ui.add_space(4.0);
-        ui.label(egui::RichText::new("  🎙 Recording Tracks").size(20.0).color(egui::Color32::from_gray(70)));
+        ui.label(egui::RichText::new(t!("  🎙 Recording Tracks")).size(20.0).color(egui::Color32::from_gray(70)));
 
         let active_rec_track = *self.rec_active_track.read();
 
@@ -583,7 +1533,7 @@ impl AppState {
                 let tracks = self.rec_tracks.read();
                 let t = &tracks[rec_idx];
                 let dur = t.duration_secs().map(|s| format!("{:.2}s", s)).unwrap_or_else(|| "–".to_string());
-                (t.state.clone(), t.short_name(), t.asset.is_some(), t.steps, t.muted, dur, t.take_number)
+                (t.state.clone(), t.short_name(), t.asset.is_some(), (0..n_steps).map(|s| step_on(&t.steps, s)).collect::<Vec<_>>(), t.muted, dur, t.take_number)
             };
 
             let is_active = active_rec_track == Some(rec_idx);
@@ -634,39 +1584,47 @@ impl AppState {
                              else { egui::Color32::from_rgb(50, 220, 80) };
                     ui.painter().rect_filled(egui::Rect::from_min_size(mr.min, egui::vec2(fill, 3.0)), 1.0, mc);
                 }
+                draw_focus_ring(ui, lr, &lresp);
+                lresp.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button,
+                    t!("Recording track {}: {}", rec_idx + 1, short_name)));
                 let rct = self.rec_tracks.clone();
                 lresp.context_menu(|ui| {
                     ui.set_min_width(160.0);
                     ui.label(egui::RichText::new(&short_name).size(20.0).color(rec_base));
                     ui.separator();
-                    if ui.button(if muted { "🔊 Unmute" } else { "🔇 Mute" }).clicked() {
+                    if ui.button(if muted { t!("🔊 Unmute") } else { t!("🔇 Mute") }).clicked() {
                         if let Some(t) = rct.write().get_mut(rec_idx) { t.muted = !t.muted; }
                         ui.close_menu();
                     }
-                    if ui.button("🗑 Clear Steps").clicked() {
-                        if let Some(t) = rct.write().get_mut(rec_idx) { t.steps = [false; NUM_STEPS]; }
+                    if ui.button(t!("🗑 Clear Steps")).clicked() {
+                        if let Some(t) = rct.write().get_mut(rec_idx) { t.steps.fill(false); }
                         ui.close_menu();
                     }
                     ui.separator();
-                    if ui.button(egui::RichText::new("✕ Remove Track").color(egui::Color32::from_rgb(220,80,60))).clicked() {
+                    if let Some(t) = rct.write().get_mut(rec_idx) { adsr_menu(ui, &mut t.adsr, &mut t.adsr_enabled); }
+                    ui.separator();
+                    if ui.button(egui::RichText::new(t!("✕ Remove Track")).color(egui::Color32::from_rgb(220,80,60))).clicked() {
                         rct.write().remove(rec_idx);
                         ui.close_menu();
                     }
                 });
                 ui.add_space(8.0);
                 if has_asset {
-                    draw_step_buttons(ui, step_w, row_h, rec_base, rec_dim, &steps, current_step, seq_playing,
+                    draw_step_buttons(ui, &short_name, step_w, row_h, rec_base, rec_dim, &steps, per_beat, current_step, seq_playing,
                         &mut |step| {
-                            if let Some(t) = self.rec_tracks.write().get_mut(rec_idx) { t.steps[step] = !t.steps[step]; }
+                            self.push_undo_gesture(t!("step"));
+                            if let Some(t) = self.rec_tracks.write().get_mut(rec_idx) { let on = !step_on(&t.steps, step); set_step(&mut t.steps, step, on); }
                         },
+                        None,
+                        None,
                     );
                 } else {
-                    let total_w = step_w * NUM_STEPS as f32;
+                    let total_w = step_w * n_steps as f32;
                     let (ph, _) = ui.allocate_exact_size(egui::vec2(total_w, row_h), egui::Sense::hover());
                     ui.painter().rect_filled(ph, 2.0, egui::Color32::from_rgb(17, 11, 11));
                     ui.painter().text(ph.center(), egui::Align2::CENTER_CENTER,
-                        if is_active { "Recording…  stop to enable steps" }
-                        else         { "Select a device and press 🔴 Rec to record" },
+                        if is_active { t!("Recording…  stop to enable steps") }
+                        else         { t!("Select a device and press 🔴 Rec to record") },
                         egui::FontId::proportional(9.0), egui::Color32::from_gray(52));
                 }
             });
@@ -679,7 +1637,7 @@ impl AppState {
                 let devices = self.input_devices.read().clone();
                 let current_label = {
                     let tracks = self.rec_tracks.read();
-                    tracks.get(rec_idx).and_then(|t| t.device_label.clone()).unwrap_or_else(|| "Pick input…".to_string())
+                    tracks.get(rec_idx).and_then(|t| t.device_label.clone()).unwrap_or_else(|| t!("Pick input…").to_string())
                 };
                 let short_lbl = {
                     let s = current_label.split(": ").nth(1).unwrap_or(&current_label);
@@ -703,38 +1661,73 @@ impl AppState {
                             }
                         }
                         ui.separator();
-                        if ui.button(egui::RichText::new("↻ Refresh devices").size(20.0)).clicked() { self.refresh_input_devices(); }
+                        if ui.button(egui::RichText::new(t!("↻ Refresh devices")).size(20.0)).clicked() { self.refresh_input_devices(); }
                     });
 
                 ui.add_space(6.0);
 
                 if is_active {
-                    if ui.add(egui::Button::new(egui::RichText::new("⏹ Stop").size(20.0).color(egui::Color32::from_rgb(255, 120, 80)))).clicked() {
+                    if ui.add(egui::Button::new(egui::RichText::new(t!("⏹ Stop")).size(20.0).color(egui::Color32::from_rgb(255, 120, 80)))).clicked() {
                         self.stop_recording(rec_idx);
                     }
+                    let loop_state = self.loop_rec.read().clone();
+                    if let Some(l) = loop_state.filter(|l| l.track_idx == rec_idx) {
+                        let txt = if l.started { t!("🔁 {} bar(s) left", l.bars_left) } else { t!("🔁 waiting for bar…").to_string() };
+                        ui.label(egui::RichText::new(txt).size(20.0).color(egui::Color32::from_rgb(255, 160, 90)));
+                    }
                 } else {
                     let can_rec = { let tracks = self.rec_tracks.read(); tracks.get(rec_idx).and_then(|t| t.device_label.as_ref()).is_some() };
                     let already_busy = self.rec_manager.is_recording();
                     if ui.add_enabled(can_rec && !already_busy, egui::Button::new(
-                        egui::RichText::new("🔴 Rec").size(20.0).color(
+                        egui::RichText::new(t!("🔴 Rec")).size(20.0).color(
                             if can_rec && !already_busy { egui::Color32::from_rgb(255, 60, 60) } else { egui::Color32::from_gray(65) }
                         )
-                    )).on_hover_text(if already_busy { "Stop the current recording first" }
-                       else if !can_rec { "Select an input device first" }
-                       else { "Record from selected input" }).clicked() {
+                    )).on_hover_text(if already_busy { t!("Stop the current recording first") }
+                       else if !can_rec { t!("Select an input device first") }
+                       else { t!("Record from selected input") }).clicked() {
                         self.start_recording(rec_idx);
                     }
+
+                    // ── Loop recording: N bars in sync with the pattern ──
+                    let (mut bars, mut target) = {
+                        let tracks = self.rec_tracks.read();
+                        tracks.get(rec_idx).map(|t| (t.loop_bars, t.loop_target)).unwrap_or((1, LoopTarget::DrumTrack))
+                    };
+                    egui::ComboBox::from_id_source(egui::Id::new("rec_loop_bars").with(rec_idx))
+                        .selected_text(egui::RichText::new(t!("{} bar", bars)).size(20.0))
+                        .width(70.0)
+                        .show_ui(ui, |ui| {
+                            for n in [1u32, 2, 4, 8] {
+                                ui.selectable_value(&mut bars, n, t!("{} bar", n));
+                            }
+                        });
+                    let target_lbl = match target { LoopTarget::DrumTrack => t!("→ Track"), LoopTarget::ChopSet => t!("→ Chops") };
+                    if ui.add(egui::Button::new(egui::RichText::new(target_lbl).size(20.0).color(egui::Color32::from_gray(150))))
+                        .on_hover_text(t!("What the loop take becomes: a drum track, or a drum track chopped on every beat")).clicked() {
+                        target = match target { LoopTarget::DrumTrack => LoopTarget::ChopSet, LoopTarget::ChopSet => LoopTarget::DrumTrack };
+                    }
+                    if let Some(t) = self.rec_tracks.write().get_mut(rec_idx) {
+                        t.loop_bars   = bars;
+                        t.loop_target = target;
+                    }
+                    if ui.add_enabled(can_rec && !already_busy, egui::Button::new(
+                        egui::RichText::new(t!("🔁 Loop Rec")).size(20.0).color(
+                            if can_rec && !already_busy { egui::Color32::from_rgb(255, 140, 60) } else { egui::Color32::from_gray(65) }
+                        )
+                    )).on_hover_text(t!("Record exactly this many bars from the next bar line, then make a track from the take")).clicked() {
+                        self.start_loop_recording(rec_idx);
+                    }
                 }
 
                 if has_asset {
                     ui.add_space(4.0);
-                    if ui.add(egui::Button::new(egui::RichText::new("→ Drum Track").size(20.0).color(egui::Color32::from_rgb(80, 200, 130))))
-                        .on_hover_text("Promote this recording to a full drum track").clicked() {
+                    if ui.add(egui::Button::new(egui::RichText::new(t!("→ Drum Track")).size(20.0).color(egui::Color32::from_rgb(80, 200, 130))))
+                        .on_hover_text(t!("Promote this recording to a full drum track")).clicked() {
                         self.promote_rec_to_drum(rec_idx);
                         return;
                     }
                     ui.add_space(4.0);
-                    ui.label(egui::RichText::new(format!("take {} · {}", take_num - 1, dur_str)).size(20.0).color(egui::Color32::from_gray(75)));
+                    ui.label(egui::RichText::new(t!("take {} · {}", take_num - 1, dur_str)).size(20.0).color(egui::Color32::from_gray(75)));
                 }
             });
 
@@ -742,6 +1735,322 @@ impl AppState {
         }
     }
 
+    /// Always-on input buffer with a "Keep that!" button.
+    pub fn draw_retro_capture(&mut self, ui: &mut egui::Ui) {
+        let rc      = &self.retro_capture;
+        let running = rc.is_running();
+        let accent  = egui::Color32::from_rgb(240, 150, 70);
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(t!("⏺ Capture")).size(20.0).color(if running { accent } else { egui::Color32::from_gray(90) }))
+                .on_hover_text(t!("Keeps the last few seconds of an input so you can save what you just played"));
+
+            let devices = self.input_devices.read().clone();
+            let current = rc.device_label.read().clone();
+            let shown = current.as_deref()
+                .map(|l| l.split(": ").nth(1).unwrap_or(l).to_string())
+                .unwrap_or_else(|| t!("Pick input…").to_string());
+            let mut picked: Option<String> = None;
+            egui::ComboBox::from_id_source("retro_dev")
+                .selected_text(egui::RichText::new(&shown).size(20.0))
+                .width(160.0)
+                .show_ui(ui, |ui| {
+                    for dev in &devices {
+                        if ui.selectable_label(current.as_deref() == Some(dev.label.as_str()), egui::RichText::new(&dev.label).size(20.0)).clicked() {
+                            picked = Some(dev.label.clone());
+                        }
+                    }
+                    ui.separator();
+                    if ui.button(egui::RichText::new(t!("↻ Refresh devices")).size(20.0)).clicked() { self.refresh_input_devices(); }
+                });
+
+            let mut secs = *rc.seconds.read();
+            egui::ComboBox::from_id_source("retro_secs")
+                .selected_text(egui::RichText::new(format!("{}s", secs)).size(20.0))
+                .width(60.0)
+                .show_ui(ui, |ui| {
+                    for s in CAPTURE_LENGTHS {
+                        ui.selectable_value(&mut secs, s, format!("{}s", s));
+                    }
+                });
+            if secs != *rc.seconds.read() { rc.set_seconds(secs); }
+
+            if let Some(label) = picked {
+                *rc.device_label.write() = Some(label.clone());
+                if running { self.start_retro_capture(&label); }
+            }
+            let label = rc.device_label.read().clone();
+            if ui.add_enabled(label.is_some(), egui::SelectableLabel::new(running,
+                egui::RichText::new(if running { t!("● Listening") } else { t!("○ Off") }).size(20.0))).clicked() {
+                if running { self.stop_retro_capture(); }
+                else if let Some(l) = label { self.start_retro_capture(&l); }
+            }
+            if running {
+                ui.label(egui::RichText::new(format!("{:.0}s", rc.buffered_secs())).size(20.0).color(egui::Color32::from_gray(110)));
+                if ui.add(egui::Button::new(egui::RichText::new(t!("✋ Keep that!")).size(20.0).color(accent)))
+                    .on_hover_text(t!("Save the buffer as a new drum track")).clicked() {
+                    self.keep_retro_capture();
+                }
+            }
+        });
+    }
+
+    /// Panic / unmute toggle plus the safety limiter switch and its gain-reduction readout.
+    pub fn draw_panic_button(&mut self, ui: &mut egui::Ui) {
+        let muted = self.safety.muted.load(Ordering::Relaxed);
+        let (text, fill) = if muted {
+            (t!("🔇 Muted"), egui::Color32::from_rgb(170, 40, 40))
+        } else {
+            (t!("⚠ Panic"), egui::Color32::from_rgb(90, 30, 30))
+        };
+        let resp = ui.add(egui::Button::new(egui::RichText::new(text).strong().color(egui::Color32::WHITE)).fill(fill))
+            .on_hover_text(if muted { t!("Restore output") } else { t!("Silence everything now (Esc Esc)") });
+        if resp.clicked() { if muted { self.unmute(); } else { self.panic(); } }
+
+        let mut limit = self.safety.limiter.load(Ordering::Relaxed);
+        let gr = self.safety.take_reduction();
+        let label = if limit && gr < -0.1 { t!("Limit {:.1} dB", gr) } else { t!("Limit").to_string() };
+        let label = egui::RichText::new(label).color(
+            if gr < -6.0 { egui::Color32::from_rgb(240, 90, 60) }
+            else if gr < -0.1 { egui::Color32::from_rgb(230, 190, 90) }
+            else { ui.visuals().text_color() });
+        if ui.checkbox(&mut limit, label)
+            .on_hover_text(t!("Safety limiter at -1 dBFS on every output"))
+            .changed() {
+            self.safety.limiter.store(limit, Ordering::Relaxed);
+        }
+    }
+
+    /// Master gain on the whole mix, sample playback included (the soft clipper comes after it).
+    pub fn draw_master_volume(&mut self, ui: &mut egui::Ui) {
+        let mut vol = self.master_volume.load(Ordering::Relaxed);
+        ui.label("🔈");
+        if ui.add(egui::Slider::new(&mut vol, 0.0..=1.0)
+                .custom_formatter(|v, _| if v <= 0.0 { t!("-∞ dB").to_string() } else { t!("{:.1} dB", 20.0 * v.log10()) }))
+            .on_hover_text(t!("Master volume (sequencer, pads, drums and sample playback)"))
+            .changed() {
+            self.master_volume.store(vol, Ordering::Relaxed);
+        }
+    }
+
+    /// Mix snapshots: save the mix under a name, recall one (fading over the set time).
+    pub fn draw_mix_snapshots(&mut self, ui: &mut egui::Ui) {
+        let mut snaps = self.mix_snapshots.read().clone();
+        let (mut recall, mut delete, mut save) = (None, None, false);
+        ui.menu_button(t!("📸 Mix"), |ui| {
+            if snaps.list.is_empty() {
+                ui.label(egui::RichText::new(t!("No snapshots yet")).italics().color(egui::Color32::GRAY));
+            }
+            for (i, s) in snaps.list.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui.button(&s.name).on_hover_text(t!("Recall this mix")).clicked() { recall = Some(i); }
+                    if ui.small_button("🗑").clicked() { delete = Some(i); }
+                });
+            }
+            ui.separator();
+            ui.horizontal(|ui| {
+                let resp = ui.add(egui::TextEdit::singleline(&mut snaps.draft).hint_text(t!("Name")).desired_width(120.0));
+                let enter = resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if ui.button(t!("💾 Save mix")).on_hover_text(t!("Gains, trims, mutes, solos, master and crossfader")).clicked() || enter {
+                    save = true;
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(t!("Fade"));
+                ui.add(egui::DragValue::new(&mut snaps.fade_secs).speed(0.05).clamp_range(0.0..=30.0).suffix(" s"))
+                    .on_hover_text(t!("Time a recall takes to reach the snapshot (0 = at once)"));
+            });
+        });
+        if save {
+            let name = std::mem::take(&mut snaps.draft);
+            *self.mix_snapshots.write() = snaps;
+            self.save_mix_snapshot(&name);
+        } else {
+            *self.mix_snapshots.write() = snaps;
+        }
+        if let Some(i) = delete { self.delete_mix_snapshot(i); }
+        if let Some(i) = recall { self.recall_mix_snapshot(i); }
+    }
+
+    /// Varispeed for the main playhead, with the position in source time beside it.
+    pub fn draw_varispeed(&mut self, ui: &mut egui::Ui) {
+        let mut speed = self.playback_speed.load(Ordering::Relaxed);
+        ui.label(egui::RichText::new(t!("Varispeed")).small().color(egui::Color32::from_gray(100)));
+        if ui.add(egui::Slider::new(&mut speed, 0.25..=4.0).logarithmic(true)
+                .custom_formatter(|v, _| format!("×{:.2}", v)))
+            .on_hover_text(t!("Playback rate of the main sample (pitch follows)"))
+            .changed() {
+            self.playback_speed.store(speed, Ordering::Relaxed);
+        }
+        if speed != 1.0 && ui.small_button("1×").clicked() {
+            self.playback_speed.store(1.0, Ordering::Relaxed);
+        }
+        let asset = self.playback_asset.read().clone().or_else(|| self.focused_asset());
+        if let Some(a) = asset {
+            let unit = self.marker_editor.read().unit;
+            let bpm  = self.events.bpm();
+            let pos  = self.playback_position.load(Ordering::Relaxed);
+            ui.label(egui::RichText::new(format!("⏱ {} / {}",
+                    unit.format(pos, a.frames, a.sample_rate, bpm),
+                    unit.format(1.0, a.frames, a.sample_rate, bpm)))
+                .monospace().small())
+                .on_hover_text(t!("Position in the file ({}), the same at any varispeed", unit.label().to_lowercase()));
+        }
+    }
+
+    /// Seek slider for the main playhead. Dragging it with nothing playing scrubs.
+    pub fn draw_seek_slider(&mut self, ui: &mut egui::Ui) {
+        let Some(asset) = self.playback_asset.read().clone().or_else(|| self.focused_asset()) else { return; };
+        let mut pos = self.playback_position.load(Ordering::Relaxed);
+        let response = ui.add(egui::Slider::new(&mut pos, 0.0..=1.0).show_value(false))
+            .on_hover_text(t!("Seek — drag while stopped to hear where you are"));
+        if response.changed() {
+            self.seek_to(pos);
+            if response.dragged() { self.scrub_grain(&asset, pos); }
+        }
+        if response.drag_stopped() { self.end_scrub(); }
+    }
+
+    /// Transport readout: bar:beat:step, loops round the pattern and time since ▶.
+    pub fn draw_transport_clock(&self, ui: &mut egui::Ui) {
+        let playing = self.events.playing();
+        let (pos, loops, time) = self.clock_reading().map_or_else(
+            || ("001:1:1".to_string(), "⟳ 0".to_string(), "0:00.000".to_string()),
+            |r| r.parts());
+        let col = if playing { egui::Color32::from_rgb(120, 220, 255) } else { egui::Color32::from_gray(140) };
+        ui.label(egui::RichText::new(pos).monospace().size(22.0).color(col))
+            .on_hover_text(t!("Bar : beat : step since ▶ (beats in the metronome's time signature)"));
+        ui.label(egui::RichText::new(loops).monospace().size(16.0).color(col))
+            .on_hover_text(t!("Times round the pattern"));
+        ui.label(egui::RichText::new(time).monospace().size(16.0).color(col))
+            .on_hover_text(t!("Time since ▶, on the audio output's clock"));
+    }
+
+    /// Punch-in row: in/out markers, the input to record, and the punch button.
+    pub fn draw_punch_controls(&mut self, ui: &mut egui::Ui, drum_idx: usize, n_marks: usize) {
+        let running = self.punch.read().as_ref().map(|p| p.track_idx);
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(t!("Punch-in")).small().color(egui::Color32::from_gray(100)));
+            for (label, slot, id) in [("in", &self.selected_from_marker, "punch_from"), ("out", &self.selected_to_marker, "punch_to")] {
+                let mut pick = *slot.read();
+                egui::ComboBox::from_id_source(id)
+                    .selected_text(pick.map_or(format!("{} —", label), |m| format!("{} {}", label, m + 1)))
+                    .width(70.0)
+                    .show_ui(ui, |ui| {
+                        for m in 0..n_marks { ui.selectable_value(&mut pick, Some(m), t!("Marker {}", m + 1)); }
+                    });
+                if pick.is_some_and(|m| m >= n_marks) { pick = None; }
+                *slot.write() = pick;
+            }
+            let mut device = self.punch_device.read().clone();
+            egui::ComboBox::from_id_source("punch_input")
+                .selected_text(device.as_deref().map(|d| d.split(": ").nth(1).unwrap_or(d)).unwrap_or(t!("Input…")))
+                .width(140.0)
+                .show_ui(ui, |ui| {
+                    if self.input_devices.read().is_empty() { self.refresh_input_devices(); }
+                    for d in self.input_devices.read().iter() {
+                        ui.selectable_value(&mut device, Some(d.label.clone()), &d.label);
+                    }
+                });
+            *self.punch_device.write() = device;
+            match running {
+                Some(_) => {
+                    if ui.button(egui::RichText::new(t!("■ Cancel punch")).color(egui::Color32::from_rgb(240, 90, 60))).clicked() {
+                        self.cancel_punch_in();
+                    }
+                }
+                None => {
+                    if ui.button(t!("⏺ Punch in")).on_hover_text(t!("Play from a bar before the in marker and re-record up to the out marker")).clicked() {
+                        self.start_punch_in(drum_idx);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Tempo from a span: two markers and the bars between them give the BPM.
+    pub fn draw_span_tempo(&mut self, ui: &mut egui::Ui, drum_idx: usize, n_marks: usize) {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(t!("Tempo")).small().color(egui::Color32::from_gray(100)));
+            {
+                let mut guard = self.span_pick.write();
+                let pick = &mut *guard;
+                for (slot, id) in [(&mut pick.from, "span_from"), (&mut pick.to, "span_to")] {
+                    egui::ComboBox::from_id_source(id)
+                        .selected_text(slot.map_or("—".to_string(), |m| t!("Marker {}", m + 1)))
+                        .width(80.0)
+                        .show_ui(ui, |ui| {
+                            for m in 0..n_marks { ui.selectable_value(slot, Some(m), t!("Marker {}", m + 1)); }
+                        });
+                    if slot.is_some_and(|m| m >= n_marks) { *slot = None; }
+                }
+                ui.label(t!("is"));
+                ui.add(egui::DragValue::new(&mut pick.bars).speed(0.25).clamp_range(0.25..=64.0).suffix(t!(" bars")));
+            }
+            let bpm = self.span_bpm(drum_idx);
+            let meter = self.metronome.lock().settings.meter.label();
+            ui.label(egui::RichText::new(bpm.map_or("—".to_string(), |b| t!("≈ {:.2} BPM in {}", b, meter)))
+                .small().monospace());
+            if ui.add_enabled(bpm.is_some(), egui::Button::new(t!("⏱ Set BPM")))
+                .on_hover_text(t!("Set the sequencer tempo and draw a bar grid from the first marker")).clicked() {
+                self.set_tempo_from_span(drum_idx);
+            }
+            if self.bar_grid.read().is_some() && ui.small_button(t!("Hide grid")).clicked() {
+                *self.bar_grid.write() = None;
+            }
+        });
+    }
+
+    /// LAN sender toggle: port while stopped, listener count while running.
+    pub fn draw_net_send(&mut self, ui: &mut egui::Ui) {
+        let running = self.net_sender.is_running();
+        if !running {
+            let mut port = *self.net_sender.port.lock();
+            if ui.add(egui::DragValue::new(&mut port).clamp_range(1024..=65535).prefix(t!("port ")))
+                .on_hover_text(t!("TCP port listeners connect to")).changed() {
+                *self.net_sender.port.lock() = port;
+            }
+        }
+        let clients = self.net_sender.clients.load(Ordering::Relaxed);
+        let label = if running { t!("📡 Sending ({})", clients) } else { t!("📡 Send").to_string() };
+        if ui.selectable_label(running, label)
+            .on_hover_text(t!("Stream the master output over TCP: 12-byte RABS header, then f32 LE frames"))
+            .clicked() {
+            self.toggle_net_send();
+        }
+    }
+
+    /// Output backend choice; JACK adds client name and transport sync.
+    pub fn draw_backend_picker(&mut self, ui: &mut egui::Ui) {
+        let current = self.audio_backend.read().kind();
+        let mut picked: Option<BackendKind> = None;
+        egui::ComboBox::from_id_source("audio_backend")
+            .selected_text(format!("🔊 {}", current.label()))
+            .show_ui(ui, |ui| {
+                for kind in BackendKind::ALL {
+                    let resp = ui.add_enabled(kind.compiled_in(), egui::SelectableLabel::new(kind == current, kind.label()))
+                        .on_disabled_hover_text(t!("Not compiled in (build with --features jack)"));
+                    if resp.clicked() && kind != current { picked = Some(kind); }
+                }
+            });
+        let open = self.audio_settings_open.load(Ordering::Relaxed);
+        if ui.selectable_label(open, "⚙").on_hover_text(t!("Output device, sample rate, buffer size and MIDI input")).clicked() {
+            self.audio_settings_open.store(!open, Ordering::Relaxed);
+        }
+        if current == BackendKind::Jack {
+            let mut name = self.jack_client_name.read().clone();
+            let resp = ui.add(egui::TextEdit::singleline(&mut name).desired_width(80.0))
+                .on_hover_text(t!("JACK client name (Enter to reconnect)"));
+            if resp.changed() { *self.jack_client_name.write() = name; }
+            if resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) { picked = Some(BackendKind::Jack); }
+            let mut sync = self.transport_sync.load(Ordering::Relaxed);
+            if ui.checkbox(&mut sync, t!("⇆ Transport")).on_hover_text(t!("Start, stop and follow tempo with the JACK transport")).changed() {
+                self.transport_sync.store(sync, Ordering::Relaxed);
+            }
+        }
+        if let Some(kind) = picked { self.set_audio_backend(kind); }
+    }
+
     pub fn draw_piano_roll(&mut self, ctx: &egui::Context) {
         if !*self.piano_roll_open.read() { return; }
         let focus = self.waveform_focus.read().clone();
@@ -759,7 +2068,7 @@ impl AppState {
         let marks = self.samples_manager.get_marks_for_sample(&sample_uuid);
 
         let mut window_open = true;
-        egui::Window::new(format!("🎹 Piano Roll — {}", file_name))
+        egui::Window::new(t!("🎹 Piano Roll — {}", file_name))
             .id(egui::Id::new("piano_roll_window"))
             .default_size([820.0, 400.0])
             .min_size([500.0, 260.0])
@@ -768,21 +2077,22 @@ impl AppState {
             .open(&mut window_open)
             .show(ctx, |ui| {
                 ui.horizontal(|ui| {
-                    let playing = self.seq_playing.load(Ordering::Relaxed);
-                    let (lbl, col) = if playing { ("⏹ Stop", egui::Color32::from_rgb(220,80,60)) } else { ("▶ Play Pattern", egui::Color32::from_rgb(60,200,100)) };
+                    let playing = self.events.playing();
+                    let (lbl, col) = if playing { (t!("⏹ Stop"), egui::Color32::from_rgb(220,80,60)) } else { (t!("▶ Play Pattern"), egui::Color32::from_rgb(60,200,100)) };
                     if ui.add(egui::Button::new(egui::RichText::new(lbl).color(col))).clicked() {
-                        if playing { self.stop_sequencer(); } else { self.start_sequencer(); }
+                        self.send(AppCommand::TogglePlay);
                     }
-                    let mut bpm = self.seq_bpm.load(Ordering::Relaxed);
-                    ui.label("BPM");
-                    if ui.add(egui::DragValue::new(&mut bpm).speed(0.5).clamp_range(40.0..=300.0).fixed_decimals(0)).changed() { self.seq_bpm.store(bpm, Ordering::Relaxed); }
+                    let mut bpm = self.events.bpm();
+                    ui.label(t!("BPM"));
+                    if ui.add(egui::DragValue::new(&mut bpm).speed(0.5).clamp_range(40.0..=300.0).fixed_decimals(0)).changed() { self.send(AppCommand::SetBpm { bpm }); }
                     ui.separator();
-                    if ui.button(egui::RichText::new("Clear All").color(egui::Color32::from_rgb(200,80,80))).clicked() {
+                    if ui.button(egui::RichText::new(t!("Clear All")).color(egui::Color32::from_rgb(200,80,80))).clicked() {
+                        self.push_undo(t!("clear steps"));
                         let mut g = self.seq_grid.write();
                         for s in g.iter_mut() { s.clear(); }
                     }
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        ui.label(egui::RichText::new("Click cell to toggle  ·  Rows = chops").size(20.0).color(egui::Color32::from_gray(95)));
+                        ui.label(egui::RichText::new(t!("Click cell to toggle  ·  Rows = chops")).size(20.0).color(egui::Color32::from_gray(95)));
                     });
                 });
                 ui.separator();
@@ -790,7 +2100,7 @@ impl AppState {
 
                 if marks.is_empty() {
                     ui.centered_and_justified(|ui| {
-                        ui.label(egui::RichText::new("No chops yet!\nPlay your sample and press M to drop markers.").size(13.0).color(egui::Color32::from_gray(140)));
+                        ui.label(egui::RichText::new(t!("No chops yet!\nPlay your sample and press M to drop markers.")).size(13.0).color(egui::Color32::from_gray(140)));
                     });
                     return;
                 }
@@ -798,7 +2108,10 @@ impl AppState {
                 let pad_label_w = 165.0;
                 let avail = ui.available_size();
                 let grid_w = (avail.x - pad_label_w - 14.0).max(200.0);
-                let cell_w = grid_w / NUM_STEPS as f32;
+                let res    = *self.seq_resolution.read();
+                let n_steps  = self.pattern_len();
+                let per_beat = res.per_beat();
+                let cell_w = grid_w / n_steps as f32;
                 let cell_h = 34.0;
                 let n_rows = marks.len();
                 let header_h = 18.0;
@@ -811,14 +2124,14 @@ impl AppState {
                     painter.rect_filled(outer_rect, 0.0, egui::Color32::from_rgb(13, 13, 19));
                     let grid_origin = egui::pos2(outer_rect.min.x + pad_label_w, outer_rect.min.y + header_h);
 
-                    for step in 0..NUM_STEPS {
+                    for step in 0..n_steps {
                         let x = grid_origin.x + step as f32 * cell_w;
                         let hr = egui::Rect::from_min_size(egui::pos2(x, outer_rect.min.y), egui::vec2(cell_w-1.0, header_h-1.0));
-                        let grp = step / 4;
-                        painter.rect_filled(hr, 0.0, if grp%2==0{egui::Color32::from_rgb(21,21,31)}else{egui::Color32::from_rgb(17,17,27)});
-                        if step%4==0 { painter.text(hr.center(), egui::Align2::CENTER_CENTER, format!("{}", step/4+1), egui::FontId::proportional(10.0), egui::Color32::from_gray(110)); }
+                        let grp = step / per_beat;
+                        painter.rect_filled(hr, 0.0, if grp.is_multiple_of(2){egui::Color32::from_rgb(21,21,31)}else{egui::Color32::from_rgb(17,17,27)});
+                        if step.is_multiple_of(per_beat) { painter.text(hr.center(), egui::Align2::CENTER_CENTER, format!("{}", step/per_beat+1), egui::FontId::proportional(10.0), egui::Color32::from_gray(110)); }
                         else { painter.circle_filled(hr.center(), 1.5, egui::Color32::from_gray(50)); }
-                        if self.seq_playing.load(Ordering::Relaxed) && current_step == step {
+                        if self.events.playing() && current_step == step {
                             painter.rect_filled(hr, 0.0, egui::Color32::from_rgba_unmultiplied(255,220,80,38));
                         }
                     }
@@ -831,22 +2144,22 @@ impl AppState {
                         let lr = egui::Rect::from_min_size(egui::pos2(outer_rect.min.x, y), egui::vec2(pad_label_w - 3.0, cell_h - 1.0));
                         painter.rect_filled(lr, 0.0, if pad_idx%2==0{egui::Color32::from_rgb(19,19,27)}else{egui::Color32::from_rgb(16,16,24)});
                         painter.rect_filled(egui::Rect::from_min_size(lr.min+egui::vec2(5.0,9.0), egui::vec2(4.0, cell_h-18.0)), 2.0, color);
-                        painter.text(egui::pos2(lr.min.x+15.0, lr.center().y-6.0), egui::Align2::LEFT_CENTER, format!("Chop #{}", mark.id), egui::FontId::proportional(12.0), color);
+                        painter.text(egui::pos2(lr.min.x+15.0, lr.center().y-6.0), egui::Align2::LEFT_CENTER, t!("Chop #{}", mark.id), egui::FontId::proportional(12.0), color);
                         painter.text(egui::pos2(lr.min.x+15.0, lr.center().y+7.0), egui::Align2::LEFT_CENTER, format!("{:.3}s", time_at), egui::FontId::proportional(9.0), egui::Color32::from_gray(105));
                         painter.hline(outer_rect.x_range(), y + cell_h - 0.5, egui::Stroke::new(0.5, egui::Color32::from_gray(26)));
 
-                        for step in 0..NUM_STEPS {
+                        for step in 0..n_steps {
                             let x = grid_origin.x + step as f32 * cell_w;
                             let cell = egui::Rect::from_min_size(egui::pos2(x, y), egui::vec2(cell_w-1.0, cell_h-1.0));
-                            let grp = step / 4;
-                            painter.rect_filled(cell, 0.0, if grp%2==0{egui::Color32::from_rgb(19,19,27)}else{egui::Color32::from_rgb(16,16,24)});
+                            let grp = step / per_beat;
+                            painter.rect_filled(cell, 0.0, if grp.is_multiple_of(2){egui::Color32::from_rgb(19,19,27)}else{egui::Color32::from_rgb(16,16,24)});
 
                             let tracks = self.drum_tracks.read();
                             let track = tracks.get(idx);
                             let is_on = if Some(idx) == main_idx {
-                                self.seq_grid.read()[step].contains(&pad_idx)
+                                self.seq_grid.read().get(step).is_some_and(|g| g.contains(&pad_idx))
                             } else {
-                                track.and_then(|t| t.chop_steps.get(pad_idx)).map(|s| s[step]).unwrap_or(false)
+                                track.and_then(|t| t.chop_steps.get(pad_idx)).is_some_and(|s| step_on(s, step))
                             };
                             drop(tracks);
 
@@ -856,20 +2169,21 @@ impl AppState {
                             } else {
                                 painter.rect_filled(cell.shrink(3.0), 2.0, color_dim);
                             }
-                            if self.seq_playing.load(Ordering::Relaxed) && current_step == step {
+                            if self.events.playing() && current_step == step {
                                 painter.rect_filled(cell, 0.0, egui::Color32::from_rgba_unmultiplied(255,220,80,30));
                             }
-                            let lc = if step%4==0{egui::Color32::from_gray(48)}else{egui::Color32::from_gray(26)};
+                            let lc = if step.is_multiple_of(per_beat){egui::Color32::from_gray(48)}else{egui::Color32::from_gray(26)};
                             painter.vline(x, egui::Rangef::new(y, y+cell_h), egui::Stroke::new(0.5, lc));
                         }
                     }
 
                     let grid_rect = egui::Rect::from_min_size(grid_origin, egui::vec2(grid_w, n_rows as f32 * cell_h));
                     let gresp = ui.interact(grid_rect, egui::Id::new("pr_grid"), egui::Sense::click_and_drag());
+                    gresp.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Other, t!("Piano roll grid: rows are chops, columns are steps")));
                     if gresp.clicked() || gresp.dragged() {
                         if let Some(pos) = ui.input(|i| i.pointer.interact_pos()) {
                             if grid_rect.contains(pos) {
-                                let step = (((pos.x - grid_origin.x) / cell_w) as usize).min(NUM_STEPS - 1);
+                                let step = (((pos.x - grid_origin.x) / cell_w) as usize).min(n_steps - 1);
                                 let row  = (((pos.y - grid_origin.y) / cell_h) as usize).min(n_rows - 1);
                                 if Some(idx) == main_idx {
                                     let mut grid = self.seq_grid.write();
@@ -881,7 +2195,8 @@ impl AppState {
                                     let mut tracks = self.drum_tracks.write();
                                     if let Some(t) = tracks.get_mut(idx) {
                                         if let Some(row) = t.chop_steps.get_mut(row) {
-                                            row[step] = !row[step];
+                                            let on = !step_on(row, step);
+                                            set_step(row, step, on);
                                         }
                                     }
                                 }
@@ -892,4 +2207,255 @@ impl AppState {
             });
         if !window_open { *self.piano_roll_open.write() = false; }
     }
-}
\ No newline at end of file
+
+    pub fn draw_marker_editor(&mut self, ctx: &egui::Context) {
+        if !self.marker_editor.read().open { return; }
+        let focus = self.waveform_focus.read().clone();
+        let WaveformFocus::DrumTrack(idx) = focus else { return; };
+        let (file_name, frames, sample_rate, sample_uuid) = {
+            let tracks = self.drum_tracks.read();
+            let Some(track) = tracks.get(idx) else { return; };
+            (
+                track.asset.file_name.clone(),
+                track.asset.frames,
+                track.asset.sample_rate,
+                track.sample_uuid,
+            )
+        };
+        let bpm   = self.events.bpm();
+        let marks = self.samples_manager.get_marks_for_sample(&sample_uuid);
+
+        // Deferred actions — applied after the window closure releases the editor lock
+        let mut to_move:   Option<(usize, f32)> = None;
+        let mut to_delete: Option<usize>        = None;
+        let mut to_insert: Option<f32>          = None;
+        let mut parse_err: Option<String>       = None;
+
+        let mut window_open = true;
+        egui::Window::new(t!("📍 Markers — {}", file_name))
+            .id(egui::Id::new("marker_editor_window"))
+            .default_size([360.0, 320.0])
+            .resizable(true)
+            .collapsible(false)
+            .open(&mut window_open)
+            .show(ctx, |ui| {
+                let mut editor = self.marker_editor.write();
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(t!("Unit")).small().color(egui::Color32::from_gray(120)));
+                    for unit in MarkerTimeUnit::ALL {
+                        if ui.selectable_label(editor.unit == unit, unit.label()).clicked() {
+                            editor.unit = unit;
+                            editor.drafts.clear();
+                        }
+                    }
+                });
+                let unit = editor.unit;
+                if unit == MarkerTimeUnit::BarsBeats {
+                    ui.label(egui::RichText::new(t!("4/4 at {:.0} BPM  ·  bar:beat, both from 1", bpm))
+                        .small().color(egui::Color32::from_gray(95)));
+                }
+                ui.separator();
+
+                if marks.is_empty() {
+                    ui.label(egui::RichText::new(t!("No markers yet")).small().color(egui::Color32::from_gray(100)));
+                }
+                let focus_mark = editor.focus_mark.take();
+                egui::ScrollArea::vertical().max_height(220.0).show(ui, |ui| {
+                    for (chop_idx, mark) in marks.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.label(egui::RichText::new(format!("{:>2}", chop_idx + 1))
+                                .monospace().color(pad_color(chop_idx)));
+                            let current = unit.format(mark.position, frames, sample_rate, bpm);
+                            let text_id = egui::Id::new(("marker_pos", mark.id));
+                            let draft = editor.drafts.entry(mark.id).or_default();
+                            if !ui.memory(|m| m.has_focus(text_id)) {
+                                *draft = current.clone();
+                            }
+                            let resp = ui.add(egui::TextEdit::singleline(draft).id(text_id).desired_width(120.0));
+                            if focus_mark == Some(mark.id) {
+                                resp.request_focus();
+                                resp.scroll_to_me(Some(egui::Align::Center));
+                            }
+                            // Enter or clicking away commits the typed value
+                            if resp.lost_focus() && *draft != current {
+                                match unit.parse(draft, frames, sample_rate, bpm) {
+                                    Some(p) => to_move = Some((mark.id, p)),
+                                    None    => parse_err = Some(draft.clone()),
+                                }
+                            }
+                            if ui.add(egui::Button::new(
+                                egui::RichText::new("🗑").small().color(egui::Color32::from_rgb(200, 80, 80))
+                            )).on_hover_text(t!("Delete marker")).clicked() {
+                                to_delete = Some(chop_idx);
+                            }
+                        });
+                    }
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(t!("New")).small().color(egui::Color32::from_gray(120)));
+                    let resp = ui.add(egui::TextEdit::singleline(&mut editor.new_pos)
+                        .hint_text(unit.label())
+                        .desired_width(120.0));
+                    let enter = resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    if enter || ui.button(t!("Insert")).clicked() {
+                        match unit.parse(&editor.new_pos, frames, sample_rate, bpm) {
+                            Some(p) => { to_insert = Some(p); editor.new_pos.clear(); }
+                            None    => parse_err = Some(editor.new_pos.clone()),
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    if ui.button(t!("➕ At playhead")).clicked() {
+                        to_insert = Some(self.playback_position.load(Ordering::Relaxed));
+                    }
+                    let cursor = *self.waveform_cursor.read();
+                    if ui.add_enabled(cursor.is_some(), egui::Button::new(t!("➕ At cursor"))).clicked() {
+                        to_insert = cursor;
+                    }
+                });
+            });
+
+        if let Some((id, pos)) = to_move {
+            self.push_undo(t!("marker move"));
+            self.samples_manager.update_mark_position_by_id(id, pos);
+            *self.status.write() = t!("✓ Marker moved to {}", self.marker_editor.read().unit.format(pos, frames, sample_rate, bpm));
+        }
+        if let Some(pos) = to_insert {
+            self.insert_marker_at(pos);
+        }
+        if let Some(c_idx) = to_delete {
+            self.push_undo(t!("chop delete"));
+            self.remove_chop(idx, c_idx);
+            *self.status.write() = t!("Chop {} removed", c_idx + 1);
+        }
+        if let Some(text) = parse_err {
+            *self.status.write() = t!("✗ Invalid marker position: \"{}\"", text);
+        }
+        if !window_open { self.marker_editor.write().open = false; }
+    }
+
+    /// Row of macro knobs under the sequencer header.
+    pub fn draw_macro_strip(&mut self, ui: &mut egui::Ui) {
+        let knobs: Vec<(String, f32, usize)> = self.macros.read().iter()
+            .map(|m| (m.name.clone(), m.value, m.mappings.len()))
+            .collect();
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(t!("MACROS")).size(20.0).strong().color(egui::Color32::from_gray(100)));
+            ui.separator();
+            for (i, (name, value, n_maps)) in knobs.iter().enumerate() {
+                let (kr, _) = ui.allocate_exact_size(egui::vec2(64.0, 46.0), egui::Sense::hover());
+                let painter = ui.painter().clone();
+                let mut v = *value;
+                let color = if *n_maps > 0 { pad_color(i + 8) } else { egui::Color32::from_gray(70) };
+                let center = egui::pos2(kr.center().x, kr.top() + 16.0);
+                if draw_knob(&painter, ui, center, 14.0, &mut v, color, name, egui::Id::new("macro_knob").with(i)) {
+                    self.set_macro(i, v);
+                }
+            }
+            ui.separator();
+            self.draw_crossfader(ui);
+            ui.separator();
+            self.draw_stutter(ui);
+            ui.separator();
+            let open = self.macros_open.load(Ordering::Relaxed);
+            if ui.add(egui::Button::new(egui::RichText::new(t!("⚙ Assign")).size(20.0).color(egui::Color32::from_gray(140))))
+                .on_hover_text(t!("Map each macro to several parameters with ranges")).clicked() {
+                self.macros_open.store(!open, Ordering::Relaxed);
+            }
+        });
+    }
+
+    /// Chop bus ⇄ drum bus. Double-click centres it.
+    fn draw_crossfader(&mut self, ui: &mut egui::Ui) {
+        let mut x = self.crossfader.load(Ordering::Relaxed);
+        ui.label(egui::RichText::new(t!("CHOPS")).color(egui::Color32::from_gray(140)));
+        let resp = ui.add(egui::Slider::new(&mut x, 0.0..=1.0).show_value(false))
+            .on_hover_text(t!("Crossfader: [ / ] nudge, \\ or double-click to centre. Map it from a macro too"));
+        if resp.double_clicked() { x = 0.5; }
+        if resp.changed() || resp.double_clicked() { self.crossfader.store(x, Ordering::Relaxed); }
+        ui.label(egui::RichText::new(t!("DRUMS")).color(egui::Color32::from_gray(140)));
+    }
+
+    /// Stutter looper: hold the button (or B) to repeat the last 1/4, 1/2 or 1 bar.
+    fn draw_stutter(&mut self, ui: &mut egui::Ui) {
+        let bars = self.stutter.bars.load(Ordering::Relaxed);
+        for len in STUTTER_LENGTHS {
+            let label = if len >= 1.0 { t!("1 bar").to_string() } else { format!("1/{}", (1.0 / len).round()) };
+            if ui.selectable_label(bars == len, label).clicked() {
+                self.stutter.bars.store(len, Ordering::Relaxed);
+            }
+        }
+        let was_held = self.stutter.held.load(Ordering::Relaxed);
+        let fill = if was_held { egui::Color32::from_rgb(200, 120, 40) } else { egui::Color32::from_rgb(50, 40, 30) };
+        let resp = ui.add(egui::Button::new(egui::RichText::new(t!("⟲ Stutter")).strong().color(egui::Color32::WHITE))
+                .fill(fill).sense(egui::Sense::drag()))
+            .on_hover_text(t!("Hold (or hold B) to loop the last stretch of the master output; let go to drop back in"));
+        let key  = !ui.ctx().wants_keyboard_input() && ui.input(|i| i.key_down(egui::Key::B) && i.modifiers.is_none());
+        let held = resp.is_pointer_button_down_on() || key;
+        if held != was_held { self.stutter.held.store(held, Ordering::Relaxed); }
+    }
+
+    pub fn draw_macro_window(&mut self, ctx: &egui::Context) {
+        if !self.macros_open.load(Ordering::Relaxed) { return; }
+        let mut window_open = true;
+        let mut changed: Option<usize> = None;
+        egui::Window::new(t!("🎛 Macro Assignments"))
+            .id(egui::Id::new("macro_window"))
+            .default_size([460.0, 360.0])
+            .resizable(true)
+            .collapsible(false)
+            .open(&mut window_open)
+            .show(ctx, |ui| {
+                let mut macros = self.macros.write();
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for (i, m) in macros.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.label(egui::RichText::new(format!("{}", i + 1)).strong().color(pad_color(i + 8)));
+                            ui.add(egui::TextEdit::singleline(&mut m.name).desired_width(110.0));
+                            if ui.add(egui::Slider::new(&mut m.value, 0.0..=1.0).show_value(false)).changed() {
+                                changed = Some(i);
+                            }
+                        });
+                        let mut remove: Option<usize> = None;
+                        for (j, mp) in m.mappings.iter_mut().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.add_space(18.0);
+                                egui::ComboBox::from_id_source(egui::Id::new("macro_dest").with((i, j)))
+                                    .selected_text(mp.dest.label())
+                                    .width(150.0)
+                                    .show_ui(ui, |ui| {
+                                        for d in MacroDest::ALL {
+                                            if ui.selectable_label(mp.dest == d, d.label()).clicked() {
+                                                *mp = MacroMapping::new(d);
+                                            }
+                                        }
+                                    });
+                                let (lo, hi) = mp.dest.range();
+                                let speed = (hi - lo) / 200.0;
+                                ui.label(t!("from"));
+                                if ui.add(egui::DragValue::new(&mut mp.min).speed(speed).clamp_range(lo..=hi)).changed() { changed = Some(i); }
+                                ui.label(t!("to"));
+                                if ui.add(egui::DragValue::new(&mut mp.max).speed(speed).clamp_range(lo..=hi)).changed() { changed = Some(i); }
+                                if ui.small_button("✕").clicked() { remove = Some(j); }
+                            });
+                        }
+                        if let Some(j) = remove { m.mappings.remove(j); }
+                        ui.horizontal(|ui| {
+                            ui.add_space(18.0);
+                            if ui.small_button(t!("＋ Destination")).clicked() {
+                                m.mappings.push(MacroMapping::new(MacroDest::MasterVolume));
+                            }
+                        });
+                        ui.separator();
+                    }
+                });
+            });
+        if let Some(i) = changed {
+            let v = self.macros.read().get(i).map(|m| m.value).unwrap_or(0.0);
+            self.set_macro(i, v);
+        }
+        if !window_open { self.macros_open.store(false, Ordering::Relaxed); }
+    }
+}
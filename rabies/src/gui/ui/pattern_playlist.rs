@@ -3,6 +3,7 @@ use eframe::egui;
 use std::sync::atomic::Ordering;
 use crate::gui::AppState;
 use crate::audio::WaveformAnalysis;
+use crate::tempo_track::{bpm_at, TempoEvent, TEMPO_RANGE};
 
 // ─── Layout constants ────────────────────────────────────────────────────────
 const ROW_H:      f32 = 32.0;
@@ -92,15 +93,15 @@ impl AppState {
 
         frame.show(ui, |ui| {
             ui.horizontal(|ui| {
-                ui.label(egui::RichText::new("SONG EDITOR")
+                ui.label(egui::RichText::new(t!("SONG EDITOR"))
                     .small().strong().color(egui::Color32::from_rgb(100, 149, 237)));
                 ui.separator();
 
                 let song_playing = self.song_editor.is_playing.load(Ordering::Relaxed);
                 let (lbl, col) = if song_playing {
-                    ("⏹ Stop Song", egui::Color32::from_rgb(220, 80, 60))
+                    (t!("⏹ Stop Song"), egui::Color32::from_rgb(220, 80, 60))
                 } else {
-                    ("▶ Play Song", egui::Color32::from_rgb(60, 200, 100))
+                    (t!("▶ Play Song"), egui::Color32::from_rgb(60, 200, 100))
                 };
                 if ui.add(egui::Button::new(egui::RichText::new(lbl).small().color(col))).clicked() {
                     if song_playing { self.stop_song(); } else { self.start_song(); }
@@ -109,24 +110,28 @@ impl AppState {
                 ui.separator();
                 let bar   = self.song_editor.current_bar.load(Ordering::Relaxed);
                 let total = *self.song_editor.total_bars.read();
-                ui.label(egui::RichText::new(format!("Bar {}/{}", bar + 1, total))
+                ui.label(egui::RichText::new(t!("Bar {}/{} · {:.0} BPM", bar + 1, total, self.song_bpm()))
                     .small().color(egui::Color32::from_gray(140)));
 
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     if ui.add(egui::Button::new(
-                        egui::RichText::new("🗑 Clear").small().color(egui::Color32::from_rgb(180,60,60))
+                        egui::RichText::new(t!("🗑 Clear")).small().color(egui::Color32::from_rgb(180,60,60))
                     )).clicked() { self.song_editor.clear_arrangement(); }
                     ui.add_space(6.0);
                     if ui.add(egui::Button::new(
-                        egui::RichText::new("＋ Bar").small().color(egui::Color32::from_gray(140))
+                        egui::RichText::new(t!("＋ Bar")).small().color(egui::Color32::from_gray(140))
                     )).clicked() {
                         let cur = *self.song_editor.total_bars.read();
                         self.song_editor.ensure_bar_count(cur + 8);
                     }
                     ui.add_space(6.0);
                     if ui.add(egui::Button::new(
-                        egui::RichText::new("＋ Pattern").small().color(egui::Color32::from_rgb(80, 220, 140))
+                        egui::RichText::new(t!("＋ Pattern")).small().color(egui::Color32::from_rgb(80, 220, 140))
                     )).clicked() { self.create_new_pattern(); }
+                    ui.add_space(6.0);
+                    if ui.add(egui::Button::new(egui::RichText::new(t!("⤓ Bounce song")).small()))
+                        .on_hover_text(t!("Render the arrangement to a WAV, tempo changes included"))
+                        .clicked() { self.bounce_song(); }
                 });
             });
 
@@ -170,6 +175,8 @@ impl AppState {
                         p.vline(x, egui::Rangef::new(outer.min.y, outer.min.y + HEADER_H),
                             egui::Stroke::new(if bar % 4 == 0 { 0.8 } else { 0.3 }, egui::Color32::from_gray(45)));
                     }
+                    let ruler = egui::Rect::from_min_size(egui::pos2(grid_orig.x, outer.min.y), egui::vec2(grid_w, HEADER_H));
+                    self.draw_tempo_ruler(ui, &p, ruler, BAR_W, total_bars, "song_tempo_ruler");
 
                     if self.song_editor.is_playing.load(Ordering::Relaxed) {
                         let cur_bar = self.song_editor.current_bar.load(Ordering::Relaxed);
@@ -226,10 +233,22 @@ impl AppState {
                             ui.set_min_width(160.0);
                             ui.label(egui::RichText::new(&pattern.name).small().color(color));
                             ui.separator();
-                            if ui.button("✏ Edit (switch here)").clicked() { self.switch_pattern(row_i); ui.close_menu(); }
-                            if ui.button("⎘ Duplicate").clicked() { self.song_editor.duplicate_pattern(row_i); ui.close_menu(); }
+                            if ui.button(t!("✏ Edit (switch here)")).clicked() { self.switch_pattern(row_i); ui.close_menu(); }
+                            if ui.button(t!("⎘ Duplicate")).clicked() { self.song_editor.duplicate_pattern(row_i); ui.close_menu(); }
+                            self.pattern_clip_menu(ui, row_i);
+                            ui.menu_button(t!("🥁 Fill pattern"), |ui| {
+                                if ui.selectable_label(pattern.fill.is_none(), t!("None")).clicked() {
+                                    self.set_fill_pattern(row_i, None); ui.close_menu();
+                                }
+                                for (j, other) in self.song_editor.get_all_patterns().iter().enumerate() {
+                                    if j == row_i { continue; }
+                                    if ui.selectable_label(pattern.fill == Some(j), &other.name).clicked() {
+                                        self.set_fill_pattern(row_i, Some(j)); ui.close_menu();
+                                    }
+                                }
+                            });
                             ui.separator();
-                            if ui.button(egui::RichText::new("✕ Remove").color(egui::Color32::from_rgb(200,80,80))).clicked() {
+                            if ui.button(egui::RichText::new(t!("✕ Remove")).color(egui::Color32::from_rgb(200,80,80))).clicked() {
                                 self.song_editor.remove_pattern(row_i); ui.close_menu();
                             }
                         });
@@ -263,6 +282,7 @@ impl AppState {
 
                     let grid_rect = egui::Rect::from_min_size(grid_orig, egui::vec2(grid_w, ROW_H * n_patterns as f32));
                     let gresp = ui.interact(grid_rect, egui::Id::new("se_grid"), egui::Sense::click_and_drag());
+                    gresp.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Other, t!("Song editor grid: rows are patterns, columns are bars")));
                     let primary   = ui.input(|i| i.pointer.primary_down());
                     let secondary = ui.input(|i| i.pointer.secondary_down());
                     if (primary || secondary) && (gresp.dragged() || gresp.clicked() || gresp.drag_started()) {
@@ -281,7 +301,7 @@ impl AppState {
 
             ui.add_space(2.0);
             ui.label(egui::RichText::new(
-                "Left-drag = paint  ·  Right-drag = erase  ·  Click pattern name to edit  ·  Right-click pattern for options"
+                t!("Left-drag = paint  ·  Right-drag = erase  ·  Click pattern name to edit  ·  Right-click pattern for options")
             ).small().color(egui::Color32::from_gray(55)));
         });
     }
@@ -307,7 +327,7 @@ impl AppState {
 
         let active_edit  = self.song_editor.active_edit_idx();
         let song_playing = self.song_editor.is_playing.load(Ordering::Relaxed);
-        let bpm          = self.seq_bpm.load(Ordering::Relaxed);
+        let bpm          = self.events.bpm();
 
         // ── Snapshot audio tracks (release lock before painting) ──────────────
         let audio_snaps: Vec<AudioTrackSnap> = {
@@ -355,23 +375,23 @@ impl AppState {
                     if song_playing { self.stop_song(); } else { self.start_song(); }
                 }
                 ui.add_space(4.0);
-                ui.label(egui::RichText::new("Playlist — Arrangement").size(11.0).color(fl_text_dim()));
+                ui.label(egui::RichText::new(t!("Playlist — Arrangement")).size(11.0).color(fl_text_dim()));
                 ui.separator();
                 if ui.add(egui::Button::new(
-                    egui::RichText::new("＋ Pattern").size(10.5).color(egui::Color32::from_rgb(100, 220, 140)))
+                    egui::RichText::new(t!("＋ Pattern")).size(10.5).color(egui::Color32::from_rgb(100, 220, 140)))
                     .fill(egui::Color32::from_rgb(30, 45, 35))
                 ).clicked() { self.create_new_pattern(); }
                 ui.add_space(2.0);
                 // ── NEW: Add Audio Track button ───────────────────────────────
                 if ui.add(egui::Button::new(
-                    egui::RichText::new("🔊 Audio").size(10.5).color(egui::Color32::from_rgb(80, 200, 255)))
+                    egui::RichText::new(t!("🔊 Audio")).size(10.5).color(egui::Color32::from_rgb(80, 200, 255)))
                     .fill(egui::Color32::from_rgb(22, 35, 48))
-                ).on_hover_text("Add an audio track — load a file, then paint clips onto the timeline").clicked() {
+                ).on_hover_text(t!("Add an audio track — load a file, then paint clips onto the timeline")).clicked() {
                     self.add_playlist_audio_track();
                 }
                 ui.add_space(4.0);
                 if ui.add(egui::Button::new(
-                    egui::RichText::new("＋ Bar").size(10.5).color(fl_text_dim()))
+                    egui::RichText::new(t!("＋ Bar")).size(10.5).color(fl_text_dim()))
                     .fill(egui::Color32::from_rgb(38, 42, 52))
                 ).clicked() {
                     let cur = *self.song_editor.total_bars.read();
@@ -379,19 +399,26 @@ impl AppState {
                 }
                 ui.add_space(4.0);
                 if ui.add(egui::Button::new(
-                    egui::RichText::new("🗑 Clear").size(10.5).color(egui::Color32::from_rgb(200, 80, 80)))
+                    egui::RichText::new(t!("🗑 Clear")).size(10.5).color(egui::Color32::from_rgb(200, 80, 80)))
                     .fill(egui::Color32::from_rgb(45, 32, 32))
                 ).clicked() {
                     self.song_editor.clear_arrangement();
                     *self.pl_drag_src.write() = None;
                 }
+                ui.add_space(4.0);
+                if ui.add(egui::Button::new(
+                    egui::RichText::new(t!("⤓ Song")).size(10.5).color(fl_text_dim()))
+                    .fill(egui::Color32::from_rgb(38, 42, 52))
+                ).on_hover_text(t!("Bounce the arrangement to a WAV, tempo changes included")).clicked() {
+                    self.bounce_song();
+                }
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     ui.add_space(8.0);
                     let bar = self.song_editor.current_bar.load(Ordering::Relaxed);
-                    ui.label(egui::RichText::new(format!("Bar {}", bar + 1)).size(10.5).color(fl_text_dim()));
+                    ui.label(egui::RichText::new(t!("Bar {} · {:.0} BPM", bar + 1, self.song_bpm())).size(10.5).color(fl_text_dim()));
                     if self.pl_drag_src.read().is_some() {
                         ui.add_space(8.0);
-                        ui.label(egui::RichText::new("✊ Drag to move · Right-click to cancel")
+                        ui.label(egui::RichText::new(t!("✊ Drag to move · Right-click to cancel"))
                             .size(10.0).color(egui::Color32::from_rgb(237, 164, 80)));
                     }
                 });
@@ -420,7 +447,7 @@ impl AppState {
                 let sb_hdr = egui::Rect::from_min_size(outer.min, egui::vec2(FL_SIDEBAR_W, FL_HEADER_H));
                 p.rect_filled(sb_hdr, 0.0, fl_toolbar_bg());
                 p.hline(sb_hdr.x_range(), sb_hdr.bottom(), egui::Stroke::new(0.7, fl_border()));
-                for (i, lbl) in ["NOTE","CHAN","PAT"].iter().enumerate() {
+                for (i, lbl) in [t!("NOTE"),t!("CHAN"),t!("PAT")].iter().enumerate() {
                     p.text(
                         egui::pos2(sb_hdr.left() + 8.0 + i as f32 * 48.0, sb_hdr.center().y),
                         egui::Align2::LEFT_CENTER, lbl,
@@ -470,11 +497,12 @@ impl AppState {
                         ui.set_min_width(160.0);
                         ui.label(egui::RichText::new(&pattern.name).small().color(color));
                         ui.separator();
-                        if ui.button("✏ Edit pattern").clicked() { self.switch_pattern(row_i); ui.close_menu(); }
-                        if ui.button("🖌 Select as brush").clicked() { self.song_editor.set_active_edit_idx(row_i); ui.close_menu(); }
-                        if ui.button("⎘ Duplicate").clicked() { self.song_editor.duplicate_pattern(row_i); ui.close_menu(); }
+                        if ui.button(t!("✏ Edit pattern")).clicked() { self.switch_pattern(row_i); ui.close_menu(); }
+                        if ui.button(t!("🖌 Select as brush")).clicked() { self.song_editor.set_active_edit_idx(row_i); ui.close_menu(); }
+                        if ui.button(t!("⎘ Duplicate")).clicked() { self.song_editor.duplicate_pattern(row_i); ui.close_menu(); }
+                        self.pattern_clip_menu(ui, row_i);
                         ui.separator();
-                        if ui.button(egui::RichText::new("✕ Remove").color(egui::Color32::from_rgb(200,80,80))).clicked() {
+                        if ui.button(egui::RichText::new(t!("✕ Remove")).color(egui::Color32::from_rgb(200,80,80))).clicked() {
                             self.song_editor.remove_pattern(row_i);
                             *self.pl_drag_src.write() = None;
                             ui.close_menu();
@@ -493,7 +521,7 @@ impl AppState {
                     );
                     p.rect_filled(div_rect, 0.0, fl_audio_hdr_bg());
                     p.text(egui::pos2(div_rect.left() + 8.0, div_rect.center().y),
-                        egui::Align2::LEFT_CENTER, "🔊 AUDIO",
+                        egui::Align2::LEFT_CENTER, t!("🔊 AUDIO"),
                         egui::FontId::monospace(8.5), egui::Color32::from_rgb(80, 200, 255));
                     p.hline(div_rect.x_range(), div_rect.bottom(), egui::Stroke::new(0.7, fl_border()));
 
@@ -511,7 +539,7 @@ impl AppState {
                         p.text(egui::pos2(item_rect.left() + 18.0, item_rect.center().y - 4.0),
                             egui::Align2::LEFT_CENTER, &short,
                             egui::FontId::proportional(10.0), if snap.muted { fl_text_dim() } else { fl_text() });
-                        let src_label = if snap.has_source { "loaded" } else { "no file" };
+                        let src_label = if snap.has_source { "loaded" } else { t!("no file") };
                         p.text(egui::pos2(item_rect.left() + 18.0, item_rect.center().y + 5.0),
                             egui::Align2::LEFT_CENTER, src_label,
                             egui::FontId::proportional(8.0),
@@ -530,21 +558,21 @@ impl AppState {
                             ui.set_min_width(170.0);
                             ui.label(egui::RichText::new(&snap.name).small().color(color));
                             ui.separator();
-                            if ui.button("📂 Load audio file").clicked() {
+                            if ui.button(t!("📂 Load audio file")).clicked() {
                                 // trigger load via a flag — actual file pick below scroll area
                                 *status.write() = format!("__load_audio__{}", track_idx);
                                 ui.close_menu();
                             }
-                            if ui.button(if snap.muted { "🔊 Unmute" } else { "🔇 Mute" }).clicked() {
+                            if ui.button(if snap.muted { t!("🔊 Unmute") } else { t!("🔇 Mute") }).clicked() {
                                 if let Some(t) = at_arc.write().get_mut(track_idx) { t.muted = !t.muted; }
                                 ui.close_menu();
                             }
                             ui.separator();
-                            if ui.button("🗑 Clear clips").clicked() {
+                            if ui.button(t!("🗑 Clear clips")).clicked() {
                                 if let Some(t) = at_arc.write().get_mut(track_idx) { t.clips.clear(); }
                                 ui.close_menu();
                             }
-                            if ui.button(egui::RichText::new("✕ Remove track").color(egui::Color32::from_rgb(200,80,80))).clicked() {
+                            if ui.button(egui::RichText::new(t!("✕ Remove track")).color(egui::Color32::from_rgb(200,80,80))).clicked() {
                                 let mut tracks = at_arc.write();
                                 if track_idx < tracks.len() { tracks.remove(track_idx); }
                                 ui.close_menu();
@@ -568,7 +596,7 @@ impl AppState {
                 p.rect_filled(hdr_rect, 0.0, fl_header_bg());
                 p.text(
                     egui::pos2(grid_x0 + FL_TRACK_LBL * 0.5, outer.top() + FL_HEADER_H * 0.5),
-                    egui::Align2::CENTER_CENTER, "Track",
+                    egui::Align2::CENTER_CENTER, t!("Track"),
                     egui::FontId::monospace(8.5), fl_text_dim(),
                 );
                 for bar in 0..total_bars {
@@ -583,6 +611,8 @@ impl AppState {
                         egui::Stroke::new(if bar % 4 == 0 { 0.8 } else { 0.3 }, lc));
                 }
                 p.hline(hdr_rect.x_range(), hdr_rect.bottom(), egui::Stroke::new(1.0, fl_border()));
+                let ruler = egui::Rect::from_min_size(egui::pos2(grid_orig.x, outer.top()), egui::vec2(grid_total_w, FL_HEADER_H));
+                self.draw_tempo_ruler(ui, &p, ruler, FL_BAR_W, total_bars, "fl_tempo_ruler");
 
                 // Playhead
                 if song_playing {
@@ -636,7 +666,7 @@ impl AppState {
                     ));
                     p.circle_filled(egui::pos2(label_cell.left() + 10.0, label_cell.center().y), 3.5, egui::Color32::from_rgb(60,200,80));
                     p.text(egui::pos2(label_cell.left() + 22.0, label_cell.center().y),
-                        egui::Align2::LEFT_CENTER, format!("Track {}", track_i + 1),
+                        egui::Align2::LEFT_CENTER, t!("Track {}", track_i + 1),
                         egui::FontId::proportional(10.5), fl_text_dim());
                     p.vline(label_cell.right(), egui::Rangef::new(y, y + FL_TRACK_H - 1.0),
                         egui::Stroke::new(1.0, fl_border()));
@@ -698,6 +728,7 @@ impl AppState {
 
                 // ── Pattern grid interactions ─────────────────────────────────
                 let gresp = ui.interact(grid_area, egui::Id::new("fl_pl_grid_v5"), egui::Sense::click_and_drag());
+                gresp.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Other, t!("Playlist arrangement grid")));
                 let primary_pressed  = ui.input(|i| i.pointer.primary_pressed());
                 let primary_down     = ui.input(|i| i.pointer.primary_down());
                 let primary_released = ui.input(|i| i.pointer.primary_released());
@@ -765,7 +796,7 @@ impl AppState {
                     p.text(
                         egui::pos2(div_rect.left() + FL_TRACK_LBL + 8.0, div_rect.center().y),
                         egui::Align2::LEFT_CENTER,
-                        "🔊  Audio Tracks — Left-click to paint · Right-click to erase · Double-click label to load file",
+                        t!("🔊  Audio Tracks — Left-click to paint · Right-click to erase · Double-click label to load file"),
                         egui::FontId::proportional(9.5),
                         egui::Color32::from_rgb(80, 200, 255),
                     );
@@ -808,7 +839,7 @@ impl AppState {
                                 egui::FontId::proportional(9.5), if snap.muted { fl_text_dim() } else { fl_text() });
                             p.text(egui::pos2(lbl_rect.left() + 10.0, lbl_rect.center().y + 6.0),
                                 egui::Align2::LEFT_CENTER,
-                                if snap.has_source { "ready" } else { "dbl-click to load" },
+                                if snap.has_source { "ready" } else { t!("dbl-click to load") },
                                 egui::FontId::proportional(7.5),
                                 if snap.has_source { egui::Color32::from_rgb(80,200,100) } else { egui::Color32::from_gray(80) });
                         }
@@ -954,13 +985,13 @@ impl AppState {
             ui.horizontal(|ui| {
                 ui.add_space(8.0);
                 ui.label(egui::RichText::new(
-                    "Patterns: Left=draw · Drag=move · Right=erase  ‖  Audio: Left=paint clip · Right=erase · Dbl-click label=load file"
+                    t!("Patterns: Left=draw · Drag=move · Right=erase  ‖  Audio: Left=paint clip · Right=erase · Dbl-click label=load file")
                 ).size(9.5).color(fl_text_dim()));
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     let np = self.song_editor.pattern_count();
                     let na = self.playlist_audio_tracks.read().len();
                     ui.add_space(8.0);
-                    ui.label(egui::RichText::new(format!("{} pat · {} audio", np, na)).size(9.5).color(fl_text_dim()));
+                    ui.label(egui::RichText::new(t!("{} pat · {} audio", np, na)).size(9.5).color(fl_text_dim()));
                 });
             });
         }); // end outer_frame
@@ -975,6 +1006,85 @@ impl AppState {
         }
     }
 
+    /// Pattern menu entries: copy this pattern, paste a copied one over it or into a new one.
+    fn pattern_clip_menu(&self, ui: &mut egui::Ui, idx: usize) {
+        if ui.button(t!("📋 Copy")).clicked() { self.copy_pattern(idx); ui.close_menu(); }
+        let can_paste = self.can_paste_pattern();
+        if ui.add_enabled(can_paste, egui::Button::new(t!("📋 Paste over")))
+            .on_hover_text(t!("Replace this pattern's steps and tracks with the copied pattern's; name and song place stay"))
+            .clicked() {
+            self.paste_pattern(idx);
+            ui.close_menu();
+        }
+        if ui.add_enabled(can_paste, egui::Button::new(t!("📋 Paste as new"))).clicked() {
+            self.paste_pattern_as_new();
+            ui.close_menu();
+        }
+    }
+
+    // =========================================================================
+    //  Tempo changes on the bar ruler
+    // =========================================================================
+    /// Mark the tempo track on `ruler` (left edge on bar 1) and let a right-click
+    /// on a bar add, edit or remove its tempo change.
+    fn draw_tempo_ruler(&self, ui: &mut egui::Ui, p: &egui::Painter, ruler: egui::Rect, bar_w: f32, total_bars: usize, id: &str) {
+        let tempo_col = egui::Color32::from_rgb(237, 164, 80);
+        let base      = self.events.bpm();
+        let events    = self.tempo_events.read().clone();
+        for (i, e) in events.iter().enumerate().filter(|(_, e)| e.bar < total_bars) {
+            let x = ruler.left() + e.bar as f32 * bar_w;
+            if e.ramp_bars > 0 {
+                let span = egui::Rect::from_min_max(egui::pos2(x, ruler.top()),
+                    egui::pos2(x + e.ramp_bars as f32 * bar_w, ruler.bottom()));
+                p.rect_filled(span, 0.0, egui::Color32::from_rgba_unmultiplied(237, 164, 80, 22));
+            }
+            let before = if i == 0 { base } else { events[i - 1].bpm };
+            let mark = match (e.ramp_bars > 0, e.bpm >= before) {
+                (false, _)    => "♩",
+                (true, true)  => "↗",
+                (true, false) => "↘",
+            };
+            p.vline(x, ruler.y_range(), egui::Stroke::new(1.5, tempo_col));
+            p.text(egui::pos2(x + 2.0, ruler.bottom() - 1.0), egui::Align2::LEFT_BOTTOM,
+                format!("{}{:.0}", mark, e.bpm), egui::FontId::proportional(8.0), tempo_col);
+        }
+
+        let resp = ui.interact(ruler, egui::Id::new(id), egui::Sense::click())
+            .on_hover_text(t!("Right-click a bar for a tempo change"));
+        let bar_id = egui::Id::new(id).with("bar");
+        if resp.secondary_clicked() {
+            if let Some(pos) = resp.interact_pointer_pos() {
+                let bar = (((pos.x - ruler.left()) / bar_w).max(0.0) as usize).min(total_bars.saturating_sub(1));
+                ui.data_mut(|d| d.insert_temp(bar_id, bar));
+            }
+        }
+        resp.context_menu(|ui| {
+            let bar = ui.data(|d| d.get_temp::<usize>(bar_id)).unwrap_or(0);
+            ui.label(egui::RichText::new(t!("Tempo at bar {}", bar + 1)).small().color(tempo_col));
+            ui.separator();
+            match self.tempo_event(bar) {
+                Some(mut e) => {
+                    let mut changed = ui.add(egui::DragValue::new(&mut e.bpm).clamp_range(TEMPO_RANGE).speed(0.5).suffix(t!(" BPM"))).changed();
+                    ui.horizontal(|ui| {
+                        ui.label(t!("Ramp over"));
+                        changed |= ui.add(egui::DragValue::new(&mut e.ramp_bars).clamp_range(0..=64).suffix(t!(" bars"))).changed();
+                    }).response.on_hover_text(t!("Glide from the tempo before; 0 jumps at the bar"));
+                    if changed { self.set_tempo_event(e); }
+                    if ui.button(egui::RichText::new(t!("✕ Remove")).color(egui::Color32::from_rgb(200, 80, 80))).clicked() {
+                        self.remove_tempo_event(bar);
+                        ui.close_menu();
+                    }
+                }
+                None => {
+                    let here = bpm_at(&events, base, bar as f64);
+                    if ui.button(t!("＋ Tempo change ({:.0} BPM)", here)).clicked() {
+                        self.set_tempo_event(TempoEvent { bar, bpm: here, ramp_bars: 0 });
+                    }
+                }
+            }
+        });
+    }
+
     // =========================================================================
     //  Pattern-tab bar
     // =========================================================================
@@ -983,7 +1093,7 @@ impl AppState {
         let active = self.song_editor.active_edit_idx();
 
         ui.horizontal(|ui| {
-            ui.label(egui::RichText::new("PAT").size(20.0).color(egui::Color32::from_gray(80)));
+            ui.label(egui::RichText::new(t!("PAT")).size(20.0).color(egui::Color32::from_gray(80)));
             ui.add_space(2.0);
 
             for i in 0..n {
@@ -1009,11 +1119,12 @@ impl AppState {
                     ui.set_min_width(130.0);
                     ui.label(egui::RichText::new(&pattern.name).size(20.0).color(color));
                     ui.separator();
-                    if ui.button("⎘ Duplicate").clicked() {
+                    if ui.button(t!("⎘ Duplicate")).clicked() {
                         self.song_editor.duplicate_pattern(i); ui.close_menu();
                     }
+                    self.pattern_clip_menu(ui, i);
                     if n > 1 {
-                        if ui.button(egui::RichText::new("✕ Remove").color(egui::Color32::from_rgb(200,80,80))).clicked() {
+                        if ui.button(egui::RichText::new(t!("✕ Remove")).color(egui::Color32::from_rgb(200,80,80))).clicked() {
                             let new_active = if active >= n - 1 { n.saturating_sub(2) } else { active };
                             self.song_editor.remove_pattern(i);
                             if i != active || new_active != active {
@@ -1031,7 +1142,7 @@ impl AppState {
                 egui::Button::new(egui::RichText::new("+").size(20.0).color(egui::Color32::from_rgb(80, 220, 140)))
                     .fill(egui::Color32::from_rgb(18, 28, 20))
                     .stroke(egui::Stroke::new(0.8, egui::Color32::from_rgb(50, 130, 70)))
-            ).on_hover_text("Create new pattern (fresh workspace)").clicked() {
+            ).on_hover_text(t!("Create new pattern (fresh workspace)")).clicked() {
                 self.create_new_pattern();
             }
         });
@@ -0,0 +1,84 @@
+// src/gui/ui/pinned_waveform.rs
+use eframe::egui;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use crate::gui::{AppState, WaveformFocus};
+use super::widgets::*;
+
+impl AppState {
+    /// The pinned waveform, drawn above the focused one so two sounds can be lined up
+    /// without switching focus. It shows markers and the playhead but isn't edited
+    /// here; ⇅ swaps it with the focused panel.
+    pub fn draw_pinned_waveform(&mut self, ui: &mut egui::Ui) {
+        let Some(pinned) = self.pinned_waveform.read().clone() else { return; };
+        if pinned == *self.waveform_focus.read() { return; }
+        let Some(asset) = self.waveform_asset(&pinned) else { return; };
+
+        let (title, wave_color) = match pinned {
+            WaveformFocus::DrumTrack(idx) => (t!("📌 {} (Track {})", asset.file_name, idx + 1), drum_color(idx)),
+            WaveformFocus::MainSample     => (format!("📌 {}", asset.file_name), egui::Color32::from_rgb(80, 160, 255)),
+        };
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(title).small().color(egui::Color32::from_gray(170)));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button("✕").on_hover_text(t!("Unpin")).clicked() {
+                        *self.pinned_waveform.write() = None;
+                    }
+                    if ui.small_button(t!("⇅ Swap")).on_hover_text(t!("Focus this sound and pin the other one")).clicked() {
+                        let focus = std::mem::replace(&mut *self.waveform_focus.write(), pinned.clone());
+                        *self.pinned_waveform.write() = Some(focus);
+                    }
+                });
+            });
+
+            let size = egui::Vec2::new(ui.available_width(), 110.0);
+            let (response, painter) = ui.allocate_painter(size, egui::Sense::hover());
+            let rect = response.rect;
+            painter.rect_filled(rect, 0.0, egui::Color32::from_gray(18));
+            let buckets = ((rect.width() * ui.ctx().pixels_per_point()) as usize).div_ceil(64) * 64;
+            if buckets == 0 { return; }
+            let analysis = self.display_waveform(&asset, buckets);
+            let cy = rect.center().y;
+            let hs = rect.height() * 0.45;
+            let w  = rect.width();
+            let bw = w / analysis.min_max_buckets.len().max(1) as f32;
+            let gap = if bw > 2.0 { 0.5 } else { 0.0 };
+            for (i, (min, max)) in analysis.min_max_buckets.iter().enumerate() {
+                let x  = rect.left() + i as f32 * bw;
+                let bh = (max.abs().max(min.abs()) * hs * 2.0).min(rect.height() * 0.9);
+                painter.rect_filled(
+                    egui::Rect::from_min_max(egui::pos2(x, cy - bh / 2.0), egui::pos2(x + bw - gap, cy + bh / 2.0)),
+                    0.0, wave_color,
+                );
+            }
+            painter.hline(rect.x_range(), cy, egui::Stroke::new(0.5, egui::Color32::from_gray(55)));
+            draw_bar_grid(&painter, rect, &self.bar_grid_lines(&asset.sample_uuid, asset.frames as f32 / asset.sample_rate.max(1) as f32));
+
+            for (chop_idx, mark) in self.samples_manager.get_marks_for_sample(&asset.sample_uuid).iter().enumerate() {
+                let mx = rect.left() + mark.position * w;
+                painter.vline(mx, rect.y_range(), egui::Stroke::new(1.5, pad_color(chop_idx)));
+                painter.text(egui::pos2(mx + 3.0, rect.top() + 2.0), egui::Align2::LEFT_TOP,
+                    format!("{}", chop_idx + 1), egui::FontId::proportional(10.0), pad_color(chop_idx));
+            }
+
+            // Playhead only while this sound is the one playing
+            let playing = self.is_playing.load(Ordering::Relaxed)
+                && self.playback_asset.read().as_ref().is_some_and(|a| Arc::ptr_eq(a, &asset));
+            if playing {
+                let px = rect.left() + self.playback_position.load(Ordering::Relaxed) * w;
+                painter.vline(px, rect.y_range(), egui::Stroke::new(2.0, egui::Color32::from_rgb(255, 80, 80)));
+            }
+
+            // Hover guide shared with the focused panel, for lining hits up
+            let hover = response.hover_pos().map(|p| ((p.x - rect.left()) / w).clamp(0.0, 1.0));
+            let other = self.waveform_guide.write().take().filter(|g| !g.0).map(|g| g.1);
+            if let Some(norm) = hover.or(other) {
+                painter.vline(rect.left() + norm * w, rect.y_range(),
+                    egui::Stroke::new(1.0, egui::Color32::from_rgba_unmultiplied(255, 255, 255, 60)));
+            }
+            if let Some(norm) = hover { *self.waveform_guide.write() = Some((true, norm)); }
+        });
+        ui.add_space(4.0);
+    }
+}
@@ -0,0 +1,68 @@
+// src/gui/ui/tasks_window.rs
+use eframe::egui;
+use crate::gui::AppState;
+use crate::jobs::JobState;
+
+impl AppState {
+    /// Background jobs: what's queued or running with its progress, and how the rest ended.
+    pub fn draw_tasks_window(&mut self, ctx: &egui::Context) {
+        if !*self.tasks_open.read() { return; }
+        let jobs = self.jobs.list();
+
+        let mut to_cancel: Option<u64> = None;
+        let mut clear = false;
+
+        let mut window_open = true;
+        egui::Window::new(t!("⏳ Tasks"))
+            .id(egui::Id::new("tasks_window"))
+            .default_size([360.0, 280.0])
+            .collapsible(false)
+            .open(&mut window_open)
+            .show(ctx, |ui| {
+                if jobs.is_empty() {
+                    ui.label(egui::RichText::new(t!("Nothing running")).small().color(egui::Color32::from_gray(100)));
+                }
+                egui::ScrollArea::vertical().max_height(220.0).show(ui, |ui| {
+                    for job in jobs.iter().rev() {
+                        ui.horizontal(|ui| {
+                            ui.label(job.kind.icon());
+                            ui.vertical(|ui| {
+                                ui.label(&job.label);
+                                match &job.state {
+                                    JobState::Queued => {
+                                        ui.label(egui::RichText::new(t!("Waiting")).small().color(egui::Color32::from_gray(140)));
+                                    }
+                                    JobState::Running => match job.progress {
+                                        Some(p) => { ui.add(egui::ProgressBar::new(p).desired_width(200.0).show_percentage()); }
+                                        None    => { ui.spinner(); }
+                                    },
+                                    JobState::Done(msg) => {
+                                        ui.label(egui::RichText::new(msg).small().color(egui::Color32::from_rgb(110, 190, 110)));
+                                    }
+                                    JobState::Failed(e) => {
+                                        ui.label(egui::RichText::new(format!("✗ {}", e)).small().color(egui::Color32::from_rgb(240, 90, 60)));
+                                    }
+                                    JobState::Cancelled => {
+                                        ui.label(egui::RichText::new(t!("Cancelled")).small().color(egui::Color32::from_gray(140)));
+                                    }
+                                }
+                            });
+                            if !job.state.finished() {
+                                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                    if ui.small_button(t!("Cancel")).clicked() { to_cancel = Some(job.id); }
+                                });
+                            }
+                        });
+                        ui.separator();
+                    }
+                });
+                if jobs.iter().any(|j| j.state.finished()) && ui.button(t!("Clear finished")).clicked() {
+                    clear = true;
+                }
+            });
+
+        if let Some(id) = to_cancel { self.jobs.cancel(id); }
+        if clear { self.jobs.clear_finished(); }
+        if !window_open { *self.tasks_open.write() = false; }
+    }
+}
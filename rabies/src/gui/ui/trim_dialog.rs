@@ -0,0 +1,93 @@
+// src/gui/ui/trim_dialog.rs
+use eframe::egui;
+use crate::gui::AppState;
+
+impl AppState {
+    /// Start/end handles + normalise for a drum track that's about to be added.
+    pub fn draw_trim_dialog(&mut self, ctx: &egui::Context) {
+        if self.pending_trim.read().is_none() { return; }
+
+        let mut add     = false;
+        let mut preview = false;
+        let mut cancel  = false;
+        let mut window_open = true;
+        egui::Window::new(t!("✂ Trim new track"))
+            .id(egui::Id::new("trim_dialog"))
+            .default_size([460.0, 220.0])
+            .collapsible(false)
+            .open(&mut window_open)
+            .show(ctx, |ui| {
+                let mut guard = self.pending_trim.write();
+                let Some(d) = guard.as_mut() else { return; };
+                let secs = d.asset.frames as f32 / d.asset.sample_rate.max(1) as f32;
+                // Handles are stored 0-1 but shown and typed in seconds
+                let parse_secs = |t: &str| t.trim().trim_end_matches('s').trim().parse::<f64>().ok()
+                    .map(|v| v / secs.max(1e-6) as f64);
+                ui.label(egui::RichText::new(&d.asset.file_name).strong());
+
+                let size = egui::Vec2::new(ui.available_width(), 110.0);
+                let (resp, painter) = ui.allocate_painter(size, egui::Sense::click_and_drag());
+                let rect = resp.rect;
+                painter.rect_filled(rect, 0.0, egui::Color32::from_gray(22));
+
+                let x_at = |p: f32| rect.left() + p * rect.width();
+                let (sx, ex) = (x_at(d.start), x_at(d.end));
+                let cy = rect.center().y;
+                let hs = rect.height() * 0.45;
+                let n  = d.waveform.min_max_buckets.len().max(1);
+                let bw = (rect.width() / n as f32).max(1.0);
+                for (i, (min, max)) in d.waveform.min_max_buckets.iter().enumerate() {
+                    let x    = rect.left() + i as f32 * bw;
+                    let kept = x + bw * 0.5 >= sx && x + bw * 0.5 <= ex;
+                    let col  = if kept { egui::Color32::from_rgb(80, 220, 140) } else { egui::Color32::from_gray(60) };
+                    let bh   = (max.abs().max(min.abs()) * hs * 2.0).min(rect.height() * 0.9);
+                    painter.rect_filled(
+                        egui::Rect::from_min_max(egui::pos2(x, cy - bh / 2.0), egui::pos2(x + bw - 0.5, cy + bh / 2.0)),
+                        0.0, col,
+                    );
+                }
+                for x in [sx, ex] {
+                    painter.vline(x, rect.y_range(), egui::Stroke::new(2.0, egui::Color32::from_rgb(255, 200, 60)));
+                }
+
+                // Drag whichever handle is nearer the pointer
+                if let Some(pos) = resp.interact_pointer_pos() {
+                    let p = ((pos.x - rect.left()) / rect.width()).clamp(0.0, 1.0);
+                    if (pos.x - sx).abs() <= (pos.x - ex).abs() { d.start = p.min(d.end); } else { d.end = p.max(d.start); }
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label(t!("Start"));
+                    ui.add(egui::DragValue::new(&mut d.start).speed(0.001).clamp_range(0.0..=d.end)
+                        .custom_formatter(|v, _| format!("{:.3} s", v as f32 * secs))
+                        .custom_parser(parse_secs));
+                    ui.label(t!("End"));
+                    ui.add(egui::DragValue::new(&mut d.end).speed(0.001).clamp_range(d.start..=1.0)
+                        .custom_formatter(|v, _| format!("{:.3} s", v as f32 * secs))
+                        .custom_parser(parse_secs));
+                    ui.checkbox(&mut d.normalize, t!("Normalize"))
+                        .on_hover_text(t!("Bring the peak to -1 dBFS"));
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(t!("▶ Preview")).clicked() { preview = true; }
+                    if ui.button(egui::RichText::new(t!("＋ Add track")).color(egui::Color32::from_rgb(80, 220, 140))).clicked() {
+                        add = true;
+                    }
+                    if ui.button(t!("Cancel")).clicked() { cancel = true; }
+                    if !d.is_untouched() {
+                        let (s, e) = d.frame_range();
+                        ui.label(egui::RichText::new(t!("{:.3} s kept", (e - s) as f32 / d.asset.sample_rate.max(1) as f32))
+                            .small().color(egui::Color32::from_gray(130)));
+                    }
+                });
+            });
+
+        if preview { self.preview_trim(); }
+        if add {
+            self.commit_trim();
+        } else if cancel || !window_open {
+            self.cancel_trim();
+        }
+    }
+}
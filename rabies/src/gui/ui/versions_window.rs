@@ -0,0 +1,76 @@
+// src/gui/ui/versions_window.rs
+use eframe::egui;
+use std::sync::atomic::Ordering;
+use crate::gui::AppState;
+use crate::versions::{format_age, format_time, VersionInfo};
+
+impl AppState {
+    /// "Save Version" plus the list of saved versions, newest first.
+    pub fn draw_versions_window(&mut self, ctx: &egui::Context) {
+        if !self.versions_open.load(Ordering::Relaxed) { return; }
+        let has_project = self.project_path.read().is_some();
+        let versions    = self.versions.read().clone();
+
+        // Deferred actions — applied once the window closure is done
+        let mut save_now:   bool                = false;
+        let mut to_restore: Option<VersionInfo> = None;
+        let mut to_delete:  Option<VersionInfo> = None;
+
+        let mut window_open = true;
+        egui::Window::new(t!("🕘 Versions"))
+            .id(egui::Id::new("versions_window"))
+            .default_size([380.0, 340.0])
+            .collapsible(false)
+            .open(&mut window_open)
+            .show(ctx, |ui| {
+                if !has_project {
+                    ui.label(egui::RichText::new(t!("Save the project once — versions are kept beside it"))
+                        .small().color(egui::Color32::from_gray(140)));
+                    return;
+                }
+                ui.horizontal(|ui| {
+                    let resp = ui.add(egui::TextEdit::singleline(&mut *self.version_note.write())
+                        .hint_text(t!("Note (optional)"))
+                        .desired_width(220.0));
+                    let enter = resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    if enter || ui.button(t!("💾 Save Version")).clicked() { save_now = true; }
+                });
+                ui.separator();
+
+                if versions.is_empty() {
+                    ui.label(egui::RichText::new(t!("No versions yet")).small().color(egui::Color32::from_gray(100)));
+                }
+                egui::ScrollArea::vertical().max_height(260.0).show(ui, |ui| {
+                    for v in versions.iter().rev() {
+                        ui.horizontal(|ui| {
+                            ui.vertical(|ui| {
+                                ui.label(egui::RichText::new(format_time(v.saved_at)).monospace())
+                                    .on_hover_text(format_age(v.saved_at));
+                                let note = if v.note.is_empty() { "—" } else { v.note.as_str() };
+                                ui.label(egui::RichText::new(t!("{}  ·  {} pattern(s)", note, v.patterns))
+                                    .small().color(egui::Color32::from_gray(140)));
+                            });
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                if ui.add(egui::Button::new(
+                                    egui::RichText::new("🗑").small().color(egui::Color32::from_rgb(200, 80, 80))
+                                )).on_hover_text(t!("Delete this version")).clicked() {
+                                    to_delete = Some(v.clone());
+                                }
+                                if ui.button(t!("⟲ Restore"))
+                                    .on_hover_text(t!("Load this version (the current state is saved as a version first)"))
+                                    .clicked() {
+                                    to_restore = Some(v.clone());
+                                }
+                            });
+                        });
+                        ui.separator();
+                    }
+                });
+            });
+
+        if save_now { self.save_version_from_ui(); }
+        if let Some(v) = to_restore { self.restore_version(&v); }
+        if let Some(v) = to_delete  { self.delete_version(&v); }
+        if !window_open { self.versions_open.store(false, Ordering::Relaxed); }
+    }
+}
@@ -2,22 +2,139 @@
 use eframe::egui;
 use std::time::Duration;
 use std::sync::atomic::Ordering;
+use crate::bus::AppCommand;
 use crate::gui::{AppState, WaveformFocus};
+use crate::jobs::{JobKind, JobState};
 use crate::samples::PlaybackMode;
+use crate::selection::WaveSelection;
+use crate::safe_mode::SelfTest;
 use super::widgets::*;
+use super::browser::sample_info_line;
 
 impl eframe::App for AppState {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.sync_transport();
         self.tick_sequencer();
+        self.tick_link();
+        self.tick_midi_input();
+        self.tick_midi_clock_out();
+        self.tick_grid();
+        self.tick_chop_audition();
+        self.tick_punch_in();
+        self.tick_mix_fade();
+        self.tick_resume();
+        self.tick_audition();
+        self.tick_loaded_tracks();
+        self.tick_merge_import();
+        self.tick_history(ctx.input(|i| i.pointer.any_down()));
+        self.tick_file_watch();
+        self.tick_sidecars();
         self.draw_piano_roll(ctx);
         self.draw_chop_piano_roll(ctx);
+        self.draw_marker_editor(ctx);
+        self.draw_merge_window(ctx);
+        self.draw_versions_window(ctx);
+        self.draw_trim_dialog(ctx);
+        self.draw_autochop_dialog(ctx);
+        self.draw_midi_import_dialog(ctx);
+        self.draw_kit_report(ctx);
+        self.draw_kit_swap(ctx);
+        self.draw_audio_settings(ctx);
+        self.draw_macro_window(ctx);
+        self.draw_tasks_window(ctx);
+        self.draw_notes_window(ctx);
         egui::CentralPanel::default().show(ctx, |ui| {
             egui::ScrollArea::vertical().show(ui, |ui| {
-                ui.heading("Audio Sampler");
+                ui.heading(t!("Audio Sampler"));
+
+                let fault = self.engine_fault.read().clone();
+                if let Some(msg) = fault {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(t!("✗ Audio engine stopped: {}", msg))
+                            .color(egui::Color32::from_rgb(240, 90, 60)));
+                        if ui.button(t!("⟳ Restart engine")).on_hover_text(t!("Reopen audio output; your project is kept")).clicked() {
+                            self.restart_engine();
+                        }
+                    });
+                }
+                let offered = self.resume.lock().offered.clone();
+                if let Some(point) = offered {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(t!("Playback stopped at {}", point.describe()))
+                            .color(egui::Color32::from_rgb(120, 200, 240)));
+                        if ui.button(t!("⏯ Resume")).on_hover_text(t!("Put the project, pattern, step and playhead back and play on")).clicked() {
+                            self.resume_session();
+                        }
+                        if ui.small_button("✕").on_hover_text(t!("Dismiss")).clicked() { self.dismiss_resume(); }
+                    });
+                }
+                let safe = self.safe_mode.read().clone();
+                let failed_test = match &*self.self_test.read() {
+                    SelfTest::Done(r) if !r.passed() => Some(r.summary()),
+                    _ => None,
+                };
+                if let Some(reason) = safe {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(t!("🛟 Safe mode ({}) — audio is off; projects open and save as usual", reason))
+                            .color(egui::Color32::from_rgb(237, 164, 80)));
+                        if ui.button(t!("🔊 Turn audio on")).on_hover_text(t!("Leave safe mode; audio opens on the next sound")).clicked() {
+                            self.leave_safe_mode();
+                        }
+                        if ui.button(t!("🩺 Self-test")).on_hover_text(t!("Try the selected output with a short silent stream")).clicked() {
+                            self.start_self_test();
+                        }
+                    });
+                } else if let Some(summary) = failed_test {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(t!("✗ Audio self-test: {}", summary))
+                            .color(egui::Color32::from_rgb(240, 90, 60)));
+                        if ui.button(t!("🛟 Safe mode")).on_hover_text(t!("Turn audio off for this session")).clicked() {
+                            self.enter_safe_mode(t!("self-test failed"));
+                        }
+                    });
+                }
 
                 // ── Transport ──────────────────────────────────────────
                 ui.horizontal(|ui| {
-                    if ui.button("Load Sample").clicked() {
+                    if ui.button(t!("📂 Open")).on_hover_text(t!("Open a .rabies project")).clicked() {
+                        self.open_project();
+                    }
+                    if ui.button(t!("💾 Save")).on_hover_text(t!("Save project (Ctrl+S)")).clicked() {
+                        self.save_project();
+                    }
+                    if ui.button(t!("⤵ Import…")).on_hover_text(t!("Bring patterns, kits or marks in from another project")).clicked() {
+                        self.begin_merge_import();
+                    }
+                    if ui.button(t!("🕘 Versions")).on_hover_text(t!("Save a version of the project or roll back to one")).clicked() {
+                        self.refresh_versions();
+                        self.versions_open.store(true, Ordering::Relaxed);
+                    }
+                    if ui.button(t!("📝 Notes")).on_hover_text(t!("Project notes and credits, and where each sample came from")).clicked() {
+                        self.notes_open.store(true, Ordering::Relaxed);
+                    }
+                    let mut copy = self.copy_on_import.load(Ordering::Relaxed);
+                    if ui.checkbox(&mut copy, t!("📁 Copy samples"))
+                        .on_hover_text(t!("Copy imported samples into the project's samples/ folder so the project is portable"))
+                        .changed() {
+                        self.copy_on_import.store(copy, Ordering::Relaxed);
+                    }
+                    let mut mono = self.mono_oneshots.load(Ordering::Relaxed);
+                    if ui.checkbox(&mut mono, t!("◐ Mono one-shots"))
+                        .on_hover_text(t!("Fold new drum tracks to one channel: half the memory and mixing work. A track's right-click menu switches it back"))
+                        .changed() {
+                        self.mono_oneshots.store(mono, Ordering::Relaxed);
+                    }
+                    if let Some(name) = self.project_name() {
+                        ui.label(egui::RichText::new(name).small().color(egui::Color32::from_gray(170)));
+                    }
+                    ui.separator();
+                    self.draw_panic_button(ui);
+                    self.draw_master_volume(ui);
+                    self.draw_mix_snapshots(ui);
+                    self.draw_backend_picker(ui);
+                    self.draw_net_send(ui);
+                    ui.separator();
+                    if ui.button(t!("Load Sample")).clicked() {
                         self.stop_playback();
                         self.stop_sequencer();
                         self.load_sample_as_track();
@@ -25,7 +142,7 @@ impl eframe::App for AppState {
 
                     ui.group(|ui| {
                         ui.horizontal(|ui| {
-                            ui.label(egui::RichText::new("Tracks").strong());
+                            ui.label(egui::RichText::new(t!("Tracks")).strong());
                             let tracks = self.drum_tracks.read();
                             for (idx, track) in tracks.iter().enumerate() {
                                 let focus = self.waveform_focus.read().clone();
@@ -47,18 +164,19 @@ impl eframe::App for AppState {
                     let has_tracks = !self.drum_tracks.read().is_empty();
                     if has_tracks && idx < self.drum_tracks.read().len() {
                         let is_playing = self.is_playing.load(Ordering::Relaxed);
-                        if ui.button(if is_playing { "⏸ Pause" } else { "▶ Play" }).clicked() {
+                        if ui.button(if is_playing { t!("⏸ Pause") } else { t!("▶ Play") }).clicked() {
                             self.toggle_playback();
                         }
                     } else {
-                        ui.add_enabled(false, egui::Button::new("▶ Play"));
+                        ui.add_enabled(false, egui::Button::new(t!("▶ Play")));
                     }
-                    if ui.button("■ Stop").clicked() {
+                    if ui.button(t!("■ Stop")).clicked() {
                         self.stop_playback();
                         self.playback_position.store(0.0, Ordering::Relaxed);
                         self.playback_sample_index.store(0, Ordering::Relaxed);
-                        *self.status.write() = "Stopped".to_string();
+                        *self.status.write() = t!("Stopped").to_string();
                     }
+                    self.draw_seek_slider(ui);
                 });
 
                 ui.add_space(6.0);
@@ -66,35 +184,53 @@ impl eframe::App for AppState {
                 // ── Playback Mode Controls ─────────────────────────────────
                 ui.group(|ui| {
                     ui.horizontal(|ui| {
-                        ui.label(egui::RichText::new("Playback Mode").strong().small());
+                        ui.label(egui::RichText::new(t!("Playback Mode")).strong().small());
                         let current_mode = self.samples_manager.get_playback_mode();
                         let is_end = matches!(current_mode, PlaybackMode::PlayToEnd);
-                        if ui.selectable_label(is_end, "Play to End").clicked() {
+                        if ui.selectable_label(is_end, t!("Play to End")).clicked() {
                             self.samples_manager.set_playback_mode(PlaybackMode::PlayToEnd);
-                            *self.status.write() = "Playback: Play to End".to_string();
+                            *self.status.write() = t!("Playback: Play to End").to_string();
                         }
                         let is_next = matches!(current_mode, PlaybackMode::PlayToNextMarker);
-                        if ui.selectable_label(is_next, "Play to Next").clicked() {
+                        if ui.selectable_label(is_next, t!("Play to Next")).clicked() {
                             self.samples_manager.set_playback_mode(PlaybackMode::PlayToNextMarker);
-                            *self.status.write() = "Playback: Stop at Next Marker".to_string();
+                            *self.status.write() = t!("Playback: Stop at Next Marker").to_string();
                         }
                         ui.separator();
                         let regions = self.samples_manager.get_regions();
                         if !regions.is_empty() {
-                            ui.label(egui::RichText::new("Region:").small().color(egui::Color32::from_gray(100)));
+                            ui.label(egui::RichText::new(t!("Region:")).small().color(egui::Color32::from_gray(100)));
                             for region in &regions {
                                 let is_region = matches!(current_mode, PlaybackMode::CustomRegion { region_id } if region_id == region.id);
                                 if ui.selectable_label(is_region, &region.name).clicked() {
                                     self.samples_manager.set_playback_mode(PlaybackMode::CustomRegion { region_id: region.id });
-                                    *self.status.write() = format!("Playback: Region {}", region.name);
+                                    *self.status.write() = t!("Playback: Region {}", region.name);
                                 }
                             }
                         }
+                        ui.separator();
+                        let mut looping = self.playback_loop.load(Ordering::Relaxed);
+                        if ui.selectable_label(looping, t!("🔁 Loop"))
+                            .on_hover_text(t!("Regions and selections repeat instead of stopping at their end"))
+                            .clicked() {
+                            looping = !looping;
+                            self.playback_loop.store(looping, Ordering::Relaxed);
+                        }
+                        ui.separator();
+                        self.draw_varispeed(ui);
                     });
                 });
 
                 ui.add_space(4.0);
-                ui.label(self.status.read().as_str());
+                ui.horizontal(|ui| {
+                    let active = self.jobs.active();
+                    let label  = if active > 0 { t!("⏳ Tasks ({})", active) } else { t!("⏳ Tasks").to_string() };
+                    if ui.small_button(label).on_hover_text(t!("Background loading, analysis and export")).clicked() {
+                        let open = !*self.tasks_open.read();
+                        *self.tasks_open.write() = open;
+                    }
+                    ui.label(self.status.read().as_str());
+                });
 
                 // ── Waveform Display ─────────────────────────────────────
                 ui.add_space(8.0);
@@ -103,16 +239,23 @@ impl eframe::App for AppState {
                     let tracks = self.drum_tracks.read();
                     if let WaveformFocus::DrumTrack(idx) = &focus {
                         tracks.get(*idx)
-                            .map(|t| format!("Waveform  ·  {} (Track {})  —  press M while previewing to chop", t.asset.file_name, idx + 1))
-                            .unwrap_or("Waveform".to_string())
+                            .map(|t| t!("Waveform  ·  {} (Track {})  —  press M while previewing to chop", t.asset.file_name, idx + 1))
+                            .unwrap_or(t!("Waveform").to_string())
                     } else {
-                        "Waveform".to_string()
+                        t!("Waveform").to_string()
                     }
                 };
+                self.draw_pinned_waveform(ui);
                 ui.group(|ui| {
                     ui.horizontal(|ui| {
                         ui.label(egui::RichText::new(&focus_label).small().color(egui::Color32::from_gray(170)));
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            let pinned = self.pinned_waveform.read().as_ref() == Some(&focus);
+                            if ui.add(egui::SelectableLabel::new(pinned, egui::RichText::new(t!("📌 Pin")).small()))
+                                .on_hover_text(t!("Keep this waveform on screen above the focused one"))
+                                .clicked() {
+                                *self.pinned_waveform.write() = if pinned { None } else { Some(focus.clone()) };
+                            }
                             if let WaveformFocus::DrumTrack(idx) = &focus {
                                 let asset_opt = {
                                     let tracks = self.drum_tracks.read();
@@ -120,7 +263,7 @@ impl eframe::App for AppState {
                                 };
                                 if let Some(drum_asset) = asset_opt {
                                     let is_playing = self.is_playing.load(Ordering::Relaxed);
-                                    let btn_label = if is_playing { "⏹ Stop" } else { "▶ Preview" };
+                                    let btn_label = if is_playing { t!("⏹ Stop") } else { t!("▶ Preview") };
                                     let btn_color = if is_playing {
                                         egui::Color32::from_rgb(220, 80, 60)
                                     } else {
@@ -140,19 +283,37 @@ impl eframe::App for AppState {
                             }
                         });
                     });
+                    if let WaveformFocus::DrumTrack(idx) = &focus {
+                        let info = self.drum_tracks.read().get(*idx).map(|t| sample_info_line(&t.asset));
+                        if let Some(info) = info {
+                            ui.label(egui::RichText::new(info).small().color(egui::Color32::from_gray(130)));
+                        }
+                    }
                     ui.add_space(2.0);
                     let size = egui::Vec2::new(ui.available_width(), 150.0);
                     let (response, painter) = ui.allocate_painter(size, egui::Sense::click_and_drag());
                     let rect = response.rect;
                     painter.rect_filled(rect, 0.0, egui::Color32::from_gray(22));
+                    response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Other,
+                        t!("Waveform. Left and right arrows move the playhead, M drops a marker")));
+                    draw_focus_ring(ui, rect, &response);
 
                     let (focused_asset, focused_waveform) = self.focused_display();
+                    // About one bucket per physical pixel, in steps of 64 so a resize
+                    // drag doesn't re-analyse every frame
+                    let buckets = ((rect.width() * ui.ctx().pixels_per_point()) as usize).div_ceil(64) * 64;
+                    let focused_waveform = match (&focused_asset, focused_waveform) {
+                        (Some(asset), Some(_)) if buckets > 0 => Some(self.display_waveform(asset, buckets)),
+                        (_, wf) => wf,
+                    };
                     if let Some(analysis) = focused_waveform.as_ref() {
                         let cy = rect.center().y;
                         let hs = rect.height() * 0.45;
                         let w  = rect.width();
                         let bc = analysis.min_max_buckets.len();
-                        let bw = (w / bc as f32).max(1.0);
+                        let bw = w / bc as f32;
+                        // Gaps between bars only while they are wide enough to show them
+                        let gap = if bw > 2.0 { 0.5 } else { 0.0 };
                         let wave_color = if let WaveformFocus::DrumTrack(idx) = &focus {
                             drum_color(*idx)
                         } else {
@@ -165,11 +326,14 @@ impl eframe::App for AppState {
                             let bh   = (peak * hs * 2.0).min(rect.height() * 0.9);
                             let bt   = cy - bh / 2.0;
                             painter.rect_filled(
-                                egui::Rect::from_min_max(egui::pos2(x, bt), egui::pos2(x + bw - 0.5, bt + bh)),
+                                egui::Rect::from_min_max(egui::pos2(x, bt), egui::pos2(x + bw - gap, bt + bh)),
                                 0.0, wave_color,
                             );
                         }
                         painter.hline(rect.x_range(), cy, egui::Stroke::new(0.5, egui::Color32::from_gray(55)));
+                        if let Some(a) = focused_asset.as_ref() {
+                            draw_bar_grid(&painter, rect, &self.bar_grid_lines(&a.sample_uuid, a.frames as f32 / a.sample_rate.max(1) as f32));
+                        }
 
                         let pointer_pos  = ui.input(|i| i.pointer.hover_pos());
                         let ptr_pressed  = ui.input(|i| i.pointer.primary_pressed());
@@ -209,6 +373,9 @@ impl eframe::App for AppState {
                                     if let (Some(drag_id), Some(pos)) = (dragging_id, pointer_pos) {
                                         if rect.contains(pos) || ptr_down {
                                             let norm = ((pos.x - rect.left()) / w).clamp(0.0, 1.0);
+                                            if self.samples_manager.get_mark_by_id(drag_id).is_some_and(|m| m.position != norm) {
+                                                self.push_undo_gesture(t!("marker move"));
+                                            }
                                             self.samples_manager.update_mark_position_by_id(drag_id, norm);
                                             ui.ctx().request_repaint();
                                         }
@@ -219,6 +386,21 @@ impl eframe::App for AppState {
                                     *self.dragged_mark_index.write() = None;
                                 }
 
+                                // Double-click a marker → open it in the numeric editor
+                                if response.double_clicked() {
+                                    if let Some(pos) = pointer_pos {
+                                        let hit = marks.iter().find(|m| {
+                                            let mx = rect.left() + m.position * w;
+                                            (pos.x - mx).abs() < HIT_PX
+                                        });
+                                        if let Some(m) = hit {
+                                            let mut editor = self.marker_editor.write();
+                                            editor.open = true;
+                                            editor.focus_mark = Some(m.id);
+                                        }
+                                    }
+                                }
+
                                 if let Some(pos) = pointer_pos {
                                     if rect.contains(pos) {
                                         let near_any = marks.iter().any(|m| {
@@ -318,6 +500,21 @@ impl eframe::App for AppState {
                                         );
                                     }
                                 }
+
+                                // Drag selection
+                                if let Some(sel) = self.track_selection(*drum_idx) {
+                                    let (x1, x2) = (rect.left() + sel.from * w, rect.left() + sel.to * w);
+                                    let sel_col = egui::Color32::from_rgb(240, 200, 80);
+                                    painter.rect_filled(
+                                        egui::Rect::from_min_max(egui::pos2(x1, rect.top()), egui::pos2(x2, rect.bottom())),
+                                        0.0, egui::Color32::from_rgba_unmultiplied(240, 200, 80, 35));
+                                    for x in [x1, x2] { painter.vline(x, rect.y_range(), egui::Stroke::new(1.0, sel_col)); }
+                                    let dur_secs = self.drum_tracks.read().get(*drum_idx)
+                                        .map_or(0.0, |t| t.asset.frames as f32 / t.asset.sample_rate.max(1) as f32);
+                                    painter.text(egui::pos2(x1 + 4.0, rect.bottom() - 4.0), egui::Align2::LEFT_BOTTOM,
+                                        format!("{:.3}s", (sel.to - sel.from) * dur_secs),
+                                        egui::FontId::proportional(10.0), sel_col);
+                                }
                             }
                         }
 
@@ -338,8 +535,92 @@ impl eframe::App for AppState {
                             ));
                         }
 
+                        // Hover cursor — remembered for "insert at cursor"
+                        let guide = self.waveform_guide.write().take().filter(|g| g.0).map(|g| g.1);
+                        match pointer_pos.filter(|p| rect.contains(*p)) {
+                            Some(pos) => {
+                                let norm = ((pos.x - rect.left()) / w).clamp(0.0, 1.0);
+                                *self.waveform_cursor.write() = Some(norm);
+                                *self.waveform_guide.write()  = Some((false, norm));
+                                painter.vline(pos.x, rect.y_range(),
+                                    egui::Stroke::new(1.0, egui::Color32::from_rgba_unmultiplied(255,255,255,40)));
+                            }
+                            // Pointer over the pinned panel
+                            None => if let Some(norm) = guide {
+                                painter.vline(rect.left() + norm * w, rect.y_range(),
+                                    egui::Stroke::new(1.0, egui::Color32::from_rgba_unmultiplied(255,255,255,60)));
+                            },
+                        }
+
+                        response.context_menu(|ui| {
+                            if let WaveformFocus::DrumTrack(drum_idx) = &focus {
+                                if self.track_selection(*drum_idx).is_some() {
+                                    if ui.button(t!("▶ Play selection")).clicked() {
+                                        self.play_selection(*drum_idx);
+                                        ui.close_menu();
+                                    }
+                                    if ui.button(t!("＋ Create region from selection")).clicked() {
+                                        self.region_from_selection(*drum_idx);
+                                        ui.close_menu();
+                                    }
+                                    ui.separator();
+                                }
+                            }
+                            let cursor = *self.waveform_cursor.read();
+                            if ui.add_enabled(cursor.is_some(), egui::Button::new(t!("➕ Insert marker here"))).clicked() {
+                                if let Some(c) = cursor { self.insert_marker_at(c); }
+                                ui.close_menu();
+                            }
+                            if ui.button(t!("➕ Insert marker at playhead")).clicked() {
+                                self.insert_marker_at(self.playback_position.load(Ordering::Relaxed));
+                                ui.close_menu();
+                            }
+                            ui.separator();
+                            if ui.button(t!("✏ Edit markers…")).clicked() {
+                                self.marker_editor.write().open = true;
+                                ui.close_menu();
+                            }
+                        });
+
+                        // Keyboard: ←/→ nudge the playhead while the waveform has focus
+                        if response.has_focus() {
+                            ui.memory_mut(|m| m.set_focus_lock_filter(response.id,
+                                egui::EventFilter { horizontal_arrows: true, ..Default::default() }));
+                            let (left, right) = ui.input(|i| (i.key_pressed(egui::Key::ArrowLeft), i.key_pressed(egui::Key::ArrowRight)));
+                            if left || right {
+                                let pos = (self.playback_position.load(Ordering::Relaxed) + if right { 0.01 } else { -0.01 }).clamp(0.0, 1.0);
+                                self.playback_position.store(pos, Ordering::Relaxed);
+                                if let WaveformFocus::DrumTrack(drum_idx) = &focus {
+                                    if let Some(t) = self.drum_tracks.read().get(*drum_idx) {
+                                        self.playback_sample_index.store((pos as f64 * t.asset.pcm.len() as f64) as u64, Ordering::Relaxed);
+                                    }
+                                }
+                            }
+                        }
+
+                        // Dragging across a track (not on a marker) selects; clicking seeks
                         let is_dragging_marker = self.dragged_mark_index.read().is_some();
-                        if !is_dragging_marker && (response.dragged() || response.clicked()) {
+                        let selecting = if let WaveformFocus::DrumTrack(drum_idx) = &focus {
+                            let uuid = self.drum_tracks.read().get(*drum_idx).map(|t| t.sample_uuid);
+                            let at = |x: f32| ((x - rect.left()) / w).clamp(0.0, 1.0);
+                            if let (Some(sample_uuid), false) = (uuid, is_dragging_marker) {
+                                if response.drag_started() {
+                                    if let Some(origin) = ui.input(|i| i.pointer.press_origin()) {
+                                        let anchor = at(origin.x);
+                                        *self.waveform_selection.write() = Some(WaveSelection { sample_uuid, anchor, from: anchor, to: anchor });
+                                    }
+                                }
+                                if response.dragged() {
+                                    if let (Some(pos), Some(sel)) = (pointer_pos, self.waveform_selection.write().as_mut()) {
+                                        let p = at(pos.x);
+                                        (sel.from, sel.to) = (sel.anchor.min(p), sel.anchor.max(p));
+                                    }
+                                }
+                                if response.clicked() { *self.waveform_selection.write() = None; }
+                            }
+                            uuid.is_some()
+                        } else { false };
+                        if !is_dragging_marker && (response.clicked() || (response.dragged() && !selecting)) {
                             if let Some(pos) = ui.input(|i| i.pointer.hover_pos()) {
                                 if rect.contains(pos) {
                                     let normalized = ((pos.x - rect.left()) / rect.width()).clamp(0.0, 1.0);
@@ -355,14 +636,18 @@ impl eframe::App for AppState {
                                     if let Some(sp) = sp {
                                         self.playback_sample_index.store(sp, Ordering::Relaxed);
                                     }
+                                    if response.dragged() {
+                                        if let Some(asset) = focused_asset.as_ref() { self.scrub_grain(asset, normalized); }
+                                    }
                                 }
                             }
                         }
+                        if response.drag_stopped() { self.end_scrub(); }
                     } else {
                         let text = if focused_asset.is_none() {
-                            "No sample loaded – click Load Sample"
+                            t!("No sample loaded – click Load Sample")
                         } else {
-                            "Analyzing waveform..."
+                            t!("Analyzing waveform...")
                         };
                         painter.text(rect.center(), egui::Align2::CENTER_CENTER, text,
                             egui::FontId::monospace(13.0), egui::Color32::from_gray(160));
@@ -380,19 +665,38 @@ impl eframe::App for AppState {
                     if let Some((_file_name, sample_uuid)) = track_info {
                         let marks = self.samples_manager.get_marks_for_sample(&sample_uuid);
                         ui.horizontal(|ui| {
-                            ui.label(egui::RichText::new("Regions").small().color(egui::Color32::from_gray(100)));
+                            if ui.add(egui::Button::new(egui::RichText::new(t!("📍 Markers…")).small())).clicked() {
+                                self.marker_editor.write().open = true;
+                            }
+                            if ui.add(egui::Button::new(egui::RichText::new(t!("✂ Auto-chop…")).small()))
+                                .on_hover_text(t!("Find the hits in this track and pick which ones become chops"))
+                                .clicked()
+                            {
+                                self.start_auto_chop(*drum_idx);
+                            }
+                            ui.separator();
+                            ui.label(egui::RichText::new(t!("Regions")).small().color(egui::Color32::from_gray(100)));
+                            if self.track_selection(*drum_idx).is_some() {
+                                if ui.add(egui::Button::new(egui::RichText::new(t!("＋ Region from Selection")).small())).clicked() {
+                                    self.region_from_selection(*drum_idx);
+                                }
+                                if ui.add(egui::Button::new(egui::RichText::new(t!("▶ Play Selection")).small())).clicked() {
+                                    self.play_selection(*drum_idx);
+                                }
+                            }
 
                             if marks.len() >= 2 {
                                 if ui.add(egui::Button::new(
-                                    egui::RichText::new("＋ Create Region (First→Last Marker)").small()
+                                    egui::RichText::new(t!("＋ Create Region (First→Last Marker)")).small()
                                 )).clicked() {
                                     if let (Some(first), Some(last)) = (marks.first(), marks.last()) {
+                                        self.push_undo(t!("region"));
                                         // ✅ Pass the track's UUID so the region is scoped to it
                                         let region_id = self.samples_manager.create_region(
                                             first.id, last.id, sample_uuid
                                         );
                                         if let Some(region) = self.samples_manager.get_region_by_id(region_id) {
-                                            *self.status.write() = format!("✓ Created region: {}", region.name);
+                                            *self.status.write() = t!("✓ Created region: {}", region.name);
                                         }
                                     }
                                 }
@@ -404,18 +708,26 @@ impl eframe::App for AppState {
                                         egui::RichText::new(format!("🗑 {}", region.name)).small()
                                             .color(egui::Color32::from_rgb(200, 80, 80))
                                     )).clicked() {
+                                        self.push_undo(t!("region delete"));
                                         self.samples_manager.delete_region(region.id);
-                                        *self.status.write() = format!("✓ Deleted region: {}", region.name);
+                                        *self.status.write() = t!("✓ Deleted region: {}", region.name);
                                     }
                                 }
                             } else {
-                                ui.label(egui::RichText::new("Add 2+ markers to create regions").small()
+                                ui.label(egui::RichText::new(t!("Add 2+ markers or drag across the waveform to create regions")).small()
                                     .color(egui::Color32::from_gray(60)));
                             }
                         });
+                        if marks.len() >= 2 {
+                            self.draw_punch_controls(ui, *drum_idx, marks.len());
+                            self.draw_span_tempo(ui, *drum_idx, marks.len());
+                        }
                     }
                 }
+                ui.add_space(4.0);
+                self.draw_sample_pads(ui);
                 ui.add_space(8.0);
+                self.draw_sample_browser(ui);
 
                 // ── Step Sequencer ────────────────────────────────────
                 ui.add_space(8.0);
@@ -429,34 +741,45 @@ impl eframe::App for AppState {
                 ui.add_space(4.0);
                 self.draw_fl_playlist(ui);
 
+                // ── Space — play/stop sequencer when no widget holds keyboard focus ─
+                if ctx.memory(|m| m.focused().is_none()) && ctx.input(|i| i.key_pressed(egui::Key::Space)) {
+                    self.send(AppCommand::TogglePlay);
+                }
+
+                // ── Esc Esc — panic mute ─────────────────────────────────
+                if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    self.stop_chop_audition();
+                    self.handle_escape();
+                }
+                // ── Enter — swap the auditioned B chop into the track ────
+                if self.chop_audition.read().is_some() && !ctx.wants_keyboard_input()
+                    && ctx.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    self.swap_audition_into_track();
+                }
+
+                // ── Pad keys (1234 / QWER / ASDF / ZXCV) and Ctrl+S ──────
+                self.handle_pad_keys(ctx);
+                if ctx.input(|i| i.modifiers.command && i.key_pressed(egui::Key::S)) {
+                    self.save_project();
+                }
+
+                // ── Ctrl+Z / Ctrl+Shift+Z — undo / redo ──────────────
+                if !ctx.wants_keyboard_input() && ctx.input(|i| i.modifiers.command && i.key_pressed(egui::Key::Z)) {
+                    self.send(if ctx.input(|i| i.modifiers.shift) { AppCommand::Redo } else { AppCommand::Undo });
+                }
+
                 // ── M key — mark chop point ──────────────────────────
-                if self.is_playing.load(Ordering::Relaxed) {
+                if self.is_playing.load(Ordering::Relaxed) && !ctx.wants_keyboard_input() {
                     if ctx.input(|i| i.key_pressed(egui::Key::M)) {
-                        let pos = self.playback_position.load(Ordering::Relaxed);
-                        let focus = self.waveform_focus.read().clone();
-                        if let WaveformFocus::DrumTrack(idx) = focus {
-                            let info = {
-                                let tracks = self.drum_tracks.read();
-                                tracks.get(idx).map(|t| (
-                                    t.asset.file_name.clone(),
-                                    t.asset.frames as f32 / t.asset.sample_rate as f32,
-                                    t.sample_uuid,  // ✅ pass the track's UUID
-                                ))
-                            };
-                            if let Some((file_name, dur, sample_uuid)) = info {
-                                // ✅ mark_current_position now takes a UUID — marks are
-                                //    scoped to this exact load instance
-                                self.samples_manager.mark_current_position(
-                                    sample_uuid, &file_name, pos
-                                );
-                                *self.status.write() = format!("✓ Chopped {} at {:.2}s", file_name, pos * dur);
-                                ctx.request_repaint();
-                            }
+                        let pos = self.quantize_mark_position(self.playback_position.load(Ordering::Relaxed));
+                        if self.insert_marker_at(pos).is_some() {
+                            ctx.request_repaint();
                         }
                     }
                 }
 
-                if self.loading.load(Ordering::Relaxed) || self.drum_loading.load(Ordering::Relaxed) {
+                // A single file decoding; longer loads show their progress under ⏳ Tasks
+                if self.jobs.list().iter().any(|j| j.kind == JobKind::Load && j.state == JobState::Running && j.progress.is_none()) {
                     let sr = ctx.screen_rect();
                     let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("loading")));
                     painter.rect_filled(sr, 0.0, egui::Color32::from_black_alpha(180));
@@ -469,7 +792,7 @@ impl eframe::App for AppState {
                         let alpha = (100.0 + (i as f32 / 8.0) * 155.0) as u8;
                         painter.circle_filled(egui::pos2(c.x+off.x, c.y+off.y-10.0), 6.0, egui::Color32::from_rgba_unmultiplied(80,160,255,alpha));
                     }
-                    painter.text(egui::pos2(c.x, c.y+25.0), egui::Align2::CENTER_TOP, "Loading...", egui::FontId::proportional(16.0), egui::Color32::WHITE);
+                    painter.text(egui::pos2(c.x, c.y+25.0), egui::Align2::CENTER_TOP, t!("Loading..."), egui::FontId::proportional(16.0), egui::Color32::WHITE);
                 }
                 ctx.request_repaint_after(Duration::from_millis(16));
             });
@@ -1,6 +1,7 @@
 use eframe::egui;
 use crate::adsr::ADSREnvelope;
-use crate::gui::NUM_STEPS;
+use crate::category::DrumCategory;
+use crate::step_pitch::RANGE;
 
 const PAD_COLORS: &[(u8, u8, u8)] = &[
     (80, 160, 255), (80, 220, 140), (240, 160, 60), (200, 80, 200),
@@ -21,6 +22,45 @@ pub fn pad_color_dim(idx: usize) -> egui::Color32 {
 pub fn drum_color(idx: usize) -> egui::Color32 { pad_color(idx + 4) }
 pub fn drum_color_dim(idx: usize) -> egui::Color32 { pad_color_dim(idx + 4) }
 
+pub fn category_color(c: DrumCategory) -> egui::Color32 {
+    let (r, g, b) = c.rgb();
+    egui::Color32::from_rgb(r, g, b)
+}
+
+pub fn category_color_dim(c: DrumCategory) -> egui::Color32 {
+    let (r, g, b) = c.rgb();
+    egui::Color32::from_rgb(r / 5, g / 5, b / 5)
+}
+
+/// Outline drawn around a custom-painted widget while it has keyboard focus.
+pub fn draw_focus_ring(ui: &egui::Ui, rect: egui::Rect, resp: &egui::Response) {
+    if resp.has_focus() {
+        ui.painter().rect_stroke(rect.expand(1.5), 3.0, ui.visuals().selection.stroke);
+    }
+}
+
+/// Bar and beat lines over a waveform, from `bar_grid_lines`.
+pub fn draw_bar_grid(painter: &egui::Painter, rect: egui::Rect, lines: &[(f32, bool)]) {
+    for &(pos, bar) in lines {
+        let alpha = if bar { 90 } else { 30 };
+        painter.vline(rect.left() + pos * rect.width(), rect.y_range(),
+            egui::Stroke::new(1.0, egui::Color32::from_rgba_unmultiplied(240, 200, 80, alpha)));
+    }
+}
+
+/// Vertical peak meter filling `rect` from the bottom; `peak` is linear, shown over 48 dB.
+pub fn draw_row_meter(painter: &egui::Painter, rect: egui::Rect, peak: f32) {
+    painter.rect_filled(rect, 1.0, egui::Color32::from_gray(28));
+    let db = 20.0 * peak.max(1e-6).log10();
+    let fill = ((db + 48.0) / 48.0).clamp(0.0, 1.0);
+    if fill <= 0.0 { return; }
+    let color = if db > -1.0 { egui::Color32::from_rgb(240, 80, 70) }
+        else if db > -9.0 { egui::Color32::from_rgb(230, 200, 70) }
+        else { egui::Color32::from_rgb(90, 210, 120) };
+    let top = rect.bottom() - fill * rect.height();
+    painter.rect_filled(egui::Rect::from_min_max(egui::pos2(rect.left(), top), rect.right_bottom()), 1.0, color);
+}
+
 pub fn draw_knob(
     painter: &egui::Painter,
     ui: &mut egui::Ui,
@@ -39,6 +79,16 @@ pub fn draw_knob(
     let resp = ui.interact(rect, id, egui::Sense::click_and_drag());
     let mut changed = false;
 
+    // Keyboard: ↑/↓ nudge while focused (←/→ keep moving focus)
+    if resp.has_focus() {
+        ui.memory_mut(|m| m.set_focus_lock_filter(id, egui::EventFilter { vertical_arrows: true, ..Default::default() }));
+        let (up, down) = ui.input(|i| (i.key_pressed(egui::Key::ArrowUp), i.key_pressed(egui::Key::ArrowDown)));
+        if up || down {
+            *value = (*value + if up { 0.05 } else { -0.05 }).clamp(0.0, 1.0);
+            changed = true;
+        }
+    }
+
     if resp.dragged() {
         let delta = resp.drag_delta();
         let change = (-delta.y / 80.0).clamp(-1.0, 1.0);
@@ -88,30 +138,122 @@ pub fn draw_knob(
     if resp.hovered() {
         painter.circle_stroke(center, radius, egui::Stroke::new(1.5, egui::Color32::from_rgba_unmultiplied(255,255,255,60)));
     }
+    if resp.has_focus() {
+        painter.circle_stroke(center, radius + 2.0, ui.visuals().selection.stroke);
+    }
+    let v = *value;
+    resp.widget_info(|| egui::WidgetInfo::slider(v as f64, label));
     changed
 }
 
+/// Velocities of a step row and where changes to them go.
+pub struct StepVelocity<'a> {
+    pub values:    &'a [f32],
+    pub on_change: &'a mut dyn FnMut(usize, f32),
+}
+
+/// Semitone offsets of a step row and where changes to them go.
+pub struct StepPitches<'a> {
+    pub values:    &'a [i8],
+    pub on_change: &'a mut dyn FnMut(usize, i8),
+}
+
+/// With `velocity`, each hit's fill height shows its level, and scrolling over a hit or
+/// right-dragging it up and down sets it. With `pitch`, shift-scrolling over a hit moves
+/// it a semitone a notch and alt-clicking opens a box to type it. Returns the step
+/// right-clicked, if any.
 pub fn draw_step_buttons(
     ui: &mut egui::Ui,
+    row_label: &str,
     step_w: f32, row_h: f32,
     color: egui::Color32, color_dim: egui::Color32,
-    is_ons: &[bool; NUM_STEPS],
+    is_ons: &[bool], per_beat: usize,
     current_step: usize, seq_playing: bool,
     on_toggle: &mut dyn FnMut(usize),
-) {
-    for step in 0..NUM_STEPS {
-        let is_on = is_ons[step];
+    mut velocity: Option<StepVelocity>,
+    mut pitch: Option<StepPitches>,
+) -> Option<usize> {
+    // One live step per entry, shaded by beat
+    let per_beat = per_beat.max(1);
+    let sense = if velocity.is_some() { egui::Sense::click_and_drag() } else { egui::Sense::click() };
+    let mut right_clicked = None;
+    // Hold-and-drag paints the row: every step the pointer crosses is set to the opposite
+    // of the step the drag began on, so starting on an empty step fills, on a lit one clears
+    let stroke_id = egui::Id::new("step_stroke");
+    let row_id    = egui::Id::new(row_label);
+    let (down, pressed, pointer, alt) = ui.input(|i|
+        (i.pointer.primary_down(), i.pointer.primary_pressed(), i.pointer.interact_pos(), i.modifiers.alt));
+    let held = if pressed { None } else { ui.data(|d| d.get_temp::<StepStroke>(stroke_id)) };
+    let mut stroke = held.filter(|_| down);
+    // A stroke that just ended already toggled the step it began on
+    let painted = held.is_some_and(|s| !down && s.painting && s.row == row_id);
+    for (step, &is_on) in is_ons.iter().enumerate() {
         let is_cur = seq_playing && current_step == step;
         let sz = egui::vec2(step_w - 2.0, row_h);
-        let (sr, sresp) = ui.allocate_exact_size(sz, egui::Sense::click());
-        let grp = step / 4;
-        let bg = if grp % 2 == 0 { egui::Color32::from_rgb(25,25,33) } else { egui::Color32::from_rgb(21,21,29) };
+        let (sr, sresp) = ui.allocate_exact_size(sz, sense);
+        let grp = step / per_beat;
+        let bg = if grp.is_multiple_of(2) { egui::Color32::from_rgb(25,25,33) } else { egui::Color32::from_rgb(21,21,29) };
+        let inner = sr.shrink(2.0);
+        let mut vel = velocity.as_ref().and_then(|v| v.values.get(step).copied()).unwrap_or(1.0);
+        if is_on {
+            if let Some(v) = velocity.as_mut() {
+                let scroll = if sresp.hovered() {
+                    ui.input_mut(|i| std::mem::take(&mut i.smooth_scroll_delta.y))
+                } else { 0.0 };
+                let dragged = sresp.dragged_by(egui::PointerButton::Secondary);
+                let before  = vel;
+                if scroll != 0.0 { vel += scroll / 400.0; }
+                if let (true, Some(p)) = (dragged, sresp.interact_pointer_pos()) {
+                    vel = (inner.bottom() - p.y) / inner.height();
+                }
+                vel = vel.clamp(0.05, 1.0);
+                if vel != before { (v.on_change)(step, vel); }
+            }
+        }
+        let mut semi = pitch.as_ref().and_then(|p| p.values.get(step).copied()).unwrap_or(0);
+        let pitch_popup = sresp.id.with("pitch");
+        if is_on {
+            if let Some(p) = pitch.as_mut() {
+                let before = semi;
+                // Shift turns the wheel sideways; one semitone per notch
+                let notch = if sresp.hovered() {
+                    ui.input_mut(|i| { i.smooth_scroll_delta.x = 0.0; std::mem::take(&mut i.raw_scroll_delta.x) })
+                } else { 0.0 };
+                if notch != 0.0 { semi += notch.signum() as i8; }
+                if ui.memory(|m| m.is_popup_open(pitch_popup)) {
+                    let area = egui::Area::new(pitch_popup)
+                        .order(egui::Order::Foreground)
+                        .constrain(true)
+                        .fixed_pos(sr.left_bottom())
+                        .show(ui.ctx(), |ui| {
+                            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                                ui.horizontal(|ui| {
+                                    ui.add(egui::DragValue::new(&mut semi).clamp_range(-RANGE..=RANGE).suffix(t!(" st")));
+                                    if ui.small_button("0").on_hover_text(t!("Back to the sample's pitch")).clicked() { semi = 0; }
+                                });
+                            });
+                        });
+                    let outside = ui.input(|i| i.pointer.any_click() && i.pointer.interact_pos().is_some_and(|pos| !area.response.rect.contains(pos)));
+                    if ui.input(|i| i.key_pressed(egui::Key::Escape)) || (outside && !sresp.hovered()) {
+                        ui.memory_mut(|m| m.close_popup());
+                    }
+                }
+                semi = semi.clamp(-RANGE, RANGE);
+                if semi != before { (p.on_change)(step, semi); }
+            }
+        }
         ui.painter().rect_filled(sr, 2.0, bg);
-        ui.painter().rect_filled(sr.shrink(2.0), 2.0, if is_on { color } else { color_dim });
-        
+        ui.painter().rect_filled(inner, 2.0, color_dim);
         if is_on {
-            ui.painter().hline(sr.shrink(2.0).x_range(), sr.shrink(2.0).top() + 1.5,
+            // Filled from the bottom up to the step's velocity
+            let fill = egui::Rect::from_min_max(egui::pos2(inner.left(), inner.bottom() - inner.height() * vel), inner.max);
+            ui.painter().rect_filled(fill, 2.0, color);
+            ui.painter().hline(fill.x_range(), fill.top() + 1.5,
                 egui::Stroke::new(1.5, egui::Color32::from_rgba_unmultiplied(255,255,255,70)));
+            if semi != 0 {
+                ui.painter().text(inner.center_top() + egui::vec2(0.0, 1.0), egui::Align2::CENTER_TOP,
+                    format!("{:+}", semi), egui::FontId::proportional(9.0), egui::Color32::from_gray(235));
+            }
         }
         if is_cur {
             ui.painter().rect_filled(sr, 2.0, egui::Color32::from_rgba_unmultiplied(255,220,80,45));
@@ -122,8 +264,64 @@ pub fn draw_step_buttons(
         if sresp.hovered() {
             ui.painter().rect_stroke(sr, 2.0, egui::Stroke::new(1.0, egui::Color32::from_rgba_unmultiplied(255,255,255,50)));
         }
-        if sresp.clicked() { on_toggle(step); }
+        draw_focus_ring(ui, sr, &sresp);
+        sresp.widget_info(|| egui::WidgetInfo::selected(
+            egui::WidgetType::Checkbox, is_on,
+            format!("{} step {}{}{}{}", row_label, step + 1,
+                if is_on && vel < 1.0 { format!(", velocity {:.0}%", vel * 100.0) } else { String::new() },
+                if is_on && semi != 0 { format!(", {:+} semitones", semi) } else { String::new() },
+                if is_cur { t!(", playing") } else { "" }),
+        ));
+        if down && pressed && !alt && stroke.is_none() && sresp.is_pointer_button_down_on() {
+            stroke = Some(StepStroke { row: row_id, origin: step, on: !is_on, painting: false });
+        }
+        if let Some(s) = stroke.as_mut().filter(|s| s.row == row_id) {
+            if pointer.is_some_and(|p| sr.contains(p)) && (s.painting || step != s.origin) {
+                if !s.painting {
+                    s.painting = true;
+                    on_toggle(s.origin);
+                }
+                if step != s.origin && is_on != s.on { on_toggle(step); }
+            }
+        }
+        if sresp.clicked() && is_on && pitch.is_some() && alt {
+            ui.memory_mut(|m| m.toggle_popup(pitch_popup));
+        } else if sresp.clicked() && !painted {
+            on_toggle(step);
+        }
+        if sresp.secondary_clicked() { right_clicked = Some(step); }
+    }
+    match stroke {
+        Some(s) if s.row == row_id => ui.data_mut(|d| d.insert_temp(stroke_id, s)),
+        _ if !down && held.is_some_and(|s| s.row == row_id) => ui.data_mut(|d| d.remove::<StepStroke>(stroke_id)),
+        _ => {}
     }
+    right_clicked
+}
+
+/// A drag painting across one row of steps.
+#[derive(Clone, Copy)]
+struct StepStroke {
+    row:      egui::Id,
+    /// Step the drag began on.
+    origin:   usize,
+    /// What the crossed steps are set to.
+    on:       bool,
+    /// The pointer has left `origin`, so this is a paint rather than a click.
+    painting: bool,
+}
+
+/// "3" toggle that switches a step row onto the triplet lane.
+/// Row triplet toggle; `enabled` is off on a triplet grid, where it can only be switched back.
+pub fn triplet_button(ui: &mut egui::Ui, triplet: bool, n_trip: usize, row_h: f32, enabled: bool) -> egui::Response {
+    let col = if triplet { egui::Color32::from_rgb(240, 200, 60) } else { egui::Color32::from_gray(90) };
+    ui.add_enabled(enabled,
+        egui::Button::new(egui::RichText::new("3").size(11.0).color(col))
+            .min_size(egui::vec2(20.0, row_h))
+            .selected(triplet)
+    )
+    .on_hover_text(t!("Triplet lane ({} steps per bar)", n_trip))
+    .on_disabled_hover_text(t!("The steps already have a triplet feel"))
 }
 
 pub fn draw_adsr_knobs(
@@ -157,4 +355,31 @@ pub fn draw_adsr_knobs(
         }
     }
     (adsr, changed)
-}
\ No newline at end of file
+}
+/// Envelope section of a row's context menu: on/off, typed A/D/S/R and presets.
+/// Returns whether anything changed.
+pub fn adsr_menu(ui: &mut egui::Ui, adsr: &mut ADSREnvelope, enabled: &mut bool) -> bool {
+    let mut changed = ui.checkbox(enabled, t!("Envelope")).on_hover_text(t!("Off plays the sample at full level")).changed();
+    ui.add_enabled_ui(*enabled, |ui| {
+        egui::Grid::new(ui.next_auto_id()).num_columns(2).spacing([8.0, 2.0]).show(ui, |ui| {
+            let params: [(&str, f32, &mut f32, &str); 4] = [
+                (t!("Attack"),  2.0, &mut adsr.attack,  t!(" s")),
+                (t!("Decay"),   2.0, &mut adsr.decay,   t!(" s")),
+                (t!("Sustain"), 1.0, &mut adsr.sustain, ""),
+                (t!("Release"), 3.0, &mut adsr.release, t!(" s")),
+            ];
+            for (label, max, val, suffix) in params {
+                ui.label(label);
+                changed |= ui.add(egui::DragValue::new(val).speed(0.005).clamp_range(0.0..=max)
+                    .fixed_decimals(3).suffix(suffix)).changed();
+                ui.end_row();
+            }
+        });
+        ui.horizontal(|ui| {
+            for (name, preset) in [(t!("Percussive"), ADSREnvelope::percussive()), (t!("Pad"), ADSREnvelope::pad()), (t!("Pluck"), ADSREnvelope::pluck())] {
+                if ui.small_button(name).clicked() { *adsr = preset; changed = true; }
+            }
+        });
+    });
+    changed
+}
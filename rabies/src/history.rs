@@ -0,0 +1,168 @@
+// src/history.rs
+// Undo and redo for the edits that shape a beat: steps, markers, regions, adding and
+// removing tracks, the tempo, and the pattern tools. Each edit records the state from
+// just before it (every pattern with its tracks, marks and regions, the recording
+// tracks' steps, the step count, the BPM and the main sample's marks); undo puts that
+// back, dropping patterns made since, and keeps what it replaced for redo. A new edit
+// clears the redo side.
+//
+// Edits that stream in — painting a row of steps, dragging a marker — make one entry
+// per gesture, closed when the pointer is let go; tempo changes within a second of each
+// other (a dragged BPM field, a knob) make one entry too. Ctrl+Z undoes, Ctrl+Shift+Z
+// redoes, and both are `AppCommand`s so any front end can send them.
+
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+use crate::bus::EngineEvent;
+use crate::gui::AppState;
+use crate::pattern::Pattern;
+use crate::samples::{CustomRegion, SampleMark};
+
+/// Edits that can be undone.
+const UNDO_DEPTH: usize = 32;
+
+/// Tempo changes closer together than this are one edit.
+const TEMPO_MERGE: Duration = Duration::from_secs(1);
+
+/// Everything an edit may change, from just before it ran.
+pub struct Snapshot {
+    label:       String,
+    patterns:    Vec<Pattern>,
+    len:         usize,
+    rec_steps:   Vec<Vec<bool>>,
+    bpm:         f32,
+    /// Markers and regions on the main sample, which no pattern keeps.
+    main_sample: Option<(Uuid, Vec<SampleMark>, Vec<CustomRegion>)>,
+}
+
+#[derive(Default)]
+pub struct History {
+    undo:     Vec<Snapshot>,
+    redo:     Vec<Snapshot>,
+    /// Label of the gesture under way; same-label edits join its entry.
+    gesture:  Option<String>,
+    tempo_at: Option<Instant>,
+}
+
+impl AppState {
+    /// The state as it is now, with the live pattern written back first.
+    fn snapshot(&self, label: &str) -> Snapshot {
+        self.save_current_pattern_state();
+        let main_sample = self.current_asset.read().as_ref().map(|a| {
+            let (marks, regions) = self.samples_manager.sample_state(&a.sample_uuid);
+            (a.sample_uuid, marks, regions)
+        });
+        Snapshot {
+            label:     label.to_string(),
+            patterns:  self.song_editor.get_all_patterns(),
+            len:       self.pattern_len(),
+            rec_steps: self.rec_tracks.read().iter().map(|t| t.steps.clone()).collect(),
+            bpm:       self.events.bpm(),
+            main_sample,
+        }
+    }
+
+    fn restore(&self, snap: Snapshot) {
+        self.song_editor.restore_patterns(snap.patterns);
+        for (t, steps) in self.rec_tracks.write().iter_mut().zip(snap.rec_steps) { t.steps = steps; }
+        self.set_tool_len(snap.len);
+        if let Some((uuid, marks, regions)) = snap.main_sample {
+            self.samples_manager.restore_sample_state(&uuid, marks, regions);
+        }
+        self.events.emit(EngineEvent::Bpm { bpm: snap.bpm });
+        self.reload_active_pattern();
+    }
+
+    /// Record the state before an edit.
+    pub(crate) fn push_undo(&self, label: &str) {
+        let snap = self.snapshot(label);
+        let mut history = self.history.lock();
+        history.undo.push(snap);
+        if history.undo.len() > UNDO_DEPTH { history.undo.remove(0); }
+        history.redo.clear();
+        history.gesture  = None;
+        history.tempo_at = None;
+    }
+
+    /// `push_undo` for an edit repeated while the pointer is held: the first one records.
+    pub(crate) fn push_undo_gesture(&self, label: &str) {
+        if self.history.lock().gesture.as_deref() == Some(label) { return; }
+        self.push_undo(label);
+        self.history.lock().gesture = Some(label.to_string());
+    }
+
+    /// Before a tempo change; a run of them is one edit.
+    pub(crate) fn push_tempo_undo(&self) {
+        let recent = self.history.lock().tempo_at.is_some_and(|at| at.elapsed() < TEMPO_MERGE);
+        if !recent { self.push_undo(t!("tempo")); }
+        self.history.lock().tempo_at = Some(Instant::now());
+    }
+
+    /// Called every frame: letting go of the pointer ends the gesture.
+    pub(crate) fn tick_history(&self, pointer_down: bool) {
+        if !pointer_down { self.history.lock().gesture = None; }
+    }
+
+    /// What the next undo takes back.
+    pub fn undo_label(&self) -> Option<String> {
+        self.history.lock().undo.last().map(|s| s.label.clone())
+    }
+
+    /// What the next redo puts back.
+    pub fn redo_label(&self) -> Option<String> {
+        self.history.lock().redo.last().map(|s| s.label.clone())
+    }
+
+    /// Take back the last edit; false when there is none.
+    pub fn undo(&self) -> bool {
+        let Some(snap) = self.history.lock().undo.pop() else { return false; };
+        let now = self.snapshot(&snap.label);
+        let label = snap.label.clone();
+        self.restore(snap);
+        *self.status.write() = t!("↶ Undid {}", label);
+        let mut history = self.history.lock();
+        history.redo.push(now);
+        history.gesture  = None;
+        history.tempo_at = None;
+        true
+    }
+
+    /// Do the last undone edit again; false when there is none.
+    pub fn redo(&self) -> bool {
+        let Some(snap) = self.history.lock().redo.pop() else { return false; };
+        let now = self.snapshot(&snap.label);
+        let label = snap.label.clone();
+        self.restore(snap);
+        *self.status.write() = t!("↷ Redid {}", label);
+        let mut history = self.history.lock();
+        history.undo.push(now);
+        history.gesture  = None;
+        history.tempo_at = None;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gui::AppState;
+
+    #[test]
+    fn undo_takes_back_a_pattern_pasted_as_new() {
+        let app = AppState::default();
+        app.copy_pattern(0);
+        app.paste_pattern_as_new();
+        assert_eq!(app.song_editor.pattern_count(), 2);
+        app.song_editor.toggle_block(1, 0);
+        app.song_editor.set_active_edit_idx(1);
+
+        assert!(app.undo());
+        assert_eq!(app.song_editor.pattern_count(), 1);
+        assert_eq!(app.song_editor.arrangement.read().len(), 1);
+        assert_eq!(app.song_editor.active_edit_idx(), 0);
+        assert!(app.song_editor.arrangement.read().iter().flatten().all(|c| c.is_none_or(|p| p < 1)));
+
+        assert!(app.redo());
+        assert_eq!(app.song_editor.pattern_count(), 2);
+        assert_eq!(app.song_editor.arrangement.read().len(), 2);
+    }
+}
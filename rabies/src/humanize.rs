@@ -0,0 +1,80 @@
+// src/humanize.rs
+// Humanize: every hit on a row lands a little off the grid and a little louder or softer,
+// rolled fresh each time it plays, so a programmed part loosens up without a single step
+// being moved — switch it off and the grid is exactly as it was. The amount (0-1) can be
+// set per row, and for the whole project on top; a hit at full amount moves up to ±12 ms
+// and ±30 % in level. Hits can land early, so a humanized row is scheduled the way an
+// early row offset is, from the step before. Bounces reset the dice and sound the same
+// each time.
+
+use serde::{Deserialize, Serialize};
+use crate::adsr::Voice;
+use crate::gui::DrumTrack;
+use crate::row_timing;
+use crate::variation::Dice;
+
+/// Furthest a hit moves either side of its spot at full amount, ms.
+pub const MAX_MS: f32 = 12.0;
+/// Furthest a hit's level moves either way at full amount.
+pub const MAX_LEVEL: f32 = 0.3;
+/// The timing spread stays within this share of a step either side, however slow the tempo.
+const MAX_SPREAD: f64 = 0.2;
+/// Amounts smaller than this are off.
+const MIN_AMOUNT: f32 = 0.01;
+
+/// Row `chop` humanized by `amount`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RowHumanize {
+    /// Chop row, or `None` for the track's own row.
+    pub chop:   Option<usize>,
+    /// 0-1.
+    pub amount: f32,
+}
+
+/// Humanize amount of row `chop`; 0 when it has none.
+pub fn amount(rows: &[RowHumanize], chop: Option<usize>) -> f32 {
+    rows.iter().find(|r| r.chop == chop).map_or(0.0, |r| r.amount)
+}
+
+/// Humanize row `chop` by `amount`; a tiny one switches it off.
+pub fn set(rows: &mut Vec<RowHumanize>, chop: Option<usize>, amount: f32) {
+    rows.retain(|r| r.chop != chop);
+    let amount = amount.clamp(0.0, 1.0);
+    if amount >= MIN_AMOUNT { rows.push(RowHumanize { chop, amount }); }
+}
+
+/// Forget chop `removed`: its amount goes, and later chops shift down one.
+pub fn remove_chop(rows: &mut Vec<RowHumanize>, removed: usize) {
+    rows.retain(|r| r.chop != Some(removed));
+    for r in rows.iter_mut() {
+        if let Some(c) = r.chop.as_mut().filter(|c| **c > removed) { *c -= 1; }
+    }
+}
+
+/// Where a row sits against the grid this step: its timing offset and its humanize.
+pub struct RowFeel {
+    /// Offset to schedule the row's hits at, in steps of its lane: the row's own,
+    /// moved early by the humanize spread so hits can roll to either side of it.
+    pub offset: f32,
+    amount:     f32,
+    /// Timing spread either side, seconds.
+    spread:     f64,
+}
+
+impl RowFeel {
+    /// Row `chop` of `track`, with `global` humanize on top, on a lane of `lane_secs` steps.
+    pub fn new(track: &DrumTrack, chop: Option<usize>, global: f32, lane_secs: f64) -> Self {
+        let amount = (amount(&track.row_humanize, chop) + global).min(1.0);
+        let amount = if amount >= MIN_AMOUNT { amount } else { 0.0 };
+        let spread = (amount as f64 * MAX_MS as f64 / 1000.0).min(MAX_SPREAD * lane_secs);
+        let offset = row_timing::fraction(&track.row_offsets, chop) - (spread / lane_secs.max(1e-6)) as f32;
+        Self { offset, amount, spread }
+    }
+
+    /// Roll this hit's place and level.
+    pub fn apply(&self, voice: &mut Voice, dice: &mut Dice) {
+        if self.amount == 0.0 { return; }
+        voice.delay += dice.roll() as f64 * 2.0 * self.spread;
+        voice.level *= 1.0 + (dice.roll() * 2.0 - 1.0) * self.amount * MAX_LEVEL;
+    }
+}
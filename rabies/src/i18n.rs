@@ -0,0 +1,152 @@
+// src/i18n.rs
+// UI text in the user's language. Strings are written in English where they're used and
+// looked up in the picked language's catalog, keyed by that English text; anything the
+// catalog lacks stays English, so a missing entry never blanks a control. `t!` is
+// `format!` with the template looked up first. It understands `{}`, `{:.N}`, `{:+}`,
+// `{:0N}` and `{:>N}`, and a translation can take its arguments out of order as `{0}`,
+// `{1}`, … The language comes from the environment (LC_ALL, LC_MESSAGES, LANG) and can
+// be changed in the settings window.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+mod es;
+
+/// `t!("Open")` is the text in the current language; with arguments it formats like `format!`.
+macro_rules! t {
+    ($text:literal) => { $crate::i18n::tr($text) };
+    ($fmt:literal, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::format($crate::i18n::tr($fmt), &[$(&$arg as &dyn std::fmt::Display),+])
+    };
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    English,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Spanish];
+
+    /// The language's own name for itself, so it can be found whatever is showing.
+    pub fn label(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+        }
+    }
+
+    fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+        }
+    }
+
+    /// The first of LC_ALL, LC_MESSAGES and LANG that's set ("es_ES.UTF-8" → Spanish);
+    /// English if none is, or it names a language without a catalog.
+    pub fn from_env() -> Self {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+            .filter_map(|v| std::env::var(v).ok())
+            .find(|v| !v.is_empty())
+            .unwrap_or_default();
+        let code = locale.split(['_', '.', '-']).next().unwrap_or("").to_lowercase();
+        Self::ALL.into_iter().find(|l| l.code() == code).unwrap_or(Language::English)
+    }
+
+    fn catalog(self) -> Option<&'static HashMap<&'static str, &'static str>> {
+        static SPANISH: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+        match self {
+            Language::English => None,
+            Language::Spanish => Some(SPANISH.get_or_init(|| es::CATALOG.iter().copied().collect())),
+        }
+    }
+}
+
+static LANGUAGE: AtomicU8 = AtomicU8::new(0);
+
+pub fn language() -> Language {
+    Language::ALL.get(LANGUAGE.load(Ordering::Relaxed) as usize).copied().unwrap_or(Language::English)
+}
+
+pub fn set_language(language: Language) {
+    LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+/// `text` in the current language.
+pub fn tr(text: &'static str) -> &'static str {
+    language().catalog().and_then(|c| c.get(text).copied()).unwrap_or(text)
+}
+
+/// Fill `template`'s placeholders from `args`, in order or by `{n}`.
+pub fn format(template: &str, args: &[&dyn Display]) -> String {
+    let mut out  = String::with_capacity(template.len() + 16);
+    let mut next = 0;
+    let mut rest = template;
+    while let Some(i) = rest.find(['{', '}']) {
+        out.push_str(&rest[..i]);
+        let tail = &rest[i..];
+        if tail.starts_with("{{") || tail.starts_with("}}") || tail.starts_with('}') {
+            out.push_str(&tail[..1]);
+            rest = &tail[if tail[1..].starts_with(&tail[..1]) { 2 } else { 1 }..];
+            continue;
+        }
+        let Some(end) = tail.find('}') else { out.push_str(tail); return out; };
+        let (index, spec) = tail[1..end].split_once(':').unwrap_or((&tail[1..end], ""));
+        let arg = index.parse::<usize>().unwrap_or_else(|_| { next += 1; next - 1 });
+        if let Some(a) = args.get(arg) { out.push_str(&format_arg(*a, spec)); }
+        rest = &tail[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// One argument under a `[+][0|>][width][.precision]` spec.
+fn format_arg(arg: &dyn Display, spec: &str) -> String {
+    let (plus, spec) = spec.strip_prefix('+').map_or((false, spec), |s| (true, s));
+    let (width, precision) = spec.split_once('.').map_or((spec, None), |(w, p)| (w, p.parse::<usize>().ok()));
+    if let Some(w) = width.strip_prefix('>').and_then(|w| w.parse::<usize>().ok()) {
+        return format!("{:>w$}", arg, w = w);
+    }
+    if let Some(w) = width.strip_prefix('0').and_then(|w| w.parse::<usize>().ok()) {
+        return format!("{:0w$}", arg, w = w);
+    }
+    match (plus, precision) {
+        (false, None)    => arg.to_string(),
+        (false, Some(p)) => format!("{:.*}", p, arg),
+        (true, None)     => format!("{:+}", arg),
+        (true, Some(p))  => format!("{:+.*}", p, arg),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format;
+
+    #[test]
+    fn fills_placeholders_in_order() {
+        assert_eq!(format("{} of {}", &[&3, &"four"]), "3 of four");
+        assert_eq!(format("{1} before {0}", &[&"a", &"b"]), "b before a");
+        assert_eq!(format("{{literal}} {}", &[&1]), "{literal} 1");
+    }
+
+    #[test]
+    fn applies_format_specs() {
+        assert_eq!(format("{:.1} BPM", &[&120.25f32]), "120.2 BPM");
+        assert_eq!(format("{:.0}%", &[&99.6f32]), "100%");
+        assert_eq!(format("{:+} st", &[&3]), "+3 st");
+        assert_eq!(format("{:03}|{:>4}", &[&7, &"x"]), "007|   x");
+    }
+
+    #[test]
+    fn missing_arguments_leave_the_placeholder_empty() {
+        assert_eq!(format("{} and {}", &[&"one"]), "one and ");
+        assert_eq!(format("{2}", &[&"a"]), "");
+        assert_eq!(format("no args {:.1}", &[]), "no args ");
+        // An unclosed brace is kept as written
+        assert_eq!(format("open {", &[&1]), "open {");
+    }
+}
@@ -0,0 +1,994 @@
+// src/i18n/es.rs
+// Spanish catalog: English UI text → Spanish. Anything missing here shows in English.
+
+pub const CATALOG: &[(&str, &str)] = &[
+    ("no valid audio track found", "no se encontró ninguna pista de audio válida"),
+    ("unknown sample rate", "frecuencia de muestreo desconocida"),
+    ("unknown channels", "número de canales desconocido"),
+    ("no audio samples decoded", "no se decodificó ninguna muestra de audio"),
+    ("Transients", "Transitorios"),
+    ("Drum stem", "Stem de batería"),
+    ("✗ No new hits found in {}", "✗ No se encontraron golpes nuevos en {}"),
+    ("✗ The track changed before the chops were added", "✗ La pista cambió antes de añadir los cortes"),
+    ("✓ Added {} chop{} to {}", "✓ {0} corte(s) añadido(s) a {2}"),
+    ("System", "Sistema"),
+    ("This build has no JACK support (rebuild with --features jack)", "Esta compilación no admite JACK (recompila con --features jack)"),
+    ("unknown panic", "pánico desconocido"),
+    ("✗ Audio engine crashed: {} — press ⟳ Restart engine", "✗ El motor de audio falló: {} — pulsa ⟳ Reiniciar motor"),
+    ("Audio config error: {}", "Error de configuración de audio: {}"),
+    ("Unsupported sample format", "Formato de muestra no compatible"),
+    ("Stream error ({}): {}", "Error de flujo ({}): {}"),
+    ("Playback error: {}", "Error de reproducción: {}"),
+    ("JACK: can't connect as '{}' ({})", "JACK: no se puede conectar como '{}' ({})"),
+    ("JACK: port registration failed ({})", "JACK: falló el registro de puertos ({})"),
+    ("JACK: activation failed ({})", "JACK: falló la activación ({})"),
+    ("JACK: couldn't connect {} → {} ({})", "JACK: no se pudo conectar {} → {} ({})"),
+    ("Kick", "Bombo"),
+    ("Snare", "Caja"),
+    ("Hat", "Charles"),
+    ("Perc", "Percusión"),
+    ("✓ {} is a {}{}", "✓ {} es: {}{}"),
+    (" (guessed)", " (estimado)"),
+    ("✓ Categories guessed again", "✓ Categorías estimadas de nuevo"),
+    ("System default", "Predeterminado del sistema"),
+    ("No audio output device", "No hay dispositivo de salida de audio"),
+    ("Device list: {}", "Lista de dispositivos: {}"),
+    ("Device '{}' not found (try Refresh)", "Dispositivo '{}' no encontrado (prueba a actualizar)"),
+    ("Stopped at marker", "Detenido en el marcador"),
+    ("Playback finished", "Reproducción terminada"),
+    ("✗ Start the sequencer to play a fill", "✗ Inicia el secuenciador para tocar un fill"),
+    ("✗ Fills are for live pattern play, not the song", "✗ Los fills son para tocar patrones en vivo, no para la canción"),
+    ("Fill cancelled", "Fill cancelado"),
+    ("✗ {} has no fill pattern", "✗ {} no tiene patrón de fill"),
+    ("⏳ Fill queued for the next bar", "⏳ Fill en cola para el próximo compás"),
+    ("Play / stop", "Reproducir / parar"),
+    ("Record", "Grabar"),
+    ("Tap tempo", "Tempo por toques"),
+    ("Momentary", "Momentáneo"),
+    ("Latching", "Enclavado"),
+    ("✗ Add a recording track and pick its input to record", "✗ Añade una pista de grabación y elige su entrada para grabar"),
+    ("Tap…", "Toca…"),
+    ("Tap tempo: {:.0} BPM", "Tempo por toques: {:.0} BPM"),
+    ("4×4 pads", "Pads 4×4"),
+    ("✓ {} connected — switch it to Programmer mode", "✓ {} conectado — ponlo en modo Programmer"),
+    ("✓ {} connected as {}", "✓ {} conectado como {}"),
+    ("Text (.txt)", "Texto (.txt)"),
+    ("Image (.svg)", "Imagen (.svg)"),
+    ("✓ Pattern grid saved: {}", "✓ Rejilla del patrón guardada: {}"),
+    ("Write {}: {}", "Escribir {}: {}"),
+    ("✂ Trim tail to {:.2} s", "✂ Recortar cola a {:.2} s"),
+    ("⬆ Normalize {:+.1} dB", "⬆ Normalizar {:+.1} dB"),
+    ("⬇ Reduce gain {:+.1} dB", "⬇ Reducir ganancia {:+.1} dB"),
+    ("✓ {}: gain {:+.1} dB", "✓ {}: ganancia {:+.1} dB"),
+    (" — kept in memory only (save the project first to store trimmed copies)", " — solo en memoria (guarda antes el proyecto para almacenar las copias recortadas)"),
+    ("✓ {}: tail trimmed to {:.2} s{}", "✓ {}: cola recortada a {:.2} s{}"),
+    ("Hearing {}", "Escuchando {}"),
+    ("✓ {} replaced by {}", "✓ {} sustituido por {}"),
+    ("Master Volume", "Volumen maestro"),
+    ("Master Filter (Hz)", "Filtro maestro (Hz)"),
+    ("Crossfader (chops → drums)", "Crossfader (cortes → batería)"),
+    ("Track Attack", "Ataque de pista"),
+    ("Track Decay", "Caída de pista"),
+    ("Track Sustain", "Sostenido de pista"),
+    ("Track Release", "Liberación de pista"),
+    ("Beep", "Pitido"),
+    ("Rim", "Aro"),
+    ("Cross-stick", "Cross-stick"),
+    ("Sample", "Muestra"),
+    ("✓ Metronome click: {}", "✓ Clic del metrónomo: {}"),
+    ("✗ Metronome sample: {}", "✗ Muestra del metrónomo: {}"),
+    ("✓ MIDI input: {}", "✓ Entrada MIDI: {}"),
+    ("Unexpected end of file", "Fin de archivo inesperado"),
+    ("Bad variable-length number", "Número de longitud variable incorrecto"),
+    ("Not a MIDI file", "No es un archivo MIDI"),
+    ("Short MIDI header", "Cabecera MIDI demasiado corta"),
+    ("SMPTE-timed MIDI files aren't supported", "No se admiten archivos MIDI con tiempo SMPTE"),
+    ("MIDI data without a status byte", "Datos MIDI sin byte de estado"),
+    ("Import MIDI clip", "Importar clip MIDI"),
+    ("✗ {} has no notes", "✗ {} no tiene notas"),
+    ("✓ {}: {} hits on {} row{}{}", "✓ {}: {} golpes en {} fila{}{}"),
+    ("Pad {}", "Pad {}"),
+    ("Track {} mute", "Silencio de la pista {}"),
+    ("Track {} step {}", "Pista {} paso {}"),
+    ("Track {} chop {} step {}", "Pista {} corte {} paso {}"),
+    ("Note {}", "Nota {}"),
+    ("MIDI learn cancelled", "Aprendizaje MIDI cancelado"),
+    ("🎹 {}: play a note or move a control (right-click again to cancel)", "🎹 {}: toca una nota o mueve un control (clic derecho de nuevo para cancelar)"),
+    ("🎹 {}: pick a MIDI input in ⚙ first", "🎹 {}: elige primero una entrada MIDI en ⚙"),
+    ("✗ {} has no file to restore the channels from", "✗ {} no tiene archivo del que recuperar los canales"),
+    ("✓ {} is now {}", "✓ {} ahora es {}"),
+    ("Listen on {}: {}", "Escuchar en {}: {}"),
+    ("Read {}: {}", "Leer {}: {}"),
+    ("Folder of stems or one-shots", "Carpeta de stems o one-shots"),
+    ("✗ No audio files in {}", "✗ No hay archivos de audio en {}"),
+    ("None of the files in {} could be decoded", "No se pudo decodificar ningún archivo de {}"),
+    ("✓ Pad bank \"{}\": {} pad(s){}{}", "✓ Banco de pads \"{}\": {} pad(s){}{}"),
+    ("Pad bank \"{}\" removed", "Banco de pads \"{}\" eliminado"),
+    ("↶ Undid {}", "↶ Deshecho: {}"),
+    ("✗ Patterns can't be longer than {} steps", "✗ Los patrones no pueden tener más de {} pasos"),
+    ("double", "duplicar"),
+    ("✓ Patterns doubled to {} steps", "✓ Patrones duplicados a {} pasos"),
+    ("✗ Patterns can't be shorter than {} steps", "✗ Los patrones no pueden tener menos de {} pasos"),
+    ("halve", "reducir a la mitad"),
+    ("✓ Patterns halved to {} steps — {} hit(s) in the second halves dropped", "✓ Patrones reducidos a {} pasos — {} golpe(s) de las segundas mitades descartados"),
+    ("shift", "desplazar"),
+    ("✓ Pattern shifted {} step(s) {}", "✓ Patrón desplazado {} paso(s) a la {}"),
+    ("left", "izquierda"),
+    ("right", "derecha"),
+    ("row shift", "desplazar fila"),
+    ("✓ {} shifted {} step(s) {}", "✓ {} desplazado {} paso(s) a la {}"),
+    ("🎹  {}  ·  Chop {}  @{:.3}s", "🎹  {}  ·  Corte {}  @{:.3}s"),
+    ("⏹ Stop", "⏹ Parar"),
+    ("▶ Play", "▶ Reproducir"),
+    ("{} note{}", "{} nota{}"),
+    ("🗑 Clear all", "🗑 Borrar todo"),
+    ("Left-click = add  ·  Right-click = remove  ·  C4 = original pitch", "Clic izquierdo = añadir  ·  Clic derecho = quitar  ·  C4 = tono original"),
+    ("⚠ Empty — step buttons used as fallback", "⚠ Vacío — se usan los botones de paso"),
+    ("Chop {} piano roll grid", "Rejilla del piano roll del corte {}"),
+    ("Parse {}: {}", "Analizar {}: {}"),
+    ("Serialize: {}", "Serializar: {}"),
+    ("Skip", "Omitir"),
+    ("Pattern", "Patrón"),
+    ("Marks", "Marcas"),
+    ("Leave this pattern out", "Dejar fuera este patrón"),
+    ("Add as a new pattern (renamed if the name is taken)", "Añadir como patrón nuevo (renombrado si el nombre ya existe)"),
+    ("Add its tracks to the current pattern, without steps", "Añadir sus pistas al patrón actual, sin pasos"),
+    ("Add its chop marks to tracks here that use the same sample", "Añadir sus marcas de corte a las pistas de aquí que usan la misma muestra"),
+    ("No file name: {}", "Sin nombre de archivo: {}"),
+    ("Create {}: {}", "Crear {}: {}"),
+    ("Copy {}: {}", "Copiar {}: {}"),
+    ("Rabies project", "Proyecto de Rabies"),
+    ("Save Project", "Guardar proyecto"),
+    ("✓ Saved {} ({} recorded track(s) have no file and won't reload)", "✓ Guardado {} ({} pista(s) grabada(s) sin archivo no se recargarán)"),
+    ("✓ Saved {} — {} sample(s) couldn't be copied: {}", "✓ Guardado {} — no se pudieron copiar {} muestra(s): {}"),
+    ("✓ Saved {}", "✓ Guardado {}"),
+    ("Open Project", "Abrir proyecto"),
+    ("✓ Opened {}", "✓ Abierto {}"),
+    ("✓ Opened {} — {} sample(s) missing", "✓ Abierto {} — faltan {} muestra(s)"),
+    ("Import from Project", "Importar desde proyecto"),
+    ("✗ {} has no patterns", "✗ {} no tiene patrones"),
+    ("Nothing selected to import", "No hay nada seleccionado para importar"),
+    ("✓ Imported from {}: {} pattern(s), {} track(s), {} mark(s)", "✓ Importado de {}: {} patrón(es), {} pista(s), {} marca(s)"),
+    ("✗ Already recording — stop that first", "✗ Ya se está grabando — detén eso primero"),
+    ("✗ Pick the punch-in and punch-out markers first", "✗ Elige primero los marcadores de entrada y salida del punch"),
+    ("✗ Punch markers must be different", "✗ Los marcadores del punch deben ser distintos"),
+    ("✗ Pick an input for the punch first", "✗ Elige primero una entrada para el punch"),
+    ("Record error: {}", "Error de grabación: {}"),
+    ("🔴 Punch {:.2}s → {:.2}s (pre-roll {:.2}s)", "🔴 Punch {:.2}s → {:.2}s (pre-roll {:.2}s)"),
+    ("✗ Punch cancelled — the track is unchanged", "✗ Punch cancelado — la pista no ha cambiado"),
+    ("Punch cancelled", "Punch cancelado"),
+    ("The punched track is gone", "La pista del punch ya no existe"),
+    ("Punch take came up short — the track is unchanged", "La toma del punch se quedó corta — la pista no ha cambiado"),
+    (" — kept in memory until the project is saved", " — en memoria hasta que se guarde el proyecto"),
+    ("✓ Punched {:.2}s into {}{}", "✓ Punch de {:.2}s en {}{}"),
+    ("No input", "Sin entrada"),
+    ("Start stream: {}", "Iniciar flujo: {}"),
+    ("Input config: {}", "Configuración de entrada: {}"),
+    ("Build input stream: {}", "Crear flujo de entrada: {}"),
+    ("✓ Reloaded {} ({} track{})", "✓ Recargado {} ({} pista{})"),
+    ("Reload error: {}", "Error al recargar: {}"),
+    ("⟳ {} changed on disk", "⟳ {} cambió en el disco"),
+    ("Nothing arranged — place patterns in the song first", "Nada arreglado — coloca primero patrones en la canción"),
+    ("No pattern {}", "No existe el patrón {}"),
+    ("✓ Bounced {} bar{} to {}", "✓ {0} compás(es) exportado(s) a {2}"),
+    ("✓ Bounced the song to {}", "✓ Canción exportada a {}"),
+    ("Track {} has no chops", "La pista {} no tiene cortes"),
+    ("{} — {} device(s)", "{} — {} dispositivo(s)"),
+    ("No output", "Sin salida"),
+    ("Output opened but never asked for audio", "La salida se abrió pero nunca pidió audio"),
+    ("Output runs too slowly", "La salida va demasiado lenta"),
+    ("Output stalls between buffers", "La salida se atasca entre búferes"),
+    ("🛟 Safe mode ({}) — audio is off; projects open and save as usual", "🛟 Modo seguro ({}) — audio desactivado; los proyectos se abren y guardan como siempre"),
+    ("✓ Safe mode off — audio opens on the next sound", "✓ Modo seguro desactivado — el audio se abre con el próximo sonido"),
+    ("🛟 Safe mode — audio is off (leave it from the banner)", "🛟 Modo seguro — audio desactivado (sal desde el aviso)"),
+    ("✗ Audio self-test: {} — 🛟 safe mode keeps projects usable", "✗ Autoprueba de audio: {} — 🛟 el modo seguro mantiene los proyectos utilizables"),
+    ("Seconds", "Segundos"),
+    ("Samples", "Muestras"),
+    ("Bars:Beats", "Compases:Tiempos"),
+    ("✓ Created region: {}", "✓ Región creada: {}"),
+    ("{} failed: {}", "{} falló: {}"),
+    ("Folder for share previews", "Carpeta para las vistas previas compartidas"),
+    ("✗ Create {}: {}", "✗ Crear {}: {}"),
+    ("Sharing {}…", "Compartiendo {}…"),
+    ("✓ Shared {} (via {})", "✓ Compartido {} (con {})"),
+    ("✓ Shared {}{}", "✓ Compartido {}{}"),
+    ("Share: {}", "Compartir: {}"),
+    ("✓ Sliced {} into {}: {} marker{} added", "✓ {0} troceado en {1}: {2} marcador(es) añadido(s)"),
+    ("Synced start cancelled", "Inicio sincronizado cancelado"),
+    ("✗ Play the main sample first — the pattern starts on its next bar", "✗ Reproduce primero la muestra principal — el patrón empieza en su próximo compás"),
+    ("✗ No bar line left before the end of the sample", "✗ No queda ninguna línea de compás antes del final de la muestra"),
+    ("⏱ Pattern starts on bar {} of the sample, in {:.2} s", "⏱ El patrón empieza en el compás {} de la muestra, dentro de {:.2} s"),
+    ("Synced start dropped — the preview stopped", "Inicio sincronizado descartado — la vista previa se detuvo"),
+    ("✗ Pick two different markers first", "✗ Elige primero dos marcadores distintos"),
+    ("✗ That span gives {:.1} BPM — outside 40-300, check the bar count", "✗ Ese tramo da {:.1} BPM — fuera de 40-300, revisa el número de compases"),
+    ("✓ Tempo set to {:.2} BPM from markers {} → {}", "✓ Tempo fijado en {:.2} BPM a partir de los marcadores {} → {}"),
+    (" — trim kept in memory only (save the project first to store trimmed copies)", " — recorte solo en memoria (guarda antes el proyecto para almacenar las copias recortadas)"),
+    ("✓ Track added: {}{}{}", "✓ Pista añadida: {}{}{}"),
+    ("Import of {} cancelled", "Importación de {} cancelada"),
+    ("just now", "ahora mismo"),
+    ("Save the project once before keeping versions", "Guarda el proyecto una vez antes de guardar versiones"),
+    ("✓ Saved version {}", "✓ Versión {} guardada"),
+    ("✗ Not restored — couldn't keep the current state: {}", "✗ No restaurado — no se pudo conservar el estado actual: {}"),
+    ("✓ Restored version: {} (save to keep it)", "✓ Versión restaurada: {} (guarda para conservarla)"),
+    ("✓ Restored version: {} — {} sample(s) missing", "✓ Versión restaurada: {} — faltan {} muestra(s)"),
+    ("Off", "No"),
+    ("Click Load Sample to begin", "Pulsa Cargar muestra para empezar"),
+    ("⏳ {} queued for next {}", "⏳ {} en cola para el próximo {}"),
+    ("✓ Switched to {}", "✓ Cambiado a {}"),
+    ("✓ New pattern: {}", "✓ Patrón nuevo: {}"),
+    ("✓ Track added: {}", "✓ Pista añadida: {}"),
+    ("A/B: snapshot of {} taken — edit freely, then compare", "A/B: instantánea de {} tomada — edita libremente y luego compara"),
+    ("A/B: hearing {}", "A/B: escuchando {}"),
+    ("A (original)", "A (original)"),
+    ("B (edited)", "B (editado)"),
+    ("A/B: kept edited version", "A/B: se conserva la versión editada"),
+    ("A/B: reverted to original", "A/B: se volvió al original"),
+    ("A/B audition: chop {} vs chop {} — Enter swaps B into the track", "Audición A/B: corte {} frente a corte {} — Intro pasa B a la pista"),
+    ("A/B audition stopped", "Audición A/B detenida"),
+    ("✓ Chop {} now plays chop {}'s part — chop {} is the new B", "✓ El corte {} toca ahora la parte del corte {} — el corte {} es el nuevo B"),
+    ("Playing: {}", "Reproduciendo: {}"),
+    ("Paused: {}", "En pausa: {}"),
+    ("Seeked to {:.2}s / {:.2}s", "Saltado a {:.2}s / {:.2}s"),
+    ("✓ Track loaded: {}", "✓ Pista cargada: {}"),
+    ("Track load error: {}", "Error al cargar la pista: {}"),
+    ("Viewing: {}", "Viendo: {}"),
+    ("Already recording — stop current recording first", "Ya se está grabando — detén primero la grabación actual"),
+    ("Select an input device first", "Selecciona primero un dispositivo de entrada"),
+    ("Device '{}' not found", "Dispositivo '{}' no encontrado"),
+    ("🔴 Recording from {} (from next {})", "🔴 Grabando desde {} (desde el próximo {})"),
+    ("🔴 Recording from {}", "🔴 Grabando desde {}"),
+    ("✓ Recorded {:.2}s → {}", "✓ Grabados {:.2}s → {}"),
+    ("Recording was empty", "La grabación estaba vacía"),
+    ("🔁 Loop recording {} bar{} from next bar", "🔁 Grabación en bucle de {0} compás(es) desde el próximo compás"),
+    ("✓ Loop take '{}' → drum track {}", "✓ Toma en bucle '{}' → pista de batería {}"),
+    ("✓ Loop take '{}' → {} chops on track {}", "✓ Toma en bucle '{}' → {} cortes en la pista {}"),
+    ("✓ Promoted '{}' to drum track", "✓ '{}' convertida en pista de batería"),
+    ("⏺ Capturing last {}s from {}", "⏺ Capturando los últimos {}s desde {}"),
+    ("✗ Capture error: {}", "✗ Error de captura: {}"),
+    ("Capture off", "Captura desactivada"),
+    ("✗ Capture buffer is empty", "✗ El búfer de captura está vacío"),
+    ("✓ Kept {:.1}s as '{}' on track {}", "✓ Guardados {:.1}s como '{}' en la pista {}"),
+    ("✓ Chopped {} at {:.2}s", "✓ {} cortado en {:.2}s"),
+    ("Audio track {} added — double-click its label to load a file", "Pista de audio {} añadida — doble clic en su etiqueta para cargar un archivo"),
+    ("Load Audio into Playlist Track", "Cargar audio en la pista de la lista"),
+    ("Failed to load audio: {}", "No se pudo cargar el audio: {}"),
+    ("Loaded \"{}\" into audio track {}", "\"{}\" cargado en la pista de audio {}"),
+    ("No chops to lay out — drop markers first (M)", "No hay cortes que colocar — pon primero marcadores (M)"),
+    ("Every chop already has a step (or the bar is full)", "Cada corte ya tiene un paso (o el compás está lleno)"),
+    ("✓ Laid {} chop(s) across the bar", "✓ {} corte(s) repartidos por el compás"),
+    ("No chops to sequence — drop markers first (M)", "No hay cortes que secuenciar — pon primero marcadores (M)"),
+    ("✓ Sequenced {} chop(s) at {:.0} BPM over {} pattern(s){}", "✓ {} corte(s) secuenciados a {:.0} BPM en {} patrón(es){}"),
+    ("{} lane: {} steps per bar", "Carril {}: {} pasos por compás"),
+    ("Triplet", "Tresillo"),
+    ("Straight", "Binario"),
+    ("Cue {} — {:.2}s", "Cue {} — {:.2}s"),
+    ("✓ Pattern length {} steps — {} hit(s) past the end dropped", "✓ Longitud del patrón {} pasos — {} golpe(s) tras el final descartados"),
+    ("✓ Pattern length {} steps", "✓ Longitud del patrón {} pasos"),
+    ("✓ Grid {} — {} hit(s) merged", "✓ Rejilla {} — {} golpe(s) fusionados"),
+    ("✓ Grid {} — {} hit(s) past step {} dropped", "✓ Rejilla {} — {} golpe(s) tras el paso {} descartados"),
+    ("✓ Grid {}", "✓ Rejilla {}"),
+    ("Sequencer ▶ {:.0} BPM", "Secuenciador ▶ {:.0} BPM"),
+    ("Sequencer stopped", "Secuenciador detenido"),
+    ("▶ Song playing", "▶ Canción sonando"),
+    ("Song stopped", "Canción detenida"),
+    ("⚠ PANIC — output muted (click 🔇 Muted to restore)", "⚠ PÁNICO — salida silenciada (pulsa 🔇 Silenciado para restaurar)"),
+    ("Output unmuted", "Salida reactivada"),
+    ("✓ Audio engine restarted", "✓ Motor de audio reiniciado"),
+    ("Network send stopped", "Envío por red detenido"),
+    ("📡 Sending master output on TCP port {}", "📡 Enviando la salida maestra por el puerto TCP {}"),
+    ("✗ Network send: {}", "✗ Envío por red: {}"),
+    ("✓ Output: {}", "✓ Salida: {}"),
+    ("✓ Output: {} — {} Hz, {} ch", "✓ Salida: {} — {} Hz, {} can."),
+    ("🔊 Audio / MIDI settings", "🔊 Ajustes de audio / MIDI"),
+    ("JACK sets the device, rate and buffer size — these apply to the System backend", "JACK fija el dispositivo, la frecuencia y el búfer — estos ajustes son para el backend Sistema"),
+    ("Output", "Salida"),
+    ("Refresh devices", "Actualizar dispositivos"),
+    ("Sample rate", "Frecuencia de muestreo"),
+    ("Device default", "Predeterminado del dispositivo"),
+    ("Buffer", "Búfer"),
+    ("{} frames", "{} frames"),
+    ("Running: {} Hz · {} ch", "En marcha: {} Hz · {} can."),
+    ("Not running — opens on first sound", "Parado — se abre con el primer sonido"),
+    ("Self-test not run", "Autoprueba sin hacer"),
+    ("Self-test running…", "Autoprueba en curso…"),
+    ("🩺 Self-test", "🩺 Autoprueba"),
+    ("List devices and run a short silent stream on this output", "Listar dispositivos y abrir un flujo corto en silencio en esta salida"),
+    ("🛟 Safe mode", "🛟 Modo seguro"),
+    ("No audio this session; projects still open and save", "Sin audio en esta sesión; los proyectos se siguen abriendo y guardando"),
+    ("turned on in settings", "activado en los ajustes"),
+    ("MIDI input", "Entrada MIDI"),
+    ("None", "Ninguna"),
+    ("Scan for MIDI devices", "Buscar dispositivos MIDI"),
+    ("Channel", "Canal"),
+    ("Transport", "Transporte"),
+    ("All", "Todos"),
+    ("Start / Stop run the sequencer", "Start / Stop controlan el secuenciador"),
+    ("Follow MIDI clock", "Seguir el reloj MIDI"),
+    ("BPM from CC", "BPM desde CC"),
+    ("The CC sweeps 40-300 BPM", "El CC recorre 40-300 BPM"),
+    ("Notes play the pads through the ⌨ mapping profile, with velocity", "Las notas tocan los pads mediante el perfil de mapeo ⌨, con velocidad"),
+    ("Footswitches", "Pedales"),
+    ("On while the switch is down (or a latching pedal is on)", "Activo mientras el pedal está pisado (o un pedal enclavado está activado)"),
+    ("Each press toggles", "Cada pulsación alterna"),
+    ("＋ Footswitch", "＋ Pedal"),
+    ("Map a CC (64 is the sustain pedal) to a transport action", "Asigna un CC (64 es el pedal de sustain) a una acción de transporte"),
+    ("MIDI learn", "Aprendizaje MIDI"),
+    ("Waiting for {}…", "Esperando {}…"),
+    ("Cancel", "Cancelar"),
+    ("Right-click a pad, a track's M, the BPM field or a step, then play a note or move a control", "Haz clic derecho en un pad, la M de una pista, el campo BPM o un paso, y luego toca una nota o mueve un control"),
+    ("✂ Auto-chop", "✂ Auto-corte"),
+    ("🌐 Language", "🌐 Idioma"),
+    ("Level jumps — loops and mixed material", "Saltos de nivel — loops y material mezclado"),
+    ("Envelope peaks at least one sequencer step apart — isolated drum stems", "Picos de envolvente separados al menos un paso del secuenciador — stems de batería aislados"),
+    ("Sensitivity", "Sensibilidad"),
+    ("Higher finds quieter hits", "Más alta encuentra golpes más suaves"),
+    ("No new hits — try a higher sensitivity", "No hay golpes nuevos — prueba una sensibilidad más alta"),
+    ("{:.3} s long", "{:.3} s de duración"),
+    ("＋ Add {} chop{}", "＋ Añadir {0} corte(s)"),
+    ("🗂 Sample Browser", "🗂 Navegador de muestras"),
+    ("name, title, artist, BPM, key", "nombre, título, artista, BPM, tonalidad"),
+    ("No samples loaded yet", "Aún no hay muestras cargadas"),
+    ("No matches", "Sin coincidencias"),
+    ("Add as a new track", "Añadir como pista nueva"),
+    ("📊 Kit report", "📊 Informe del kit"),
+    ("No drum tracks yet", "Aún no hay pistas de batería"),
+    ("Track", "Pista"),
+    ("Peak", "Pico"),
+    ("Length", "Duración"),
+    ("Effective", "Efectiva"),
+    ("Suggestions", "Sugerencias"),
+    ("✓ fine", "✓ bien"),
+    ("Apply all", "Aplicar todo"),
+    ("↻ Re-measure", "↻ Volver a medir"),
+    ("🔀 Swap kit", "🔀 Cambiar kit"),
+    ("⏹ Stop pattern", "⏹ Parar patrón"),
+    ("▶ Loop pattern", "▶ Patrón en bucle"),
+    ("Unkept tracks go back to their own sample on close", "Las pistas no conservadas vuelven a su muestra al cerrar"),
+    ("Nothing left to swap", "No queda nada que cambiar"),
+    ("track gone", "la pista ya no existe"),
+    ("no similar samples in the browser", "no hay muestras parecidas en el navegador"),
+    ("✓ Keep", "✓ Conservar"),
+    ("↺ Original", "↺ Original"),
+    ("⤵ Import from project", "⤵ Importar desde proyecto"),
+    ("Steps will be remapped from {} to {}", "Los pasos se reasignarán de {} a {}"),
+    ("{} track(s)", "{} pista(s)"),
+    ("name taken → renamed", "nombre en uso → renombrado"),
+    ("⤵ Import", "⤵ Importar"),
+    ("🎼 Import MIDI clip", "🎼 Importar clip MIDI"),
+    ("Rows that get notes are cleared first", "Las filas que reciben notas se vacían primero"),
+    ("Set BPM to the file's {:.0}", "Fijar el BPM del archivo: {:.0}"),
+    ("⤓ Import", "⤓ Importar"),
+    ("Sample Pads", "Pads de muestras"),
+    ("Pad grid: {}", "Rejilla de pads: {}"),
+    ("⏭ Hot cues", "⏭ Hot cues"),
+    ("Pads jump the main playhead to their marker instead of playing the chop", "Los pads llevan el cabezal principal a su marcador en lugar de tocar el corte"),
+    ("✏ Edit Layout", "✏ Editar disposición"),
+    ("Drag pads onto other slots to match your controller; click one, then press a key to rebind it", "Arrastra los pads a otras casillas para que coincidan con tu controlador; haz clic en uno y pulsa una tecla para reasignarlo"),
+    ("Reset", "Restablecer"),
+    ("No cues yet — press M while playing to drop markers", "Aún no hay cues — pulsa M mientras suena para poner marcadores"),
+    ("No pads yet — press M while previewing a track to create chop points", "Aún no hay pads — pulsa M mientras escuchas una pista para crear puntos de corte"),
+    ("Pad {}, key {}, {}", "Pad {}, tecla {}, {}"),
+    ("Pad {}, {}", "Pad {}, {}"),
+    ("Pad {}, empty", "Pad {}, vacío"),
+    ("Pad {}, key {}, chop at {:.2} seconds", "Pad {}, tecla {}, corte en {:.2} segundos"),
+    ("Pad {}, chop at {:.2} seconds", "Pad {}, corte en {:.2} segundos"),
+    ("Chops", "Cortes"),
+    ("Chops of the focused track", "Cortes de la pista enfocada"),
+    ("{} ({} pads)", "{} ({} pads)"),
+    ("Remove this bank", "Quitar este banco"),
+    ("📁 Import folder as pad bank…", "📁 Importar carpeta como banco de pads…"),
+    ("Each audio file in the folder becomes a pad, in name order", "Cada archivo de audio de la carpeta pasa a ser un pad, por orden de nombre"),
+    ("▥ Slice", "▥ Trocear"),
+    ("Slice into", "Trocear en"),
+    ("Whole sample", "Muestra completa"),
+    ("Replace", "Reemplazar"),
+    ("Clear the markers in this range first", "Borrar primero los marcadores de este tramo"),
+    ("Append", "Añadir"),
+    ("Keep the markers already there", "Conservar los marcadores que ya hay"),
+    ("{} beat", "{} tiempo"),
+    ("⇄ Swap into track", "⇄ Pasar a la pista"),
+    ("Give B the pattern A had (Enter)", "Dar a B el patrón que tenía A (Intro)"),
+    ("Stop audition (Esc)", "Parar audición (Esc)"),
+    ("A/B: pick B (A = {})", "A/B: elige B (A = {})"),
+    ("A/B: pick A", "A/B: elige A"),
+    ("Compare two chops back to back at the sequencer tempo", "Comparar dos cortes seguidos al tempo del secuenciador"),
+    ("Slot {} has no key in {}", "La casilla {} no tiene tecla en {}"),
+    ("✓ {} plays slot {} in {}", "✓ {} toca la casilla {} en {}"),
+    ("Mapping: {}", "Mapeo: {}"),
+    ("Name", "Nombre"),
+    ("⎘ Duplicate", "⎘ Duplicar"),
+    ("Copy this profile to change it without losing the original", "Copia este perfil para cambiarlo sin perder el original"),
+    ("🗑 Delete", "🗑 Eliminar"),
+    ("Keys: ✏ Edit Layout, click a pad, press a key (Esc unbinds)", "Teclas: ✏ Editar disposición, clic en un pad, pulsa una tecla (Esc la desasigna)"),
+    ("Keyboard/MIDI mapping profile, saved with the project", "Perfil de mapeo de teclado/MIDI, guardado con el proyecto"),
+    ("🎛 Playlist ▲", "🎛 Lista ▲"),
+    ("🎛 Playlist ▼", "🎛 Lista ▼"),
+    ("Toggle FL Playlist – drag blocks to freely arrange patterns", "Mostrar/ocultar la lista estilo FL – arrastra bloques para colocar patrones libremente"),
+    ("📋 Song ▲", "📋 Canción ▲"),
+    ("📋 Song ▼", "📋 Canción ▼"),
+    ("Toggle Song Editor – arrange patterns on a timeline", "Mostrar/ocultar el editor de canción – coloca patrones en una línea de tiempo"),
+    ("STEP SEQUENCER", "SECUENCIADOR POR PASOS"),
+    ("Right-click to MIDI learn", "Clic derecho para aprendizaje MIDI"),
+    ("Tap", "Toque"),
+    ("Tap a few times in time to set the BPM", "Toca varias veces a tempo para fijar el BPM"),
+    ("Quantize", "Cuantizar"),
+    ("Grid for live marks, recording start and pattern launch", "Rejilla para marcas en vivo, inicio de grabación y lanzamiento de patrones"),
+    ("Quantize: {}", "Cuantizar: {}"),
+    ("Grid", "Rejilla"),
+    ("Steps per bar, straight or triplet (T) — existing steps are moved to the new grid", "Pasos por compás, binario o tresillo (T) — los pasos existentes se mueven a la nueva rejilla"),
+    ("{} steps", "{} pasos"),
+    ("Pattern length — hits past a shorter end are dropped", "Longitud del patrón — los golpes tras un final más corto se descartan"),
+    ("⏱ On bar", "⏱ En compás"),
+    ("Start the pattern on the next bar line of the sample playing, in phase with it (click again to cancel)", "Iniciar el patrón en la próxima línea de compás de la muestra que suena, en fase con ella (clic de nuevo para cancelar)"),
+    ("Play this pattern's fill for one bar from the next bar line (G)", "Tocar el fill de este patrón durante un compás desde la próxima línea de compás (G)"),
+    ("Pick a fill for this pattern in the song editor (right-click its name), then play", "Elige un fill para este patrón en el editor de canción (clic derecho en su nombre) y luego reproduce"),
+    ("🕭 Click", "🕭 Clic"),
+    ("Metronome", "Metrónomo"),
+    ("Sound", "Sonido"),
+    ("Load a click sample", "Cargar una muestra de clic"),
+    ("Time signature", "Compás"),
+    ("Accents the downbeat (and every third eighth in 6/8, 9/8, 12/8)", "Acentúa el primer tiempo (y cada tercera corchea en 6/8, 9/8, 12/8)"),
+    ("Metronome: sound, level and time signature (never in bounces)", "Metrónomo: sonido, nivel y compás (nunca en las exportaciones)"),
+    ("🎧 Track {} chop {} ✕", "🎧 Pista {} corte {} ✕"),
+    ("Only this chop is heard; click to hear everything again", "Solo se oye este corte; haz clic para volver a oírlo todo"),
+    ("▦ Grid", "▦ Rejilla"),
+    ("Steps", "Pasos"),
+    ("Sequencer rows, each wrapped over as many grid rows as it needs", "Filas del secuenciador, cada una repartida en tantas filas de la rejilla como necesite"),
+    ("Pads", "Pads"),
+    ("Play the pad layout", "Tocar la disposición de pads"),
+    ("From row {}", "Desde la fila {}"),
+    ("Disconnect", "Desconectar"),
+    ("No controllers — plug one in and scan", "No hay controladores — conecta uno y busca"),
+    ("Connect as {}", "Conectar como {}"),
+    ("↻ Scan", "↻ Buscar"),
+    ("Launchpad / Push: steps or pads on the grid, with LED feedback", "Launchpad / Push: pasos o pads en la rejilla, con respuesta de LED"),
+    ("🗑 Clear", "🗑 Borrar"),
+    ("⇶ Lay chops", "⇶ Colocar cortes"),
+    ("One per step", "Uno por paso"),
+    ("Chop 1 on step 1, chop 2 on step 2, …", "Corte 1 en el paso 1, corte 2 en el paso 2, …"),
+    ("Spread over the bar", "Repartir por el compás"),
+    ("Space the chops evenly, e.g. four chops on the four beats", "Espaciar los cortes por igual, p. ej. cuatro cortes en los cuatro tiempos"),
+    ("Only chops with no steps yet, onto empty steps", "Solo los cortes que aún no tienen pasos, en pasos vacíos"),
+    ("As in the original loop", "Como en el loop original"),
+    ("Replace this track's chop steps with each chop at its spot in the loop (BPM tag if present); longer loops continue in new patterns", "Sustituir los pasos de corte de esta pista poniendo cada corte en su sitio del loop (etiqueta BPM si la hay); los loops más largos siguen en patrones nuevos"),
+    ("Place the focused track's chops on the grid to rebuild the loop", "Colocar los cortes de la pista enfocada en la rejilla para reconstruir el loop"),
+    ("⎙ Export grid", "⎙ Exportar rejilla"),
+    ("Rows with hits × steps, with velocities", "Filas con golpes × pasos, con velocidades"),
+    ("Save this pattern as a printable grid", "Guardar este patrón como rejilla imprimible"),
+    ("🎼 Import MIDI", "🎼 Importar MIDI"),
+    ("Fill rows of this pattern from the notes of a .mid clip", "Rellenar filas de este patrón con las notas de un clip .mid"),
+    ("⇆ Steps", "⇆ Pasos"),
+    ("×2  Double", "×2  Duplicar"),
+    ("Play every pattern twice, in patterns twice as long", "Tocar cada patrón dos veces, en patrones el doble de largos"),
+    ("½  Halve", "½  Mitad"),
+    ("Keep the first half of every pattern", "Conservar la primera mitad de cada patrón"),
+    ("Shift this pattern earlier, wrapping round", "Adelantar este patrón, dando la vuelta"),
+    (" step(s)", " paso(s)"),
+    ("Shift this pattern later, wrapping round", "Retrasar este patrón, dando la vuelta"),
+    ("Right-click a row to shift just that row", "Clic derecho en una fila para desplazar solo esa fila"),
+    ("↶ Undo {}", "↶ Deshacer {}"),
+    ("Double, halve or shift the pattern's steps", "Duplicar, reducir a la mitad o desplazar los pasos del patrón"),
+    ("🎨 Rows", "🎨 Filas"),
+    ("Colour rows by", "Colorear filas por"),
+    ("Track number", "Número de pista"),
+    ("Category", "Categoría"),
+    ("Group by category", "Agrupar por categoría"),
+    ("↻ Guess categories again", "↻ Volver a estimar categorías"),
+    ("Forget categories set by hand", "Olvidar las categorías puestas a mano"),
+    ("Right-click a track to set its category", "Clic derecho en una pista para fijar su categoría"),
+    ("Colour and group track rows by kick, snare, hat and percussion", "Colorear y agrupar las filas por bombo, caja, charles y percusión"),
+    ("⤓ Bounce", "⤓ Exportar"),
+    ("{} bar{}", "{0} compás(es)"),
+    ("Stereo WAV at the current BPM, tails included", "WAV estéreo al BPM actual, con colas incluidas"),
+    ("Render this pattern offline to a WAV file", "Renderizar este patrón a un archivo WAV"),
+    ("📤 Share", "📤 Compartir"),
+    ("📤 Share preview", "📤 Compartir vista previa"),
+    ("Two loops of this pattern into the share folder", "Dos vueltas de este patrón en la carpeta para compartir"),
+    ("asks the first time", "se pregunta la primera vez"),
+    ("Folder: {}", "Carpeta: {}"),
+    ("Choose…", "Elegir…"),
+    ("Use project's {}/", "Usar {}/ del proyecto"),
+    ("Also write one on every save", "Escribir también una en cada guardado"),
+    ("MP3 and OGG go through ffmpeg, lame or oggenc if installed", "MP3 y OGG pasan por ffmpeg, lame u oggenc si están instalados"),
+    ("Bounce a quick MP3/OGG of this pattern to send to collaborators", "Exportar un MP3/OGG rápido de este patrón para enviarlo a colaboradores"),
+    ("Snapshot this pattern, then flip between original and edits", "Tomar una instantánea de este patrón y alternar entre original y cambios"),
+    ("✓ Commit", "✓ Confirmar"),
+    ("Keep the edited version (B)", "Conservar la versión editada (B)"),
+    ("↺ Revert", "↺ Revertir"),
+    ("Go back to the snapshot (A)", "Volver a la instantánea (A)"),
+    ("＋ Add Track", "＋ Añadir pista"),
+    ("🎙 Rec Track", "🎙 Pista de grabación"),
+    ("Add a recording track", "Añadir una pista de grabación"),
+    ("🎹 Piano Roll", "🎹 Piano roll"),
+    ("Peak, RMS, loudness and length of every track, with fixes", "Pico, RMS, sonoridad y duración de cada pista, con arreglos"),
+    ("Step through similar samples from the browser for each track while the pattern loops", "Recorrer muestras parecidas del navegador para cada pista mientras el patrón suena en bucle"),
+    ("≈ Match", "≈ Igualar"),
+    ("Loudness-match one-shots as they're added (per-track ↺ restores the original gain)", "Igualar la sonoridad de los one-shots al añadirlos (↺ en cada pista restaura la ganancia original)"),
+    ("👁 Watch", "👁 Vigilar"),
+    ("Notice when a track's sample file is edited outside the app", "Avisar cuando el archivo de muestra de una pista se edite fuera de la aplicación"),
+    ("⟳ {} changed", "⟳ {} ha cambiado"),
+    ("⟳ Reload", "⟳ Recargar"),
+    ("Ignore", "Ignorar"),
+    ("Sample files edited on disk since they were loaded", "Archivos de muestra editados en el disco desde que se cargaron"),
+    ("Channel conversion coefficients", "Coeficientes de conversión de canales"),
+    ("Center", "Centro"),
+    ("Surround", "Envolvente"),
+    ("Normalize to avoid clipping", "Normalizar para evitar saturación"),
+    ("Applies to voices triggered after the change", "Se aplica a las voces disparadas después del cambio"),
+    ("A source's channel layout differs from the output device", "La disposición de canales de una fuente difiere de la del dispositivo de salida"),
+    ("  Tracks", "  Pistas"),
+    ("Track {}: {}{}{}, preview", "Pista {}: {}{}{}, escuchar"),
+    (", soloed", ", en solo"),
+    (", muted", ", silenciada"),
+    ("Previewing: {}", "Escuchando: {}"),
+    ("Track {}", "Pista {}"),
+    ("🔊 Unmute", "🔊 Activar sonido"),
+    ("🔇 Mute", "🔇 Silenciar"),
+    ("Unsolo", "Quitar solo"),
+    ("🎧 Solo", "🎧 Solo"),
+    ("◑ Restore channels", "◑ Restaurar canales"),
+    ("Go back to the file's own channel layout", "Volver a la disposición de canales del archivo"),
+    ("◐ Fold to mono", "◐ Pasar a mono"),
+    ("⟳ Reload from disk", "⟳ Recargar desde el disco"),
+    ("Re-read the sample file; markers keep their place", "Volver a leer el archivo de muestra; los marcadores se mantienen"),
+    ("🏷 Category: {}", "🏷 Categoría: {}"),
+    ("Guess from the sound", "Estimar por el sonido"),
+    ("Unsolo (ctrl-click: clear every solo)", "Quitar solo (ctrl-clic: quitar todos los solos)"),
+    ("Solo: only soloed tracks play (ctrl-click: solo just this one)", "Solo: solo suenan las pistas en solo (ctrl-clic: solo esta)"),
+    ("Unmute (right-click to MIDI learn)", "Activar sonido (clic derecho para aprendizaje MIDI)"),
+    ("Mute (right-click to MIDI learn)", "Silenciar (clic derecho para aprendizaje MIDI)"),
+    ("Remove track and all its chops", "Quitar la pista y todos sus cortes"),
+    ("ADSR ON for {}", "ADSR activado para {}"),
+    ("ADSR OFF for {} (full volume)", "ADSR desactivado para {} (volumen completo)"),
+    ("trim ", "ajuste "),
+    ("Input trim, before the track gain", "Ajuste de entrada, antes de la ganancia de la pista"),
+    ("Invert polarity (for layered hits that cancel)", "Invertir polaridad (para golpes superpuestos que se cancelan)"),
+    ("Track gain", "Ganancia de pista"),
+    ("Match loudness to {:.0} LUFS", "Igualar sonoridad a {:.0} LUFS"),
+    ("✓ {} matched ({:+.1} dB)", "✓ {} igualado ({:+.1} dB)"),
+    ("✗ {} is silent", "✗ {} está en silencio"),
+    ("Restore gain from before loudness match", "Restaurar la ganancia anterior a la igualación de sonoridad"),
+    ("Lateness", "Retraso"),
+    ("late ", "retraso "),
+    ("Every hit on this track starts this much late", "Cada golpe de esta pista empieza con este retraso"),
+    ("+ up to ", "+ hasta "),
+    ("Plus a random extra delay per hit, for loose layering", "Más un retraso aleatorio por golpe, para capas más sueltas"),
+    ("Start drift: play this track's hits a little late", "Deriva de inicio: tocar los golpes de esta pista un poco tarde"),
+    ("Chop {}{}{}{}", "Corte {}{}{}{}"),
+    ("Chop {} at {:.2} seconds", "Corte {} en {:.2} segundos"),
+    ("Chop {}  @{:.2}s", "Corte {}  @{:.2}s"),
+    ("🎹  Piano Roll", "🎹  Piano roll"),
+    ("🎧  Unsolo", "🎧  Quitar solo"),
+    ("🎧  Solo listen", "🎧  Escuchar en solo"),
+    ("Hear only this chop's hits while the pattern plays", "Oír solo los golpes de este corte mientras suena el patrón"),
+    ("🗑  Clear Steps", "🗑  Borrar pasos"),
+    ("Remove this chop marker", "Quitar este marcador de corte"),
+    ("ADSR ON for Chop {}", "ADSR activado para el corte {}"),
+    ("ADSR OFF for Chop {} (full volume)", "ADSR desactivado para el corte {} (volumen completo)"),
+    ("Play to end of sample", "Reproducir hasta el final de la muestra"),
+    ("Play to next chop marker", "Reproducir hasta el siguiente marcador de corte"),
+    ("Play for one step then stop", "Reproducir un paso y parar"),
+    ("Play to a specific marker you choose", "Reproducir hasta el marcador que elijas"),
+    ("Pick marker", "Elegir marcador"),
+    ("No tracks yet — click ＋ Add Track to load a sample", "Aún no hay pistas — pulsa ＋ Añadir pista para cargar una muestra"),
+    ("Click steps to toggle  ·  Click label to focus/preview  ·  Right-click for options  ·  X to remove", "Clic en los pasos para alternar  ·  Clic en la etiqueta para enfocar/escuchar  ·  Clic derecho para opciones  ·  X para quitar"),
+    ("Track {} removed", "Pista {} eliminada"),
+    ("Chop {} removed", "Corte {} eliminado"),
+    ("◀ Shift", "◀ Desplazar"),
+    ("{} step(s) earlier, wrapping round", "{} paso(s) antes, dando la vuelta"),
+    ("Shift ▶", "Desplazar ▶"),
+    ("{} step(s) later, wrapping round", "{} paso(s) después, dando la vuelta"),
+    ("🎲 Alternates", "🎲 Alternativas"),
+    ("step ", "paso "),
+    ("→ chop ", "→ corte "),
+    ("How often the alternate plays instead", "Con qué frecuencia suena la alternativa en su lugar"),
+    ("＋ Add alternate", "＋ Añadir alternativa"),
+    ("Region lane {}, preview", "Carril de región {}, escuchar"),
+    ("🗑 Clear Steps", "🗑 Borrar pasos"),
+    ("  🎙 Recording Tracks", "  🎙 Pistas de grabación"),
+    ("Recording track {}: {}", "Pista de grabación {}: {}"),
+    ("✕ Remove Track", "✕ Quitar pista"),
+    ("Recording…  stop to enable steps", "Grabando…  detén para activar los pasos"),
+    ("Select a device and press 🔴 Rec to record", "Selecciona un dispositivo y pulsa 🔴 Grabar"),
+    ("Pick input…", "Elegir entrada…"),
+    ("↻ Refresh devices", "↻ Actualizar dispositivos"),
+    ("🔁 {} bar(s) left", "🔁 quedan {} compás(es)"),
+    ("🔁 waiting for bar…", "🔁 esperando al compás…"),
+    ("🔴 Rec", "🔴 Grabar"),
+    ("Stop the current recording first", "Detén primero la grabación actual"),
+    ("Record from selected input", "Grabar desde la entrada seleccionada"),
+    ("{} bar", "{} compás"),
+    ("→ Track", "→ Pista"),
+    ("→ Chops", "→ Cortes"),
+    ("What the loop take becomes: a drum track, or a drum track chopped on every beat", "En qué se convierte la toma en bucle: una pista de batería, o una pista de batería cortada en cada tiempo"),
+    ("🔁 Loop Rec", "🔁 Grabar bucle"),
+    ("Record exactly this many bars from the next bar line, then make a track from the take", "Grabar exactamente estos compases desde la próxima línea de compás y crear una pista con la toma"),
+    ("→ Drum Track", "→ Pista de batería"),
+    ("Promote this recording to a full drum track", "Convertir esta grabación en una pista de batería completa"),
+    ("take {} · {}", "toma {} · {}"),
+    ("⏺ Capture", "⏺ Captura"),
+    ("Keeps the last few seconds of an input so you can save what you just played", "Guarda los últimos segundos de una entrada para que puedas conservar lo que acabas de tocar"),
+    ("● Listening", "● Escuchando"),
+    ("○ Off", "○ Apagado"),
+    ("✋ Keep that!", "✋ ¡Guarda eso!"),
+    ("Save the buffer as a new drum track", "Guardar el búfer como pista de batería nueva"),
+    ("🔇 Muted", "🔇 Silenciado"),
+    ("⚠ Panic", "⚠ Pánico"),
+    ("Restore output", "Restaurar salida"),
+    ("Silence everything now (Esc Esc)", "Silenciarlo todo ya (Esc Esc)"),
+    ("Limit {:.1} dB", "Límite {:.1} dB"),
+    ("Limit", "Límite"),
+    ("Safety limiter at -1 dBFS on every output", "Limitador de seguridad a -1 dBFS en cada salida"),
+    ("Master volume (sequencer, pads, drums and sample playback)", "Volumen maestro (secuenciador, pads, batería y reproducción de la muestra)"),
+    ("Varispeed", "Varispeed"),
+    ("Playback rate of the main sample (pitch follows)", "Velocidad de reproducción de la muestra principal (el tono la sigue)"),
+    ("Position in the file ({}), the same at any varispeed", "Posición en el archivo ({}), la misma con cualquier varispeed"),
+    ("Seek — drag while stopped to hear where you are", "Buscar — arrastra con la reproducción parada para oír dónde estás"),
+    ("Marker {}", "Marcador {}"),
+    ("Input…", "Entrada…"),
+    ("■ Cancel punch", "■ Cancelar punch"),
+    ("⏺ Punch in", "⏺ Punch in"),
+    ("Play from a bar before the in marker and re-record up to the out marker", "Reproducir desde un compás antes del marcador de entrada y regrabar hasta el marcador de salida"),
+    (" bars", " compases"),
+    ("≈ {:.2} BPM in {}", "≈ {:.2} BPM en {}"),
+    ("⏱ Set BPM", "⏱ Fijar BPM"),
+    ("Set the sequencer tempo and draw a bar grid from the first marker", "Fijar el tempo del secuenciador y dibujar una rejilla de compases desde el primer marcador"),
+    ("Hide grid", "Ocultar rejilla"),
+    ("port ", "puerto "),
+    ("TCP port listeners connect to", "Puerto TCP al que se conectan los oyentes"),
+    ("📡 Sending ({})", "📡 Enviando ({})"),
+    ("📡 Send", "📡 Enviar"),
+    ("Stream the master output over TCP: 12-byte RABS header, then f32 LE frames", "Emitir la salida maestra por TCP: cabecera RABS de 12 bytes y luego frames f32 LE"),
+    ("Not compiled in (build with --features jack)", "No incluido en la compilación (compila con --features jack)"),
+    ("Output device, sample rate, buffer size and MIDI input", "Dispositivo de salida, frecuencia de muestreo, tamaño de búfer y entrada MIDI"),
+    ("JACK client name (Enter to reconnect)", "Nombre del cliente JACK (Intro para reconectar)"),
+    ("⇆ Transport", "⇆ Transporte"),
+    ("Start, stop and follow tempo with the JACK transport", "Iniciar, parar y seguir el tempo con el transporte de JACK"),
+    ("🎹 Piano Roll — {}", "🎹 Piano roll — {}"),
+    ("▶ Play Pattern", "▶ Reproducir patrón"),
+    ("Clear All", "Borrar todo"),
+    ("Click cell to toggle  ·  Rows = chops", "Clic en una celda para alternar  ·  Filas = cortes"),
+    ("No chops yet!\nPlay your sample and press M to drop markers.", "¡Aún no hay cortes!\nReproduce tu muestra y pulsa M para poner marcadores."),
+    ("Chop #{}", "Corte n.º {}"),
+    ("Piano roll grid: rows are chops, columns are steps", "Rejilla del piano roll: las filas son cortes, las columnas son pasos"),
+    ("📍 Markers — {}", "📍 Marcadores — {}"),
+    ("Unit", "Unidad"),
+    ("4/4 at {:.0} BPM  ·  bar:beat, both from 1", "4/4 a {:.0} BPM  ·  compás:tiempo, ambos desde 1"),
+    ("No markers yet", "Aún no hay marcadores"),
+    ("Delete marker", "Eliminar marcador"),
+    ("New", "Nuevo"),
+    ("Insert", "Insertar"),
+    ("➕ At playhead", "➕ En el cabezal"),
+    ("➕ At cursor", "➕ En el cursor"),
+    ("✓ Marker moved to {}", "✓ Marcador movido a {}"),
+    ("✗ Invalid marker position: \"{}\"", "✗ Posición de marcador no válida: \"{}\""),
+    ("MACROS", "MACROS"),
+    ("⚙ Assign", "⚙ Asignar"),
+    ("Map each macro to several parameters with ranges", "Asignar cada macro a varios parámetros con rangos"),
+    ("CHOPS", "CORTES"),
+    ("Crossfader: [ / ] nudge, \\ or double-click to centre. Map it from a macro too", "Crossfader: [ / ] para mover, \\ o doble clic para centrar. También se asigna desde una macro"),
+    ("DRUMS", "BATERÍA"),
+    ("1 bar", "1 compás"),
+    ("⟲ Stutter", "⟲ Stutter"),
+    ("Hold (or hold B) to loop the last stretch of the master output; let go to drop back in", "Mantén pulsado (o mantén B) para repetir el último tramo de la salida maestra; suelta para volver"),
+    ("🎛 Macro Assignments", "🎛 Asignaciones de macros"),
+    ("＋ Destination", "＋ Destino"),
+    ("SONG EDITOR", "EDITOR DE CANCIÓN"),
+    ("⏹ Stop Song", "⏹ Parar canción"),
+    ("▶ Play Song", "▶ Reproducir canción"),
+    ("Bar {}/{} · {:.0} BPM", "Compás {}/{} · {:.0} BPM"),
+    ("＋ Bar", "＋ Compás"),
+    ("＋ Pattern", "＋ Patrón"),
+    ("⤓ Bounce song", "⤓ Exportar canción"),
+    ("Render the arrangement to a WAV, tempo changes included", "Renderizar el arreglo a un WAV, con los cambios de tempo"),
+    ("✏ Edit (switch here)", "✏ Editar (cambiar aquí)"),
+    ("🥁 Fill pattern", "🥁 Patrón de fill"),
+    ("✕ Remove", "✕ Quitar"),
+    ("Song editor grid: rows are patterns, columns are bars", "Rejilla del editor de canción: las filas son patrones, las columnas son compases"),
+    ("Left-drag = paint  ·  Right-drag = erase  ·  Click pattern name to edit  ·  Right-click pattern for options", "Arrastrar con el izquierdo = pintar  ·  Arrastrar con el derecho = borrar  ·  Clic en el nombre del patrón para editarlo  ·  Clic derecho en el patrón para opciones"),
+    ("Playlist — Arrangement", "Lista — Arreglo"),
+    ("Add an audio track — load a file, then paint clips onto the timeline", "Añadir una pista de audio — carga un archivo y pinta clips en la línea de tiempo"),
+    ("⤓ Song", "⤓ Canción"),
+    ("Bounce the arrangement to a WAV, tempo changes included", "Exportar el arreglo a un WAV, con los cambios de tempo"),
+    ("Bar {} · {:.0} BPM", "Compás {} · {:.0} BPM"),
+    ("✊ Drag to move · Right-click to cancel", "✊ Arrastra para mover · Clic derecho para cancelar"),
+    ("NOTE", "NOTA"),
+    ("CHAN", "CANAL"),
+    ("PAT", "PAT"),
+    ("✏ Edit pattern", "✏ Editar patrón"),
+    ("🖌 Select as brush", "🖌 Usar como pincel"),
+    ("🔊 AUDIO", "🔊 AUDIO"),
+    ("no file", "sin archivo"),
+    ("📂 Load audio file", "📂 Cargar archivo de audio"),
+    ("🗑 Clear clips", "🗑 Borrar clips"),
+    ("✕ Remove track", "✕ Quitar pista"),
+    ("Playlist arrangement grid", "Rejilla del arreglo de la lista"),
+    ("🔊  Audio Tracks — Left-click to paint · Right-click to erase · Double-click label to load file", "🔊  Pistas de audio — Clic izquierdo para pintar · Clic derecho para borrar · Doble clic en la etiqueta para cargar un archivo"),
+    ("dbl-click to load", "doble clic para cargar"),
+    ("Patterns: Left=draw · Drag=move · Right=erase  ‖  Audio: Left=paint clip · Right=erase · Dbl-click label=load file", "Patrones: Izq.=dibujar · Arrastrar=mover · Der.=borrar  ‖  Audio: Izq.=pintar clip · Der.=borrar · Doble clic en la etiqueta=cargar archivo"),
+    ("{} pat · {} audio", "{} pat · {} audio"),
+    ("Right-click a bar for a tempo change", "Clic derecho en un compás para un cambio de tempo"),
+    ("Tempo at bar {}", "Tempo en el compás {}"),
+    ("Ramp over", "Rampa de"),
+    ("Glide from the tempo before; 0 jumps at the bar", "Deslizar desde el tempo anterior; 0 salta en el compás"),
+    ("＋ Tempo change ({:.0} BPM)", "＋ Cambio de tempo ({:.0} BPM)"),
+    ("Create new pattern (fresh workspace)", "Crear patrón nuevo (espacio de trabajo limpio)"),
+    ("📌 {} (Track {})", "📌 {} (Pista {})"),
+    ("Unpin", "Desfijar"),
+    ("⇅ Swap", "⇅ Intercambiar"),
+    ("Focus this sound and pin the other one", "Enfocar este sonido y fijar el otro"),
+    ("✂ Trim new track", "✂ Recortar pista nueva"),
+    ("Start", "Inicio"),
+    ("End", "Fin"),
+    ("Normalize", "Normalizar"),
+    ("Bring the peak to -1 dBFS", "Llevar el pico a -1 dBFS"),
+    ("▶ Preview", "▶ Escuchar"),
+    ("＋ Add track", "＋ Añadir pista"),
+    ("{:.3} s kept", "{:.3} s conservados"),
+    ("🕘 Versions", "🕘 Versiones"),
+    ("Save the project once — versions are kept beside it", "Guarda el proyecto una vez — las versiones se guardan a su lado"),
+    ("Note (optional)", "Nota (opcional)"),
+    ("💾 Save Version", "💾 Guardar versión"),
+    ("No versions yet", "Aún no hay versiones"),
+    ("{}  ·  {} pattern(s)", "{}  ·  {} patrón(es)"),
+    ("Delete this version", "Eliminar esta versión"),
+    ("⟲ Restore", "⟲ Restaurar"),
+    ("Load this version (the current state is saved as a version first)", "Cargar esta versión (antes se guarda el estado actual como versión)"),
+    ("Audio Sampler", "Sampler de audio"),
+    ("✗ Audio engine stopped: {}", "✗ Motor de audio detenido: {}"),
+    ("⟳ Restart engine", "⟳ Reiniciar motor"),
+    ("Reopen audio output; your project is kept", "Volver a abrir la salida de audio; tu proyecto se conserva"),
+    ("🔊 Turn audio on", "🔊 Activar audio"),
+    ("Leave safe mode; audio opens on the next sound", "Salir del modo seguro; el audio se abre con el próximo sonido"),
+    ("Try the selected output with a short silent stream", "Probar la salida seleccionada con un flujo corto en silencio"),
+    ("✗ Audio self-test: {}", "✗ Autoprueba de audio: {}"),
+    ("Turn audio off for this session", "Desactivar el audio en esta sesión"),
+    ("self-test failed", "la autoprueba falló"),
+    ("the last session didn't close cleanly", "la última sesión no se cerró bien"),
+    ("📂 Open", "📂 Abrir"),
+    ("Open a .rabies project", "Abrir un proyecto .rabies"),
+    ("💾 Save", "💾 Guardar"),
+    ("Save project (Ctrl+S)", "Guardar proyecto (Ctrl+S)"),
+    ("⤵ Import…", "⤵ Importar…"),
+    ("Bring patterns, kits or marks in from another project", "Traer patrones, kits o marcas de otro proyecto"),
+    ("Save a version of the project or roll back to one", "Guardar una versión del proyecto o volver a una"),
+    ("📁 Copy samples", "📁 Copiar muestras"),
+    ("Copy imported samples into the project's samples/ folder so the project is portable", "Copiar las muestras importadas en la carpeta samples/ del proyecto para que sea portable"),
+    ("◐ Mono one-shots", "◐ One-shots en mono"),
+    ("Fold new drum tracks to one channel: half the memory and mixing work. A track's right-click menu switches it back", "Pasar las pistas de batería nuevas a un canal: la mitad de memoria y de trabajo de mezcla. El menú contextual de una pista lo revierte"),
+    ("Load Sample", "Cargar muestra"),
+    ("Tracks", "Pistas"),
+    ("⏸ Pause", "⏸ Pausa"),
+    ("■ Stop", "■ Parar"),
+    ("Stopped", "Detenido"),
+    ("Playback Mode", "Modo de reproducción"),
+    ("Play to End", "Hasta el final"),
+    ("Playback: Play to End", "Reproducción: hasta el final"),
+    ("Play to Next", "Hasta el siguiente"),
+    ("Playback: Stop at Next Marker", "Reproducción: parar en el siguiente marcador"),
+    ("Region:", "Región:"),
+    ("Playback: Region {}", "Reproducción: región {}"),
+    ("🔁 Loop", "🔁 Bucle"),
+    ("Regions and selections repeat instead of stopping at their end", "Las regiones y selecciones se repiten en lugar de parar al final"),
+    ("Waveform  ·  {} (Track {})  —  press M while previewing to chop", "Forma de onda  ·  {} (Pista {})  —  pulsa M mientras escuchas para cortar"),
+    ("Waveform", "Forma de onda"),
+    ("📌 Pin", "📌 Fijar"),
+    ("Keep this waveform on screen above the focused one", "Mantener esta forma de onda en pantalla sobre la enfocada"),
+    ("Waveform. Left and right arrows move the playhead, M drops a marker", "Forma de onda. Las flechas izquierda y derecha mueven el cabezal, M pone un marcador"),
+    ("▶ Play selection", "▶ Reproducir selección"),
+    ("＋ Create region from selection", "＋ Crear región desde la selección"),
+    ("➕ Insert marker here", "➕ Insertar marcador aquí"),
+    ("➕ Insert marker at playhead", "➕ Insertar marcador en el cabezal"),
+    ("✏ Edit markers…", "✏ Editar marcadores…"),
+    ("No sample loaded – click Load Sample", "No hay muestra cargada – pulsa Cargar muestra"),
+    ("Analyzing waveform...", "Analizando la forma de onda..."),
+    ("📍 Markers…", "📍 Marcadores…"),
+    ("✂ Auto-chop…", "✂ Auto-corte…"),
+    ("Find the hits in this track and pick which ones become chops", "Buscar los golpes de esta pista y elegir cuáles se convierten en cortes"),
+    ("Regions", "Regiones"),
+    ("＋ Region from Selection", "＋ Región desde la selección"),
+    ("▶ Play Selection", "▶ Reproducir selección"),
+    ("＋ Create Region (First→Last Marker)", "＋ Crear región (primer→último marcador)"),
+    ("✓ Deleted region: {}", "✓ Región eliminada: {}"),
+    ("Add 2+ markers or drag across the waveform to create regions", "Añade 2 o más marcadores o arrastra sobre la forma de onda para crear regiones"),
+    ("Loading...", "Cargando..."),
+    ("Back to the sample's pitch", "Volver al tono de la muestra"),
+    (", playing", ", sonando"),
+    ("Triplet lane ({} steps per bar)", "Carril de tresillos ({} pasos por compás)"),
+    ("Envelope", "Envolvente"),
+    ("Off plays the sample at full level", "Apagado toca la muestra a nivel completo"),
+    ("Attack", "Ataque"),
+    ("Decay", "Caída"),
+    ("Sustain", "Sostenido"),
+    ("Release", "Liberación"),
+    ("Percussive", "Percusivo"),
+    ("Pluck", "Pulsado"),
+    ("MIDI: only the ALSA sequencer (Linux) is supported so far", "MIDI: por ahora solo se admite el secuenciador ALSA (Linux)"),
+    ("Nearest step", "Paso más cercano"),
+    ("Micro-timing", "Microtiempo"),
+    ("⏺ Recording pad hits into the pattern ({})", "⏺ Grabando los golpes de pad en el patrón ({})"),
+    ("Pad recording off", "Grabación de pads desactivada"),
+    ("✗ Track {} is chopped — focus it to record its chops", "✗ La pista {} tiene cortes — enfócala para grabar sus cortes"),
+    ("live take", "toma en vivo"),
+    ("⏺ Rec", "⏺ Grabar"),
+    ("Record pad and drum-key hits into the pattern while it plays", "Graba en el patrón los golpes de pads y teclas de batería mientras suena"),
+    ("Recorded hits snap to the nearest step, or keep their timing as a nudge off the step", "Los golpes grabados se ajustan al paso más cercano o conservan su tiempo como un desplazamiento del paso"),
+    ("BPM must be a number", "El BPM tiene que ser un número"),
+    ("Pad {} has nothing to play", "El pad {} no tiene nada que tocar"),
+    ("Step {} is past the end of a {}-step row", "El paso {} está más allá del final de una fila de {} pasos"),
+    ("No such row", "No existe esa fila"),
+    ("🕭 Count-in — the pattern starts in {:.1} s", "🕭 Cuenta previa — el patrón empieza en {:.1} s"),
+    ("🔴 Recording from {} after a bar's count-in", "🔴 Grabando desde {} tras un compás de cuenta previa"),
+    ("Count-in", "Cuenta previa"),
+    ("One bar of clicks before Play, or a recording, starts the pattern", "Un compás de clics antes de que Reproducir, o una grabación, inicie el patrón"),
+    ("crashed", "se bloqueó"),
+    ("{} crashed", "{} se bloqueó"),
+    ("Cancelled", "Cancelado"),
+    ("Cancelled: {}", "Cancelado: {}"),
+    ("Load {}", "Cargar {}"),
+    ("✓ Decoded {} — set its trim", "✓ {} decodificado — ajusta su recorte"),
+    ("Reload {}", "Recargar {}"),
+    ("Share {}", "Compartir {}"),
+    ("Audio self-test", "Autoprueba de audio"),
+    ("Pad bank {}", "Banco de pads {}"),
+    ("Kit report", "Informe del kit"),
+    ("✓ Measured {} track(s)", "✓ {} pista(s) medidas"),
+    ("Measuring…", "Midiendo…"),
+    ("⏳ Tasks", "⏳ Tareas"),
+    ("⏳ Tasks ({})", "⏳ Tareas ({})"),
+    ("Background loading, analysis and export", "Carga, análisis y exportación en segundo plano"),
+    ("Nothing running", "Nada en marcha"),
+    ("Waiting", "En espera"),
+    ("Clear finished", "Quitar terminadas"),
+    ("Clock", "Reloj"),
+    ("Sync to MIDI clock", "Sincronizar con el reloj MIDI"),
+    ("Not checked", "Sin revisar"),
+    ("Needs clearing", "Requiere autorización"),
+    ("Clearance pending", "Autorización pendiente"),
+    ("Cleared", "Autorizado"),
+    ("Free to use", "De uso libre"),
+    ("Source / credit", "Fuente / crédito"),
+    ("Notes", "Notas"),
+    ("📝 Notes & credits", "📝 Notas y créditos"),
+    ("Project notes", "Notas del proyecto"),
+    ("Credits", "Créditos"),
+    ("Writers, players, samples to credit on release", "Autores, músicos y samples que acreditar al publicar"),
+    ("{} still to clear", "{} por autorizar"),
+    ("Only those to clear", "Solo los que faltan por autorizar"),
+    ("📋 Copy credits", "📋 Copiar créditos"),
+    ("Project credits and every sample's source, as text", "Créditos del proyecto y la fuente de cada sample, como texto"),
+    ("✓ Credits copied", "✓ Créditos copiados"),
+    ("No samples yet", "Aún no hay samples"),
+    ("📝 Notes", "📝 Notas"),
+    ("Project notes and credits, and where each sample came from", "Notas y créditos del proyecto, y de dónde viene cada sample"),
+    ("📝 Notes & credit", "📝 Notas y crédito"),
+    ("The sequencer steps on the device's clock instead of its own, following its Start and Stop", "El secuenciador avanza con el reloj del dispositivo en lugar del suyo, siguiendo su Start y Stop"),
+    ("Clock out", "Salida de reloj"),
+    ("Send MIDI clock, Start and Stop so other gear follows our BPM", "Enviar reloj MIDI, Start y Stop para que otros equipos sigan nuestro BPM"),
+    ("✓ MIDI clock out: {}", "✓ Salida de reloj MIDI: {}"),
+    ("🔗 Link ({})", "🔗 Link ({})"),
+    ("🔗 Link", "🔗 Link"),
+    ("Ableton Link: share tempo and bar phase with apps on the network", "Ableton Link: compartir tempo y fase del compás con apps de la red"),
+    ("Link off", "Link desactivado"),
+    ("✓ Link on", "✓ Link activado"),
+    ("This build has no Ableton Link support (rebuild with --features link)", "Esta compilación no incluye Ableton Link (recompila con --features link)"),
+    ("Duck", "Atenuar"),
+    ("How far everything else dips while a sample prelistens (0 = not at all)", "Cuánto baja todo lo demás mientras se preescucha una muestra (0 = nada)"),
+    ("Stop the prelisten", "Detener la preescucha"),
+    ("Prelisten — whatever is playing dips under it", "Preescuchar — lo que esté sonando baja por debajo"),
+    ("Prelistening: {}", "Preescuchando: {}"),
+    ("📸 Mix", "📸 Mezcla"),
+    ("No snapshots yet", "Aún no hay instantáneas"),
+    ("Recall this mix", "Recuperar esta mezcla"),
+    ("💾 Save mix", "💾 Guardar mezcla"),
+    ("Gains, trims, mutes, solos, master and crossfader", "Ganancias, trims, mutes, solos, máster y crossfader"),
+    ("Fade", "Fundido"),
+    ("Time a recall takes to reach the snapshot (0 = at once)", "Tiempo que tarda una recuperación en llegar a la instantánea (0 = al instante)"),
+    ("✓ Mix snapshot \"{}\" saved", "✓ Instantánea de mezcla \"{}\" guardada"),
+    ("Mix snapshot \"{}\"", "Instantánea de mezcla \"{}\""),
+    ("The steps already have a triplet feel", "Los pasos ya van en tresillos"),
+    ("Stems…", "Stems…"),
+    ("One WAV per unmuted track, all sample-aligned and the same length", "Un WAV por pista sin silenciar, todos alineados a la muestra y de la misma duración"),
+    ("Folder for stems", "Carpeta para los stems"),
+    ("✗ No unmuted tracks to export", "✗ No hay pistas sin silenciar que exportar"),
+    ("✓ {} stems of {} frames to {} — sample-aligned from frame 0, 0 frames of latency to compensate", "✓ {} stems de {} frames en {} — alineados desde el frame 0, 0 frames de latencia que compensar"),
+    ("Bar : beat : step since ▶ (beats in the metronome's time signature)", "Compás : tiempo : paso desde ▶ (tiempos en el compás del metrónomo)"),
+    ("Times round the pattern", "Vueltas al patrón"),
+    ("Time since ▶, on the audio output's clock", "Tiempo desde ▶, según el reloj de la salida de audio"),
+    ("Whole row ahead of the grid (−) or behind it (+), up to half a step", "Toda la fila por delante de la rejilla (−) o por detrás (+), hasta medio paso"),
+    ("Show offsets in ticks, 96 to the beat", "Mostrar los desfases en ticks, 96 por tiempo"),
+    ("Back on the grid", "De vuelta a la rejilla"),
+    ("🎲 Humanize", "🎲 Humanizar"),
+    ("every row", "todas las filas"),
+    ("Each hit lands a little off the grid and a little louder or softer, rolled as it plays; the steps stay where they are", "Cada golpe cae un poco fuera de la rejilla y un poco más fuerte o más suave, sorteado al sonar; los pasos se quedan donde están"),
+    ("Rows can add their own from their right-click menu", "Cada fila puede añadir el suyo desde su menú contextual"),
+    ("Humanize timing and velocity as the pattern plays", "Humanizar tiempo y velocidad mientras suena el patrón"),
+    ("🎲 humanize", "🎲 humanizar"),
+    ("Random timing and level on this row's hits, on top of the project's", "Tiempo y nivel aleatorios en los golpes de esta fila, sumados a los del proyecto"),
+    ("euclidean fill", "relleno euclidiano"),
+    ("✓ {} filled with {} over {} (rotated {})", "✓ {} rellenada con {} en {} (rotado {})"),
+    ("⬡ Euclidean…", "⬡ Euclidiano…"),
+    ("pulses ", "pulsos "),
+    ("steps ", "pasos "),
+    ("rotate ", "rotar "),
+    ("Fill row", "Rellenar fila"),
+    ("Replace this row's hits with the rhythm, repeated along the row", "Sustituir los golpes de esta fila por el ritmo, repetido a lo largo de la fila"),
+    ("📋 Copied pattern \"{}\"", "📋 Patrón \"{}\" copiado"),
+    ("pattern paste", "pegado de patrón"),
+    ("📋 \"{}\" pasted over \"{}\"", "📋 \"{}\" pegado sobre \"{}\""),
+    ("📋 Copied {}", "📋 Copiada {}"),
+    ("row paste", "pegado de fila"),
+    ("📋 Pasted onto {}", "📋 Pegado en {}"),
+    ("📋 Copy row", "📋 Copiar fila"),
+    ("Paste row", "Pegar fila"),
+    ("Replace this row's hits with the copied row's", "Sustituir los golpes de esta fila por los de la fila copiada"),
+    ("📋 Copy", "📋 Copiar"),
+    ("📋 Paste over", "📋 Pegar encima"),
+    ("Replace this pattern's steps and tracks with the copied pattern's; name and song place stay", "Sustituir los pasos y pistas de este patrón por los del patrón copiado; el nombre y su lugar en la canción se mantienen"),
+    ("📋 Paste as new", "📋 Pegar como nuevo"),
+    ("song bar {}", "compás {} de la canción"),
+    ("pattern {}, step {}", "patrón {}, paso {}"),
+    ("{} at {:.0}%", "{} al {:.0}%"),
+    ("✗ Can't resume: {} is gone", "✗ No se puede reanudar: {} ya no existe"),
+    ("⏯ Resumed: {}", "⏯ Reanudado: {}"),
+    ("Playback stopped at {}", "La reproducción se detuvo en {}"),
+    ("⏯ Resume", "⏯ Reanudar"),
+    ("Put the project, pattern, step and playhead back and play on", "Recuperar el proyecto, el patrón, el paso y el cabezal y seguir tocando"),
+    ("Dismiss", "Descartar"),
+    ("step", "paso"),
+    ("note", "nota"),
+    ("clear notes", "borrar notas"),
+    ("clear steps", "borrar pasos"),
+    ("marker", "marcador"),
+    ("marker move", "mover marcador"),
+    ("chop delete", "borrar corte"),
+    ("region", "región"),
+    ("region delete", "borrar región"),
+    ("slice", "rebanado"),
+    ("auto-chop", "corte automático"),
+    ("track add", "añadir pista"),
+    ("track remove", "quitar pista"),
+    ("↷ Redo {}", "↷ Rehacer {}"),
+    ("↷ Redid {}", "↷ Rehecho: {}"),
+    ("Nothing to undo", "Nada que deshacer"),
+    ("Nothing to redo", "Nada que rehacer"),
+    ("Load {} tracks", "Cargar {} pistas"),
+    ("None of the {} files could be loaded", "No se pudo cargar ninguno de los {} archivos"),
+    ("✓ {} tracks loaded{}", "✓ {} pistas cargadas{}"),
+    (" — {} file(s) couldn't be decoded", " — no se pudieron decodificar {} archivo(s)"),
+    ("Audition a file", "Audicionar un archivo"),
+    ("Audition {}", "Audicionar {}"),
+    ("Can't audition {}: {}", "No se puede audicionar {}: {}"),
+    ("Audition a file before loading it: its first {:.0} s prelisten", "Audicionar un archivo antes de cargarlo: se preescuchan sus primeros {:.0} s"),
+    ("Load the whole file as a new track", "Cargar el archivo completo como pista nueva"),
+    ("Prelisten again", "Preescuchar de nuevo"),
+    ("🎧 {} (first {:.0} s)", "🎧 {} (primeros {:.0} s)"),
+    (" — {} chops from its sidecar", " — {} cortes desde su archivo auxiliar"),
+    ("Import from {}", "Importar de {}"),
+    ("✓ Decoded {} sample(s)", "✓ {} muestra(s) decodificada(s)"),
+    ("MIDI: {} is gone", "MIDI: {} ya no está"),
+    ("✗ The main track's chops are on the main grid, which sets its steps", "✗ Los cortes de la pista principal están en la rejilla principal, que fija sus pasos"),
+    ("The main track's chops are on the main grid, which sets its steps", "Los cortes de la pista principal están en la rejilla principal, que fija sus pasos"),
+    ("✗ Switch the triplet rows back to straight first", "✗ Vuelve primero las filas de tresillos a binario"),
+    ("✓ Track {} at {} — {} hit(s) merged", "✓ Pista {} a {} — {} golpe(s) fusionados"),
+    ("✓ Track {} at {}", "✓ Pista {} a {}"),
+    ("Grid ({})", "Rejilla ({})"),
+    ("Steps: {}", "Pasos: {}"),
+    ("Steps per bar for this track's rows; hits keep their place in the bar", "Pasos por compás para las filas de esta pista; los golpes conservan su lugar en el compás"),
+    ("track resolution", "resolución de pista"),
+    (", chops up to {:.0} ms off the grid", ", cortes hasta {:.0} ms fuera de la rejilla"),
+    (" — not saved to disk: {}", " — no se guardó en disco: {}"),
+    (", {} past the pattern end left out", ", {} más allá del final del patrón omitidos"),
+    ("{} device(s) · {} Hz · {} ch · {} callbacks, {:.0}% of real time, worst gap {:.1} ms ({:.1} ms buffer)", "{} dispositivo(s) · {} Hz · {} canales · {} llamadas, {:.0}% del tiempo real, peor hueco {:.1} ms (búfer de {:.1} ms)"),
+    ("Output didn't open: {}", "La salida no se abrió: {}"),
+    ("{} min ago", "hace {} min"),
+    ("{} h ago", "hace {} h"),
+    ("{} d ago", "hace {} d"),
+    ("Before restoring {}", "Antes de restaurar {}"),
+    ("is", "son"),
+    ("from", "de"),
+    ("to", "a"),
+    (" — no {} encoder found (install ffmpeg), kept as WAV", " — no se encontró codificador {} (instala ffmpeg), se dejó en WAV"),
+    (" — {} encoding failed ({}), kept as WAV", " — falló la codificación {} ({}), se dejó en WAV"),
+    (", {} past this {}-pad grid", ", {} más allá de esta rejilla de {} pads"),
+];
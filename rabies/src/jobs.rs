@@ -0,0 +1,196 @@
+// src/jobs.rs
+// Background jobs. Slow work — decoding files, analysis, encoding exports, the audio
+// self-test — runs as a job rather than on a bare thread: it gets a line in the Tasks
+// panel with its progress, can be cancelled, and leaves its result or error there once
+// it's done (its last words also go to the status line). Jobs wait in a queue for one of
+// a few worker slots, so a big import doesn't start dozens of decoders at once. A job
+// that panics is reported as crashed and the app carries on. Senders that run for as
+// long as a setting is on — MIDI clock out, network send — keep a thread of their own
+// instead of holding a slot.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use parking_lot::{Condvar, Mutex};
+use crate::gui::AppState;
+
+/// Jobs running at once; the rest queue.
+const WORKERS: usize = 2;
+/// Finished jobs kept for the panel.
+const KEEP_FINISHED: usize = 20;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JobKind {
+    /// Decoding audio into the project.
+    Load,
+    /// Decoding a file just to hear it; unlike `Load` it doesn't block the window.
+    Preview,
+    Analysis,
+    Export,
+    Test,
+}
+
+impl JobKind {
+    pub fn icon(self) -> &'static str {
+        match self {
+            JobKind::Load     => "📂",
+            JobKind::Preview  => "🎧",
+            JobKind::Analysis => "🔬",
+            JobKind::Export   => "📤",
+            JobKind::Test     => "🩺",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum JobState {
+    Queued,
+    Running,
+    Done(String),
+    Failed(String),
+    Cancelled,
+}
+
+impl JobState {
+    pub fn finished(&self) -> bool { !matches!(self, JobState::Queued | JobState::Running) }
+}
+
+#[derive(Clone, Debug)]
+pub struct JobInfo {
+    pub id:       u64,
+    pub kind:     JobKind,
+    pub label:    String,
+    /// 0-1 when the job can tell; `None` spins.
+    pub progress: Option<f32>,
+    pub state:    JobState,
+}
+
+struct Entry {
+    info:   JobInfo,
+    cancel: Arc<AtomicBool>,
+}
+
+#[derive(Default)]
+struct Queue {
+    entries: Vec<Entry>,
+    running: usize,
+    next_id: u64,
+}
+
+impl Queue {
+    fn entry(&mut self, id: u64) -> Option<&mut Entry> { self.entries.iter_mut().find(|e| e.info.id == id) }
+}
+
+/// The job list, shared by the workers and the panel.
+#[derive(Clone, Default)]
+pub struct Jobs {
+    queue: Arc<(Mutex<Queue>, Condvar)>,
+}
+
+/// What a running job sees of itself.
+pub struct Job {
+    id:     u64,
+    cancel: Arc<AtomicBool>,
+    jobs:   Jobs,
+}
+
+impl Job {
+    /// Cancel was pressed; the job should stop at the next good place and return an `Err`.
+    pub fn cancelled(&self) -> bool { self.cancel.load(Ordering::Relaxed) }
+
+    /// `done` of `total` parts finished.
+    pub fn progress(&self, done: usize, total: usize) {
+        if let Some(e) = self.jobs.queue.0.lock().entry(self.id) {
+            e.info.progress = Some(done as f32 / total.max(1) as f32);
+        }
+    }
+}
+
+impl Jobs {
+    /// Queue `work`. Its `Ok` message or `Err` is what the panel shows when it's done.
+    pub fn spawn<F>(&self, kind: JobKind, label: impl Into<String>, work: F) -> u64
+    where F: FnOnce(&Job) -> Result<String, String> + Send + 'static {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let id = {
+            let mut q = self.queue.0.lock();
+            q.next_id += 1;
+            let id = q.next_id;
+            q.entries.push(Entry {
+                info: JobInfo { id, kind, label: label.into(), progress: None, state: JobState::Queued },
+                cancel: cancel.clone(),
+            });
+            let finished = q.entries.iter().filter(|e| e.info.state.finished()).count();
+            let mut drop = finished.saturating_sub(KEEP_FINISHED);
+            q.entries.retain(|e| {
+                let old = drop > 0 && e.info.state.finished();
+                if old { drop -= 1; }
+                !old
+            });
+            id
+        };
+
+        let jobs = self.clone();
+        std::thread::spawn(move || {
+            let (lock, slots) = &*jobs.queue;
+            {
+                let mut q = lock.lock();
+                while q.running >= WORKERS && !cancel.load(Ordering::Relaxed) { slots.wait(&mut q); }
+                let state = if cancel.load(Ordering::Relaxed) { JobState::Cancelled } else { JobState::Running };
+                let run = state == JobState::Running;
+                if let Some(e) = q.entry(id) { e.info.state = state; }
+                if !run { return; }
+                q.running += 1;
+            }
+            let job = Job { id, cancel: cancel.clone(), jobs: jobs.clone() };
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| work(&job)));
+            let state = match result {
+                Ok(Ok(msg))                                  => JobState::Done(msg),
+                Ok(Err(_)) if cancel.load(Ordering::Relaxed) => JobState::Cancelled,
+                Ok(Err(e))                                   => JobState::Failed(e),
+                Err(_)                                       => JobState::Failed(t!("crashed").to_string()),
+            };
+            let mut q = lock.lock();
+            q.running -= 1;
+            if let Some(e) = q.entry(id) { e.info.state = state; }
+            slots.notify_all();
+        });
+        id
+    }
+
+    /// Ask job `id` to stop; a queued one never starts.
+    pub fn cancel(&self, id: u64) {
+        if let Some(e) = self.queue.0.lock().entry(id) { e.cancel.store(true, Ordering::Relaxed); }
+        self.queue.1.notify_all();
+    }
+
+    pub fn list(&self) -> Vec<JobInfo> { self.queue.0.lock().entries.iter().map(|e| e.info.clone()).collect() }
+
+    /// Jobs queued or running.
+    pub fn active(&self) -> usize { self.queue.0.lock().entries.iter().filter(|e| !e.info.state.finished()).count() }
+
+    /// Some job of `kind` is queued or running.
+    pub fn busy(&self, kind: JobKind) -> bool {
+        self.queue.0.lock().entries.iter().any(|e| e.info.kind == kind && !e.info.state.finished())
+    }
+
+    pub fn clear_finished(&self) { self.queue.0.lock().entries.retain(|e| !e.info.state.finished()); }
+}
+
+impl AppState {
+    /// `Jobs::spawn`, with the outcome also put on the status line.
+    pub fn spawn_job<F>(&self, kind: JobKind, label: impl Into<String>, work: F) -> u64
+    where F: FnOnce(&Job) -> Result<String, String> + Send + 'static {
+        let label  = label.into();
+        let status = self.status.clone();
+        let name   = label.clone();
+        self.jobs.spawn(kind, label, move |job| {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| work(job)))
+                .unwrap_or_else(|_| Err(t!("{} crashed", name)));
+            *status.write() = match &result {
+                Ok(msg)                    => msg.clone(),
+                Err(_) if job.cancelled()  => t!("Cancelled: {}", name),
+                Err(e)                     => format!("✗ {}", e),
+            };
+            result
+        })
+    }
+}
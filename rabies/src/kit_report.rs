@@ -0,0 +1,166 @@
+// src/kit_report.rs
+// Kit report: every drum track measured as it plays (trim, polarity and gain applied) —
+// peak, RMS, short-term LUFS and the length up to where the tail dies away — with fixes
+// that apply in one click. Gain fixes only move the track's gain; trimming a tail
+// writes a trimmed copy like the import trim does, and the markers keep their place.
+
+use std::sync::Arc;
+use crate::audio::AudioAsset;
+use crate::gui::AppState;
+use crate::jobs::JobKind;
+use crate::limiter::CEILING;
+use crate::loudness::short_term_lufs;
+
+/// Level, relative to the peak, below which the tail counts as silence.
+const TAIL_DB: f32 = -60.0;
+/// Tails shorter than this aren't worth trimming.
+const MIN_TAIL_SECS: f32 = 0.05;
+/// Peaks quieter than this get a normalise suggestion.
+const QUIET_PEAK_DB: f32 = -6.0;
+const FADE_SECS: f32 = 0.005;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KitFix {
+    /// Cut the file after `keep` frames.
+    TrimTail { keep: usize },
+    /// Change the gain by `db` so the peak reaches the limiter ceiling.
+    Normalize { db: f32 },
+    /// Lower the gain by `db` so the peak stays under the limiter ceiling.
+    ReduceGain { db: f32 },
+}
+
+impl KitFix {
+    pub fn label(&self, sample_rate: u32) -> String {
+        match *self {
+            KitFix::TrimTail { keep } => t!("✂ Trim tail to {:.2} s", keep as f32 / sample_rate.max(1) as f32),
+            KitFix::Normalize { db }  => t!("⬆ Normalize {:+.1} dB", db),
+            KitFix::ReduceGain { db } => t!("⬇ Reduce gain {:+.1} dB", db),
+        }
+    }
+}
+
+/// One row of the report.
+#[derive(Clone, Debug)]
+pub struct TrackReport {
+    pub track_idx:   usize,
+    pub name:        String,
+    pub sample_rate: u32,
+    pub peak_db:     f32,
+    /// Over the effective length, so a silent tail doesn't drag it down.
+    pub rms_db:      f32,
+    pub lufs:        Option<f32>,
+    pub length_secs: f32,
+    pub effective_secs: f32,
+    pub fixes:       Vec<KitFix>,
+}
+
+fn db(x: f32) -> f32 { 20.0 * x.max(1e-9).log10() }
+
+fn measure(track_idx: usize, asset: &AudioAsset, level: f32) -> TrackReport {
+    let ch     = (asset.channels as usize).max(1);
+    let rate   = asset.sample_rate.max(1);
+    let frames = asset.pcm.len() / ch;
+    let level  = level.abs();
+    let peak   = asset.pcm.iter().fold(0.0f32, |m, x| m.max(x.abs())) * level;
+
+    let floor  = peak * 10f32.powf(TAIL_DB / 20.0);
+    let last   = asset.pcm.iter().rposition(|x| x.abs() * level > floor).map_or(0, |i| i / ch + 1);
+    let body   = &asset.pcm[..last * ch];
+    let rms    = if body.is_empty() { 0.0 } else {
+        (body.iter().map(|x| x * x).sum::<f32>() / body.len() as f32).sqrt() * level
+    };
+    let lufs   = short_term_lufs(&asset.pcm, ch, rate).map(|l| l + db(level));
+
+    let mut fixes = Vec::new();
+    let tail = (frames - last) as f32 / rate as f32;
+    if last > 0 && tail >= MIN_TAIL_SECS { fixes.push(KitFix::TrimTail { keep: last }); }
+    if peak > 0.0 {
+        let to_ceiling = db(CEILING) - db(peak);
+        if to_ceiling < -0.1 { fixes.push(KitFix::ReduceGain { db: to_ceiling }); }
+        else if db(peak) < QUIET_PEAK_DB { fixes.push(KitFix::Normalize { db: to_ceiling }); }
+    }
+
+    TrackReport {
+        track_idx,
+        name: asset.file_name.clone(),
+        sample_rate: rate,
+        peak_db: db(peak),
+        rms_db: db(rms),
+        lufs,
+        length_secs: frames as f32 / rate as f32,
+        effective_secs: last as f32 / rate as f32,
+        fixes,
+    }
+}
+
+impl AppState {
+    /// Measure every drum track in the background, then open (or refresh) the report window.
+    pub fn open_kit_report(&self) {
+        let tracks: Vec<(Arc<AudioAsset>, f32)> = self.drum_tracks.read().iter()
+            .map(|t| (t.asset.clone(), t.level()))
+            .collect();
+        let kit_report = self.kit_report.clone();
+        self.jobs.spawn(JobKind::Analysis, t!("Kit report"), move |job| {
+            let mut rows = Vec::new();
+            for (i, (asset, level)) in tracks.iter().enumerate() {
+                if job.cancelled() { return Err(t!("Cancelled").to_string()); }
+                job.progress(i, tracks.len());
+                rows.push(measure(i, asset, *level));
+            }
+            *kit_report.write() = Some(rows);
+            Ok(t!("✓ Measured {} track(s)", tracks.len()))
+        });
+    }
+
+    pub fn apply_kit_fix(&self, track_idx: usize, fix: KitFix) {
+        let note = match fix {
+            KitFix::Normalize { db } | KitFix::ReduceGain { db } => {
+                let mut tracks = self.drum_tracks.write();
+                let Some(t) = tracks.get_mut(track_idx) else { return; };
+                t.gain *= 10f32.powf(db / 20.0);
+                t!("✓ {}: gain {:+.1} dB", t.asset.file_name, db)
+            }
+            KitFix::TrimTail { keep } => self.trim_track_tail(track_idx, keep),
+        };
+        *self.status.write() = note;
+    }
+
+    /// Cut track `track_idx` after `keep` frames, with a short fade so it doesn't click.
+    fn trim_track_tail(&self, track_idx: usize, keep: usize) -> String {
+        let Some(old) = self.drum_tracks.read().get(track_idx).map(|t| t.asset.clone()) else { return String::new(); };
+        let ch   = (old.channels as usize).max(1);
+        let keep = keep.min(old.frames as usize).max(1);
+        let mut pcm = old.pcm[..keep * ch].to_vec();
+        let fade = ((FADE_SECS * old.sample_rate as f32) as usize).min(keep);
+        for (i, frame) in pcm.chunks_mut(ch).skip(keep - fade).enumerate() {
+            let g = 1.0 - (i + 1) as f32 / fade as f32;
+            for x in frame.iter_mut() { *x *= g; }
+        }
+        let asset = Arc::new(AudioAsset { pcm, frames: keep as u64, ..(*old).clone() });
+
+        let saved = self.import_dir().map(|d| {
+            let path = crate::trim::trimmed_path(&d.join(crate::project::SAMPLES_DIR), &asset.file_name);
+            crate::render::write_float_wav(&path, &asset.pcm, asset.sample_rate, asset.channels)
+                .map(|()| path.to_string_lossy().to_string())
+        });
+        let (file_path, note) = match saved {
+            Some(Ok(p))  => (Some(p), String::new()),
+            Some(Err(e)) => (None, t!(" — not saved to disk: {}", e)),
+            None         => (None, t!(" — kept in memory only (save the project first to store trimmed copies)").to_string()),
+        };
+        if let Some(p) = &file_path { self.pool_asset(p, asset.clone()); }
+
+        // Markers are fractions of the length: rescale so they stay on the same audio
+        let scale = old.frames as f32 / keep as f32;
+        for m in self.samples_manager.get_marks_for_sample(&old.sample_uuid) {
+            self.samples_manager.update_mark_position_by_id(m.id, m.position * scale);
+        }
+        let waveform = self.audio_manager.analyze_waveform(&asset, 400);
+        if let Some(t) = self.drum_tracks.write().get_mut(track_idx) {
+            t.asset     = asset.clone();
+            t.waveform  = Some(waveform);
+            t.file_path = file_path;
+        }
+        t!("✓ {}: tail trimmed to {:.2} s{}", asset.file_name, keep as f32 / asset.sample_rate.max(1) as f32, note)
+    }
+}
@@ -0,0 +1,132 @@
+// src/kit_swap.rs
+// Swap kit: every drum track gets a line-up of browser samples of the same category
+// and a similar length. Stepping through them puts each one in the track in place —
+// steps, chops and settings stay — so it's heard with the pattern looping. Keep makes
+// the stand-in the track's sample for good; rows not kept go back to the original
+// when the tool closes.
+
+use std::sync::Arc;
+use uuid::Uuid;
+use crate::audio::AudioAsset;
+use crate::category::classify;
+use crate::gui::{AppState, WaveformFocus};
+use crate::mono::fold_to_mono;
+
+/// Stand-ins offered per track.
+const MAX_CANDIDATES: usize = 8;
+
+pub struct SwapRow {
+    /// The track, found again by UUID so reordering doesn't lose it.
+    pub sample_uuid: Uuid,
+    pub name:        String,
+    original:        Arc<AudioAsset>,
+    /// Pool paths, closest in length first.
+    pub candidates:  Vec<String>,
+    /// Candidate in the track now, `None` for the original.
+    pub current:     Option<usize>,
+}
+
+pub struct KitSwap {
+    pub rows: Vec<SwapRow>,
+}
+
+fn secs(asset: &AudioAsset) -> f32 {
+    asset.frames.max(1) as f32 / asset.sample_rate.max(1) as f32
+}
+
+impl AppState {
+    /// Line up stand-ins for every drum track and open the tool.
+    pub fn open_kit_swap(&self) {
+        let pool: Vec<(String, Arc<AudioAsset>, crate::category::DrumCategory)> = self.asset_pool.read().iter()
+            .map(|(p, a)| (p.clone(), a.clone(), classify(a)))
+            .collect();
+        let rows = self.drum_tracks.read().iter().map(|t| {
+            let len = secs(&t.asset);
+            let mut candidates: Vec<(f32, String)> = pool.iter()
+                .filter(|(p, a, c)| *c == t.category
+                    && t.file_path.as_deref() != Some(p.as_str())
+                    && a.file_name != t.asset.file_name)
+                .map(|(p, a, _)| ((secs(a) / len).ln().abs(), p.clone()))
+                .collect();
+            candidates.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+            SwapRow {
+                sample_uuid: t.sample_uuid,
+                name:        t.asset.file_name.clone(),
+                original:    t.asset.clone(),
+                candidates:  candidates.into_iter().take(MAX_CANDIDATES).map(|(_, p)| p).collect(),
+                current:     None,
+            }
+        }).collect();
+        *self.kit_swap.write() = Some(KitSwap { rows });
+    }
+
+    /// Put the next (`dir` > 0) or previous stand-in into row `row`'s track, wrapping
+    /// round through the original.
+    pub fn kit_swap_step(&self, row: usize, dir: isize) {
+        let (uuid, pick) = {
+            let mut guard = self.kit_swap.write();
+            let Some(r) = guard.as_mut().and_then(|s| s.rows.get_mut(row)) else { return; };
+            if r.candidates.is_empty() { return; }
+            // Slot 0 is the original, 1..=n the candidates
+            let n    = r.candidates.len() as isize + 1;
+            let slot = r.current.map_or(0, |c| c as isize + 1);
+            let next = (slot + dir).rem_euclid(n);
+            r.current = (next > 0).then(|| next as usize - 1);
+            (r.sample_uuid, r.current.map(|c| r.candidates[c].clone()))
+        };
+        match pick {
+            Some(path) => {
+                let Some(pooled) = self.asset_pool.read().get(&path).cloned() else { return; };
+                self.put_in_track(uuid, AudioAsset { sample_uuid: uuid, ..(*pooled).clone() });
+                *self.status.write() = t!("Hearing {}", pooled.file_name);
+            }
+            None => self.kit_swap_revert(row),
+        }
+    }
+
+    /// Make the stand-in row `row` is on the track's sample and drop the row.
+    pub fn kit_swap_keep(&self, row: usize) {
+        let mut guard = self.kit_swap.write();
+        let Some(swap) = guard.as_mut() else { return; };
+        let Some(r) = swap.rows.get(row) else { return; };
+        let Some(path) = r.current.map(|c| r.candidates[c].clone()) else { return; };
+        let name = r.name.clone();
+        if let Some(t) = self.drum_tracks.write().iter_mut().find(|t| t.sample_uuid == r.sample_uuid) {
+            *self.status.write() = t!("✓ {} replaced by {}", name, t.asset.file_name);
+            t.file_path = Some(path);
+        }
+        swap.rows.remove(row);
+    }
+
+    /// Give row `row`'s track its original sample back.
+    pub fn kit_swap_revert(&self, row: usize) {
+        let (uuid, original) = {
+            let mut guard = self.kit_swap.write();
+            let Some(r) = guard.as_mut().and_then(|s| s.rows.get_mut(row)) else { return; };
+            r.current = None;
+            (r.sample_uuid, r.original.clone())
+        };
+        self.put_in_track(uuid, (*original).clone());
+    }
+
+    /// Close the tool, putting back every original that wasn't kept.
+    pub fn close_kit_swap(&self) {
+        let Some(swap) = self.kit_swap.write().take() else { return; };
+        for r in swap.rows.into_iter().filter(|r| r.current.is_some()) {
+            self.put_in_track(r.sample_uuid, (*r.original).clone());
+        }
+    }
+
+    /// Put `asset` in the track with `uuid`, folded if the track is mono.
+    fn put_in_track(&self, uuid: Uuid, asset: AudioAsset) {
+        let mut tracks = self.drum_tracks.write();
+        let Some((i, t)) = tracks.iter_mut().enumerate().find(|(_, t)| t.sample_uuid == uuid) else { return; };
+        let asset    = Arc::new(if t.mono { fold_to_mono(&asset) } else { asset });
+        let waveform = self.audio_manager.analyze_waveform(&asset, 400);
+        if *self.waveform_focus.read() == WaveformFocus::DrumTrack(i) {
+            *self.waveform_analysis.write() = Some(waveform.clone());
+        }
+        t.asset    = asset;
+        t.waveform = Some(waveform);
+    }
+}
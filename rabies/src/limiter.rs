@@ -0,0 +1,93 @@
+// src/limiter.rs
+// Last stage of every output stream: panic mute gate + safety peak limiter.
+// Also the master bus soft clipper that stacked voices go through first.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use atomic_float::AtomicF32;
+
+/// Limiter ceiling, linear (-1 dBFS).
+pub const CEILING: f32 = 0.891;
+const RELEASE_SECS: f32 = 0.25;
+/// Soft clipper knee: linear below, tanh-rounded up to ±1 above.
+const KNEE: f32 = 0.8;
+
+/// Saturate `x` smoothly into ±1 (unchanged while |x| ≤ `KNEE`).
+pub fn soft_clip(x: f32) -> f32 {
+    let a = x.abs();
+    if a <= KNEE { return x; }
+    let room = 1.0 - KNEE;
+    (KNEE + room * ((a - KNEE) / room).tanh()).copysign(x)
+}
+
+/// Switches shared between the UI and the output streams.
+#[derive(Clone)]
+pub struct SafetyControls {
+    /// Panic gate: every stream outputs silence while set.
+    pub muted:     Arc<AtomicBool>,
+    pub limiter:   Arc<AtomicBool>,
+    /// Deepest gain reduction since the UI last took it, dB (≤ 0).
+    pub reduction: Arc<AtomicF32>,
+}
+
+impl Default for SafetyControls {
+    fn default() -> Self {
+        Self {
+            muted:     Arc::new(AtomicBool::new(false)),
+            limiter:   Arc::new(AtomicBool::new(true)),
+            reduction: Arc::new(AtomicF32::new(0.0)),
+        }
+    }
+}
+
+impl SafetyControls {
+    /// Gain reduction peak since the last call, then reset.
+    pub fn take_reduction(&self) -> f32 {
+        self.reduction.swap(0.0, Ordering::Relaxed)
+    }
+}
+
+/// Per-stream limiter state (instant attack, smooth release).
+pub struct SafetyStage {
+    controls: SafetyControls,
+    channels: usize,
+    release:  f32,
+    gain:     f32,
+}
+
+impl SafetyStage {
+    pub fn new(controls: SafetyControls, channels: usize, sample_rate: f32) -> Self {
+        let release = 1.0 - (-1.0 / (RELEASE_SECS * sample_rate.max(1.0))).exp();
+        Self { controls, channels: channels.max(1), release, gain: 1.0 }
+    }
+
+    /// Process one interleaved buffer in place.
+    pub fn process(&mut self, data: &mut [f32]) {
+        if self.controls.muted.load(Ordering::Relaxed) {
+            data.fill(0.0);
+            self.gain = 1.0;
+            return;
+        }
+        let limit = self.controls.limiter.load(Ordering::Relaxed);
+        let mut lowest = 1.0f32;
+        for frame in data.chunks_mut(self.channels) {
+            let mut peak = 0.0f32;
+            for s in frame.iter_mut() {
+                // A blown-up feedback path can go NaN/inf — never pass that on
+                if !s.is_finite() { *s = 0.0; }
+                peak = peak.max(s.abs());
+            }
+            if !limit { continue; }
+            let need  = if peak > CEILING { CEILING / peak } else { 1.0 };
+            self.gain = need.min(self.gain + (1.0 - self.gain) * self.release);
+            for s in frame.iter_mut() { *s *= self.gain; }
+            lowest = lowest.min(self.gain);
+        }
+        if lowest < 1.0 {
+            let db = 20.0 * lowest.log10();
+            if db < self.controls.reduction.load(Ordering::Relaxed) {
+                self.controls.reduction.store(db, Ordering::Relaxed);
+            }
+        }
+    }
+}
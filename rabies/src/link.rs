@@ -0,0 +1,164 @@
+// src/link.rs
+// Ableton Link. With Link on, the app joins the Link session on the local network: a
+// tempo change here goes out to every peer and theirs comes back into the BPM field,
+// and the sequencer's steps follow the session's beat rather than its own timer, each
+// one on the session's step boundary and at the step the bar's phase says. Steps are
+// timed off Link's timeline and queued a little ahead with a delay, so they land on
+// the boundary rather than on whichever UI frame notices it. Four beats
+// make the bar (Link's "quantum"), so step 0 of a pattern lands on the session's
+// downbeat whichever peer started first.
+//
+// Link is a C++ library; builds without `--features link` get a stub that says so.
+
+use std::time::{Duration, Instant};
+use crate::bus::AppCommand;
+use crate::gui::AppState;
+
+#[cfg(feature = "link")]
+pub use ableton::Link;
+#[cfg(not(feature = "link"))]
+pub use unsupported::Link;
+
+/// Beats in the bar whose phase peers agree on.
+const QUANTUM: f64 = 4.0;
+/// How far ahead of the session's timeline steps are scheduled. Covers a UI frame and
+/// some, so each step's voices are queued before its boundary and start on it exactly.
+const LINK_LEAD_SECS: f64 = 0.05;
+/// Tempo differences smaller than this are rounding, not a change.
+const BPM_EPSILON: f32 = 0.01;
+
+#[cfg(feature = "link")]
+mod ableton {
+    use rusty_link::{AblLink, SessionState};
+
+    pub struct Link {
+        link:  AblLink,
+        state: SessionState,
+    }
+
+    impl Link {
+        /// Join the session, proposing `bpm` if nobody else is in it yet.
+        pub fn join(bpm: f32) -> Result<Self, String> {
+            let link = AblLink::new(bpm as f64);
+            link.enable(true);
+            Ok(Self { link, state: SessionState::new() })
+        }
+
+        /// The session tempo, and the beat it is on now on a `quantum`-beat bar.
+        pub fn capture(&mut self, quantum: f64) -> (f32, f64) {
+            self.link.capture_app_session_state(&mut self.state);
+            let now = self.link.clock_micros();
+            (self.state.tempo() as f32, self.state.beat_at_time(now, quantum))
+        }
+
+        /// Send `bpm` to every peer.
+        /// Seconds from now until `beat` on the last captured timeline; negative if past.
+        pub fn secs_until(&self, beat: f64, quantum: f64) -> f64 {
+            (self.state.time_at_beat(beat, quantum) - self.link.clock_micros()) as f64 / 1e6
+        }
+
+        pub fn set_tempo(&mut self, bpm: f32) {
+            self.link.capture_app_session_state(&mut self.state);
+            self.state.set_tempo(bpm as f64, self.link.clock_micros());
+            self.link.commit_app_session_state(&self.state);
+        }
+
+        pub fn peers(&self) -> u64 { self.link.num_peers() }
+    }
+
+    impl Drop for Link {
+        fn drop(&mut self) { self.link.enable(false); }
+    }
+}
+
+#[cfg(not(feature = "link"))]
+mod unsupported {
+    pub struct Link;
+
+    impl Link {
+        pub fn join(_bpm: f32) -> Result<Self, String> {
+            Err(crate::i18n::tr("This build has no Ableton Link support (rebuild with --features link)").to_string())
+        }
+        pub fn capture(&mut self, _quantum: f64) -> (f32, f64) { (0.0, 0.0) }
+        pub fn secs_until(&self, _beat: f64, _quantum: f64) -> f64 { 0.0 }
+        pub fn set_tempo(&mut self, _bpm: f32) {}
+        pub fn peers(&self) -> u64 { 0 }
+    }
+}
+
+/// The joined session, with what we last agreed with it.
+pub struct LinkSync {
+    link:      Link,
+    /// The BPM both sides had last frame; the side that moved away from it changed tempo.
+    agreed:    f32,
+    /// Session step (beats × steps per beat) last scheduled, while the sequencer runs.
+    last_step: Option<i64>,
+}
+
+impl AppState {
+    /// Join or leave the Link session.
+    pub fn set_link_enabled(&self, on: bool) {
+        if !on {
+            *self.link.lock() = None;
+            *self.status.write() = t!("Link off").to_string();
+            return;
+        }
+        let bpm = self.events.bpm();
+        match Link::join(bpm) {
+            Ok(link) => {
+                *self.link.lock() = Some(LinkSync { link, agreed: bpm, last_step: None });
+                *self.status.write() = t!("✓ Link on").to_string();
+            }
+            Err(e) => *self.status.write() = format!("✗ {}", e),
+        }
+    }
+
+    /// Other apps in the session, when Link is on.
+    pub fn link_peers(&self) -> Option<u64> {
+        self.link.lock().as_ref().map(|s| s.link.peers())
+    }
+
+    /// The sequencer steps on Link's beat rather than its own timer. MIDI clock sync wins.
+    pub(crate) fn link_steps(&self) -> bool {
+        self.link.lock().is_some() && !self.clock_slaved()
+    }
+
+    /// Called every frame: swap tempo changes with the session and schedule the steps its
+    /// beat reaches within the lead.
+    pub(crate) fn tick_link(&self) {
+        let slaved = self.clock_slaved();
+        let mut guard = self.link.lock();
+        let Some(sync) = guard.as_mut() else { return; };
+        let (tempo, beat) = sync.link.capture(QUANTUM);
+        let local = self.events.bpm();
+        if (local - sync.agreed).abs() > BPM_EPSILON {
+            sync.link.set_tempo(local);
+            sync.agreed = local;
+        } else if (tempo - local).abs() > BPM_EPSILON {
+            self.send(AppCommand::SetBpm { bpm: tempo });
+            sync.agreed = self.events.bpm();
+        }
+
+        if slaved || !self.events.playing() { sync.last_step = None; return; }
+        let per_beat = self.seq_resolution.read().per_beat() as f64;
+        let horizon  = ((beat + LINK_LEAD_SECS * tempo as f64 / 60.0) * per_beat).floor() as i64;
+        // A fresh run starts on the next boundary; a stall skips ahead rather than bursting
+        let first = match sync.last_step {
+            Some(last) if last < horizon && horizon - last <= 2 => last + 1,
+            Some(last) if last >= horizon => return,
+            _ => (beat * per_beat).ceil() as i64,
+        };
+        let due: Vec<(i64, f64)> = (first..=horizon)
+            .map(|s| (s, sync.link.secs_until(s as f64 / per_beat, QUANTUM).max(0.0)))
+            .collect();
+        sync.last_step = Some(horizon);
+        drop(guard);
+        let now = Instant::now();
+        for (step, delay) in due {
+            // Play the step the session's phase is on, so the bar lines up with the peers'
+            *self.seq_current_step.write() = step.rem_euclid(self.pattern_len() as i64) as usize;
+            *self.seq_last_step_time.write() = Some(now + Duration::from_secs_f64(delay));
+            self.advance_step(delay);
+        }
+    }
+}
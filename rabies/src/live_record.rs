@@ -0,0 +1,77 @@
+// src/live_record.rs
+// Live recording into the step grid. With Record on and the sequencer running, every
+// pad hit (on screen, from the keyboard, MIDI or a grid controller) is also written to
+// the row that pad plays: drum track N's own row, or chop N of the focused track when
+// that track is chopped. A hit snaps to the nearest step of the row's lane, or with
+// micro-timing goes on the step it follows and keeps how far past it came as a nudge.
+// Main rows keep the hit's velocity. Everything recorded between Record going on and
+// the sequencer stopping is one take, and one undo.
+
+use std::sync::atomic::Ordering;
+use crate::grid_controller::Lane;
+use crate::gui::AppState;
+use crate::step_nudge;
+use crate::step_pitch;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordTiming {
+    /// Onto the closest step.
+    Nearest,
+    /// Onto the step before, nudged late to where the hit came.
+    Micro,
+}
+
+impl RecordTiming {
+    pub const ALL: [RecordTiming; 2] = [RecordTiming::Nearest, RecordTiming::Micro];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RecordTiming::Nearest => t!("Nearest step"),
+            RecordTiming::Micro   => t!("Micro-timing"),
+        }
+    }
+}
+
+impl AppState {
+    pub fn set_seq_record(&self, on: bool) {
+        self.seq_record.store(on, Ordering::Relaxed);
+        self.record_take.store(false, Ordering::Relaxed);
+        *self.status.write() = if on {
+            t!("⏺ Recording pad hits into the pattern ({})", self.record_timing.read().label())
+        } else {
+            t!("Pad recording off").to_string()
+        };
+    }
+
+    /// Write a pad hit into `lane` at the sequencer's position, if recording.
+    pub(crate) fn record_pad_hit(&self, lane: Lane, velocity: f32) {
+        if !self.seq_record.load(Ordering::Relaxed) { return; }
+        let Some(pos) = self.seq_step_position() else { return; };
+        let (track_idx, chop) = match lane {
+            Lane::Main(t)    => (t, None),
+            Lane::Chop(t, c) => (t, Some(c)),
+        };
+        let Some(uuid) = self.drum_tracks.read().get(track_idx).map(|t| t.sample_uuid) else { return; };
+        let n_chops = self.samples_manager.get_marks_for_sample(&uuid).len();
+        // A chopped track plays its chop rows, not its own
+        if chop.is_none() && n_chops > 0 {
+            *self.status.write() = t!("✗ Track {} is chopped — focus it to record its chops", track_idx + 1);
+            return;
+        }
+
+        let (lane_n, _) = self.lane_shape(lane);
+        let lane_pos = pos * lane_n as f64 / self.pattern_len() as f64;
+        let (step, nudge) = match *self.record_timing.read() {
+            RecordTiming::Nearest => (lane_pos.round() as usize % lane_n, 0.0),
+            RecordTiming::Micro   => (lane_pos.floor() as usize % lane_n, lane_pos.fract() as f32),
+        };
+
+        if !self.record_take.swap(true, Ordering::Relaxed) { self.push_undo(t!("live take")); }
+        if let Some(t) = self.drum_tracks.write().get_mut(track_idx) {
+            t.ensure_chop_steps(n_chops);
+            step_pitch::set(&mut t.step_pitches, chop, step, 0);
+            step_nudge::set(&mut t.step_nudges, chop, step, nudge);
+        }
+        self.set_lane_step(lane, step, Some(velocity));
+    }
+}
@@ -0,0 +1,105 @@
+// src/looper.rs
+// Stutter looper on the master output: keeps the last bar of audio, and while held
+// repeats the last 1/4, 1/2 or 1 bar of it. Live audio keeps running underneath, so
+// letting go drops straight back in time.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use atomic_float::AtomicF32;
+
+/// Loop lengths offered, in bars.
+pub const STUTTER_LENGTHS: [f32; 3] = [0.25, 0.5, 1.0];
+/// Slowest tempo the history buffer has room for a whole bar at.
+const MIN_BPM: f32 = 40.0;
+const FADE_SECS: f32 = 0.005;
+
+/// Shared between the UI and the mixer.
+#[derive(Clone)]
+pub struct StutterControls {
+    pub held: Arc<AtomicBool>,
+    /// Loop length in bars (one of `STUTTER_LENGTHS`).
+    pub bars: Arc<AtomicF32>,
+}
+
+impl Default for StutterControls {
+    fn default() -> Self {
+        Self {
+            held: Arc::new(AtomicBool::new(false)),
+            bars: Arc::new(AtomicF32::new(0.25)),
+        }
+    }
+}
+
+/// Mixer-side state. Buffers are sized up front so `process` never allocates.
+pub struct Stutter {
+    controls: StutterControls,
+    bpm:      Arc<AtomicF32>,
+    channels: usize,
+    sample_rate: f32,
+    /// Ring of the most recent output frames.
+    history:  Vec<f32>,
+    write:    usize,
+    /// The captured loop and its length in frames.
+    slice:    Vec<f32>,
+    len:      usize,
+    pos:      usize,
+    active:   bool,
+    /// 0 = live, 1 = loop; ramps over `FADE_SECS` to avoid clicks.
+    mix:      f32,
+    fade:     f32,
+}
+
+impl Stutter {
+    pub fn new(controls: StutterControls, bpm: Arc<AtomicF32>, channels: usize, sample_rate: f32) -> Self {
+        let channels = channels.max(1);
+        let frames   = (4.0 * 60.0 / MIN_BPM * sample_rate.max(1.0)) as usize;
+        Self {
+            controls, bpm, channels, sample_rate,
+            history: vec![0.0; frames * channels],
+            write:   0,
+            slice:   vec![0.0; frames * channels],
+            len:     0,
+            pos:     0,
+            active:  false,
+            mix:     0.0,
+            fade:    1.0 / (FADE_SECS * sample_rate.max(1.0)),
+        }
+    }
+
+    /// Record `data` and, while held, replace it with the loop.
+    pub fn process(&mut self, data: &mut [f32]) {
+        let held = self.controls.held.load(Ordering::Relaxed);
+        if held && !self.active { self.capture(); }
+        self.active = held;
+
+        let cap = self.history.len() / self.channels;
+        for frame in data.chunks_mut(self.channels) {
+            let w = self.write * self.channels;
+            self.history[w..w + frame.len()].copy_from_slice(frame);
+            self.write = (self.write + 1) % cap;
+
+            let target = if self.active { 1.0 } else { 0.0 };
+            self.mix = if self.mix < target { (self.mix + self.fade).min(1.0) } else { (self.mix - self.fade).max(0.0) };
+            if self.mix <= 0.0 || self.len == 0 { continue; }
+            let r = self.pos * self.channels;
+            for (c, s) in frame.iter_mut().enumerate() {
+                *s += (self.slice[r + c] - *s) * self.mix;
+            }
+            self.pos = (self.pos + 1) % self.len;
+        }
+    }
+
+    /// Copy the last loop-length of history into the slice.
+    fn capture(&mut self) {
+        let bpm  = self.bpm.load(Ordering::Relaxed).max(MIN_BPM);
+        let bars = self.controls.bars.load(Ordering::Relaxed).clamp(0.0, 1.0);
+        let cap  = self.history.len() / self.channels;
+        self.len = ((bars * 4.0 * 60.0 / bpm * self.sample_rate) as usize).clamp(1, cap);
+        let start = (self.write + cap - self.len) % cap;
+        for i in 0..self.len {
+            let (src, dst) = (((start + i) % cap) * self.channels, i * self.channels);
+            self.slice[dst..dst + self.channels].copy_from_slice(&self.history[src..src + self.channels]);
+        }
+        self.pos = 0;
+    }
+}
@@ -0,0 +1,92 @@
+// src/loudness.rs
+// Perceived loudness (ITU-R BS.1770 K-weighting) for gain-matching one-shots.
+
+/// Default match target, LUFS short-term.
+pub const DEFAULT_TARGET_LUFS: f32 = -14.0;
+
+const SHORT_TERM_SECS: f64 = 3.0;
+const HOP_SECS:        f64 = 0.1;
+const SILENCE_LUFS:    f64 = -70.0;
+
+struct Biquad { b: [f64; 3], a: [f64; 2], z: [f64; 2] }
+
+impl Biquad {
+    fn new(b: [f64; 3], a: [f64; 3]) -> Self {
+        Self { b: b.map(|v| v / a[0]), a: [a[1] / a[0], a[2] / a[0]], z: [0.0; 2] }
+    }
+
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b[0] * x + self.z[0];
+        self.z[0] = self.b[1] * x - self.a[0] * y + self.z[1];
+        self.z[1] = self.b[2] * x - self.a[1] * y;
+        y
+    }
+}
+
+/// The two BS.1770 pre-filters (high shelf + RLB high-pass) at `sample_rate`.
+fn k_weighting(sample_rate: f64) -> [Biquad; 2] {
+    let (g, fc, q) = (4.0, 1_681.974_450_955_533, 0.707_175_236_955_419_6);
+    let a  = 10f64.powf(g / 40.0);
+    let w0 = std::f64::consts::TAU * fc / sample_rate;
+    let (cs, al) = (w0.cos(), w0.sin() / (2.0 * q));
+    let shelf = Biquad::new(
+        [
+            a * ((a + 1.0) + (a - 1.0) * cs + 2.0 * a.sqrt() * al),
+            -2.0 * a * ((a - 1.0) + (a + 1.0) * cs),
+            a * ((a + 1.0) + (a - 1.0) * cs - 2.0 * a.sqrt() * al),
+        ],
+        [
+            (a + 1.0) - (a - 1.0) * cs + 2.0 * a.sqrt() * al,
+            2.0 * ((a - 1.0) - (a + 1.0) * cs),
+            (a + 1.0) - (a - 1.0) * cs - 2.0 * a.sqrt() * al,
+        ],
+    );
+
+    let (fc, q) = (38.135_470_876_024_44, 0.500_327_037_323_877_3);
+    let w0 = std::f64::consts::TAU * fc / sample_rate;
+    let (cs, al) = (w0.cos(), w0.sin() / (2.0 * q));
+    let highpass = Biquad::new(
+        [(1.0 + cs) / 2.0, -(1.0 + cs), (1.0 + cs) / 2.0],
+        [1.0 + al, -2.0 * cs, 1.0 - al],
+    );
+    [shelf, highpass]
+}
+
+/// Loudest 3 s short-term loudness of interleaved `pcm`, in LUFS. Samples
+/// shorter than the window are measured whole. `None` for silence.
+pub fn short_term_lufs(pcm: &[f32], channels: usize, sample_rate: u32) -> Option<f32> {
+    let channels = channels.max(1);
+    let frames   = pcm.len() / channels;
+    if frames == 0 || sample_rate == 0 { return None; }
+
+    // Running sums of K-weighted energy per channel
+    let mut energy = vec![vec![0.0f64; frames + 1]; channels];
+    for (ch, sums) in energy.iter_mut().enumerate() {
+        let [mut f1, mut f2] = k_weighting(sample_rate as f64);
+        for i in 0..frames {
+            let y = f2.process(f1.process(pcm[i * channels + ch] as f64));
+            sums[i + 1] = sums[i] + y * y;
+        }
+    }
+
+    let window = ((SHORT_TERM_SECS * sample_rate as f64) as usize).clamp(1, frames);
+    let hop    = ((HOP_SECS * sample_rate as f64) as usize).max(1);
+    let mut loudest = f64::MIN;
+    let mut start   = 0;
+    loop {
+        let end = start + window;
+        let power: f64 = energy.iter().map(|s| (s[end] - s[start]) / window as f64).sum();
+        if power > 0.0 { loudest = loudest.max(-0.691 + 10.0 * power.log10()); }
+        if end >= frames { break; }
+        start = (start + hop).min(frames - window);
+    }
+    (loudest > SILENCE_LUFS).then_some(loudest as f32)
+}
+
+/// Linear gain that brings `pcm` to `target` LUFS without pushing its peak past 0 dBFS.
+pub fn match_gain(pcm: &[f32], channels: usize, sample_rate: u32, target: f32) -> Option<f32> {
+    let lufs = short_term_lufs(pcm, channels, sample_rate)?;
+    let peak = pcm.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+    let gain = 10f32.powf((target - lufs) / 20.0);
+    Some(if peak > 0.0 { gain.min(1.0 / peak) } else { gain })
+}
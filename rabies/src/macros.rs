@@ -0,0 +1,107 @@
+// src/macros.rs
+
+pub const NUM_MACROS: usize = 4;
+
+/// A parameter a macro can drive. `Track*` destinations act on the focused drum track.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MacroDest {
+    MasterVolume,
+    MasterFilter,
+    Bpm,
+    Crossfader,
+    TrackAttack,
+    TrackDecay,
+    TrackSustain,
+    TrackRelease,
+}
+
+impl MacroDest {
+    pub const ALL: [MacroDest; 8] = [
+        MacroDest::MasterVolume,
+        MacroDest::MasterFilter,
+        MacroDest::Bpm,
+        MacroDest::Crossfader,
+        MacroDest::TrackAttack,
+        MacroDest::TrackDecay,
+        MacroDest::TrackSustain,
+        MacroDest::TrackRelease,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MacroDest::MasterVolume => t!("Master Volume"),
+            MacroDest::MasterFilter => t!("Master Filter (Hz)"),
+            MacroDest::Bpm          => "BPM",
+            MacroDest::Crossfader   => t!("Crossfader (chops → drums)"),
+            MacroDest::TrackAttack  => t!("Track Attack"),
+            MacroDest::TrackDecay   => t!("Track Decay"),
+            MacroDest::TrackSustain => t!("Track Sustain"),
+            MacroDest::TrackRelease => t!("Track Release"),
+        }
+    }
+
+    /// Full range of the destination, in its own units.
+    pub fn range(&self) -> (f32, f32) {
+        match self {
+            MacroDest::MasterVolume => (0.0, 1.0),
+            MacroDest::MasterFilter => (20.0, 20000.0),
+            MacroDest::Bpm          => (40.0, 300.0),
+            MacroDest::Crossfader   => (0.0, 1.0),
+            MacroDest::TrackAttack  => (0.0, 2.0),
+            MacroDest::TrackDecay   => (0.0, 2.0),
+            MacroDest::TrackSustain => (0.0, 1.0),
+            MacroDest::TrackRelease => (0.0, 3.0),
+        }
+    }
+}
+
+/// One destination of a macro and the span the knob sweeps across it.
+#[derive(Clone, Copy, Debug)]
+pub struct MacroMapping {
+    pub dest: MacroDest,
+    /// Destination value at knob = 0.
+    pub min:  f32,
+    /// Destination value at knob = 1.
+    pub max:  f32,
+}
+
+impl MacroMapping {
+    pub fn new(dest: MacroDest) -> Self {
+        let (min, max) = dest.range();
+        Self { dest, min, max }
+    }
+
+    pub fn value_at(&self, knob: f32) -> f32 {
+        let (lo, hi) = self.dest.range();
+        let v = if self.dest == MacroDest::MasterFilter && self.min > 0.0 && self.max > 0.0 {
+            // Cutoff sweeps are exponential so the knob feels even
+            self.min * (self.max / self.min).powf(knob)
+        } else {
+            self.min + (self.max - self.min) * knob
+        };
+        v.clamp(lo, hi)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MacroKnob {
+    pub name:     String,
+    /// Knob position, 0-1.
+    pub value:    f32,
+    pub mappings: Vec<MacroMapping>,
+}
+
+impl MacroKnob {
+    pub fn new(idx: usize) -> Self {
+        Self { name: format!("Macro {}", idx + 1), value: 0.0, mappings: Vec::new() }
+    }
+}
+
+/// The default bank: macro 1 opens the master filter, the rest start unassigned.
+pub fn default_macros() -> Vec<MacroKnob> {
+    let mut macros: Vec<MacroKnob> = (0..NUM_MACROS).map(MacroKnob::new).collect();
+    macros[0].name  = "Filter".to_string();
+    macros[0].value = 1.0;
+    macros[0].mappings.push(MacroMapping::new(MacroDest::MasterFilter));
+    macros
+}
@@ -1,5 +1,7 @@
 // src/main.rs
 
+#[macro_use]
+mod i18n;
 mod audio;
 mod gui;
 mod adsr;
@@ -8,12 +10,100 @@ mod piano_roll;
 mod recording; 
 mod pattern;   
 mod playlist;
+mod macros;
+mod pads;
+mod mapping;
+mod project;
+mod loudness;
+mod channels;
+mod backend;
+mod devices;
+mod engine;
+mod limiter;
+mod looper;
+mod netsend;
+mod versions;
+mod render;
+mod server;
+mod trim;
+mod autochop;
+mod punch;
+mod grid_export;
+mod variation;
+mod fill;
+mod metronome;
+mod midi;
+mod midi_clock_out;
+mod footswitch;
+mod midi_learn;
+mod grid_controller;
+mod midi_file;
+mod pattern_tools;
+mod slicer;
+mod reload;
+mod tempo_span;
+mod kit_report;
+mod selection;
+mod mono;
+mod category;
+mod kit_swap;
+mod sync_start;
+mod share;
+mod step_pitch;
+mod tempo_track;
+mod pad_bank;
+mod safe_mode;
+mod step_nudge;
+mod live_record;
+mod bus;
+mod jobs;
+mod link;
+mod notes;
+mod scrub;
+mod prelisten;
+mod mix_snapshot;
+mod stems;
+mod transport_clock;
+mod row_timing;
+mod humanize;
+mod euclid;
+mod clipboard;
+mod resume;
+mod history;
+mod sidecar;
+#[cfg(test)]
+mod golden;
 
 use eframe::egui;
 
 fn main() -> Result<(), eframe::Error> {
+    // Headless: `--serve [addr]` runs the HTTP API instead of the window
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(i) = args.iter().position(|a| a == "--serve") {
+        let addr = args.get(i + 1).filter(|a| !a.starts_with("--")).map(String::as_str).unwrap_or(server::DEFAULT_ADDR);
+        if let Err(e) = server::run(addr, args.iter().any(|a| a == "--verbose")) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    i18n::set_language(i18n::Language::from_env());
+
     // ✅ AppState is in gui module
     let app = gui::AppState::default();
+
+    // Safe mode on request, or when the last session didn't get to close
+    let crashed = safe_mode::previous_run_crashed();
+    safe_mode::mark_running();
+    if args.iter().any(|a| a == "--safe-mode") {
+        app.enter_safe_mode("--safe-mode");
+    } else if crashed {
+        app.enter_safe_mode(t!("the last session didn't close cleanly"));
+    } else {
+        app.start_self_test();
+    }
+    app.load_resume_point();
     
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
@@ -23,9 +113,11 @@ fn main() -> Result<(), eframe::Error> {
         ..Default::default()
     };
 
-    eframe::run_native(
+    let result = eframe::run_native(
         "Audio Sampler",
         native_options,
         Box::new(|_cc| Box::new(app)),
-    )
+    );
+    safe_mode::mark_stopped();
+    result
 }
\ No newline at end of file
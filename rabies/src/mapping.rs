@@ -0,0 +1,132 @@
+// src/mapping.rs
+// Controller mapping profiles: which computer key and which MIDI note plays each pad
+// slot. Profiles are named and saved with the project, so a session opened on a QWERTZ
+// laptop or next to an MPD218 picks up the right map without redoing it.
+
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+/// Slots with a MIDI note (the largest pad grid, 4×8).
+pub const NOTE_SLOTS: usize = 32;
+/// Slots with a keyboard key (the first 16, row-major).
+pub const KEY_SLOTS: usize = 16;
+
+const QWERTY: [&str; KEY_SLOTS] = ["1", "2", "3", "4", "Q", "W", "E", "R", "A", "S", "D", "F", "Z", "X", "C", "V"];
+const QWERTZ: [&str; KEY_SLOTS] = ["1", "2", "3", "4", "Q", "W", "E", "R", "A", "S", "D", "F", "Y", "X", "C", "V"];
+
+/// One named map. Slots are row-major screen positions in a four-wide grid.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MappingProfile {
+    pub name:  String,
+    /// `egui::Key` name per slot; `None` leaves the slot off the keyboard.
+    pub keys:  Vec<Option<String>>,
+    /// MIDI note per slot.
+    pub notes: Vec<Option<u8>>,
+}
+
+impl MappingProfile {
+    fn new(name: &str, keys: &[&str], note: impl Fn(usize) -> Option<u8>) -> Self {
+        Self {
+            name:  name.to_string(),
+            keys:  keys.iter().map(|k| Some(k.to_string())).collect(),
+            notes: (0..NOTE_SLOTS).map(note).collect(),
+        }
+    }
+
+    /// Profiles every project starts with.
+    pub fn builtin() -> Vec<MappingProfile> {
+        // General MIDI drums from C1, top-left first
+        let chromatic = |slot: usize| Some(36 + slot as u8);
+        // Pads count up from the bottom-left on the hardware
+        let bottom_up = |slot: usize| {
+            let (row, col) = (slot / 4, slot % 4);
+            let rows = if slot < 16 { 4 } else { 8 };
+            Some(36 + ((rows - 1 - row) * 4 + col) as u8)
+        };
+        // Programmer mode: note = row·10 + column, row 1 at the bottom
+        let launchpad = |slot: usize| {
+            let (row, col) = (slot / 4, slot % 4);
+            let rows = if slot < 16 { 4 } else { 8 };
+            Some(((rows - row) * 10 + col + 1) as u8)
+        };
+        vec![
+            MappingProfile::new("Laptop QWERTY", &QWERTY, chromatic),
+            MappingProfile::new("Laptop QWERTZ", &QWERTZ, chromatic),
+            MappingProfile::new("MPD218", &QWERTY, bottom_up),
+            MappingProfile::new("Launchpad", &QWERTY, launchpad),
+        ]
+    }
+
+    pub fn key(&self, slot: usize) -> Option<egui::Key> {
+        self.keys.get(slot)?.as_deref().and_then(egui::Key::from_name)
+    }
+
+    /// Slot that `note` plays, if any.
+    pub fn slot_for_note(&self, note: u8) -> Option<usize> {
+        self.notes.iter().position(|&n| n == Some(note))
+    }
+
+    pub fn key_label(&self, slot: usize) -> Option<&'static str> {
+        self.key(slot).map(|k| k.symbol_or_name())
+    }
+
+    /// Bind `key` to `slot`, taking it off any other slot.
+    pub fn set_key(&mut self, slot: usize, key: egui::Key) {
+        if slot >= KEY_SLOTS { return; }
+        self.keys.resize(KEY_SLOTS, None);
+        for k in self.keys.iter_mut() {
+            if k.as_deref() == Some(key.name()) { *k = None; }
+        }
+        self.keys[slot] = Some(key.name().to_string());
+    }
+}
+
+/// Every profile of the project and the one in use.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MappingProfiles {
+    pub profiles: Vec<MappingProfile>,
+    pub active:   usize,
+}
+
+impl Default for MappingProfiles {
+    fn default() -> Self { Self { profiles: MappingProfile::builtin(), active: 0 } }
+}
+
+impl MappingProfiles {
+    pub fn active(&self) -> &MappingProfile {
+        &self.profiles[self.active.min(self.profiles.len() - 1)]
+    }
+
+    pub fn active_mut(&mut self) -> &mut MappingProfile {
+        let i = self.active.min(self.profiles.len() - 1);
+        &mut self.profiles[i]
+    }
+
+    /// Copy the active profile under a new name and switch to it.
+    pub fn duplicate_active(&mut self) {
+        let mut p = self.active().clone();
+        let base = p.name.clone();
+        let mut n = 2;
+        while self.profiles.iter().any(|q| q.name == p.name) {
+            p.name = format!("{} ({})", base, n);
+            n += 1;
+        }
+        self.profiles.push(p);
+        self.active = self.profiles.len() - 1;
+    }
+
+    /// Drop the active profile; the last one always stays.
+    pub fn remove_active(&mut self) {
+        if self.profiles.len() <= 1 { return; }
+        self.profiles.remove(self.active.min(self.profiles.len() - 1));
+        self.active = self.active.min(self.profiles.len() - 1);
+    }
+
+    /// Load from a project: an empty list falls back to the built-ins.
+    pub fn sanitized(mut self) -> Self {
+        if self.profiles.is_empty() { self.profiles = MappingProfile::builtin(); }
+        self.active = self.active.min(self.profiles.len() - 1);
+        self
+    }
+}
@@ -0,0 +1,260 @@
+// src/metronome.rs
+// Metronome: a click on every beat of its meter while the sequencer runs, louder and
+// higher on the downbeat (and on each group of three in 6/8, 9/8 and 12/8). Clicks go
+// straight to the output, never into bounces, and count from when play was pressed.
+// With count-in on, Play and a recording started from a stopped sequencer click one bar
+// first (whether or not the metronome is), and the pattern starts on the bar after it.
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use crate::adsr::{ADSREnvelope, Bus, Voice};
+use crate::gui::{AppState, StepResolution};
+use crate::variation::Dice;
+
+/// Built-in clicks are synthesised once at this rate.
+const CLICK_RATE: u32   = 48_000;
+const CLICK_SECS: f32   = 0.06;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ClickSound {
+    Beep,
+    Rim,
+    CrossStick,
+    /// The user's own sample (falls back to the beep until one is loaded).
+    Sample,
+}
+
+impl ClickSound {
+    pub const ALL: [ClickSound; 4] = [ClickSound::Beep, ClickSound::Rim, ClickSound::CrossStick, ClickSound::Sample];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ClickSound::Beep       => t!("Beep"),
+            ClickSound::Rim        => t!("Rim"),
+            ClickSound::CrossStick => t!("Cross-stick"),
+            ClickSound::Sample     => t!("Sample"),
+        }
+    }
+}
+
+/// Time signature the clicks count in.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Meter {
+    pub beats: usize,
+    /// 4 clicks quarter notes, 8 clicks eighths.
+    pub unit:  usize,
+}
+
+impl Meter {
+    pub const ALL: [Meter; 8] = [
+        Meter { beats: 2,  unit: 4 },
+        Meter { beats: 3,  unit: 4 },
+        Meter { beats: 4,  unit: 4 },
+        Meter { beats: 5,  unit: 4 },
+        Meter { beats: 6,  unit: 8 },
+        Meter { beats: 7,  unit: 8 },
+        Meter { beats: 9,  unit: 8 },
+        Meter { beats: 12, unit: 8 },
+    ];
+
+    pub fn label(&self) -> String { format!("{}/{}", self.beats, self.unit) }
+
+    /// Length of a bar in quarter notes (the BPM's beat).
+    pub fn quarters(&self) -> f32 { self.beats as f32 * 4.0 / self.unit.max(1) as f32 }
+
+    /// Compound meters feel their eighths in threes.
+    fn compound(&self) -> bool { self.unit == 8 && self.beats > 3 && self.beats.is_multiple_of(3) }
+
+    pub fn accent(&self, beat: usize) -> Accent {
+        if beat == 0 { Accent::Downbeat }
+        else if self.compound() && beat.is_multiple_of(3) { Accent::Group }
+        else { Accent::Beat }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Accent {
+    Downbeat,
+    /// Start of a dotted-quarter group in a compound meter.
+    Group,
+    Beat,
+}
+
+impl Accent {
+    /// (level, playback speed) of the click.
+    fn shape(&self) -> (f32, f32) {
+        match self {
+            Accent::Downbeat => (1.0, 1.5),
+            Accent::Group    => (0.8, 1.25),
+            Accent::Beat     => (0.6, 1.0),
+        }
+    }
+}
+
+/// What the project remembers about the metronome.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetronomeSettings {
+    pub enabled:     bool,
+    pub sound:       ClickSound,
+    pub meter:       Meter,
+    /// 0-1.
+    pub level:       f32,
+    pub sample_path: Option<String>,
+    /// Click a bar before the sequencer starts.
+    pub count_in:    bool,
+}
+
+impl Default for MetronomeSettings {
+    fn default() -> Self {
+        Self {
+            enabled:     false,
+            sound:       ClickSound::Beep,
+            meter:       Meter { beats: 4, unit: 4 },
+            level:       0.7,
+            sample_path: None,
+            count_in:    false,
+        }
+    }
+}
+
+pub struct Metronome {
+    pub settings: MetronomeSettings,
+    /// Beep, rim and cross-stick, mono at `CLICK_RATE`.
+    clicks:  [Arc<Vec<f32>>; 3],
+    /// Decoded user sample: PCM, channels, rate.
+    sample:  Option<(Arc<Vec<f32>>, usize, u32)>,
+    /// Steps since the sequencer started.
+    elapsed: usize,
+}
+
+impl Default for Metronome {
+    fn default() -> Self {
+        let mut dice = Dice::default();
+        Self {
+            settings: MetronomeSettings::default(),
+            clicks:   [
+                Arc::new(synth_click(&[(1000.0, 1.0)], 40.0, 0.0, &mut dice)),
+                Arc::new(synth_click(&[(1750.0, 0.5), (2900.0, 0.3)], 90.0, 0.5, &mut dice)),
+                Arc::new(synth_click(&[(520.0, 0.6), (1250.0, 0.3)], 55.0, 0.35, &mut dice)),
+            ],
+            sample:   None,
+            elapsed:  0,
+        }
+    }
+}
+
+/// Decaying partials plus a short noise burst for the stick.
+fn synth_click(partials: &[(f32, f32)], decay: f32, noise: f32, dice: &mut Dice) -> Vec<f32> {
+    let n = (CLICK_SECS * CLICK_RATE as f32) as usize;
+    (0..n).map(|i| {
+        let t = i as f32 / CLICK_RATE as f32;
+        let tone: f32 = partials.iter().map(|&(f, a)| a * (std::f32::consts::TAU * f * t).sin()).sum();
+        let burst = noise * (dice.roll() * 2.0 - 1.0) * (-t * 400.0).exp();
+        (tone * (-t * decay).exp() + burst) * 0.8
+    }).collect()
+}
+
+impl Metronome {
+    pub fn reset(&mut self) { self.elapsed = 0; }
+
+    pub fn has_sample(&self) -> bool { self.sample.is_some() }
+
+    pub fn set_sample(&mut self, path: String, pcm: Vec<f32>, channels: usize, rate: u32) {
+        self.sample = Some((Arc::new(pcm), channels, rate));
+        self.settings.sample_path = Some(path);
+    }
+
+    /// Clicks that fall inside the next step, delayed to their spot. Counts the step
+    /// even when off, so switching on mid-play stays in time with the start.
+    pub fn tick(&mut self, res: StepResolution, step_secs: f64) -> Vec<Voice> {
+        let n = self.elapsed;
+        self.elapsed += 1;
+        if !self.settings.enabled { return Vec::new(); }
+        let meter = self.settings.meter;
+        // A click every 4 · per_beat / unit steps: click k lands in step n when
+        // n · unit <= k · 4 · per_beat < (n + 1) · unit
+        let per_click = 4 * res.per_beat();
+        let first     = (n * meter.unit).div_ceil(per_click);
+        (first..)
+            .take_while(|k| k * per_click < (n + 1) * meter.unit)
+            .map(|k| {
+                let mut v = self.click_voice(meter.accent(k % meter.beats.max(1)));
+                v.delay = (k * per_click - n * meter.unit) as f64 / meter.unit as f64 * step_secs;
+                v
+            })
+            .collect()
+    }
+
+    /// One bar of clicks from now at `bpm`, and how long the bar lasts.
+    pub fn count_in(&self, bpm: f32) -> (Vec<Voice>, f64) {
+        let meter = self.settings.meter;
+        let click = 60.0 / bpm.max(1.0) as f64 * 4.0 / meter.unit.max(1) as f64;
+        let voices = (0..meter.beats).map(|k| {
+            let mut v = self.click_voice(meter.accent(k));
+            v.delay = k as f64 * click;
+            v
+        }).collect();
+        (voices, meter.beats as f64 * click)
+    }
+
+    fn click_voice(&self, accent: Accent) -> Voice {
+        let (level, speed) = accent.shape();
+        let (pcm, channels, rate) = match (self.settings.sound, &self.sample) {
+            (ClickSound::Sample, Some((pcm, ch, rate))) => (pcm.clone(), *ch, *rate),
+            (ClickSound::Rim, _)        => (self.clicks[1].clone(), 1, CLICK_RATE),
+            (ClickSound::CrossStick, _) => (self.clicks[2].clone(), 1, CLICK_RATE),
+            _                           => (self.clicks[0].clone(), 1, CLICK_RATE),
+        };
+        let mut v = Voice::new(pcm, channels, 0, speed, ADSREnvelope::default(), false, rate);
+        v.level = level * self.settings.level;
+        v.bus   = Bus::Thru;
+        v
+    }
+}
+
+impl AppState {
+    /// Start the sequencer the way Play does: after a bar of clicks when count-in is on.
+    /// Returns how long until the first step. Locked to MIDI clock there's no count-in:
+    /// the first step comes on the other device's next clock.
+    pub fn play_sequencer(&self) -> f64 {
+        if !self.metronome.lock().settings.count_in || self.clock_slaved() {
+            self.start_sequencer();
+            return 0.0;
+        }
+        let (voices, secs) = self.metronome.lock().count_in(self.song_bpm());
+        self.play_voices(voices);
+        *self.seq_start_at.write() = Some(Instant::now() + Duration::from_secs_f64(secs));
+        self.seq_counting_in.store(true, Ordering::Relaxed);
+        *self.status.write() = t!("🕭 Count-in — the pattern starts in {:.1} s", secs);
+        secs
+    }
+
+    /// Pick a file for the metronome's Sample sound.
+    pub fn pick_metronome_sample(&self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(t!("Audio"), &["mp3","wav","flac","ogg","m4a","aac"])
+            .pick_file()
+        else { return; };
+        if self.load_metronome_sample(&path.to_string_lossy()) {
+            self.metronome.lock().settings.sound = ClickSound::Sample;
+        }
+    }
+
+    /// Decode `path` as the click sample. Returns false (with a status) when it won't load.
+    pub fn load_metronome_sample(&self, path: &str) -> bool {
+        match self.audio_manager.load_audio(path) {
+            Ok(asset) => {
+                self.metronome.lock().set_sample(path.to_string(), asset.pcm.clone(), asset.channels as usize, asset.sample_rate);
+                *self.status.write() = t!("✓ Metronome click: {}", asset.file_name);
+                true
+            }
+            Err(e) => {
+                *self.status.write() = t!("✗ Metronome sample: {}", e);
+                false
+            }
+        }
+    }
+}
@@ -0,0 +1,329 @@
+// src/midi.rs
+// MIDI in and out through midir: the ALSA sequencer on Linux, CoreMIDI on macOS and
+// WinMM on Windows. Each connection is its own client with an input and an output
+// connection to the device; dropping the client closes them. Input arrives on midir's
+// thread and is queued for the UI loop, which wakes every frame anyway.
+//
+// The MIDI input (picked in Audio / MIDI settings) plays the pads through the active
+// mapping profile, with velocity, and can run the sequencer: Start/Continue/Stop,
+// tempo from MIDI clock, a CC that sweeps the BPM, and footswitch CCs (footswitch.rs).
+// Controls bound by MIDI learn (midi_learn.rs) get their messages first.
+//
+// With clock sync on, the sequencer is a clock slave: it stops timing its own steps and
+// plays one on every 24/(steps per beat) clocks from the connected device, the first
+// on the clock after Start, so it stays locked to a groovebox however its tempo drifts.
+// Each step is scheduled a fixed lead after its clock arrived, not when the UI frame
+// gets to it, so frame timing doesn't jitter the groove.
+// The BPM still follows the clock, for note lengths and triplet rows. The other way
+// round, midi_clock_out.rs sends our own clock to a device as the master.
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::{Duration, Instant};
+use crate::bus::AppCommand;
+use crate::gui::AppState;
+
+/// Tempo range of the BPM field, which a BPM CC sweeps.
+const BPM_MIN: f32 = 40.0;
+const BPM_MAX: f32 = 300.0;
+/// Clocks measured for each tempo reading (four beats at 24 per quarter), long enough
+/// that polling once a frame doesn't make it jitter.
+const CLOCK_WINDOW: usize = 96;
+/// MIDI clocks per quarter note.
+pub const CLOCKS_PER_BEAT: usize = 24;
+/// How long after its clock a synced step sounds. The clocks are stamped as they arrive,
+/// so the step keeps its place against them however late in a frame the UI reads them;
+/// this only has to cover that wait.
+const CLOCK_LEAD_SECS: f64 = 0.04;
+/// General-purpose controller 1, free on most gear.
+pub const DEFAULT_BPM_CC: u8 = 16;
+
+/// BPM for a CC value swept across the tempo range, in whole beats.
+pub fn cc_bpm(value: u8) -> f32 {
+    (BPM_MIN + (BPM_MAX - BPM_MIN) * value as f32 / 127.0).round()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MidiMessage {
+    NoteOn  { channel: u8, note: u8, velocity: u8 },
+    NoteOff { channel: u8, note: u8 },
+    Control { channel: u8, controller: u8, value: u8 },
+    Start,
+    Continue,
+    Stop,
+    /// Timing clock, 24 per quarter note.
+    Clock,
+}
+
+impl MidiMessage {
+    /// Channel (0-15) of a channel message; realtime messages have none.
+    pub fn channel(&self) -> Option<u8> {
+        match *self {
+            MidiMessage::NoteOn { channel, .. }
+            | MidiMessage::NoteOff { channel, .. }
+            | MidiMessage::Control { channel, .. } => Some(channel),
+            _ => None,
+        }
+    }
+
+    /// Decode one complete message as midir delivers it. Running status and realtime
+    /// bytes interleaved in a message are already untangled by then.
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        let (&status, data) = bytes.split_first()?;
+        let channel = status & 0x0F;
+        let byte = |i: usize| data.get(i).map(|&b| b & 0x7F);
+        match status {
+            0xF8 => Some(MidiMessage::Clock),
+            0xFA => Some(MidiMessage::Start),
+            0xFB => Some(MidiMessage::Continue),
+            0xFC => Some(MidiMessage::Stop),
+            0x90..=0x9F => {
+                let (note, velocity) = (byte(0)?, byte(1)?);
+                // A zero-velocity note-on is how running status sends note-off
+                Some(if velocity > 0 { MidiMessage::NoteOn { channel, note, velocity } } else { MidiMessage::NoteOff { channel, note } })
+            }
+            0x80..=0x8F => Some(MidiMessage::NoteOff { channel, note: byte(0)? }),
+            0xB0..=0xBF => Some(MidiMessage::Control { channel, controller: byte(0)?, value: byte(1)? }),
+            _ => None,
+        }
+    }
+
+    /// The wire bytes, into `buf`; returns how many were written.
+    pub fn encode(&self, buf: &mut [u8; 3]) -> usize {
+        let mut put = |bytes: &[u8]| { buf[..bytes.len()].copy_from_slice(bytes); bytes.len() };
+        match *self {
+            MidiMessage::NoteOn { channel, note, velocity }    => put(&[0x90 | channel & 0x0F, note & 0x7F, velocity & 0x7F]),
+            MidiMessage::NoteOff { channel, note }             => put(&[0x80 | channel & 0x0F, note & 0x7F, 0]),
+            MidiMessage::Control { channel, controller, value } => put(&[0xB0 | channel & 0x0F, controller & 0x7F, value & 0x7F]),
+            MidiMessage::Start    => put(&[0xFA]),
+            MidiMessage::Continue => put(&[0xFB]),
+            MidiMessage::Stop     => put(&[0xFC]),
+            MidiMessage::Clock    => put(&[0xF8]),
+        }
+    }
+}
+
+/// A device port, as the platform's MIDI API names it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MidiPort {
+    pub name:   String,
+    /// Sends to us (a controller's keys and pads).
+    pub input:  bool,
+    /// Takes what we send (LEDs, a synth).
+    pub output: bool,
+}
+
+fn err(e: impl std::fmt::Display) -> String { t!("MIDI: {}", e) }
+
+/// Every port the devices offer, inputs and outputs of the same name merged.
+pub fn list_ports() -> Result<Vec<MidiPort>, String> {
+    let mut ports: Vec<MidiPort> = Vec::new();
+    let input = midir::MidiInput::new("Rabies scan").map_err(err)?;
+    for p in input.ports() {
+        let Ok(name) = input.port_name(&p) else { continue; };
+        ports.push(MidiPort { name, input: true, output: false });
+    }
+    let output = midir::MidiOutput::new("Rabies scan").map_err(err)?;
+    for p in output.ports() {
+        let Ok(name) = output.port_name(&p) else { continue; };
+        match ports.iter_mut().find(|q| q.name == name) {
+            Some(q) => q.output = true,
+            None    => ports.push(MidiPort { name, input: false, output: true }),
+        }
+    }
+    Ok(ports)
+}
+
+/// Our end of one device: an input connection feeding a queue, and an output connection.
+/// Dropping it closes both.
+pub struct MidiClient {
+    name:   String,
+    input:  Option<midir::MidiInputConnection<()>>,
+    output: std::sync::Mutex<Option<midir::MidiOutputConnection>>,
+    tx:     Sender<(Instant, MidiMessage)>,
+    rx:     Receiver<(Instant, MidiMessage)>,
+}
+
+impl MidiClient {
+    pub fn open(name: &str) -> Result<Self, String> {
+        let (tx, rx) = mpsc::channel();
+        Ok(Self { name: name.to_string(), input: None, output: Default::default(), tx, rx })
+    }
+
+    /// Connect to `port` in whichever directions it supports.
+    pub fn connect(&mut self, port: &MidiPort) -> Result<(), String> {
+        if port.input {
+            let mut midi = midir::MidiInput::new(&self.name).map_err(err)?;
+            midi.ignore(midir::Ignore::SysexAndActiveSense);
+            let found = midi.ports().into_iter().find(|p| midi.port_name(p).is_ok_and(|n| n == port.name));
+            let found = found.ok_or_else(|| t!("MIDI: {} is gone", port.name))?;
+            let tx = self.tx.clone();
+            // Stamped on arrival, on midir's thread rather than when the UI gets round to it
+            let conn = midi.connect(&found, "in", move |_, bytes, _| {
+                if let Some(msg) = MidiMessage::parse(bytes) { let _ = tx.send((Instant::now(), msg)); }
+            }, ()).map_err(err)?;
+            self.input = Some(conn);
+        }
+        if port.output {
+            let midi  = midir::MidiOutput::new(&self.name).map_err(err)?;
+            let found = midi.ports().into_iter().find(|p| midi.port_name(p).is_ok_and(|n| n == port.name));
+            let found = found.ok_or_else(|| t!("MIDI: {} is gone", port.name))?;
+            let conn  = midi.connect(&found, "out").map_err(err)?;
+            *self.output.lock().unwrap() = Some(conn);
+        }
+        Ok(())
+    }
+
+    /// Everything that has arrived since the last call.
+    pub fn poll(&self) -> Vec<MidiMessage> {
+        self.poll_timed().into_iter().map(|(_, msg)| msg).collect()
+    }
+
+    /// As `poll`, with when each message arrived.
+    pub fn poll_timed(&self) -> Vec<(Instant, MidiMessage)> {
+        self.rx.try_iter().collect()
+    }
+
+    /// Send a message to the output, if there is one.
+    pub fn send(&self, msg: MidiMessage) {
+        let mut buf = [0u8; 3];
+        let n = msg.encode(&mut buf);
+        if let Some(conn) = self.output.lock().unwrap().as_mut() { let _ = conn.send(&buf[..n]); }
+    }
+}
+
+/// How the MIDI input is read.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MidiInputSettings {
+    /// Notes and CCs on this channel only (0-15); `None` takes them all.
+    pub channel:      Option<u8>,
+    /// Start, Continue and Stop run the sequencer.
+    pub transport:    bool,
+    /// Take the tempo from MIDI clock.
+    pub follow_clock: bool,
+    /// Step the sequencer on MIDI clock instead of its own timer.
+    pub clock_sync:   bool,
+    /// CC whose value sweeps the BPM from 40 to 300.
+    pub bpm_cc:       Option<u8>,
+}
+
+impl Default for MidiInputSettings {
+    fn default() -> Self {
+        Self { channel: None, transport: true, follow_clock: false, clock_sync: false, bpm_cc: None }
+    }
+}
+
+/// The connected MIDI input.
+pub struct MidiInput {
+    pub port: MidiPort,
+    client:   MidiClient,
+    /// When the current clock window started, and clocks counted in it.
+    window:   Option<(Instant, usize)>,
+    /// Clocks since the last synced step.
+    clock_phase: usize,
+}
+
+impl AppState {
+    /// Scan for MIDI ports, for the input picker and the grid menu.
+    pub fn refresh_midi_ports(&self) {
+        match list_ports() {
+            Ok(ports) => *self.midi_ports.write() = ports,
+            Err(e)    => *self.status.write() = format!("✗ {}", e),
+        }
+    }
+
+    /// Listen to `port`, or to nothing for `None`.
+    pub fn connect_midi_input(&self, port: Option<MidiPort>) {
+        *self.midi_input.lock() = None;
+        let Some(port) = port else { return; };
+        match MidiClient::open("Rabies").and_then(|mut c| c.connect(&port).map(|_| c)) {
+            Ok(client) => {
+                *self.status.write() = t!("✓ MIDI input: {}", port.name);
+                *self.midi_input.lock() = Some(MidiInput { port, client, window: None, clock_phase: 0 });
+            }
+            Err(e) => *self.status.write() = format!("✗ {}", e),
+        }
+    }
+
+    /// Called every frame: play the pads and follow the transport.
+    pub(crate) fn tick_midi_input(&self) {
+        let messages = match self.midi_input.lock().as_ref() {
+            Some(input) => input.client.poll_timed(),
+            None        => return,
+        };
+        let settings = self.midi_settings.read().clone();
+        for (at, msg) in messages {
+            if msg.channel().is_some_and(|ch| settings.channel.is_some_and(|want| want != ch)) { continue; }
+            if self.midi_learn_message(msg) { continue; }
+            match msg {
+                MidiMessage::NoteOn { note, velocity, .. } => {
+                    let Some(slot) = self.mapping.read().active().slot_for_note(note) else { continue; };
+                    let pad = self.pad_layout.read().pad_at(slot);
+                    if let Some(pad) = pad { self.trigger_pad_velocity(pad, velocity as f32 / 127.0); }
+                }
+                MidiMessage::Control { controller, value, .. } if settings.bpm_cc == Some(controller) => {
+                    self.send(AppCommand::SetBpm { bpm: cc_bpm(value) });
+                }
+                MidiMessage::Control { controller, value, .. } => self.footswitch(controller, value),
+                MidiMessage::Start if settings.transport => {
+                    if let Some(input) = self.midi_input.lock().as_mut() { input.window = None; input.clock_phase = 0; }
+                    self.send(AppCommand::Start);
+                }
+                MidiMessage::Continue if settings.transport => {
+                    if let Some(input) = self.midi_input.lock().as_mut() { input.clock_phase = 0; }
+                    self.send(AppCommand::Start);
+                }
+                MidiMessage::Stop if settings.transport => self.send(AppCommand::Stop),
+                MidiMessage::Clock if settings.clock_sync => {
+                    self.midi_clock(at);
+                    self.midi_clock_step(at);
+                }
+                MidiMessage::Clock if settings.follow_clock => self.midi_clock(at),
+                _ => {}
+            }
+        }
+    }
+
+    /// The sequencer steps on MIDI clock rather than its own timer.
+    pub(crate) fn clock_slaved(&self) -> bool {
+        self.midi_settings.read().clock_sync && self.midi_input.lock().is_some()
+    }
+
+    /// With clock sync, play a step on every step's worth of clocks while running, the
+    /// clock having arrived `at`.
+    fn midi_clock_step(&self, at: Instant) {
+        let per_step = CLOCKS_PER_BEAT / self.seq_resolution.read().per_beat();
+        let due = {
+            let mut guard = self.midi_input.lock();
+            let Some(input) = guard.as_mut() else { return; };
+            // Stopped, the next start lines up with the next clock
+            if !self.events.playing() { input.clock_phase = 0; return; }
+            let due = input.clock_phase % per_step == 0;
+            input.clock_phase = (input.clock_phase + 1) % per_step;
+            due
+        };
+        if due {
+            let sounds = at + Duration::from_secs_f64(CLOCK_LEAD_SECS);
+            let lead   = sounds.saturating_duration_since(Instant::now()).as_secs_f64();
+            *self.seq_last_step_time.write() = Some(sounds);
+            self.advance_step(lead);
+        }
+    }
+
+    /// Count a clock that arrived at `now`; each full window gives a tempo reading.
+    fn midi_clock(&self, now: Instant) {
+        let mut guard = self.midi_input.lock();
+        let Some(input) = guard.as_mut() else { return; };
+        match input.window {
+            Some((start, n)) if n + 1 >= CLOCK_WINDOW => {
+                let secs = now.duration_since(start).as_secs_f32();
+                if secs > 0.0 {
+                    let bpm = 60.0 * CLOCK_WINDOW as f32 / 24.0 / secs;
+                    self.send(AppCommand::SetBpm { bpm: (bpm * 10.0).round() / 10.0 });
+                }
+                input.window = Some((now, 0));
+            }
+            Some((start, n)) => input.window = Some((start, n + 1)),
+            None             => input.window = Some((now, 0)),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+// src/midi_clock_out.rs
+// MIDI clock as master. With a clock output picked in Audio / MIDI settings, a thread of
+// its own sends 24 clocks per quarter note at the sequencer's tempo, so drum machines
+// and synths set to external sync follow it, and Start and Stop as the sequencer starts
+// and stops. The clock runs while the sequencer is stopped too, as most gear expects,
+// and is re-phased on Start so the device's first beat lands with ours.
+//
+// The UI loop only hands the thread the current tempo (song tempo track included);
+// everything else comes to it on the event bus.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use atomic_float::AtomicF32;
+use crate::bus::EngineEvent;
+use crate::gui::AppState;
+use crate::midi::{MidiClient, MidiMessage, MidiPort, CLOCKS_PER_BEAT};
+
+/// Longest the thread sleeps between clocks, so Start and Stop go out promptly.
+const MAX_SLEEP: Duration = Duration::from_millis(2);
+/// Further behind than this (a stalled thread), the clock skips ahead rather than bursting.
+const MAX_LAG: Duration = Duration::from_millis(50);
+
+/// The running clock output. Dropping it stops the thread and closes the port.
+pub struct ClockOut {
+    pub port: MidiPort,
+    bpm:      Arc<AtomicF32>,
+    running:  Arc<AtomicBool>,
+}
+
+impl Drop for ClockOut {
+    fn drop(&mut self) { self.running.store(false, Ordering::Relaxed); }
+}
+
+impl AppState {
+    /// Send clock to `port`, or to nothing for `None`.
+    pub fn connect_clock_output(&self, port: Option<MidiPort>) {
+        *self.midi_clock_out.lock() = None;
+        let Some(port) = port else { return; };
+        // Only our output is subscribed; nothing is read back from the device
+        let out_only = MidiPort { input: false, ..port.clone() };
+        let client = match MidiClient::open("Rabies clock").and_then(|mut c| c.connect(&out_only).map(|_| c)) {
+            Ok(c)  => c,
+            Err(e) => { *self.status.write() = format!("✗ {}", e); return; }
+        };
+        let bpm     = Arc::new(AtomicF32::new(self.song_bpm()));
+        let running = Arc::new(AtomicBool::new(true));
+        let events  = self.events.subscribe();
+        if self.events.playing() { client.send(MidiMessage::Start); }
+        let (thread_bpm, thread_running) = (bpm.clone(), running.clone());
+        // A thread of its own, not a job: the clock runs for as long as the output is
+        // picked, and a job would hold one of the few worker slots the whole session and
+        // queue every load behind it. It also has to wake every couple of milliseconds.
+        std::thread::spawn(move || {
+            let mut next = Instant::now();
+            while thread_running.load(Ordering::Relaxed) {
+                for ev in events.try_iter() {
+                    match ev {
+                        EngineEvent::Started => { client.send(MidiMessage::Start); next = Instant::now(); }
+                        EngineEvent::Stopped => client.send(MidiMessage::Stop),
+                        _ => {}
+                    }
+                }
+                let now = Instant::now();
+                if now >= next {
+                    client.send(MidiMessage::Clock);
+                    let bpm = thread_bpm.load(Ordering::Relaxed).max(1.0);
+                    next += Duration::from_secs_f64(60.0 / (bpm as f64 * CLOCKS_PER_BEAT as f64));
+                    if now.duration_since(next.min(now)) > MAX_LAG { next = now; }
+                } else {
+                    std::thread::sleep((next - now).min(MAX_SLEEP));
+                }
+            }
+            client.send(MidiMessage::Stop);
+        });
+        *self.status.write() = t!("✓ MIDI clock out: {}", port.name);
+        *self.midi_clock_out.lock() = Some(ClockOut { port, bpm, running });
+    }
+
+    /// Called every frame: keep the clock at the tempo the sequencer is playing.
+    pub(crate) fn tick_midi_clock_out(&self) {
+        if let Some(out) = self.midi_clock_out.lock().as_ref() {
+            out.bpm.store(self.song_bpm(), Ordering::Relaxed);
+        }
+    }
+}
@@ -0,0 +1,331 @@
+// src/midi_file.rs
+// MIDI clip import: the note-ons of a Standard MIDI File (every track and channel) are
+// read, then the import dialog assigns each note number to a sequencer row. Mapped rows
+// are cleared and refilled, each hit quantised to the row's own steps (triplet rows
+// included); main rows keep the note velocities. Hits past the pattern end are left out.
+
+use std::path::Path;
+use crate::bus::EngineEvent;
+use crate::grid_controller::Lane;
+use crate::gui::AppState;
+use crate::piano_roll::semitone_to_name;
+
+/// One note-on.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClipNote {
+    pub tick:     u32,
+    pub note:     u8,
+    pub velocity: u8,
+}
+
+/// The parts of a MIDI file the import uses.
+#[derive(Clone, Debug, Default)]
+pub struct MidiClip {
+    pub ticks_per_beat: u16,
+    /// In time order.
+    pub notes:          Vec<ClipNote>,
+    /// First tempo in the file.
+    pub bpm:            Option<f32>,
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos:  usize,
+}
+
+impl<'a> Reader<'a> {
+    fn done(&self) -> bool { self.pos >= self.data.len() }
+
+    fn bytes(&mut self, n: usize) -> Result<&'a [u8], String> {
+        let s = self.data.get(self.pos..self.pos + n).ok_or(t!("Unexpected end of file"))?;
+        self.pos += n;
+        Ok(s)
+    }
+
+    fn byte(&mut self) -> Result<u8, String> { Ok(self.bytes(1)?[0]) }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let b = self.bytes(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    /// Variable-length quantity: seven bits a byte, high bit set on all but the last.
+    fn vlq(&mut self) -> Result<u32, String> {
+        let mut v = 0u32;
+        for _ in 0..4 {
+            let b = self.byte()?;
+            v = (v << 7) | (b & 0x7F) as u32;
+            if b & 0x80 == 0 { return Ok(v); }
+        }
+        Err(t!("Bad variable-length number").into())
+    }
+}
+
+impl MidiClip {
+    pub fn parse(data: &[u8]) -> Result<MidiClip, String> {
+        let mut r = Reader { data, pos: 0 };
+        if r.bytes(4)? != b"MThd" { return Err(t!("Not a MIDI file").into()); }
+        let len    = r.u32()? as usize;
+        let header = r.bytes(len)?;
+        if header.len() < 6 { return Err(t!("Short MIDI header").into()); }
+        let division = u16::from_be_bytes([header[4], header[5]]);
+        if division & 0x8000 != 0 { return Err(t!("SMPTE-timed MIDI files aren't supported").into()); }
+
+        let mut clip = MidiClip { ticks_per_beat: division.max(1), ..Default::default() };
+        while !r.done() {
+            let id  = r.bytes(4)?;
+            let len = r.u32()? as usize;
+            let body = r.bytes(len)?;
+            if id == b"MTrk" { clip.read_track(body)?; }
+        }
+        clip.notes.sort_by_key(|n| n.tick);
+        Ok(clip)
+    }
+
+    fn read_track(&mut self, data: &[u8]) -> Result<(), String> {
+        let mut r = Reader { data, pos: 0 };
+        let mut tick = 0u32;
+        let mut running: Option<u8> = None;
+        while !r.done() {
+            tick = tick.saturating_add(r.vlq()?);
+            let mut status = r.byte()?;
+            if status < 0x80 {
+                // Running status: that was already the first data byte
+                status = running.ok_or(t!("MIDI data without a status byte"))?;
+                r.pos -= 1;
+            }
+            match status {
+                0xFF => {
+                    let kind = r.byte()?;
+                    let len  = r.vlq()? as usize;
+                    let body = r.bytes(len)?;
+                    if kind == 0x51 && len == 3 && self.bpm.is_none() {
+                        let micros = u32::from_be_bytes([0, body[0], body[1], body[2]]);
+                        if micros > 0 { self.bpm = Some(60_000_000.0 / micros as f32); }
+                    }
+                    if kind == 0x2F { break; }
+                }
+                0xF0 | 0xF7 => {
+                    let len = r.vlq()? as usize;
+                    r.bytes(len)?;
+                    running = None;
+                }
+                _ => {
+                    running = Some(status);
+                    let first = r.byte()?;
+                    match status & 0xF0 {
+                        0xC0 | 0xD0 => {}
+                        0x90 => {
+                            let velocity = r.byte()?;
+                            if velocity > 0 { self.notes.push(ClipNote { tick, note: first, velocity }); }
+                        }
+                        _ => { r.byte()?; }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Each note number used, lowest first, with how often it plays.
+    pub fn note_counts(&self) -> Vec<(u8, usize)> {
+        let mut counts = [0usize; 128];
+        for n in &self.notes { counts[(n.note & 0x7F) as usize] += 1; }
+        (0..128u8).map(|n| (n, counts[n as usize])).filter(|&(_, c)| c > 0).collect()
+    }
+}
+
+pub fn note_name(note: u8) -> String { semitone_to_name(note as i32 - 60) }
+
+/// Where one note number of the clip goes.
+pub struct NoteMapping {
+    pub note: u8,
+    pub hits: usize,
+    /// `None` leaves the note out.
+    pub lane: Option<Lane>,
+}
+
+/// A parsed clip waiting in the import dialog.
+pub struct MidiImportDraft {
+    pub file_name: String,
+    pub clip:      MidiClip,
+    pub map:       Vec<NoteMapping>,
+    /// Set the BPM from the file's tempo as well.
+    pub use_tempo: bool,
+}
+
+impl AppState {
+    /// Pick a .mid file and open the mapping dialog for it. Notes start mapped to the
+    /// drum tracks in order, lowest note on the first track.
+    pub fn open_midi_import(&self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(t!("MIDI"), &["mid", "midi"])
+            .set_title(t!("Import MIDI clip"))
+            .pick_file() else { return; };
+        let file_name = Path::new(&path).file_name().map(|f| f.to_string_lossy().to_string()).unwrap_or_default();
+        let clip = match std::fs::read(&path).map_err(|e| e.to_string()).and_then(|d| MidiClip::parse(&d)) {
+            Ok(c)  => c,
+            Err(e) => { *self.status.write() = format!("✗ {}: {}", file_name, e); return; }
+        };
+        if clip.notes.is_empty() {
+            *self.status.write() = t!("✗ {} has no notes", file_name);
+            return;
+        }
+        let mains: Vec<Lane> = self.grid_lanes().into_iter().filter(|l| matches!(l, Lane::Main(_))).collect();
+        let map = clip.note_counts().into_iter().enumerate()
+            .map(|(i, (note, hits))| NoteMapping { note, hits, lane: mains.get(i).copied() })
+            .collect();
+        *self.pending_midi_import.write() = Some(MidiImportDraft { file_name, clip, map, use_tempo: false });
+    }
+
+    /// Row name as the step sequencer shows it.
+    pub fn lane_label(&self, lane: Lane) -> String {
+        let tracks = self.drum_tracks.read();
+        match lane {
+            Lane::Main(t)    => format!("{} {}", t + 1, tracks.get(t).map_or("", |t| t.asset.file_name.as_str())),
+            Lane::Chop(t, c) => format!("{} {} chop {}", t + 1, tracks.get(t).map_or("", |t| t.asset.file_name.as_str()), c + 1),
+        }
+    }
+
+    pub fn commit_midi_import(&self) {
+        let Some(d) = self.pending_midi_import.write().take() else { return; };
+        let mut lanes: Vec<Lane> = Vec::new();
+        for lane in d.map.iter().filter_map(|m| m.lane) {
+            if !lanes.contains(&lane) { lanes.push(lane); }
+        }
+        for &lane in &lanes {
+            let (len, _) = self.lane_shape(lane);
+            for step in 0..len { self.set_lane_step(lane, step, None); }
+        }
+
+        // Loudest note wins where several land on one step
+        let tpb = d.clip.ticks_per_beat.max(1) as f32;
+        let mut hits: Vec<(Lane, usize, f32)> = Vec::new();
+        let mut dropped = 0;
+        for n in &d.clip.notes {
+            let Some(lane) = d.map.iter().find(|m| m.note == n.note).and_then(|m| m.lane) else { continue; };
+            let (len, per_beat) = self.lane_shape(lane);
+            let step = (n.tick as f32 * per_beat as f32 / tpb).round() as usize;
+            if step >= len { dropped += 1; continue; }
+            let v = n.velocity as f32 / 127.0;
+            match hits.iter_mut().find(|h| h.0 == lane && h.1 == step) {
+                Some(h) => h.2 = h.2.max(v),
+                None    => hits.push((lane, step, v)),
+            }
+        }
+        for &(lane, step, v) in &hits { self.set_lane_step(lane, step, Some(v)); }
+
+        if let (true, Some(bpm)) = (d.use_tempo, d.clip.bpm) {
+            self.events.emit(EngineEvent::Bpm { bpm: bpm.clamp(40.0, 300.0).round() });
+        }
+        *self.status.write() = t!("✓ {}: {} hits on {} row{}{}", d.file_name, hits.len(), lanes.len(),
+            if lanes.len() == 1 { "" } else { "s" },
+            if dropped > 0 { t!(", {} past the pattern end left out", dropped) } else { String::new() });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A format-1 file, 96 ticks to the beat, with one `MTrk` per body.
+    fn smf(tracks: &[&[u8]]) -> Vec<u8> {
+        let mut f = b"MThd".to_vec();
+        f.extend_from_slice(&6u32.to_be_bytes());
+        f.extend_from_slice(&[0, 1, 0, tracks.len() as u8, 0, 96]);
+        for t in tracks {
+            f.extend_from_slice(b"MTrk");
+            f.extend_from_slice(&(t.len() as u32).to_be_bytes());
+            f.extend_from_slice(t);
+        }
+        f
+    }
+
+    fn vlq(bytes: &[u8]) -> Result<u32, String> { Reader { data: bytes, pos: 0 }.vlq() }
+
+    #[test]
+    fn reads_variable_length_numbers() {
+        assert_eq!(vlq(&[0x00]), Ok(0));
+        assert_eq!(vlq(&[0x7F]), Ok(127));
+        assert_eq!(vlq(&[0x81, 0x00]), Ok(128));
+        assert_eq!(vlq(&[0xC0, 0x00]), Ok(0x2000));
+        assert_eq!(vlq(&[0xFF, 0xFF, 0xFF, 0x7F]), Ok(0x0FFF_FFFF));
+        // Five bytes is too long, and a number can't stop mid-way
+        assert!(vlq(&[0x80, 0x80, 0x80, 0x80, 0x00]).is_err());
+        assert!(vlq(&[0x81]).is_err());
+    }
+
+    #[test]
+    fn follows_running_status_across_tracks() {
+        let drums: &[u8] = &[
+            0x00, 0x99, 36, 100,   // kick on channel 10
+            0x60, 38, 90,          // running status: snare a beat later
+            0x00, 38, 0,           // running note-on at velocity 0 is a note-off
+            0x81, 0x40, 0x89, 36, 0,  // note-off after a two-byte delta (192 ticks)
+            0x00, 0xC9, 5,         // program change has one data byte
+            0x00, 0x99, 42, 64,
+            0x00, 0xFF, 0x2F, 0x00,
+        ];
+        let bass: &[u8] = &[0x30, 0x90, 40, 80, 0x00, 0xFF, 0x2F, 0x00];
+        let clip = MidiClip::parse(&smf(&[drums, bass])).unwrap();
+        assert_eq!(clip.ticks_per_beat, 96);
+        let notes: Vec<(u32, u8, u8)> = clip.notes.iter().map(|n| (n.tick, n.note, n.velocity)).collect();
+        assert_eq!(notes, vec![(0, 36, 100), (48, 40, 80), (96, 38, 90), (288, 42, 64)]);
+        assert_eq!(clip.note_counts(), vec![(36, 1), (38, 1), (40, 1), (42, 1)]);
+    }
+
+    #[test]
+    fn takes_the_first_tempo() {
+        let track: &[u8] = &[
+            0x00, 0xFF, 0x51, 0x03, 0x07, 0xA1, 0x20,  // 500 000 µs a beat: 120 BPM
+            0x00, 0xFF, 0x03, 0x04, b'b', b'e', b'a', b't',
+            0x60, 0xFF, 0x51, 0x03, 0x09, 0x27, 0xC0,  // 600 000 µs later on is ignored
+            0x00, 0x90, 60, 100,
+            0x00, 0xFF, 0x2F, 0x00,
+        ];
+        let clip = MidiClip::parse(&smf(&[track])).unwrap();
+        assert_eq!(clip.bpm, Some(120.0));
+        assert_eq!(clip.notes.len(), 1);
+        assert_eq!(MidiClip::parse(&smf(&[&[0x00, 0x90, 60, 100]])).unwrap().bpm, None);
+    }
+
+    #[test]
+    fn bad_input_is_an_error_not_a_panic() {
+        let track: &[u8] = &[
+            0x00, 0xFF, 0x51, 0x03, 0x07, 0xA1, 0x20,
+            0x00, 0xF0, 0x03, 0x43, 0x12, 0xF7,
+            0x00, 0x90, 36, 100, 0x60, 38, 90,
+            0x00, 0xFF, 0x2F, 0x00,
+        ];
+        let file = smf(&[track]);
+        assert!(MidiClip::parse(&file).is_ok());
+        // Cut anywhere short of the end (other than right after the header) it fails
+        for n in (0..file.len()).filter(|&n| n != 14) {
+            assert!(MidiClip::parse(&file[..n]).is_err(), "cut at {}", n);
+        }
+
+        assert!(MidiClip::parse(b"RIFF\x00\x00\x00\x00WAVE").is_err());
+        assert!(MidiClip::parse(&smf(&[&[0x00, 36, 100]])).is_err(), "data before any status byte");
+        let mut smpte = smf(&[]);
+        smpte[12] = 0xE7;
+        assert!(MidiClip::parse(&smpte).is_err());
+        let mut short = smf(&[]);
+        short[7] = 2;
+        assert!(MidiClip::parse(&short).is_err());
+        // Chunk lengths far past the end of the data
+        let mut huge = smf(&[track]);
+        huge[18..22].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(MidiClip::parse(&huge).is_err());
+
+        // Noise after a valid header parses or fails, but never panics
+        let mut seed = 0x2545_F491u32;
+        for _ in 0..2000 {
+            let mut body = Vec::new();
+            for _ in 0..(seed % 64) {
+                seed ^= seed << 13; seed ^= seed >> 17; seed ^= seed << 5;
+                body.push(seed as u8);
+            }
+            let _ = MidiClip::parse(&smf(&[&body]));
+        }
+    }
+}
@@ -0,0 +1,144 @@
+// src/midi_learn.rs
+// MIDI learn: right-click a pad, a track's mute, the BPM field or a step to arm it, and
+// the next note or CC from the MIDI input is bound to it (right-click again to cancel).
+// Bound messages are taken before the pad map and the footswitches see them, and the
+// bindings are saved with the project.
+//
+// Notes press on note-on. CCs press when they cross 64 upward, except on the BPM field,
+// where the CC sweeps 40-300 and a note taps the tempo.
+
+use serde::{Deserialize, Serialize};
+use crate::grid_controller::Lane;
+use crate::bus::AppCommand;
+use crate::gui::AppState;
+use crate::midi::{cc_bpm, MidiMessage};
+
+/// CC value from which a bound button counts as pressed.
+const PRESS: u8 = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum LearnTarget {
+    Pad(usize),
+    /// Mute of a drum track in the current pattern.
+    Mute(usize),
+    Bpm,
+    Step(Lane, usize),
+}
+
+impl LearnTarget {
+    pub fn label(&self) -> String {
+        match *self {
+            LearnTarget::Pad(p)                    => t!("Pad {}", p + 1),
+            LearnTarget::Mute(t)                   => t!("Track {} mute", t + 1),
+            LearnTarget::Bpm                       => t!("BPM").to_string(),
+            LearnTarget::Step(Lane::Main(t), s)    => t!("Track {} step {}", t + 1, s + 1),
+            LearnTarget::Step(Lane::Chop(t, c), s) => t!("Track {} chop {} step {}", t + 1, c + 1, s + 1),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MidiSource {
+    Note(u8),
+    Cc(u8),
+}
+
+impl MidiSource {
+    pub fn label(&self) -> String {
+        match self {
+            MidiSource::Note(n) => t!("Note {}", n),
+            MidiSource::Cc(c)   => format!("CC {}", c),
+        }
+    }
+
+    /// Source of a note or CC message, with its velocity or value (0 for note-off).
+    fn of(msg: MidiMessage) -> Option<(MidiSource, u8)> {
+        match msg {
+            MidiMessage::NoteOn { note, velocity, .. }     => Some((MidiSource::Note(note), velocity)),
+            MidiMessage::NoteOff { note, .. }              => Some((MidiSource::Note(note), 0)),
+            MidiMessage::Control { controller, value, .. } => Some((MidiSource::Cc(controller), value)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MidiBinding {
+    pub source: MidiSource,
+    pub target: LearnTarget,
+    /// CC above the press line last time, so only the upward crossing presses.
+    #[serde(skip)]
+    down:       bool,
+}
+
+impl AppState {
+    /// Arm `target` for the next note or CC; arming it again cancels.
+    pub fn toggle_midi_learn(&self, target: LearnTarget) {
+        let mut learn = self.midi_learn.write();
+        if *learn == Some(target) {
+            *learn = None;
+            *self.status.write() = t!("MIDI learn cancelled").into();
+        } else {
+            *learn = Some(target);
+            *self.status.write() = if self.midi_input.lock().is_some() {
+                t!("🎹 {}: play a note or move a control (right-click again to cancel)", target.label())
+            } else {
+                t!("🎹 {}: pick a MIDI input in ⚙ first", target.label())
+            };
+        }
+    }
+
+    /// Learn from `msg` if a control is armed, or run the binding it matches.
+    /// Returns whether `msg` was used.
+    pub(crate) fn midi_learn_message(&self, msg: MidiMessage) -> bool {
+        let Some((source, value)) = MidiSource::of(msg) else { return false; };
+
+        let armed = *self.midi_learn.read();
+        if let (Some(target), true) = (armed, value > 0) {
+            *self.midi_learn.write() = None;
+            let mut bindings = self.midi_bindings.write();
+            bindings.retain(|b| b.source != source && b.target != target);
+            bindings.push(MidiBinding { source, target, down: value >= PRESS });
+            *self.status.write() = format!("✓ {} → {}", source.label(), target.label());
+            return true;
+        }
+
+        let hits: Vec<(LearnTarget, bool)> = self.midi_bindings.write().iter_mut()
+            .filter(|b| b.source == source)
+            .map(|b| {
+                let was = b.down;
+                b.down  = value >= PRESS;
+                let pressed = match source {
+                    MidiSource::Note(_) => value > 0,
+                    MidiSource::Cc(_)   => b.down && !was,
+                };
+                (b.target, pressed)
+            })
+            .collect();
+        if hits.is_empty() { return false; }
+        for (target, pressed) in hits {
+            match (target, source) {
+                (LearnTarget::Bpm, MidiSource::Cc(_)) => {
+                    self.send(AppCommand::SetBpm { bpm: cc_bpm(value) });
+                }
+                _ if pressed => self.run_learned(target, value, source),
+                _ => {}
+            }
+        }
+        true
+    }
+
+    fn run_learned(&self, target: LearnTarget, value: u8, source: MidiSource) {
+        match target {
+            LearnTarget::Pad(pad) => {
+                let velocity = if matches!(source, MidiSource::Note(_)) { value as f32 / 127.0 } else { 1.0 };
+                self.trigger_pad_velocity(pad, velocity);
+            }
+            LearnTarget::Mute(t) => {
+                if let Some(t) = self.drum_tracks.write().get_mut(t) { t.muted = !t.muted; }
+            }
+            LearnTarget::Bpm => self.tap_tempo(),
+            LearnTarget::Step(lane, step) => self.toggle_lane_step(lane, step),
+        }
+    }
+}
@@ -0,0 +1,164 @@
+// src/mix_snapshot.rs
+// Named mix snapshots. A snapshot holds the mix as it stands — master volume, master
+// filter and crossfader, and each drum track's gain, trim, mute and solo, by row — so
+// two mixes can be flipped between to compare, or moved between as a transition.
+// Recalling one fades to it over the project's fade time (0 = at once): levels glide,
+// a track being muted or unmuted fades out or in before its mute flips, and solos
+// switch straight away. Tracks beyond a snapshot's rows are left as they are.
+// Snapshots are saved with the project.
+
+use std::sync::atomic::Ordering;
+use std::time::Instant;
+use serde::{Deserialize, Serialize};
+use crate::gui::AppState;
+
+/// One drum track's part of the mix.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrackMix {
+    pub gain:    f32,
+    pub trim_db: f32,
+    pub muted:   bool,
+    pub soloed:  bool,
+}
+
+impl Default for TrackMix {
+    fn default() -> Self { Self { gain: 1.0, trim_db: 0.0, muted: false, soloed: false } }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MixSnapshot {
+    pub name:          String,
+    pub master_volume: f32,
+    pub master_cutoff: f32,
+    pub crossfader:    f32,
+    pub tracks:        Vec<TrackMix>,
+}
+
+impl Default for MixSnapshot {
+    fn default() -> Self {
+        Self { name: String::new(), master_volume: 1.0, master_cutoff: 20000.0, crossfader: 0.5, tracks: Vec::new() }
+    }
+}
+
+impl MixSnapshot {
+    /// The mix a fraction `t` (0-1) of the way from `self` to `to`.
+    pub fn lerp(&self, to: &MixSnapshot, t: f32) -> MixSnapshot {
+        let t   = t.clamp(0.0, 1.0);
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        let tracks = to.tracks.iter().enumerate().map(|(i, b)| {
+            let Some(a) = self.tracks.get(i) else { return b.clone(); };
+            // A mute that changes fades the audible level instead, and flips at the end
+            let (gain, muted) = if a.muted != b.muted && t < 1.0 {
+                let level = |m: &TrackMix| if m.muted { 0.0 } else { m.gain };
+                (mix(level(a), level(b)), false)
+            } else {
+                (mix(a.gain, b.gain), b.muted)
+            };
+            TrackMix { gain, trim_db: mix(a.trim_db, b.trim_db), muted, soloed: b.soloed }
+        }).collect();
+        MixSnapshot {
+            name:          to.name.clone(),
+            master_volume: mix(self.master_volume, to.master_volume),
+            // The filter glides in octaves, as it's heard
+            master_cutoff: (self.master_cutoff.max(1.0).ln() * (1.0 - t) + to.master_cutoff.max(1.0).ln() * t).exp(),
+            crossfader:    mix(self.crossfader, to.crossfader),
+            tracks,
+        }
+    }
+}
+
+/// The project's snapshots and how long recalling one takes.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MixSnapshots {
+    pub list:      Vec<MixSnapshot>,
+    /// Fade time on recall, seconds.
+    pub fade_secs: f32,
+    /// Name typed for the next save; not saved.
+    #[serde(skip)]
+    pub draft:     String,
+}
+
+/// A recall on its way.
+pub struct MixFade {
+    from:    MixSnapshot,
+    to:      MixSnapshot,
+    started: Instant,
+    secs:    f32,
+}
+
+impl AppState {
+    /// The mix as it stands.
+    pub fn capture_mix(&self, name: &str) -> MixSnapshot {
+        MixSnapshot {
+            name:          name.to_string(),
+            master_volume: self.master_volume.load(Ordering::Relaxed),
+            master_cutoff: self.master_cutoff.load(Ordering::Relaxed),
+            crossfader:    self.crossfader.load(Ordering::Relaxed),
+            tracks:        self.drum_tracks.read().iter()
+                .map(|t| TrackMix { gain: t.gain, trim_db: t.trim_db, muted: t.muted, soloed: t.soloed })
+                .collect(),
+        }
+    }
+
+    fn apply_mix(&self, mix: &MixSnapshot) {
+        self.master_volume.store(mix.master_volume, Ordering::Relaxed);
+        self.master_cutoff.store(mix.master_cutoff, Ordering::Relaxed);
+        self.crossfader.store(mix.crossfader, Ordering::Relaxed);
+        for (track, m) in self.drum_tracks.write().iter_mut().zip(&mix.tracks) {
+            track.gain    = m.gain;
+            track.trim_db = m.trim_db;
+            track.muted   = m.muted;
+            track.soloed  = m.soloed;
+        }
+    }
+
+    /// Save the current mix as `name`, replacing a snapshot of that name.
+    pub fn save_mix_snapshot(&self, name: &str) {
+        let name = name.trim();
+        if name.is_empty() { return; }
+        let snap = self.capture_mix(name);
+        let mut snaps = self.mix_snapshots.write();
+        match snaps.list.iter_mut().find(|s| s.name == name) {
+            Some(s) => *s = snap,
+            None    => snaps.list.push(snap),
+        }
+        *self.status.write() = t!("✓ Mix snapshot \"{}\" saved", name);
+    }
+
+    /// Go to snapshot `idx`, over the fade time.
+    pub fn recall_mix_snapshot(&self, idx: usize) {
+        let (to, secs) = {
+            let snaps = self.mix_snapshots.read();
+            let Some(to) = snaps.list.get(idx).cloned() else { return; };
+            (to, snaps.fade_secs)
+        };
+        *self.status.write() = t!("Mix snapshot \"{}\"", to.name);
+        if secs <= 0.0 {
+            *self.mix_fade.write() = None;
+            self.apply_mix(&to);
+            return;
+        }
+        let from = self.capture_mix("");
+        *self.mix_fade.write() = Some(MixFade { from, to, started: Instant::now(), secs });
+    }
+
+    pub fn delete_mix_snapshot(&self, idx: usize) {
+        let mut snaps = self.mix_snapshots.write();
+        if idx < snaps.list.len() { snaps.list.remove(idx); }
+    }
+
+    /// Called every frame: move a recall in progress along.
+    pub(crate) fn tick_mix_fade(&self) {
+        let (mix, done) = {
+            let guard = self.mix_fade.read();
+            let Some(f) = guard.as_ref() else { return; };
+            let t = f.started.elapsed().as_secs_f32() / f.secs;
+            (f.from.lerp(&f.to, t), t >= 1.0)
+        };
+        self.apply_mix(&mix);
+        if done { *self.mix_fade.write() = None; }
+    }
+}
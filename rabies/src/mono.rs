@@ -0,0 +1,61 @@
+// src/mono.rs
+// Mono one-shots: with the import preference on, new drum tracks are folded to one
+// channel as they're added, which halves their memory and the mixer's work per voice.
+// The pool keeps the file as decoded, so a track can go back to its full layout later
+// (or be folded afterwards) from its right-click menu. The main sample is never folded.
+
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use crate::audio::AudioAsset;
+use crate::channels::{ChannelMatrix, DownmixCoeffs};
+use crate::gui::{AppState, DrumTrack};
+
+/// `asset` folded down to one channel, same UUID.
+pub fn fold_to_mono(asset: &AudioAsset) -> AudioAsset {
+    let ch = (asset.channels as usize).max(1);
+    if ch == 1 { return asset.clone(); }
+    let matrix = ChannelMatrix::new(ch, 1, &DownmixCoeffs::default());
+    let mut out = [0.0f32];
+    let pcm = asset.pcm.chunks_exact(ch).map(|frame| { matrix.apply(frame, &mut out); out[0] }).collect();
+    AudioAsset { pcm, channels: 1, ..asset.clone() }
+}
+
+impl AppState {
+    /// Fold a track that's being added, if the import preference asks for it.
+    pub(crate) fn apply_mono_import(&self, track: &mut DrumTrack) {
+        if !self.mono_oneshots.load(Ordering::Relaxed) || track.asset.channels < 2 { return; }
+        track.asset    = Arc::new(fold_to_mono(&track.asset));
+        track.waveform = Some(self.audio_manager.analyze_waveform(&track.asset, 400));
+        track.mono     = true;
+    }
+
+    /// Fold track `track_idx` to mono, or give it back the file's own channels.
+    pub fn set_track_mono(&self, track_idx: usize, mono: bool) {
+        let Some((asset, path)) = self.drum_tracks.read().get(track_idx).map(|t| (t.asset.clone(), t.file_path.clone())) else { return; };
+        let new = if mono {
+            fold_to_mono(&asset)
+        } else {
+            let pooled = path.as_ref().and_then(|p| self.asset_pool.read().get(p).cloned());
+            let source = match (pooled, path) {
+                (Some(a), _)    => a,
+                (None, Some(p)) => match self.audio_manager.load_audio(&p) {
+                    Ok(a)  => a,
+                    Err(e) => { *self.status.write() = format!("✗ {}: {}", asset.file_name, e); return; }
+                },
+                (None, None) => {
+                    *self.status.write() = t!("✗ {} has no file to restore the channels from", asset.file_name);
+                    return;
+                }
+            };
+            AudioAsset { sample_uuid: asset.sample_uuid, ..(*source).clone() }
+        };
+        let waveform = self.audio_manager.analyze_waveform(&new, 400);
+        let channels = new.channels;
+        if let Some(t) = self.drum_tracks.write().get_mut(track_idx) {
+            t.asset    = Arc::new(new);
+            t.waveform = Some(waveform);
+            t.mono     = mono;
+        }
+        *self.status.write() = t!("✓ {} is now {}", asset.file_name, crate::channels::layout_name(channels as usize));
+    }
+}
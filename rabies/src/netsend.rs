@@ -0,0 +1,133 @@
+// src/netsend.rs
+// Plain TCP sender for the master output, so another machine on the LAN can listen in.
+//
+// Each client gets a 12-byte header — b"RABS", sample rate (u32 LE), channels (u16 LE),
+// 2 reserved bytes — followed by interleaved f32 LE frames for as long as it stays connected.
+
+use std::collections::VecDeque;
+use std::io::Write;
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use std::time::Duration;
+use parking_lot::Mutex;
+
+pub const DEFAULT_PORT: u16 = 47000;
+/// Samples held for clients before the oldest are dropped (~1 s of 48 kHz stereo).
+const MAX_BUFFERED: usize = 96_000;
+
+/// Where the audio thread drops finished output. Never blocks the callback.
+pub struct NetTap {
+    active:      AtomicBool,
+    sample_rate: AtomicU32,
+    channels:    AtomicUsize,
+    buf:         Mutex<VecDeque<f32>>,
+}
+
+impl Default for NetTap {
+    fn default() -> Self {
+        Self {
+            active:      AtomicBool::new(false),
+            sample_rate: AtomicU32::new(48000),
+            channels:    AtomicUsize::new(2),
+            buf:         Mutex::new(VecDeque::new()),
+        }
+    }
+}
+
+impl NetTap {
+    /// Format of what `push` will deliver; sent to clients that connect afterwards.
+    pub fn set_format(&self, sample_rate: u32, channels: usize) {
+        self.sample_rate.store(sample_rate, Ordering::Relaxed);
+        self.channels.store(channels, Ordering::Relaxed);
+    }
+
+    pub fn push(&self, data: &[f32]) {
+        if !self.active.load(Ordering::Relaxed) { return; }
+        // Skip a block rather than wait on the sender thread
+        let Some(mut buf) = self.buf.try_lock() else { return; };
+        buf.extend(data.iter().copied());
+        let ch   = self.channels.load(Ordering::Relaxed).max(1);
+        let over = buf.len().saturating_sub(MAX_BUFFERED);
+        buf.drain(..over - over % ch);
+    }
+
+    fn header(&self) -> [u8; 12] {
+        let mut h = [0u8; 12];
+        h[..4].copy_from_slice(b"RABS");
+        h[4..8].copy_from_slice(&self.sample_rate.load(Ordering::Relaxed).to_le_bytes());
+        h[8..10].copy_from_slice(&(self.channels.load(Ordering::Relaxed) as u16).to_le_bytes());
+        h
+    }
+}
+
+/// TCP listener + fan-out thread for a `NetTap`.
+pub struct NetSender {
+    pub tap:     Arc<NetTap>,
+    pub port:    Mutex<u16>,
+    pub clients: Arc<AtomicUsize>,
+    running:     Arc<AtomicBool>,
+}
+
+impl Default for NetSender {
+    fn default() -> Self {
+        Self {
+            tap:     Arc::new(NetTap::default()),
+            port:    Mutex::new(DEFAULT_PORT),
+            clients: Arc::new(AtomicUsize::new(0)),
+            running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl NetSender {
+    pub fn is_running(&self) -> bool { self.running.load(Ordering::Relaxed) }
+
+    pub fn start(&self) -> Result<u16, String> {
+        if self.is_running() { return Ok(*self.port.lock()); }
+        let port     = *self.port.lock();
+        let listener = TcpListener::bind(("0.0.0.0", port)).map_err(|e| t!("Listen on {}: {}", port, e))?;
+        listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+
+        self.running.store(true, Ordering::Relaxed);
+        let (tap, running, clients) = (self.tap.clone(), self.running.clone(), self.clients.clone());
+        // Not a job: the sender lives until it's switched off, which would tie up a job
+        // worker slot for the whole session; `running` is its cancel.
+        std::thread::spawn(move || {
+            let mut streams: Vec<TcpStream> = Vec::new();
+            let mut bytes: Vec<u8> = Vec::new();
+            while running.load(Ordering::Relaxed) {
+                while let Ok((mut s, addr)) = listener.accept() {
+                    let _ = s.set_nonblocking(false);
+                    let _ = s.set_nodelay(true);
+                    // A stalled listener gets dropped instead of holding everyone up
+                    let _ = s.set_write_timeout(Some(Duration::from_millis(250)));
+                    if s.write_all(&tap.header()).is_ok() {
+                        println!("[net] listener connected: {}", addr);
+                        streams.push(s);
+                    }
+                }
+                clients.store(streams.len(), Ordering::Relaxed);
+                tap.active.store(!streams.is_empty(), Ordering::Relaxed);
+
+                bytes.clear();
+                {
+                    let mut buf = tap.buf.lock();
+                    bytes.extend(buf.drain(..).flat_map(f32::to_le_bytes));
+                }
+                if !bytes.is_empty() {
+                    streams.retain_mut(|s| s.write_all(&bytes).is_ok());
+                }
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            tap.active.store(false, Ordering::Relaxed);
+            tap.buf.lock().clear();
+            clients.store(0, Ordering::Relaxed);
+        });
+        Ok(port)
+    }
+
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::Relaxed);
+    }
+}
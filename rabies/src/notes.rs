@@ -0,0 +1,132 @@
+// src/notes.rs
+// Notes and source credits. Every sample file in a project — behind a drum track, its
+// chop pads, or a pad bank's pad — can carry where it came from, who to credit, how far
+// its clearance has got and free-text notes; the project has its own notes and credits.
+// They're kept per file rather than per track, so a sample used by several patterns or
+// banks has one record, and they're saved in the project with paths made relative like
+// every other sample path. "Copy credits" gathers them into plain text for liner notes
+// or a clearance request.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::gui::AppState;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Clearance {
+    #[default]
+    Unknown,
+    /// Needs clearing before release.
+    Needed,
+    /// Asked for, waiting on an answer.
+    Pending,
+    Cleared,
+    /// Own recording, or royalty-free.
+    Free,
+}
+
+impl Clearance {
+    pub const ALL: [Clearance; 5] = [Clearance::Unknown, Clearance::Needed, Clearance::Pending, Clearance::Cleared, Clearance::Free];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Clearance::Unknown => t!("Not checked"),
+            Clearance::Needed  => t!("Needs clearing"),
+            Clearance::Pending => t!("Clearance pending"),
+            Clearance::Cleared => t!("Cleared"),
+            Clearance::Free    => t!("Free to use"),
+        }
+    }
+
+    /// Still has to be sorted out before release.
+    pub fn outstanding(self) -> bool { matches!(self, Clearance::Needed | Clearance::Pending) }
+}
+
+/// What's known about one sample file.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SampleNotes {
+    pub file:      String,
+    /// Where it came from and who to credit: a record, a pack, a URL.
+    pub source:    String,
+    pub clearance: Clearance,
+    pub notes:     String,
+}
+
+impl SampleNotes {
+    pub fn is_empty(&self) -> bool {
+        self.source.trim().is_empty() && self.notes.trim().is_empty() && self.clearance == Clearance::Unknown
+    }
+
+    /// One line for a tooltip; `None` when there's nothing to say.
+    pub fn summary(&self) -> Option<String> {
+        if self.is_empty() { return None; }
+        let mut parts = vec![self.clearance.label().to_string()];
+        if !self.source.trim().is_empty() { parts.push(self.source.trim().to_string()); }
+        if !self.notes.trim().is_empty() { parts.push(self.notes.trim().to_string()); }
+        Some(parts.join("  ·  "))
+    }
+}
+
+/// Notes on the project as a whole.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProjectNotes {
+    pub notes:   String,
+    /// Writers, players, samples credited on release.
+    pub credits: String,
+}
+
+fn file_name(file: &str) -> String {
+    Path::new(file).file_name().map_or_else(|| file.to_string(), |n| n.to_string_lossy().to_string())
+}
+
+impl AppState {
+    /// Notes on `file`, blank if it has none.
+    pub fn sample_notes(&self, file: &str) -> SampleNotes {
+        self.sample_notes.read().iter().find(|n| n.file == file).cloned()
+            .unwrap_or_else(|| SampleNotes { file: file.to_string(), ..Default::default() })
+    }
+
+    /// Store `notes` for its file; blank notes are dropped.
+    pub fn set_sample_notes(&self, notes: SampleNotes) {
+        let mut all = self.sample_notes.write();
+        all.retain(|n| n.file != notes.file);
+        if !notes.is_empty() { all.push(notes); }
+    }
+
+    /// Every sample file the project uses: drum tracks in any pattern, and pad banks.
+    pub fn project_sample_files(&self) -> Vec<String> {
+        let mut files: BTreeSet<String> = self.drum_tracks.read().iter()
+            .filter_map(|t| t.file_path.clone())
+            .collect();
+        for p in self.song_editor.get_all_patterns() {
+            files.extend(p.tracks.into_iter().map(|t| t.file_path).filter(|f| !f.is_empty()));
+        }
+        for b in self.pad_banks.read().iter() { files.extend(b.files.iter().cloned()); }
+        files.into_iter().collect()
+    }
+
+    /// The project's credits and every sample's source, as plain text.
+    pub fn credits_text(&self) -> String {
+        let project = self.project_notes.read().clone();
+        let mut out = String::new();
+        if !project.credits.trim().is_empty() {
+            out.push_str(project.credits.trim());
+            out.push_str("\n\n");
+        }
+        out.push_str(t!("Samples"));
+        out.push('\n');
+        for file in self.project_sample_files() {
+            let n = self.sample_notes(&file);
+            let source = if n.source.trim().is_empty() { "—" } else { n.source.trim() };
+            out.push_str(&format!("{} — {} [{}]\n", file_name(&file), source, n.clearance.label()));
+        }
+        out
+    }
+
+    /// Samples in use still waiting on clearance.
+    pub fn outstanding_clearances(&self) -> usize {
+        self.project_sample_files().iter().filter(|f| self.sample_notes(f).clearance.outstanding()).count()
+    }
+}
@@ -0,0 +1,126 @@
+// src/pad_bank.rs
+// Pad banks: a folder of pre-chopped stems or one-shots loaded straight onto the pads,
+// one file per pad in name order, without making drum tracks or dropping markers.
+// While a bank is picked the pads play its files whole; with none picked they play
+// the focused track's chops as before. Banks keep only file paths, and the samples
+// live in the asset pool, so they show up in the browser and reload with the project.
+
+use std::path::Path;
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use crate::adsr::{ADSREnvelope, Bus, Voice};
+use crate::gui::AppState;
+use crate::jobs::JobKind;
+
+/// Extensions taken from a folder, as offered by the file dialogs.
+const AUDIO_EXTS: [&str; 7] = ["wav", "mp3", "flac", "ogg", "aiff", "aif", "m4a"];
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PadBank {
+    pub name:  String,
+    /// Pad `n` plays `files[n]`.
+    pub files: Vec<String>,
+}
+
+impl PadBank {
+    /// What pad `pad` shows: its file's name without the extension.
+    pub fn pad_label(&self, pad: usize) -> Option<String> {
+        self.files.get(pad).map(|f| {
+            Path::new(f).file_stem().map_or_else(|| f.clone(), |s| s.to_string_lossy().to_string())
+        })
+    }
+}
+
+/// Audio files directly in `dir`, sorted by name (case-insensitively).
+fn audio_files(dir: &Path) -> Result<Vec<String>, String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| t!("Read {}: {}", dir.display(), e))?;
+    let mut files: Vec<std::path::PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.is_file() && p.extension()
+            .is_some_and(|x| AUDIO_EXTS.contains(&x.to_string_lossy().to_lowercase().as_str())))
+        .collect();
+    files.sort_by_key(|p| p.file_name().map(|n| n.to_string_lossy().to_lowercase()));
+    Ok(files.into_iter().map(|p| p.to_string_lossy().to_string()).collect())
+}
+
+impl AppState {
+    /// Pick a folder and load its audio files, as a background job, into a new pad bank
+    /// that becomes the one the pads play. Files past the pad count still load; a bigger
+    /// grid reaches them.
+    pub fn import_folder_as_pad_bank(&self) {
+        let Some(dir) = rfd::FileDialog::new().set_title(t!("Folder of stems or one-shots")).pick_folder() else { return; };
+        let files = match audio_files(&dir) {
+            Ok(f) if f.is_empty() => {
+                *self.status.write() = t!("✗ No audio files in {}", dir.display());
+                return;
+            }
+            Ok(f)  => f,
+            Err(e) => { *self.status.write() = format!("✗ {}", e); return; }
+        };
+        let name = dir.file_name().map_or_else(|| "Bank".to_string(), |n| n.to_string_lossy().to_string());
+        let audio_manager   = self.audio_manager.clone();
+        let asset_pool      = self.asset_pool.clone();
+        let pad_banks       = self.pad_banks.clone();
+        let active_pad_bank = self.active_pad_bank.clone();
+        let pad_layout      = self.pad_layout.clone();
+
+        self.spawn_job(JobKind::Load, t!("Pad bank {}", name), move |job| {
+            let mut loaded = Vec::new();
+            let mut failed = 0;
+            for (i, file) in files.iter().enumerate() {
+                if job.cancelled() { return Err(t!("Cancelled").to_string()); }
+                job.progress(i, files.len());
+                if !asset_pool.read().contains_key(file) {
+                    match audio_manager.load_audio(file) {
+                        Ok(asset) => { asset_pool.write().insert(file.clone(), asset); }
+                        Err(_)    => { failed += 1; continue; }
+                    }
+                }
+                loaded.push(file.clone());
+            }
+            if loaded.is_empty() {
+                return Err(t!("None of the files in {} could be decoded", dir.display()));
+            }
+            let n   = loaded.len();
+            let idx = {
+                let mut banks = pad_banks.write();
+                banks.push(PadBank { name: name.clone(), files: loaded });
+                banks.len() - 1
+            };
+            *active_pad_bank.write() = Some(idx);
+            let pads = pad_layout.read().size.count();
+            Ok(t!("✓ Pad bank \"{}\": {} pad(s){}{}", name, n,
+                if n > pads { t!(", {} past this {}-pad grid", n - pads, pads) } else { String::new() },
+                if failed > 0 { t!(" — {} file(s) couldn't be decoded", failed) } else { String::new() }))
+        });
+    }
+
+    /// The bank the pads play, if one is picked.
+    pub fn active_bank(&self) -> Option<PadBank> {
+        let idx = (*self.active_pad_bank.read())?;
+        self.pad_banks.read().get(idx).cloned()
+    }
+
+    /// Drop bank `idx`; the pads go back to chops if it was the one playing.
+    pub fn remove_pad_bank(&self, idx: usize) {
+        let mut banks = self.pad_banks.write();
+        if idx >= banks.len() { return; }
+        let gone = banks.remove(idx);
+        let mut active = self.active_pad_bank.write();
+        *active = match *active {
+            Some(a) if a == idx => None,
+            Some(a) if a > idx  => Some(a - 1),
+            other => other,
+        };
+        *self.status.write() = t!("Pad bank \"{}\" removed", gone.name);
+    }
+
+    /// Voice for pad `pad` of the active bank: its file from the top, on the drum bus.
+    pub(crate) fn bank_voice(&self, bank: &PadBank, pad: usize) -> Option<Voice> {
+        let asset = self.asset_pool.read().get(bank.files.get(pad)?).cloned()?;
+        let mut v = Voice::new(Arc::new(asset.pcm.clone()), asset.channels as usize, 0, 1.0,
+            ADSREnvelope::default(), false, asset.sample_rate);
+        v.bus = Bus::Drum;
+        Some(v)
+    }
+}
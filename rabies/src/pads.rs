@@ -0,0 +1,75 @@
+// src/pads.rs
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PadGridSize {
+    Grid4x4,
+    Grid8x2,
+    Grid4x8,
+}
+
+impl PadGridSize {
+    pub const ALL: [PadGridSize; 3] = [PadGridSize::Grid4x4, PadGridSize::Grid8x2, PadGridSize::Grid4x8];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PadGridSize::Grid4x4 => "4×4",
+            PadGridSize::Grid8x2 => "8×2",
+            PadGridSize::Grid4x8 => "4×8",
+        }
+    }
+
+    pub fn cols(&self) -> usize {
+        match self {
+            PadGridSize::Grid4x4 | PadGridSize::Grid4x8 => 4,
+            PadGridSize::Grid8x2 => 8,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        match self {
+            PadGridSize::Grid4x4 => 4,
+            PadGridSize::Grid8x2 => 2,
+            PadGridSize::Grid4x8 => 8,
+        }
+    }
+
+    pub fn count(&self) -> usize { self.cols() * self.rows() }
+}
+
+/// Pad grid dimensions plus which pad sits in each slot. Pad `n` plays chop `n`
+/// of the focused track; slots are row-major screen positions.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PadLayout {
+    pub size:  PadGridSize,
+    /// slot → pad number
+    pub slots: Vec<usize>,
+}
+
+impl Default for PadLayout {
+    fn default() -> Self { Self::new(PadGridSize::Grid4x4) }
+}
+
+impl PadLayout {
+    pub fn new(size: PadGridSize) -> Self {
+        Self { size, slots: (0..size.count()).collect() }
+    }
+
+    /// Change grid size, keeping the custom order of every pad that still fits.
+    pub fn resize(&mut self, size: PadGridSize) {
+        let n = size.count();
+        let mut slots: Vec<usize> = self.slots.iter().copied().filter(|&p| p < n).collect();
+        for p in 0..n {
+            if !slots.contains(&p) { slots.push(p); }
+        }
+        slots.truncate(n);
+        self.size  = size;
+        self.slots = slots;
+    }
+
+    pub fn swap(&mut self, a: usize, b: usize) {
+        if a < self.slots.len() && b < self.slots.len() { self.slots.swap(a, b); }
+    }
+
+    pub fn pad_at(&self, slot: usize) -> Option<usize> { self.slots.get(slot).copied() }
+}
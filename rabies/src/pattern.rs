@@ -1,9 +1,16 @@
 // src/pattern.rs
 use eframe::egui;
-use crate::gui::NUM_STEPS;
+use crate::gui::{lane_len, lane_steps, DEFAULT_PATTERN_LEN, StepResolution};
 use crate::adsr::ADSREnvelope;
 use crate::gui::ChopPlayMode;
 use crate::piano_roll::PianoRollNote;
+use crate::step_pitch::{self, StepPitch};
+use crate::step_nudge::{self, StepNudge};
+use crate::row_timing::RowOffset;
+use crate::humanize::RowHumanize;
+use crate::variation::{self, ChopAlternate};
+use crate::category::DrumCategory;
+use serde::{Deserialize, Serialize};
 
 /// Colour palette – one per pattern, cycles
 pub const PATTERN_COLORS: &[(u8, u8, u8)] = &[
@@ -20,30 +27,190 @@ pub const PATTERN_COLORS: &[(u8, u8, u8)] = &[
 ];
 
 /// Saved position of a single chop marker
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarkSnapshot {
     pub position: f32,
 }
 
+/// A region of a track and its sequencer lane. `from` / `to` index the track's marks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionSnapshot {
+    pub from:  usize,
+    pub to:    usize,
+    pub name:  String,
+    pub steps: Vec<bool>,
+}
+
 /// Full state of one drum track, serialisable per pattern
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrackSnapshot {
     pub file_path: String,
     pub file_name: String,
-    pub steps: [bool; NUM_STEPS],
-    pub chop_steps: Vec<[bool; NUM_STEPS]>,
+    pub steps: Vec<bool>,
+    /// Missing entries play at full velocity.
+    #[serde(default)]
+    pub step_velocity: Vec<f32>,
+    pub chop_steps: Vec<Vec<bool>>,
+    #[serde(default)]
+    pub triplet: bool,
+    #[serde(default)]
+    pub chop_triplet: Vec<bool>,
+    /// Rows run at this resolution rather than the grid's.
+    #[serde(default)]
+    pub resolution: Option<StepResolution>,
     pub adsr: ADSREnvelope,
     pub adsr_enabled: bool,
     pub chop_adsr: Vec<ADSREnvelope>,
     pub chop_adsr_enabled: Vec<bool>,
     pub chop_play_modes: Vec<ChopPlayMode>,
     pub chop_piano_notes: Vec<Vec<PianoRollNote>>,
+    #[serde(default)]
+    pub chop_alternates: Vec<ChopAlternate>,
+    #[serde(default)]
+    pub step_pitches: Vec<StepPitch>,
+    #[serde(default)]
+    pub step_nudges: Vec<StepNudge>,
+    #[serde(default)]
+    pub row_offsets: Vec<RowOffset>,
+    #[serde(default)]
+    pub row_humanize: Vec<RowHumanize>,
     pub marks: Vec<MarkSnapshot>,   // chop marker positions (normalised 0-1)
+    #[serde(default)]
+    pub regions: Vec<RegionSnapshot>,
     pub muted: bool,
+    #[serde(default)]
+    pub soloed: bool,
+    #[serde(default)]
+    pub trim_db: f32,
+    #[serde(default)]
+    pub invert: bool,
+    #[serde(default)]
+    pub mono: bool,
+    /// Set when the category was chosen by hand; otherwise it's guessed on load.
+    #[serde(default)]
+    pub category: Option<DrumCategory>,
+    #[serde(default = "unity_gain")]
+    pub gain: f32,
+    #[serde(default)]
+    pub gain_before_match: Option<f32>,
+    #[serde(default)]
+    pub late_ms: f32,
+    #[serde(default)]
+    pub late_jitter_ms: f32,
+}
+
+fn unity_gain() -> f32 { 1.0 }
+
+impl TrackSnapshot {
+    /// Same sample as `other`: the same file, or the same name when neither has a path.
+    pub fn same_sample(&self, other: &TrackSnapshot) -> bool {
+        if self.file_path.is_empty() && other.file_path.is_empty() {
+            self.file_name == other.file_name
+        } else {
+            self.file_path == other.file_path
+        }
+    }
+
+    /// Drop every step, lane and note, keeping the sample, chops and sound settings.
+    pub fn clear_steps(&mut self) {
+        self.steps.fill(false);
+        for row in self.chop_steps.iter_mut() { row.fill(false); }
+        for r in self.regions.iter_mut() { r.steps.fill(false); }
+        self.triplet = false;
+        for t in self.chop_triplet.iter_mut() { *t = false; }
+        for n in self.chop_piano_notes.iter_mut() { n.clear(); }
+        self.chop_alternates.clear();
+        self.step_pitches.clear();
+        self.step_nudges.clear();
+    }
+
+    /// Whether the main row (`None`) or chop `c`'s row runs on the triplet lane.
+    pub fn row_triplet(&self, chop: Option<usize>) -> bool {
+        chop.map_or(self.triplet, |c| self.chop_triplet.get(c).copied().unwrap_or(false))
+    }
+
+    /// Steps per bar on the main row (`None`) or a chop row; `on_grid` for chops that
+    /// live on the main grid.
+    pub fn lane(&self, grid: StepResolution, chop: Option<usize>, on_grid: bool) -> usize {
+        lane_steps(grid, if on_grid { None } else { self.resolution }, self.row_triplet(chop))
+    }
+
+    /// Steps in each row for a pattern of `len` grid steps.
+    pub fn row_len(&self, len: usize, grid: StepResolution) -> usize {
+        lane_len(len, grid, lane_steps(grid, self.resolution, false))
+    }
+
+    /// Move every step row, and what hangs off its steps, from lanes on `from` to `to`.
+    /// Returns how many hits were merged.
+    pub fn remap_rows(&mut self, from: StepResolution, to: StepResolution) -> usize {
+        self.step_velocity = from.remap_velocity(&self.steps, &self.step_velocity, to);
+        let (steps, mut merged) = from.remap_row(&self.steps, to);
+        self.steps = steps;
+        for row in self.chop_steps.iter_mut() {
+            let (r, m) = from.remap_row(row, to);
+            *row = r;
+            merged += m;
+        }
+        variation::move_steps(&mut self.chop_alternates, |_| true, |s| Some(from.map_step(s, to)));
+        step_pitch::move_steps(&mut self.step_pitches, |_| true, |s| Some(from.map_step(s, to)));
+        step_nudge::move_steps(&mut self.step_nudges, |_| true, |s| Some(from.map_step(s, to)));
+        merged
+    }
+
+    /// Size every step row to a pattern of `len` grid steps.
+    pub fn fit_steps(&mut self, len: usize, grid: StepResolution) {
+        let rows = self.row_len(len, grid);
+        self.steps.resize(rows, false);
+        self.step_velocity.resize(rows, 1.0);
+        for row in self.chop_steps.iter_mut() { row.resize(rows, false); }
+        // Regions play on the grid
+        for r in self.regions.iter_mut() { r.steps.resize(len, false); }
+        variation::move_steps(&mut self.chop_alternates, |_| true, |s| (s < rows).then_some(s));
+        step_pitch::move_steps(&mut self.step_pitches, |_| true, |s| (s < rows).then_some(s));
+        step_nudge::move_steps(&mut self.step_nudges, |_| true, |s| (s < rows).then_some(s));
+    }
+
+    /// Add `incoming` marks that aren't already here. Per-chop rows follow their
+    /// mark to its new index, so existing programming keeps its sound. Returns how many were added.
+    pub fn merge_marks(&mut self, incoming: &[MarkSnapshot]) -> usize {
+        const SAME_POS: f32 = 0.001;
+        let mut order: Vec<(f32, Option<usize>)> = self.marks.iter().enumerate()
+            .map(|(i, m)| (m.position, Some(i)))
+            .collect();
+        for m in incoming {
+            if !order.iter().any(|(p, _)| (p - m.position).abs() < SAME_POS) {
+                order.push((m.position, None));
+            }
+        }
+        let added = order.len() - self.marks.len();
+        if added == 0 { return 0; }
+        order.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let adsr = self.adsr;
+        fn follow<T: Clone>(v: &[T], order: &[(f32, Option<usize>)], blank: T) -> Vec<T> {
+            order.iter().map(|(_, old)| old.and_then(|i| v.get(i).cloned()).unwrap_or_else(|| blank.clone())).collect()
+        }
+        let blank_row = vec![false; self.steps.len()];
+        self.chop_steps        = follow(&self.chop_steps, &order, blank_row);
+        self.chop_triplet      = follow(&self.chop_triplet, &order, false);
+        self.chop_adsr         = follow(&self.chop_adsr, &order, adsr);
+        self.chop_adsr_enabled = follow(&self.chop_adsr_enabled, &order, false);
+        self.chop_play_modes   = follow(&self.chop_play_modes, &order, ChopPlayMode::ToNextChop);
+        self.chop_piano_notes  = follow(&self.chop_piano_notes, &order, Vec::new());
+        let moved = |old: usize| order.iter().position(|(_, o)| *o == Some(old)).unwrap_or(old);
+        for r in self.regions.iter_mut() { (r.from, r.to) = (moved(r.from), moved(r.to)); }
+        for a in self.chop_alternates.iter_mut() { (a.chop, a.alt) = (moved(a.chop), moved(a.alt)); }
+        for p in self.step_pitches.iter_mut() { p.chop = p.chop.map(moved); }
+        for n in self.step_nudges.iter_mut() { n.chop = n.chop.map(moved); }
+        for o in self.row_offsets.iter_mut() { o.chop = o.chop.map(moved); }
+        for h in self.row_humanize.iter_mut() { h.chop = h.chop.map(moved); }
+        self.marks = order.iter().map(|(p, _)| MarkSnapshot { position: *p }).collect();
+        added
+    }
 }
 
 /// A single pattern – the equivalent of one FL Studio "pattern" in the channel rack
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Pattern {
     pub id: usize,
     pub name: String,
@@ -54,6 +221,9 @@ pub struct Pattern {
     pub tracks: Vec<TrackSnapshot>,
     /// Visual length in the song editor (bars)
     pub length_bars: usize,
+    /// Pattern played for one bar when a fill is triggered
+    #[serde(default)]
+    pub fill: Option<usize>,
 }
 
 impl Pattern {
@@ -63,9 +233,10 @@ impl Pattern {
             id,
             name,
             color,
-            main_grid: vec![Vec::new(); NUM_STEPS],
+            main_grid: vec![Vec::new(); DEFAULT_PATTERN_LEN],
             tracks: Vec::new(),
             length_bars: 1,
+            fill: None,
         }
     }
 
@@ -87,6 +258,29 @@ impl Pattern {
         )
     }
 
+    /// Move every step and piano note onto `to`. Returns how many hits were merged.
+    pub fn remap_steps(&mut self, from: StepResolution, to: StepResolution) -> usize {
+        let (grid, mut merged) = from.remap_grid(&self.main_grid, to);
+        self.main_grid = grid;
+        for t in self.tracks.iter_mut() {
+            // A track at its own resolution keeps its rows where they are
+            if t.resolution.is_none() { merged += t.remap_rows(from, to); }
+            for r in t.regions.iter_mut() {
+                let (row, m) = from.remap_row(&r.steps, to);
+                r.steps = row;
+                merged += m;
+            }
+            for notes in t.chop_piano_notes.iter_mut() { merged += from.remap_notes(notes, to); }
+        }
+        merged
+    }
+
+    /// Size the grid and every track's step rows to a pattern of `len` grid steps.
+    pub fn fit_steps(&mut self, len: usize, grid: StepResolution) {
+        self.main_grid.resize(len, Vec::new());
+        for t in self.tracks.iter_mut() { t.fit_steps(len, grid); }
+    }
+
     /// (legacy compat) no-op – we use TrackSnapshot Vec directly
     pub fn ensure_track_count(&mut self, _count: usize) {}
 }
\ No newline at end of file
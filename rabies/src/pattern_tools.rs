@@ -0,0 +1,281 @@
+// src/pattern_tools.rs
+// Pattern length and timing tools. Double plays the pattern twice in one twice as long,
+// halve keeps the first half, and shift rotates the hits of one row or of the whole
+// pattern left or right, wrapping round the end. Each row moves on its own lane
+// (triplet rows by triplet steps, a track at its own resolution by its steps), with its
+// velocities, alternates and piano notes.
+//
+// The tools edit the pattern model and reload it. The step count is shared by every
+// pattern, so double and halve apply to all of them (and the recording tracks); a shift
+// only touches the pattern being edited. Every tool can be undone.
+
+use std::sync::atomic::Ordering;
+use crate::grid_controller::Lane;
+use crate::gui::{lane_len, lane_steps, AppState, StepResolution, WaveformFocus, MAX_STEPS};
+use crate::pattern::Pattern;
+use crate::piano_roll::PianoRollNote;
+use crate::step_pitch::{self, StepPitch};
+use crate::step_nudge::{self, StepNudge};
+use crate::variation::{self, ChopAlternate};
+
+fn wrap(step: usize, lane: usize, by: isize) -> usize {
+    (step as isize + by).rem_euclid(lane.max(1) as isize) as usize
+}
+
+/// Rotate the first `lane` steps of `row`; a positive `by` moves hits later.
+fn rotate<T: Clone>(row: &mut Vec<T>, lane: usize, by: isize, blank: T) {
+    if lane == 0 { return; }
+    if row.len() < lane { row.resize(lane, blank); }
+    row[..lane].rotate_right(by.rem_euclid(lane as isize) as usize);
+}
+
+/// Copy the first `lane` steps of `row` right after them, in a row of `total` steps.
+fn repeat<T: Clone>(row: &mut Vec<T>, lane: usize, total: usize, blank: T) {
+    row.resize(total.max(2 * lane), blank);
+    let (head, tail) = row.split_at_mut(lane);
+    tail[..lane].clone_from_slice(head);
+}
+
+fn grid_row(grid: &[Vec<usize>], chop: usize) -> Vec<bool> {
+    grid.iter().map(|g| g.contains(&chop)).collect()
+}
+
+/// Every chop with a hit on `grid`.
+fn grid_chops(grid: &[Vec<usize>]) -> Vec<usize> {
+    let mut chops: Vec<usize> = grid.iter().flatten().copied().collect();
+    chops.sort_unstable();
+    chops.dedup();
+    chops
+}
+
+fn set_grid_row(grid: &mut Vec<Vec<usize>>, chop: usize, row: &[bool]) {
+    if grid.len() < row.len() { grid.resize(row.len(), Vec::new()); }
+    for (s, cell) in grid.iter_mut().enumerate() {
+        cell.retain(|&c| c != chop);
+        if row.get(s).copied().unwrap_or(false) { cell.push(chop); }
+    }
+}
+
+impl Pattern {
+    /// Steps per bar of chop `c` of the main track, on the main grid.
+    fn main_chop_lane(&self, main: Option<usize>, c: usize, res: StepResolution) -> usize {
+        let triplet = main.and_then(|m| self.tracks.get(m)).and_then(|t| t.chop_triplet.get(c).copied()).unwrap_or(false);
+        lane_steps(res, None, triplet)
+    }
+
+    /// Rotate one row of a `len`-step pattern on the `res` grid. Chops of the `main`
+    /// track live on the main grid.
+    pub fn shift_lane(&mut self, lane: Lane, by: isize, len: usize, res: StepResolution, main: Option<usize>) {
+        match lane {
+            Lane::Main(t) => if let Some(tr) = self.tracks.get_mut(t) {
+                let n = lane_len(len, res, tr.lane(res, None, false));
+                rotate(&mut tr.steps, n, by, false);
+                rotate(&mut tr.step_velocity, n, by, 1.0);
+                step_pitch::move_steps(&mut tr.step_pitches, |chop| chop.is_none(), |s| Some(wrap(s, n, by)));
+                step_nudge::move_steps(&mut tr.step_nudges, |chop| chop.is_none(), |s| Some(wrap(s, n, by)));
+            },
+            Lane::Chop(t, c) => {
+                let on_grid = main == Some(t);
+                let Some(tr) = self.tracks.get_mut(t) else { return; };
+                let n = lane_len(len, res, tr.lane(res, Some(c), on_grid));
+                if !on_grid {
+                    if let Some(row) = tr.chop_steps.get_mut(c) { rotate(row, n, by, false); }
+                }
+                variation::move_steps(&mut tr.chop_alternates, |chop| chop == c, |s| Some(wrap(s, n, by)));
+                step_pitch::move_steps(&mut tr.step_pitches, |chop| chop == Some(c), |s| Some(wrap(s, n, by)));
+                step_nudge::move_steps(&mut tr.step_nudges, |chop| chop == Some(c), |s| Some(wrap(s, n, by)));
+                if let Some(notes) = tr.chop_piano_notes.get_mut(c) {
+                    for note in notes.iter_mut() { note.step = wrap(note.step, len, by); }
+                }
+                if on_grid {
+                    let mut row = grid_row(&self.main_grid, c);
+                    rotate(&mut row, n, by, false);
+                    set_grid_row(&mut self.main_grid, c, &row);
+                }
+            }
+        }
+    }
+
+    /// Set the hits of one row to `row(n)`, `n` being the row's lane length.
+    pub fn fill_lane(&mut self, lane: Lane, len: usize, res: StepResolution, main: Option<usize>, row: impl Fn(usize) -> Vec<bool>) {
+        match lane {
+            Lane::Main(t) => if let Some(tr) = self.tracks.get_mut(t) {
+                let hits = row(lane_len(len, res, tr.lane(res, None, false)));
+                tr.steps.resize(tr.steps.len().max(hits.len()), false);
+                tr.steps[..hits.len()].copy_from_slice(&hits);
+            },
+            Lane::Chop(t, c) => {
+                let Some(tr) = self.tracks.get_mut(t) else { return; };
+                let hits = row(lane_len(len, res, tr.lane(res, Some(c), main == Some(t))));
+                if main == Some(t) {
+                    set_grid_row(&mut self.main_grid, c, &hits);
+                } else {
+                    if tr.chop_steps.len() <= c { tr.chop_steps.resize(c + 1, vec![false; tr.row_len(len, res)]); }
+                    let r = &mut tr.chop_steps[c];
+                    r.resize(r.len().max(hits.len()), false);
+                    r[..hits.len()].copy_from_slice(&hits);
+                }
+            }
+        }
+    }
+
+    /// Rotate every row, region lane and the main grid.
+    pub fn shift_all(&mut self, by: isize, len: usize, res: StepResolution, main: Option<usize>) {
+        for t in 0..self.tracks.len() {
+            self.shift_lane(Lane::Main(t), by, len, res, main);
+            for c in 0..self.tracks[t].chop_steps.len() { self.shift_lane(Lane::Chop(t, c), by, len, res, main); }
+            for r in self.tracks[t].regions.iter_mut() { rotate(&mut r.steps, len, by, false); }
+        }
+        // Grid chops no track row covers (the main sample's own chops)
+        let covered = main.and_then(|m| self.tracks.get(m)).map_or(0, |t| t.chop_steps.len());
+        for c in grid_chops(&self.main_grid).into_iter().filter(|&c| c >= covered) {
+            let mut row = grid_row(&self.main_grid, c);
+            rotate(&mut row, len, by, false);
+            set_grid_row(&mut self.main_grid, c, &row);
+        }
+    }
+
+    /// Grow a `len`-step pattern to twice the length, playing it twice.
+    pub fn repeat_twice(&mut self, len: usize, res: StepResolution, main: Option<usize>) {
+        let total = 2 * len;
+        let old = std::mem::replace(&mut self.main_grid, vec![Vec::new(); total]);
+        for c in grid_chops(&old) {
+            let mut row = grid_row(&old, c);
+            repeat(&mut row, lane_len(len, res, self.main_chop_lane(main, c, res)), total, false);
+            set_grid_row(&mut self.main_grid, c, &row);
+        }
+        for (t, tr) in self.tracks.iter_mut().enumerate() {
+            // Rows on the track's own lane double on it
+            let rows  = 2 * tr.row_len(len, res);
+            let (own, triplet, chop_triplet) = (tr.resolution, tr.triplet, tr.chop_triplet.clone());
+            let lane  = |chop: Option<usize>| {
+                let on_grid = chop.is_some() && main == Some(t);
+                let trip    = chop.map_or(triplet, |c| chop_triplet.get(c).copied().unwrap_or(false));
+                lane_len(len, res, lane_steps(res, if on_grid { None } else { own }, trip))
+            };
+            repeat(&mut tr.steps, lane(None), rows, false);
+            repeat(&mut tr.step_velocity, lane(None), rows, 1.0);
+            for (c, row) in tr.chop_steps.iter_mut().enumerate() { repeat(row, lane(Some(c)), rows, false); }
+            for r in tr.regions.iter_mut() { repeat(&mut r.steps, len, total, false); }
+            let copies: Vec<_> = tr.chop_alternates.iter().map(|a| ChopAlternate { step: a.step + lane(Some(a.chop)), ..*a }).collect();
+            tr.chop_alternates.extend(copies);
+            let copies: Vec<_> = tr.step_pitches.iter().map(|p| StepPitch { step: p.step + lane(p.chop), ..*p }).collect();
+            tr.step_pitches.extend(copies);
+            let copies: Vec<_> = tr.step_nudges.iter().map(|p| StepNudge { step: p.step + lane(p.chop), ..*p }).collect();
+            tr.step_nudges.extend(copies);
+            for notes in tr.chop_piano_notes.iter_mut() {
+                let copies: Vec<_> = notes.iter().map(|n| PianoRollNote { step: n.step + len, ..n.clone() }).collect();
+                notes.extend(copies);
+            }
+        }
+    }
+
+    /// Keep the first `len` grid steps. Returns how many hits went.
+    pub fn keep_first(&mut self, len: usize, res: StepResolution) -> usize {
+        let past = |row: &[bool], n: usize| row.iter().skip(n).filter(|&&on| on).count();
+        let mut dropped = self.main_grid.iter().skip(len).map(|g| g.len()).sum::<usize>();
+        self.main_grid.truncate(len);
+        for tr in self.tracks.iter_mut() {
+            let n = tr.row_len(len, res);
+            dropped += past(&tr.steps, n) + tr.chop_steps.iter().map(|r| past(r, n)).sum::<usize>();
+            dropped += tr.regions.iter().map(|r| past(&r.steps, len)).sum::<usize>();
+            for notes in tr.chop_piano_notes.iter_mut() {
+                let before = notes.len();
+                notes.retain(|n| n.step < len);
+                dropped += before - notes.len();
+            }
+            tr.fit_steps(len, res);
+        }
+        dropped
+    }
+}
+
+impl AppState {
+    /// Play every pattern twice, in patterns twice as long.
+    pub fn double_pattern(&self) {
+        let len = self.pattern_len();
+        if 2 * len > MAX_STEPS {
+            *self.status.write() = t!("✗ Patterns can't be longer than {} steps", MAX_STEPS);
+            return;
+        }
+        self.push_undo(t!("double"));
+        let main = *self.main_track_index.read();
+        let res  = *self.seq_resolution.read();
+        for (idx, mut p) in self.song_editor.get_all_patterns().into_iter().enumerate() {
+            p.repeat_twice(len, res, main);
+            self.song_editor.update_pattern_by_idx(idx, p);
+        }
+        for t in self.rec_tracks.write().iter_mut() { repeat(&mut t.steps, len, 2 * len, false); }
+        if let Some(ab) = self.ab_compare.write().as_mut() { ab.other.repeat_twice(len, res, main); }
+        self.set_tool_len(2 * len);
+        self.reload_active_pattern();
+        *self.status.write() = t!("✓ Patterns doubled to {} steps", 2 * len);
+    }
+
+    /// Cut every pattern to its first half.
+    pub fn halve_pattern(&self) {
+        let len  = self.pattern_len();
+        let half = len / 2;
+        let res  = *self.seq_resolution.read();
+        let min  = res.pattern_lengths()[0];
+        if half < min {
+            *self.status.write() = t!("✗ Patterns can't be shorter than {} steps", min);
+            return;
+        }
+        self.push_undo(t!("halve"));
+        let mut dropped = 0;
+        for (idx, mut p) in self.song_editor.get_all_patterns().into_iter().enumerate() {
+            dropped += p.keep_first(half, res);
+            self.song_editor.update_pattern_by_idx(idx, p);
+        }
+        for t in self.rec_tracks.write().iter_mut() { t.steps.truncate(half); }
+        if let Some(ab) = self.ab_compare.write().as_mut() { ab.other.keep_first(half, res); }
+        self.set_tool_len(half);
+        self.reload_active_pattern();
+        *self.status.write() = t!("✓ Patterns halved to {} steps — {} hit(s) in the second halves dropped", half, dropped);
+    }
+
+    /// Rotate the pattern being edited by `by` steps (negative is earlier).
+    pub fn shift_pattern(&self, by: isize) {
+        self.push_undo(t!("shift"));
+        let len  = self.pattern_len();
+        let idx  = self.song_editor.active_edit_idx();
+        let main = *self.main_track_index.read();
+        let Some(mut p) = self.song_editor.get_pattern_by_idx(idx) else { return; };
+        p.shift_all(by, len, *self.seq_resolution.read(), main);
+        self.song_editor.update_pattern_by_idx(idx, p);
+        for t in self.rec_tracks.write().iter_mut() { rotate(&mut t.steps, len, by, false); }
+        self.reload_active_pattern();
+        *self.status.write() = t!("✓ Pattern shifted {} step(s) {}", by.unsigned_abs(), if by < 0 { t!("left") } else { t!("right") });
+    }
+
+    /// Rotate one row of the pattern being edited.
+    pub fn shift_lane(&self, lane: Lane, by: isize) {
+        self.push_undo(t!("row shift"));
+        let idx = self.song_editor.active_edit_idx();
+        let Some(mut p) = self.song_editor.get_pattern_by_idx(idx) else { return; };
+        p.shift_lane(lane, by, self.pattern_len(), *self.seq_resolution.read(), *self.main_track_index.read());
+        self.song_editor.update_pattern_by_idx(idx, p);
+        self.reload_active_pattern();
+        *self.status.write() = t!("✓ {} shifted {} step(s) {}", self.lane_label(lane), by.unsigned_abs(), if by < 0 { t!("left") } else { t!("right") });
+    }
+
+    pub(crate) fn set_tool_len(&self, len: usize) {
+        self.pattern_len.store(len, Ordering::Relaxed);
+        let mut cur = self.seq_current_step.write();
+        if *cur >= len { *cur = 0; }
+    }
+
+    /// Rebuild the live pattern from the model, keeping the focused track.
+    pub(crate) fn reload_active_pattern(&self) {
+        let focus = self.waveform_focus.read().clone();
+        self.load_pattern_state(self.song_editor.active_edit_idx());
+        if let WaveformFocus::DrumTrack(t) = focus {
+            let wf = self.drum_tracks.read().get(t).map(|tr| tr.waveform.clone());
+            if let Some(wf) = wf {
+                *self.waveform_focus.write()    = WaveformFocus::DrumTrack(t);
+                *self.waveform_analysis.write() = wf;
+            }
+        }
+    }
+}
@@ -1,10 +1,11 @@
 // src/piano_roll.rs
 use eframe::egui;
-use std::sync::atomic::Ordering;
-use crate::gui::{AppState, NUM_STEPS};
+use crate::bus::AppCommand;
+use crate::gui::AppState;
 use crate::gui::ui::widgets::pad_color;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PianoRollNote {
     pub step:     usize,
     pub semitone: i32,
@@ -34,6 +35,7 @@ pub const SEM_MAX: i32 =  37;
 
 const ROW_H:  f32 = 14.0;
 const KEY_W:  f32 = 60.0;
+/// Width of one 1/16 step; other resolutions scale so a bar keeps its width.
 const STEP_W: f32 = 38.0;
 const HDR_H:  f32 = 22.0;
 
@@ -59,18 +61,22 @@ impl AppState {
         let marks    = self.samples_manager.get_marks_for_sample(&sample_uuid);
         let mark_pos = marks.get(chop_idx).map(|m| m.position * dur_secs).unwrap_or(0.0);
 
-        let seq_playing  = self.seq_playing.load(Ordering::Relaxed);
+        let seq_playing  = self.events.playing();
         let current_step = *self.seq_current_step.read();
 
         let total_rows = (SEM_MAX - SEM_MIN) as usize;
-        let grid_w     = STEP_W * NUM_STEPS as f32;
+        let res        = *self.seq_resolution.read();
+        let n_steps    = self.pattern_len();
+        let per_beat   = res.per_beat();
+        let step_w     = STEP_W * 16.0 / res.steps() as f32;
+        let grid_w     = step_w * n_steps as f32;
         let grid_h     = ROW_H  * total_rows as f32;
         let c4_row_y   = (SEM_MAX - 1) as f32 * ROW_H;
         let init_scroll = (c4_row_y - 150.0).max(0.0);
 
         let mut window_open = true;
 
-        egui::Window::new(format!(
+        egui::Window::new(t!(
             "🎹  {}  ·  Chop {}  @{:.3}s",
             file_name, chop_idx + 1, mark_pos
         ))
@@ -84,23 +90,23 @@ impl AppState {
 
             ui.horizontal(|ui| {
                 let (lbl, col) = if seq_playing {
-                    ("⏹ Stop", egui::Color32::from_rgb(220, 80, 60))
+                    (t!("⏹ Stop"), egui::Color32::from_rgb(220, 80, 60))
                 } else {
-                    ("▶ Play", egui::Color32::from_rgb(60, 200, 100))
+                    (t!("▶ Play"), egui::Color32::from_rgb(60, 200, 100))
                 };
                 if ui.add(egui::Button::new(egui::RichText::new(lbl).color(col))).clicked() {
-                    if seq_playing { self.stop_sequencer(); } else { self.start_sequencer(); }
+                    self.send(AppCommand::TogglePlay);
                 }
 
-                let mut bpm = self.seq_bpm.load(Ordering::Relaxed);
-                ui.label("BPM");
+                let mut bpm = self.events.bpm();
+                ui.label(t!("BPM"));
                 if ui.add(
                     egui::DragValue::new(&mut bpm)
                         .speed(0.5)
                         .clamp_range(40.0..=300.0)
                         .fixed_decimals(0)
                 ).changed() {
-                    self.seq_bpm.store(bpm, Ordering::Relaxed);
+                    self.send(AppCommand::SetBpm { bpm });
                 }
 
                 ui.separator();
@@ -113,7 +119,7 @@ impl AppState {
                         .unwrap_or(0)
                 };
                 ui.label(
-                    egui::RichText::new(format!(
+                    egui::RichText::new(t!(
                         "{} note{}",
                         note_count,
                         if note_count == 1 { "" } else { "s" }
@@ -125,10 +131,11 @@ impl AppState {
                 ui.separator();
 
                 if ui.button(
-                    egui::RichText::new("🗑 Clear all")
+                    egui::RichText::new(t!("🗑 Clear all"))
                         .small()
                         .color(egui::Color32::from_rgb(200, 80, 80))
                 ).clicked() {
+                    self.push_undo(t!("clear notes"));
                     let mut tracks = self.drum_tracks.write();
                     if let Some(t) = tracks.get_mut(track_idx) {
                         if let Some(notes) = t.chop_piano_notes.get_mut(chop_idx) {
@@ -139,14 +146,14 @@ impl AppState {
 
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     ui.label(
-                        egui::RichText::new("Left-click = add  ·  Right-click = remove  ·  C4 = original pitch")
+                        egui::RichText::new(t!("Left-click = add  ·  Right-click = remove  ·  C4 = original pitch"))
                             .small()
                             .color(egui::Color32::from_gray(85)),
                     );
                     if note_count == 0 {
                         ui.separator();
                         ui.label(
-                            egui::RichText::new("⚠ Empty — step buttons used as fallback")
+                            egui::RichText::new(t!("⚠ Empty — step buttons used as fallback"))
                                 .small()
                                 .color(egui::Color32::from_rgb(200, 175, 55)),
                         );
@@ -177,19 +184,19 @@ impl AppState {
 
                     p.rect_filled(outer, 0.0, egui::Color32::from_rgb(13, 13, 19));
 
-                    for step in 0..NUM_STEPS {
-                        let x  = grid_orig.x + step as f32 * STEP_W;
+                    for step in 0..n_steps {
+                        let x  = grid_orig.x + step as f32 * step_w;
                         let hr = egui::Rect::from_min_size(
                             egui::pos2(x, outer.min.y),
-                            egui::vec2(STEP_W - 1.0, HDR_H - 1.0),
+                            egui::vec2(step_w - 1.0, HDR_H - 1.0),
                         );
-                        let grp = step / 4;
+                        let grp = step / per_beat;
                         p.rect_filled(hr, 0.0,
-                            if grp % 2 == 0 { egui::Color32::from_rgb(22, 22, 33) }
+                            if grp.is_multiple_of(2) { egui::Color32::from_rgb(22, 22, 33) }
                             else             { egui::Color32::from_rgb(17, 17, 26) });
-                        if step % 4 == 0 {
+                        if step.is_multiple_of(per_beat) {
                             p.text(hr.center(), egui::Align2::CENTER_CENTER,
-                                format!("{}", step / 4 + 1),
+                                format!("{}", step / per_beat + 1),
                                 egui::FontId::proportional(10.0),
                                 egui::Color32::from_gray(145));
                         } else {
@@ -276,9 +283,9 @@ impl AppState {
                             );
                         }
 
-                        for step in 0..NUM_STEPS {
-                            let x = grid_orig.x + step as f32 * STEP_W;
-                            if step % 4 == 0 {
+                        for step in 0..n_steps {
+                            let x = grid_orig.x + step as f32 * step_w;
+                            if step.is_multiple_of(per_beat) {
                                 p.vline(x,
                                     egui::Rangef::new(y, y + ROW_H),
                                     egui::Stroke::new(0.6, egui::Color32::from_gray(38)));
@@ -287,7 +294,7 @@ impl AppState {
                                 p.rect_filled(
                                     egui::Rect::from_min_size(
                                         egui::pos2(x, y),
-                                        egui::vec2(STEP_W - 1.0, ROW_H - 0.5),
+                                        egui::vec2(step_w - 1.0, ROW_H - 0.5),
                                     ),
                                     0.0,
                                     egui::Color32::from_rgba_unmultiplied(255, 220, 80, 16),
@@ -314,10 +321,10 @@ impl AppState {
                         if note.semitone < SEM_MIN || note.semitone >= SEM_MAX { continue; }
                         let row_i = (SEM_MAX - 1 - note.semitone) as usize;
                         let y     = grid_orig.y + row_i as f32 * ROW_H;
-                        let x     = grid_orig.x + note.step as f32 * STEP_W;
+                        let x     = grid_orig.x + note.step as f32 * step_w;
                         let nr    = egui::Rect::from_min_size(
                             egui::pos2(x + 2.5, y + 2.5),
-                            egui::vec2(STEP_W - 5.0, ROW_H - 5.0),
+                            egui::vec2(step_w - 5.0, ROW_H - 5.0),
                         );
                         let alpha = (note.velocity * 190.0 + 65.0) as u8;
                         p.rect_filled(nr, 2.5,
@@ -333,11 +340,11 @@ impl AppState {
                     }
 
                     if seq_playing {
-                        let sx = grid_orig.x + current_step as f32 * STEP_W;
+                        let sx = grid_orig.x + current_step as f32 * step_w;
                         p.rect_filled(
                             egui::Rect::from_min_size(
                                 egui::pos2(sx, grid_orig.y),
-                                egui::vec2(STEP_W - 1.0, grid_h),
+                                egui::vec2(step_w - 1.0, grid_h),
                             ),
                             0.0,
                             egui::Color32::from_rgba_unmultiplied(255, 220, 80, 10),
@@ -351,18 +358,21 @@ impl AppState {
                     let gresp = ui.interact(
                         grid_rect,
                         egui::Id::new("chpr").with(track_idx).with(chop_idx),
-                        egui::Sense::click(),
+                        egui::Sense::click_and_drag(),
                     );
+                    gresp.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Other,
+                        t!("Chop {} piano roll grid", chop_idx + 1)));
 
                     if gresp.clicked() || gresp.secondary_clicked() {
                         if let Some(pos) = ui.input(|i| i.pointer.interact_pos()) {
                             if grid_rect.contains(pos) {
-                                let step = (((pos.x - grid_orig.x) / STEP_W) as usize)
-                                    .min(NUM_STEPS - 1);
+                                let step = (((pos.x - grid_orig.x) / step_w) as usize)
+                                    .min(n_steps - 1);
                                 let row_i = (((pos.y - grid_orig.y) / ROW_H) as usize)
                                     .min(total_rows - 1);
                                 let semitone = SEM_MAX - 1 - row_i as i32;
 
+                                self.push_undo_gesture(t!("note"));
                                 let mut tracks = self.drum_tracks.write();
                                 if let Some(t) = tracks.get_mut(track_idx) {
                                     if let Some(notes) = t.chop_piano_notes.get_mut(chop_idx) {
@@ -383,6 +393,41 @@ impl AppState {
                         }
                     }
 
+                    // Dragging paints along the note row it began on: notes on where the
+                    // first cell was empty, off where it held one
+                    let cell = |pos: egui::Pos2| (
+                        (((pos.x - grid_orig.x).max(0.0) / step_w) as usize).min(n_steps - 1),
+                        SEM_MAX - 1 - (((pos.y - grid_orig.y).max(0.0) / ROW_H) as usize).min(total_rows - 1) as i32,
+                    );
+                    let paint_id = gresp.id.with("paint");
+                    if gresp.drag_started_by(egui::PointerButton::Primary) {
+                        if let Some(origin) = ui.input(|i| i.pointer.press_origin()) {
+                            let (step, semitone) = cell(origin);
+                            let on = !notes.iter().any(|n| n.step == step && n.semitone == semitone);
+                            ui.data_mut(|d| d.insert_temp(paint_id, (semitone, on)));
+                        }
+                    }
+                    let stroke = ui.data(|d| d.get_temp::<(i32, bool)>(paint_id));
+                    if let (Some((row, on)), true) = (stroke, gresp.dragged_by(egui::PointerButton::Primary)) {
+                        let origin = ui.input(|i| i.pointer.press_origin());
+                        let hover  = ui.input(|i| i.pointer.interact_pos());
+                        // The first frame of a drag paints its start cell too
+                        for pos in [origin.filter(|_| gresp.drag_started()), hover].into_iter().flatten() {
+                            let (step, _) = cell(pos);
+                            self.push_undo_gesture(t!("note"));
+                            let mut tracks = self.drum_tracks.write();
+                            if let Some(notes) = tracks.get_mut(track_idx).and_then(|t| t.chop_piano_notes.get_mut(chop_idx)) {
+                                let at = notes.iter().position(|n| n.step == step && n.semitone == row);
+                                match (at, on) {
+                                    (None, true)     => notes.push(PianoRollNote { step, semitone: row, velocity: 1.0 }),
+                                    (Some(i), false) => { notes.remove(i); }
+                                    _ => {}
+                                }
+                            }
+                        }
+                    }
+                    if gresp.drag_stopped() { ui.data_mut(|d| d.remove::<(i32, bool)>(paint_id)); }
+
                     if let Some(pos) = ui.input(|i| i.pointer.hover_pos()) {
                         if grid_rect.contains(pos) {
                             let row_i    = (((pos.y - grid_orig.y) / ROW_H) as usize)
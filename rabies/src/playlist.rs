@@ -4,8 +4,7 @@ use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use parking_lot::RwLock;
 use eframe::egui;
 
-use crate::pattern::Pattern;
-use crate::gui::NUM_STEPS;
+use crate::pattern::{Pattern, PATTERN_COLORS};
 use crate::audio::{AudioAsset, WaveformAnalysis};
 
 // ── legacy compat ─────────────────────────────────────────────────────────────
@@ -58,6 +57,53 @@ impl SongEditor {
         self.patterns.read().len() - 1
     }
 
+    /// Swap in a whole set of patterns + arrangement (project load).
+    pub fn replace_all(&self, patterns: Vec<Pattern>, mut arrangement: Vec<Vec<Option<usize>>>) {
+        let bars = arrangement.iter().map(|r| r.len()).max().unwrap_or(0).max(Self::DEFAULT_BARS);
+        for row in arrangement.iter_mut() { row.resize(bars, None); }
+        let next = patterns.iter().map(|p| p.id + 1).max().unwrap_or(0);
+        *self.patterns.write()    = patterns;
+        *self.arrangement.write() = arrangement;
+        *self.total_bars.write()  = bars;
+        self.next_id.store(next, Ordering::Relaxed);
+        self.active_edit_idx.store(0, Ordering::Relaxed);
+    }
+
+    /// Put back patterns taken earlier (undo): ones made since are dropped with their
+    /// arrangement rows and blocks, and the edited pattern stays in range.
+    pub fn restore_patterns(&self, patterns: Vec<Pattern>) {
+        let n = patterns.len();
+        *self.patterns.write() = patterns;
+        {
+            let total = *self.total_bars.read();
+            let mut arr = self.arrangement.write();
+            arr.resize(n, vec![None; total]);
+            for cell in arr.iter_mut().flatten() {
+                if cell.is_some_and(|p| p >= n) { *cell = None; }
+            }
+        }
+        let active = self.active_edit_idx.load(Ordering::Relaxed);
+        if active >= n { self.active_edit_idx.store(n.saturating_sub(1), Ordering::Relaxed); }
+    }
+
+    /// Append a pattern from elsewhere: fresh id and colour, name made unique, not yet arranged.
+    pub fn import_pattern(&self, mut p: Pattern) -> usize {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        p.id    = id;
+        p.color = PATTERN_COLORS[id % PATTERN_COLORS.len()];
+        p.fill  = None;
+        let base = p.name.clone();
+        let mut n = 2;
+        while self.patterns.read().iter().any(|q| q.name == p.name) {
+            p.name = format!("{} ({})", base, n);
+            n += 1;
+        }
+        let total = *self.total_bars.read();
+        self.patterns.write().push(p);
+        self.arrangement.write().push(vec![None; total]);
+        self.patterns.read().len() - 1
+    }
+
     pub fn pattern_count(&self) -> usize { self.patterns.read().len() }
 
     pub fn get_pattern_by_idx(&self, idx: usize) -> Option<Pattern> {
@@ -78,6 +124,13 @@ impl SongEditor {
             let mut arr = self.arrangement.write();
             if idx < arr.len() { arr.remove(idx); }
         }
+        for p in self.patterns.write().iter_mut() {
+            p.fill = match p.fill {
+                Some(f) if f == idx => None,
+                Some(f) if f > idx  => Some(f - 1),
+                f => f,
+            };
+        }
         let n = self.patterns.read().len();
         let active = self.active_edit_idx.load(Ordering::Relaxed);
         if active >= n { self.active_edit_idx.store(n.saturating_sub(1), Ordering::Relaxed); }
@@ -172,7 +225,7 @@ impl SongEditor {
         self.current_step_in_bar.store(0, Ordering::Relaxed);
     }
 
-    pub fn advance_song(&self) -> (Vec<usize>, usize, usize) {
+    pub fn advance_song(&self, steps_per_bar: usize) -> (Vec<usize>, usize, usize) {
         if !self.is_playing.load(Ordering::Relaxed) {
             let bar  = self.current_bar.load(Ordering::Relaxed);
             let step = self.current_step_in_bar.load(Ordering::Relaxed);
@@ -181,7 +234,7 @@ impl SongEditor {
 
         let bar       = self.current_bar.load(Ordering::Relaxed);
         let step      = self.current_step_in_bar.load(Ordering::Relaxed);
-        let next_step = (step + 1) % NUM_STEPS;
+        let next_step = (step + 1) % steps_per_bar.max(1);
         self.current_step_in_bar.store(next_step, Ordering::Relaxed);
 
         if next_step == 0 {
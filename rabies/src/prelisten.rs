@@ -0,0 +1,111 @@
+// src/prelisten.rs
+// Browser prelisten with auto-duck. ▶ on a browser entry plays the sample on a voice of
+// its own that skips the master bus, so it is heard as it is. While that voice sounds,
+// everything else — the sequencer, pads and the main playhead — dips by the duck
+// amount and comes back once it ends, so a sample can be auditioned over a running
+// pattern without stopping it. The dip is a short ramp each way so it doesn't click.
+//
+// 🎧 auditions a file that isn't loaded yet: only its first few seconds are decoded,
+// they play on the same voice, and ＋ then loads the file for real as a drum track.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use atomic_float::AtomicF32;
+use crate::adsr::{ADSREnvelope, Voice};
+use crate::audio::AudioAsset;
+use crate::engine::Command;
+use crate::gui::AppState;
+use crate::jobs::JobKind;
+
+/// Default dip under a prelisten, dB.
+pub const DEFAULT_DUCK_DB: f32 = -12.0;
+/// Time the dip takes to go down or come back up.
+const RAMP_SECS: f32 = 0.08;
+/// How much of a file is decoded to audition it.
+pub const AUDITION_SECS: f32 = 6.0;
+
+/// The start of a file that isn't loaded, decoded to audition it.
+pub struct Audition {
+    pub path:  PathBuf,
+    pub asset: Arc<AudioAsset>,
+    /// Decoded and not played yet.
+    fresh:     bool,
+}
+
+/// The mixer's side: ramps the mix toward the duck gain while a prelisten plays.
+pub struct Ducker {
+    amount_db: Arc<AtomicF32>,
+    channels:  usize,
+    coeff:     f32,
+    gain:      f32,
+}
+
+impl Ducker {
+    pub fn new(amount_db: Arc<AtomicF32>, channels: usize, sample_rate: f32) -> Self {
+        let coeff = 1.0 - (-1.0 / (RAMP_SECS * sample_rate.max(1.0))).exp();
+        Self { amount_db, channels: channels.max(1), coeff, gain: 1.0 }
+    }
+
+    /// Scale the interleaved buffer `data` in place; `active` while a prelisten sounds.
+    pub fn process(&mut self, data: &mut [f32], active: bool) {
+        let target = if active { 10f32.powf(self.amount_db.load(Ordering::Relaxed).min(0.0) / 20.0) } else { 1.0 };
+        if self.gain == 1.0 && target == 1.0 { return; }
+        for frame in data.chunks_mut(self.channels) {
+            self.gain += (target - self.gain) * self.coeff;
+            for s in frame.iter_mut() { *s *= self.gain; }
+        }
+        if !active && (1.0 - self.gain) < 1e-4 { self.gain = 1.0; }
+    }
+}
+
+impl AppState {
+    /// Play `asset` from the start on the prelisten voice, cutting off the last one.
+    pub fn prelisten(&self, asset: &AudioAsset) {
+        let Some(out) = self.ensure_engine() else { return; };
+        let mut voices = [Voice::new(Arc::new(asset.pcm.clone()), asset.channels as usize, 0, 1.0,
+            ADSREnvelope::default(), false, asset.sample_rate)];
+        self.attach_channel_maps(&mut voices, out);
+        let [v] = voices;
+        self.send_engine(Command::Prelisten(Some(v)));
+        *self.status.write() = t!("Prelistening: {}", asset.file_name);
+    }
+
+    pub fn stop_prelisten(&self) {
+        self.send_engine(Command::Prelisten(None));
+    }
+
+    /// Pick a file and prelisten its first `AUDITION_SECS`, without loading it.
+    pub fn audition_file(&self) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_title(t!("Audition a file"))
+            .add_filter(t!("Audio"), &["mp3","wav","flac","ogg","m4a","aac"])
+            .pick_file() else { return; };
+        let audio_manager = self.audio_manager.clone();
+        let audition      = self.audition.clone();
+        let name          = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+
+        self.spawn_job(JobKind::Preview, t!("Audition {}", name), move |_| {
+            let asset = audio_manager.load_audio_head(&path.to_string_lossy(), AUDITION_SECS)
+                .map_err(|e| t!("Can't audition {}: {}", name, e))?;
+            *audition.write() = Some(Audition { path, asset, fresh: true });
+            Ok(t!("Prelistening: {}", name))
+        });
+    }
+
+    /// Called every frame: play an audition as soon as it's decoded.
+    pub(crate) fn tick_audition(&self) {
+        let fresh = self.audition.write().as_mut().filter(|a| a.fresh).map(|a| {
+            a.fresh = false;
+            a.asset.clone()
+        });
+        if let Some(asset) = fresh { self.prelisten(&asset); }
+    }
+
+    /// Load the auditioned file as a new drum track, through the trim dialog.
+    pub fn load_audition(&self) {
+        let Some(a) = self.audition.write().take() else { return; };
+        self.stop_prelisten();
+        self.load_drum_track_from(a.path);
+    }
+}
@@ -0,0 +1,542 @@
+// src/project.rs
+use std::collections::{BTreeSet, HashMap};
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use serde::{Deserialize, Serialize};
+use crate::bus::EngineEvent;
+use crate::gui::{AppState, Quantize, StepResolution, MAX_STEPS};
+use crate::jobs::JobKind;
+use crate::metronome::MetronomeSettings;
+use crate::footswitch::Footswitch;
+use crate::midi_learn::MidiBinding;
+use crate::mapping::MappingProfiles;
+use crate::pads::PadLayout;
+use crate::pattern::Pattern;
+use crate::category::RowColors;
+use crate::share::ShareSettings;
+use crate::tempo_track::TempoEvent;
+use crate::pad_bank::PadBank;
+use crate::notes::{ProjectNotes, SampleNotes};
+use crate::mix_snapshot::MixSnapshots;
+
+pub const PROJECT_EXT: &str = "rabies";
+/// Where copied-on-import samples live, relative to the project file.
+pub const SAMPLES_DIR: &str = "samples";
+const PROJECT_VERSION: u32 = 1;
+
+/// Everything written to a `.rabies` project file (JSON). Fields default when
+/// missing so older files keep loading as the format grows.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProjectFile {
+    pub version:        u32,
+    pub bpm:            f32,
+    pub quantize:       Quantize,
+    pub resolution:     StepResolution,
+    /// Steps per pattern; older files ran one bar at `resolution`.
+    pub pattern_len:    Option<usize>,
+    pub pads:           PadLayout,
+    pub pad_banks:      Vec<PadBank>,
+    pub active_pad_bank: Option<usize>,
+    pub mapping:        MappingProfiles,
+    pub patterns:       Vec<Pattern>,
+    pub arrangement:    Vec<Vec<Option<usize>>>,
+    pub tempo_events:   Vec<TempoEvent>,
+    pub active_pattern: usize,
+    /// Copy samples into `samples/` next to the project on import and save.
+    pub copy_samples:   bool,
+    /// Fold new drum tracks to mono on import.
+    #[serde(default)]
+    pub mono_oneshots:  bool,
+    pub row_colors:     RowColors,
+    pub group_rows:     bool,
+    pub share:          ShareSettings,
+    pub metronome:      MetronomeSettings,
+    pub footswitches:   Vec<Footswitch>,
+    pub midi_bindings:  Vec<MidiBinding>,
+    pub notes:          ProjectNotes,
+    /// Source and clearance of the samples in use.
+    pub sample_notes:   Vec<SampleNotes>,
+    pub mix_snapshots:  MixSnapshots,
+    /// Humanize on every row (0-1).
+    pub humanize:       f32,
+}
+
+impl Default for ProjectFile {
+    fn default() -> Self {
+        Self {
+            version:        PROJECT_VERSION,
+            bpm:            120.0,
+            quantize:       Quantize::Off,
+            resolution:     StepResolution::default(),
+            pattern_len:    None,
+            pads:           PadLayout::default(),
+            pad_banks:      Vec::new(),
+            active_pad_bank: None,
+            mapping:        MappingProfiles::default(),
+            patterns:       Vec::new(),
+            arrangement:    Vec::new(),
+            tempo_events:   Vec::new(),
+            active_pattern: 0,
+            copy_samples:   true,
+            mono_oneshots:  false,
+            row_colors:     RowColors::default(),
+            group_rows:     false,
+            share:          ShareSettings::default(),
+            metronome:      MetronomeSettings::default(),
+            footswitches:   Vec::new(),
+            midi_bindings:  Vec::new(),
+            notes:          ProjectNotes::default(),
+            sample_notes:   Vec::new(),
+            mix_snapshots:  MixSnapshots::default(),
+            humanize:       0.0,
+        }
+    }
+}
+
+impl ProjectFile {
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| t!("Read {}: {}", path.display(), e))?;
+        serde_json::from_str(&text).map_err(|e| t!("Parse {}: {}", path.display(), e))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let text = serde_json::to_string_pretty(self).map_err(|e| t!("Serialize: {}", e))?;
+        std::fs::write(path, text).map_err(|e| t!("Write {}: {}", path.display(), e))
+    }
+
+    /// Prepare sample paths for writing into `dir`: copy outside files in when
+    /// `copy_samples` is on, then store everything under `dir` relative to it.
+    /// Returns the files that couldn't be copied.
+    pub fn localize_paths(&mut self, dir: &Path) -> Vec<String> {
+        let mut copied: HashMap<String, String> = HashMap::new();
+        let mut failed = Vec::new();
+        let paths = self.patterns.iter_mut().flat_map(|p| p.tracks.iter_mut().map(|t| &mut t.file_path))
+            .chain(self.pad_banks.iter_mut().flat_map(|b| b.files.iter_mut()))
+            .chain(self.sample_notes.iter_mut().map(|n| &mut n.file));
+        for file_path in paths {
+            if self.copy_samples && Path::new(file_path.as_str()).is_file() {
+                if let Some(done) = copied.get(file_path.as_str()) {
+                    *file_path = done.clone();
+                } else {
+                    match copy_into_project(Path::new(file_path.as_str()), dir) {
+                        Ok(dst) => {
+                            let dst = dst.to_string_lossy().to_string();
+                            copied.insert(file_path.clone(), dst.clone());
+                            *file_path = dst;
+                        }
+                        Err(e) => failed.push(e),
+                    }
+                }
+            }
+            *file_path = relativize(file_path, dir);
+        }
+        failed
+    }
+
+    /// Turn project-relative sample paths back into absolute ones.
+    pub fn resolve_paths(&mut self, dir: &Path) {
+        let paths = self.patterns.iter_mut().flat_map(|p| p.tracks.iter_mut().map(|t| &mut t.file_path))
+            .chain(self.pad_banks.iter_mut().flat_map(|b| b.files.iter_mut()))
+            .chain(self.sample_notes.iter_mut().map(|n| &mut n.file));
+        for file_path in paths {
+            if Path::new(file_path.as_str()).is_relative() && !file_path.is_empty() {
+                *file_path = dir.join(file_path.as_str()).to_string_lossy().to_string();
+            }
+        }
+    }
+}
+
+/// What "Import from project…" does with one pattern of the other file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergeMode { Skip, Pattern, Kit, Marks }
+
+impl MergeMode {
+    pub const ALL: [MergeMode; 4] = [MergeMode::Skip, MergeMode::Pattern, MergeMode::Kit, MergeMode::Marks];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Skip    => t!("Skip"),
+            Self::Pattern => t!("Pattern"),
+            Self::Kit     => t!("Kit"),
+            Self::Marks   => t!("Marks"),
+        }
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self {
+            Self::Skip    => t!("Leave this pattern out"),
+            Self::Pattern => t!("Add as a new pattern (renamed if the name is taken)"),
+            Self::Kit     => t!("Add its tracks to the current pattern, without steps"),
+            Self::Marks   => t!("Add its chop marks to tracks here that use the same sample"),
+        }
+    }
+}
+
+/// Another project opened for merging, with a choice per pattern.
+pub struct MergeImport {
+    pub path:    PathBuf,
+    pub project: ProjectFile,
+    pub modes:   Vec<MergeMode>,
+}
+
+/// `path` relative to `dir` (with `/` separators) when it lives under it, else unchanged.
+fn relativize(path: &str, dir: &Path) -> String {
+    match Path::new(path).strip_prefix(dir) {
+        Ok(rel) => rel.components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+            .join("/"),
+        Err(_) => path.to_string(),
+    }
+}
+
+/// Copy `src` into `<project_dir>/samples/`. Files already inside the project are
+/// left alone; a same-named file with the same bytes is the same sample, and a
+/// different one of that name goes in as `name (2).wav`.
+pub fn copy_into_project(src: &Path, project_dir: &Path) -> Result<PathBuf, String> {
+    if src.starts_with(project_dir) { return Ok(src.to_path_buf()); }
+    let name = src.file_name().ok_or_else(|| t!("No file name: {}", src.display()))?;
+    let dir  = project_dir.join(SAMPLES_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| t!("Create {}: {}", dir.display(), e))?;
+    let src_len = std::fs::metadata(src).map_err(|e| t!("Read {}: {}", src.display(), e))?.len();
+
+    let stem = Path::new(name).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let ext  = Path::new(name).extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+    for n in 1.. {
+        let dst = if n == 1 { dir.join(name) } else { dir.join(format!("{} ({}){}", stem, n, ext)) };
+        match std::fs::metadata(&dst) {
+            Ok(m) if m.len() == src_len && same_contents(src, &dst) => return Ok(dst),
+            Ok(_) => continue,
+            Err(_) => {
+                std::fs::copy(src, &dst).map_err(|e| t!("Copy {}: {}", src.display(), e))?;
+                return Ok(dst);
+            }
+        }
+    }
+    unreachable!()
+}
+
+/// `a` and `b` hold the same bytes; unreadable counts as different.
+fn same_contents(a: &Path, b: &Path) -> bool {
+    let (Ok(a), Ok(b)) = (std::fs::File::open(a), std::fs::File::open(b)) else { return false; };
+    let (mut a, mut b) = (std::io::BufReader::new(a), std::io::BufReader::new(b));
+    loop {
+        let (Ok(x), Ok(y)) = (a.fill_buf(), b.fill_buf()) else { return false; };
+        let n = x.len().min(y.len());
+        if n == 0 { return x.len() == y.len(); }
+        if x[..n] != y[..n] { return false; }
+        a.consume(n);
+        b.consume(n);
+    }
+}
+
+/// Copy-on-import: the path a picked file is used from. With a project folder to copy
+/// into, that's the project's copy, so the track (and project) point at it; otherwise,
+/// or when the copy fails, the file itself.
+pub fn import_path(src: &Path, import_dir: Option<&Path>) -> String {
+    match import_dir.map(|d| copy_into_project(src, d)) {
+        Some(Ok(dst)) => dst.to_string_lossy().to_string(),
+        Some(Err(e))  => { eprintln!("[import] {}", e); src.to_string_lossy().to_string() }
+        None          => src.to_string_lossy().to_string(),
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+//  Project save / open
+// ═══════════════════════════════════════════════════════════════════════════════
+impl AppState {
+    /// Snapshot the whole session into a `ProjectFile`.
+    pub fn project_snapshot(&self) -> ProjectFile {
+        self.save_current_pattern_state();
+        // Notes on samples no longer used stay behind
+        let in_use = self.project_sample_files();
+        let sample_notes = self.sample_notes.read().iter().filter(|n| in_use.contains(&n.file)).cloned().collect();
+        ProjectFile {
+            version:        PROJECT_VERSION,
+            bpm:            self.events.bpm(),
+            quantize:       *self.quantize.read(),
+            resolution:     *self.seq_resolution.read(),
+            pattern_len:    Some(self.pattern_len()),
+            pads:           self.pad_layout.read().clone(),
+            pad_banks:      self.pad_banks.read().clone(),
+            active_pad_bank: *self.active_pad_bank.read(),
+            mapping:        self.mapping.read().clone(),
+            patterns:       self.song_editor.get_all_patterns(),
+            arrangement:    self.song_editor.get_arrangement_snapshot(),
+            tempo_events:   self.tempo_events.read().clone(),
+            active_pattern: self.song_editor.active_edit_idx(),
+            copy_samples:   self.copy_on_import.load(Ordering::Relaxed),
+            mono_oneshots:  self.mono_oneshots.load(Ordering::Relaxed),
+            row_colors:     *self.row_colors.read(),
+            group_rows:     self.group_rows.load(Ordering::Relaxed),
+            share:          self.share.read().clone(),
+            metronome:      self.metronome.lock().settings.clone(),
+            footswitches:   self.footswitches.read().clone(),
+            midi_bindings:  self.midi_bindings.read().clone(),
+            notes:          self.project_notes.read().clone(),
+            sample_notes,
+            mix_snapshots:  self.mix_snapshots.read().clone(),
+            humanize:       self.humanize.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Folder imports get copied into, when copy-on-import is on and the project has been saved.
+    pub fn import_dir(&self) -> Option<PathBuf> {
+        if !self.copy_on_import.load(Ordering::Relaxed) { return None; }
+        self.project_path.read().as_ref().and_then(|p| p.parent().map(Path::to_path_buf))
+    }
+
+    pub fn save_project(&self) {
+        let path = self.project_path.read().clone();
+        match path {
+            Some(p) => self.save_project_to(&p),
+            None    => self.save_project_as(),
+        }
+    }
+
+    pub fn save_project_as(&self) {
+        let Some(mut path) = rfd::FileDialog::new()
+            .add_filter(t!("Rabies project"), &[PROJECT_EXT])
+            .set_title(t!("Save Project"))
+            .save_file()
+        else { return; };
+        if path.extension().is_none() { path.set_extension(PROJECT_EXT); }
+        self.save_project_to(&path);
+    }
+
+    pub fn save_project_to(&self, path: &Path) {
+        let mut project = self.project_snapshot();
+        let unsaved = project.patterns.iter()
+            .flat_map(|p| p.tracks.iter())
+            .filter(|t| !Path::new(&t.file_path).is_file())
+            .count();
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let not_copied = project.localize_paths(dir);
+        match project.write(path) {
+            Ok(()) => {
+                *self.project_path.write() = Some(path.to_path_buf());
+                *self.status.write() = if unsaved > 0 {
+                    t!("✓ Saved {} ({} recorded track(s) have no file and won't reload)", path.display(), unsaved)
+                } else if !not_copied.is_empty() {
+                    t!("✓ Saved {} — {} sample(s) couldn't be copied: {}", path.display(), not_copied.len(), not_copied[0])
+                } else {
+                    t!("✓ Saved {}", path.display())
+                };
+                if self.share.read().on_save { self.share_preview(); }
+            }
+            Err(e) => *self.status.write() = format!("✗ {}", e),
+        }
+    }
+
+    pub fn open_project(&self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(t!("Rabies project"), &[PROJECT_EXT])
+            .set_title(t!("Open Project"))
+            .pick_file()
+        else { return; };
+        self.open_project_from(&path);
+    }
+
+    pub fn open_project_from(&self, path: &Path) {
+        let mut project = match ProjectFile::read(path) {
+            Ok(p)  => p,
+            Err(e) => { *self.status.write() = format!("✗ {}", e); return; }
+        };
+        project.resolve_paths(path.parent().unwrap_or(Path::new(".")));
+        self.stop_playback();
+        self.stop_sequencer();
+
+        // Decode every referenced sample into the pool before rebuilding tracks
+        let files: BTreeSet<String> = project.patterns.iter()
+            .flat_map(|p| p.tracks.iter().map(|t| t.file_path.clone()))
+            .chain(project.pad_banks.iter().flat_map(|b| b.files.iter().cloned()))
+            .collect();
+        let missing = self.pool_samples(&files);
+
+        self.apply_project(project);
+        *self.project_path.write() = Some(path.to_path_buf());
+        *self.status.write() = if missing == 0 {
+            t!("✓ Opened {}", path.display())
+        } else {
+            t!("✓ Opened {} — {} sample(s) missing", path.display(), missing)
+        };
+    }
+
+    /// Decode any of `files` not yet in the pool. Returns how many couldn't be loaded.
+    pub fn pool_samples(&self, files: &BTreeSet<String>) -> usize {
+        let mut missing = 0;
+        for file in files {
+            if self.asset_pool.read().contains_key(file) { continue; }
+            match self.audio_manager.load_audio(file) {
+                Ok(asset) => self.pool_asset(file, asset),
+                Err(_)    => missing += 1,
+            }
+        }
+        missing
+    }
+
+    /// Replace the session with `project`. Samples must already be pooled.
+    pub fn apply_project(&self, project: ProjectFile) {
+        *self.ab_compare.write()      = None;
+        *self.pending_pattern.write() = None;
+        *self.history.lock()          = Default::default();
+        self.events.emit(EngineEvent::Bpm { bpm: project.bpm });
+        *self.quantize.write()   = project.quantize;
+        *self.seq_resolution.write() = project.resolution;
+        self.pattern_len.store(project.pattern_len.unwrap_or(project.resolution.steps()).clamp(1, MAX_STEPS), Ordering::Relaxed);
+        *self.pad_layout.write() = project.pads;
+        *self.active_pad_bank.write() = project.active_pad_bank.filter(|&b| b < project.pad_banks.len());
+        *self.pad_banks.write() = project.pad_banks;
+        *self.mapping.write()    = project.mapping.sanitized();
+        self.copy_on_import.store(project.copy_samples, Ordering::Relaxed);
+        self.mono_oneshots.store(project.mono_oneshots, Ordering::Relaxed);
+        *self.row_colors.write() = project.row_colors;
+        self.group_rows.store(project.group_rows, Ordering::Relaxed);
+        *self.share.write() = project.share;
+        let click_sample = project.metronome.sample_path.clone();
+        self.metronome.lock().settings = project.metronome;
+        if let Some(path) = click_sample { self.load_metronome_sample(&path); }
+        *self.footswitches.write() = project.footswitches;
+        *self.midi_bindings.write() = project.midi_bindings;
+        *self.project_notes.write() = project.notes;
+        *self.sample_notes.write()  = project.sample_notes;
+        *self.mix_snapshots.write() = project.mix_snapshots;
+        *self.mix_fade.write()      = None;
+        self.humanize.store(project.humanize, Ordering::Relaxed);
+
+        let mut patterns = project.patterns;
+        if patterns.is_empty() { patterns.push(Pattern::empty(0)); }
+        let n = patterns.len();
+        let mut tempo_events = project.tempo_events;
+        tempo_events.sort_by_key(|e| e.bar);
+        *self.tempo_events.write() = tempo_events;
+        let mut arrangement = project.arrangement;
+        arrangement.resize(n, Vec::new());
+        self.song_editor.replace_all(patterns, arrangement);
+        self.load_pattern_state(project.active_pattern.min(n - 1));
+    }
+
+    /// Pick another project to merge from; the choices are made in the merge window.
+    pub fn begin_merge_import(&self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(t!("Rabies project"), &[PROJECT_EXT])
+            .set_title(t!("Import from Project"))
+            .pick_file()
+        else { return; };
+        let mut project = match ProjectFile::read(&path) {
+            Ok(p)  => p,
+            Err(e) => { *self.status.write() = format!("✗ {}", e); return; }
+        };
+        project.resolve_paths(path.parent().unwrap_or(Path::new(".")));
+        if project.patterns.is_empty() {
+            *self.status.write() = t!("✗ {} has no patterns", path.display());
+            return;
+        }
+        let modes = vec![MergeMode::Pattern; project.patterns.len()];
+        *self.merge_import.write() = Some(MergeImport { path, project, modes });
+    }
+
+    /// Bring the chosen parts of the pending merge into the session. The samples they
+    /// need decode in a load job; `tick_merge_import` then merges them in.
+    pub fn apply_merge_import(&self) {
+        let Some(merge) = self.merge_import.write().take() else { return; };
+        if merge.modes.iter().all(|m| *m == MergeMode::Skip) {
+            *self.status.write() = t!("Nothing selected to import").to_string();
+            return;
+        }
+        let files: BTreeSet<String> = merge.project.patterns.iter().zip(&merge.modes)
+            .filter(|(_, m)| !matches!(m, MergeMode::Skip | MergeMode::Marks))
+            .flat_map(|(p, _)| p.tracks.iter().map(|t| t.file_path.clone()))
+            .collect();
+        let audio_manager = self.audio_manager.clone();
+        let asset_pool    = self.asset_pool.clone();
+        let ready         = self.merge_ready.clone();
+        let name = merge.path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+
+        // Not `spawn_job`: the merge, a frame later, has the last word on the status line
+        self.jobs.spawn(JobKind::Load, t!("Import from {}", name), move |job| {
+            let mut missing = 0;
+            for (i, file) in files.iter().enumerate() {
+                if job.cancelled() { return Err(t!("Cancelled").to_string()); }
+                if !asset_pool.read().contains_key(file) {
+                    match audio_manager.load_audio(file) {
+                        Ok(asset) => { asset_pool.write().insert(file.clone(), asset); }
+                        Err(_)    => missing += 1,
+                    }
+                }
+                job.progress(i + 1, files.len());
+            }
+            *ready.lock() = Some((merge, missing));
+            Ok(t!("✓ Decoded {} sample(s)", files.len()))
+        });
+    }
+
+    /// Called every frame: merge an import whose samples have been decoded. Imported
+    /// patterns get fresh ids and never touch the arrangement.
+    pub(crate) fn tick_merge_import(&self) {
+        let Some((merge, missing)) = self.merge_ready.lock().take() else { return; };
+        self.save_current_pattern_state();
+
+        let mut picked: Vec<(MergeMode, Pattern)> = merge.project.patterns.into_iter()
+            .zip(merge.modes)
+            .filter(|(_, m)| *m != MergeMode::Skip)
+            .map(|(p, m)| (m, p))
+            .collect();
+
+        // Their steps were written at their resolution
+        let (from, to) = (merge.project.resolution, *self.seq_resolution.read());
+        if from != to {
+            for (_, p) in picked.iter_mut() { p.remap_steps(from, to); }
+        }
+
+        let active = self.song_editor.active_edit_idx();
+        let (mut patterns, mut tracks, mut marks) = (0, 0, 0);
+        for (mode, p) in picked {
+            match mode {
+                MergeMode::Pattern => {
+                    self.song_editor.import_pattern(p);
+                    patterns += 1;
+                }
+                MergeMode::Kit => {
+                    let Some(mut current) = self.song_editor.get_pattern_by_idx(active) else { continue; };
+                    for mut t in p.tracks {
+                        t.clear_steps();
+                        current.tracks.push(t);
+                        tracks += 1;
+                    }
+                    self.song_editor.update_pattern_by_idx(active, current);
+                }
+                MergeMode::Marks => {
+                    for idx in 0..self.song_editor.pattern_count() {
+                        let Some(mut current) = self.song_editor.get_pattern_by_idx(idx) else { continue; };
+                        let mut added = 0;
+                        for t in current.tracks.iter_mut() {
+                            for src in p.tracks.iter() {
+                                if src.same_sample(t) { added += t.merge_marks(&src.marks); }
+                            }
+                        }
+                        if added > 0 {
+                            self.song_editor.update_pattern_by_idx(idx, current);
+                            marks += added;
+                        }
+                    }
+                }
+                MergeMode::Skip => {}
+            }
+        }
+        self.load_pattern_state(active);
+
+        let name = merge.path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let mut msg = t!("✓ Imported from {}: {} pattern(s), {} track(s), {} mark(s)", name, patterns, tracks, marks);
+        if missing > 0 { msg.push_str(&format!(" — {} sample(s) missing", missing)); }
+        *self.status.write() = msg;
+    }
+
+    pub fn project_name(&self) -> Option<String> {
+        self.project_path.read().as_ref()
+            .and_then(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+    }
+}
+
@@ -0,0 +1,237 @@
+// src/punch.rs
+// Punch-in re-recording: play a drum track from a little before one marker, record the
+// input between that marker and another, and splice the new audio over just that span
+// (crossfaded at both ends) instead of redoing the whole take.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Instant;
+use crate::audio::AudioAsset;
+use crate::channels::ChannelMatrix;
+use crate::gui::AppState;
+
+/// Crossfade at the punch-in and punch-out points.
+const PUNCH_FADE_SECS: f32 = 0.010;
+/// Takes shorter than this share of the span were stopped early and are thrown away.
+const MIN_COVERAGE: f32 = 0.9;
+
+/// A punch in progress on one drum track.
+#[derive(Clone, Debug)]
+pub struct PunchIn {
+    pub track_idx: usize,
+    /// Normalised marker positions bounding the re-recorded span.
+    pub from: f32,
+    pub to:   f32,
+    /// Seconds of playback before `from`.
+    pub preroll_secs: f64,
+}
+
+/// Where the punch-in point falls in the take, in seconds from its first frame: the
+/// pre-roll after playback was heard, measured from when the first frame was captured.
+/// Negative when capture only began after the punch-in point.
+pub fn take_offset(captured: Instant, heard: Instant, preroll_secs: f64) -> f64 {
+    let lead = match heard.checked_duration_since(captured) {
+        Some(d) => d.as_secs_f64(),
+        None    => -captured.duration_since(heard).as_secs_f64(),
+    };
+    lead + preroll_secs
+}
+
+/// Replace frames `from..to` of `pcm` with `take` (same channel count and rate), fading
+/// across `fade` frames at each end. A short take hands back to the original early.
+pub fn splice(pcm: &mut [f32], channels: usize, from: usize, to: usize, take: &[f32], fade: usize) {
+    let ch   = channels.max(1);
+    let span = to.saturating_sub(from).min(take.len() / ch).min((pcm.len() / ch).saturating_sub(from));
+    let fade = fade.clamp(1, (span / 2).max(1));
+    for i in 0..span {
+        let g = ((i + 1) as f32 / fade as f32).min((span - i) as f32 / fade as f32).min(1.0);
+        for c in 0..ch {
+            let o = &mut pcm[(from + i) * ch + c];
+            *o += (take[i * ch + c] - *o) * g;
+        }
+    }
+}
+
+/// `take` moved onto `ch` channels at `rate`, by linear interpolation.
+fn conform(take: &AudioAsset, ch: usize, rate: u32, matrix: &ChannelMatrix) -> Vec<f32> {
+    let tc     = (take.channels as usize).max(1);
+    let frames = take.pcm.len() / tc;
+    let step   = take.sample_rate as f64 / rate.max(1) as f64;
+    let n      = (frames as f64 / step) as usize;
+    let (mut src, mut dst) = (vec![0.0; tc], vec![0.0; ch]);
+    let mut out = Vec::with_capacity(n * ch);
+    for k in 0..n {
+        let fp = k as f64 * step;
+        let i0 = (fp as usize).min(frames.saturating_sub(1));
+        let i1 = (i0 + 1).min(frames.saturating_sub(1));
+        let t  = (fp - i0 as f64) as f32;
+        for (c, s) in src.iter_mut().enumerate() {
+            let (a, b) = (take.pcm[i0 * tc + c], take.pcm[i1 * tc + c]);
+            *s = a + t * (b - a);
+        }
+        matrix.apply(&src, &mut dst);
+        out.extend_from_slice(&dst);
+    }
+    out
+}
+
+impl AppState {
+    /// Start a punch on `track_idx` between the selected from/to markers, recording from
+    /// the chosen punch input. Playback starts up to a bar early as pre-roll.
+    pub fn start_punch_in(&self, track_idx: usize) {
+        if self.audio_blocked() { return; }
+        if self.rec_manager.is_recording() {
+            *self.status.write() = t!("✗ Already recording — stop that first").to_string();
+            return;
+        }
+        let Some((asset, uuid)) = self.drum_tracks.read().get(track_idx).map(|t| (t.asset.clone(), t.sample_uuid)) else { return; };
+        let marks = self.samples_manager.get_marks_for_sample(&uuid);
+        let from_mark = *self.selected_from_marker.read();
+        let to_mark   = *self.selected_to_marker.read();
+        let (Some(from), Some(to)) = (from_mark.and_then(|i| marks.get(i)), to_mark.and_then(|i| marks.get(i))) else {
+            *self.status.write() = t!("✗ Pick the punch-in and punch-out markers first").to_string();
+            return;
+        };
+        let (from, to) = (from.position.min(to.position), from.position.max(to.position));
+        if to - from <= 0.0 {
+            *self.status.write() = t!("✗ Punch markers must be different").to_string();
+            return;
+        }
+        let label = self.punch_device.read().clone();
+        let Some(dev) = label.and_then(|l| self.input_devices.read().iter().find(|d| d.label == l).cloned()) else {
+            *self.status.write() = t!("✗ Pick an input for the punch first").to_string();
+            return;
+        };
+
+        let dur      = asset.frames as f32 / asset.sample_rate.max(1) as f32;
+        let bar_secs = 4.0 * 60.0 / self.events.bpm().max(1.0);
+        let start    = (from - bar_secs / dur.max(1e-6)).max(0.0);
+        if let Err(e) = self.rec_manager.start(&dev) {
+            *self.status.write() = t!("Record error: {}", e);
+            return;
+        }
+        // The take holds exactly the span: pre-roll skipped, cut at the out point. The skip
+        // is redone from the stream timestamps once both sides have reported.
+        let preroll = (from - start) * dur;
+        self.rec_manager.set_skip_secs(preroll);
+        self.rec_manager.set_length_secs(Some((to - from) * dur));
+        *self.punch.write() = Some(PunchIn { track_idx, from, to, preroll_secs: preroll as f64 });
+        *self.playback_heard_at.lock() = None;
+
+        // Source time has to match wall time while recording
+        self.playback_speed.store(1.0, Ordering::Relaxed);
+        self.playback_position.store(start, Ordering::Relaxed);
+        self.playback_sample_index.store((start as f64 * asset.pcm.len() as f64) as u64, Ordering::Relaxed);
+        self.start_playback(asset);
+        self.playback_stop_target.store(to, Ordering::Relaxed);
+        self.playback_loop_from.store(-1.0, Ordering::Relaxed);
+        *self.status.write() = t!("🔴 Punch {:.2}s → {:.2}s (pre-roll {:.2}s)", from * dur, to * dur, (from - start) * dur);
+    }
+
+    /// Finishes the punch once the playhead has stopped at the out point.
+    pub fn tick_punch_in(&self) {
+        if self.punch.read().is_none() || self.is_playing.load(Ordering::Relaxed) { return; }
+        let Some(punch) = self.punch.write().take() else { return; };
+        self.rec_manager.stop();
+        // Line the take up with what was heard: input and output latency both count
+        let mut late = 0.0;
+        if let (Some(captured), Some(heard)) = (self.rec_manager.first_frame_at(), *self.playback_heard_at.lock()) {
+            let offset = take_offset(captured, heard, punch.preroll_secs);
+            if offset >= 0.0 { self.rec_manager.set_skip_secs(offset as f32); } else { self.rec_manager.set_skip_secs(0.0); late = -offset; }
+        }
+        let take = self.rec_manager.take_asset("punch".to_string());
+        let reached = self.playback_position.load(Ordering::Relaxed) >= punch.to - 1e-4;
+        let (Some(take), true) = (take, reached) else {
+            *self.status.write() = t!("✗ Punch cancelled — the track is unchanged").to_string();
+            return;
+        };
+        *self.status.write() = match self.apply_punch(&punch, &take, late) {
+            Ok(msg) => msg,
+            Err(e)  => format!("✗ {}", e),
+        };
+    }
+
+    pub fn cancel_punch_in(&self) {
+        if self.punch.write().take().is_some() {
+            self.rec_manager.stop();
+            self.stop_playback();
+            *self.status.write() = t!("Punch cancelled").to_string();
+        }
+    }
+
+    /// Splice `take` in; `late` seconds of the span went by before it began.
+    fn apply_punch(&self, punch: &PunchIn, take: &AudioAsset, late: f64) -> Result<String, String> {
+        let (old, old_path) = self.drum_tracks.read().get(punch.track_idx)
+            .map(|t| (t.asset.clone(), t.file_path.clone()))
+            .ok_or(t!("The punched track is gone"))?;
+        let ch     = (old.channels as usize).max(1);
+        let frames = old.pcm.len() / ch;
+        let to     = (punch.to as f64 * frames as f64).round() as usize;
+        let from   = ((punch.from as f64 * frames as f64 + late * old.sample_rate as f64).round() as usize).min(to);
+        let matrix = ChannelMatrix::new(take.channels as usize, ch, &self.downmix.read());
+        let new    = conform(take, ch, old.sample_rate, &matrix);
+        if ((new.len() / ch) as f32) < (to - from) as f32 * MIN_COVERAGE {
+            return Err(t!("Punch take came up short — the track is unchanged").to_string());
+        }
+
+        let mut pcm = old.pcm.clone();
+        splice(&mut pcm, ch, from, to, &new, (PUNCH_FADE_SECS * old.sample_rate as f32) as usize);
+        // Same UUID and name, so markers, pads and pattern rows keep pointing at it
+        let asset = Arc::new(AudioAsset {
+            pcm,
+            sample_rate: old.sample_rate,
+            channels:    old.channels,
+            frames:      old.frames,
+            file_name:   old.file_name.clone(),
+            sample_uuid: old.sample_uuid,
+            tags:        old.tags.clone(),
+        });
+
+        let saved = self.import_dir().map(|d| {
+            let dir  = d.join(crate::project::SAMPLES_DIR);
+            let stem = Path::new(&asset.file_name).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            let path = dir.join(format!("{} (punch).wav", stem));
+            std::fs::create_dir_all(&dir).map_err(|e| e.to_string())
+                .and_then(|()| crate::render::write_float_wav(&path, &asset.pcm, asset.sample_rate, asset.channels))
+                .map(|()| path.to_string_lossy().to_string())
+        });
+        let (file_path, note) = match saved {
+            Some(Ok(p))  => (Some(p), String::new()),
+            Some(Err(e)) => (old_path, t!(" — not saved to disk: {}", e)),
+            None         => (old_path, t!(" — kept in memory until the project is saved").to_string()),
+        };
+        self.pool_asset(file_path.as_deref().unwrap_or(&asset.file_name), asset.clone());
+        let waveform = self.audio_manager.analyze_waveform(&asset, 400);
+        if let Some(t) = self.drum_tracks.write().get_mut(punch.track_idx) {
+            t.asset     = asset.clone();
+            t.waveform  = Some(waveform.clone());
+            t.file_path = file_path;
+        }
+        if matches!(&*self.waveform_focus.read(), crate::gui::WaveformFocus::DrumTrack(i) if *i == punch.track_idx) {
+            *self.waveform_analysis.write() = Some(waveform);
+        }
+        let secs = (to - from) as f32 / asset.sample_rate as f32;
+        Ok(t!("✓ Punched {:.2}s into {}{}", secs, asset.file_name, note))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn offset_counts_the_wait_for_playback() {
+        let captured = Instant::now();
+        let heard    = captured + Duration::from_millis(120);
+        assert!((take_offset(captured, heard, 2.0) - 2.12).abs() < 1e-9);
+    }
+
+    #[test]
+    fn offset_goes_negative_when_capture_starts_late() {
+        let heard    = Instant::now();
+        let captured = heard + Duration::from_millis(2500);
+        assert!((take_offset(captured, heard, 2.0) + 0.5).abs() < 1e-9);
+    }
+}
@@ -1,10 +1,11 @@
 // src/recording.rs
 use std::sync::{Arc, Mutex};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 use parking_lot::RwLock;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use crate::audio::AudioAsset;
-use crate::gui::NUM_STEPS;
+use crate::audio::{AudioAsset, SampleTags};
 use crate::adsr::ADSREnvelope;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -22,28 +23,50 @@ pub struct InputDevice {
     pub label: String,
 }
 
+/// What a finished loop take turns into.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LoopTarget {
+    DrumTrack,
+    /// Drum track pre-chopped on every beat.
+    ChopSet,
+}
+
+/// An armed or running sequencer-synced loop take.
+#[derive(Clone, Debug)]
+pub struct LoopRecording {
+    pub track_idx: usize,
+    pub bars_left: u32,
+    /// Set on the first bar line after arming.
+    pub started:   bool,
+}
+
+#[derive(Clone)]
 pub struct RecordingTrack {
     pub device_label: Option<String>,
     pub state:        RecordState,
     pub asset:        Option<Arc<AudioAsset>>,
-    pub steps:        [bool; NUM_STEPS],
+    pub steps:        Vec<bool>,
     pub adsr:         ADSREnvelope,
     pub adsr_enabled: bool,
     pub muted:        bool,
     pub take_number:  u32,
+    pub loop_bars:    u32,
+    pub loop_target:  LoopTarget,
 }
 
 impl RecordingTrack {
-    pub fn new() -> Self {
+    pub fn new(pattern_len: usize) -> Self {
         Self {
             device_label: None,
             state:        RecordState::Idle,
             asset:        None,
-            steps:        [false; NUM_STEPS],
+            steps:        vec![false; pattern_len],
             adsr:         ADSREnvelope::default(),
             adsr_enabled: false,
             muted:        false,
             take_number:  1,
+            loop_bars:    1,
+            loop_target:  LoopTarget::DrumTrack,
         }
     }
 
@@ -55,7 +78,7 @@ impl RecordingTrack {
             let dev = lbl.split(": ").nth(1).unwrap_or(lbl);
             if dev.len() > 15 { format!("{}…", &dev[..13]) } else { dev.to_string() }
         } else {
-            "No input".to_string()
+            t!("No input").to_string()
         }
     }
 
@@ -71,6 +94,12 @@ pub struct RecordingManager {
     pub sample_rate:  Arc<RwLock<u32>>,
     pub channels:     Arc<RwLock<u16>>,
     pub peak:         Arc<RwLock<f32>>,
+    /// Seconds dropped from the head of the take (quantized record start).
+    pub skip_secs:    Arc<RwLock<f32>>,
+    /// Take length cap in seconds (loop recording), applied after the skip.
+    pub length_secs:  Arc<RwLock<Option<f32>>>,
+    /// When the take's first frame was captured, as the input stream reports it.
+    pub first_frame_at: Arc<Mutex<Option<Instant>>>,
 }
 
 impl RecordingManager {
@@ -82,6 +111,9 @@ impl RecordingManager {
             sample_rate:  Arc::new(RwLock::new(44100)),
             channels:     Arc::new(RwLock::new(1)),
             peak:         Arc::new(RwLock::new(0.0)),
+            skip_secs:    Arc::new(RwLock::new(0.0)),
+            length_secs:  Arc::new(RwLock::new(None)),
+            first_frame_at: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -110,74 +142,34 @@ impl RecordingManager {
     pub fn start(&self, dev: &InputDevice) -> Result<(), String> {
         self.stop();
 
-        let host = cpal::host_from_id(dev.host_id)
-            .map_err(|e| format!("Host error: {:?}", e))?;
-
-        let device = host.input_devices()
-            .map_err(|e| format!("Device list: {}", e))?
-            .find(|d| d.name().map(|n| n == dev.device_name).unwrap_or(false))
-            .ok_or_else(|| format!("Device '{}' not found (try Refresh)", dev.device_name))?;
-
-        let cfg = device.default_input_config()
-            .map_err(|e| format!("Input config: {}", e))?;
-
+        let (device, cfg) = open_input(dev)?;
         *self.sample_rate.write() = cfg.sample_rate().0;
         *self.channels.write()    = cfg.channels();
 
         self.buffer.lock().unwrap().clear();
+        *self.skip_secs.write() = 0.0;
+        *self.length_secs.write() = None;
+        *self.first_frame_at.lock().unwrap() = None;
         self.is_recording.store(true, Ordering::Relaxed);
 
-        let scfg: cpal::StreamConfig = cfg.clone().into();
-
         let stream = {
-            let buf  = self.buffer.clone();
-            let rec  = self.is_recording.clone();
-            let peak = self.peak.clone();
-
-            match cfg.sample_format() {
-                cpal::SampleFormat::F32 => device.build_input_stream(
-                    &scfg,
-                    move |data: &[f32], _| {
-                        if !rec.load(Ordering::Relaxed) { return; }
-                        let p = data.iter().map(|s| s.abs()).fold(0.0f32, f32::max);
-                        *peak.write() = p;
-                        buf.lock().unwrap().extend_from_slice(data);
-                    },
-                    |e| eprintln!("Rec stream error: {}", e), None,
-                ),
-                cpal::SampleFormat::I16 => device.build_input_stream(
-                    &scfg,
-                    {
-                        let buf2 = buf.clone(); let rec2 = rec.clone(); let peak2 = peak.clone();
-                        move |data: &[i16], _| {
-                            if !rec2.load(Ordering::Relaxed) { return; }
-                            let s: Vec<f32> = data.iter().map(|&x| x as f32 / 32767.0).collect();
-                            let p = s.iter().map(|x| x.abs()).fold(0.0f32, f32::max);
-                            *peak2.write() = p;
-                            buf2.lock().unwrap().extend_from_slice(&s);
-                        }
-                    },
-                    |e| eprintln!("Rec stream error: {}", e), None,
-                ),
-                cpal::SampleFormat::U16 => device.build_input_stream(
-                    &scfg,
-                    {
-                        let buf3 = buf.clone(); let rec3 = rec.clone(); let peak3 = peak.clone();
-                        move |data: &[u16], _| {
-                            if !rec3.load(Ordering::Relaxed) { return; }
-                            let s: Vec<f32> = data.iter().map(|&x| x as f32 / 32767.5 - 1.0).collect();
-                            let p = s.iter().map(|x| x.abs()).fold(0.0f32, f32::max);
-                            *peak3.write() = p;
-                            buf3.lock().unwrap().extend_from_slice(&s);
-                        }
-                    },
-                    |e| eprintln!("Rec stream error: {}", e), None,
-                ),
-                fmt => return Err(format!("Unsupported input sample format: {:?}", fmt)),
-            }
-        }.map_err(|e| format!("Build input stream: {}", e))?;
+            let buf   = self.buffer.clone();
+            let rec   = self.is_recording.clone();
+            let peak  = self.peak.clone();
+            let first = self.first_frame_at.clone();
+            build_input_stream(&device, &cfg, move |data: &[f32], latency| {
+                if !rec.load(Ordering::Relaxed) { return; }
+                first.lock().unwrap().get_or_insert_with(|| {
+                    let now = Instant::now();
+                    now.checked_sub(latency).unwrap_or(now)
+                });
+                let p = data.iter().map(|s| s.abs()).fold(0.0f32, f32::max);
+                *peak.write() = p;
+                buf.lock().unwrap().extend_from_slice(data);
+            })?
+        };
 
-        stream.play().map_err(|e| format!("Start stream: {}", e))?;
+        stream.play().map_err(|e| t!("Start stream: {}", e))?;
         *self.stream.write() = Some(stream);
         Ok(())
     }
@@ -196,14 +188,32 @@ impl RecordingManager {
         *self.peak.read()
     }
 
+    /// When the current take's first frame was captured; `None` until audio arrives.
+    pub fn first_frame_at(&self) -> Option<Instant> {
+        *self.first_frame_at.lock().unwrap()
+    }
+
+    pub fn set_skip_secs(&self, secs: f32) {
+        *self.skip_secs.write() = secs.max(0.0);
+    }
+
+    pub fn set_length_secs(&self, secs: Option<f32>) {
+        *self.length_secs.write() = secs;
+    }
+
     pub fn take_asset(&self, file_name: String) -> Option<Arc<AudioAsset>> {
-        let pcm = {
+        let sr = *self.sample_rate.read();
+        let ch = *self.channels.read();
+        let mut pcm = {
             let mut buf = self.buffer.lock().ok()?;
             std::mem::take(&mut *buf)
         };
+        let skip = (*self.skip_secs.read() * sr as f32) as usize * ch.max(1) as usize;
+        pcm.drain(..skip.min(pcm.len()));
+        if let Some(len) = *self.length_secs.read() {
+            pcm.truncate((len * sr as f32) as usize * ch.max(1) as usize);
+        }
         if pcm.is_empty() { return None; }
-        let sr = *self.sample_rate.read();
-        let ch = *self.channels.read();
         Some(Arc::new(AudioAsset {
             frames: pcm.len() as u64 / ch.max(1) as u64,
             pcm,
@@ -211,6 +221,7 @@ impl RecordingManager {
             channels: ch,
             file_name,
             sample_uuid: uuid::Uuid::new_v4(), // ✅ fresh UUID for every recording
+            tags: SampleTags::default(),
         }))
     }
 
@@ -223,4 +234,161 @@ impl RecordingManager {
             0.0
         }
     }
-}
\ No newline at end of file
+}
+/// Find `dev` again on its host and fetch its default input config.
+fn open_input(dev: &InputDevice) -> Result<(cpal::Device, cpal::SupportedStreamConfig), String> {
+    let host = cpal::host_from_id(dev.host_id)
+        .map_err(|e| format!("Host error: {:?}", e))?;
+
+    let device = host.input_devices()
+        .map_err(|e| t!("Device list: {}", e))?
+        .find(|d| d.name().map(|n| n == dev.device_name).unwrap_or(false))
+        .ok_or_else(|| t!("Device '{}' not found (try Refresh)", dev.device_name))?;
+
+    let cfg = device.default_input_config()
+        .map_err(|e| t!("Input config: {}", e))?;
+    Ok((device, cfg))
+}
+
+/// How long before its callback a block's first frame was captured.
+fn capture_latency(info: &cpal::InputCallbackInfo) -> Duration {
+    let ts = info.timestamp();
+    ts.callback.duration_since(&ts.capture).unwrap_or_default()
+}
+
+/// Build an input stream that hands interleaved f32 samples to `sink`, whatever the
+/// device's native sample format, with how long ago the first of them was captured.
+fn build_input_stream(
+    device: &cpal::Device,
+    cfg: &cpal::SupportedStreamConfig,
+    mut sink: impl FnMut(&[f32], Duration) + Send + 'static,
+) -> Result<cpal::Stream, String> {
+    let scfg: cpal::StreamConfig = cfg.clone().into();
+    match cfg.sample_format() {
+        cpal::SampleFormat::F32 => device.build_input_stream(
+            &scfg,
+            move |data: &[f32], info| sink(data, capture_latency(info)),
+            |e| eprintln!("Rec stream error: {}", e), None,
+        ),
+        cpal::SampleFormat::I16 => device.build_input_stream(
+            &scfg,
+            move |data: &[i16], info| {
+                let s: Vec<f32> = data.iter().map(|&x| x as f32 / 32767.0).collect();
+                sink(&s, capture_latency(info));
+            },
+            |e| eprintln!("Rec stream error: {}", e), None,
+        ),
+        cpal::SampleFormat::U16 => device.build_input_stream(
+            &scfg,
+            move |data: &[u16], info| {
+                let s: Vec<f32> = data.iter().map(|&x| x as f32 / 32767.5 - 1.0).collect();
+                sink(&s, capture_latency(info));
+            },
+            |e| eprintln!("Rec stream error: {}", e), None,
+        ),
+        fmt => return Err(format!("Unsupported input sample format: {:?}", fmt)),
+    }
+    .map_err(|e| t!("Build input stream: {}", e))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+//  Retrospective capture — always-on rolling input buffer
+// ═══════════════════════════════════════════════════════════════════════════════
+
+pub const CAPTURE_LENGTHS: [u32; 4] = [10, 30, 60, 120];
+
+/// Listens to one input device all the time and keeps only the last
+/// `seconds`, so something just played can be kept after the fact.
+pub struct RetroCapture {
+    ring:         Arc<Mutex<VecDeque<f32>>>,
+    /// Ring size in samples (interleaved).
+    capacity:     Arc<AtomicUsize>,
+    stream:       RwLock<Option<cpal::Stream>>,
+    sample_rate:  RwLock<u32>,
+    channels:     RwLock<u16>,
+    pub seconds:      RwLock<u32>,
+    pub device_label: RwLock<Option<String>>,
+    /// Counter for naming kept captures.
+    keeps:        AtomicU32,
+}
+
+impl RetroCapture {
+    pub fn new() -> Self {
+        Self {
+            ring:         Arc::new(Mutex::new(VecDeque::new())),
+            capacity:     Arc::new(AtomicUsize::new(0)),
+            stream:       RwLock::new(None),
+            sample_rate:  RwLock::new(44100),
+            channels:     RwLock::new(1),
+            seconds:      RwLock::new(30),
+            device_label: RwLock::new(None),
+            keeps:        AtomicU32::new(0),
+        }
+    }
+
+    pub fn start(&self, dev: &InputDevice) -> Result<(), String> {
+        self.stop();
+        let (device, cfg) = open_input(dev)?;
+        let sr = cfg.sample_rate().0;
+        let ch = cfg.channels();
+        *self.sample_rate.write() = sr;
+        *self.channels.write()    = ch;
+        self.capacity.store(*self.seconds.read() as usize * sr as usize * ch.max(1) as usize, Ordering::Relaxed);
+        self.ring.lock().unwrap().clear();
+
+        let stream = {
+            let ring     = self.ring.clone();
+            let capacity = self.capacity.clone();
+            build_input_stream(&device, &cfg, move |data: &[f32], _| {
+                let Ok(mut ring) = ring.lock() else { return; };
+                ring.extend(data.iter().copied());
+                let excess = ring.len().saturating_sub(capacity.load(Ordering::Relaxed));
+                ring.drain(..excess);
+            })?
+        };
+        stream.play().map_err(|e| t!("Start stream: {}", e))?;
+        *self.stream.write()       = Some(stream);
+        *self.device_label.write() = Some(dev.label.clone());
+        Ok(())
+    }
+
+    pub fn stop(&self) {
+        *self.stream.write() = None;
+        if let Ok(mut ring) = self.ring.lock() { ring.clear(); }
+    }
+
+    pub fn is_running(&self) -> bool { self.stream.read().is_some() }
+
+    /// Change the buffer length; a shorter length trims the oldest audio.
+    pub fn set_seconds(&self, secs: u32) {
+        *self.seconds.write() = secs;
+        let sr = *self.sample_rate.read() as usize;
+        let ch = (*self.channels.read()).max(1) as usize;
+        self.capacity.store(secs as usize * sr * ch, Ordering::Relaxed);
+    }
+
+    pub fn buffered_secs(&self) -> f32 {
+        let sr = *self.sample_rate.read() as f32;
+        let ch = (*self.channels.read()).max(1) as f32;
+        self.ring.lock().map(|r| r.len() as f32 / sr / ch).unwrap_or(0.0)
+    }
+
+    /// Copy the buffer into a new asset. Listening carries on.
+    pub fn keep(&self) -> Option<Arc<AudioAsset>> {
+        let sr = *self.sample_rate.read();
+        let ch = *self.channels.read();
+        let pcm: Vec<f32> = self.ring.lock().ok()?.iter().copied().collect();
+        let frames = pcm.len() as u64 / ch.max(1) as u64;
+        if frames == 0 { return None; }
+        let n = self.keeps.fetch_add(1, Ordering::Relaxed) + 1;
+        Some(Arc::new(AudioAsset {
+            pcm,
+            sample_rate: sr,
+            channels:    ch,
+            frames,
+            file_name:   format!("Capture {}", n),
+            sample_uuid: uuid::Uuid::new_v4(),
+            tags:        SampleTags::default(),
+        }))
+    }
+}
@@ -0,0 +1,101 @@
+// src/reload.rs
+// Reloading samples from disk. ⟳ on a track re-reads its file; with watching on, the
+// files behind the drum tracks are checked about once a second and any that changed
+// (say, edited in an external editor) are offered for reload. A reloaded file replaces
+// the audio of every track that uses it, and each track keeps its UUID, so its markers
+// stay where they were relative to the sample's length.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use crate::audio::AudioAsset;
+use crate::gui::{AppState, WaveformFocus};
+use crate::jobs::JobKind;
+use crate::mono::fold_to_mono;
+
+const CHECK_EVERY: Duration = Duration::from_secs(1);
+
+#[derive(Default)]
+pub struct FileWatch {
+    pub enabled: bool,
+    /// Paths that changed on disk since they were loaded, waiting for an answer.
+    pub changed: Vec<String>,
+    /// Modification time each file had when it was last loaded or looked at.
+    stamps:      HashMap<String, SystemTime>,
+    next_check:  Option<Instant>,
+}
+
+fn modified(path: &str) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+impl AppState {
+    /// Re-read `path` in the background and swap it into every track that plays it.
+    pub fn reload_asset(&self, path: &str) {
+        let audio_manager     = self.audio_manager.clone();
+        let drum_tracks       = self.drum_tracks.clone();
+        let asset_pool        = self.asset_pool.clone();
+        let waveform_focus    = self.waveform_focus.clone();
+        let waveform_analysis = self.waveform_analysis.clone();
+        let file_watch        = self.file_watch.clone();
+        let path              = path.to_string();
+        let name              = std::path::Path::new(&path).file_name()
+            .map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+
+        self.spawn_job(JobKind::Load, t!("Reload {}", name), move |_| {
+            let loaded = audio_manager.load_audio(&path).map_err(|e| t!("Reload error: {}", e))?;
+            asset_pool.write().insert(path.clone(), loaded.clone());
+            let focus = waveform_focus.read().clone();
+            let mut reloaded = 0;
+            for (i, t) in drum_tracks.write().iter_mut().enumerate() {
+                if t.file_path.as_deref() != Some(path.as_str()) { continue; }
+                let asset = AudioAsset { sample_uuid: t.sample_uuid, ..(*loaded).clone() };
+                let asset = Arc::new(if t.mono { fold_to_mono(&asset) } else { asset });
+                let waveform = audio_manager.analyze_waveform(&asset, 400);
+                if focus == WaveformFocus::DrumTrack(i) { *waveform_analysis.write() = Some(waveform.clone()); }
+                t.asset    = asset;
+                t.waveform = Some(waveform);
+                t.reclassify();
+                reloaded += 1;
+            }
+            let mut watch = file_watch.lock();
+            if let Some(m) = modified(&path) { watch.stamps.insert(path.clone(), m); }
+            watch.changed.retain(|p| *p != path);
+            Ok(t!("✓ Reloaded {} ({} track{})", loaded.file_name, reloaded, if reloaded == 1 { "" } else { "s" }))
+        });
+    }
+
+    /// Keep the audio already loaded for `path` and stop offering to reload it.
+    pub fn ignore_file_change(&self, path: &str) {
+        let mut watch = self.file_watch.lock();
+        if let Some(m) = modified(path) { watch.stamps.insert(path.to_string(), m); }
+        watch.changed.retain(|p| p != path);
+    }
+
+    /// Called every frame; looks at the files behind the drum tracks once a second.
+    pub(crate) fn tick_file_watch(&self) {
+        let mut watch = self.file_watch.lock();
+        if !watch.enabled { return; }
+        let now = Instant::now();
+        if watch.next_check.is_some_and(|t| now < t) { return; }
+        watch.next_check = Some(now + CHECK_EVERY);
+
+        let mut paths: Vec<String> = self.drum_tracks.read().iter().filter_map(|t| t.file_path.clone()).collect();
+        paths.sort();
+        paths.dedup();
+        for path in paths {
+            let Some(m) = modified(&path) else { continue; };
+            match watch.stamps.get(&path) {
+                // First look at a file: what's on disk now is what was loaded
+                None => { watch.stamps.insert(path, m); }
+                Some(&seen) if seen != m && !watch.changed.contains(&path) => {
+                    let name = std::path::Path::new(&path).file_name()
+                        .map(|f| f.to_string_lossy().to_string()).unwrap_or_else(|| path.clone());
+                    *self.status.write() = t!("⟳ {} changed on disk", name);
+                    watch.changed.push(path);
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,296 @@
+// src/render.rs
+// Offline rendering: the sequencer's voice builder and mixer driven by a sample counter
+// instead of the UI clock, plus a plain WAV writer for the results.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use crate::adsr::{mix_voices, ADSREnvelope, Voice};
+use crate::audio::AudioAsset;
+use crate::bus::EngineEvent;
+use crate::channels::DownmixCoeffs;
+use crate::gui::{AppState, DrumTrack, StepResolution};
+use crate::recording::RecordingTrack;
+use crate::samples::SamplesManager;
+use crate::limiter::soft_clip;
+use crate::tempo_track::bpm_at;
+use crate::variation::TrackDice;
+
+/// Render keeps going after the last step until voices finish, up to this long.
+const MAX_TAIL_SECS: f64 = 2.0;
+/// Lengths offered by the bounce menu, in bars.
+pub const BOUNCE_BARS: [usize; 5] = [1, 2, 4, 8, 16];
+/// Bounce rate when no output is running to match.
+const BOUNCE_RATE: u32 = 48_000;
+
+/// RIFF header for `bytes` of sample data.
+fn wav_header(bytes: u32, format: u16, bits: u16, sample_rate: u32, channels: u16) -> Vec<u8> {
+    let block = channels * bits / 8;
+    let mut w = Vec::with_capacity(44 + bytes as usize);
+    w.extend_from_slice(b"RIFF");
+    w.extend_from_slice(&(36 + bytes).to_le_bytes());
+    w.extend_from_slice(b"WAVEfmt ");
+    w.extend_from_slice(&16u32.to_le_bytes());
+    w.extend_from_slice(&format.to_le_bytes());
+    w.extend_from_slice(&channels.to_le_bytes());
+    w.extend_from_slice(&sample_rate.to_le_bytes());
+    w.extend_from_slice(&(sample_rate * block as u32).to_le_bytes());
+    w.extend_from_slice(&block.to_le_bytes());
+    w.extend_from_slice(&bits.to_le_bytes());
+    w.extend_from_slice(b"data");
+    w.extend_from_slice(&bytes.to_le_bytes());
+    w
+}
+
+/// 16-bit PCM WAV image of interleaved `data`.
+pub fn wav_bytes(data: &[f32], sample_rate: u32, channels: u16) -> Vec<u8> {
+    let mut w = wav_header((data.len() * 2) as u32, 1, 16, sample_rate, channels);
+    for s in data { w.extend_from_slice(&((s.clamp(-1.0, 1.0) * 32767.0).round() as i16).to_le_bytes()); }
+    w
+}
+
+/// 32-bit float WAV image of interleaved `data`, for samples that shouldn't lose depth.
+pub fn float_wav_bytes(data: &[f32], sample_rate: u32, channels: u16) -> Vec<u8> {
+    let mut w = wav_header((data.len() * 4) as u32, 3, 32, sample_rate, channels);
+    for s in data { w.extend_from_slice(&s.to_le_bytes()); }
+    w
+}
+
+pub fn write_wav(path: &Path, data: &[f32], sample_rate: u32, channels: u16) -> Result<(), String> {
+    write_file(path, &wav_bytes(data, sample_rate, channels))
+}
+
+pub fn write_float_wav(path: &Path, data: &[f32], sample_rate: u32, channels: u16) -> Result<(), String> {
+    write_file(path, &float_wav_bytes(data, sample_rate, channels))
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir).map_err(|e| t!("Create {}: {}", dir.display(), e))?;
+    }
+    std::fs::write(path, bytes).map_err(|e| t!("Write {}: {}", path.display(), e))
+}
+
+/// Mix `voices` into `out` until it holds `frame` frames.
+fn render_to(out: &mut Vec<f32>, voices: &mut Vec<Voice>, frame: usize, sample_rate: u32, channels: usize, crossfader: f32) {
+    let from = out.len();
+    out.resize(frame * channels, 0.0);
+    mix_voices(voices, &mut out[from..], sample_rate as f32, channels, crossfader);
+}
+
+/// Render up to `end`, let voices ring out for up to `MAX_TAIL_SECS`, then apply the
+/// master volume and soft-clip, as the live master bus does.
+fn finish(out: &mut Vec<f32>, voices: &mut Vec<Voice>, end: usize, sample_rate: u32, channels: usize, crossfader: f32, volume: f32) {
+    render_to(out, voices, end, sample_rate, channels, crossfader);
+    while !voices.is_empty() && out.len() / channels < end + (MAX_TAIL_SECS * sample_rate as f64) as usize {
+        let frame = out.len() / channels + 512;
+        render_to(out, voices, frame, sample_rate, channels, crossfader);
+    }
+    for s in out.iter_mut() { *s = soft_clip(*s * volume); }
+}
+
+/// A copy of what `render_steps` reads — tempo, grid, tracks, markers, the main sample
+/// and the mix settings — so a bounce can run on a job thread while editing goes on.
+pub(crate) struct RenderScene {
+    bpm:           f32,
+    res:           StepResolution,
+    len:           usize,
+    grid:          Vec<Vec<usize>>,
+    chop_adsr:     Vec<ADSREnvelope>,
+    main_asset:    Option<Arc<AudioAsset>>,
+    main_track:    Option<usize>,
+    drum_tracks:   Vec<DrumTrack>,
+    rec_tracks:    Vec<RecordingTrack>,
+    samples:       SamplesManager,
+    downmix:       DownmixCoeffs,
+    crossfader:    f32,
+    master_volume: f32,
+    humanize:      f32,
+}
+
+impl RenderScene {
+    /// A stand-alone app holding the scene, on whichever thread will render it. Its
+    /// tracks meter into their own meters, not the live ones.
+    pub(crate) fn into_app(self) -> AppState {
+        let app = AppState { samples_manager: Arc::new(self.samples), ..AppState::default() };
+        app.events.emit(EngineEvent::Bpm { bpm: self.bpm });
+        app.pattern_len.store(self.len, Ordering::Relaxed);
+        app.crossfader.store(self.crossfader, Ordering::Relaxed);
+        app.master_volume.store(self.master_volume, Ordering::Relaxed);
+        app.humanize.store(self.humanize, Ordering::Relaxed);
+        *app.seq_resolution.write()   = self.res;
+        *app.seq_grid.write()         = self.grid;
+        *app.chop_adsr.write()        = self.chop_adsr;
+        *app.current_asset.write()    = self.main_asset;
+        *app.main_track_index.write() = self.main_track;
+        *app.rec_tracks.write()       = self.rec_tracks;
+        *app.downmix.write()          = self.downmix;
+        *app.drum_tracks.write() = self.drum_tracks.into_iter()
+            .map(|t| DrumTrack { meter: Default::default(), ..t })
+            .collect();
+        app
+    }
+}
+
+impl AppState {
+    /// Render `steps` sequencer steps of the live pattern, interleaved at `channels`.
+    /// Voices start exactly on their step instead of on the next audio callback. The
+    /// master volume and soft clip are applied; the master filter and the output's
+    /// safety limiter are not.
+    pub fn render_steps(&self, steps: usize, sample_rate: u32, channels: usize) -> Vec<f32> {
+        self.render_steps_of(steps, sample_rate, channels, None)
+    }
+
+    /// `render_steps` with only drum track `stem` playing, when given.
+    pub(crate) fn render_steps_of(&self, steps: usize, sample_rate: u32, channels: usize, stem: Option<usize>) -> Vec<f32> {
+        let sr        = sample_rate as f64;
+        let res       = *self.seq_resolution.read();
+        let step_secs = res.step_secs(self.events.bpm());
+        let xf        = self.crossfader.load(Ordering::Relaxed);
+        let mut out: Vec<f32>      = Vec::new();
+        let mut voices: Vec<Voice> = Vec::new();
+        // Same rolls every bounce
+        *self.dice.lock() = TrackDice::default();
+
+        for k in 0..steps {
+            render_to(&mut out, &mut voices, (k as f64 * step_secs * sr).round() as usize, sample_rate, channels, xf);
+            let mut new = self.step_voices(k % self.pattern_len(), res, step_secs, None, stem, k == 0);
+            self.attach_channel_maps(&mut new, channels);
+            voices.extend(new);
+        }
+        let end = (steps as f64 * step_secs * sr).round() as usize;
+        finish(&mut out, &mut voices, end, sample_rate, channels, xf, self.master_volume.load(Ordering::Relaxed));
+        out
+    }
+
+    /// Render the arrangement from bar 1 to its last block, switching patterns and
+    /// following the tempo track the way song playback does.
+    pub fn render_song(&self, sample_rate: u32, channels: usize) -> Result<Vec<f32>, String> {
+        let arr  = self.song_editor.get_arrangement_snapshot();
+        let bars = arr.iter().filter_map(|row| row.iter().rposition(|c| c.is_some())).max()
+            .map(|last| last + 1)
+            .ok_or(t!("Nothing arranged — place patterns in the song first"))?;
+        let active = self.song_editor.active_edit_idx();
+        self.save_current_pattern_state();
+
+        let sr     = sample_rate as f64;
+        let res    = *self.seq_resolution.read();
+        let base   = self.events.bpm();
+        let events = self.tempo_events.read().clone();
+        let xf     = self.crossfader.load(Ordering::Relaxed);
+        let mut out: Vec<f32>      = Vec::new();
+        let mut voices: Vec<Voice> = Vec::new();
+        let mut at     = 0.0f64;
+        let mut loaded = active;
+        *self.dice.lock() = TrackDice::default();
+
+        for bar in 0..bars {
+            // Bars with no block keep the pattern before them, as they do live
+            let first = arr.iter().position(|row| row.get(bar).copied().flatten().is_some());
+            if let Some(idx) = first.filter(|&i| i != loaded) {
+                self.load_pattern_state(idx);
+                loaded = idx;
+            }
+            let len = self.pattern_len();
+            for k in 0..len {
+                let step_secs = res.step_secs(bpm_at(&events, base, bar as f64 + k as f64 / len as f64));
+                render_to(&mut out, &mut voices, (at * sr).round() as usize, sample_rate, channels, xf);
+                let mut new = self.step_voices(k, res, step_secs, None, None, bar == 0 && k == 0);
+                self.attach_channel_maps(&mut new, channels);
+                voices.extend(new);
+                at += step_secs;
+            }
+        }
+        finish(&mut out, &mut voices, (at * sr).round() as usize, sample_rate, channels, xf, self.master_volume.load(Ordering::Relaxed));
+        if loaded != active { self.load_pattern_state(active); }
+        Ok(out)
+    }
+
+    /// Render `bars` bars of pattern `idx`, then put the edited pattern back.
+    pub fn render_pattern(&self, idx: usize, bars: usize, sample_rate: u32, channels: usize) -> Result<Vec<f32>, String> {
+        if idx >= self.song_editor.pattern_count() { return Err(t!("No pattern {}", idx)); }
+        let active = self.song_editor.active_edit_idx();
+        self.save_current_pattern_state();
+        self.load_pattern_state(idx);
+        let steps = bars.max(1) * self.seq_resolution.read().steps();
+        let out = self.render_steps(steps, sample_rate, channels);
+        if idx != active { self.load_pattern_state(active); }
+        Ok(out)
+    }
+
+    /// Everything a pattern render reads, copied off the live state.
+    pub(crate) fn render_scene(&self) -> RenderScene {
+        RenderScene {
+            bpm:           self.events.bpm(),
+            res:           *self.seq_resolution.read(),
+            len:           self.pattern_len(),
+            grid:          self.seq_grid.read().clone(),
+            chop_adsr:     self.chop_adsr.read().clone(),
+            main_asset:    self.current_asset.read().clone(),
+            main_track:    *self.main_track_index.read(),
+            drum_tracks:   self.drum_tracks.read().clone(),
+            rec_tracks:    self.rec_tracks.read().clone(),
+            samples:       self.samples_manager.snapshot(),
+            downmix:       *self.downmix.read(),
+            crossfader:    self.crossfader.load(Ordering::Relaxed),
+            master_volume: self.master_volume.load(Ordering::Relaxed),
+            humanize:      self.humanize.load(Ordering::Relaxed),
+        }
+    }
+
+    /// The output's sample rate, so bounces sound as they do live.
+    pub(crate) fn bounce_rate(&self) -> u32 {
+        self.engine.read().as_ref().map(|e| e.spec().sample_rate).filter(|&r| r > 0).unwrap_or(BOUNCE_RATE)
+    }
+
+    /// Ask where to save, then bounce `bars` bars of the edited pattern to a stereo WAV
+    /// at the output's sample rate.
+    pub fn bounce_pattern(&self, bars: usize) {
+        let idx  = self.song_editor.active_edit_idx();
+        let name = self.song_editor.get_pattern_by_idx(idx).map_or_else(|| "pattern".to_string(), |p| p.name);
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(t!("WAV"), &["wav"])
+            .set_file_name(format!("{}.wav", name))
+            .save_file() else { return; };
+        let rate = self.bounce_rate();
+        let result = self.render_pattern(idx, bars, rate, 2).and_then(|pcm| write_wav(&path, &pcm, rate, 2));
+        *self.status.write() = match result {
+            Ok(())  => t!("✓ Bounced {} bar{} to {}", bars, if bars == 1 { "" } else { "s" }, path.display()),
+            Err(e)  => format!("✗ {}", e),
+        };
+    }
+
+    /// Ask where to save, then bounce the whole arrangement to a stereo WAV.
+    pub fn bounce_song(&self) {
+        let name = self.project_name().unwrap_or_else(|| "song".to_string());
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(t!("WAV"), &["wav"])
+            .set_file_name(format!("{}.wav", name))
+            .save_file() else { return; };
+        let rate = self.bounce_rate();
+        let result = self.render_song(rate, 2).and_then(|pcm| write_wav(&path, &pcm, rate, 2));
+        *self.status.write() = match result {
+            Ok(())  => t!("✓ Bounced the song to {}", path.display()),
+            Err(e)  => format!("✗ {}", e),
+        };
+    }
+
+    /// Chop `track_idx` of the live pattern as separate slices (mark to next mark),
+    /// each with the track's sample rate and channel count.
+    pub fn chop_slices(&self, track_idx: usize) -> Result<Vec<Vec<f32>>, String> {
+        let tracks = self.drum_tracks.read();
+        let track  = tracks.get(track_idx).ok_or_else(|| format!("No track {}", track_idx))?;
+        let mut marks: Vec<f32> = self.samples_manager.get_marks_for_sample(&track.sample_uuid)
+            .iter().map(|m| m.position).collect();
+        if marks.is_empty() { return Err(t!("Track {} has no chops", track_idx)); }
+        marks.sort_by(f32::total_cmp);
+
+        let ch     = (track.asset.channels as usize).max(1);
+        let frames = track.asset.pcm.len() / ch;
+        let at     = |p: f32| ((p.clamp(0.0, 1.0) as f64 * frames as f64) as usize).min(frames);
+        Ok(marks.iter().enumerate().map(|(i, &p)| {
+            let end = marks.get(i + 1).map_or(frames, |&n| at(n));
+            track.asset.pcm[at(p) * ch..end.max(at(p)) * ch].to_vec()
+        }).collect())
+    }
+}
@@ -0,0 +1,150 @@
+// src/resume.rs
+// Panic-resume. While anything plays, where the session is — project, pattern, step,
+// whether the sequencer or the song runs, and the main playhead — is written to a small
+// file in the temp folder about once a second, and again when playback stops. When the
+// audio engine is restarted after a device fault, or PANIC cuts everything, or the app
+// starts after a session that ended mid-play (a crash, or closing while playing), a
+// ⏯ Resume banner offers to put it all back: the project is reopened if it isn't the one
+// open, and playback picks up on the step and bar it stopped at.
+
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use crate::gui::AppState;
+
+/// How often the point is written while playing.
+const WRITE_EVERY: Duration = Duration::from_secs(1);
+
+fn resume_path() -> PathBuf { std::env::temp_dir().join("rabies.resume.json") }
+
+/// Where the session was.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResumePoint {
+    /// Saved project that was open, if any.
+    pub project:         Option<PathBuf>,
+    pub pattern:         usize,
+    pub step:            usize,
+    pub sequencer:       bool,
+    pub song:            bool,
+    pub song_bar:        usize,
+    /// File name of the sample on the main playhead, and where it was (0-1).
+    pub sample:          Option<String>,
+    pub sample_position: f32,
+    pub sample_playing:  bool,
+}
+
+impl ResumePoint {
+    /// Something was playing, so there is somewhere to resume.
+    pub fn playing(&self) -> bool { self.sequencer || self.song || self.sample_playing }
+
+    /// "pattern 2, step 5", for the banner.
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if self.song { parts.push(t!("song bar {}", self.song_bar + 1)); }
+        if self.sequencer { parts.push(t!("pattern {}, step {}", self.pattern + 1, self.step + 1)); }
+        if let (true, Some(name)) = (self.sample_playing, &self.sample) {
+            parts.push(t!("{} at {:.0}%", name, self.sample_position * 100.0));
+        }
+        parts.join(" · ")
+    }
+}
+
+/// The point on offer, and when it was last written.
+#[derive(Default)]
+pub struct ResumeState {
+    pub offered: Option<ResumePoint>,
+    written:     Option<(Instant, bool)>,
+}
+
+impl AppState {
+    pub fn capture_resume_point(&self) -> ResumePoint {
+        ResumePoint {
+            project:         self.project_path.read().clone(),
+            pattern:         self.song_editor.active_edit_idx(),
+            step:            *self.seq_current_step.read(),
+            sequencer:       self.events.playing(),
+            song:            self.song_editor.is_playing.load(Ordering::Relaxed),
+            song_bar:        self.song_editor.current_bar.load(Ordering::Relaxed),
+            sample:          self.playback_asset.read().as_ref().map(|a| a.file_name.clone()),
+            sample_position: self.playback_position.load(Ordering::Relaxed),
+            sample_playing:  self.is_playing.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Keep the current point to offer back, before playback is cut.
+    pub(crate) fn hold_resume_point(&self) {
+        let point = self.capture_resume_point();
+        if point.playing() { self.resume.lock().offered = Some(point); }
+    }
+
+    /// At startup: offer the last session's point if it ended mid-play.
+    pub fn load_resume_point(&self) {
+        let Ok(text) = std::fs::read_to_string(resume_path()) else { return; };
+        if let Ok(point) = serde_json::from_str::<ResumePoint>(&text) {
+            if point.playing() { self.resume.lock().offered = Some(point); }
+        }
+    }
+
+    /// Called every frame: write the point while playing, and once more when it stops.
+    pub(crate) fn tick_resume(&self) {
+        let point   = self.capture_resume_point();
+        let playing = point.playing();
+        let due = match self.resume.lock().written {
+            None              => playing,
+            Some((at, was))   => if playing { !was || at.elapsed() >= WRITE_EVERY } else { was },
+        };
+        if !due { return; }
+        self.resume.lock().written = Some((Instant::now(), playing));
+        if let Ok(text) = serde_json::to_string(&point) {
+            let _ = std::fs::write(resume_path(), text);
+        }
+    }
+
+    pub fn dismiss_resume(&self) {
+        self.resume.lock().offered = None;
+    }
+
+    /// Put the offered point back: project, pattern, step and playhead, playing as they were.
+    pub fn resume_session(&self) {
+        let Some(point) = self.resume.lock().offered.take() else { return; };
+        if let Some(path) = point.project.as_ref() {
+            if self.project_path.read().as_ref() != Some(path) {
+                if !path.exists() {
+                    *self.status.write() = t!("✗ Can't resume: {} is gone", path.display());
+                    return;
+                }
+                self.open_project_from(path);
+            }
+        }
+        if point.pattern < self.song_editor.pattern_count() && point.pattern != self.song_editor.active_edit_idx() {
+            self.ab_commit();
+            self.save_current_pattern_state();
+            self.load_pattern_state(point.pattern);
+        }
+
+        if point.song {
+            self.start_song();
+            self.song_editor.current_bar.store(point.song_bar, Ordering::Relaxed);
+        } else if point.sequencer {
+            self.start_sequencer();
+        }
+        if point.song || point.sequencer {
+            let step = point.step % self.pattern_len();
+            *self.seq_current_step.write() = step;
+            self.song_editor.current_step_in_bar.store(step, Ordering::Relaxed);
+        }
+
+        if let (true, Some(name)) = (point.sample_playing, point.sample.as_ref()) {
+            let asset = self.current_asset.read().clone().filter(|a| &a.file_name == name)
+                .or_else(|| self.drum_tracks.read().iter().find(|t| &t.asset.file_name == name).map(|t| t.asset.clone()));
+            if let Some(asset) = asset {
+                self.playback_position.store(point.sample_position, Ordering::Relaxed);
+                self.playback_sample_index.store((point.sample_position as f64 * asset.pcm.len() as f64) as u64, Ordering::Relaxed);
+                self.start_playback(asset);
+            }
+        }
+        *self.status.write() = t!("⏯ Resumed: {}", point.describe());
+    }
+}
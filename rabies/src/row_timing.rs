@@ -0,0 +1,52 @@
+// src/row_timing.rs
+// Per-row micro-timing: a drum track's own row or one of its chop rows can sit ahead of
+// or behind the grid by up to half a step, every hit on it alike — hats pushed to rush,
+// a snare pulled back to drag. The offset is kept as a fraction of a step on the row's
+// lane, so the feel holds at any tempo, and is shown in ms at the current tempo or in
+// ticks (96 to the beat). A late row just delays its voices; an early row's hits are
+// scheduled from the step before, since a step can't start voices before it is played.
+// On the first step of a run there is no step before, so its early hits play on it.
+
+use serde::{Deserialize, Serialize};
+
+/// Furthest a row sits off the grid, in steps.
+pub const MAX_OFFSET: f32 = 0.5;
+/// Tick resolution offsets are shown in.
+pub const TICKS_PER_BEAT: usize = 96;
+/// Offsets smaller than this are on the grid.
+const MIN_FRACTION: f32 = 0.005;
+
+/// Row `chop` plays `fraction` of a step late (early when negative).
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RowOffset {
+    /// Chop row, or `None` for the track's own row.
+    pub chop:     Option<usize>,
+    /// -0.5 to 0.5.
+    pub fraction: f32,
+}
+
+/// Offset of row `chop`; 0 when it has none.
+pub fn fraction(offsets: &[RowOffset], chop: Option<usize>) -> f32 {
+    offsets.iter().find(|o| o.chop == chop).map_or(0.0, |o| o.fraction)
+}
+
+/// Set row `chop` `fraction` of a step off the grid; a tiny one drops the offset.
+pub fn set(offsets: &mut Vec<RowOffset>, chop: Option<usize>, fraction: f32) {
+    offsets.retain(|o| o.chop != chop);
+    let fraction = fraction.clamp(-MAX_OFFSET, MAX_OFFSET);
+    if fraction.abs() >= MIN_FRACTION { offsets.push(RowOffset { chop, fraction }); }
+}
+
+/// Forget chop `removed`: its offset goes, and later chops shift down one.
+pub fn remove_chop(offsets: &mut Vec<RowOffset>, removed: usize) {
+    offsets.retain(|o| o.chop != Some(removed));
+    for o in offsets.iter_mut() {
+        if let Some(c) = o.chop.as_mut().filter(|c| **c > removed) { *c -= 1; }
+    }
+}
+
+/// Ticks in one step of a grid with `per_beat` steps to the beat.
+pub fn ticks_per_step(per_beat: usize) -> f32 {
+    TICKS_PER_BEAT as f32 / per_beat.max(1) as f32
+}
+
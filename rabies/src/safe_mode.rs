@@ -0,0 +1,189 @@
+// src/safe_mode.rs
+// Safe mode and the audio self-test. At startup a short silent stream is opened on the
+// chosen output to check that devices list, the stream opens and its callbacks keep
+// time. A marker file is held while the app runs and removed on a clean exit. If the
+// marker is still there at the next start, that session crashed (a broken driver can
+// take the process down mid-test), so the app comes up in safe mode: no audio at all,
+// and projects can still be opened, inspected and saved. `--safe-mode` does the same.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use crate::backend::{AudioBackend, OutputRequest, OutputSpec};
+use crate::devices::AudioDeviceManager;
+use crate::gui::AppState;
+use crate::jobs::JobKind;
+
+/// How long the test stream runs.
+const TEST_TIME: Duration = Duration::from_millis(400);
+/// Share of real time the callbacks must render to pass.
+const MIN_RATE: f32 = 0.5;
+/// Longest callback gap passed, as buffer periods (and never under `MIN_GAP_MS`).
+const MAX_GAP_PERIODS: f32 = 4.0;
+const MIN_GAP_MS: f32 = 50.0;
+/// No gap seen yet.
+const NO_GAP: u64 = u64::MAX;
+
+/// Held while the app runs.
+fn marker_path() -> PathBuf { std::env::temp_dir().join("rabies.running") }
+
+/// Whether the last session never got to `mark_stopped`. A marker left by a session
+/// that is still running (a second window) doesn't count.
+pub fn previous_run_crashed() -> bool {
+    let Ok(pid) = std::fs::read_to_string(marker_path()) else { return false; };
+    let pid = pid.trim();
+    let proc = std::path::Path::new("/proc");
+    !(proc.is_dir() && pid != std::process::id().to_string() && proc.join(pid).exists())
+}
+
+pub fn mark_running() {
+    let _ = std::fs::write(marker_path(), std::process::id().to_string());
+}
+
+pub fn mark_stopped() {
+    let _ = std::fs::remove_file(marker_path());
+}
+
+#[derive(Clone, Debug)]
+pub struct SelfTestReport {
+    /// Output devices listed across hosts.
+    pub devices:    usize,
+    /// What the test stream opened, if it did.
+    pub spec:       Option<OutputSpec>,
+    pub callbacks:  usize,
+    /// Frames rendered over frames due in the test time.
+    pub rate:       f32,
+    /// Longest wait between two callbacks, and the buffer period it's judged against.
+    pub worst_gap_ms: f32,
+    pub period_ms:  f32,
+    /// Why it failed; `None` passed.
+    pub problem:    Option<String>,
+}
+
+impl SelfTestReport {
+    pub fn passed(&self) -> bool { self.problem.is_none() }
+
+    /// One line: the problem if there is one, then what was measured.
+    pub fn summary(&self) -> String {
+        let Some(spec) = self.spec else {
+            return t!("{} — {} device(s)", self.problem.as_deref().unwrap_or(t!("No output")), self.devices);
+        };
+        let timing = t!("{} device(s) · {} Hz · {} ch · {} callbacks, {:.0}% of real time, worst gap {:.1} ms ({:.1} ms buffer)",
+            self.devices, spec.sample_rate, spec.channels, self.callbacks, self.rate * 100.0, self.worst_gap_ms, self.period_ms);
+        match &self.problem {
+            Some(p) => format!("{} — {}", p, timing),
+            None    => timing,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub enum SelfTest {
+    #[default]
+    NotRun,
+    Running,
+    Done(SelfTestReport),
+}
+
+/// List devices, then run a silent stream on the selected output for `TEST_TIME` and
+/// check its callbacks came often enough.
+pub fn run_self_test(backend: &dyn AudioBackend, devices: &AudioDeviceManager) -> SelfTestReport {
+    devices.refresh();
+    let mut report = SelfTestReport {
+        devices: devices.devices().len(), spec: None, callbacks: 0, rate: 0.0,
+        worst_gap_ms: 0.0, period_ms: 0.0, problem: None,
+    };
+
+    let callbacks = Arc::new(AtomicUsize::new(0));
+    let samples   = Arc::new(AtomicUsize::new(0));
+    let last_ns   = Arc::new(AtomicU64::new(NO_GAP));
+    let worst_ns  = Arc::new(AtomicU64::new(0));
+    let start     = Instant::now();
+    let sel = devices.selection();
+    let req = OutputRequest {
+        role: "selftest", device: sel.device, sample_rate: sel.sample_rate, buffer_frames: sel.buffer_frames,
+        on_error: Box::new(|err| eprintln!("Self-test stream error: {}", err)),
+        latency:  Default::default(),
+    };
+    let opened = backend.open_output(req, &mut |_spec| {
+        let (callbacks, samples, last_ns, worst_ns) = (callbacks.clone(), samples.clone(), last_ns.clone(), worst_ns.clone());
+        Box::new(move |data: &mut [f32]| {
+            data.fill(0.0);
+            let now  = start.elapsed().as_nanos() as u64;
+            let last = last_ns.swap(now, Ordering::Relaxed);
+            if last != NO_GAP { worst_ns.fetch_max(now.saturating_sub(last), Ordering::Relaxed); }
+            callbacks.fetch_add(1, Ordering::Relaxed);
+            samples.fetch_add(data.len(), Ordering::Relaxed);
+        })
+    });
+    let (stream, spec) = match opened {
+        Ok(o)  => o,
+        Err(e) => { report.problem = Some(t!("Output didn't open: {}", e)); return report; }
+    };
+    std::thread::sleep(TEST_TIME);
+    let elapsed = start.elapsed().as_secs_f32();
+    drop(stream);
+
+    report.spec      = Some(spec);
+    report.callbacks = callbacks.load(Ordering::Relaxed);
+    let frames = samples.load(Ordering::Relaxed) as f32 / spec.channels.max(1) as f32;
+    report.rate         = frames / (spec.sample_rate as f32 * elapsed).max(1.0);
+    report.worst_gap_ms = match worst_ns.load(Ordering::Relaxed) { 0 => 0.0, ns => ns as f32 / 1e6 };
+    report.period_ms    = frames / report.callbacks.max(1) as f32 / spec.sample_rate.max(1) as f32 * 1000.0;
+    let max_gap = (report.period_ms * MAX_GAP_PERIODS).max(MIN_GAP_MS);
+    report.problem = if report.callbacks == 0 {
+        Some(t!("Output opened but never asked for audio").to_string())
+    } else if report.rate < MIN_RATE {
+        Some(t!("Output runs too slowly").to_string())
+    } else if report.worst_gap_ms > max_gap {
+        Some(t!("Output stalls between buffers").to_string())
+    } else {
+        None
+    };
+    report
+}
+
+impl AppState {
+    /// Turn all audio off for this session, saying why.
+    pub fn enter_safe_mode(&self, reason: &str) {
+        self.stop_playback();
+        self.song_editor.stop();
+        self.stop_sequencer();
+        *self.engine.write() = None;
+        *self.safe_mode.write() = Some(reason.to_string());
+        *self.status.write() = t!("🛟 Safe mode ({}) — audio is off; projects open and save as usual", reason);
+    }
+
+    /// Let audio open again; the engine starts on the next sound.
+    pub fn leave_safe_mode(&self) {
+        *self.safe_mode.write() = None;
+        *self.engine_fault.write() = None;
+        *self.status.write() = t!("✓ Safe mode off — audio opens on the next sound").to_string();
+    }
+
+    /// In safe mode, say so and return true: the caller mustn't touch audio.
+    pub(crate) fn audio_blocked(&self) -> bool {
+        if self.safe_mode.read().is_none() { return false; }
+        *self.status.write() = t!("🛟 Safe mode — audio is off (leave it from the banner)").to_string();
+        true
+    }
+
+    /// Run the self-test in the background; its report lands in `self_test`, and in the
+    /// status line if it failed.
+    pub fn start_self_test(&self) {
+        if matches!(*self.self_test.read(), SelfTest::Running) { return; }
+        *self.self_test.write() = SelfTest::Running;
+        let (backend, devices) = (self.audio_backend.clone(), self.audio_devices.clone());
+        let (result, status)   = (self.self_test.clone(), self.status.clone());
+        self.jobs.spawn(JobKind::Test, t!("Audio self-test"), move |_| {
+            let report  = run_self_test(backend.read().as_ref(), &devices);
+            let summary = report.summary();
+            let passed  = report.passed();
+            *result.write() = SelfTest::Done(report);
+            if passed { return Ok(summary); }
+            *status.write() = t!("✗ Audio self-test: {} — 🛟 safe mode keeps projects usable", summary);
+            Err(summary)
+        });
+    }
+}
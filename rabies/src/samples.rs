@@ -25,6 +25,69 @@ pub struct CustomRegion {
     pub to: usize,
     pub sample_uuid: Uuid, // Which track/load instance owns this region
     pub name: String,
+    /// Sequencer lane: each step that's on plays the region, `from` → `to`.
+    pub steps: Vec<bool>,
+}
+
+/// Unit used when typing or displaying a marker position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MarkerTimeUnit {
+    Seconds,
+    Samples,
+    BarsBeats,
+}
+
+impl MarkerTimeUnit {
+    pub const ALL: [MarkerTimeUnit; 3] = [
+        MarkerTimeUnit::Seconds,
+        MarkerTimeUnit::Samples,
+        MarkerTimeUnit::BarsBeats,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MarkerTimeUnit::Seconds   => t!("Seconds"),
+            MarkerTimeUnit::Samples   => t!("Samples"),
+            MarkerTimeUnit::BarsBeats => t!("Bars:Beats"),
+        }
+    }
+
+    /// Format a normalised (0-1) position in this unit. Bars:beats assume 4/4 at `bpm`.
+    pub fn format(&self, position: f32, frames: u64, sample_rate: u32, bpm: f32) -> String {
+        let secs = position as f64 * frames as f64 / sample_rate.max(1) as f64;
+        match self {
+            MarkerTimeUnit::Seconds => format!("{:.3}", secs),
+            MarkerTimeUnit::Samples => format!("{}", (position as f64 * frames as f64).round() as u64),
+            MarkerTimeUnit::BarsBeats => {
+                let beats = secs * bpm.max(1.0) as f64 / 60.0;
+                let bar   = (beats / 4.0).floor();
+                format!("{}:{:.2}", bar as u64 + 1, beats - bar * 4.0 + 1.0)
+            }
+        }
+    }
+
+    /// Parse text typed in this unit back into a normalised position.
+    /// Returns `None` for malformed input or a time outside the sample.
+    pub fn parse(&self, text: &str, frames: u64, sample_rate: u32, bpm: f32) -> Option<f32> {
+        let text = text.trim();
+        let secs = match self {
+            MarkerTimeUnit::Seconds => text.parse::<f64>().ok()?,
+            MarkerTimeUnit::Samples => text.parse::<f64>().ok()? / sample_rate.max(1) as f64,
+            MarkerTimeUnit::BarsBeats => {
+                let mut parts = text.splitn(2, ':');
+                let bar: f64  = parts.next()?.trim().parse().ok()?;
+                let beat: f64 = match parts.next() {
+                    Some(b) => b.trim().parse().ok()?,
+                    None    => 1.0,
+                };
+                if bar < 1.0 || beat < 1.0 { return None; }
+                ((bar - 1.0) * 4.0 + (beat - 1.0)) * 60.0 / bpm.max(1.0) as f64
+            }
+        };
+        let dur = frames as f64 / sample_rate.max(1) as f64;
+        if !secs.is_finite() || secs < 0.0 || secs > dur || dur <= 0.0 { return None; }
+        Some((secs / dur) as f32)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -55,6 +118,18 @@ impl SamplesManager {
         }
     }
 
+    /// A separate copy of every marker and region, for work done off the UI thread.
+    pub fn snapshot(&self) -> Self {
+        Self {
+            marks:          RwLock::new(self.marks.read().clone()),
+            next_id:        RwLock::new(*self.next_id.read()),
+            relations:      RwLock::new(self.relations.read().clone()),
+            playback_mode:  RwLock::new(self.playback_mode.read().clone()),
+            regions:        RwLock::new(self.regions.read().clone()),
+            next_region_id: RwLock::new(*self.next_region_id.read()),
+        }
+    }
+
     /// Add a chop marker associated with a specific track UUID.
     ///
     /// The caller must pass the `DrumTrack::sample_uuid` so that marks are
@@ -65,7 +140,7 @@ impl SamplesManager {
         sample_uuid: Uuid,   // ✅ caller supplies the track's UUID
         sample_name: &str,
         position: f32,
-    ) {
+    ) -> usize {
         let mut next_id = self.next_id.write();
         let id = *next_id;
         *next_id += 1;
@@ -83,6 +158,7 @@ impl SamplesManager {
             timestamp,
         };
         self.marks.write().push(mark);
+        id
     }
 
     pub fn get_marks(&self) -> Vec<SampleMark> {
@@ -147,6 +223,7 @@ impl SamplesManager {
             to,
             sample_uuid,
             name: format!("R{} ({:.2}→{:.2})", id, from_pos, to_pos),
+            steps: Vec::new(),
         };
         self.regions.write().push(region);
         id
@@ -178,6 +255,25 @@ impl SamplesManager {
         }
     }
 
+    /// Turn step `step` of region `id`'s lane on or off.
+    pub fn set_region_step(&self, id: usize, step: usize, on: bool) {
+        if let Some(region) = self.regions.write().iter_mut().find(|r| r.id == id) {
+            if region.steps.len() <= step { region.steps.resize(step + 1, false); }
+            region.steps[step] = on;
+        }
+    }
+
+    pub fn set_region_steps(&self, id: usize, steps: Vec<bool>) {
+        if let Some(region) = self.regions.write().iter_mut().find(|r| r.id == id) {
+            region.steps = steps;
+        }
+    }
+
+    /// Apply `f` to every region's step lane (re-gridding, resizing, clearing).
+    pub fn for_each_region_lane(&self, mut f: impl FnMut(&mut Vec<bool>)) {
+        for region in self.regions.write().iter_mut() { f(&mut region.steps); }
+    }
+
     pub fn rename_region(&self, id: usize, new_name: String) {
         if let Some(region) = self.regions.write().iter_mut().find(|r| r.id == id) {
             region.name = new_name;
@@ -249,6 +345,23 @@ impl SamplesManager {
             mark.position = new_position.clamp(0.0, 1.0);
         }
     }
+    /// Marks and regions of one sample, to put back with `restore_sample_state`.
+    pub fn sample_state(&self, sample_uuid: &Uuid) -> (Vec<SampleMark>, Vec<CustomRegion>) {
+        (self.get_marks_for_sample(sample_uuid), self.get_regions_for_sample(sample_uuid))
+    }
+
+    /// Replace one sample's marks and regions, ids included.
+    pub fn restore_sample_state(&self, sample_uuid: &Uuid, marks: Vec<SampleMark>, regions: Vec<CustomRegion>) {
+        {
+            let mut all = self.marks.write();
+            all.retain(|m| &m.sample_uuid != sample_uuid);
+            all.extend(marks);
+        }
+        let mut all = self.regions.write();
+        all.retain(|r| &r.sample_uuid != sample_uuid);
+        all.extend(regions);
+    }
+
     pub fn clear_marks_for_uuid(&self, sample_uuid: &uuid::Uuid) {
         self.marks.write().retain(|m| &m.sample_uuid != sample_uuid);
         // Also remove any regions that referenced marks under this UUID
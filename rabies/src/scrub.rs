@@ -0,0 +1,62 @@
+// src/scrub.rs
+// Scrubbing. While the playhead is dragged with nothing playing — along the seek slider
+// or across the waveform — short grains of the sample under the pointer play, one every
+// few tens of milliseconds, so a spot in a long recording can be found by ear. Each
+// grain fades in and out so the stream of them doesn't click. The sample's PCM is shared
+// with the grains once per drag rather than copied for every grain.
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+use crate::adsr::{ADSREnvelope, Bus, Voice};
+use crate::audio::AudioAsset;
+use crate::gui::AppState;
+
+/// Length of one grain.
+const GRAIN_SECS: f32 = 0.07;
+/// Grains start at most this often, however fast the pointer moves.
+const GRAIN_EVERY: Duration = Duration::from_millis(50);
+
+#[derive(Default)]
+pub struct Scrub {
+    last_grain: Option<Instant>,
+    /// PCM of the sample being scrubbed, shared by its grains.
+    pcm:        Option<(Uuid, Arc<Vec<f32>>)>,
+}
+
+impl AppState {
+    /// Play a grain of `asset` at `pos` (0-1), unless one started moments ago or the
+    /// sample is playing anyway.
+    pub fn scrub_grain(&self, asset: &AudioAsset, pos: f32) {
+        if self.is_playing.load(Ordering::Relaxed) { return; }
+        let pcm = {
+            let mut scrub = self.scrub.lock();
+            if scrub.last_grain.is_some_and(|t| t.elapsed() < GRAIN_EVERY) { return; }
+            scrub.last_grain = Some(Instant::now());
+            match &scrub.pcm {
+                Some((uuid, pcm)) if *uuid == asset.sample_uuid && pcm.len() == asset.pcm.len() => pcm.clone(),
+                _ => {
+                    let pcm = Arc::new(asset.pcm.clone());
+                    scrub.pcm = Some((asset.sample_uuid, pcm.clone()));
+                    pcm
+                }
+            }
+        };
+        let channels = (asset.channels as usize).max(1);
+        let frames   = pcm.len() / channels;
+        if frames == 0 { return; }
+        let start = ((pos.clamp(0.0, 1.0) as f64 * frames as f64) as usize).min(frames - 1);
+        let len   = (GRAIN_SECS * asset.sample_rate as f32) as usize;
+        let fade  = ADSREnvelope::new(0.005, GRAIN_SECS, 0.0, 0.005);
+        let mut v = Voice::new(pcm, channels, start, 1.0, fade, true, asset.sample_rate);
+        v.end_frame = Some((start + len).min(frames));
+        v.bus       = Bus::Thru;
+        self.play_voices(vec![v]);
+    }
+
+    /// The drag is over; let go of the shared PCM.
+    pub fn end_scrub(&self) {
+        *self.scrub.lock() = Scrub::default();
+    }
+}
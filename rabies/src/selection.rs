@@ -0,0 +1,62 @@
+// src/selection.rs
+// Waveform selection: dragging across a track's waveform (away from the markers)
+// selects a span of it. The span can be played on its own or turned into a region;
+// a region needs a marker at each end, so a marker is dropped there unless one is
+// already close enough to use.
+
+use std::sync::atomic::Ordering;
+use uuid::Uuid;
+use crate::gui::AppState;
+
+/// A marker this close (fraction of the sample) to a selection edge is used as that end.
+const SNAP: f32 = 0.002;
+
+#[derive(Clone, Copy, Debug)]
+pub struct WaveSelection {
+    pub sample_uuid: Uuid,
+    /// Where the drag started; `from`/`to` are the ordered span.
+    pub anchor:      f32,
+    pub from:        f32,
+    pub to:          f32,
+}
+
+impl AppState {
+    /// Selection on track `track_idx`'s sample, if it has one.
+    pub fn track_selection(&self, track_idx: usize) -> Option<WaveSelection> {
+        let uuid = self.drum_tracks.read().get(track_idx)?.sample_uuid;
+        self.waveform_selection.read().filter(|s| s.sample_uuid == uuid && s.to > s.from)
+    }
+
+    /// Play the selected span of track `track_idx` and stop at its end.
+    pub fn play_selection(&self, track_idx: usize) {
+        let Some(sel) = self.track_selection(track_idx) else { return; };
+        let Some(asset) = self.drum_tracks.read().get(track_idx).map(|t| t.asset.clone()) else { return; };
+        self.playback_position.store(sel.from, Ordering::Relaxed);
+        self.playback_sample_index.store((sel.from as f64 * asset.pcm.len() as f64) as u64, Ordering::Relaxed);
+        self.start_playback(asset);
+        self.playback_stop_target.store(sel.to, Ordering::Relaxed);
+        self.playback_loop_from.store(sel.from, Ordering::Relaxed);
+    }
+
+    /// Region over the selected span of track `track_idx`.
+    pub fn region_from_selection(&self, track_idx: usize) {
+        let Some(sel) = self.track_selection(track_idx) else { return; };
+        let Some(name) = self.drum_tracks.read().get(track_idx).map(|t| t.asset.file_name.clone()) else { return; };
+        let uuid = sel.sample_uuid;
+        self.push_undo(t!("region"));
+        let end_mark = |pos: f32| {
+            let marks = self.samples_manager.get_marks_for_sample(&uuid);
+            marks.iter()
+                .filter(|m| (m.position - pos).abs() < SNAP)
+                .min_by(|a, b| (a.position - pos).abs().total_cmp(&(b.position - pos).abs()))
+                .map(|m| m.id)
+                .unwrap_or_else(|| self.samples_manager.mark_current_position(uuid, &name, pos))
+        };
+        let (from, to) = (end_mark(sel.from), end_mark(sel.to));
+        let id = self.samples_manager.create_region(from, to, uuid);
+        if let Some(region) = self.samples_manager.get_region_by_id(id) {
+            *self.status.write() = t!("✓ Created region: {}", region.name);
+        }
+        *self.waveform_selection.write() = None;
+    }
+}
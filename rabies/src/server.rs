@@ -0,0 +1,300 @@
+// src/server.rs
+// `--serve`: the engine without a window, driven over a small HTTP/JSON API.
+//
+//   GET  /status           project, BPM, grid and pattern list
+//   POST /project/load     {"path"}
+//   POST /bpm              {"bpm"}
+//   POST /render           {"pattern"?, "bars"?, "sample_rate"?, "channels"?, "path"?}
+//                          → the WAV itself, or {"path", "frames"} when `path` is given
+//   POST /chops/export     {"dir", "track"?, "pattern"?} → {"files"}
+//   POST /command          any `AppCommand`, e.g. {"command": "switch_pattern", "index": 1}
+//   GET  /events           every `EngineEvent` since the last call
+//
+// Each connection is read and answered on its own thread, with timeouts and capped
+// request lines and headers, so a slow client holds up only itself; the requests then
+// run one at a time on the main thread, and a render blocks the next one until it's done.
+// These are plain threads rather than jobs: they're short I/O waits with no Tasks panel
+// to report to. A request is only served when its Host is loopback (or it carries the
+// `RABIES_SERVE_TOKEN` bearer token, when that's set) and any Origin is loopback too, so
+// a web page can't drive it. Files are only written inside the open project's folder.
+// There's no window to clock the sequencer, so transport commands change state only.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::Duration;
+use serde_json::{json, Value};
+use crate::bus::{AppCommand, EngineEvent};
+use crate::gui::AppState;
+use crate::render::{wav_bytes, write_wav};
+
+pub const DEFAULT_ADDR: &str = "127.0.0.1:8787";
+const MAX_BODY: usize = 1 << 20;
+/// Longest request line or header line, and most header lines.
+const MAX_LINE: usize = 8 << 10;
+const MAX_HEADERS: usize = 64;
+/// Connections served at once; more are turned away.
+const MAX_CLIENTS: usize = 16;
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+const WRITE_TIMEOUT: Duration = Duration::from_secs(30);
+
+struct Request {
+    method: String,
+    path:   String,
+    body:   Value,
+}
+
+enum Reply {
+    Json(u16, Value),
+    Wav(Vec<u8>),
+}
+
+fn error(code: u16, msg: impl Into<String>) -> Reply {
+    Reply::Json(code, json!({ "error": msg.into() }))
+}
+
+/// Serve on `addr` until the process ends. `verbose` logs each request.
+pub fn run(addr: &str, verbose: bool) -> Result<(), String> {
+    let listener = TcpListener::bind(addr).map_err(|e| format!("Listen on {}: {}", addr, e))?;
+    let app = AppState::default();
+    let events = app.events.subscribe();
+    let token  = std::env::var("RABIES_SERVE_TOKEN").ok().filter(|t| !t.is_empty());
+    eprintln!("[serve] listening on http://{}", addr);
+
+    let (tx, rx) = mpsc::channel::<(Request, Sender<Reply>)>();
+    std::thread::spawn(move || {
+        let clients = Arc::new(AtomicUsize::new(0));
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else { continue; };
+            if clients.fetch_add(1, Ordering::Relaxed) >= MAX_CLIENTS {
+                clients.fetch_sub(1, Ordering::Relaxed);
+                continue;
+            }
+            let (tx, token, clients) = (tx.clone(), token.clone(), clients.clone());
+            std::thread::spawn(move || {
+                serve_connection(stream, &tx, token.as_deref(), verbose);
+                clients.fetch_sub(1, Ordering::Relaxed);
+            });
+        }
+    });
+    for (req, reply) in rx {
+        let _ = reply.send(handle(&app, &events, req));
+    }
+    Ok(())
+}
+
+/// Read one request, have the main thread answer it, and write the reply.
+fn serve_connection(mut stream: TcpStream, tx: &Sender<(Request, Sender<Reply>)>, token: Option<&str>, verbose: bool) {
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+    let _ = stream.set_write_timeout(Some(WRITE_TIMEOUT));
+    let reply = match read_request(&mut stream, token) {
+        Ok(req) => {
+            if verbose { eprintln!("[serve] {} {}", req.method, req.path); }
+            let (reply_tx, reply_rx) = mpsc::channel();
+            match tx.send((req, reply_tx)).ok().and_then(|()| reply_rx.recv().ok()) {
+                Some(reply) => reply,
+                None        => return,
+            }
+        }
+        Err(reply) => reply,
+    };
+    if let Err(e) = write_reply(&mut stream, reply) {
+        eprintln!("[serve] reply failed: {}", e);
+    }
+}
+
+/// One line, up to `MAX_LINE` bytes.
+fn read_line(reader: &mut impl BufRead) -> Result<String, Reply> {
+    let mut line = String::new();
+    reader.take(MAX_LINE as u64 + 1).read_line(&mut line).map_err(|e| error(400, e.to_string()))?;
+    if line.len() > MAX_LINE { return Err(error(400, format!("Line over {} bytes", MAX_LINE))); }
+    Ok(line)
+}
+
+/// `host[:port]` names this machine.
+fn is_loopback(host: &str) -> bool {
+    let host = host.trim();
+    let name = if let Some(rest) = host.strip_prefix('[') {
+        rest.split(']').next().unwrap_or("")
+    } else {
+        host.rsplit_once(':').map_or(host, |(h, _)| h)
+    };
+    name.eq_ignore_ascii_case("localhost") || name.parse::<std::net::IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+fn read_request(stream: &mut TcpStream, token: Option<&str>) -> Result<Request, Reply> {
+    let mut reader = BufReader::new(stream);
+    let line = read_line(&mut reader)?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().ok_or_else(|| error(400, "Empty request"))?.to_string();
+    let path   = parts.next().ok_or_else(|| error(400, "No request path"))?.to_string();
+
+    let mut len = 0;
+    let (mut host, mut origin, mut auth) = (None, None, None);
+    for n in 0.. {
+        if n == MAX_HEADERS { return Err(error(400, format!("Over {} headers", MAX_HEADERS))); }
+        let header = read_line(&mut reader)?;
+        let header = header.trim_end();
+        if header.is_empty() { break; }
+        let Some((name, value)) = header.split_once(':') else { continue; };
+        let value = value.trim().to_string();
+        match name.to_ascii_lowercase().as_str() {
+            "content-length" => len = value.parse().map_err(|_| error(400, "Bad Content-Length"))?,
+            "host"           => host = Some(value),
+            "origin"         => origin = Some(value),
+            "authorization"  => auth = Some(value),
+            _ => {}
+        }
+    }
+
+    // A page in a browser sends its own Origin; a rebound name shows up in Host
+    let origin_ok = origin.as_deref().is_none_or(|o| {
+        o.split_once("://").is_some_and(|(scheme, host)| matches!(scheme, "http" | "https") && is_loopback(host))
+    });
+    let allowed = match token {
+        Some(t) => auth.as_deref().and_then(|a| a.strip_prefix("Bearer ")) == Some(t),
+        None    => host.as_deref().is_some_and(is_loopback),
+    };
+    if !origin_ok || !allowed { return Err(error(403, "Forbidden")); }
+
+    if len > MAX_BODY { return Err(error(400, format!("Body over {} bytes", MAX_BODY))); }
+    let mut body = vec![0u8; len];
+    reader.read_exact(&mut body).map_err(|e| error(400, e.to_string()))?;
+    let body = if body.is_empty() { json!({}) }
+        else { serde_json::from_slice(&body).map_err(|e| error(400, format!("Bad JSON: {}", e)))? };
+    Ok(Request { method, path, body })
+}
+
+/// Where to write `path`: inside the open project's folder, relative to it unless absolute.
+fn output_path(app: &AppState, path: &str) -> Result<PathBuf, String> {
+    let root = app.project_path.read().as_ref().and_then(|p| p.parent().map(Path::to_path_buf))
+        .ok_or("Load a project first; files are written inside its folder")?;
+    let path = Path::new(path);
+    if path.components().any(|c| c == Component::ParentDir) { return Err("\"..\" isn't allowed in output paths".to_string()); }
+    let full = root.join(path);
+    if !full.starts_with(&root) { return Err(format!("{} is outside the project folder {}", full.display(), root.display())); }
+    Ok(full)
+}
+
+fn write_reply(stream: &mut TcpStream, reply: Reply) -> std::io::Result<()> {
+    let (code, kind, body) = match reply {
+        Reply::Json(code, v) => (code, "application/json", v.to_string().into_bytes()),
+        Reply::Wav(bytes)    => (200, "audio/wav", bytes),
+    };
+    let reason = match code { 200 => "OK", 403 => "Forbidden", 404 => "Not Found", 405 => "Method Not Allowed", _ => "Bad Request" };
+    write!(stream, "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        code, reason, kind, body.len())?;
+    stream.write_all(&body)
+}
+
+fn handle(app: &AppState, events: &Receiver<EngineEvent>, req: Request) -> Reply {
+    let b = &req.body;
+    match (req.method.as_str(), req.path.as_str()) {
+        ("GET", "/status") => Reply::Json(200, status(app)),
+
+        ("POST", "/project/load") => {
+            let Some(path) = b["path"].as_str() else { return error(400, "Missing \"path\""); };
+            if let Err(e) = app.dispatch(AppCommand::OpenProject { path: path.into() }) { return error(400, e); }
+            let msg = app.status.read().clone();
+            Reply::Json(200, json!({ "message": msg.trim_start_matches('✓').trim(), "status": status(app) }))
+        }
+
+        ("POST", "/bpm") => {
+            let Some(bpm) = b["bpm"].as_f64() else { return error(400, "Missing \"bpm\""); };
+            if let Err(e) = app.dispatch(AppCommand::SetBpm { bpm: bpm as f32 }) { return error(400, e); }
+            Reply::Json(200, json!({ "bpm": app.events.bpm() }))
+        }
+
+        ("POST", "/command") => {
+            let cmd: AppCommand = match serde_json::from_value(b.clone()) {
+                Ok(c)  => c,
+                Err(e) => return error(400, format!("Bad command: {}", e)),
+            };
+            match app.dispatch(cmd) {
+                Ok(())  => Reply::Json(200, json!({ "status": status(app) })),
+                Err(e)  => error(400, e),
+            }
+        }
+
+        ("GET", "/events") => Reply::Json(200, json!({ "events": events.try_iter().collect::<Vec<_>>() })),
+
+        ("POST", "/render") => {
+            let pattern  = b["pattern"].as_u64().map_or(app.song_editor.active_edit_idx(), |v| v as usize);
+            let bars     = b["bars"].as_u64().unwrap_or(1).clamp(1, 64) as usize;
+            let rate     = b["sample_rate"].as_u64().unwrap_or(48000).clamp(8000, 192_000) as u32;
+            let channels = b["channels"].as_u64().unwrap_or(2).clamp(1, 8) as usize;
+            let pcm = match app.render_pattern(pattern, bars, rate, channels) {
+                Ok(p)  => p,
+                Err(e) => return error(404, e),
+            };
+            match b["path"].as_str() {
+                Some(path) => match output_path(app, path).and_then(|p| write_wav(&p, &pcm, rate, channels as u16).map(|()| p)) {
+                    Ok(p)   => Reply::Json(200, json!({ "path": p, "frames": pcm.len() / channels })),
+                    Err(e)  => error(400, e),
+                },
+                None => Reply::Wav(wav_bytes(&pcm, rate, channels as u16)),
+            }
+        }
+
+        ("POST", "/chops/export") => {
+            let Some(dir) = b["dir"].as_str() else { return error(400, "Missing \"dir\""); };
+            let dir = match output_path(app, dir) {
+                Ok(d)  => d,
+                Err(e) => return error(400, e),
+            };
+            let track  = b["track"].as_u64().unwrap_or(0) as usize;
+            let active = app.song_editor.active_edit_idx();
+            let pattern = b["pattern"].as_u64().map_or(active, |v| v as usize);
+            if pattern >= app.song_editor.pattern_count() { return error(404, format!("No pattern {}", pattern)); }
+            if pattern != active {
+                app.save_current_pattern_state();
+                app.load_pattern_state(pattern);
+            }
+            let result = export_chops(app, track, &dir);
+            if pattern != active { app.load_pattern_state(active); }
+            match result {
+                Ok(files) => Reply::Json(200, json!({ "files": files })),
+                Err(e)    => error(400, e),
+            }
+        }
+
+        (_, "/status" | "/project/load" | "/bpm" | "/render" | "/chops/export" | "/command" | "/events") => error(405, "Method not allowed"),
+        _ => error(404, format!("No route {}", req.path)),
+    }
+}
+
+fn status(app: &AppState) -> Value {
+    let patterns: Vec<Value> = app.song_editor.get_all_patterns().iter().enumerate()
+        .map(|(i, p)| json!({ "index": i, "name": p.name, "tracks": p.tracks.len() }))
+        .collect();
+    json!({
+        "project":    app.project_name(),
+        "bpm":        app.events.bpm(),
+        "playing":    app.events.playing(),
+        "resolution": app.seq_resolution.read().label(),
+        "active":     app.song_editor.active_edit_idx(),
+        "patterns":   patterns,
+    })
+}
+
+/// Write each chop of `track` as `<sample>_chopNN.wav` into `dir`.
+fn export_chops(app: &AppState, track: usize, dir: &Path) -> Result<Vec<String>, String> {
+    let slices = app.chop_slices(track)?;
+    let (name, rate, channels) = {
+        let tracks = app.drum_tracks.read();
+        let asset  = &tracks[track].asset;
+        let stem   = Path::new(&asset.file_name).file_stem()
+            .map(|s| s.to_string_lossy().to_string()).unwrap_or_else(|| "sample".to_string());
+        (stem, asset.sample_rate, asset.channels)
+    };
+    let mut files = Vec::with_capacity(slices.len());
+    for (i, pcm) in slices.iter().enumerate() {
+        let path = dir.join(format!("{}_chop{:02}.wav", name, i + 1));
+        write_wav(&path, pcm, rate, channels)?;
+        files.push(path.to_string_lossy().to_string());
+    }
+    Ok(files)
+}
@@ -0,0 +1,169 @@
+// src/share.rs
+// Share previews: two loops of the edited pattern bounced straight into a share folder
+// as MP3 or OGG, ready to send. There's no encoder in the app itself, so the WAV is
+// piped through ffmpeg, then lame or oggenc; whichever is missing or fails, the next
+// is tried, and when none works the preview is kept as a WAV. The bounce and the
+// encode both run as a job. Optionally a fresh preview is written on every save.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use serde::{Deserialize, Serialize};
+use crate::gui::AppState;
+use crate::jobs::JobKind;
+use crate::render::wav_bytes;
+
+/// Times the pattern plays in a preview.
+const SHARE_LOOPS: usize = 2;
+/// Folder next to the project that previews go to unless another is chosen.
+pub const SHARE_DIR: &str = "share";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShareFormat {
+    #[default]
+    Mp3,
+    Ogg,
+    Wav,
+}
+
+impl ShareFormat {
+    pub const ALL: [ShareFormat; 3] = [ShareFormat::Mp3, ShareFormat::Ogg, ShareFormat::Wav];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ShareFormat::Mp3 => "MP3",
+            ShareFormat::Ogg => "OGG",
+            ShareFormat::Wav => "WAV",
+        }
+    }
+
+    fn ext(self) -> &'static str {
+        match self {
+            ShareFormat::Mp3 => "mp3",
+            ShareFormat::Ogg => "ogg",
+            ShareFormat::Wav => "wav",
+        }
+    }
+
+    /// Encoders to try, in order: program and arguments, with `{out}` for the file.
+    fn encoders(self) -> &'static [(&'static str, &'static [&'static str])] {
+        match self {
+            ShareFormat::Mp3 => &[
+                ("ffmpeg", &["-y", "-loglevel", "error", "-f", "wav", "-i", "-", "-codec:a", "libmp3lame", "-q:a", "2", "{out}"]),
+                ("lame",   &["--quiet", "-V", "2", "-", "{out}"]),
+            ],
+            ShareFormat::Ogg => &[
+                ("ffmpeg", &["-y", "-loglevel", "error", "-f", "wav", "-i", "-", "-codec:a", "libvorbis", "-q:a", "5", "{out}"]),
+                ("oggenc", &["--quiet", "-q", "5", "-o", "{out}", "-"]),
+            ],
+            ShareFormat::Wav => &[],
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShareSettings {
+    /// Where previews go; the project's `share/` folder when unset.
+    pub folder:  Option<PathBuf>,
+    pub format:  ShareFormat,
+    /// Write a preview every time the project is saved.
+    pub on_save: bool,
+}
+
+/// Run one encoder over `wav`, writing `out`. `Err(None)` when it isn't installed.
+fn run_encoder(program: &str, args: &[&str], wav: &[u8], out: &Path) -> Result<(), Option<String>> {
+    let args: Vec<String> = args.iter()
+        .map(|a| if *a == "{out}" { out.to_string_lossy().to_string() } else { a.to_string() })
+        .collect();
+    let mut child = Command::new(program).args(&args)
+        .stdin(Stdio::piped()).stdout(Stdio::null()).stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| (e.kind() != std::io::ErrorKind::NotFound).then(|| format!("{}: {}", program, e)))?;
+    // An encoder that dies early closes its input; its exit status says why
+    let fed = child.stdin.take().map_or(Ok(()), |mut stdin| stdin.write_all(wav));
+    let done = child.wait_with_output().map_err(|e| Some(format!("{}: {}", program, e)))?;
+    if !done.status.success() {
+        let err = String::from_utf8_lossy(&done.stderr);
+        return Err(Some(t!("{} failed: {}", program, err.lines().next().unwrap_or("no output"))));
+    }
+    fed.map_err(|e| Some(format!("{}: {}", program, e)))
+}
+
+/// Pipe `wav` through the encoders for `format` in turn until one works. `Err` has
+/// why each installed one failed: empty when none is installed, or for WAV.
+fn encode(wav: &[u8], format: ShareFormat, out: &Path) -> Result<&'static str, Vec<String>> {
+    let mut failed = Vec::new();
+    for &(program, args) in format.encoders() {
+        match run_encoder(program, args, wav, out) {
+            Ok(())       => return Ok(program),
+            Err(None)    => {}
+            Err(Some(e)) => {
+                eprintln!("[share] {}", e);
+                // Don't leave a half-written file behind for the next encoder
+                let _ = std::fs::remove_file(out);
+                failed.push(e);
+            }
+        }
+    }
+    Err(failed)
+}
+
+/// `stem (n).ext` with the first `n` not already in `dir`.
+fn free_name(dir: &Path, stem: &str, ext: &str) -> PathBuf {
+    (1..).map(|n| dir.join(format!("{} ({}).{}", stem, n, ext)))
+        .find(|p| !p.exists())
+        .unwrap_or_else(|| dir.join(format!("{}.{}", stem, ext)))
+}
+
+impl AppState {
+    /// Folder previews go to: the chosen one, else `share/` next to the saved project.
+    pub fn share_dir(&self) -> Option<PathBuf> {
+        self.share.read().folder.clone().or_else(|| {
+            self.project_path.read().as_ref().and_then(|p| p.parent().map(|d| d.join(SHARE_DIR)))
+        })
+    }
+
+    /// Bounce two loops of the edited pattern into the share folder, encoding in the background.
+    pub fn share_preview(&self) {
+        let dir = match self.share_dir() {
+            Some(d) => d,
+            None => {
+                let Some(d) = rfd::FileDialog::new().set_title(t!("Folder for share previews")).pick_folder() else { return; };
+                self.share.write().folder = Some(d.clone());
+                d
+            }
+        };
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            *self.status.write() = t!("✗ Create {}: {}", dir.display(), e);
+            return;
+        }
+        let idx     = self.song_editor.active_edit_idx();
+        let pattern = self.song_editor.get_pattern_by_idx(idx).map_or_else(|| "pattern".to_string(), |p| p.name);
+        let stem    = match self.project_name() {
+            Some(project) => format!("{} - {}", project, pattern),
+            None          => pattern,
+        };
+        let stem: String = stem.chars().map(|c| if c.is_alphanumeric() || " -_".contains(c) { c } else { '_' }).collect();
+
+        let rate   = self.bounce_rate();
+        let steps  = SHARE_LOOPS * self.pattern_len();
+        let scene  = self.render_scene();
+        let format = self.share.read().format;
+        *self.status.write() = t!("Sharing {}…", stem);
+        self.spawn_job(JobKind::Export, t!("Share {}", stem), move |_| {
+            let wav = wav_bytes(&scene.into_app().render_steps(steps, rate, 2), rate, 2);
+            let out = free_name(&dir, &stem, format.ext());
+            let note = match encode(&wav, format, &out) {
+                Ok(encoder) => return Ok(t!("✓ Shared {} (via {})", out.display(), encoder)),
+                Err(_) if format == ShareFormat::Wav => String::new(),
+                Err(failed) if failed.is_empty() => t!(" — no {} encoder found (install ffmpeg), kept as WAV", format.label()),
+                Err(failed) => t!(" — {} encoding failed ({}), kept as WAV", format.label(), failed.join("; ")),
+            };
+            let path = if format == ShareFormat::Wav { out } else { free_name(&dir, &stem, "wav") };
+            std::fs::write(&path, &wav)
+                .map(|()| t!("✓ Shared {}{}", path.display(), note))
+                .map_err(|e| t!("Write {}: {}", path.display(), e))
+        });
+    }
+}
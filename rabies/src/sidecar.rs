@@ -0,0 +1,136 @@
+// src/sidecar.rs
+// Chop sidecars. A sample's markers and regions are also kept next to the file, in
+// `<file>.chops.json` (song.wav → song.wav.chops.json), rewritten within a second of
+// being edited. A track loaded fresh from that file — ＋ Add Track, several at once, the
+// browser's ＋ — gets them back, so chopping done on a sample carries over to the next
+// project that uses it. Copy-on-import looks beside the project's copy first, then
+// beside the file picked. Opening a project or a pattern never reads sidecars: the
+// chops saved there win. Trimmed copies are new files and start without one.
+//
+// Only files inside the saved project's folder get a sidecar written — the copies
+// copy-on-import makes. A file used from anywhere else is the user's own and nothing is
+// written beside it; a sidecar already there is still read. Loading a track or
+// switching patterns (each pattern has its own chops) never writes one; only an edit does.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::gui::AppState;
+use crate::samples::SamplesManager;
+
+/// How often tracks are looked at for changed chops.
+const CHECK_EVERY: Duration = Duration::from_secs(1);
+
+/// Where `sample`'s chops are kept.
+pub fn sidecar_path(sample: &Path) -> PathBuf {
+    let mut name = sample.as_os_str().to_owned();
+    name.push(".chops.json");
+    PathBuf::from(name)
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChopSidecar {
+    /// Marker positions (0-1 of the sample), in chop order.
+    pub marks:   Vec<f32>,
+    pub regions: Vec<SidecarRegion>,
+}
+
+/// A region between two of the sidecar's markers, by index.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SidecarRegion {
+    pub from: usize,
+    pub to:   usize,
+    pub name: String,
+}
+
+impl ChopSidecar {
+    /// The chops sample `uuid` has now.
+    fn of(samples: &SamplesManager, uuid: &Uuid) -> Self {
+        let (marks, regions) = samples.sample_state(uuid);
+        let idx = |id: usize| marks.iter().position(|m| m.id == id);
+        Self {
+            regions: regions.into_iter()
+                .filter_map(|r| Some(SidecarRegion { from: idx(r.from)?, to: idx(r.to)?, name: r.name }))
+                .collect(),
+            marks: marks.iter().map(|m| m.position).collect(),
+        }
+    }
+
+    fn read(sample: &Path) -> Option<Self> {
+        let text = std::fs::read_to_string(sidecar_path(sample)).ok()?;
+        serde_json::from_str(&text).ok()
+    }
+
+    /// Write beside `sample`; no markers left removes the sidecar.
+    fn write(&self, sample: &Path) -> std::io::Result<()> {
+        let path = sidecar_path(sample);
+        if self.marks.is_empty() {
+            return match std::fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            };
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Give the fresh, unchopped sample `uuid` the chops beside the first of `sources` that
+/// has a sidecar. Returns how many markers came back.
+pub fn restore_chops(samples: &SamplesManager, uuid: Uuid, file_name: &str, sources: &[&Path]) -> usize {
+    if !samples.get_marks_for_sample(&uuid).is_empty() { return 0; }
+    let Some(side) = sources.iter().find_map(|p| ChopSidecar::read(p)) else { return 0; };
+    let ids: Vec<usize> = side.marks.iter()
+        .map(|&p| samples.mark_current_position(uuid, file_name, p.clamp(0.0, 1.0)))
+        .collect();
+    for r in &side.regions {
+        let (Some(&from), Some(&to)) = (ids.get(r.from), ids.get(r.to)) else { continue; };
+        let id = samples.create_region(from, to, uuid);
+        samples.rename_region(id, r.name.clone());
+    }
+    ids.len()
+}
+
+/// " — 4 chops from its sidecar", or nothing when none came back.
+pub fn restored_note(n: usize) -> String {
+    if n == 0 { String::new() } else { t!(" — {} chops from its sidecar", n) }
+}
+
+/// The chops last seen on each track, to tell when they change.
+#[derive(Default)]
+pub struct SidecarWatch {
+    seen:       HashMap<Uuid, ChopSidecar>,
+    next_check: Option<Instant>,
+}
+
+impl AppState {
+    /// Called every frame: write the sidecar of each project-copy track whose chops
+    /// changed since it was last looked at. A track seen for the first time — just loaded,
+    /// or rebuilt by a pattern switch, which gives every track a new uuid — only has its
+    /// chops noted.
+    pub(crate) fn tick_sidecars(&self) {
+        let mut watch = self.sidecars.lock();
+        let now = Instant::now();
+        if watch.next_check.is_some_and(|t| now < t) { return; }
+        watch.next_check = Some(now + CHECK_EVERY);
+
+        let project_dir = self.project_path.read().as_ref().and_then(|p| p.parent().map(Path::to_path_buf));
+        let tracks: Vec<(Uuid, String)> = match project_dir {
+            Some(dir) => self.drum_tracks.read().iter()
+                .filter_map(|t| Some((t.sample_uuid, t.file_path.clone()?)))
+                .filter(|(_, path)| Path::new(path).starts_with(&dir))
+                .collect(),
+            None => Vec::new(),
+        };
+        watch.seen.retain(|uuid, _| tracks.iter().any(|(u, _)| u == uuid));
+        for (uuid, path) in tracks {
+            let chops = ChopSidecar::of(&self.samples_manager, &uuid);
+            if watch.seen.get(&uuid).is_some_and(|seen| *seen != chops) {
+                if let Err(e) = chops.write(Path::new(&path)) { eprintln!("[sidecar] {}: {}", path, e); }
+            }
+            watch.seen.insert(uuid, chops);
+        }
+    }
+}
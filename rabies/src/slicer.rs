@@ -0,0 +1,66 @@
+// src/slicer.rs
+// Equal-slice chopping: N evenly spaced markers across the sample the pads show, or
+// across one of its regions. Replace clears the markers in that range first (a region
+// keeps its two end markers); Append leaves them and skips spots that already have one.
+
+use uuid::Uuid;
+use crate::gui::AppState;
+
+/// Closer than this (as a fraction of the sample) counts as the same spot.
+const SAME_SPOT: f32 = 0.0005;
+
+impl AppState {
+    /// Drop `n` evenly spaced markers on sample `uuid`, across region `region` if given.
+    pub fn slice_evenly(&self, uuid: Uuid, n: usize, region: Option<usize>, replace: bool) {
+        let n = n.max(1);
+        let track = self.drum_tracks.read().iter().position(|t| t.sample_uuid == uuid);
+        let name = match track {
+            Some(t) => self.drum_tracks.read().get(t).map(|t| t.asset.file_name.clone()),
+            None    => self.current_asset.read().as_ref().filter(|a| a.sample_uuid == uuid).map(|a| a.file_name.clone()),
+        };
+        let Some(name) = name else { return; };
+        self.push_undo(t!("slice"));
+
+        // A whole sample gets a marker at the start of each slice; a region already has
+        // its start and end, so only the n - 1 cuts in between are added
+        let (from, to, ends) = match region.and_then(|id| self.samples_manager.get_region_by_id(id)) {
+            Some(r) => {
+                let pos = |id| self.samples_manager.get_mark_by_id(id).map(|m| m.position);
+                let (Some(a), Some(b)) = (pos(r.from), pos(r.to)) else { return; };
+                (a.min(b), a.max(b), Some((r.from, r.to)))
+            }
+            None => (0.0, 1.0, None),
+        };
+
+        if replace {
+            let doomed: Vec<usize> = self.samples_manager.get_marks_for_sample(&uuid).iter().enumerate()
+                .filter(|(_, m)| match ends {
+                    Some((a, b)) => m.id != a && m.id != b && m.position > from && m.position < to,
+                    None         => true,
+                })
+                .map(|(i, _)| i)
+                .collect();
+            for &c in doomed.iter().rev() {
+                match track {
+                    Some(t) => self.remove_chop(t, c),
+                    None => {
+                        let id = self.samples_manager.get_marks_for_sample(&uuid)[c].id;
+                        if let Some(gi) = self.samples_manager.get_marks().iter().position(|m| m.id == id) {
+                            self.samples_manager.delete_mark(gi);
+                        }
+                    }
+                }
+            }
+        }
+
+        let first = if ends.is_some() { 1 } else { 0 };
+        let mut added = 0;
+        for i in first..n {
+            let pos = from + (to - from) * i as f32 / n as f32;
+            if self.samples_manager.find_mark_near(&uuid, pos, SAME_SPOT).is_some() { continue; }
+            self.samples_manager.mark_current_position(uuid, &name, pos);
+            added += 1;
+        }
+        *self.status.write() = t!("✓ Sliced {} into {}: {} marker{} added", name, n, added, if added == 1 { "" } else { "s" });
+    }
+}
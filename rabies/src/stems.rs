@@ -0,0 +1,57 @@
+// src/stems.rs
+// Stem export: the edited pattern bounced once per drum track, each track on its own
+// (the way its solo sounds), into one WAV per track in a folder. Every stem is rendered
+// on the same sample clock from frame 0 and padded to the longest tail, so dropped into
+// a DAW side by side they line up to the sample. Each track rolls its own seeded dice,
+// so its humanize, drift and alternates land where they do in the bounce, and the stems
+// sum back to it — short of recorded takes, which have no stem, and of the soft clip on
+// a bounce that runs hot. Nothing in a track's chain delays its signal — voices start
+// exactly on their step and there are no look-ahead effects — so there is no latency to
+// take out; the summary says as much, and a track's Start Drift stays in its stem
+// because it is part of the feel.
+// Muted tracks are left out, as they are from the mix.
+
+use std::path::Path;
+use crate::gui::AppState;
+use crate::render::write_wav;
+
+impl AppState {
+    /// Render `bars` bars of the edited pattern once per unmuted drum track, each alone,
+    /// all the same length. Returns (track index, name, interleaved PCM) per stem.
+    pub fn render_stems(&self, bars: usize, sample_rate: u32, channels: usize) -> Vec<(usize, String, Vec<f32>)> {
+        let steps = bars.max(1) * self.seq_resolution.read().steps();
+        let names: Vec<(usize, String)> = self.drum_tracks.read().iter().enumerate()
+            .filter(|(_, t)| !t.muted)
+            .map(|(i, t)| (i, t.asset.file_name.clone()))
+            .collect();
+        let mut stems: Vec<(usize, String, Vec<f32>)> = names.into_iter()
+            .map(|(i, name)| (i, name, self.render_steps_of(steps, sample_rate, channels, Some(i))))
+            .collect();
+
+        // Tails differ; pad every stem to the longest so they all end together
+        let len = stems.iter().map(|s| s.2.len()).max().unwrap_or(0);
+        for s in stems.iter_mut() { s.2.resize(len, 0.0); }
+        stems
+    }
+
+    /// Ask for a folder, then write `bars` bars of each track's stem into it.
+    pub fn export_stems(&self, bars: usize) {
+        let Some(dir) = rfd::FileDialog::new().set_title(t!("Folder for stems")).pick_folder() else { return; };
+        let rate   = self.bounce_rate();
+        let stems  = self.render_stems(bars, rate, 2);
+        if stems.is_empty() {
+            *self.status.write() = t!("✗ No unmuted tracks to export").to_string();
+            return;
+        }
+        let frames = stems[0].2.len() / 2;
+        let result = stems.iter().try_for_each(|(i, name, pcm)| {
+            let stem = Path::new(name).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            write_wav(&dir.join(format!("{:02} {}.wav", i + 1, stem)), pcm, rate, 2)
+        });
+        *self.status.write() = match result {
+            Ok(()) => t!("✓ {} stems of {} frames to {} — sample-aligned from frame 0, 0 frames of latency to compensate",
+                stems.len(), frames, dir.display()),
+            Err(e) => format!("✗ {}", e),
+        };
+    }
+}
@@ -0,0 +1,53 @@
+// src/step_nudge.rs
+// Per-step micro-timing: a hit on a drum track's own row or on one of its chop rows can
+// sound a little after its step, as a fraction of a step on that row's lane. Live
+// recording with micro-timing leaves these behind so a played take keeps its feel, and
+// being a fraction it keeps that feel at any tempo. Nudges are kept sparsely, like step
+// pitches, and follow their step through remaps, rotations and lane switches.
+
+use serde::{Deserialize, Serialize};
+
+/// Nudges smaller than this are on the step.
+const MIN_FRACTION: f32 = 0.02;
+
+/// Start `step` of a row `fraction` of a step late.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StepNudge {
+    /// Chop row, or `None` for the track's own row.
+    pub chop:     Option<usize>,
+    /// Step on the row's lane (a triplet step on a triplet row).
+    pub step:     usize,
+    /// 0 up to (not including) 1.
+    pub fraction: f32,
+}
+
+/// Nudge of `step` on row `chop`; 0 when it has none.
+pub fn fraction(nudges: &[StepNudge], chop: Option<usize>, step: usize) -> f32 {
+    nudges.iter().find(|n| n.chop == chop && n.step == step).map_or(0.0, |n| n.fraction)
+}
+
+/// Set `step` on row `chop` to sound `fraction` of a step late; a tiny one drops the nudge.
+pub fn set(nudges: &mut Vec<StepNudge>, chop: Option<usize>, step: usize, fraction: f32) {
+    nudges.retain(|n| n.chop != chop || n.step != step);
+    let fraction = fraction.clamp(0.0, 0.99);
+    if fraction >= MIN_FRACTION { nudges.push(StepNudge { chop, step, fraction }); }
+}
+
+/// Forget chop `removed`: its nudges go, and later chops shift down one.
+pub fn remove_chop(nudges: &mut Vec<StepNudge>, removed: usize) {
+    nudges.retain(|n| n.chop != Some(removed));
+    for n in nudges.iter_mut() {
+        if let Some(c) = n.chop.as_mut().filter(|c| **c > removed) { *c -= 1; }
+    }
+}
+
+/// Move the nudges of the rows picked by `row` to new steps; `None` drops one.
+pub fn move_steps(nudges: &mut Vec<StepNudge>, row: impl Fn(Option<usize>) -> bool, to: impl Fn(usize) -> Option<usize>) {
+    nudges.retain_mut(|n| {
+        if !row(n.chop) { return true; }
+        match to(n.step) {
+            Some(s) => { n.step = s; true }
+            None    => false,
+        }
+    });
+}
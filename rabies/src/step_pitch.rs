@@ -0,0 +1,62 @@
+// src/step_pitch.rs
+// Per-step pitch: a hit on a drum track's own row or on one of its chop rows can be
+// played a number of semitones up or down. The offset sets the voice's playback speed,
+// the way the piano roll does, so a melodic line can be programmed from one chop
+// straight on the step grid. Offsets are kept sparsely, like chop alternates, and
+// follow their step through remaps, rotations and lane switches.
+
+use serde::{Deserialize, Serialize};
+
+/// Semitones either way a step can be pitched.
+pub const RANGE: i8 = 24;
+
+/// Play `step` of a row `semitones` away from the sample's own pitch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StepPitch {
+    /// Chop row, or `None` for the track's own row.
+    pub chop:      Option<usize>,
+    /// Step on the row's lane (a triplet step on a triplet row).
+    pub step:      usize,
+    pub semitones: i8,
+}
+
+/// Offset of `step` on row `chop`; 0 when it has none.
+pub fn semitones(pitches: &[StepPitch], chop: Option<usize>, step: usize) -> i8 {
+    pitches.iter().find(|p| p.chop == chop && p.step == step).map_or(0, |p| p.semitones)
+}
+
+/// Playback speed for `step` on row `chop`.
+pub fn speed(pitches: &[StepPitch], chop: Option<usize>, step: usize) -> f32 {
+    2f32.powf(semitones(pitches, chop, step) as f32 / 12.0)
+}
+
+/// Offsets of the first `n` steps of row `chop`.
+pub fn row(pitches: &[StepPitch], chop: Option<usize>, n: usize) -> Vec<i8> {
+    (0..n).map(|s| semitones(pitches, chop, s)).collect()
+}
+
+/// Set `step` on row `chop` to `semitones`, clamped to `RANGE`; 0 drops the offset.
+pub fn set(pitches: &mut Vec<StepPitch>, chop: Option<usize>, step: usize, semitones: i8) {
+    pitches.retain(|p| p.chop != chop || p.step != step);
+    let semitones = semitones.clamp(-RANGE, RANGE);
+    if semitones != 0 { pitches.push(StepPitch { chop, step, semitones }); }
+}
+
+/// Forget chop `removed`: its offsets go, and later chops shift down one.
+pub fn remove_chop(pitches: &mut Vec<StepPitch>, removed: usize) {
+    pitches.retain(|p| p.chop != Some(removed));
+    for p in pitches.iter_mut() {
+        if let Some(c) = p.chop.as_mut().filter(|c| **c > removed) { *c -= 1; }
+    }
+}
+
+/// Move the offsets of the rows picked by `row` to new steps; `None` drops one.
+pub fn move_steps(pitches: &mut Vec<StepPitch>, row: impl Fn(Option<usize>) -> bool, to: impl Fn(usize) -> Option<usize>) {
+    pitches.retain_mut(|p| {
+        if !row(p.chop) { return true; }
+        match to(p.step) {
+            Some(s) => { p.step = s; true }
+            None    => false,
+        }
+    });
+}
@@ -0,0 +1,78 @@
+// src/sync_start.rs
+// Start the pattern in phase with the preview: while the main sample (or a track) plays
+// as a backing track, the sequencer is armed for the next bar line of that sample's grid —
+// the tempo span's bar grid when it's anchored there, otherwise bars from the top of the
+// file at the current BPM. The sequencer ticks from the GUI, so the first step is
+// taken a little early and its voices are delayed to land exactly on the bar line; the
+// steps after it keep that phase.
+
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+use crate::gui::AppState;
+
+/// How far ahead of the bar line the first step is scheduled.
+const LOOKAHEAD_SECS: f64 = 0.05;
+
+/// What an armed start means for this sequencer tick.
+pub enum ArmedStart {
+    /// Nothing armed.
+    Idle,
+    /// Armed, bar line not close yet.
+    Waiting,
+    /// Bar line is this many seconds away: the sequencer has started and the first
+    /// step's voices wait this long.
+    Due(f64),
+}
+
+impl AppState {
+    /// Arm the sequencer for the next bar line of the sample playing, or disarm it.
+    pub fn start_pattern_at_playhead(&self) {
+        if self.seq_start_at.write().take().is_some() {
+            self.seq_counting_in.store(false, Ordering::Relaxed);
+            *self.status.write() = t!("Synced start cancelled").into();
+            return;
+        }
+        let playing = self.playback_asset.read().clone().filter(|_| self.is_playing.load(Ordering::Relaxed));
+        let Some(asset) = playing else {
+            *self.status.write() = t!("✗ Play the main sample first — the pattern starts on its next bar").into();
+            return;
+        };
+
+        let dur    = asset.frames as f64 / asset.sample_rate.max(1) as f64;
+        let at     = self.playback_position.load(Ordering::Relaxed) as f64 * dur;
+        let anchor = self.bar_grid.read()
+            .filter(|g| g.sample_uuid == asset.sample_uuid)
+            .map_or(0.0, |g| g.anchor as f64 * dur);
+        let bpm    = self.events.bpm().max(1.0) as f64;
+        let bar    = self.metronome.lock().settings.meter.quarters() as f64 * 60.0 / bpm;
+        let next   = ((at - anchor) / bar).ceil();
+        let line   = anchor + next * bar;
+        if line > dur {
+            *self.status.write() = t!("✗ No bar line left before the end of the sample").into();
+            return;
+        }
+        // Positions are in source time; varispeed changes how soon the line comes round
+        let speed = self.playback_speed.load(Ordering::Relaxed).max(0.01) as f64;
+        let wait  = (line - at) / speed;
+
+        if self.events.playing() { self.stop_sequencer(); }
+        *self.seq_start_at.write() = Some(Instant::now() + Duration::from_secs_f64(wait));
+        *self.status.write() = t!("⏱ Pattern starts on bar {} of the sample, in {:.2} s", next as i64 + 1, wait);
+    }
+
+    /// Check the armed start once per sequencer tick, starting the sequencer when due.
+    pub(crate) fn tick_armed_start(&self) -> ArmedStart {
+        let Some(at) = *self.seq_start_at.read() else { return ArmedStart::Idle; };
+        let now = Instant::now();
+        if at.saturating_duration_since(now).as_secs_f64() > LOOKAHEAD_SECS { return ArmedStart::Waiting; }
+        *self.seq_start_at.write() = None;
+        let counted_in = self.seq_counting_in.swap(false, Ordering::Relaxed);
+        // Preview stopped while waiting: nothing left to line up with
+        if !counted_in && !self.is_playing.load(Ordering::Relaxed) {
+            *self.status.write() = t!("Synced start dropped — the preview stopped").into();
+            return ArmedStart::Idle;
+        }
+        self.start_sequencer();
+        ArmedStart::Due(at.saturating_duration_since(now).as_secs_f64())
+    }
+}
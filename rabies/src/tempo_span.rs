@@ -0,0 +1,87 @@
+// src/tempo_span.rs
+// Tempo from a marked span: pick two markers on a track, say how many bars lie between
+// them, and the BPM is worked out from the time in between (bars counted in the
+// metronome's time signature). The first marker becomes the track's bar grid anchor;
+// the grid is drawn over its waveform at the current BPM, so it follows later tempo
+// changes. More dependable than detection on sparse material.
+
+use uuid::Uuid;
+use crate::bus::EngineEvent;
+use crate::gui::AppState;
+
+/// The tempo tool's picks: two marker indices on the focused track and the bar count.
+pub struct SpanPick {
+    pub from: Option<usize>,
+    pub to:   Option<usize>,
+    pub bars: f32,
+}
+
+impl Default for SpanPick {
+    fn default() -> Self { Self { from: None, to: None, bars: 2.0 } }
+}
+
+/// Bar lines over one sample's waveform, starting from `anchor` (0-1).
+#[derive(Clone, Copy, Debug)]
+pub struct BarGrid {
+    pub sample_uuid: Uuid,
+    pub anchor:      f32,
+}
+
+impl AppState {
+    /// Seconds between markers `from` and `to` of track `track_idx`.
+    fn span_secs(&self, track_idx: usize, from: usize, to: usize) -> Option<(f32, f32)> {
+        let tracks = self.drum_tracks.read();
+        let t = tracks.get(track_idx)?;
+        let marks = self.samples_manager.get_marks_for_sample(&t.sample_uuid);
+        let (a, b) = (marks.get(from)?.position, marks.get(to)?.position);
+        let dur = t.asset.frames as f32 / t.asset.sample_rate.max(1) as f32;
+        Some(((b - a).abs() * dur, a.min(b)))
+    }
+
+    /// BPM at which the picked span is `bars` bars long.
+    pub fn span_bpm(&self, track_idx: usize) -> Option<f32> {
+        let (from, to, bars) = {
+            let p = self.span_pick.read();
+            (p.from?, p.to?, p.bars)
+        };
+        let (secs, _) = self.span_secs(track_idx, from, to)?;
+        let quarters = bars * self.metronome.lock().settings.meter.quarters();
+        (secs > 0.0).then(|| quarters * 60.0 / secs)
+    }
+
+    /// Set the BPM from the picked span and anchor the bar grid on its first marker.
+    pub fn set_tempo_from_span(&self, track_idx: usize) {
+        let Some(bpm) = self.span_bpm(track_idx) else {
+            *self.status.write() = t!("✗ Pick two different markers first").into();
+            return;
+        };
+        if !(40.0..=300.0).contains(&bpm) {
+            *self.status.write() = t!("✗ That span gives {:.1} BPM — outside 40-300, check the bar count", bpm);
+            return;
+        }
+        let (from, to) = { let p = self.span_pick.read(); (p.from.unwrap_or(0), p.to.unwrap_or(0)) };
+        let Some((_, anchor)) = self.span_secs(track_idx, from, to) else { return; };
+        let Some(sample_uuid) = self.drum_tracks.read().get(track_idx).map(|t| t.sample_uuid) else { return; };
+        self.events.emit(EngineEvent::Bpm { bpm });
+        *self.bar_grid.write() = Some(BarGrid { sample_uuid, anchor });
+        *self.status.write() = t!("✓ Tempo set to {:.2} BPM from markers {} → {}", bpm, from + 1, to + 1);
+    }
+
+    /// Bar and beat lines (0-1, with `true` on bar starts) for the sample `uuid`, whose
+    /// length is `dur` seconds.
+    pub fn bar_grid_lines(&self, uuid: &Uuid, dur: f32) -> Vec<(f32, bool)> {
+        let Some(grid) = *self.bar_grid.read() else { return Vec::new(); };
+        if grid.sample_uuid != *uuid || dur <= 0.0 { return Vec::new(); }
+        let meter = self.metronome.lock().settings.meter;
+        let bpm   = self.events.bpm().max(1.0);
+        // One line per click of the meter: quarters in x/4, eighths in x/8
+        let click = 60.0 / bpm * 4.0 / meter.unit.max(1) as f32 / dur;
+        let per_bar = meter.beats.max(1) as i64;
+        let first = -(grid.anchor / click).floor() as i64;
+        let last  = ((1.0 - grid.anchor) / click).floor() as i64;
+        (first..=last)
+            .map(|k| (grid.anchor + k as f32 * click, k.rem_euclid(per_bar) == 0))
+            .filter(|(p, _)| (0.0..=1.0).contains(p))
+            .collect()
+    }
+}
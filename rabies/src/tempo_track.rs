@@ -0,0 +1,69 @@
+// src/tempo_track.rs
+// Song tempo track: tempo changes placed on song bars. A change either jumps at its bar
+// or ramps there from the tempo before it over a number of bars. Until the first change
+// the song plays at the sequencer's BPM. The sequencer takes its step length from the
+// map while the song plays, and song bounces walk the same map, so a bounce keeps the
+// tempo changes heard live.
+
+use std::sync::atomic::Ordering;
+use serde::{Deserialize, Serialize};
+use crate::gui::AppState;
+
+/// Tempo range offered for a change.
+pub const TEMPO_RANGE: std::ops::RangeInclusive<f32> = 40.0..=300.0;
+
+/// From song bar `bar` on, play at `bpm`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TempoEvent {
+    pub bar:       usize,
+    pub bpm:       f32,
+    /// Glide from the tempo before over this many bars, reaching `bpm` at their end;
+    /// 0 jumps straight there.
+    #[serde(default)]
+    pub ramp_bars: usize,
+}
+
+/// Tempo at `pos` song bars (fractions are part of a bar), starting from `base`.
+/// `events` are sorted by bar.
+pub fn bpm_at(events: &[TempoEvent], base: f32, pos: f64) -> f32 {
+    let mut bpm = base;
+    for e in events.iter().take_while(|e| e.bar as f64 <= pos) {
+        let into = pos - e.bar as f64;
+        if into < e.ramp_bars as f64 {
+            return bpm + (e.bpm - bpm) * (into / e.ramp_bars as f64) as f32;
+        }
+        bpm = e.bpm;
+    }
+    bpm
+}
+
+impl AppState {
+    /// The tempo the sequencer runs at now: the tempo track's while the song plays,
+    /// otherwise the sequencer's own BPM.
+    pub fn song_bpm(&self) -> f32 {
+        let base = self.events.bpm();
+        if !self.song_editor.is_playing.load(Ordering::Relaxed) { return base; }
+        let events = self.tempo_events.read();
+        if events.is_empty() { return base; }
+        let bar  = self.song_editor.current_bar.load(Ordering::Relaxed);
+        let step = self.song_editor.current_step_in_bar.load(Ordering::Relaxed);
+        bpm_at(&events, base, bar as f64 + step as f64 / self.pattern_len().max(1) as f64)
+    }
+
+    /// The change placed on song bar `bar`, if there is one.
+    pub fn tempo_event(&self, bar: usize) -> Option<TempoEvent> {
+        self.tempo_events.read().iter().find(|e| e.bar == bar).copied()
+    }
+
+    /// Put a tempo change on `bar`, replacing any already there.
+    pub fn set_tempo_event(&self, event: TempoEvent) {
+        let mut events = self.tempo_events.write();
+        events.retain(|e| e.bar != event.bar);
+        events.push(TempoEvent { bpm: event.bpm.clamp(*TEMPO_RANGE.start(), *TEMPO_RANGE.end()), ..event });
+        events.sort_by_key(|e| e.bar);
+    }
+
+    pub fn remove_tempo_event(&self, bar: usize) {
+        self.tempo_events.write().retain(|e| e.bar != bar);
+    }
+}
@@ -0,0 +1,90 @@
+// src/transport_clock.rs
+// Master clock readout. The mixer counts every frame it renders; the sequencer notes
+// that count when it starts and counts the steps it plays. From those the header shows
+// where the run has got to — bar:beat:step since Start, how many times the pattern has
+// come round, and the time elapsed on the output's own clock rather than the UI's, so
+// it doesn't drift with frame drops. After Stop the readout holds where it stopped.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use parking_lot::Mutex;
+use crate::gui::AppState;
+
+/// Position of the current (or last) sequencer run.
+#[derive(Default)]
+pub struct TransportClock {
+    /// Output frames rendered so far, counted by the mixer.
+    pub frames: Arc<AtomicU64>,
+    /// Frame count at Start, and at Stop once stopped.
+    run:        Mutex<Option<(u64, Option<u64>)>>,
+    /// Steps played since Start.
+    steps:      AtomicU64,
+}
+
+/// What the readout shows.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClockReading {
+    /// 1-based bar, beat in the bar and step in the beat.
+    pub bar:   u64,
+    pub beat:  u64,
+    pub step:  u64,
+    /// Whole passes through the pattern.
+    pub loops: u64,
+    pub secs:  f64,
+}
+
+impl TransportClock {
+    pub fn start(&self) {
+        *self.run.lock() = Some((self.frames.load(Ordering::Relaxed), None));
+        self.steps.store(0, Ordering::Relaxed);
+    }
+
+    pub fn stop(&self) {
+        if let Some((_, end @ None)) = self.run.lock().as_mut() {
+            *end = Some(self.frames.load(Ordering::Relaxed));
+        }
+    }
+
+    pub fn step(&self) { self.steps.fetch_add(1, Ordering::Relaxed); }
+
+    /// Steps played since Start.
+    pub fn steps(&self) -> u64 { self.steps.load(Ordering::Relaxed) }
+
+    /// The run's position with `per_beat` steps to a beat and `beats` beats to the bar,
+    /// in `len`-step patterns; `None` before the first Start.
+    pub fn reading(&self, per_beat: usize, beats: usize, len: usize, sample_rate: u32) -> Option<ClockReading> {
+        let (from, to) = (*self.run.lock())?;
+        let frames = to.unwrap_or_else(|| self.frames.load(Ordering::Relaxed)).saturating_sub(from);
+        // The step now sounding; before the first one, the top of bar 1
+        let s = self.steps.load(Ordering::Relaxed).saturating_sub(1);
+        let (per_beat, per_bar) = (per_beat.max(1) as u64, (per_beat * beats).max(1) as u64);
+        Some(ClockReading {
+            bar:   s / per_bar + 1,
+            beat:  s % per_bar / per_beat + 1,
+            step:  s % per_beat + 1,
+            loops: self.steps.load(Ordering::Relaxed) / len.max(1) as u64,
+            secs:  frames as f64 / sample_rate.max(1) as f64,
+        })
+    }
+}
+
+impl ClockReading {
+    /// "003:2:1", "⟳ 4", "0:12.345".
+    pub fn parts(&self) -> (String, String, String) {
+        let mins = (self.secs / 60.0).floor();
+        (format!("{:03}:{}:{}", self.bar, self.beat, self.step),
+         format!("⟳ {}", self.loops),
+         format!("{}:{:06.3}", mins as u64, self.secs - mins * 60.0))
+    }
+}
+
+impl AppState {
+    /// Where the sequencer run is, for the header readout.
+    pub fn clock_reading(&self) -> Option<ClockReading> {
+        // Bars and beats in the metronome's time signature; a 6/8 beat is an eighth
+        let meter    = self.metronome.lock().settings.meter;
+        let per_beat = self.seq_resolution.read().per_beat() * 4 / meter.unit.max(1);
+        let rate     = self.engine.read().as_ref().map_or(48_000, |e| e.spec().sample_rate);
+        self.transport.reading(per_beat, meter.beats, self.pattern_len(), rate)
+    }
+}
@@ -0,0 +1,153 @@
+// src/trim.rs
+// Trim step for new drum tracks: the decoded file waits here while the user sets
+// start/end and normalisation, then becomes the track (trimmed copy written to disk).
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use crate::adsr::Voice;
+use crate::audio::{AudioAsset, WaveformAnalysis};
+use crate::gui::{AppState, DrumTrack};
+use crate::limiter::CEILING;
+
+/// Fade applied where the end handle cuts into the sound, so the tail doesn't click.
+const END_FADE_SECS: f32 = 0.005;
+
+/// A decoded file waiting in the trim dialog.
+pub struct TrimDraft {
+    pub path:      String,
+    pub asset:     Arc<AudioAsset>,
+    pub waveform:  WaveformAnalysis,
+    /// Normalised 0-1 handle positions.
+    pub start:     f32,
+    pub end:       f32,
+    pub normalize: bool,
+    /// Project folder the sample (or its trimmed copy) goes into, if any.
+    pub import_dir: Option<PathBuf>,
+    pub match_target: Option<f32>,
+}
+
+impl TrimDraft {
+    pub fn frame_range(&self) -> (usize, usize) {
+        let frames = self.asset.frames as usize;
+        let start  = (self.start.clamp(0.0, 1.0) as f64 * frames as f64) as usize;
+        let end    = (self.end.clamp(0.0, 1.0) as f64 * frames as f64) as usize;
+        (start.min(frames), end.clamp(start.min(frames), frames))
+    }
+
+    pub fn is_untouched(&self) -> bool {
+        let (s, e) = self.frame_range();
+        s == 0 && e == self.asset.frames as usize && !self.normalize
+    }
+
+    /// Gain that brings the trimmed region's peak to the limiter ceiling.
+    pub fn normalize_gain(&self) -> f32 {
+        if !self.normalize { return 1.0; }
+        let ch = (self.asset.channels as usize).max(1);
+        let (s, e) = self.frame_range();
+        let peak = self.asset.pcm[s * ch..e * ch].iter().fold(0.0f32, |m, x| m.max(x.abs()));
+        if peak > 1e-6 { CEILING / peak } else { 1.0 }
+    }
+
+    /// The trimmed, normalised PCM.
+    pub fn render(&self) -> Vec<f32> {
+        let ch   = (self.asset.channels as usize).max(1);
+        let (s, e) = self.frame_range();
+        let gain = self.normalize_gain();
+        let mut pcm: Vec<f32> = self.asset.pcm[s * ch..e * ch].iter().map(|x| x * gain).collect();
+        if e < self.asset.frames as usize {
+            let n      = pcm.len() / ch;
+            let fade   = ((END_FADE_SECS * self.asset.sample_rate as f32) as usize).min(n);
+            for (i, frame) in pcm.chunks_mut(ch).skip(n - fade).enumerate() {
+                let g = 1.0 - (i + 1) as f32 / fade as f32;
+                for x in frame.iter_mut() { *x *= g; }
+            }
+        }
+        pcm
+    }
+}
+
+/// `kick.wav` → `<dir>/kick (trim).wav`, numbered if that's taken.
+pub(crate) fn trimmed_path(dir: &Path, file_name: &str) -> PathBuf {
+    let stem = Path::new(file_name).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let mut path = dir.join(format!("{} (trim).wav", stem));
+    for n in 2.. {
+        if !path.exists() { break; }
+        path = dir.join(format!("{} (trim {}).wav", stem, n));
+    }
+    path
+}
+
+impl AppState {
+    /// Create the drum track from the dialog's draft. Untouched drafts use the file as is;
+    /// trimmed ones are written next to the project's samples (or kept in memory only when
+    /// the project has no folder yet).
+    pub fn commit_trim(&self) {
+        let Some(draft) = self.pending_trim.write().take() else { return; };
+        let (asset, file_path, waveform, mut note) = if draft.is_untouched() {
+            let path = crate::project::import_path(Path::new(&draft.path), draft.import_dir.as_deref());
+            self.pool_asset(&path, draft.asset.clone());
+            (draft.asset.clone(), Some(path), draft.waveform.clone(), None)
+        } else {
+            let pcm    = draft.render();
+            let frames = (pcm.len() / (draft.asset.channels as usize).max(1)) as u64;
+            let asset  = Arc::new(AudioAsset {
+                pcm,
+                sample_rate: draft.asset.sample_rate,
+                channels:    draft.asset.channels,
+                frames,
+                file_name:   draft.asset.file_name.clone(),
+                sample_uuid: uuid::Uuid::new_v4(),
+                tags:        draft.asset.tags.clone(),
+            });
+            let saved = draft.import_dir.as_ref().map(|d| {
+                let path = trimmed_path(&d.join(crate::project::SAMPLES_DIR), &asset.file_name);
+                crate::render::write_float_wav(&path, &asset.pcm, asset.sample_rate, asset.channels)
+                    .map(|()| path.to_string_lossy().to_string())
+            });
+            let (file_path, note) = match saved {
+                Some(Ok(p))  => (Some(p), None),
+                Some(Err(e)) => (None, Some(t!(" — not saved to disk: {}", e))),
+                None         => (None, Some(t!(" — trim kept in memory only (save the project first to store trimmed copies)").to_string())),
+            };
+            if let Some(p) = &file_path { self.pool_asset(p, asset.clone()); }
+            let waveform = self.audio_manager.analyze_waveform(&asset, 400);
+            (asset, file_path, waveform, note)
+        };
+
+        let mut track = DrumTrack::new(asset.clone(), Some(waveform));
+        track.file_path = file_path;
+        self.apply_mono_import(&mut track);
+        let matched = draft.match_target.and_then(|t| track.match_loudness(t));
+        self.push_undo(t!("track add"));
+        if draft.is_untouched() {
+            let sources: Vec<&Path> = track.file_path.iter().map(Path::new).chain([Path::new(&draft.path)]).collect();
+            let restored = crate::sidecar::restore_chops(&self.samples_manager, track.sample_uuid, &asset.file_name, &sources);
+            note = Some(crate::sidecar::restored_note(restored));
+        }
+        self.drum_tracks.write().push(track);
+        *self.status.write() = t!("✓ Track added: {}{}{}",
+            asset.file_name,
+            matched.map(|g| format!(" (matched {:+.1} dB)", 20.0 * g.log10())).unwrap_or_default(),
+            note.unwrap_or_default());
+    }
+
+    pub fn cancel_trim(&self) {
+        if let Some(d) = self.pending_trim.write().take() {
+            *self.status.write() = t!("Import of {} cancelled", d.asset.file_name);
+        }
+    }
+
+    /// Play the current trim through the engine.
+    pub fn preview_trim(&self) {
+        let voice = {
+            let guard = self.pending_trim.read();
+            let Some(d) = guard.as_ref() else { return; };
+            let (s, e) = d.frame_range();
+            let mut v = Voice::new(Arc::new(d.asset.pcm.clone()), d.asset.channels as usize, s, 1.0, Default::default(), false, d.asset.sample_rate);
+            v.end_frame = Some(e);
+            v.level     = d.normalize_gain();
+            v
+        };
+        self.play_voices(vec![voice]);
+    }
+}
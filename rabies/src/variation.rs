@@ -0,0 +1,83 @@
+// src/variation.rs
+// Controlled variation for sequenced chops: a step can swap its chop for an alternate
+// some of the time (e.g. 70% chop 3 / 30% chop 7). The dice are seeded, one set per
+// track, and offline renders reseed them, so a bounce comes out the same every time.
+
+use serde::{Deserialize, Serialize};
+
+/// On `step` of chop `chop`'s row, play chop `alt` instead `chance` of the time.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChopAlternate {
+    /// Step on the primary chop's lane (a triplet step on a triplet row).
+    pub step:   usize,
+    pub chop:   usize,
+    pub alt:    usize,
+    /// 0-1.
+    pub chance: f32,
+}
+
+/// Xorshift generator for the sequencer's rolls; cheap and repeatable from a seed.
+pub struct Dice(u64);
+
+impl Dice {
+    pub const SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+    pub fn new(seed: u64) -> Self { Self(seed.max(1)) }
+
+    /// Uniform in 0..1.
+    pub fn roll(&mut self) -> f32 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        (x >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+impl Default for Dice {
+    fn default() -> Self { Self::new(Self::SEED) }
+}
+
+/// A `Dice` per drum track, seeded from the track's index, so a track rolls the same
+/// whether it plays alone or with others.
+#[derive(Default)]
+pub struct TrackDice(Vec<Dice>);
+
+impl TrackDice {
+    pub fn track(&mut self, idx: usize) -> &mut Dice {
+        while self.0.len() <= idx {
+            let i = self.0.len() as u64;
+            self.0.push(Dice::new(Dice::SEED ^ i.wrapping_mul(0x2545_F491_4F6C_DD1D)));
+        }
+        &mut self.0[idx]
+    }
+}
+
+/// The chop that sounds for `chop` on `step`. Only steps with an alternate roll the dice.
+pub fn pick_chop(alternates: &[ChopAlternate], chop: usize, step: usize, dice: &mut Dice) -> usize {
+    match alternates.iter().find(|a| a.chop == chop && a.step == step) {
+        Some(a) if dice.roll() < a.chance => a.alt,
+        _ => chop,
+    }
+}
+
+/// Forget chop `removed`: its alternates go, and later chops shift down one.
+pub fn remove_chop(alternates: &mut Vec<ChopAlternate>, removed: usize) {
+    alternates.retain(|a| a.chop != removed && a.alt != removed);
+    for a in alternates.iter_mut() {
+        if a.chop > removed { a.chop -= 1; }
+        if a.alt  > removed { a.alt  -= 1; }
+    }
+}
+
+/// Move the alternates of the rows picked by `row` to new steps; `None` drops one.
+pub fn move_steps(alternates: &mut Vec<ChopAlternate>, row: impl Fn(usize) -> bool, to: impl Fn(usize) -> Option<usize>) {
+    alternates.retain_mut(|a| {
+        if !row(a.chop) { return true; }
+        match to(a.step) {
+            Some(s) => { a.step = s; true }
+            None    => false,
+        }
+    });
+}
@@ -0,0 +1,159 @@
+// src/versions.rs
+// "Save Version": timestamped copies of the project kept in `<name>.versions/` beside it,
+// listed by a small JSON index so the restore browser doesn't have to parse every file.
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+use crate::gui::AppState;
+use crate::project::{ProjectFile, PROJECT_EXT};
+
+const INDEX_FILE: &str = "index.json";
+
+/// One saved version, as listed in the index.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VersionInfo {
+    /// File name inside the versions folder.
+    pub file:     String,
+    /// Unix seconds.
+    pub saved_at: u64,
+    pub note:     String,
+    pub patterns: usize,
+}
+
+/// `song.rabies` → `song.versions/` next to it.
+pub fn versions_dir(project_path: &Path) -> PathBuf {
+    let stem = project_path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    project_path.with_file_name(format!("{}.versions", stem))
+}
+
+fn read_index(dir: &Path) -> Vec<VersionInfo> {
+    std::fs::read_to_string(dir.join(INDEX_FILE)).ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn write_index(dir: &Path, versions: &[VersionInfo]) -> Result<(), String> {
+    let text = serde_json::to_string_pretty(versions).map_err(|e| t!("Serialize: {}", e))?;
+    let path = dir.join(INDEX_FILE);
+    std::fs::write(&path, text).map_err(|e| t!("Write {}: {}", path.display(), e))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+/// "2026-10-16 14:03" (UTC).
+pub fn format_time(secs: u64) -> String {
+    // Days → civil date (Howard Hinnant's algorithm)
+    let z   = secs / 86_400 + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp  = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year  = yoe + era * 400 + u64::from(month <= 2);
+    let tod   = secs % 86_400;
+    format!("{:04}-{:02}-{:02} {:02}:{:02}", year, month, day, tod / 3600, tod % 3600 / 60)
+}
+
+/// "just now", "5 min ago", "3 h ago", "2 d ago".
+pub fn format_age(secs: u64) -> String {
+    let age = now_secs().saturating_sub(secs);
+    match age {
+        0..=59         => t!("just now").to_string(),
+        60..=3599      => t!("{} min ago", age / 60),
+        3600..=86_399  => t!("{} h ago", age / 3600),
+        _              => t!("{} d ago", age / 86_400),
+    }
+}
+
+impl AppState {
+    /// Re-read the version list of the current project.
+    pub fn refresh_versions(&self) {
+        let list = self.project_path.read().as_deref().map(|p| read_index(&versions_dir(p))).unwrap_or_default();
+        *self.versions.write() = list;
+    }
+
+    /// Snapshot the session into the versions folder. The project file itself is left as it is.
+    pub fn save_version(&self, note: &str) -> Result<VersionInfo, String> {
+        let Some(project_path) = self.project_path.read().clone() else {
+            return Err(t!("Save the project once before keeping versions").to_string());
+        };
+        let project_dir = project_path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        let dir = versions_dir(&project_path);
+        std::fs::create_dir_all(&dir).map_err(|e| t!("Create {}: {}", dir.display(), e))?;
+
+        // Sample paths stay relative to the project, not the versions folder
+        let mut project = self.project_snapshot();
+        project.localize_paths(&project_dir);
+
+        let saved_at = now_secs();
+        let mut file = format!("{}.{}", saved_at, PROJECT_EXT);
+        for n in 2.. {
+            if !dir.join(&file).exists() { break; }
+            file = format!("{}-{}.{}", saved_at, n, PROJECT_EXT);
+        }
+        project.write(&dir.join(&file))?;
+
+        let info = VersionInfo { file, saved_at, note: note.trim().to_string(), patterns: project.patterns.len() };
+        let mut index = read_index(&dir);
+        index.push(info.clone());
+        write_index(&dir, &index)?;
+        *self.versions.write() = index;
+        Ok(info)
+    }
+
+    pub fn save_version_from_ui(&self) {
+        let note = std::mem::take(&mut *self.version_note.write());
+        *self.status.write() = match self.save_version(&note) {
+            Ok(v)  => t!("✓ Saved version {}", format_time(v.saved_at)),
+            Err(e) => format!("✗ {}", e),
+        };
+    }
+
+    /// Bring back a saved version. The current state is kept as a version first, so a
+    /// restore can itself be undone.
+    pub fn restore_version(&self, version: &VersionInfo) {
+        let Some(project_path) = self.project_path.read().clone() else { return; };
+        let path = versions_dir(&project_path).join(&version.file);
+        let mut project = match ProjectFile::read(&path) {
+            Ok(p)  => p,
+            Err(e) => { *self.status.write() = format!("✗ {}", e); return; }
+        };
+        if let Err(e) = self.save_version(&t!("Before restoring {}", format_time(version.saved_at))) {
+            *self.status.write() = t!("✗ Not restored — couldn't keep the current state: {}", e);
+            return;
+        }
+        project.resolve_paths(project_path.parent().unwrap_or(Path::new(".")));
+        self.stop_playback();
+        self.stop_sequencer();
+
+        let files = project.patterns.iter()
+            .flat_map(|p| p.tracks.iter().map(|t| t.file_path.clone()))
+            .collect();
+        let missing = self.pool_samples(&files);
+        self.apply_project(project);
+
+        let label = if version.note.is_empty() { format_time(version.saved_at) } else { version.note.clone() };
+        *self.status.write() = if missing == 0 {
+            t!("✓ Restored version: {} (save to keep it)", label)
+        } else {
+            t!("✓ Restored version: {} — {} sample(s) missing", label, missing)
+        };
+    }
+
+    pub fn delete_version(&self, version: &VersionInfo) {
+        let Some(project_path) = self.project_path.read().clone() else { return; };
+        let dir = versions_dir(&project_path);
+        let _ = std::fs::remove_file(dir.join(&version.file));
+        let mut index = read_index(&dir);
+        index.retain(|v| v.file != version.file);
+        if let Err(e) = write_index(&dir, &index) {
+            *self.status.write() = format!("✗ {}", e);
+        }
+        *self.versions.write() = index;
+    }
+}